@@ -0,0 +1,357 @@
+// The API client
+// Thin over reqwest: one method per endpoint, taking and returning the
+// server's own request and response types. Only reads that change nothing
+// are retried on a 5xx: receipt and audit trail pages, readonly resolves and
+// the published keys. An attested resolve writes a receipt, so like every
+// POST it is sent once.
+use reqwest::{Method, RequestBuilder, Url};
+use serde::de::DeserializeOwned;
+use std::{sync::Arc, time::Duration};
+use uuid::Uuid;
+use veto_types::{
+    AuditTrailQuery, CreatePointerRequest, CreatePointerResponse, GetAuditTrailResponse,
+    GetReceiptsResponse, OrphanPointerRequest, OrphanPointerResponse, PublicKeysQuery,
+    PublicKeysResponse, ReceiptEntry, ReceiptInfo, ReceiptsQuery, ResolveMode,
+    ResolvePointerResponse, ResolveQuery,
+};
+
+use crate::{
+    error::ClientError,
+    verify::{KeyCache, SignedHash},
+};
+
+/// Header naming the person or system behind a request in the audit log
+const ACTOR_ID_HEADER: &str = "x-actor-id";
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Configuration of a [`VetoClient`]
+#[derive(Debug, Clone)]
+pub struct VetoClientBuilder {
+    base_url: String,
+    api_key: String,
+    timeout: Duration,
+    connect_timeout: Duration,
+    max_retries: u32,
+    retry_backoff: Duration,
+    verify_receipts: bool,
+    org_id: Option<Uuid>,
+    actor_id: Option<String>,
+}
+
+impl VetoClientBuilder {
+    /// Whole-request timeout, from connecting to reading the body; 10s by
+    /// default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 5s by default
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Retries of a read answered with a 5xx, 2 by default; 0 disables
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Wait before the first retry, doubled before each one after; 100ms by
+    /// default
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Check the signature of every receipt a response carries against the
+    /// keys the service publishes, failing with
+    /// [`ClientError::InvalidReceipt`] when one doesn't verify
+    pub fn verify_receipts(mut self, verify: bool) -> Self {
+        self.verify_receipts = verify;
+        self
+    }
+
+    /// Verify receipts against this org's keys, for services that sign each
+    /// org's receipts with its own key
+    pub fn org_id(mut self, org_id: Uuid) -> Self {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    /// Sent as X-Actor-Id, recorded as the actor of every request in the
+    /// audit log instead of the API key
+    pub fn actor_id(mut self, actor_id: impl Into<String>) -> Self {
+        self.actor_id = Some(actor_id.into());
+        self
+    }
+
+    pub fn build(self) -> Result<VetoClient> {
+        let mut base_url = Url::parse(&self.base_url)
+            .map_err(|e| ClientError::InvalidBaseUrl(format!("{}: {}", self.base_url, e)))?;
+        if base_url.cannot_be_a_base() || !matches!(base_url.scheme(), "http" | "https") {
+            return Err(ClientError::InvalidBaseUrl(format!(
+                "{}: must be an http or https URL",
+                self.base_url
+            )));
+        }
+        // Endpoint paths are appended to the base URL's own
+        base_url
+            .path_segments_mut()
+            .expect("http URLs have path segments")
+            .pop_if_empty();
+
+        let http = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout)
+            .user_agent(concat!("veto-client/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+
+        Ok(VetoClient {
+            http,
+            base_url,
+            api_key: self.api_key,
+            max_retries: self.max_retries,
+            retry_backoff: self.retry_backoff,
+            verify_receipts: self.verify_receipts,
+            org_id: self.org_id,
+            actor_id: self.actor_id,
+            keys: Arc::new(KeyCache::default()),
+        })
+    }
+}
+
+/// Async client for the Veto Frontier API. Cheap to clone; clones share
+/// connections and cached verification keys.
+#[derive(Clone)]
+pub struct VetoClient {
+    http: reqwest::Client,
+    base_url: Url,
+    api_key: String,
+    max_retries: u32,
+    retry_backoff: Duration,
+    verify_receipts: bool,
+    org_id: Option<Uuid>,
+    actor_id: Option<String>,
+    keys: Arc<KeyCache>,
+}
+
+impl std::fmt::Debug for VetoClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VetoClient")
+            .field("base_url", &self.base_url.as_str())
+            .field("verify_receipts", &self.verify_receipts)
+            .finish_non_exhaustive()
+    }
+}
+
+impl VetoClient {
+    /// A client with the default timeouts and retries
+    pub fn new(base_url: impl Into<String>, api_key: impl Into<String>) -> Result<Self> {
+        Self::builder(base_url, api_key).build()
+    }
+
+    /// `base_url` is where the service is reached, such as
+    /// `https://veto.example.com`; `api_key` is an organization's `vf_` key
+    pub fn builder(base_url: impl Into<String>, api_key: impl Into<String>) -> VetoClientBuilder {
+        VetoClientBuilder {
+            base_url: base_url.into(),
+            api_key: api_key.into(),
+            timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(5),
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(100),
+            verify_receipts: false,
+            org_id: None,
+            actor_id: None,
+        }
+    }
+
+    /// POST /api/pointer/create
+    pub async fn create_pointer(
+        &self,
+        request: &CreatePointerRequest,
+    ) -> Result<CreatePointerResponse> {
+        let created: CreatePointerResponse = self
+            .send(false, || {
+                self.request(Method::POST, &["api", "pointer", "create"])
+                    .json(request)
+            })
+            .await?;
+        self.check_receipt(&created.receipt).await?;
+        Ok(created)
+    }
+
+    /// GET /api/pointer/resolve/{id}. An orphaned pointer fails with
+    /// [`ClientError::PointerOrphaned`]. Retried only in readonly mode.
+    pub async fn resolve_pointer(
+        &self,
+        pointer_id: Uuid,
+        query: &ResolveQuery,
+    ) -> Result<ResolvePointerResponse> {
+        let id = pointer_id.to_string();
+        let resolved: ResolvePointerResponse = self
+            .send(query.mode == ResolveMode::Readonly, || {
+                self.request(Method::GET, &["api", "pointer", "resolve", &id])
+                    .query(query)
+            })
+            .await?;
+        if let Some(receipt) = &resolved.receipt {
+            self.check_receipt(receipt).await?;
+        }
+        Ok(resolved)
+    }
+
+    /// POST /api/pointer/orphan: the veto
+    pub async fn orphan_pointer(
+        &self,
+        request: &OrphanPointerRequest,
+    ) -> Result<OrphanPointerResponse> {
+        let orphaned: OrphanPointerResponse = self
+            .send(false, || {
+                self.request(Method::POST, &["api", "pointer", "orphan"])
+                    .json(request)
+            })
+            .await?;
+        self.check_receipt(&orphaned.receipt).await?;
+        Ok(orphaned)
+    }
+
+    /// GET /api/receipts/{id}: a page of the pointer's receipts, newest first
+    pub async fn get_receipts(
+        &self,
+        pointer_id: Uuid,
+        query: &ReceiptsQuery,
+    ) -> Result<GetReceiptsResponse> {
+        let id = pointer_id.to_string();
+        let page: GetReceiptsResponse = self
+            .send(true, || {
+                self.request(Method::GET, &["api", "receipts", &id])
+                    .query(query)
+            })
+            .await?;
+        for entry in &page.receipts {
+            // Tombstones are the service's own report of a receipt that
+            // failed its verification; there is nothing to check
+            if let ReceiptEntry::Receipt(receipt) = entry {
+                self.check_signed(SignedHash {
+                    receipt_hash: &receipt.receipt_hash,
+                    signature: &receipt.signature,
+                    signature_algorithm: veto_types::SIGNATURE_ALGORITHM,
+                    key_id: receipt.key_id.as_deref(),
+                })
+                .await?;
+            }
+        }
+        Ok(page)
+    }
+
+    /// GET /api/audit/{subject_id}: a page of the subject's audit trail.
+    /// The query's `format` is not sent; downloads are not parsed here.
+    pub async fn get_audit_trail(
+        &self,
+        subject_id: &str,
+        query: &AuditTrailQuery,
+    ) -> Result<GetAuditTrailResponse> {
+        let query = AuditTrailQuery {
+            format: None,
+            ..query.clone()
+        };
+        self.send(true, || {
+            self.request(Method::GET, &["api", "audit", subject_id])
+                .query(&query)
+        })
+        .await
+    }
+
+    /// GET /api/keys/public: the keys receipts are verified against, for
+    /// the org set with [`VetoClientBuilder::org_id`]
+    pub async fn public_keys(&self) -> Result<PublicKeysResponse> {
+        let query = PublicKeysQuery {
+            org_id: self.org_id,
+        };
+        self.send(true, || {
+            self.request(Method::GET, &["api", "keys", "public"])
+                .query(&query)
+        })
+        .await
+    }
+
+    /// Check a receipt's signature against the published keys, whether or
+    /// not the client verifies receipts on its own
+    pub async fn verify_receipt(&self, receipt: &ReceiptInfo) -> Result<()> {
+        self.verify_signed(SignedHash {
+            receipt_hash: &receipt.receipt_hash,
+            signature: &receipt.signature,
+            signature_algorithm: &receipt.signature_algorithm,
+            key_id: Some(&receipt.key_id),
+        })
+        .await
+    }
+
+    async fn check_receipt(&self, receipt: &ReceiptInfo) -> Result<()> {
+        if self.verify_receipts {
+            self.verify_receipt(receipt).await?;
+        }
+        Ok(())
+    }
+
+    async fn check_signed(&self, signed: SignedHash<'_>) -> Result<()> {
+        if self.verify_receipts {
+            self.verify_signed(signed).await?;
+        }
+        Ok(())
+    }
+
+    async fn verify_signed(&self, signed: SignedHash<'_>) -> Result<()> {
+        if !self.keys.knows(signed.key_id) {
+            self.keys.replace(&self.public_keys().await?);
+        }
+        self.keys
+            .check(signed)
+            .map_err(|failure| ClientError::InvalidReceipt {
+                receipt_hash: signed.receipt_hash.to_string(),
+                failure,
+            })
+    }
+
+    fn request(&self, method: Method, segments: &[&str]) -> RequestBuilder {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .expect("checked when built")
+            .extend(segments);
+
+        let request = self.http.request(method, url).bearer_auth(&self.api_key);
+        match &self.actor_id {
+            Some(actor_id) => request.header(ACTOR_ID_HEADER, actor_id),
+            None => request,
+        }
+    }
+
+    /// Send a request, again after a 5xx when `retry` allows, and parse the
+    /// success body or map the error body
+    async fn send<T: DeserializeOwned>(
+        &self,
+        retry: bool,
+        build: impl Fn() -> RequestBuilder,
+    ) -> Result<T> {
+        let mut attempt = 0;
+        loop {
+            let response = build().send().await?;
+            let status = response.status();
+            let body = response.bytes().await?;
+
+            if status.is_success() {
+                return Ok(serde_json::from_slice(&body)?);
+            }
+            if retry && status.is_server_error() && attempt < self.max_retries {
+                tokio::time::sleep(self.retry_backoff * 2u32.saturating_pow(attempt)).await;
+                attempt += 1;
+                continue;
+            }
+            return Err(ClientError::from_response(status, &body));
+        }
+    }
+}
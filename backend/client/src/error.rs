@@ -0,0 +1,208 @@
+// Errors returned by the client
+// Error responses are mapped by the body's stable `code`, not the status, so
+// a 403 for an orphaned pointer is told apart from one for another org's
+use reqwest::StatusCode;
+use thiserror::Error;
+use veto_types::{ErrorBody, ReceiptFailure};
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// 403 `pointer_orphaned`: the pointer was vetoed and no longer resolves
+    #[error("pointer orphaned: {}", .0.error)]
+    PointerOrphaned(Box<ErrorBody>),
+    /// 403 `processing_restricted`, until `restricted_until`
+    #[error("processing restricted: {}", .0.error)]
+    ProcessingRestricted(Box<ErrorBody>),
+    /// 403 `forbidden`: another org's pointer or subject
+    #[error("forbidden: {}", .0.error)]
+    Forbidden(Box<ErrorBody>),
+    /// 403 `reinstatement_disabled`
+    #[error("reinstatement disabled: {}", .0.error)]
+    ReinstatementDisabled(Box<ErrorBody>),
+    /// 404 `not_found`
+    #[error("not found: {}", .0.error)]
+    NotFound(Box<ErrorBody>),
+    /// 400 `bad_request`
+    #[error("bad request: {}", .0.error)]
+    BadRequest(Box<ErrorBody>),
+    /// 422 `validation_failed`, with every broken rule in `errors`
+    #[error("validation failed: {}", .0.error)]
+    ValidationFailed(Box<ErrorBody>),
+    /// 422 `idempotency_key_reused` with a different request
+    #[error("idempotency key reused: {}", .0.error)]
+    IdempotencyKeyReused(Box<ErrorBody>),
+    /// 401 `unauthorized`: missing, unknown or revoked API key
+    #[error("unauthorized: {}", .0.error)]
+    Unauthorized(Box<ErrorBody>),
+    /// 401 `api_key_rotated`: the key was replaced and its overlap ended
+    #[error("API key rotated: {}", .0.error)]
+    ApiKeyRotated(Box<ErrorBody>),
+    /// 409 `conflict`, told apart by `conflict_type`
+    #[error("conflict: {}", .0.error)]
+    Conflict(Box<ErrorBody>),
+    /// 409 `orphan_group_rejected`, with the blocking members in `failures`
+    #[error("orphan group rejected: {}", .0.error)]
+    OrphanGroupRejected(Box<ErrorBody>),
+    /// 423 `legal_hold`
+    #[error("legal hold: {}", .0.error)]
+    LegalHold(Box<ErrorBody>),
+    /// 423 `chain_frozen`
+    #[error("chain frozen: {}", .0.error)]
+    ChainFrozen(Box<ErrorBody>),
+    /// 429 `rate_limited`, with `retry_after_seconds`
+    #[error("rate limited: {}", .0.error)]
+    RateLimited(Box<ErrorBody>),
+    /// 503 `service_unavailable`
+    #[error("service unavailable: {}", .0.error)]
+    ServiceUnavailable(Box<ErrorBody>),
+    /// 500 `payload_decryption_failed`
+    #[error("payload decryption failed: {}", .0.error)]
+    PayloadDecryptionFailed(Box<ErrorBody>),
+    /// 500 `internal`
+    #[error("internal server error: {}", .0.error)]
+    Internal(Box<ErrorBody>),
+    /// An error body with a code this client does not know
+    #[error("{status}: {} ({})", body.error, body.code)]
+    Api {
+        status: StatusCode,
+        body: Box<ErrorBody>,
+    },
+    /// An error response without an error body, such as a 413 refused
+    /// before the request reached the service
+    #[error("{status}: {body}")]
+    Status { status: StatusCode, body: String },
+    /// A receipt in the response did not verify against the published keys
+    #[error("receipt {receipt_hash} failed verification: {failure:?}")]
+    InvalidReceipt {
+        receipt_hash: String,
+        failure: ReceiptFailure,
+    },
+    /// A success response whose body did not parse
+    #[error("unexpected response body: {0}")]
+    Decode(#[from] serde_json::Error),
+    /// Connecting, sending or reading failed, or the request timed out
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("invalid base URL: {0}")]
+    InvalidBaseUrl(String),
+}
+
+impl ClientError {
+    /// Map an error response by its body's `code`
+    pub fn from_response(status: StatusCode, body: &[u8]) -> Self {
+        let body: Box<ErrorBody> = match serde_json::from_slice(body) {
+            Ok(body) => Box::new(body),
+            Err(_) => {
+                return ClientError::Status {
+                    status,
+                    body: String::from_utf8_lossy(body).into_owned(),
+                }
+            }
+        };
+
+        match body.code.as_str() {
+            "pointer_orphaned" => ClientError::PointerOrphaned(body),
+            "processing_restricted" => ClientError::ProcessingRestricted(body),
+            "forbidden" => ClientError::Forbidden(body),
+            "reinstatement_disabled" => ClientError::ReinstatementDisabled(body),
+            "not_found" => ClientError::NotFound(body),
+            "bad_request" => ClientError::BadRequest(body),
+            "validation_failed" => ClientError::ValidationFailed(body),
+            "idempotency_key_reused" => ClientError::IdempotencyKeyReused(body),
+            "unauthorized" => ClientError::Unauthorized(body),
+            "api_key_rotated" => ClientError::ApiKeyRotated(body),
+            "conflict" => ClientError::Conflict(body),
+            "orphan_group_rejected" => ClientError::OrphanGroupRejected(body),
+            "legal_hold" => ClientError::LegalHold(body),
+            "chain_frozen" => ClientError::ChainFrozen(body),
+            "rate_limited" => ClientError::RateLimited(body),
+            "service_unavailable" => ClientError::ServiceUnavailable(body),
+            "payload_decryption_failed" => ClientError::PayloadDecryptionFailed(body),
+            "internal" => ClientError::Internal(body),
+            _ => ClientError::Api { status, body },
+        }
+    }
+
+    /// The error body the service sent, if any
+    pub fn body(&self) -> Option<&ErrorBody> {
+        match self {
+            ClientError::PointerOrphaned(body)
+            | ClientError::ProcessingRestricted(body)
+            | ClientError::Forbidden(body)
+            | ClientError::ReinstatementDisabled(body)
+            | ClientError::NotFound(body)
+            | ClientError::BadRequest(body)
+            | ClientError::ValidationFailed(body)
+            | ClientError::IdempotencyKeyReused(body)
+            | ClientError::Unauthorized(body)
+            | ClientError::ApiKeyRotated(body)
+            | ClientError::Conflict(body)
+            | ClientError::OrphanGroupRejected(body)
+            | ClientError::LegalHold(body)
+            | ClientError::ChainFrozen(body)
+            | ClientError::RateLimited(body)
+            | ClientError::ServiceUnavailable(body)
+            | ClientError::PayloadDecryptionFailed(body)
+            | ClientError::Internal(body)
+            | ClientError::Api { body, .. } => Some(body),
+            _ => None,
+        }
+    }
+
+    /// `X-Request-Id` of the failed request, to quote when reporting it
+    pub fn request_id(&self) -> Option<&str> {
+        self.body()?.request_id.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_map_by_code_not_status() {
+        let orphaned =
+            br#"{"error":"pointer_orphaned: gone","code":"pointer_orphaned","request_id":"r1"}"#;
+        let err = ClientError::from_response(StatusCode::FORBIDDEN, orphaned);
+        assert!(matches!(err, ClientError::PointerOrphaned(_)));
+        assert_eq!(err.request_id(), Some("r1"));
+
+        let forbidden = br#"{"error":"forbidden: not yours","code":"forbidden"}"#;
+        assert!(matches!(
+            ClientError::from_response(StatusCode::FORBIDDEN, forbidden),
+            ClientError::Forbidden(_)
+        ));
+
+        // Codes added after this client was built keep their body
+        let newer = br#"{"error":"teapot","code":"brewing"}"#;
+        match ClientError::from_response(StatusCode::IM_A_TEAPOT, newer) {
+            ClientError::Api { status, body } => {
+                assert_eq!(status, StatusCode::IM_A_TEAPOT);
+                assert_eq!(body.code, "brewing");
+            }
+            other => panic!("expected Api, got {:?}", other),
+        }
+
+        match ClientError::from_response(StatusCode::PAYLOAD_TOO_LARGE, b"length limit exceeded") {
+            ClientError::Status { status, body } => {
+                assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+                assert_eq!(body, "length limit exceeded");
+            }
+            other => panic!("expected Status, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_legal_hold_fields_keep_explicit_nulls() {
+        let held = br#"{"error":"legal_hold: held","code":"legal_hold","legal_hold_reason":null,"legal_hold_set_at":"2026-01-01T00:00:00+00:00"}"#;
+        let err = ClientError::from_response(StatusCode::LOCKED, held);
+        let body = err.body().unwrap();
+        assert!(matches!(err, ClientError::LegalHold(_)));
+        assert_eq!(body.legal_hold_reason, Some(None));
+        assert_eq!(
+            body.legal_hold_set_at,
+            Some(Some("2026-01-01T00:00:00+00:00".to_string()))
+        );
+        assert_eq!(body.conflict_type, None);
+    }
+}
@@ -0,0 +1,27 @@
+// Veto Frontier API client
+// An async client for the endpoints partner services call: creating,
+// resolving and orphaning pointers, and reading receipts and audit trails.
+// Requests and responses are the server's own types from veto-types, so the
+// two cannot drift apart. Error bodies map to ClientError by their `code`,
+// and receipts can be verified against the service's published keys.
+//
+//     let client = VetoClient::builder("https://veto.example.com", api_key)
+//         .verify_receipts(true)
+//         .build()?;
+//     match client.resolve_pointer(pointer_id, &ResolveQuery::default()).await {
+//         Ok(resolved) => { /* use resolved.data_id */ }
+//         Err(ClientError::PointerOrphaned(_)) => { /* vetoed: drop the data */ }
+//         Err(e) => return Err(e.into()),
+//     }
+mod client;
+mod error;
+mod verify;
+
+pub use client::{Result, VetoClient, VetoClientBuilder};
+pub use error::ClientError;
+pub use veto_types as types;
+pub use veto_types::{
+    AuditTrailQuery, CreatePointerRequest, CreatePointerResponse, ErrorBody, GetAuditTrailResponse,
+    GetReceiptsResponse, OrphanPointerRequest, OrphanPointerResponse, ReceiptEntry, ReceiptFailure,
+    ReceiptInfo, ReceiptsQuery, ResolveMode, ResolvePointerResponse, ResolveQuery,
+};
@@ -0,0 +1,184 @@
+// Client-side receipt verification
+// Every receipt is an Ed25519 signature over the ASCII bytes of its
+// receipt_hash, by a key the service publishes at /api/keys/public. Keys are
+// fetched once and again whenever a receipt names a key_id not yet seen, so
+// a signing key rotation needs no restart.
+use data_encoding::{BASE64, BASE64URL_NOPAD};
+use ed25519_dalek::{Signature, VerifyingKey};
+use std::{collections::HashMap, sync::RwLock};
+use veto_types::{PublicKeysResponse, ReceiptFailure, SIGNATURE_ALGORITHM};
+
+/// A receipt's signature, as it appears in any response that carries one
+#[derive(Debug, Clone, Copy)]
+pub struct SignedHash<'a> {
+    pub receipt_hash: &'a str,
+    /// Base64
+    pub signature: &'a str,
+    pub signature_algorithm: &'a str,
+    /// None on receipts from before key ids were recorded
+    pub key_id: Option<&'a str>,
+}
+
+/// Verification keys by key_id
+#[derive(Default)]
+pub struct KeyCache {
+    keys: RwLock<HashMap<String, VerifyingKey>>,
+}
+
+impl KeyCache {
+    /// Whether a receipt signed by `key_id` has a key to check it against
+    /// without fetching
+    pub fn knows(&self, key_id: Option<&str>) -> bool {
+        let keys = self.keys.read().unwrap();
+        match key_id {
+            Some(key_id) => keys.contains_key(key_id),
+            None => !keys.is_empty(),
+        }
+    }
+
+    /// Replace the cached keys with the Ed25519 keys of a JWKS document.
+    /// Keys of other types are skipped.
+    pub fn replace(&self, published: &PublicKeysResponse) {
+        let keys = published
+            .keys
+            .iter()
+            .filter(|jwk| jwk.kty == "OKP" && jwk.crv == "Ed25519")
+            .filter_map(|jwk| {
+                let bytes: [u8; 32] = BASE64URL_NOPAD
+                    .decode(jwk.x.as_bytes())
+                    .ok()?
+                    .try_into()
+                    .ok()?;
+                Some((jwk.kid.clone(), VerifyingKey::from_bytes(&bytes).ok()?))
+            })
+            .collect();
+        *self.keys.write().unwrap() = keys;
+    }
+
+    pub fn check(&self, signed: SignedHash<'_>) -> Result<(), ReceiptFailure> {
+        if signed.signature_algorithm != SIGNATURE_ALGORITHM {
+            return Err(ReceiptFailure::UnsupportedAlgorithm);
+        }
+        let signature: [u8; 64] = BASE64
+            .decode(signed.signature.as_bytes())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(ReceiptFailure::InvalidSignature)?;
+        let signature = Signature::from_bytes(&signature);
+
+        let keys = self.keys.read().unwrap();
+        let candidates: Vec<&VerifyingKey> = match signed.key_id {
+            Some(key_id) => keys.get(key_id).into_iter().collect(),
+            None => keys.values().collect(),
+        };
+        if candidates.is_empty() {
+            return Err(ReceiptFailure::UnknownKey);
+        }
+        if candidates.iter().any(|key| {
+            key.verify_strict(signed.receipt_hash.as_bytes(), &signature)
+                .is_ok()
+        }) {
+            Ok(())
+        } else {
+            Err(ReceiptFailure::InvalidSignature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use veto_types::Jwk;
+
+    fn published(keys: &[(&str, &SigningKey)]) -> PublicKeysResponse {
+        let jwks: Vec<Jwk> = keys
+            .iter()
+            .map(|(kid, key)| Jwk {
+                kty: "OKP".to_string(),
+                crv: "Ed25519".to_string(),
+                x: BASE64URL_NOPAD.encode(key.verifying_key().as_bytes()),
+                kid: kid.to_string(),
+                alg: "EdDSA".to_string(),
+                key_use: "sig".to_string(),
+            })
+            .collect();
+        let (key_id, key) = keys[0];
+        PublicKeysResponse {
+            keys: jwks,
+            key_id: key_id.to_string(),
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            public_key_base64: BASE64.encode(key.verifying_key().as_bytes()),
+            public_key_hex: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_receipts_verify_against_the_named_key() {
+        let current = SigningKey::from_bytes(&[1u8; 32]);
+        let retired = SigningKey::from_bytes(&[2u8; 32]);
+        let cache = KeyCache::default();
+        assert!(!cache.knows(None));
+        cache.replace(&published(&[("k2", &current), ("k1", &retired)]));
+        assert!(cache.knows(Some("k1")) && !cache.knows(Some("k3")));
+
+        let receipt_hash = "ab".repeat(64);
+        let signature = BASE64.encode(&retired.sign(receipt_hash.as_bytes()).to_bytes());
+        let signed = SignedHash {
+            receipt_hash: &receipt_hash,
+            signature: &signature,
+            signature_algorithm: SIGNATURE_ALGORITHM,
+            key_id: Some("k1"),
+        };
+        assert_eq!(cache.check(signed), Ok(()));
+        // Receipts without a key id are tried against every key
+        assert_eq!(
+            cache.check(SignedHash {
+                key_id: None,
+                ..signed
+            }),
+            Ok(())
+        );
+
+        let failures = [
+            (
+                SignedHash {
+                    key_id: Some("k2"),
+                    ..signed
+                },
+                ReceiptFailure::InvalidSignature,
+            ),
+            (
+                SignedHash {
+                    key_id: Some("k3"),
+                    ..signed
+                },
+                ReceiptFailure::UnknownKey,
+            ),
+            (
+                SignedHash {
+                    receipt_hash: &"cd".repeat(64),
+                    ..signed
+                },
+                ReceiptFailure::InvalidSignature,
+            ),
+            (
+                SignedHash {
+                    signature: "not base64!",
+                    ..signed
+                },
+                ReceiptFailure::InvalidSignature,
+            ),
+            (
+                SignedHash {
+                    signature_algorithm: "ML-DSA-65",
+                    ..signed
+                },
+                ReceiptFailure::UnsupportedAlgorithm,
+            ),
+        ];
+        for (signed, failure) in failures {
+            assert_eq!(cache.check(signed), Err(failure), "{:?}", signed);
+        }
+    }
+}
@@ -0,0 +1,816 @@
+-- FinalBoss Veto Frontier - Production Database Schema
+-- Patent-referenced pointer orphaning system
+-- US 19/240,581 Claim 9: "orphaning said pointer while preserving the underlying data object"
+-- US 63/920,993: Zero-Multiplier Veto System
+
+-- ============================================================================
+-- EXTENSIONS
+-- ============================================================================
+
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+CREATE EXTENSION IF NOT EXISTS "pgcrypto";
+
+-- ============================================================================
+-- ENUMS
+-- ============================================================================
+
+CREATE TYPE pointer_status AS ENUM ('active', 'orphaned', 'pending_orphan');
+CREATE TYPE receipt_operation AS ENUM (
+    'create', 'resolve', 'orphan', 'denied', 'merge', 'restrict', 'unrestrict', 'repair',
+    'data_swap', 'chain_freeze_review', 'orphan_scheduled', 'orphan_cancelled',
+    'legal_hold', 'legal_hold_release', 'purge'
+);
+
+-- ============================================================================
+-- ORGANIZATIONS TABLE
+-- ============================================================================
+
+CREATE TABLE organizations (
+    org_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    metadata JSONB DEFAULT '{}'::jsonb,
+
+    -- Seconds after orphaning during which resolves are answered with an
+    -- explicit revocation disclosure instead of a hard denial (0 = none)
+    orphan_grace_seconds INTEGER NOT NULL DEFAULT 0,
+
+    -- Length of each signed liveness heartbeat interval (NULL = disabled)
+    heartbeat_interval_seconds INTEGER,
+
+    CONSTRAINT org_name_not_empty CHECK (length(trim(name)) > 0),
+    CONSTRAINT org_orphan_grace_non_negative CHECK (orphan_grace_seconds >= 0),
+    CONSTRAINT org_heartbeat_interval_valid CHECK (heartbeat_interval_seconds >= 60)
+);
+
+CREATE INDEX idx_organizations_created_at ON organizations(created_at DESC);
+
+-- ============================================================================
+-- API KEYS TABLE
+-- ============================================================================
+-- Bearer keys, each authenticating as one organization. Only the SHA3-256
+-- hash of a key is stored; the key itself is shown once when minted.
+
+CREATE TABLE api_keys (
+    key_id UUID PRIMARY KEY,
+    key_hash VARCHAR(64) NOT NULL UNIQUE,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    label TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ,
+
+    -- Set by rotation: the key keeps authenticating until expires_at, then
+    -- the key sweeper revokes it
+    replaced_by UUID REFERENCES api_keys(key_id),
+    expires_at TIMESTAMPTZ,
+
+    CONSTRAINT api_key_label_not_empty CHECK (length(trim(label)) > 0)
+);
+
+CREATE INDEX idx_api_keys_org ON api_keys(org_id);
+CREATE INDEX idx_api_keys_expiring ON api_keys(expires_at)
+    WHERE revoked_at IS NULL AND expires_at IS NOT NULL;
+
+-- ============================================================================
+-- SUBJECTS TABLE
+-- ============================================================================
+-- Full value of subject_ids too long to index; pointers, data and receipts
+-- carry the 'sha3-256:<hex>' key instead
+
+CREATE TABLE subjects (
+    subject_key VARCHAR(255) PRIMARY KEY,
+    subject_value TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================================================================
+-- DATA_STORE TABLE
+-- ============================================================================
+-- Stores the actual data objects that pointers reference
+-- Data persists even when pointers are orphaned (per patent claim)
+
+CREATE TABLE data_store (
+    data_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    subject_id VARCHAR(255) NOT NULL,
+    content_hash VARCHAR(128) NOT NULL, -- SHA3-512 hash of content
+    encrypted_payload BYTEA, -- Actual encrypted data (optional)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    metadata JSONB DEFAULT '{}'::jsonb,
+
+    -- When a data swap last moved a pointer off this row; GC grace runs from here
+    released_at TIMESTAMPTZ,
+    -- Set by operators; garbage collection never touches held rows
+    legal_hold BOOLEAN NOT NULL DEFAULT false,
+    -- Data category from RETENTION_CLASSES, fixed at create
+    retention_class VARCHAR(64),
+    -- Hard deadline from the class maximum or a requested expiry. Past it,
+    -- the retention sweeper orphans pointers to the row and drops its payload
+    retain_until TIMESTAMPTZ,
+    -- When the payload of an orphaned pointer was crypto-shredded; the row
+    -- and content_hash stay so the receipt chain remains verifiable
+    payload_purged_at TIMESTAMPTZ,
+
+    CONSTRAINT subject_id_not_empty CHECK (length(trim(subject_id)) > 0),
+    CONSTRAINT content_hash_not_empty CHECK (length(trim(content_hash)) > 0)
+);
+
+CREATE INDEX idx_data_store_org_id ON data_store(org_id);
+CREATE INDEX idx_data_store_subject_id ON data_store(subject_id);
+CREATE INDEX idx_data_store_created_at ON data_store(created_at DESC);
+CREATE INDEX idx_data_store_content_hash ON data_store(content_hash);
+CREATE INDEX idx_data_store_retain_until ON data_store(retain_until)
+    WHERE retain_until IS NOT NULL;
+
+-- ============================================================================
+-- POINTERS TABLE
+-- ============================================================================
+-- Core patent implementation: pointers can be orphaned without deleting data
+-- When status='orphaned', pointer resolution is blocked by enforcement layer
+
+CREATE TABLE pointers (
+    pointer_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    data_id UUID NOT NULL REFERENCES data_store(data_id) ON DELETE CASCADE,
+    subject_id VARCHAR(255) NOT NULL,
+    status pointer_status NOT NULL DEFAULT 'active',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    orphaned_at TIMESTAMPTZ,
+    orphan_reason TEXT,
+    -- When a scheduled orphan takes effect; resolution keeps working until then
+    orphan_effective_at TIMESTAMPTZ,
+    metadata JSONB DEFAULT '{}'::jsonb,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- The integrator's own id for the record, unique within the org
+    external_ref VARCHAR(255),
+
+    -- Set while the receipt chain is frozen pending investigation; no
+    -- receipt can be appended, whatever the status
+    chain_frozen BOOLEAN NOT NULL DEFAULT FALSE,
+
+    -- Litigation hold: the pointer cannot be orphaned while set, though it
+    -- keeps resolving
+    legal_hold BOOLEAN NOT NULL DEFAULT FALSE,
+    legal_hold_reason TEXT,
+    legal_hold_set_at TIMESTAMPTZ,
+
+    -- Transaction that last wrote this row; orders the changes feed
+    change_xid XID8 NOT NULL DEFAULT pg_current_xact_id(),
+
+    CONSTRAINT subject_id_not_empty CHECK (length(trim(subject_id)) > 0),
+    CONSTRAINT orphaned_at_valid CHECK (
+        (status = 'orphaned' AND orphaned_at IS NOT NULL) OR
+        (status IN ('active', 'pending_orphan') AND orphaned_at IS NULL)
+    ),
+    CONSTRAINT orphan_effective_at_valid CHECK (
+        status != 'pending_orphan' OR orphan_effective_at IS NOT NULL
+    ),
+    CONSTRAINT legal_hold_valid CHECK (
+        (legal_hold AND legal_hold_set_at IS NOT NULL) OR
+        (NOT legal_hold AND legal_hold_reason IS NULL AND legal_hold_set_at IS NULL)
+    )
+);
+
+CREATE INDEX idx_pointers_org_id ON pointers(org_id);
+CREATE INDEX idx_pointers_data_id ON pointers(data_id);
+CREATE INDEX idx_pointers_subject_id ON pointers(subject_id);
+CREATE INDEX idx_pointers_status ON pointers(status);
+CREATE INDEX idx_pointers_created_at ON pointers(created_at DESC);
+CREATE INDEX idx_pointers_orphaned_at ON pointers(orphaned_at DESC) WHERE orphaned_at IS NOT NULL;
+
+-- Composite index for common query pattern: org + subject + status
+CREATE INDEX idx_pointers_org_subject_status ON pointers(org_id, subject_id, status);
+
+-- Pending orphans in the order they fall due
+CREATE INDEX idx_pointers_pending_orphan ON pointers(orphan_effective_at)
+    WHERE status = 'pending_orphan';
+
+-- Pointer listing page order
+CREATE INDEX idx_pointers_org_created ON pointers(org_id, created_at DESC, pointer_id DESC);
+
+-- Changes feed cursor order, within an org
+CREATE INDEX idx_pointers_change ON pointers(org_id, change_xid, pointer_id);
+
+-- Lookup by the integrator's reference; orphaned pointers keep theirs
+CREATE UNIQUE INDEX idx_pointers_org_external_ref ON pointers(org_id, external_ref)
+    WHERE external_ref IS NOT NULL;
+
+-- Org stats: time from create to orphan for pointers orphaned in a range
+CREATE INDEX idx_pointers_org_orphaned ON pointers(org_id, orphaned_at)
+    WHERE orphaned_at IS NOT NULL;
+
+-- ============================================================================
+-- IDEMPOTENCY_KEYS TABLE
+-- ============================================================================
+-- Idempotency-Key of each pointer create, per org, with the response it
+-- produced, so a retried create returns that response instead of creating a
+-- second pointer. Expired keys may be claimed again.
+
+CREATE TABLE idempotency_keys (
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    idempotency_key VARCHAR(255) NOT NULL,
+    content_hash VARCHAR(128) NOT NULL,
+    pointer_id UUID REFERENCES pointers(pointer_id) ON DELETE CASCADE,
+    response_json JSONB,
+    created_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+
+    PRIMARY KEY (org_id, idempotency_key)
+);
+
+-- ============================================================================
+-- SIGNING_KEYS TABLE
+-- ============================================================================
+-- Every key that has signed receipts, by key_id (SHA3-256 of the public key,
+-- hex). At most one is unretired; retired keys stay here so the receipts they
+-- signed still verify.
+
+CREATE TABLE signing_keys (
+    key_id VARCHAR(64) PRIMARY KEY,
+    public_key BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    retired_at TIMESTAMPTZ,
+
+    CONSTRAINT public_key_length CHECK (length(public_key) = 32)
+);
+
+CREATE UNIQUE INDEX idx_signing_keys_active ON signing_keys((retired_at IS NULL))
+    WHERE retired_at IS NULL;
+
+-- ============================================================================
+-- ORG_SIGNING_KEYS TABLE
+-- ============================================================================
+-- Each organization's own receipt signing key, generated on first use. The
+-- seed is sealed (AES-256-GCM) under SIGNING_MASTER_KEY, bound to the org and
+-- key_id. Kept with the org's receipts so they still verify.
+
+CREATE TABLE org_signing_keys (
+    org_id UUID PRIMARY KEY REFERENCES organizations(org_id),
+    key_id VARCHAR(64) NOT NULL UNIQUE,
+    public_key BYTEA NOT NULL,
+    sealed_private_key BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT org_public_key_length CHECK (length(public_key) = 32)
+);
+
+-- ============================================================================
+-- WEBHOOKS
+-- ============================================================================
+-- Endpoints notified of orphan events. Deliveries are written in the same
+-- transaction as the event and sent by a background worker with retries.
+
+CREATE TABLE webhooks (
+    webhook_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    -- HMAC-SHA256 key for X-Veto-Signature
+    secret TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    events TEXT[] NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT webhook_events_not_empty CHECK (cardinality(events) > 0)
+);
+
+CREATE INDEX idx_webhooks_org ON webhooks(org_id);
+
+CREATE TABLE webhook_deliveries (
+    delivery_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    webhook_id UUID NOT NULL REFERENCES webhooks(webhook_id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    pointer_id UUID,
+    event_type VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    -- pending until delivered, or failed once out of attempts
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    delivered_at TIMESTAMPTZ,
+
+    CONSTRAINT webhook_delivery_status_valid CHECK (status IN ('pending', 'delivered', 'failed'))
+);
+
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at)
+    WHERE status = 'pending';
+CREATE INDEX idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, created_at DESC);
+
+-- One row per HTTP attempt, so operators can see why a delivery failed
+CREATE TABLE webhook_delivery_attempts (
+    attempt_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    delivery_id UUID NOT NULL REFERENCES webhook_deliveries(delivery_id) ON DELETE CASCADE,
+    attempted_at TIMESTAMPTZ NOT NULL,
+    status_code INTEGER,
+    error TEXT,
+    duration_ms BIGINT NOT NULL
+);
+
+CREATE INDEX idx_webhook_delivery_attempts_delivery
+    ON webhook_delivery_attempts(delivery_id, attempted_at);
+
+-- ============================================================================
+-- GOVERNANCE_RECEIPTS TABLE
+-- ============================================================================
+-- Cryptographically signed audit trail of all pointer operations
+-- Implements chain hashing for tamper-evidence
+
+CREATE TABLE governance_receipts (
+    receipt_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    pointer_id UUID NOT NULL REFERENCES pointers(pointer_id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    operation receipt_operation NOT NULL,
+
+    -- Receipt content (canonical JSON serialized)
+    receipt_json JSONB NOT NULL,
+    receipt_hash VARCHAR(128) NOT NULL, -- SHA3-512 hash of canonical JSON
+
+    -- Cryptographic signature (ED25519 initially, ML-DSA-65 later)
+    signature BYTEA NOT NULL,
+    signature_algorithm VARCHAR(50) NOT NULL DEFAULT 'ED25519',
+    key_id VARCHAR(64), -- signing_keys or org_signing_keys key_id; NULL before key ids were recorded
+
+    -- Chain linking for audit trail
+    prev_hash VARCHAR(128), -- Links to previous receipt in chain
+
+    -- Insert time rather than transaction start: writers serialize on the
+    -- pointer's row lock, so timestamp order is chain order
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    metadata JSONB DEFAULT '{}'::jsonb,
+
+    CONSTRAINT receipt_hash_not_empty CHECK (length(trim(receipt_hash)) > 0),
+    CONSTRAINT signature_not_empty CHECK (length(signature) > 0),
+    CONSTRAINT signature_algorithm_valid CHECK (signature_algorithm IN ('ED25519', 'ML-DSA-65'))
+);
+
+CREATE INDEX idx_receipts_pointer_id ON governance_receipts(pointer_id);
+CREATE INDEX idx_receipts_org_id ON governance_receipts(org_id);
+CREATE INDEX idx_receipts_operation ON governance_receipts(operation);
+CREATE INDEX idx_receipts_timestamp ON governance_receipts(timestamp DESC);
+CREATE INDEX idx_receipts_receipt_hash ON governance_receipts(receipt_hash);
+CREATE INDEX idx_receipts_prev_hash ON governance_receipts(prev_hash);
+
+-- Composite index for audit trail queries
+CREATE INDEX idx_receipts_org_timestamp ON governance_receipts(org_id, timestamp DESC);
+CREATE INDEX idx_receipts_pointer_timestamp ON governance_receipts(pointer_id, timestamp DESC);
+
+-- ============================================================================
+-- RECEIPT_OVERFLOW TABLE
+-- ============================================================================
+-- Receipt fields too large to sign inline, keyed by the SHA3-512 hash the
+-- receipt carries in their place. Content-addressed, so never rewritten.
+
+CREATE TABLE receipt_overflow (
+    overflow_hash VARCHAR(128) PRIMARY KEY,
+    -- Canonical JSON exactly as hashed
+    content TEXT NOT NULL,
+    byte_size INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT overflow_size_matches CHECK (byte_size = octet_length(content))
+);
+
+-- ============================================================================
+-- RECEIPT_CHECKPOINTS TABLE
+-- ============================================================================
+-- Periodic Merkle root over every governance receipt written since the
+-- previous checkpoint, signed with the service key and chained to that
+-- checkpoint, so rewriting any stored receipt contradicts a signed root.
+-- Instances racing to write the same checkpoint collide on sequence.
+
+CREATE TABLE receipt_checkpoints (
+    checkpoint_id UUID PRIMARY KEY,
+    sequence BIGINT NOT NULL UNIQUE, -- 1 for the first, then consecutive
+    period_start TIMESTAMPTZ, -- Previous checkpoint's period_end; NULL on the first
+    period_end TIMESTAMPTZ NOT NULL,
+    receipt_count INTEGER NOT NULL,
+    merkle_root VARCHAR(128) NOT NULL,
+
+    receipt_json JSONB NOT NULL,
+    receipt_hash VARCHAR(128) NOT NULL,
+    signature BYTEA NOT NULL,
+    signature_algorithm VARCHAR(50) NOT NULL DEFAULT 'ED25519',
+    key_id VARCHAR(64) NOT NULL,
+    prev_hash VARCHAR(128), -- Previous checkpoint's receipt_hash
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT checkpoint_not_empty CHECK (receipt_count > 0)
+);
+
+CREATE INDEX idx_checkpoints_created ON receipt_checkpoints(created_at DESC, checkpoint_id DESC);
+
+-- The leaves of each checkpoint's tree, in tree order. Not a foreign key to
+-- governance_receipts: the proof must outlive a deleted receipt. Each
+-- receipt is covered by exactly one checkpoint.
+CREATE TABLE receipt_checkpoint_leaves (
+    checkpoint_id UUID NOT NULL REFERENCES receipt_checkpoints(checkpoint_id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    receipt_id UUID NOT NULL UNIQUE,
+    receipt_hash VARCHAR(128) NOT NULL,
+
+    PRIMARY KEY (checkpoint_id, position)
+);
+
+CREATE INDEX idx_checkpoint_leaves_receipt_hash ON receipt_checkpoint_leaves(receipt_hash);
+
+-- ============================================================================
+-- AUDIT_LOG TABLE
+-- ============================================================================
+-- Comprehensive audit log for compliance and debugging
+
+CREATE TABLE audit_log (
+    log_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    org_id UUID REFERENCES organizations(org_id) ON DELETE CASCADE,
+    pointer_id UUID REFERENCES pointers(pointer_id) ON DELETE SET NULL,
+    receipt_id UUID REFERENCES governance_receipts(receipt_id) ON DELETE SET NULL,
+
+    event_type VARCHAR(100) NOT NULL,
+    event_data JSONB NOT NULL,
+    actor_id VARCHAR(255), -- User/service that triggered the event
+    ip_address INET,
+    user_agent TEXT,
+
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT event_type_not_empty CHECK (length(trim(event_type)) > 0)
+);
+
+CREATE INDEX idx_audit_log_org_id ON audit_log(org_id);
+CREATE INDEX idx_audit_log_pointer_id ON audit_log(pointer_id);
+CREATE INDEX idx_audit_log_receipt_id ON audit_log(receipt_id);
+CREATE INDEX idx_audit_log_event_type ON audit_log(event_type);
+CREATE INDEX idx_audit_log_timestamp ON audit_log(timestamp DESC);
+CREATE INDEX idx_audit_log_actor_id ON audit_log(actor_id);
+
+-- Composite index for org audit queries
+CREATE INDEX idx_audit_log_org_timestamp ON audit_log(org_id, timestamp DESC);
+
+-- ============================================================================
+-- ORPHAN_GROUPS TABLE
+-- ============================================================================
+-- Signed manifests for all-or-nothing vetoes over a set of pointers
+
+CREATE TABLE orphan_groups (
+    group_id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    member_pointer_ids UUID[] NOT NULL,
+    reason TEXT NOT NULL,
+
+    -- Manifest content (canonical JSON serialized) and its signature
+    manifest_json JSONB NOT NULL,
+    manifest_hash VARCHAR(128) NOT NULL,
+    signature BYTEA NOT NULL,
+    signature_algorithm VARCHAR(50) NOT NULL DEFAULT 'ED25519',
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT member_pointer_ids_not_empty CHECK (cardinality(member_pointer_ids) > 0)
+);
+
+CREATE INDEX idx_orphan_groups_org_id ON orphan_groups(org_id);
+CREATE INDEX idx_orphan_groups_members ON orphan_groups USING GIN (member_pointer_ids);
+
+-- ============================================================================
+-- DATA_GC_RUNS TABLE
+-- ============================================================================
+-- Signed record of every data_store garbage collection run, one per org
+
+CREATE TABLE data_gc_runs (
+    manifest_id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    mode VARCHAR(20) NOT NULL,
+    data_ids UUID[] NOT NULL,
+    bytes_reclaimed BIGINT NOT NULL,
+
+    -- Manifest content (canonical JSON serialized) and its signature
+    manifest_json JSONB NOT NULL,
+    manifest_hash VARCHAR(128) NOT NULL,
+    signature BYTEA NOT NULL,
+    signature_algorithm VARCHAR(50) NOT NULL DEFAULT 'ED25519',
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT gc_mode_valid CHECK (mode IN ('delete', 'null_payload')),
+    CONSTRAINT data_ids_not_empty CHECK (cardinality(data_ids) > 0)
+);
+
+CREATE INDEX idx_data_gc_runs_org_id ON data_gc_runs(org_id, created_at DESC);
+CREATE INDEX idx_data_gc_runs_data_ids ON data_gc_runs USING GIN (data_ids);
+
+-- ============================================================================
+-- RESOLVE_STATS TABLE
+-- ============================================================================
+-- Hourly resolve counts per pointer, aggregated from resolve receipts by a
+-- background job so the resolve hot path never writes here
+
+CREATE TABLE resolve_stats (
+    pointer_id UUID NOT NULL REFERENCES pointers(pointer_id) ON DELETE CASCADE,
+    bucket_start TIMESTAMPTZ NOT NULL,
+    resolve_count BIGINT NOT NULL DEFAULT 0,
+    alerted_at TIMESTAMPTZ, -- Set once a spike in this bucket has been reported
+
+    PRIMARY KEY (pointer_id, bucket_start)
+);
+
+CREATE INDEX idx_resolve_stats_bucket_start ON resolve_stats(bucket_start DESC);
+
+-- ============================================================================
+-- POINTER RESTRICTIONS TABLE
+-- ============================================================================
+-- Time-boxed processing restrictions (GDPR Art. 18): resolves are denied
+-- while open and unexpired, orphaning stays allowed
+
+CREATE TABLE pointer_restrictions (
+    restriction_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    pointer_id UUID NOT NULL REFERENCES pointers(pointer_id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    restricted_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    lifted_at TIMESTAMPTZ,
+    lift_reason VARCHAR(50), -- expired | manual | orphaned
+
+    CONSTRAINT restriction_window_valid CHECK (expires_at > restricted_at),
+    CONSTRAINT restriction_lift_valid CHECK ((lifted_at IS NULL) = (lift_reason IS NULL))
+);
+
+-- At most one open restriction per pointer
+CREATE UNIQUE INDEX idx_pointer_restrictions_open ON pointer_restrictions(pointer_id)
+    WHERE lifted_at IS NULL;
+CREATE INDEX idx_pointer_restrictions_expiry ON pointer_restrictions(expires_at)
+    WHERE lifted_at IS NULL;
+
+-- ============================================================================
+-- POINTER CHAIN FREEZES TABLE
+-- ============================================================================
+-- Administrative freezes of a single pointer's receipt chain pending
+-- investigation. The open freeze mirrors pointers.chain_frozen.
+
+CREATE TABLE pointer_chain_freezes (
+    freeze_id UUID PRIMARY KEY,
+    pointer_id UUID NOT NULL REFERENCES pointers(pointer_id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    frozen_at TIMESTAMPTZ NOT NULL,
+    cleared_at TIMESTAMPTZ,
+    outcome TEXT, -- Investigation outcome given when clearing
+
+    CONSTRAINT chain_freeze_clear_valid CHECK ((cleared_at IS NULL) = (outcome IS NULL))
+);
+
+-- At most one open freeze per pointer
+CREATE UNIQUE INDEX idx_pointer_chain_freezes_open ON pointer_chain_freezes(pointer_id)
+    WHERE cleared_at IS NULL;
+
+-- ============================================================================
+-- ORG RECEIPTS TABLE
+-- ============================================================================
+-- Org-level chain of signed receipts for administrative actions that cannot
+-- be evidenced on a pointer's own chain: chain freezes and API key issuance,
+-- rotation and revocation
+
+CREATE TABLE org_receipts (
+    receipt_id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL, -- chain_freeze | chain_unfreeze | api_key_*
+    pointer_id UUID REFERENCES pointers(pointer_id) ON DELETE SET NULL,
+
+    receipt_json JSONB NOT NULL,
+    receipt_hash VARCHAR(128) NOT NULL,
+    signature BYTEA NOT NULL,
+    signature_algorithm VARCHAR(50) NOT NULL DEFAULT 'ED25519',
+    prev_hash VARCHAR(128), -- Previous org receipt of the same org
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+
+    CONSTRAINT org_receipt_kind_valid CHECK (
+        kind IN ('chain_freeze', 'chain_unfreeze',
+                 'api_key_issued', 'api_key_rotated', 'api_key_revoked')
+    )
+);
+
+CREATE INDEX idx_org_receipts_org_created ON org_receipts(org_id, created_at DESC);
+
+-- ============================================================================
+-- FUNCTIONS & TRIGGERS
+-- ============================================================================
+
+-- Update updated_at timestamp automatically
+CREATE OR REPLACE FUNCTION update_updated_at_column()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_organizations_updated_at
+    BEFORE UPDATE ON organizations
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Stamp pointer writes for the changes feed
+CREATE OR REPLACE FUNCTION track_pointer_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    NEW.change_xid = pg_current_xact_id();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER track_pointers_change
+    BEFORE UPDATE ON pointers
+    FOR EACH ROW
+    EXECUTE FUNCTION track_pointer_change();
+
+-- Automatically log pointer status changes
+CREATE OR REPLACE FUNCTION log_pointer_status_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.status != NEW.status THEN
+        INSERT INTO audit_log (
+            org_id,
+            pointer_id,
+            event_type,
+            event_data,
+            actor_id
+        ) VALUES (
+            NEW.org_id,
+            NEW.pointer_id,
+            'pointer_status_change',
+            jsonb_build_object(
+                'old_status', OLD.status::text,
+                'new_status', NEW.status::text,
+                'orphan_reason', NEW.orphan_reason
+            ),
+            current_setting('app.current_user_id', true)
+        );
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_log_pointer_status_change
+    AFTER UPDATE ON pointers
+    FOR EACH ROW
+    EXECUTE FUNCTION log_pointer_status_change();
+
+-- Refuse to extend a frozen pointer chain, whichever code path tries
+CREATE OR REPLACE FUNCTION guard_frozen_chain()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM pointers WHERE pointer_id = NEW.pointer_id AND chain_frozen
+    ) THEN
+        RAISE EXCEPTION 'chain_frozen: The receipt chain of pointer % is frozen pending investigation',
+            NEW.pointer_id
+            USING ERRCODE = 'object_not_in_prerequisite_state',
+                  CONSTRAINT = 'pointer_chain_frozen';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_guard_frozen_chain
+    BEFORE INSERT ON governance_receipts
+    FOR EACH ROW
+    EXECUTE FUNCTION guard_frozen_chain();
+
+-- ============================================================================
+-- ORG HEARTBEATS TABLE
+-- ============================================================================
+-- Org-level chain of signed liveness attestations, one per interval. Intervals
+-- the service missed are covered by an explicit gap marker, never backfilled.
+
+CREATE TABLE org_heartbeats (
+    heartbeat_id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    kind VARCHAR(10) NOT NULL, -- beat | gap
+    interval_start TIMESTAMPTZ NOT NULL,
+    interval_end TIMESTAMPTZ NOT NULL,
+
+    receipt_json JSONB NOT NULL,
+    receipt_hash VARCHAR(128) NOT NULL,
+    signature BYTEA NOT NULL,
+    signature_algorithm VARCHAR(50) NOT NULL DEFAULT 'ED25519',
+    prev_hash VARCHAR(128), -- Previous heartbeat of the same org
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT heartbeat_kind_valid CHECK (kind IN ('beat', 'gap')),
+    CONSTRAINT heartbeat_interval_valid CHECK (interval_end > interval_start),
+    CONSTRAINT heartbeat_interval_unique UNIQUE (org_id, interval_start)
+);
+
+-- ============================================================================
+-- VETO SLO DAILY TABLE
+-- ============================================================================
+-- Per-org daily count of vetoes and how many committed within the latency
+-- target, timed by the service from request to commit. Keyed by target so a
+-- changed target never mixes with days counted under the old one.
+
+CREATE TABLE veto_slo_daily (
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    target_ms INTEGER NOT NULL,
+    veto_count BIGINT NOT NULL DEFAULT 0,
+    within_target_count BIGINT NOT NULL DEFAULT 0,
+    max_latency_ms INTEGER NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (org_id, day, target_ms)
+);
+
+CREATE INDEX idx_veto_slo_daily_day ON veto_slo_daily(day DESC);
+
+-- ============================================================================
+-- VIEWS
+-- ============================================================================
+
+-- Active pointers view (frequently used query)
+CREATE VIEW active_pointers AS
+SELECT
+    p.pointer_id,
+    p.org_id,
+    p.data_id,
+    p.subject_id,
+    p.status,
+    p.created_at,
+    d.content_hash,
+    o.name AS org_name
+FROM pointers p
+JOIN data_store d ON p.data_id = d.data_id
+JOIN organizations o ON p.org_id = o.org_id
+WHERE p.status = 'active';
+
+-- Orphaned pointers view with revocation info
+CREATE VIEW orphaned_pointers AS
+SELECT
+    p.pointer_id,
+    p.org_id,
+    p.data_id,
+    p.subject_id,
+    p.created_at,
+    p.orphaned_at,
+    p.orphan_reason,
+    d.content_hash,
+    o.name AS org_name,
+    (p.orphaned_at - p.created_at) AS lifetime_duration
+FROM pointers p
+JOIN data_store d ON p.data_id = d.data_id
+JOIN organizations o ON p.org_id = o.org_id
+WHERE p.status = 'orphaned';
+
+-- Receipt chain view for audit trail
+CREATE VIEW receipt_chain AS
+SELECT
+    r.receipt_id,
+    r.pointer_id,
+    r.org_id,
+    r.operation,
+    r.receipt_hash,
+    r.prev_hash,
+    r.signature_algorithm,
+    r.timestamp,
+    p.subject_id,
+    o.name AS org_name
+FROM governance_receipts r
+JOIN pointers p ON r.pointer_id = p.pointer_id
+JOIN organizations o ON r.org_id = o.org_id
+ORDER BY r.timestamp DESC;
+
+-- ============================================================================
+-- SEED DATA (DEVELOPMENT/DEMO)
+-- ============================================================================
+
+-- Insert demo organization
+INSERT INTO organizations (org_id, name, metadata)
+VALUES (
+    '00000000-0000-0000-0000-000000000001'::uuid,
+    'FinalBoss Demo Org',
+    '{"environment": "demo", "type": "internal"}'::jsonb
+);
+
+-- ============================================================================
+-- COMMENTS
+-- ============================================================================
+
+COMMENT ON TABLE pointers IS 'Core patent implementation: pointers reference data but can be orphaned independently';
+COMMENT ON COLUMN pointers.status IS 'active = resolvable, orphaned = blocked by enforcement layer, pending_orphan = resolvable until orphan_effective_at';
+COMMENT ON TABLE data_store IS 'Persistent data storage - data survives pointer orphaning per US 19/240,581 Claim 9';
+COMMENT ON TABLE governance_receipts IS 'Cryptographically signed audit trail with chain hashing';
+COMMENT ON COLUMN governance_receipts.prev_hash IS 'Links to previous receipt hash for tamper-evident chain';
+COMMENT ON COLUMN governance_receipts.signature_algorithm IS 'ED25519 (current) or ML-DSA-65 (future post-quantum)';
+
+-- ============================================================================
+-- GRANT PERMISSIONS (adjust for your security requirements)
+-- ============================================================================
+
+-- Example: Create application role with limited permissions
+-- CREATE ROLE veto_app WITH LOGIN PASSWORD 'changeme';
+-- GRANT SELECT, INSERT, UPDATE ON ALL TABLES IN SCHEMA public TO veto_app;
+-- GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA public TO veto_app;
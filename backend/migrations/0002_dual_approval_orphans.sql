@@ -0,0 +1,37 @@
+-- Two-person orphaning
+-- A pointer created with requires_dual_approval is only orphaned once one
+-- operator requests it and a different one approves the request before it
+-- expires. Pointers without the flag are orphaned in a single call as before.
+
+ALTER TYPE receipt_operation ADD VALUE 'orphan_requested';
+
+ALTER TABLE pointers
+    ADD COLUMN requires_dual_approval BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE orphan_requests (
+    request_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    pointer_id UUID NOT NULL REFERENCES pointers(pointer_id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(org_id) ON DELETE CASCADE,
+    reason TEXT,
+    -- Actor ids as recorded in audit_log: X-Actor-Id, or api_key:<key_id>
+    requested_by VARCHAR(255) NOT NULL,
+    requested_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    approved_by VARCHAR(255),
+    -- When the request was approved, or found expired
+    closed_at TIMESTAMPTZ,
+
+    CONSTRAINT orphan_request_status_valid CHECK (status IN ('pending', 'approved', 'expired')),
+    CONSTRAINT orphan_request_window_valid CHECK (expires_at > requested_at),
+    CONSTRAINT orphan_request_closed_valid CHECK ((status = 'pending') = (closed_at IS NULL)),
+    CONSTRAINT orphan_request_approval_valid CHECK (
+        (status = 'approved') = (approved_by IS NOT NULL)
+    ),
+    CONSTRAINT orphan_request_second_person CHECK (approved_by IS DISTINCT FROM requested_by)
+);
+
+-- At most one pending request per pointer
+CREATE UNIQUE INDEX idx_orphan_requests_pending ON orphan_requests(pointer_id)
+    WHERE status = 'pending';
+CREATE INDEX idx_orphan_requests_pointer ON orphan_requests(pointer_id, requested_at DESC);
@@ -0,0 +1,6 @@
+-- Reinstatement of pointers orphaned in error
+-- A reinstated pointer is active again; its earlier orphan is kept in
+-- pointers.metadata.orphan_history and in the chain, where a reinstate
+-- receipt follows the orphan receipt it reverses.
+
+ALTER TYPE receipt_operation ADD VALUE 'reinstate';
@@ -0,0 +1,8 @@
+-- Receipt lookup by hash
+-- A receipt hash names exactly one receipt, so the index on it is unique.
+-- The pattern operator class also serves the prefix lookups of
+-- GET /api/receipts/by-hash/:receipt_hash?prefix=true in any collation.
+
+DROP INDEX idx_receipts_receipt_hash;
+CREATE UNIQUE INDEX idx_receipts_receipt_hash
+    ON governance_receipts(receipt_hash varchar_pattern_ops);
@@ -0,0 +1,13 @@
+-- Resolve stats by signed time
+-- Resolve counts are bucketed by the time each receipt was signed at, taken
+-- from the service's clock, rather than by when the row was inserted. The
+-- signed timestamp is RFC 3339 with an explicit offset, so reading it doesn't
+-- depend on the session time zone and the expression can be indexed.
+
+CREATE FUNCTION receipt_signed_at(receipt_json JSONB) RETURNS TIMESTAMPTZ
+    LANGUAGE sql IMMUTABLE PARALLEL SAFE
+    AS $$ SELECT (receipt_json->>'timestamp')::timestamptz $$;
+
+CREATE INDEX idx_receipts_resolve_signed_at
+    ON governance_receipts(receipt_signed_at(receipt_json))
+    WHERE operation = 'resolve';
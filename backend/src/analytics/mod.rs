@@ -0,0 +1,8 @@
+// Analytics module
+pub mod receipt_timing;
+pub mod resolve_stats;
+pub mod veto_slo;
+
+pub use receipt_timing::*;
+pub use resolve_stats::*;
+pub use veto_slo::*;
@@ -0,0 +1,246 @@
+// Receipt generation timing
+// When enabled, request-path receipts are signed with per-phase timing and
+// the insert is timed too. Each phase feeds a histogram served on /metrics,
+// and a receipt slower than the threshold overall is logged with its size.
+// When disabled, receipts are signed exactly as before.
+use anyhow::Result;
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::crypto::{jcs::canonicalize, Ed25519Keypair, ReceiptData, SignedReceipt};
+
+/// Histogram bucket upper bounds, in seconds
+const BUCKET_BOUNDS: [f64; 10] = [
+    0.00001, 0.00005, 0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.05,
+];
+
+#[derive(Default)]
+struct Histogram {
+    /// Per-bucket counts, the last one for everything past the bounds
+    buckets: [AtomicU64; BUCKET_BOUNDS.len() + 1],
+    count: AtomicU64,
+    sum_nanos: AtomicU64,
+}
+
+impl Histogram {
+    fn observe(&self, value: Duration) {
+        let seconds = value.as_secs_f64();
+        let bucket = BUCKET_BOUNDS
+            .iter()
+            .position(|&bound| seconds <= bound)
+            .unwrap_or(BUCKET_BOUNDS.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_nanos.fetch_add(
+            value.as_nanos().min(u64::MAX as u128) as u64,
+            Ordering::Relaxed,
+        );
+    }
+
+    fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+pub struct ReceiptPhaseMetrics {
+    enabled: bool,
+    slow_threshold: Duration,
+    canonicalize: Histogram,
+    hash: Histogram,
+    sign: Histogram,
+    persist: Histogram,
+    slow: AtomicU64,
+}
+
+impl ReceiptPhaseMetrics {
+    pub fn new(enabled: bool, slow_threshold: Duration) -> Self {
+        Self {
+            enabled,
+            slow_threshold,
+            canonicalize: Histogram::default(),
+            hash: Histogram::default(),
+            sign: Histogram::default(),
+            persist: Histogram::default(),
+            slow: AtomicU64::new(0),
+        }
+    }
+
+    /// Sign a receipt, timing each phase only when enabled
+    pub fn sign(&self, receipt: &ReceiptData, keypair: &Ed25519Keypair) -> Result<SignedReceipt> {
+        if self.enabled {
+            receipt.sign_timed(keypair)
+        } else {
+            receipt.sign(keypair)
+        }
+    }
+
+    /// Record a stored receipt's phases. Returns whether it was slow; an
+    /// untimed receipt is never recorded.
+    pub fn record(&self, pointer_id: Uuid, signed: &SignedReceipt, persist: Duration) -> bool {
+        let Some(timings) = &signed.timings else {
+            return false;
+        };
+
+        self.canonicalize.observe(timings.canonicalize);
+        self.hash.observe(timings.hash);
+        self.sign.observe(timings.sign);
+        self.persist.observe(persist);
+
+        let total = timings.total() + persist;
+        if total <= self.slow_threshold {
+            return false;
+        }
+
+        self.slow.fetch_add(1, Ordering::Relaxed);
+        // Only slow receipts pay for measuring their metadata
+        let metadata_bytes = canonicalize(&signed.receipt_json["metadata"]).len();
+        warn!(
+            "Slow receipt for pointer {}: {:?} total (canonicalize {:?}, hash {:?}, sign {:?}, \
+             persist {:?}), metadata {} bytes, canonical {} bytes",
+            pointer_id,
+            total,
+            timings.canonicalize,
+            timings.hash,
+            timings.sign,
+            persist,
+            metadata_bytes,
+            timings.canonical_bytes
+        );
+        true
+    }
+
+    /// Append the phase histograms and slow-receipt counter to an
+    /// OpenMetrics exposition. Nothing is written while disabled.
+    pub fn render_openmetrics(&self, out: &mut String) {
+        if !self.enabled {
+            return;
+        }
+
+        let _ = writeln!(out, "# TYPE receipt_phase_seconds histogram");
+        let _ = writeln!(
+            out,
+            "# HELP receipt_phase_seconds Time spent in each receipt generation phase"
+        );
+        let phases = [
+            ("canonicalize", &self.canonicalize),
+            ("hash", &self.hash),
+            ("sign", &self.sign),
+            ("persist", &self.persist),
+        ];
+        for (phase, histogram) in phases {
+            let mut cumulative = 0;
+            for (i, bound) in BUCKET_BOUNDS.iter().enumerate() {
+                cumulative += histogram.buckets[i].load(Ordering::Relaxed);
+                let _ = writeln!(
+                    out,
+                    "receipt_phase_seconds_bucket{{phase=\"{}\",le=\"{}\"}} {}",
+                    phase, bound, cumulative
+                );
+            }
+            cumulative += histogram.buckets[BUCKET_BOUNDS.len()].load(Ordering::Relaxed);
+            let _ = writeln!(
+                out,
+                "receipt_phase_seconds_bucket{{phase=\"{}\",le=\"+Inf\"}} {}",
+                phase, cumulative
+            );
+            let _ = writeln!(
+                out,
+                "receipt_phase_seconds_sum{{phase=\"{}\"}} {}",
+                phase,
+                histogram.sum_nanos.load(Ordering::Relaxed) as f64 / 1e9
+            );
+            let _ = writeln!(
+                out,
+                "receipt_phase_seconds_count{{phase=\"{}\"}} {}",
+                phase,
+                histogram.count()
+            );
+        }
+
+        let _ = writeln!(out, "# TYPE slow_receipts counter");
+        let _ = writeln!(
+            out,
+            "# HELP slow_receipts Receipts over the slow threshold from signing to stored"
+        );
+        let _ = writeln!(
+            out,
+            "slow_receipts_total {}",
+            self.slow.load(Ordering::Relaxed)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crypto::ReceiptTimings, db::models::ReceiptOperation};
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn receipt(metadata: serde_json::Value) -> ReceiptData {
+        ReceiptData::new(
+            Uuid::new_v4(),
+            ReceiptOperation::Create,
+            "user_123".to_string(),
+            None,
+            metadata,
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_disabled_metrics_sign_untimed_and_render_nothing() {
+        let metrics = ReceiptPhaseMetrics::new(false, Duration::ZERO);
+        let keypair = Ed25519Keypair::generate();
+
+        let signed = metrics.sign(&receipt(json!({})), &keypair).unwrap();
+        assert!(signed.timings.is_none());
+        assert!(!metrics.record(Uuid::new_v4(), &signed, Duration::from_millis(1)));
+        assert_eq!(metrics.persist.count(), 0);
+
+        let mut out = String::new();
+        metrics.render_openmetrics(&mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_large_metadata_receipt_is_flagged_slow() {
+        let metrics = ReceiptPhaseMetrics::new(true, Duration::from_millis(1));
+        let keypair = Ed25519Keypair::generate();
+
+        let small = metrics.sign(&receipt(json!({})), &keypair).unwrap();
+        assert!(small.timings.is_some());
+        // Pinned to zero so the outcome doesn't depend on the machine
+        let instant = SignedReceipt {
+            timings: Some(ReceiptTimings::default()),
+            ..small
+        };
+        assert!(!metrics.record(Uuid::new_v4(), &instant, Duration::ZERO));
+
+        // Megabytes of metadata take well over a millisecond to canonicalize
+        // and hash
+        let blob = "x".repeat(4_000_000);
+        let large = metrics
+            .sign(&receipt(json!({ "blob": blob })), &keypair)
+            .unwrap();
+        let timings = large.timings.unwrap();
+        assert!(timings.canonical_bytes > 4_000_000);
+        assert!(timings.total() > Duration::from_millis(1));
+        assert!(metrics.record(Uuid::new_v4(), &large, Duration::ZERO));
+        assert_eq!(metrics.persist.count(), 2);
+        assert_eq!(metrics.slow.load(Ordering::Relaxed), 1);
+
+        let mut out = String::new();
+        metrics.render_openmetrics(&mut out);
+        assert!(out.contains("# TYPE receipt_phase_seconds histogram\n"));
+        assert!(out.contains("receipt_phase_seconds_bucket{phase=\"persist\",le=\"0.00001\"} 2\n"));
+        assert!(out.contains("receipt_phase_seconds_bucket{phase=\"hash\",le=\"+Inf\"} 2\n"));
+        assert!(out.contains("receipt_phase_seconds_count{phase=\"canonicalize\"} 2\n"));
+        assert!(out.contains("slow_receipts_total 1\n"));
+    }
+}
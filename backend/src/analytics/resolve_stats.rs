@@ -0,0 +1,311 @@
+// Per-pointer resolve frequency analytics
+// Counts are aggregated from resolve receipts on a timer, never on the resolve path
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::{
+    clock::Clock,
+    config::Config,
+    db::{models::ResolveSpikeCandidate, queries},
+};
+
+/// Thresholds for flagging the latest hourly bucket as a spike
+#[derive(Debug, Clone, Copy)]
+pub struct SpikeThresholds {
+    /// Latest bucket must exceed the trailing average by this factor
+    pub ratio: f64,
+    /// Latest bucket must contain at least this many resolves
+    pub min_count: i64,
+    /// Number of preceding hourly buckets averaged into the baseline
+    pub baseline_hours: i32,
+}
+
+impl SpikeThresholds {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            ratio: config.resolve_spike_ratio,
+            min_count: config.resolve_spike_min_count,
+            baseline_hours: config.resolve_spike_baseline_hours,
+        }
+    }
+
+    /// A zero baseline is treated as one resolve per hour so a brand-new
+    /// pointer needs `min_count` resolves before it can be flagged
+    pub fn is_spike(&self, count: i64, baseline: f64) -> bool {
+        count >= self.min_count && count as f64 > self.ratio * baseline.max(1.0)
+    }
+}
+
+pub fn hour_bucket(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.duration_trunc(Duration::hours(1)).unwrap_or(at)
+}
+
+/// Refresh the current and previous hourly buckets from resolve receipts
+pub async fn aggregate(pool: &PgPool, now: DateTime<Utc>) -> Result<u64> {
+    queries::aggregate_resolve_stats(pool, hour_bucket(now) - Duration::hours(1)).await
+}
+
+/// Flag the bucket containing `now` for every pointer whose resolves spiked,
+/// emitting an alert log line and an audit event once per pointer and bucket.
+/// Each alert is claimed and audited in one transaction, so detectors running
+/// at once (on several replicas, say) never alert the same bucket twice.
+pub async fn detect_spikes(
+    pool: &PgPool,
+    thresholds: SpikeThresholds,
+    now: DateTime<Utc>,
+) -> Result<Vec<ResolveSpikeCandidate>> {
+    let bucket_start = hour_bucket(now);
+    let candidates =
+        queries::get_resolve_spike_candidates(pool, bucket_start, thresholds.baseline_hours)
+            .await?;
+
+    let mut spikes = Vec::new();
+
+    for candidate in candidates {
+        if !thresholds.is_spike(candidate.resolve_count, candidate.baseline) {
+            continue;
+        }
+
+        let mut tx = pool.begin().await?;
+        if !queries::claim_resolve_spike_alert(
+            &mut *tx,
+            candidate.pointer_id,
+            candidate.bucket_start,
+            now,
+        )
+        .await?
+        {
+            continue;
+        }
+
+        queries::create_audit_log(
+            &mut *tx,
+            Some(candidate.org_id),
+            Some(candidate.pointer_id),
+            None,
+            "resolve_spike_detected",
+            json!({
+                "subject_id": candidate.subject_id,
+                "bucket_start": candidate.bucket_start,
+                "resolve_count": candidate.resolve_count,
+                "baseline": candidate.baseline,
+                "ratio_threshold": thresholds.ratio,
+            }),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+
+        warn!(
+            "ALERT resolve spike on pointer {} (subject {}): {} resolves this hour vs baseline {:.2}",
+            candidate.pointer_id, candidate.subject_id, candidate.resolve_count, candidate.baseline
+        );
+
+        spikes.push(candidate);
+    }
+
+    Ok(spikes)
+}
+
+/// Background loop: aggregate, then detect, every configured interval
+pub async fn run_resolve_stats_worker(pool: PgPool, config: Config, clock: Arc<dyn Clock>) {
+    let thresholds = SpikeThresholds::from_config(&config);
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.resolve_stats_interval_seconds.max(1),
+    ));
+
+    info!(
+        "Resolve stats worker running every {}s",
+        config.resolve_stats_interval_seconds
+    );
+
+    loop {
+        interval.tick().await;
+        let now = clock.now();
+
+        if let Err(e) = aggregate(&pool, now).await {
+            error!("Resolve stats aggregation failed: {:#}", e);
+            continue;
+        }
+
+        if let Err(e) = detect_spikes(&pool, thresholds, now).await {
+            error!("Resolve spike detection failed: {:#}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{seed_pointer, test_auth, test_state, unique_subject};
+    use uuid::Uuid;
+
+    const THRESHOLDS: SpikeThresholds = SpikeThresholds {
+        ratio: 10.0,
+        min_count: 50,
+        baseline_hours: 24,
+    };
+
+    #[test]
+    fn test_is_spike_thresholds() {
+        assert!(THRESHOLDS.is_spike(100, 5.0));
+        assert!(!THRESHOLDS.is_spike(40, 0.0), "below min_count");
+        assert!(!THRESHOLDS.is_spike(100, 20.0), "within ratio");
+        assert!(THRESHOLDS.is_spike(50, 0.0), "zero baseline counts as one");
+    }
+
+    async fn seed_bucket(pool: &PgPool, pointer_id: Uuid, bucket: DateTime<Utc>, count: i64) {
+        sqlx::query(
+            "INSERT INTO resolve_stats (pointer_id, bucket_start, resolve_count) VALUES ($1, $2, $3)",
+        )
+        .bind(pointer_id)
+        .bind(bucket)
+        .bind(count)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_detects_spike_without_false_positives() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("stats");
+        let steady = seed_pointer(&state, &subject).await;
+        let spiking = seed_pointer(&state, &subject).await;
+
+        let now = Utc::now();
+
+        for hour in 1..=24 {
+            let bucket = hour_bucket(now) - Duration::hours(hour);
+            seed_bucket(&state.db.writer, steady, bucket, 5).await;
+            seed_bucket(&state.db.writer, spiking, bucket, 5).await;
+        }
+        seed_bucket(&state.db.writer, steady, hour_bucket(now), 8).await;
+        seed_bucket(&state.db.writer, spiking, hour_bucket(now), 120).await;
+
+        let spikes: Vec<ResolveSpikeCandidate> = detect_spikes(&state.db.writer, THRESHOLDS, now)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.pointer_id == steady || s.pointer_id == spiking)
+            .collect();
+        assert_eq!(spikes.len(), 1);
+        assert_eq!(spikes[0].pointer_id, spiking);
+        assert_eq!(spikes[0].resolve_count, 120);
+        assert!((spikes[0].baseline - 5.0).abs() < f64::EPSILON);
+
+        // Alerts fire once per bucket
+        let again = detect_spikes(&state.db.writer, THRESHOLDS, now)
+            .await
+            .unwrap();
+        assert!(again.iter().all(|s| s.pointer_id != spiking));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_detectors_alert_once() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let spiking = seed_pointer(&state, &unique_subject("stats")).await;
+        let now = Utc::now();
+        seed_bucket(&state.db.writer, spiking, hour_bucket(now), 120).await;
+
+        // As if run by two replicas at once
+        let pool = &state.db.writer;
+        let (first, second) = tokio::join!(
+            detect_spikes(pool, THRESHOLDS, now),
+            detect_spikes(pool, THRESHOLDS, now)
+        );
+        let alerted = [first.unwrap(), second.unwrap()]
+            .iter()
+            .flatten()
+            .filter(|s| s.pointer_id == spiking)
+            .count();
+        assert_eq!(alerted, 1);
+
+        let audited: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM audit_log WHERE pointer_id = $1 AND event_type = 'resolve_spike_detected'",
+        )
+        .bind(spiking)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(audited, 1);
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_from_resolve_receipts() {
+        use crate::api::handlers::resolve_pointer;
+        use axum::extract::{Path, Query, State};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("stats")).await;
+
+        for _ in 0..3 {
+            let resolved = resolve_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query::default(),
+            )
+            .await;
+            assert!(resolved.is_ok(), "resolve failed");
+        }
+
+        let now = Utc::now();
+        aggregate(&state.db.writer, now).await.unwrap();
+
+        let stats =
+            queries::get_resolve_stats(&state.db.writer, pointer_id, now - Duration::hours(1))
+                .await
+                .unwrap();
+        let total: i64 = stats.iter().map(|s| s.resolve_count).sum();
+        assert_eq!(total, 3);
+    }
+
+    #[tokio::test]
+    async fn test_buckets_resolves_by_signed_time() {
+        use crate::{api::handlers::resolve_pointer, clock::FixedClock};
+        use axum::{
+            extract::{Path, Query, State},
+            Json,
+        };
+
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("stats_clock")).await;
+
+        // Resolved on a clock days ahead of the database's
+        let now = hour_bucket(Utc::now()) + Duration::days(3) + Duration::minutes(10);
+        state.clock = Arc::new(FixedClock(now));
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("resolve failed"));
+        assert_eq!(resolved.receipt.unwrap().timestamp, now.to_rfc3339());
+
+        aggregate(&state.db.writer, now).await.unwrap();
+
+        let stats = queries::get_resolve_stats(&state.db.writer, pointer_id, now)
+            .await
+            .unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].bucket_start, hour_bucket(now));
+        assert_eq!(stats[0].resolve_count, 1);
+    }
+}
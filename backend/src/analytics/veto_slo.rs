@@ -0,0 +1,204 @@
+// Veto latency SLO tracking
+// Each veto is timed by the service itself, from the handler receiving the
+// request to its transaction committing, and tallied per org and UTC day in
+// the database so compliance survives restarts and never depends on logs
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::{fmt::Write, time::Duration};
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::ReceiptPhaseMetrics;
+use crate::{
+    api::{compression::CompressionMetrics, rate_limit::RateLimiter},
+    db::{models::VetoSloDay, queries, PointerCache},
+    enforcement::DegradedReadStats,
+};
+
+/// Content type of the OpenMetrics text exposition
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Tally a committed veto. Errors are logged rather than returned since the
+/// veto itself has already taken effect.
+pub async fn record_veto(
+    pool: &PgPool,
+    org_id: Uuid,
+    now: DateTime<Utc>,
+    latency: Duration,
+    target_ms: u32,
+) {
+    let latency_ms = latency.as_millis().min(i32::MAX as u128) as i32;
+    let target_ms = target_ms.min(i32::MAX as u32) as i32;
+
+    if let Err(e) =
+        queries::record_veto_latency(pool, org_id, now.date_naive(), target_ms, latency_ms).await
+    {
+        warn!("Failed to record veto latency for org {}: {:#}", org_id, e);
+    }
+}
+
+/// A day's tally with its compliance worked out
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct VetoSloSummary {
+    #[serde(flatten)]
+    pub day: VetoSloDay,
+    pub compliance_percent: f64,
+}
+
+impl From<VetoSloDay> for VetoSloSummary {
+    fn from(day: VetoSloDay) -> Self {
+        Self {
+            compliance_percent: compliance_percent(&day),
+            day,
+        }
+    }
+}
+
+/// Share of the day's vetoes that committed within the target
+pub fn compliance_percent(day: &VetoSloDay) -> f64 {
+    if day.veto_count == 0 {
+        return 100.0;
+    }
+    100.0 * day.within_target_count as f64 / day.veto_count as f64
+}
+
+/// Gauge name, help text, and how to read its value from a tally
+type Gauge = (&'static str, &'static str, fn(&VetoSloDay) -> f64);
+
+const GAUGES: [Gauge; 3] = [
+    (
+        "veto_slo_compliance_percent",
+        "Share of today's vetoes committed within the latency target",
+        compliance_percent,
+    ),
+    ("veto_slo_vetoes", "Vetoes committed today", |day| {
+        day.veto_count as f64
+    }),
+    (
+        "veto_slo_max_latency_seconds",
+        "Slowest veto today, from request to commit",
+        |day| f64::from(day.max_latency_ms) / 1000.0,
+    ),
+];
+
+/// OpenMetrics gauges for the given tallies, one sample per org and target,
+/// followed by the degraded-read counters, any receipt phase histograms and
+/// the other in-process counters
+pub fn render_openmetrics(
+    days: &[VetoSloDay],
+    degraded: &DegradedReadStats,
+    receipts: &ReceiptPhaseMetrics,
+    rate_limiter: &RateLimiter,
+    pointer_cache: &PointerCache,
+    compression: &CompressionMetrics,
+) -> String {
+    let mut out = String::new();
+    for (name, help, value) in GAUGES {
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        for day in days {
+            let _ = writeln!(
+                out,
+                "{}{{org_id=\"{}\",target_ms=\"{}\"}} {}",
+                name,
+                day.org_id,
+                day.target_ms,
+                value(day)
+            );
+        }
+    }
+
+    let counters = [
+        (
+            "degraded_resolves",
+            "Resolves answered from last known status during a database outage",
+            degraded.served,
+        ),
+        (
+            "degraded_resolve_receipts_backfilled",
+            "Receipts written for degraded resolves after recovery",
+            degraded.backfilled,
+        ),
+    ];
+    for (name, help, value) in counters {
+        let _ = writeln!(out, "# TYPE {} counter", name);
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "{}_total {}", name, value);
+    }
+    let _ = writeln!(out, "# TYPE degraded_resolve_receipts_pending gauge");
+    let _ = writeln!(
+        out,
+        "# HELP degraded_resolve_receipts_pending Degraded resolves still owed a receipt"
+    );
+    let _ = writeln!(
+        out,
+        "degraded_resolve_receipts_pending {}",
+        degraded.pending
+    );
+    receipts.render_openmetrics(&mut out);
+    rate_limiter.render_openmetrics(&mut out);
+    pointer_cache.render_openmetrics(&mut out);
+    compression.render_openmetrics(&mut out);
+
+    out.push_str("# EOF\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn tally(veto_count: i64, within_target_count: i64) -> VetoSloDay {
+        VetoSloDay {
+            org_id: Uuid::nil(),
+            day: NaiveDate::from_ymd_opt(2025, 11, 26).unwrap(),
+            target_ms: 1000,
+            veto_count,
+            within_target_count,
+            max_latency_ms: 1500,
+        }
+    }
+
+    #[test]
+    fn test_compliance_percent() {
+        assert_eq!(compliance_percent(&tally(200, 198)), 99.0);
+        assert_eq!(compliance_percent(&tally(0, 0)), 100.0);
+    }
+
+    #[test]
+    fn test_openmetrics_exposition() {
+        let degraded = DegradedReadStats {
+            served: 5,
+            backfilled: 3,
+            pending: 2,
+        };
+        let receipts = ReceiptPhaseMetrics::new(false, Duration::ZERO);
+        let text = render_openmetrics(
+            &[tally(4, 3)],
+            &degraded,
+            &receipts,
+            &RateLimiter::new(0, 0),
+            &PointerCache::new(Duration::ZERO, 0),
+            &CompressionMetrics::new(),
+        );
+        let labels = format!("{{org_id=\"{}\",target_ms=\"1000\"}}", Uuid::nil());
+
+        assert!(text.contains("# TYPE veto_slo_compliance_percent gauge\n"));
+        assert!(text.contains(&format!("veto_slo_compliance_percent{} 75\n", labels)));
+        assert!(text.contains(&format!("veto_slo_vetoes{} 4\n", labels)));
+        assert!(text.contains(&format!("veto_slo_max_latency_seconds{} 1.5\n", labels)));
+        assert!(text.contains("# TYPE degraded_resolves counter\n"));
+        assert!(text.contains("degraded_resolves_total 5\n"));
+        assert!(text.contains("degraded_resolve_receipts_backfilled_total 3\n"));
+        assert!(text.contains("degraded_resolve_receipts_pending 2\n"));
+        assert!(text.contains("rate_limited_requests_total{class=\"write\"} 0\n"));
+        assert!(text.contains("pointer_cache_hits_total 0\n"));
+        assert!(text.contains("pointer_cache_entries 0\n"));
+        assert!(text.contains("# TYPE compression_sent_bytes counter\n"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+}
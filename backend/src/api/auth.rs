@@ -0,0 +1,289 @@
+// API key authentication
+// Every route except the health check and the published verification
+// material needs `Authorization: Bearer <key>`. A key authenticates as one
+// organization: pointers are created in it, and another organization's
+// pointers are refused with 403. Only the SHA3-256 hash of a key is stored.
+// A rotated key stops authenticating when its overlap ends, and is then
+// refused with its own error code so the caller knows to use its successor.
+// While the database circuit is open, keys verified within the degraded-read
+// staleness bound keep authenticating, so degraded resolves still answer.
+use anyhow::Result;
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::{collections::HashMap, sync::Mutex};
+use uuid::Uuid;
+
+use super::{request_id::record_org, ApiError, AppState};
+use crate::{
+    crypto::hashing::sha3_256_hash_str,
+    db::queries,
+    keys::{key_is_valid, key_valid_until},
+};
+
+/// Prefix of every minted key, so leaked keys are easy to scan for
+pub const API_KEY_PREFIX: &str = "vf_";
+
+/// The organization and key a request authenticated as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthContext {
+    pub org_id: Uuid,
+    pub key_id: Uuid,
+}
+
+impl AuthContext {
+    /// Refuse access to a resource of another organization
+    pub fn authorize(&self, org_id: Uuid) -> Result<(), ApiError> {
+        if org_id == self.org_id {
+            Ok(())
+        } else {
+            Err(ApiError::Forbidden(
+                "forbidden: This resource belongs to another organization".to_string(),
+            ))
+        }
+    }
+
+    /// Keep the caller's rows of a subject-wide lookup. A subject whose rows
+    /// all belong to other organizations is refused, not reported missing.
+    pub fn own<T>(&self, rows: Vec<T>, org_of: impl Fn(&T) -> Uuid) -> Result<Vec<T>, ApiError> {
+        let found = !rows.is_empty();
+        let own: Vec<T> = rows
+            .into_iter()
+            .filter(|row| org_of(row) == self.org_id)
+            .collect();
+
+        if found && own.is_empty() {
+            return Err(ApiError::Forbidden(
+                "forbidden: This subject belongs to another organization".to_string(),
+            ));
+        }
+        Ok(own)
+    }
+}
+
+/// Set by `require_api_key`; a handler behind it can take `AuthContext`
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuthContext {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, ApiError> {
+        parts
+            .extensions
+            .get::<AuthContext>()
+            .copied()
+            .ok_or_else(|| ApiError::Unauthorized("unauthorized: Missing API key".to_string()))
+    }
+}
+
+/// Keys verified against the database, for authenticating during outages
+pub struct KnownApiKeys {
+    max_staleness: Duration,
+    entries: Mutex<HashMap<String, KnownApiKey>>,
+}
+
+struct KnownApiKey {
+    auth: AuthContext,
+    verified_at: DateTime<Utc>,
+    /// End of the key's rotation overlap, if it has one
+    valid_until: Option<DateTime<Utc>>,
+}
+
+impl KnownApiKeys {
+    /// Keys verified more than `max_staleness` ago are not trusted; zero
+    /// disables authenticating from memory
+    pub fn new(max_staleness: Duration) -> Self {
+        Self {
+            max_staleness,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn record(
+        &self,
+        key_hash: &str,
+        auth: AuthContext,
+        verified_at: DateTime<Utc>,
+        valid_until: Option<DateTime<Utc>>,
+    ) {
+        let known = KnownApiKey {
+            auth,
+            verified_at,
+            valid_until,
+        };
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key_hash.to_string(), known);
+    }
+
+    fn forget(&self, key_hash: &str) {
+        self.entries.lock().unwrap().remove(key_hash);
+    }
+
+    fn fresh(&self, key_hash: &str, now: DateTime<Utc>) -> Option<AuthContext> {
+        if self.max_staleness <= Duration::zero() {
+            return None;
+        }
+
+        self.entries
+            .lock()
+            .unwrap()
+            .get(key_hash)
+            .filter(|known| now - known.verified_at <= self.max_staleness)
+            .filter(|known| known.valid_until.is_none_or(|until| now < until))
+            .map(|known| known.auth)
+    }
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    sha3_256_hash_str(key)
+}
+
+fn bearer_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+}
+
+/// Middleware: authenticate the bearer key and hand its `AuthContext` to
+/// the handler
+pub async fn require_api_key(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let key = bearer_key(request.headers())
+        .ok_or_else(|| ApiError::Unauthorized("unauthorized: Missing API key".to_string()))?;
+    let auth = authenticate(&state, key).await?;
+    record_org(&auth.org_id);
+
+    request.extensions_mut().insert(auth);
+    Ok(next.run(request).await)
+}
+
+async fn authenticate(state: &AppState, key: &str) -> Result<AuthContext, ApiError> {
+    let key_hash = hash_api_key(key);
+    let now = state.clock.now();
+    let from_memory = || {
+        state.api_keys.fresh(&key_hash, now).ok_or_else(|| {
+            ApiError::ServiceUnavailable("database_unavailable: Try again shortly".to_string())
+        })
+    };
+
+    if state.db_circuit.is_open() {
+        return from_memory();
+    }
+    let Some(found) = state
+        .db_circuit
+        .observe(queries::get_api_key_by_hash(&state.db.writer, &key_hash).await)?
+    else {
+        return from_memory();
+    };
+
+    match found {
+        Some(api_key) if key_is_valid(&api_key, now) => {
+            let auth = AuthContext {
+                org_id: api_key.org_id,
+                key_id: api_key.key_id,
+            };
+            state
+                .api_keys
+                .record(&key_hash, auth, now, key_valid_until(&api_key));
+            Ok(auth)
+        }
+        Some(api_key) if api_key.replaced_by.is_some() => {
+            state.api_keys.forget(&key_hash);
+            Err(ApiError::ApiKeyRotated(
+                "api_key_rotated: Rotation completed; authenticate with the new key".to_string(),
+            ))
+        }
+        Some(_) => {
+            state.api_keys.forget(&key_hash);
+            Err(ApiError::Unauthorized(
+                "unauthorized: API key has been revoked".to_string(),
+            ))
+        }
+        None => Err(ApiError::Unauthorized(
+            "unauthorized: Unknown API key".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{keys::mint_api_key, test_support::test_state};
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+
+    async fn status_with(app: &Router, key: Option<&str>) -> StatusCode {
+        let mut request = axum::http::Request::builder().uri("/whoami");
+        if let Some(key) = key {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", key));
+        }
+        app.clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_missing_unknown_and_revoked_keys_are_refused() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let app = Router::new()
+            .route(
+                "/whoami",
+                get(|auth: AuthContext| async move { auth.org_id.to_string() }),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                require_api_key,
+            ))
+            .with_state(state.clone());
+
+        let issued = mint_api_key(
+            &state.db.writer,
+            &state.keypair(),
+            state.config.default_org_id,
+            "test",
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        let (api_key, key) = (issued.api_key, issued.key);
+        assert!(key.starts_with(API_KEY_PREFIX));
+        assert_ne!(api_key.key_hash, key, "only the hash is stored");
+
+        assert_eq!(status_with(&app, None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status_with(&app, Some("vf_not_a_key")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(status_with(&app, Some(&key)).await, StatusCode::OK);
+
+        queries::revoke_api_key(&state.db.writer, api_key.key_id, Utc::now())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            status_with(&app, Some(&key)).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert!(state
+            .api_keys
+            .fresh(&api_key.key_hash, Utc::now())
+            .is_none());
+    }
+}
@@ -0,0 +1,310 @@
+// veto-client against the real router
+// Each test serves the app on an ephemeral port and drives it through the
+// client, so a request or response shape the two disagree on fails here.
+use axum::{
+    http::StatusCode,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use uuid::Uuid;
+use veto_client::{
+    types::{
+        AuditTrailQuery, CreatePointerRequest, OrphanPointerRequest, ReceiptEntry, ReceiptFailure,
+        ReceiptsQuery, ResolveMode, ResolveQuery,
+    },
+    ClientError, VetoClient,
+};
+
+use crate::{
+    api::{routes::build_app, AppState},
+    test_support::{test_state, unique_subject},
+};
+
+/// Serve `app` as main does, returning its base URL
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+/// The app on a fresh port and an API key of the state's default org
+async fn serve_state(state: &AppState) -> (String, String) {
+    let issued = crate::keys::mint_api_key(
+        &state.db.writer,
+        &state.keypair(),
+        state.config.default_org_id,
+        "client test",
+        Utc::now(),
+    )
+    .await
+    .unwrap();
+    (serve(build_app(state.clone())).await, issued.key)
+}
+
+fn create_request(subject_id: &str) -> CreatePointerRequest {
+    CreatePointerRequest {
+        subject_id: subject_id.to_string(),
+        content_hash: "ab".repeat(64),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_client_pointer_lifecycle() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let (base_url, api_key) = serve_state(&state).await;
+    let client = VetoClient::builder(base_url, api_key)
+        .verify_receipts(true)
+        .org_id(state.config.default_org_id)
+        .actor_id("client-test")
+        .build()
+        .unwrap();
+    let subject_id = unique_subject("client");
+
+    let created = client
+        .create_pointer(&create_request(&subject_id))
+        .await
+        .unwrap();
+    assert_eq!(created.status, "active");
+
+    let resolved = client
+        .resolve_pointer(created.pointer_id, &ResolveQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(resolved.subject_id, subject_id);
+    assert!(resolved.receipt_generated && resolved.receipt.is_some());
+
+    let readonly = ResolveQuery {
+        mode: ResolveMode::Readonly,
+        ..Default::default()
+    };
+    let peeked = client
+        .resolve_pointer(created.pointer_id, &readonly)
+        .await
+        .unwrap();
+    assert!(!peeked.receipt_generated && peeked.receipt.is_none());
+
+    let orphaned = client
+        .orphan_pointer(&OrphanPointerRequest {
+            pointer_id: created.pointer_id,
+            reason: Some("user_request".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(orphaned.status, "orphaned");
+    assert!(!orphaned.already_orphaned);
+
+    match client
+        .resolve_pointer(created.pointer_id, &ResolveQuery::default())
+        .await
+    {
+        Err(err @ ClientError::PointerOrphaned(_)) => assert!(err.request_id().is_some()),
+        other => panic!("expected PointerOrphaned, got {:?}", other),
+    }
+
+    // Every receipt on the page was checked against the org's published key
+    let page = client
+        .get_receipts(created.pointer_id, &ReceiptsQuery::default())
+        .await
+        .unwrap();
+    let operations: Vec<&str> = page
+        .receipts
+        .iter()
+        .map(|entry| match entry {
+            ReceiptEntry::Receipt(receipt) => receipt.operation.as_str(),
+            ReceiptEntry::Tombstone(tombstone) => panic!("tombstone {:?}", tombstone),
+        })
+        .collect();
+    assert!(operations.contains(&"create") && operations.contains(&"orphan"));
+
+    let trail = client
+        .get_audit_trail(&subject_id, &AuditTrailQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(trail.orphaned_pointers, 1);
+    let created_event = trail
+        .audit_events
+        .iter()
+        .find(|event| event.event_type == "pointer_created")
+        .expect("pointer_created in the trail");
+    assert_eq!(created_event.pointer_id, Some(created.pointer_id));
+    assert_eq!(created_event.actor_id.as_deref(), Some("client-test"));
+}
+
+#[tokio::test]
+async fn test_client_maps_error_codes() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let (base_url, api_key) = serve_state(&state).await;
+    let client = VetoClient::new(&base_url, api_key).unwrap();
+
+    let missing = client
+        .resolve_pointer(Uuid::new_v4(), &ResolveQuery::default())
+        .await;
+    assert!(
+        matches!(missing, Err(ClientError::NotFound(_))),
+        "{:?}",
+        missing
+    );
+
+    let invalid = CreatePointerRequest {
+        content_hash: "not-a-hash".to_string(),
+        ..create_request(&unique_subject("client"))
+    };
+    match client.create_pointer(&invalid).await {
+        Err(ClientError::ValidationFailed(body)) => {
+            let errors = body.errors.expect("field errors");
+            assert!(
+                errors.iter().any(|e| e.field == "content_hash"),
+                "{:?}",
+                errors
+            );
+        }
+        other => panic!("expected ValidationFailed, got {:?}", other),
+    }
+
+    let stranger = VetoClient::new(&base_url, "vf_not_a_key").unwrap();
+    let refused = stranger
+        .create_pointer(&create_request(&unique_subject("client")))
+        .await;
+    assert!(
+        matches!(refused, Err(ClientError::Unauthorized(_))),
+        "{:?}",
+        refused
+    );
+}
+
+#[tokio::test]
+async fn test_client_rejects_receipts_that_do_not_verify() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let (base_url, api_key) = serve_state(&state).await;
+    let client = VetoClient::builder(&base_url, &api_key)
+        .org_id(state.config.default_org_id)
+        .build()
+        .unwrap();
+
+    let created = client
+        .create_pointer(&create_request(&unique_subject("client")))
+        .await
+        .unwrap();
+    client.verify_receipt(&created.receipt).await.unwrap();
+
+    let mut forged = created.receipt.clone();
+    forged.receipt_hash = "cd".repeat(64);
+    match client.verify_receipt(&forged).await {
+        Err(ClientError::InvalidReceipt { failure, .. }) => {
+            assert_eq!(failure, ReceiptFailure::InvalidSignature)
+        }
+        other => panic!("expected InvalidReceipt, got {:?}", other),
+    }
+
+    // Org keys are only published to clients that ask for the org's
+    if state.org_signing_keys.is_some() {
+        let service_keys_only = VetoClient::new(&base_url, &api_key).unwrap();
+        match service_keys_only.verify_receipt(&created.receipt).await {
+            Err(ClientError::InvalidReceipt { failure, .. }) => {
+                assert_eq!(failure, ReceiptFailure::UnknownKey)
+            }
+            other => panic!("expected InvalidReceipt, got {:?}", other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_client_retries_only_reads_on_5xx() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let failing = {
+        let hits = hits.clone();
+        move || {
+            hits.fetch_add(1, Ordering::SeqCst);
+            async { (StatusCode::BAD_GATEWAY, "upstream gone") }
+        }
+    };
+    let app = Router::new()
+        .route("/api/receipts/:id", get(failing.clone()))
+        .route("/api/pointer/resolve/:id", get(failing.clone()))
+        .route("/api/pointer/orphan", post(failing));
+    let client = VetoClient::builder(serve(app).await, "vf_test")
+        .max_retries(2)
+        .retry_backoff(Duration::from_millis(1))
+        .build()
+        .unwrap();
+
+    let read = client
+        .get_receipts(Uuid::new_v4(), &ReceiptsQuery::default())
+        .await;
+    assert!(matches!(read, Err(ClientError::Status { status, .. }) if status == 502));
+    assert_eq!(
+        hits.swap(0, Ordering::SeqCst),
+        3,
+        "first try and two retries"
+    );
+
+    let readonly = ResolveQuery {
+        mode: ResolveMode::Readonly,
+        ..Default::default()
+    };
+    let _ = client.resolve_pointer(Uuid::new_v4(), &readonly).await;
+    assert_eq!(hits.swap(0, Ordering::SeqCst), 3);
+
+    // An attested resolve writes a receipt, so it is sent once like a POST
+    let _ = client
+        .resolve_pointer(Uuid::new_v4(), &ResolveQuery::default())
+        .await;
+    assert_eq!(hits.swap(0, Ordering::SeqCst), 1);
+
+    let _ = client
+        .orphan_pointer(&OrphanPointerRequest {
+            pointer_id: Uuid::new_v4(),
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(hits.swap(0, Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_client_times_out() {
+    let app = Router::new().route(
+        "/api/receipts/:id",
+        get(|| async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            StatusCode::OK
+        }),
+    );
+    let client = VetoClient::builder(serve(app).await, "vf_test")
+        .timeout(Duration::from_millis(50))
+        .max_retries(0)
+        .build()
+        .unwrap();
+
+    match client
+        .get_receipts(Uuid::new_v4(), &ReceiptsQuery::default())
+        .await
+    {
+        Err(ClientError::Http(err)) => assert!(err.is_timeout(), "{:?}", err),
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+}
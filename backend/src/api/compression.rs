@@ -0,0 +1,295 @@
+// Response compression for large read endpoints
+// Applied per route group: receipt lists and audit trails compress ~10:1,
+// while resolve is never wrapped so its responses skip the encoder entirely.
+// Body bytes are counted per matched route on both sides of the encoder, so
+// /metrics shows what compression saves on each route.
+use axum::{
+    body::{Body, Bytes},
+    extract::{MatchedPath, Request, State},
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use http_body::{Frame, SizeHint};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+use tower_http::compression::{
+    predicate::{DefaultPredicate, Predicate, SizeAbove},
+    CompressionLayer,
+};
+
+use super::AppState;
+
+/// Gzip/brotli compression, chosen from Accept-Encoding, for responses
+/// larger than `min_bytes`. Bodies without a known size (streams) are
+/// compressed frame by frame so they stay streamable.
+pub fn compression_layer(min_bytes: u16) -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(DefaultPredicate::new().and(SizeAbove::new(min_bytes)))
+}
+
+/// `routes` behind `compression_layer`, with their body bytes recorded in
+/// the state's `CompressionMetrics` before and after it
+pub fn compressed(routes: Router<AppState>, state: &AppState) -> Router<AppState> {
+    let metrics = state.compression_metrics.clone();
+    routes
+        .layer(middleware::from_fn_with_state(
+            metrics.clone(),
+            count_uncompressed,
+        ))
+        .layer(compression_layer(state.config.compression_min_bytes))
+        .layer(middleware::from_fn_with_state(metrics, count_sent))
+}
+
+#[derive(Default)]
+struct RouteBytes {
+    uncompressed: Arc<AtomicU64>,
+    sent: Arc<AtomicU64>,
+}
+
+/// Response body bytes of compressed routes since startup, by matched route:
+/// as the handler produced them, and as sent after the encoder. Responses the
+/// encoder leaves alone count the same on both sides.
+#[derive(Default)]
+pub struct CompressionMetrics {
+    routes: Mutex<BTreeMap<String, RouteBytes>>,
+}
+
+impl CompressionMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn counter(&self, route: &str, pick: fn(&RouteBytes) -> &Arc<AtomicU64>) -> Arc<AtomicU64> {
+        let mut routes = self.routes.lock().unwrap();
+        pick(routes.entry(route.to_string()).or_default()).clone()
+    }
+
+    pub fn render_openmetrics(&self, out: &mut String) {
+        let routes = self.routes.lock().unwrap();
+        for (name, help, pick) in [
+            (
+                "compression_uncompressed_bytes",
+                "Response body bytes of compressed routes before compression",
+                (|bytes| &bytes.uncompressed) as fn(&RouteBytes) -> &Arc<AtomicU64>,
+            ),
+            (
+                "compression_sent_bytes",
+                "Response body bytes of compressed routes as sent",
+                |bytes| &bytes.sent,
+            ),
+        ] {
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            for (route, bytes) in routes.iter() {
+                let _ = writeln!(
+                    out,
+                    "{}_total{{route=\"{}\"}} {}",
+                    name,
+                    route,
+                    pick(bytes).load(Ordering::Relaxed)
+                );
+            }
+        }
+    }
+}
+
+async fn count_uncompressed(
+    State(metrics): State<Arc<CompressionMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    count_body(&metrics, |bytes| &bytes.uncompressed, request, next).await
+}
+
+async fn count_sent(
+    State(metrics): State<Arc<CompressionMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    count_body(&metrics, |bytes| &bytes.sent, request, next).await
+}
+
+async fn count_body(
+    metrics: &CompressionMetrics,
+    pick: fn(&RouteBytes) -> &Arc<AtomicU64>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request.extensions().get::<MatchedPath>().cloned();
+    let response = next.run(request).await;
+    let Some(route) = route else {
+        return response;
+    };
+
+    let counted = metrics.counter(route.as_str(), pick);
+    response.map(|inner| Body::new(CountingBody { inner, counted }))
+}
+
+/// Passes the body through untouched, size hint included so the encoder
+/// still sees small bodies as small, adding each data frame's length to
+/// `counted` as it goes out
+struct CountingBody {
+    inner: Body,
+    counted: Arc<AtomicU64>,
+}
+
+impl http_body::Body for CountingBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        let polled = Pin::new(&mut self.inner).poll_frame(cx);
+        if let Poll::Ready(Some(Ok(frame))) = &polled {
+            if let Some(data) = frame.data_ref() {
+                self.counted.fetch_add(data.len() as u64, Ordering::Relaxed);
+            }
+        }
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        let large = || async { "receipt ".repeat(1000) };
+        let small = || async { "ok" };
+
+        let compressed = Router::new()
+            .route("/api/receipts/large", get(large))
+            .route("/api/receipts/small", get(small))
+            .layer(compression_layer(1024));
+
+        Router::new()
+            .route("/api/pointer/resolve/large", get(large))
+            .merge(compressed)
+    }
+
+    async fn content_encoding(path: &str, accept: &str) -> Option<String> {
+        let response = app()
+            .oneshot(
+                Request::get(path)
+                    .header("accept-encoding", accept)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        response
+            .headers()
+            .get("content-encoding")
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_compresses_large_responses_by_accept_encoding() {
+        assert_eq!(
+            content_encoding("/api/receipts/large", "gzip")
+                .await
+                .as_deref(),
+            Some("gzip")
+        );
+        assert_eq!(
+            content_encoding("/api/receipts/large", "br")
+                .await
+                .as_deref(),
+            Some("br")
+        );
+        assert_eq!(
+            content_encoding("/api/receipts/large", "identity").await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skips_small_and_uncompressed_routes() {
+        assert_eq!(content_encoding("/api/receipts/small", "gzip").await, None);
+        assert_eq!(
+            content_encoding("/api/pointer/resolve/large", "gzip").await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_records_bytes_per_route_before_and_after_compression() {
+        let state = crate::test_support::offline_state();
+        let large = || async { "receipt ".repeat(1000) };
+        let small = || async { "ok" };
+        let app = compressed(
+            Router::new()
+                .route("/api/receipts/:id", get(large))
+                .route("/api/receipts/small/:id", get(small)),
+            &state,
+        )
+        .with_state(state.clone());
+
+        for path in [
+            "/api/receipts/a",
+            "/api/receipts/b",
+            "/api/receipts/small/c",
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get(path)
+                        .header("accept-encoding", "gzip")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+        }
+
+        let mut metrics = String::new();
+        state.compression_metrics.render_openmetrics(&mut metrics);
+        let sample = |name: &str, route: &str| -> u64 {
+            let prefix = format!("{}_total{{route=\"{}\"}} ", name, route);
+            metrics
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix.as_str()))
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+
+        // Both requests fold into the matched route
+        let route = "/api/receipts/:id";
+        assert_eq!(sample("compression_uncompressed_bytes", route), 16000);
+        let sent = sample("compression_sent_bytes", route);
+        assert!(sent > 0 && sent < 1000, "sent {}", sent);
+
+        // Left uncompressed, so the same on both sides
+        let route = "/api/receipts/small/:id";
+        assert_eq!(sample("compression_uncompressed_bytes", route), 2);
+        assert_eq!(sample("compression_sent_bytes", route), 2);
+    }
+}
@@ -0,0 +1,242 @@
+// Request context for audit logs
+// Who made a request and from where: the actor, the client IP and the user
+// agent. The actor is the X-Actor-Id header when an integrator names its own
+// user, otherwise the API key the request authenticated with. Behind a
+// trusted proxy the client IP is the last X-Forwarded-For entry, the one the
+// proxy appended; earlier entries are whatever the client sent, so they are
+// never used.
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{request::Parts, HeaderMap},
+};
+use std::net::{IpAddr, SocketAddr};
+
+use super::{auth::AuthContext, ApiError, AppState};
+
+/// Header naming the integrator's own user behind a request
+pub const ACTOR_ID_HEADER: &str = "x-actor-id";
+
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Longest actor id stored, the width of audit_log.actor_id
+const MAX_ACTOR_ID_BYTES: usize = 255;
+
+/// User agents are cut to this many bytes
+const MAX_USER_AGENT_BYTES: usize = 512;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestContext {
+    pub actor_id: Option<String>,
+    pub ip_address: Option<IpAddr>,
+    pub user_agent: Option<String>,
+}
+
+/// The client's address: the peer's, or behind a trusted proxy the last
+/// forwarded entry. A last entry that isn't an address falls back to the
+/// peer rather than trusting anything before it.
+pub fn client_ip(headers: &HeaderMap, peer: Option<IpAddr>, trusted_proxy: bool) -> Option<IpAddr> {
+    if !trusted_proxy {
+        return peer;
+    }
+
+    // Repeated headers are one list, in order
+    let last = headers
+        .get_all(FORWARDED_FOR_HEADER)
+        .iter()
+        .next_back()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next());
+
+    last.and_then(parse_forwarded_addr).or(peer)
+}
+
+/// An address as proxies write it: bare, with a port, or bracketed IPv6
+fn parse_forwarded_addr(entry: &str) -> Option<IpAddr> {
+    let entry = entry.trim();
+
+    entry
+        .parse::<IpAddr>()
+        .ok()
+        .or_else(|| entry.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+        .or_else(|| {
+            entry
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|ip| ip.parse().ok())
+        })
+}
+
+fn truncate(value: &str, max_bytes: usize) -> &str {
+    if value.len() <= max_bytes {
+        return value;
+    }
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for RequestContext {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, ApiError> {
+        let header_actor = match parts.headers.get(ACTOR_ID_HEADER) {
+            Some(value) => {
+                let actor = value.to_str().map(str::trim).map_err(|_| {
+                    ApiError::BadRequest("X-Actor-Id must be visible ASCII".to_string())
+                })?;
+                if actor.len() > MAX_ACTOR_ID_BYTES {
+                    return Err(ApiError::BadRequest(format!(
+                        "X-Actor-Id must be at most {} bytes",
+                        MAX_ACTOR_ID_BYTES
+                    )));
+                }
+                Some(actor.to_string()).filter(|actor| !actor.is_empty())
+            }
+            None => None,
+        };
+        let key_actor = parts
+            .extensions
+            .get::<AuthContext>()
+            .map(|auth| format!("api_key:{}", auth.key_id));
+
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+
+        let user_agent = parts
+            .headers
+            .get(axum::http::header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|agent| truncate(agent.trim(), MAX_USER_AGENT_BYTES).to_string())
+            .filter(|agent| !agent.is_empty());
+
+        Ok(RequestContext {
+            actor_id: header_actor.or(key_actor),
+            ip_address: client_ip(&parts.headers, peer, state.config.trusted_proxy),
+            user_agent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+    use axum::{
+        body::Body,
+        http::{HeaderValue, Request, StatusCode},
+        routing::get,
+        Json, Router,
+    };
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn forwarded(values: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(FORWARDED_FOR_HEADER, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn test_forwarded_for_uses_the_proxys_entry() {
+        let peer = ip("10.0.0.2");
+
+        // Ignored unless the proxy is trusted
+        let headers = forwarded(&["203.0.113.7"]);
+        assert_eq!(client_ip(&headers, peer, false), peer);
+        assert_eq!(client_ip(&headers, peer, true), ip("203.0.113.7"));
+
+        // The last entry is the one the proxy appended; earlier ones are
+        // whatever the client claimed
+        let headers = forwarded(&["1.1.1.1, 198.51.100.4 ,203.0.113.7"]);
+        assert_eq!(client_ip(&headers, peer, true), ip("203.0.113.7"));
+        let headers = forwarded(&["1.1.1.1", "198.51.100.4, 203.0.113.8"]);
+        assert_eq!(client_ip(&headers, peer, true), ip("203.0.113.8"));
+
+        // Ports and IPv6
+        let headers = forwarded(&["203.0.113.7:4711"]);
+        assert_eq!(client_ip(&headers, peer, true), ip("203.0.113.7"));
+        let headers = forwarded(&["2001:db8::1"]);
+        assert_eq!(client_ip(&headers, peer, true), ip("2001:db8::1"));
+        let headers = forwarded(&["[2001:db8::1]:443"]);
+        assert_eq!(client_ip(&headers, peer, true), ip("2001:db8::1"));
+        let headers = forwarded(&["[2001:db8::1]"]);
+        assert_eq!(client_ip(&headers, peer, true), ip("2001:db8::1"));
+    }
+
+    #[test]
+    fn test_invalid_forwarded_for_falls_back_to_the_peer() {
+        let peer = ip("10.0.0.2");
+
+        for value in ["unknown", "", "1.1.1.1, not-an-ip", "1.1.1.1,", "999.1.1.1"] {
+            let headers = forwarded(&[value]);
+            assert_eq!(client_ip(&headers, peer, true), peer, "{:?}", value);
+        }
+        assert_eq!(client_ip(&HeaderMap::new(), peer, true), peer);
+        assert_eq!(client_ip(&forwarded(&["garbage"]), None, true), None);
+    }
+
+    #[tokio::test]
+    async fn test_context_names_the_actor() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let app = Router::new()
+            .route(
+                "/whoami",
+                get(|ctx: RequestContext| async move { Json(ctx.actor_id) }),
+            )
+            .with_state(state.clone());
+        let key_id = Uuid::new_v4();
+
+        let actor = |header: Option<String>| {
+            let mut request = Request::builder().uri("/whoami");
+            if let Some(header) = header {
+                request = request.header(ACTOR_ID_HEADER, header);
+            }
+            let mut request = request.body(Body::empty()).unwrap();
+            request.extensions_mut().insert(AuthContext {
+                org_id: state.config.default_org_id,
+                key_id,
+            });
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (status, serde_json::from_slice::<Option<String>>(&body).ok())
+            }
+        };
+
+        let (status, named) = actor(Some(" user:alice ".to_string())).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(named, Some(Some("user:alice".to_string())));
+
+        // Without a usable header the API key is the actor
+        let by_key = Some(Some(format!("api_key:{}", key_id)));
+        assert_eq!(actor(None).await.1, by_key);
+        assert_eq!(actor(Some("  ".to_string())).await.1, by_key);
+
+        let (status, _) = actor(Some("x".repeat(256))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_truncate_keeps_char_boundaries() {
+        assert_eq!(truncate("curl/8.0", 512), "curl/8.0");
+        assert_eq!(truncate("ééé", 3), "é");
+    }
+}
@@ -0,0 +1,122 @@
+// Cross-origin access
+// CORS_ALLOWED_ORIGINS lists the exact origins browsers may call the API
+// from. A lone "*" opts back into any origin, for local development only.
+// Origins are checked at startup, so a typo fails fast instead of silently
+// locking the frontend out or letting everyone in.
+use anyhow::{bail, Context, Result};
+use axum::http::{HeaderValue, Uri};
+use std::str::FromStr;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CorsOrigins {
+    /// "*": any origin
+    Any,
+    /// Only these origins, matched exactly; none allows no cross-origin calls
+    Exact(Vec<HeaderValue>),
+}
+
+impl FromStr for CorsOrigins {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let entries: Vec<&str> = s
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .collect();
+
+        if entries.contains(&"*") {
+            if entries.len() > 1 {
+                bail!("'*' allows every origin and cannot be listed with others");
+            }
+            return Ok(CorsOrigins::Any);
+        }
+
+        let origins = entries
+            .into_iter()
+            .map(parse_origin)
+            .collect::<Result<_>>()?;
+        Ok(CorsOrigins::Exact(origins))
+    }
+}
+
+/// An origin is `scheme://host[:port]`, exactly as browsers send it: http or
+/// https, with no path, query or credentials
+fn parse_origin(origin: &str) -> Result<HeaderValue> {
+    let invalid = || format!("'{}' is not an origin (scheme://host[:port])", origin);
+    let uri: Uri = origin.parse().with_context(invalid)?;
+
+    let scheme_ok = matches!(uri.scheme_str(), Some("http") | Some("https"));
+    let authority_ok = uri.authority().is_some_and(|a| {
+        !a.host().is_empty()
+            && !a.as_str().contains('@')
+            // port() is None for an out-of-range port as well as no port
+            && (a.as_str() == a.host() || a.port_u16().is_some())
+    });
+    // Uri fills in "/" for a missing path, so look for one in the input
+    let has_path = origin
+        .split_once("://")
+        .is_some_and(|(_, rest)| rest.contains(['/', '?', '#']));
+    if !scheme_ok || !authority_ok || has_path {
+        bail!(invalid());
+    }
+
+    HeaderValue::from_str(origin).with_context(invalid)
+}
+
+/// CORS for the whole API. Any method and header are allowed; the origin is
+/// what gets checked.
+pub fn cors_layer(origins: &CorsOrigins) -> CorsLayer {
+    let layer = CorsLayer::new().allow_methods(Any).allow_headers(Any);
+
+    match origins {
+        CorsOrigins::Any => layer.allow_origin(Any),
+        CorsOrigins::Exact(origins) => layer.allow_origin(AllowOrigin::list(origins.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origins_parse() {
+        assert_eq!("*".parse::<CorsOrigins>().unwrap(), CorsOrigins::Any);
+        assert_eq!(
+            "https://app.example.com, http://localhost:3000,http://[::1]:8080"
+                .parse::<CorsOrigins>()
+                .unwrap(),
+            CorsOrigins::Exact(vec![
+                HeaderValue::from_static("https://app.example.com"),
+                HeaderValue::from_static("http://localhost:3000"),
+                HeaderValue::from_static("http://[::1]:8080"),
+            ])
+        );
+        assert_eq!(
+            "".parse::<CorsOrigins>().unwrap(),
+            CorsOrigins::Exact(vec![])
+        );
+    }
+
+    #[test]
+    fn test_invalid_origins_are_rejected() {
+        for origins in [
+            "app.example.com",
+            "ftp://app.example.com",
+            "https://app.example.com/",
+            "https://app.example.com/path",
+            "https://app.example.com?x=1",
+            "https://user@app.example.com",
+            "https://app.example.com:99999",
+            "https://app.example.com,*",
+            "https://",
+        ] {
+            assert!(
+                origins.parse::<CorsOrigins>().is_err(),
+                "{} should be rejected",
+                origins
+            );
+        }
+    }
+}
@@ -1,33 +1,487 @@
 // API request handlers
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
+use chrono::SubsecRound;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tracing::{info, error};
+use tracing::{error, info, warn, Instrument};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use super::{ApiError, AppState};
+use super::{
+    audit_export::{write_audit_export, AuditExportChunk, AuditExportFormat},
+    auth::AuthContext,
+    context::RequestContext,
+    idempotency::IdempotencyKey,
+    payload::{decode_payload, PayloadEncoding},
+    request_id::{record_group, record_pointer, record_subject},
+    validation::{
+        check_content_hash, check_justification, check_payload_size, check_reason,
+        check_subject_id, check_subject_key, FieldErrors,
+    },
+    ApiError, AppState, ErrorBody, GroupMemberFailure,
+};
 use crate::{
-    crypto::{ReceiptData, SignedReceipt},
+    analytics::{record_veto, render_openmetrics, VetoSloSummary, OPENMETRICS_CONTENT_TYPE},
+    auditor::{check_and_report, ConsistencyMismatch},
+    checkpoints::{inclusion_proof, InclusionProof},
+    crypto::{
+        check_client_field, encode_seed,
+        hashing::{is_sha3_512_hex, sha3_512_hash},
+        key_id, open_payload, overflow_hash, replace_key_file, resolve_overflow, seal_payload,
+        test_vectors::{generate_test_vectors, TestVectorFile},
+        verify_chain, verify_receipt, ChainVerification, GroupMember, KeySet, OrphanGroupManifest,
+        ReceiptData, ReceiptFailure, SignedReceipt, VerificationResult,
+    },
     db::{
-        models::{PointerStatus, ReceiptOperation},
-        queries::*,
+        constraints::constraint_violation,
+        fetch_pointer_snapshot,
+        models::{
+            AuditFilter, AuditLog, DataStore, GovernanceReceipt, IdempotencyRecord, OrgStats,
+            OrphanRequest, Pagination, Pointer, PointerChainState, PointerFilter,
+            PointerRestriction, PointerStatus, ReceiptCheckpoint, ReceiptOperation,
+            RetentionExpiration, SigningKey, Webhook,
+        },
+        queries::{self, *},
+        PointerSnapshot, SingleFlightStats,
     },
-    enforcement::enforce_pointer_access,
+    enforcement::{
+        clear_chain_freeze_with_receipt, enforce_pointer_access, freeze_chain_with_receipt,
+        restricted_until, DegradedResolve, PointerAccess,
+    },
+    heartbeats::{interval_floor, verify_heartbeats, HeartbeatVerification},
+    keys::{
+        key_is_valid, key_valid_until, known_key, receipt_keys, revoke_api_key_with_receipt,
+        rotate_api_key_with_receipt, rotate_signing_key, REVOKED_ON_REQUEST,
+    },
+    orphans::{purge_payload_with_receipt, purge_window, DEFAULT_ORPHAN_REASON},
+    restrictions::lift_with_receipt,
+    subjects::{
+        find_duplicate_subjects, merge_subject_group, write_subject_export, ExportChunk,
+        ExportFormat, SubjectExport, SubjectRules, MAX_SUBJECT_BYTES,
+    },
+    webhooks::{enqueue_webhook_event, generate_webhook_secret, WEBHOOK_EVENTS},
+};
+
+// Bodies shared with the client crate, so both serialize the same shapes
+pub use veto_types::{
+    AuditEventSummary, AuditTrailQuery, CreatePointerRequest, CreatePointerResponse,
+    GetAuditTrailResponse, GetReceiptsResponse, Jwk, OrphanPointerRequest, OrphanPointerResponse,
+    PublicKeysQuery, PublicKeysResponse, ReceiptEntry, ReceiptInfo, ReceiptSummary,
+    ReceiptTombstone, ReceiptsQuery, ResolveMode, ResolvePointerResponse, ResolveQuery,
+    RestrictionSummary,
 };
 
 // ============================================================================
 // HEALTH CHECK
 // ============================================================================
 
-pub async fn health_check() -> Json<serde_json::Value> {
-    Json(json!({
-        "status": "healthy",
-        "service": "veto-frontier-backend",
-        "version": env!("CARGO_PKG_VERSION"),
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HealthQuery {
+    /// Run the readiness checks, as /health/ready does
+    #[serde(default)]
+    pub deep: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ComponentHealth {
+    /// ok or error
+    pub status: String,
+    pub latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessChecks {
+    pub database: ComponentHealth,
+    pub signing: ComponentHealth,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LivenessResponse {
+    /// Always healthy
+    pub status: String,
+    pub service: String,
+    pub version: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    /// healthy, or unhealthy when any check failed
+    pub status: String,
+    pub service: String,
+    pub version: String,
+    /// Latest migration applied to the database; absent when it can't be
+    /// read or the database was never migrated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<i64>,
+    pub checks: ReadinessChecks,
+}
+
+fn liveness() -> Json<LivenessResponse> {
+    Json(LivenessResponse {
+        status: "healthy".to_string(),
+        service: "veto-frontier-backend".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+/// Liveness, or readiness with `?deep=true`
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    params(HealthQuery),
+    security(()),
+    responses(
+        (status = 200, description = "Live, or ready with `deep`", body = LivenessResponse),
+        (status = 503, description = "A readiness check failed", body = ReadinessResponse),
+    )
+)]
+pub async fn health_check(
+    State(state): State<AppState>,
+    Query(query): Query<HealthQuery>,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    if query.deep {
+        health_ready(State(state)).await.into_response()
+    } else {
+        liveness().into_response()
+    }
+}
+
+/// Liveness: the process is up and serving. Never touches the database, so
+/// an outage doesn't get healthy instances restarted.
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    security(()),
+    responses((status = 200, description = "The process is serving", body = LivenessResponse))
+)]
+pub async fn health_live() -> Json<LivenessResponse> {
+    liveness()
+}
+
+/// Readiness: the database answers within HEALTH_DB_TIMEOUT_MS and the
+/// signing key round-trips a signature. 503 when either fails, so load
+/// balancers stop routing to the instance.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    security(()),
+    responses(
+        (status = 200, description = "Every check passed", body = ReadinessResponse),
+        (status = 503, description = "A check failed", body = ReadinessResponse),
+    )
+)]
+pub async fn health_ready(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    let ((database, schema_version), signing) = tokio::join!(check_database(&state), async {
+        check_signing(&state.keypair())
+    });
+    let healthy = database.status == "ok" && signing.status == "ok";
+    if !healthy {
+        warn!(
+            database_error = ?database.error,
+            signing_error = ?signing.error,
+            "Readiness check failed"
+        );
+    }
+
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(ReadinessResponse {
+            status: if healthy { "healthy" } else { "unhealthy" }.to_string(),
+            service: "veto-frontier-backend".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version,
+            checks: ReadinessChecks { database, signing },
+        }),
+    )
+}
+
+async fn check_database(state: &AppState) -> (ComponentHealth, Option<i64>) {
+    let started = std::time::Instant::now();
+    let timeout_ms = state.config.health_db_timeout_ms;
+    let checked = tokio::time::timeout(std::time::Duration::from_millis(timeout_ms), async {
+        ping(&state.db.writer).await?;
+        crate::db::migrations::applied_version(&state.db.writer).await
+    })
+    .await;
+
+    let (result, schema_version) = match checked {
+        Ok(Ok(version)) => (Ok(()), version),
+        Ok(Err(e)) => (
+            Err(crate::secret::redact_credentials(&format!("{:#}", e))),
+            None,
+        ),
+        Err(_) => (Err(format!("timed out after {}ms", timeout_ms)), None),
+    };
+    (component_health(started, result), schema_version)
+}
+
+fn check_signing(keypair: &crate::crypto::Ed25519Keypair) -> ComponentHealth {
+    let started = std::time::Instant::now();
+    let message = format!("readiness {}", Uuid::new_v4());
+    let signature = keypair.sign(message.as_bytes());
+
+    let result = if keypair.verify(message.as_bytes(), &signature) {
+        Ok(())
+    } else {
+        Err("signature did not verify with the public key".to_string())
+    };
+    component_health(started, result)
+}
+
+fn component_health(started: std::time::Instant, result: Result<(), String>) -> ComponentHealth {
+    ComponentHealth {
+        status: if result.is_ok() { "ok" } else { "error" }.to_string(),
+        latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+        error: result.err(),
+    }
+}
+
+// ============================================================================
+// RECEIPT TEST VECTORS
+// ============================================================================
+
+/// Canonicalization and signing test vectors for partner verifiers, signed
+/// with the published test key rather than the service key
+#[utoipa::path(
+    get,
+    path = "/api/meta/test-vectors",
+    tag = "keys",
+    security(()),
+    responses((status = 200, description = "The test vectors", body = TestVectorFile))
+)]
+pub async fn get_test_vectors() -> Result<Json<TestVectorFile>, ApiError> {
+    Ok(Json(generate_test_vectors()?))
+}
+
+// ============================================================================
+// PUBLIC KEYS
+// ============================================================================
+
+fn jwk(key_id: &str, public_key: &[u8; 32]) -> Jwk {
+    Jwk {
+        kty: "OKP".to_string(),
+        crv: "Ed25519".to_string(),
+        x: data_encoding::BASE64URL_NOPAD.encode(public_key),
+        kid: key_id.to_string(),
+        alg: "EdDSA".to_string(),
+        key_use: "sig".to_string(),
+    }
+}
+
+/// The signing key first, then every service key that has signed receipts
+fn verification_jwks(state: &AppState, key_id: &str, public_key: &[u8; 32]) -> Vec<Jwk> {
+    let known = state.signing_keys.key_set();
+    let mut keys = vec![jwk(key_id, public_key)];
+    keys.extend(
+        known
+            .keys()
+            .into_iter()
+            .filter(|(id, _)| *id != key_id)
+            .map(|(id, key)| jwk(id, key.as_bytes())),
+    );
+    keys
+}
+
+/// The key receipts are signed with, first, then every key it replaced.
+/// With `org_id`, the key first is that org's, followed by the service keys
+/// that sign its receipts from background jobs. Receipt signatures are over
+/// the receipt_hash string's bytes.
+#[utoipa::path(
+    get,
+    path = "/api/keys/public",
+    tag = "keys",
+    params(PublicKeysQuery),
+    security(()),
+    responses(
+        (status = 200, description = "The verification keys", body = PublicKeysResponse),
+        (status = 404, description = "Unknown org_id", body = ErrorBody),
+    )
+)]
+pub async fn get_public_keys(
+    State(state): State<AppState>,
+    Query(query): Query<PublicKeysQuery>,
+) -> Result<Json<PublicKeysResponse>, ApiError> {
+    let active = match query.org_id {
+        Some(org_id) => {
+            if get_organization(&state.db.reader, org_id).await?.is_none() {
+                return Err(ApiError::NotFound(format!(
+                    "Organization {} not found",
+                    org_id
+                )));
+            }
+            state.signer(org_id).await?
+        }
+        None => state.keypair(),
+    };
+    let public_key = active.public_key_bytes();
+    let key_id = active.key_id();
+
+    Ok(Json(PublicKeysResponse {
+        keys: verification_jwks(&state, &key_id, &public_key),
+        key_id,
+        algorithm: "ED25519".to_string(),
+        public_key_base64: data_encoding::BASE64.encode(&public_key),
+        public_key_hex: data_encoding::HEXLOWER.encode(&public_key),
+    }))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SigningKeySummary {
+    pub key_id: String,
+    #[schema(format = Byte)]
+    pub public_key_base64: String,
+    /// Signs new receipts; every other key only verifies
+    pub active: bool,
+    #[schema(format = DateTime)]
+    pub created_at: String,
+    #[schema(format = DateTime)]
+    pub retired_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListSigningKeysResponse {
+    pub keys: Vec<SigningKeySummary>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RotateSigningKeyResponse {
+    pub key: SigningKeySummary,
+    /// The key it replaced, which keeps verifying the receipts it signed
+    pub retired: Option<SigningKeySummary>,
+    /// Whether the new key was written to SIGNING_KEY_PATH
+    pub persisted: bool,
+    /// Seed of the new key when it was not written to a key file. Set it
+    /// as SIGNING_PRIVATE_KEY before the next restart; the retired key is
+    /// refused at startup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_private_key: Option<String>,
+}
+
+impl SigningKeySummary {
+    fn new(key: SigningKey, active_key_id: &str) -> Self {
+        Self {
+            active: key.key_id == active_key_id,
+            public_key_base64: data_encoding::BASE64.encode(&key.public_key),
+            created_at: key.created_at.to_rfc3339(),
+            retired_at: key.retired_at.map(|t| t.to_rfc3339()),
+            key_id: key.key_id,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/signing-keys",
+    tag = "keys",
+    responses(
+        (status = 200, description = "Every receipt signing key", body = ListSigningKeysResponse),
+        (status = 403, description = "Not the operator org", body = ErrorBody),
+    )
+)]
+pub async fn list_signing_keys(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<Json<ListSigningKeysResponse>, ApiError> {
+    authorize_operator(&state, &auth)?;
+    let active_key_id = state.keypair().key_id();
+
+    let keys = queries::list_signing_keys(&state.db.reader)
+        .await?
+        .into_iter()
+        .map(|key| SigningKeySummary::new(key, &active_key_id))
+        .collect();
+
+    Ok(Json(ListSigningKeysResponse { keys }))
+}
+
+/// Replace the receipt signing key. The old key is retired: it signs nothing
+/// more but still verifies what it signed.
+#[utoipa::path(
+    post,
+    path = "/api/admin/signing-keys/rotate",
+    tag = "keys",
+    responses(
+        (status = 200, description = "The new key and the one it retired", body = RotateSigningKeyResponse),
+        (status = 403, description = "Not the operator org", body = ErrorBody),
+    )
+)]
+pub async fn rotate_receipt_signing_key(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+) -> Result<Json<RotateSigningKeyResponse>, ApiError> {
+    authorize_operator(&state, &auth)?;
+    info!(key_id = %state.keypair().key_id(), "Rotating receipt signing key");
+
+    let now = state.clock.now();
+    let key_path = state
+        .config
+        .signing_key_path
+        .as_deref()
+        .map(std::path::Path::new);
+
+    let mut tx = begin_write(&state).await?;
+    let rotated = rotate_signing_key(&mut tx, &state.signing_keys, now).await?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(auth.org_id),
+        None,
+        None,
+        "signing_key_rotated",
+        json!({
+            "key_id": rotated.key.key_id,
+            "retired_key_id": rotated.retired.as_ref().map(|k| &k.key_id),
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+    tx.commit().await?;
+
+    // Committed: the file only ever holds a key the table records. Should
+    // the write fail, the seed is returned to be configured instead.
+    let persisted = match key_path {
+        Some(path) => match replace_key_file(path, &rotated.keypair) {
+            Ok(()) => true,
+            Err(e) => {
+                error!(error = %format!("{:#}", e), "Rotated signing key not persisted");
+                false
+            }
+        },
+        None => false,
+    };
+
+    // Sign with the new key from here on
+    let new_key_id = rotated.key.key_id.clone();
+    state.signing_keys.activate(rotated.keypair.clone());
+    info!(key_id = %new_key_id, "Receipt signing key rotated");
+
+    Ok(Json(RotateSigningKeyResponse {
+        key: SigningKeySummary::new(rotated.key, &new_key_id),
+        retired: rotated
+            .retired
+            .map(|key| SigningKeySummary::new(key, &new_key_id)),
+        persisted,
+        signing_private_key: (!persisted).then(|| encode_seed(&rotated.keypair)),
     }))
 }
 
@@ -35,208 +489,958 @@ pub async fn health_check() -> Json<serde_json::Value> {
 // CREATE POINTER
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
-pub struct CreatePointerRequest {
-    pub subject_id: String,
-    pub content_hash: String,
-    #[serde(default)]
-    pub encrypted_payload: Option<String>, // Base64 encoded
+/// Views spanning every org, and the receipt signing keys, which are the
+/// service's, are only for the operator org (the default org)
+fn authorize_operator(state: &AppState, auth: &AuthContext) -> Result<(), ApiError> {
+    auth.authorize(state.config.default_org_id)
 }
 
-#[derive(Debug, Serialize)]
-pub struct CreatePointerResponse {
-    pub pointer_id: Uuid,
-    pub data_id: Uuid,
-    pub status: String,
-    pub receipt: ReceiptInfo,
+/// Begin a write transaction. Writes never degrade: while the database
+/// circuit is open they fail fast with 503 rather than wait on the pool.
+async fn begin_write(
+    state: &AppState,
+) -> Result<sqlx::Transaction<'static, sqlx::Postgres>, ApiError> {
+    let unavailable =
+        || ApiError::ServiceUnavailable("database_unavailable: Try again shortly".to_string());
+    if state.db_circuit.is_open() {
+        return Err(unavailable());
+    }
+
+    state
+        .db_circuit
+        .observe(state.db.writer.begin().await.map_err(Into::into))?
+        .ok_or_else(unavailable)
 }
 
-#[derive(Debug, Serialize)]
-pub struct ReceiptInfo {
-    pub receipt_hash: String,
-    pub signature: String, // Base64 encoded
-    pub signature_algorithm: String,
-    pub timestamp: String,
+/// Sign a pointer receipt on the request path with its org's key. Metadata
+/// that would take the receipt past the size limit is moved to an overflow
+/// record.
+async fn sign_receipt(
+    state: &AppState,
+    org_id: Uuid,
+    receipt_data: &mut ReceiptData,
+) -> Result<SignedReceipt, ApiError> {
+    let keypair = state.signer(org_id).await?;
+    receipt_data.key_id = Some(keypair.key_id());
+    let overflow = receipt_data.bound(state.config.receipt_max_bytes)?;
+
+    Ok(SignedReceipt {
+        overflow,
+        ..state.receipt_metrics.sign(receipt_data, &keypair)?
+    })
+}
+
+/// Refuse a client-supplied field too large for the receipts it goes into
+fn check_receipt_field(state: &AppState, name: &str, value: &str) -> Result<(), ApiError> {
+    check_client_field(name, value, state.config.receipt_max_bytes)
+        .map_err(|e| ApiError::BadRequest(format!("receipt_too_large: {}", e)))
+}
+
+/// Store a pointer receipt and any overflow record inside the caller's
+/// transaction, recording its phase timings when it was signed with them
+async fn store_receipt(
+    state: &AppState,
+    conn: &mut sqlx::PgConnection,
+    pointer_id: Uuid,
+    org_id: Uuid,
+    operation: ReceiptOperation,
+    signed_receipt: &SignedReceipt,
+    prev_hash: Option<&str>,
+) -> Result<(), ApiError> {
+    let started = std::time::Instant::now();
+    if let Some(overflow) = &signed_receipt.overflow {
+        create_receipt_overflow(&mut *conn, &overflow.overflow_hash, &overflow.content).await?;
+    }
+    create_governance_receipt(
+        conn,
+        pointer_id,
+        org_id,
+        operation,
+        signed_receipt.receipt_json.clone(),
+        &signed_receipt.receipt_hash,
+        &signed_receipt.signature,
+        &signed_receipt.signature_algorithm,
+        &signed_receipt.key_id,
+        prev_hash,
+    )
+    .await?;
+
+    state
+        .receipt_metrics
+        .record(pointer_id, signed_receipt, started.elapsed());
+    Ok(())
 }
 
+/// Create a pointer. With an Idempotency-Key, a replay of an earlier create
+/// returns its response with 200 instead of creating anything.
+#[utoipa::path(
+    post,
+    path = "/api/pointer/create",
+    tag = "pointers",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Makes retries of this create safe"),
+    ),
+    request_body = CreatePointerRequest,
+    responses(
+        (status = 201, description = "Pointer created", body = CreatePointerResponse),
+        (status = 200, description = "Replay of an earlier create with the same Idempotency-Key", body = CreatePointerResponse),
+        (status = 400, description = "Undecodable payload, content_hash mismatch or bad retention", body = ErrorBody),
+        (status = 409, description = "external_ref already used", body = ErrorBody),
+        (status = 422, description = "Validation failed, or Idempotency-Key reused with another request", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
 pub async fn create_pointer(
     State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    idempotency: IdempotencyKey,
     Json(req): Json<CreatePointerRequest>,
 ) -> Result<(StatusCode, Json<CreatePointerResponse>), ApiError> {
-    info!("Creating pointer for subject: {}", req.subject_id);
-
-    let org_id = state.config.default_org_id;
+    let mut errors = FieldErrors::default();
+    // The length limit applies to the id as stored, so long ids under the
+    // hard cap are digested rather than refused
+    check_subject_id(
+        &mut errors,
+        "subject_id",
+        &req.subject_id,
+        MAX_SUBJECT_BYTES,
+    );
+    let subject = SubjectRules::from_config(&state.config).normalize(&req.subject_id);
+    check_subject_key(
+        &mut errors,
+        "subject_id",
+        &subject.subject_id,
+        state.config.subject_max_bytes,
+    );
+    check_content_hash(&mut errors, "content_hash", &req.content_hash);
+    if let Some(external_ref) = &req.external_ref {
+        check_external_ref(&mut errors, external_ref);
+    }
 
     // Decode payload if provided
-    let payload_bytes = if let Some(ref payload_base64) = req.encrypted_payload {
-        Some(
-            data_encoding::BASE64
-                .decode(payload_base64.as_bytes())
-                .map_err(|e| ApiError::BadRequest(format!("Invalid base64 payload: {}", e)))?,
-        )
-    } else {
-        None
+    let payload = match req.encrypted_payload {
+        Some(ref encoded) => Some(
+            decode_payload(encoded, req.payload_encoding)
+                .map_err(|e| ApiError::BadRequest(e.to_string()))?,
+        ),
+        None => None,
     };
+    if let Some(payload) = &payload {
+        check_payload_size(
+            &mut errors,
+            "encrypted_payload",
+            payload.bytes.len(),
+            state.config.max_payload_bytes,
+        );
+    }
+    errors.into_result()?;
+
+    record_subject(&subject.subject_id);
+    info!("Creating pointer");
+
+    let org_id = auth.org_id;
+    let payload_encoding = payload.as_ref().map(|p| p.encoding.as_str());
+
+    // With the bytes in hand the hash is checked, not just taken on trust
+    if let Some(payload) = &payload {
+        if sha3_512_hash(&payload.bytes) != req.content_hash {
+            return Err(ApiError::BadRequest(
+                "content_hash does not match the SHA3-512 of encrypted_payload".to_string(),
+            ));
+        }
+    }
+    let content_hash_verified = payload.is_some();
+
+    let retain_until = state
+        .config
+        .retention_classes
+        .deadline(
+            req.retention_class.as_deref(),
+            req.expires_at,
+            state.clock.now(),
+        )
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    // All rows commit together, so a failure can never leave data without a
+    // pointer or a pointer without its create receipt
+    let mut tx = begin_write(&state).await?;
+
+    if let Some(key) = &idempotency.0 {
+        let now = state.clock.now();
+        let expires_at = now + chrono::Duration::seconds(state.config.idempotency_key_ttl_seconds);
+        if let Some(earlier) =
+            claim_idempotency_key(&mut tx, org_id, key, &req.content_hash, now, expires_at).await?
+        {
+            return replay_create(earlier, &req.content_hash);
+        }
+    }
+
+    // Over-long ids are stored once; everything else refers to the digest key
+    if let Some(full_value) = &subject.full_value {
+        upsert_subject_value(&mut *tx, &subject.subject_id, full_value).await?;
+    }
 
-    // 1. Store data
+    // 1. Store data, encrypted at rest when the server holds a payload key
+    let (stored_payload, data_metadata) = seal_payload(
+        state.payload_cipher.as_deref(),
+        org_id,
+        &req.content_hash,
+        payload.as_ref().map(|p| p.bytes.as_slice()),
+    )?;
     let data = create_data_store(
-        &state.db_pool,
+        &mut *tx,
         org_id,
-        &req.subject_id,
+        &subject.subject_id,
         &req.content_hash,
-        payload_bytes.as_deref(),
+        stored_payload.as_deref(),
+        &data_metadata,
     )
     .await?;
+    if retain_until.is_some() {
+        set_data_store_retention(
+            &mut *tx,
+            data.data_id,
+            req.retention_class.as_deref(),
+            retain_until,
+        )
+        .await?;
+    }
 
-    info!("Created data_store entry: {}", data.data_id);
+    info!(data_id = %data.data_id, "Created data_store entry");
 
-    // 2. Create pointer
-    let pointer = create_pointer(
-        &state.db_pool,
+    // 2. Create pointer; a taken external_ref answers with its pointer so the
+    // client can recover
+    let pointer = match queries::create_pointer(
+        &mut *tx,
         org_id,
         data.data_id,
-        &req.subject_id,
+        &subject.subject_id,
+        req.external_ref.as_deref(),
+        req.requires_dual_approval,
     )
-    .await?;
+    .await
+    {
+        Err(e) if is_external_ref_taken(&e) => {
+            drop(tx);
+            let external_ref = req.external_ref.as_deref().unwrap_or_default();
+            return Err(
+                match get_pointer_by_external_ref(&state.db.writer, org_id, external_ref).await? {
+                    Some(existing) => ApiError::ExternalRefExists {
+                        pointer_id: existing.pointer_id,
+                    },
+                    None => e.into(),
+                },
+            );
+        }
+        created => created?,
+    };
 
-    info!("Created pointer: {}", pointer.pointer_id);
+    record_pointer(&pointer.pointer_id);
+    info!("Created pointer");
 
     // 3. Generate signed receipt
-    let receipt_data = ReceiptData::new(
+    let mut metadata = json!({
+        "content_hash": req.content_hash,
+        "content_hash_verified": content_hash_verified,
+        "payload_encoding": payload_encoding,
+        "subject_id_normalized": subject.was_normalized(),
+        "subject_id_raw_hash": subject.raw_hash,
+    });
+    if let Some(preview) = subject.preview() {
+        metadata["subject_id_preview"] = preview.into();
+    }
+    if let Some(retain_until) = retain_until {
+        metadata["retention_class"] = json!(req.retention_class);
+        metadata["retain_until"] = json!(retain_until);
+    }
+    if pointer.requires_dual_approval {
+        metadata["requires_dual_approval"] = json!(true);
+    }
+
+    let mut receipt_data = ReceiptData::new(
         pointer.pointer_id,
         ReceiptOperation::Create,
-        req.subject_id.clone(),
+        subject.subject_id.clone(),
         None, // First receipt, no previous hash
-        json!({"content_hash": req.content_hash}),
+        metadata,
+        state.clock.now(),
     );
 
-    let signed_receipt = receipt_data.sign(&state.keypair)?;
+    let signed_receipt = sign_receipt(&state, org_id, &mut receipt_data).await?;
 
-    // 4. Store receipt in database
-    create_governance_receipt(
-        &state.db_pool,
+    // 4. Store receipt and audit log
+    store_receipt(
+        &state,
+        &mut tx,
         pointer.pointer_id,
         org_id,
         ReceiptOperation::Create,
-        signed_receipt.receipt_json.clone(),
-        &signed_receipt.receipt_hash,
-        &signed_receipt.signature,
-        &signed_receipt.signature_algorithm,
+        &signed_receipt,
         None,
     )
     .await?;
 
-    info!("Created governance receipt for pointer: {}", pointer.pointer_id);
-
-    // 5. Create audit log
     create_audit_log(
-        &state.db_pool,
+        &mut *tx,
         Some(org_id),
         Some(pointer.pointer_id),
         None,
         "pointer_created",
         json!({
-            "subject_id": req.subject_id,
+            "subject_id": subject.subject_id,
+            "subject_id_raw_hash": subject.raw_hash,
             "content_hash": req.content_hash,
+            "payload_encoding": payload_encoding,
+            "external_ref": req.external_ref,
+            "requires_dual_approval": pointer.requires_dual_approval,
         }),
-        None,
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
     )
     .await?;
 
-    Ok((
-        StatusCode::CREATED,
-        Json(CreatePointerResponse {
-            pointer_id: pointer.pointer_id,
-            data_id: data.data_id,
-            status: "active".to_string(),
-            receipt: ReceiptInfo {
-                receipt_hash: signed_receipt.receipt_hash,
-                signature: data_encoding::BASE64.encode(&signed_receipt.signature),
-                signature_algorithm: signed_receipt.signature_algorithm,
-                timestamp: pointer.created_at.to_rfc3339(),
-            },
-        }),
-    ))
+    let response = CreatePointerResponse {
+        pointer_id: pointer.pointer_id,
+        data_id: data.data_id,
+        status: "active".to_string(),
+        external_ref: pointer.external_ref.clone(),
+        retain_until: retain_until.map(|at| at.to_rfc3339()),
+        requires_dual_approval: pointer.requires_dual_approval,
+        receipt: ReceiptInfo {
+            receipt_hash: signed_receipt.receipt_hash,
+            signature: data_encoding::BASE64.encode(&signed_receipt.signature),
+            signature_algorithm: signed_receipt.signature_algorithm,
+            key_id: signed_receipt.key_id,
+            timestamp: pointer.created_at.to_rfc3339(),
+        },
+    };
+    if let Some(key) = &idempotency.0 {
+        let response_json = serde_json::to_value(&response)
+            .map_err(|e| ApiError::Internal(format!("Failed to store response: {}", e)))?;
+        complete_idempotency_key(&mut *tx, org_id, key, pointer.pointer_id, response_json).await?;
+    }
+
+    tx.commit().await?;
+
+    info!("Created governance receipt");
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Longest external_ref accepted, the width of its column
+const MAX_EXTERNAL_REF_BYTES: usize = 255;
+
+fn check_external_ref(errors: &mut FieldErrors, external_ref: &str) {
+    if external_ref.trim().is_empty() {
+        errors.add("external_ref", "must not be empty");
+    } else if external_ref.len() > MAX_EXTERNAL_REF_BYTES {
+        errors.add(
+            "external_ref",
+            format!("must be at most {} bytes", MAX_EXTERNAL_REF_BYTES),
+        );
+    }
+}
+
+fn is_external_ref_taken(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<sqlx::Error>()
+        .and_then(constraint_violation)
+        .is_some_and(|v| v.constraint() == "idx_pointers_org_external_ref")
+}
+
+/// Answer a replayed create with the original response, unless the replay
+/// carries different content
+fn replay_create(
+    earlier: IdempotencyRecord,
+    content_hash: &str,
+) -> Result<(StatusCode, Json<CreatePointerResponse>), ApiError> {
+    if earlier.content_hash != content_hash {
+        return Err(ApiError::IdempotencyKeyReused(format!(
+            "idempotency_key_reused: Idempotency-Key {} was used for different content",
+            earlier.idempotency_key
+        )));
+    }
+
+    let response = earlier
+        .response_json
+        .and_then(|json| serde_json::from_value(json).ok())
+        .ok_or_else(|| {
+            ApiError::Internal(format!(
+                "Idempotency-Key {} has no stored response",
+                earlier.idempotency_key
+            ))
+        })?;
+    info!(idempotency_key = %earlier.idempotency_key, "Replayed create");
+
+    Ok((StatusCode::OK, Json(response)))
 }
 
 // ============================================================================
 // RESOLVE POINTER
 // ============================================================================
 
-#[derive(Debug, Serialize)]
-pub struct ResolvePointerResponse {
-    pub pointer_id: Uuid,
-    pub data_id: Uuid,
-    pub subject_id: String,
-    pub content_hash: String,
-    pub status: String,
-    pub created_at: String,
-    pub receipt: ReceiptInfo,
-}
-
+#[utoipa::path(
+    get,
+    path = "/api/pointer/resolve/{id}",
+    tag = "pointers",
+    params(("id" = Uuid, Path, description = "Pointer id"), ResolveQuery),
+    responses(
+        (status = 200, description = "Pointer active, or orphaned within its grace window", body = ResolvePointerResponse),
+        (status = 400, description = "include_payload on a readonly resolve", body = ErrorBody),
+        (status = 403, description = "Orphaned (`pointer_orphaned`), restricted, or another org's", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 503, description = "Database unavailable and no last known status", body = ErrorBody),
+    )
+)]
 pub async fn resolve_pointer(
     State(state): State<AppState>,
+    auth: AuthContext,
     Path(pointer_id): Path<Uuid>,
+    Query(query): Query<ResolveQuery>,
 ) -> Result<Json<ResolvePointerResponse>, ApiError> {
-    info!("Resolving pointer: {}", pointer_id);
+    record_pointer(&pointer_id);
+    info!("Resolving pointer");
 
-    // 1. Get pointer
-    let pointer = get_pointer(&state.db_pool, pointer_id)
+    if query.mode == ResolveMode::Readonly && query.include_payload {
+        return Err(ApiError::BadRequest(
+            "include_payload requires an attested resolve".to_string(),
+        ));
+    }
+
+    // 1. Get pointer, any processing restriction and its data's content hash,
+    // from the cache or shared with concurrent resolves of this pointer. With
+    // the database unreachable, answer from last known status instead.
+    if state.db_circuit.is_open() {
+        return resolve_degraded(&state, auth, pointer_id, query.mode);
+    }
+    let snapshot = match state.pointer_cache.get(&pointer_id) {
+        Some(snapshot) => snapshot,
+        None => {
+            let ticket = state.pointer_cache.ticket();
+            let fetched = state
+                .resolve_flights
+                .run(pointer_id, || {
+                    fetch_pointer_snapshot(&state.db.writer, pointer_id)
+                })
+                .await;
+            let Some(snapshot) = state.db_circuit.observe(fetched)? else {
+                return resolve_degraded(&state, auth, pointer_id, query.mode);
+            };
+            state.pointer_cache.insert(ticket, &snapshot);
+            snapshot
+        }
+    };
+    let PointerSnapshot {
+        pointer,
+        restriction,
+        content_hash,
+    } = snapshot;
+    let pointer = pointer.ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    if query.mode == ResolveMode::Readonly {
+        return resolve_readonly(&state, pointer, restriction, content_hash);
+    }
+
+    // 2. Lock the pointer until the receipt is written: a veto can't land
+    // between the access decision and the receipt, and the receipt links to
+    // the chain head as of the lock
+    let begun = state.db.writer.begin().await.map_err(Into::into);
+    let Some(mut tx) = state.db_circuit.observe(begun)? else {
+        return resolve_degraded(&state, auth, pointer_id, query.mode);
+    };
+    let snapshot_data_id = pointer.data_id;
+    let pointer = lock_pointers_for_update(&mut *tx, &[pointer_id])
         .await?
+        .pop()
         .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
 
-    // 2. ENFORCE: Check if pointer is orphaned
-    enforce_pointer_access(&pointer)?;
+    // 3. ENFORCE: Check if pointer is orphaned (grace applies only then)
+    // or under a processing restriction
+    let grace_seconds = match pointer.status {
+        PointerStatus::Active => 0,
+        PointerStatus::Orphaned | PointerStatus::PendingOrphan => {
+            get_org_orphan_grace_seconds(&mut *tx, pointer.org_id).await?
+        }
+    };
+    let access = enforce_pointer_access(
+        &pointer,
+        chrono::Duration::seconds(grace_seconds),
+        state.clock.as_ref(),
+    )
+    .inspect_err(|_| state.last_known.record(&pointer, None, state.clock.now()))?;
+
+    // Restricting locks the pointer too, so the restriction is read under the
+    // lock rather than taken from the snapshot
+    let restriction = get_open_restriction(&mut *tx, pointer_id).await?;
+    if let Some(until) = restricted_until(restriction.as_ref(), state.clock.as_ref()) {
+        info!("Resolve denied, processing restricted");
+        return Err(ApiError::ProcessingRestricted {
+            restricted_until: until.to_rfc3339(),
+        });
+    }
 
-    // 3. Get associated data
-    let data = get_data_store(&state.db_pool, pointer.data_id)
-        .await?
-        .ok_or_else(|| ApiError::Internal("Data not found for pointer".to_string()))?;
+    // The payload is read under the lock, as is the data row after a data
+    // swap that landed since the snapshot
+    let (content_hash, data) = match content_hash {
+        Some(hash) if snapshot_data_id == pointer.data_id && !query.include_payload => (hash, None),
+        _ => {
+            let data = get_data_store(&mut *tx, pointer.data_id)
+                .await?
+                .ok_or_else(|| ApiError::Internal("Data not found for pointer".to_string()))?;
+            (data.content_hash.clone(), Some(data))
+        }
+    };
+    let prev_hash = get_latest_receipt_hash(&mut *tx, pointer_id).await?;
+
+    // The payload is only ever released on a granted resolve
+    let payload = match (&access, &data) {
+        (PointerAccess::Granted, Some(data)) if query.include_payload => {
+            open_stored_payload(&state, data)?
+        }
+        _ => None,
+    };
+
+    // 4. Generate resolve receipt, or a denial receipt inside the grace window
 
-    // 4. Generate resolve receipt
-    let prev_hash = get_latest_receipt_hash(&state.db_pool, pointer_id).await?;
+    let (operation, metadata, hard_denial_at) = match access {
+        PointerAccess::Granted => (
+            ReceiptOperation::Resolve,
+            json!({"data_id": pointer.data_id, "payload_released": payload.is_some()}),
+            None,
+        ),
+        PointerAccess::OrphanedGrace { hard_denial_at } => (
+            ReceiptOperation::Denied,
+            json!({
+                "data_id": pointer.data_id,
+                "reason": "pointer_orphaned",
+                "grace_disclosure": true,
+                "hard_denial_at": hard_denial_at.to_rfc3339(),
+            }),
+            Some(hard_denial_at),
+        ),
+    };
 
-    let receipt_data = ReceiptData::new(
+    let mut receipt_data = ReceiptData::new(
         pointer.pointer_id,
-        ReceiptOperation::Resolve,
+        operation.clone(),
         pointer.subject_id.clone(),
         prev_hash.clone(),
-        json!({"data_id": data.data_id}),
+        metadata,
+        state.clock.now(),
     );
 
-    let signed_receipt = receipt_data.sign(&state.keypair)?;
+    let signed_receipt = sign_receipt(&state, pointer.org_id, &mut receipt_data).await?;
 
-    // 5. Store resolve receipt
-    create_governance_receipt(
-        &state.db_pool,
+    // 5. Store receipt
+    store_receipt(
+        &state,
+        &mut tx,
         pointer.pointer_id,
         pointer.org_id,
-        ReceiptOperation::Resolve,
-        signed_receipt.receipt_json.clone(),
-        &signed_receipt.receipt_hash,
-        &signed_receipt.signature,
-        &signed_receipt.signature_algorithm,
+        operation,
+        &signed_receipt,
         prev_hash.as_deref(),
     )
     .await?;
 
-    info!("Resolved pointer successfully: {}", pointer_id);
+    tx.commit().await?;
+
+    // Only a grant leaves something a degraded resolve may repeat
+    let known_hash = matches!(access, PointerAccess::Granted).then_some(&content_hash);
+    state
+        .last_known
+        .record(&pointer, known_hash.map(String::as_str), state.clock.now());
+
+    let status = match hard_denial_at {
+        Some(_) => "orphaned_grace",
+        None => pointer.status.as_str(),
+    };
+    // Only a pending orphan still being served carries its effective time
+    let orphan_effective_at = pointer
+        .orphan_effective_at
+        .filter(|_| hard_denial_at.is_none())
+        .filter(|_| matches!(pointer.status, PointerStatus::PendingOrphan));
+
+    if hard_denial_at.is_some() {
+        info!("Disclosed orphaned pointer within grace");
+    } else {
+        info!("Resolved pointer successfully");
+    }
 
     Ok(Json(ResolvePointerResponse {
         pointer_id: pointer.pointer_id,
-        data_id: data.data_id,
+        data_id: pointer.data_id,
         subject_id: pointer.subject_id,
-        content_hash: data.content_hash,
-        status: match pointer.status {
-            PointerStatus::Active => "active".to_string(),
-            PointerStatus::Orphaned => "orphaned".to_string(),
-        },
+        content_hash,
+        status: status.to_string(),
+        created_at: pointer.created_at.to_rfc3339(),
+        receipt_generated: true,
+        receipt: Some(receipt_info(signed_receipt, receipt_data.timestamp)),
+        degraded: false,
+        last_known_at: None,
+        hard_denial_at: hard_denial_at.map(|at| at.to_rfc3339()),
+        orphan_effective_at: orphan_effective_at.map(|at| at.to_rfc3339()),
+        retry_after_seconds: hard_denial_at.map(|at| (at - state.clock.now()).num_seconds().max(0)),
+        encrypted_payload: payload.map(|bytes| data_encoding::BASE64.encode(&bytes)),
+    }))
+}
+
+/// Resolve by the integrator's external_ref, exactly as by pointer_id:
+/// the same enforcement, receipts and response
+#[utoipa::path(
+    get,
+    path = "/api/pointer/by-ref/{external_ref}",
+    tag = "pointers",
+    params(
+        ("external_ref" = String, Path, description = "The integrator's own id for the record"),
+        ResolveQuery,
+    ),
+    responses(
+        (status = 200, description = "Pointer active, or orphaned within its grace window", body = ResolvePointerResponse),
+        (status = 400, description = "include_payload on a readonly resolve", body = ErrorBody),
+        (status = 403, description = "Orphaned (`pointer_orphaned`), restricted, or another org's", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 503, description = "Database unavailable and no last known status", body = ErrorBody),
+    )
+)]
+pub async fn resolve_pointer_by_ref(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(external_ref): Path<String>,
+    query: Query<ResolveQuery>,
+) -> Result<Json<ResolvePointerResponse>, ApiError> {
+    // Last known statuses are kept by pointer_id only
+    if state.db_circuit.is_open() {
+        return Err(ApiError::ServiceUnavailable(
+            "External references cannot be looked up while the database is unavailable".to_string(),
+        ));
+    }
+    let pointer = get_pointer_by_external_ref(&state.db.writer, auth.org_id, &external_ref)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("No pointer with this external_ref".to_string()))?;
+
+    resolve_pointer(State(state), auth, Path(pointer.pointer_id), query).await
+}
+
+/// The data row's payload as the client sent it. A payload that fails to
+/// decrypt is an error of its own, never an empty or ciphertext payload.
+fn open_stored_payload(state: &AppState, data: &DataStore) -> Result<Option<Vec<u8>>, ApiError> {
+    open_payload(state.payload_cipher.as_deref(), data).map_err(|e| {
+        tracing::error!(data_id = %data.data_id, error = %e, "Payload decryption failed");
+        ApiError::PayloadDecryptionFailed(format!(
+            "payload_decryption_failed: Stored payload of data {} could not be decrypted",
+            data.data_id
+        ))
+    })
+}
+
+/// Readonly resolve from the cached or shared fetch. Grace disclosures and
+/// payload release are always attested, so only an active (or still pending
+/// orphan), unrestricted pointer is served here; an orphan is denied outright.
+fn resolve_readonly(
+    state: &AppState,
+    pointer: Pointer,
+    restriction: Option<PointerRestriction>,
+    content_hash: Option<String>,
+) -> Result<Json<ResolvePointerResponse>, ApiError> {
+    let now = state.clock.now();
+    // No grace: an orphan is denied outright
+    enforce_pointer_access(&pointer, chrono::Duration::zero(), state.clock.as_ref())
+        .inspect_err(|_| state.last_known.record(&pointer, None, now))?;
+
+    if let Some(until) = restricted_until(restriction.as_ref(), state.clock.as_ref()) {
+        info!("Readonly resolve denied, processing restricted");
+        return Err(ApiError::ProcessingRestricted {
+            restricted_until: until.to_rfc3339(),
+        });
+    }
+
+    let content_hash =
+        content_hash.ok_or_else(|| ApiError::Internal("Data not found for pointer".to_string()))?;
+
+    state.last_known.record(&pointer, Some(&content_hash), now);
+
+    Ok(Json(ResolvePointerResponse {
+        pointer_id: pointer.pointer_id,
+        data_id: pointer.data_id,
+        subject_id: pointer.subject_id,
+        content_hash,
+        status: pointer.status.as_str().to_string(),
         created_at: pointer.created_at.to_rfc3339(),
+        receipt_generated: false,
+        receipt: None,
+        degraded: false,
+        last_known_at: None,
+        hard_denial_at: None,
+        orphan_effective_at: pointer.orphan_effective_at.map(|at| at.to_rfc3339()),
+        retry_after_seconds: None,
+        encrypted_payload: None,
+    }))
+}
+
+/// Resolve from last known status while the database is unreachable. Only a
+/// grant seen within the staleness bound is repeated, status only; an
+/// attested one is owed its receipt until the backfill writes it. A known
+/// orphan stays denied; anything else is a 503.
+fn resolve_degraded(
+    state: &AppState,
+    auth: AuthContext,
+    pointer_id: Uuid,
+    mode: ResolveMode,
+) -> Result<Json<ResolvePointerResponse>, ApiError> {
+    let now = state.clock.now();
+    let no_recent_status = || {
+        ApiError::ServiceUnavailable(
+            "database_unavailable: No recent status for this pointer".to_string(),
+        )
+    };
+    let known = state
+        .last_known
+        .fresh(&pointer_id, now)
+        .ok_or_else(no_recent_status)?;
+    auth.authorize(known.pointer.org_id)?;
+
+    // A known orphan, or a pending one now due, is denied without grace
+    enforce_pointer_access(
+        &known.pointer,
+        chrono::Duration::zero(),
+        state.clock.as_ref(),
+    )?;
+    let content_hash = known.content_hash.ok_or_else(no_recent_status)?;
+
+    warn!("Degraded resolve from last known status");
+    if mode == ResolveMode::Attested {
+        state.last_known.served(DegradedResolve {
+            pointer_id,
+            data_id: known.pointer.data_id,
+            served_at: now,
+            last_known_at: known.seen_at,
+        });
+    }
+
+    Ok(Json(ResolvePointerResponse {
+        pointer_id,
+        data_id: known.pointer.data_id,
+        subject_id: known.pointer.subject_id,
+        content_hash,
+        status: known.pointer.status.as_str().to_string(),
+        created_at: known.pointer.created_at.to_rfc3339(),
+        receipt_generated: false,
+        receipt: None,
+        degraded: true,
+        last_known_at: Some(known.seen_at.to_rfc3339()),
+        hard_denial_at: None,
+        orphan_effective_at: known.pointer.orphan_effective_at.map(|at| at.to_rfc3339()),
+        retry_after_seconds: None,
+        encrypted_payload: None,
+    }))
+}
+
+// ============================================================================
+// SWAP POINTER DATA
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SwapDataRequest {
+    pub content_hash: String,
+    #[serde(default)]
+    pub encrypted_payload: Option<String>, // Base64 encoded unless payload_encoding says otherwise
+    #[serde(default)]
+    pub payload_encoding: Option<PayloadEncoding>,
+    /// Delete the replaced data row, if no other pointer references it
+    #[serde(default)]
+    pub purge_old: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SwapDataResponse {
+    pub pointer_id: Uuid,
+    pub old_data_id: Uuid,
+    pub new_data_id: Uuid,
+    pub old_content_hash: String,
+    pub new_content_hash: String,
+    pub old_data_purged: bool,
+    pub receipt: ReceiptInfo,
+}
+
+/// Repoint an active pointer at a new data row. The data_swap receipt records
+/// both rows, so what the pointer referenced before stays provable.
+#[utoipa::path(
+    post,
+    path = "/api/pointer/{id}/swap-data",
+    tag = "pointers",
+    params(("id" = Uuid, Path, description = "Pointer id")),
+    request_body = SwapDataRequest,
+    responses(
+        (status = 200, description = "Pointer repointed", body = SwapDataResponse),
+        (status = 400, description = "Undecodable payload or content_hash mismatch", body = ErrorBody),
+        (status = 403, description = "Orphaned, restricted, or another org's", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 409, description = "Pointer no longer active", body = ErrorBody),
+        (status = 422, description = "Validation failed", body = ErrorBody),
+    )
+)]
+pub async fn swap_pointer_data(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(pointer_id): Path<Uuid>,
+    Json(req): Json<SwapDataRequest>,
+) -> Result<Json<SwapDataResponse>, ApiError> {
+    record_pointer(&pointer_id);
+    info!("Swapping data");
+
+    let mut errors = FieldErrors::default();
+    check_content_hash(&mut errors, "content_hash", &req.content_hash);
+    let payload = match req.encrypted_payload {
+        Some(ref encoded) => Some(
+            decode_payload(encoded, req.payload_encoding)
+                .map_err(|e| ApiError::BadRequest(e.to_string()))?,
+        ),
+        None => None,
+    };
+    if let Some(payload) = &payload {
+        check_payload_size(
+            &mut errors,
+            "encrypted_payload",
+            payload.bytes.len(),
+            state.config.max_payload_bytes,
+        );
+    }
+    errors.into_result()?;
+    let payload_encoding = payload.as_ref().map(|p| p.encoding.as_str());
+
+    let mut tx = state.db.writer.begin().await?;
+
+    // 1. Lock the pointer so a veto or restriction can't land mid-swap
+    let pointer = lock_pointers_for_update(&mut *tx, &[pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    if matches!(pointer.status, PointerStatus::Orphaned) {
+        return Err(ApiError::PointerOrphaned(
+            "pointer_orphaned: An orphaned pointer's data cannot be swapped".to_string(),
+        ));
+    }
+
+    let restriction = get_open_restriction(&mut *tx, pointer_id).await?;
+    if let Some(until) = restricted_until(restriction.as_ref(), state.clock.as_ref()) {
+        return Err(ApiError::ProcessingRestricted {
+            restricted_until: until.to_rfc3339(),
+        });
+    }
+
+    let old_data = get_data_store(&mut *tx, pointer.data_id)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Data not found for pointer".to_string()))?;
+
+    // 2. Store the new data and repoint
+    let (stored_payload, data_metadata) = seal_payload(
+        state.payload_cipher.as_deref(),
+        pointer.org_id,
+        &req.content_hash,
+        payload.as_ref().map(|p| p.bytes.as_slice()),
+    )?;
+    let new_data = create_data_store(
+        &mut *tx,
+        pointer.org_id,
+        &pointer.subject_id,
+        &req.content_hash,
+        stored_payload.as_deref(),
+        &data_metadata,
+    )
+    .await?;
+    // Corrected data is the same category, and swapping never extends its life
+    if old_data.retain_until.is_some() {
+        set_data_store_retention(
+            &mut *tx,
+            new_data.data_id,
+            old_data.retention_class.as_deref(),
+            old_data.retain_until,
+        )
+        .await?;
+    }
+    set_pointer_data(&mut *tx, pointer_id, new_data.data_id).await?;
+
+    // 3. Drop the old row only on request, and never from under another pointer
+    let old_data_purged =
+        req.purge_old && delete_unreferenced_data_store(&mut *tx, old_data.data_id).await?;
+    if !old_data_purged {
+        mark_data_store_released(&mut *tx, old_data.data_id, state.clock.now()).await?;
+    }
+
+    // 4. Receipt with both sides of the swap
+    let prev_hash = get_latest_receipt_hash(&mut *tx, pointer_id).await?;
+    let mut receipt_data = ReceiptData::new(
+        pointer_id,
+        ReceiptOperation::DataSwap,
+        pointer.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "old_data_id": old_data.data_id,
+            "old_content_hash": old_data.content_hash,
+            "new_data_id": new_data.data_id,
+            "new_content_hash": new_data.content_hash,
+            "payload_encoding": payload_encoding,
+            "old_data_purged": old_data_purged,
+        }),
+        state.clock.now(),
+    );
+    let signed_receipt = sign_receipt(&state, pointer.org_id, &mut receipt_data).await?;
+
+    store_receipt(
+        &state,
+        &mut tx,
+        pointer_id,
+        pointer.org_id,
+        ReceiptOperation::DataSwap,
+        &signed_receipt,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(pointer.org_id),
+        Some(pointer_id),
+        None,
+        "pointer_data_swapped",
+        json!({
+            "old_data_id": old_data.data_id,
+            "new_data_id": new_data.data_id,
+            "old_data_purged": old_data_purged,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    tx.commit().await?;
+    state.pointer_written(&pointer_id);
+
+    info!(
+        data_id = %new_data.data_id,
+        previous_data_id = %old_data.data_id,
+        "Pointer now references new data"
+    );
+
+    Ok(Json(SwapDataResponse {
+        pointer_id,
+        old_data_id: old_data.data_id,
+        new_data_id: new_data.data_id,
+        old_content_hash: old_data.content_hash,
+        new_content_hash: new_data.content_hash,
+        old_data_purged,
         receipt: ReceiptInfo {
             receipt_hash: signed_receipt.receipt_hash,
             signature: data_encoding::BASE64.encode(&signed_receipt.signature),
             signature_algorithm: signed_receipt.signature_algorithm,
-            timestamp: chrono::Utc::now().to_rfc3339(),
+            key_id: signed_receipt.key_id,
+            timestamp: receipt_data.timestamp.to_rfc3339(),
         },
     }))
 }
@@ -245,218 +1449,10485 @@ pub async fn resolve_pointer(
 // ORPHAN POINTER (VETO)
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
-pub struct OrphanPointerRequest {
-    pub pointer_id: Uuid,
-    #[serde(default)]
-    pub reason: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
-pub struct OrphanPointerResponse {
-    pub pointer_id: Uuid,
-    pub status: String,
-    pub orphaned_at: String,
-    pub receipt: ReceiptInfo,
+/// The conditional orphan update found the pointer no longer active
+fn already_orphaned(pointer_id: Uuid) -> ApiError {
+    ApiError::Conflict {
+        conflict_type: "already_orphaned".to_string(),
+        message: format!("Pointer {} is already orphaned", pointer_id),
+    }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/pointer/orphan",
+    tag = "orphans",
+    request_body = OrphanPointerRequest,
+    responses(
+        (status = 200, description = "Pointer orphaned, or its orphan scheduled", body = OrphanPointerResponse),
+        (status = 400, description = "Reason too large for a receipt", body = ErrorBody),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 409, description = "Already orphaned, or dual approval required", body = ErrorBody),
+        (status = 422, description = "Validation failed", body = ErrorBody),
+        (status = 423, description = "Under a legal hold, or chain frozen", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
 pub async fn orphan_pointer(
     State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
     Json(req): Json<OrphanPointerRequest>,
 ) -> Result<Json<OrphanPointerResponse>, ApiError> {
-    info!("Orphaning pointer: {}", req.pointer_id);
+    record_pointer(&req.pointer_id);
+    info!("Orphaning pointer");
+    let started = std::time::Instant::now();
+
+    let mut errors = FieldErrors::default();
+    check_reason(&mut errors, req.reason.as_deref());
+    errors.into_result()?;
+
+    let reason = req
+        .reason
+        .clone()
+        .unwrap_or_else(|| DEFAULT_ORPHAN_REASON.to_string());
+    check_receipt_field(&state, "reason", &reason)?;
 
-    // 1. Get current pointer
-    let pointer_before = get_pointer(&state.db_pool, req.pointer_id)
+    let mut tx = begin_write(&state).await?;
+
+    // 1. Lock the pointer so a concurrent repeat waits for this veto to commit
+    let pointer_before = lock_pointers_for_update(&mut *tx, &[req.pointer_id])
         .await?
+        .pop()
         .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer_before.org_id)?;
 
-    // 2. Check if already orphaned
+    // 2. A repeat veto returns the original outcome, unless it would
+    // silently discard a different reason
     if matches!(pointer_before.status, PointerStatus::Orphaned) {
-        return Err(ApiError::BadRequest(
-            "Pointer is already orphaned".to_string(),
-        ));
+        let original_reason = pointer_before
+            .orphan_reason
+            .clone()
+            .unwrap_or_else(|| DEFAULT_ORPHAN_REASON.to_string());
+
+        if req.reason.is_some() && reason != original_reason {
+            return Err(ApiError::Conflict {
+                conflict_type: "orphan_reason_mismatch".to_string(),
+                message: format!(
+                    "Pointer is already orphaned with reason \"{}\"",
+                    original_reason
+                ),
+            });
+        }
+
+        let original = get_orphan_receipt(&mut *tx, req.pointer_id)
+            .await?
+            .ok_or_else(|| ApiError::Internal("Orphan receipt missing from chain".to_string()))?;
+
+        info!("Pointer already orphaned, returning original receipt");
+
+        return Ok(Json(OrphanPointerResponse {
+            pointer_id: pointer_before.pointer_id,
+            status: "orphaned".to_string(),
+            orphaned_at: pointer_before
+                .orphaned_at
+                .unwrap_or(original.timestamp)
+                .to_rfc3339(),
+            already_orphaned: true,
+            effective_at: None,
+            receipt: ReceiptInfo {
+                receipt_hash: original.receipt_hash,
+                signature: data_encoding::BASE64.encode(&original.signature),
+                signature_algorithm: original.signature_algorithm,
+                key_id: original.key_id.unwrap_or_default(),
+                timestamp: original.timestamp.to_rfc3339(),
+            },
+        }));
     }
 
-    // 3. Orphan the pointer
-    let orphaned_pointer = orphan_pointer(
-        &state.db_pool,
-        req.pointer_id,
+    // A legal hold blocks any veto, immediate or scheduled, until released
+    check_legal_hold(&pointer_before)?;
+    // A pointer under dual approval is only orphaned through an approved request
+    check_dual_approval(&pointer_before)?;
+
+    // 3. A future effective time schedules the orphan instead. A pending
+    // orphan can be brought forward by an immediate veto, not rescheduled.
+    let orphaned_at = state.clock.now();
+    if let Some(effective_at) = req.effective_at.filter(|at| *at > orphaned_at) {
+        if let Some(scheduled_at) = pointer_before
+            .orphan_effective_at
+            .filter(|_| matches!(pointer_before.status, PointerStatus::PendingOrphan))
+        {
+            return Err(ApiError::Conflict {
+                conflict_type: "orphan_already_scheduled".to_string(),
+                message: format!(
+                    "Pointer is already scheduled to be orphaned at {}",
+                    scheduled_at.to_rfc3339()
+                ),
+            });
+        }
+        return schedule_pointer_orphan(&state, tx, &ctx, &req, &reason, effective_at).await;
+    }
+
+    commit_orphan(
+        &state,
+        tx,
+        &ctx,
+        &pointer_before,
         req.reason.as_deref(),
+        None,
+        started,
     )
-    .await?;
+    .await
+}
+
+/// Orphan a locked pointer now with a chained orphan receipt: the end of a
+/// single-call veto, and of an orphan request once approved
+async fn commit_orphan(
+    state: &AppState,
+    mut tx: sqlx::Transaction<'static, sqlx::Postgres>,
+    ctx: &RequestContext,
+    pointer_before: &Pointer,
+    stated_reason: Option<&str>,
+    approval: Option<&OrphanRequest>,
+    started: std::time::Instant,
+) -> Result<Json<OrphanPointerResponse>, ApiError> {
+    let pointer_id = pointer_before.pointer_id;
+    let reason = stated_reason.unwrap_or(DEFAULT_ORPHAN_REASON);
+    let orphaned_at = state.clock.now();
+    let superseded_schedule = pointer_before
+        .orphan_effective_at
+        .filter(|_| matches!(pointer_before.status, PointerStatus::PendingOrphan));
+
+    // 4. Orphan the pointer
+    let orphaned_pointer =
+        queries::orphan_pointer(&mut *tx, pointer_id, stated_reason, orphaned_at)
+            .await?
+            .ok_or_else(|| already_orphaned(pointer_id))?;
+
+    // A veto supersedes any processing restriction
+    let lifted = lift_open_restriction(&mut *tx, pointer_id, "orphaned").await?;
 
-    info!("Pointer orphaned: {}", req.pointer_id);
+    info!("Pointer orphaned");
 
-    // 4. Generate orphan receipt with chain link
-    let prev_hash = get_latest_receipt_hash(&state.db_pool, req.pointer_id).await?;
+    // 5. Generate orphan receipt with chain link
+    let prev_hash = get_latest_receipt_hash(&mut *tx, pointer_id).await?;
 
-    let receipt_data = ReceiptData::new(
+    let mut metadata = json!({
+        "reason": reason,
+        "orphaned_at": orphaned_pointer.orphaned_at,
+    });
+    if let Some(lifted) = lifted {
+        metadata["lifted_restriction_id"] = json!(lifted.restriction_id);
+    }
+    if let Some(scheduled_at) = superseded_schedule {
+        metadata["superseded_effective_at"] = json!(scheduled_at);
+    }
+    // Both people behind a two-person veto are part of the signed record
+    if let Some(request) = approval {
+        metadata["orphan_request_id"] = json!(request.request_id);
+        metadata["requested_by"] = json!(request.requested_by);
+        metadata["approved_by"] = json!(request.approved_by);
+    }
+
+    let mut receipt_data = ReceiptData::new(
         orphaned_pointer.pointer_id,
         ReceiptOperation::Orphan,
         orphaned_pointer.subject_id.clone(),
         prev_hash.clone(),
-        json!({
-            "reason": req.reason.clone().unwrap_or_else(|| "user_consent_revoked".to_string()),
-            "orphaned_at": orphaned_pointer.orphaned_at,
-        }),
+        metadata,
+        orphaned_at,
     );
 
-    let signed_receipt = receipt_data.sign(&state.keypair)?;
+    let signed_receipt = sign_receipt(state, orphaned_pointer.org_id, &mut receipt_data).await?;
 
-    // 5. Store orphan receipt
-    create_governance_receipt(
-        &state.db_pool,
+    // 6. Store orphan receipt
+    store_receipt(
+        state,
+        &mut tx,
         orphaned_pointer.pointer_id,
         orphaned_pointer.org_id,
         ReceiptOperation::Orphan,
-        signed_receipt.receipt_json.clone(),
-        &signed_receipt.receipt_hash,
-        &signed_receipt.signature,
-        &signed_receipt.signature_algorithm,
+        &signed_receipt,
         prev_hash.as_deref(),
     )
     .await?;
 
-    // 6. Audit log
+    // 7. Audit log
+    let mut event_data = json!({
+        "subject_id": orphaned_pointer.subject_id,
+        "reason": stated_reason,
+    });
+    if let Some(request) = approval {
+        event_data["orphan_request_id"] = json!(request.request_id);
+        event_data["requested_by"] = json!(request.requested_by);
+    }
     create_audit_log(
-        &state.db_pool,
+        &mut *tx,
         Some(orphaned_pointer.org_id),
         Some(orphaned_pointer.pointer_id),
         None,
         "pointer_orphaned",
-        json!({
-            "subject_id": orphaned_pointer.subject_id,
-            "reason": req.reason,
-        }),
-        None,
+        event_data,
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    // 8. Queue webhook notifications; delivery happens after the response
+    enqueue_webhook_event(
+        &mut *tx,
+        orphaned_pointer.org_id,
+        orphaned_pointer.pointer_id,
+        "pointer_orphaned",
+        &signed_receipt,
+        orphaned_at,
     )
     .await?;
 
-    info!("Orphan receipt created for pointer: {}", req.pointer_id);
+    tx.commit().await?;
+    state.pointer_written(&pointer_id);
+    state.webhook_wakeup.notify_one();
+    record_veto(
+        &state.db.writer,
+        orphaned_pointer.org_id,
+        state.clock.now(),
+        started.elapsed(),
+        state.config.veto_slo_target_ms,
+    )
+    .await;
+
+    info!("Orphan receipt created");
 
     Ok(Json(OrphanPointerResponse {
         pointer_id: orphaned_pointer.pointer_id,
         status: "orphaned".to_string(),
-        orphaned_at: orphaned_pointer
-            .orphaned_at
-            .unwrap()
-            .to_rfc3339(),
-        receipt: ReceiptInfo {
-            receipt_hash: signed_receipt.receipt_hash,
-            signature: data_encoding::BASE64.encode(&signed_receipt.signature),
-            signature_algorithm: signed_receipt.signature_algorithm,
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        },
+        orphaned_at: orphaned_pointer.orphaned_at.unwrap().to_rfc3339(),
+        already_orphaned: false,
+        effective_at: None,
+        receipt: receipt_info(signed_receipt, receipt_data.timestamp),
     }))
 }
 
-// ============================================================================
-// GET RECEIPTS
-// ============================================================================
+/// Schedule a locked, active pointer's orphan for `effective_at`, chaining an
+/// OrphanScheduled receipt. The sweeper writes the orphan receipt once due.
+async fn schedule_pointer_orphan(
+    state: &AppState,
+    mut tx: sqlx::Transaction<'static, sqlx::Postgres>,
+    ctx: &RequestContext,
+    req: &OrphanPointerRequest,
+    reason: &str,
+    effective_at: chrono::DateTime<chrono::Utc>,
+) -> Result<Json<OrphanPointerResponse>, ApiError> {
+    let scheduled = queries::schedule_orphan(
+        &mut *tx,
+        req.pointer_id,
+        req.reason.as_deref(),
+        effective_at,
+    )
+    .await?
+    .ok_or_else(|| already_orphaned(req.pointer_id))?;
 
-#[derive(Debug, Serialize)]
-pub struct GetReceiptsResponse {
-    pub pointer_id: Uuid,
-    pub receipts: Vec<ReceiptSummary>,
-}
+    let prev_hash = get_latest_receipt_hash(&mut *tx, req.pointer_id).await?;
+    let mut receipt_data = ReceiptData::new(
+        scheduled.pointer_id,
+        ReceiptOperation::OrphanScheduled,
+        scheduled.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "reason": reason,
+            "effective_at": effective_at,
+        }),
+        state.clock.now(),
+    );
+    let signed_receipt = sign_receipt(state, scheduled.org_id, &mut receipt_data).await?;
 
-#[derive(Debug, Serialize)]
-pub struct ReceiptSummary {
-    pub operation: String,
-    pub receipt_hash: String,
-    pub signature: String,
-    pub prev_hash: Option<String>,
-    pub timestamp: String,
-}
+    store_receipt(
+        state,
+        &mut tx,
+        scheduled.pointer_id,
+        scheduled.org_id,
+        ReceiptOperation::OrphanScheduled,
+        &signed_receipt,
+        prev_hash.as_deref(),
+    )
+    .await?;
 
-pub async fn get_receipts(
-    State(state): State<AppState>,
-    Path(pointer_id): Path<Uuid>,
-) -> Result<Json<GetReceiptsResponse>, ApiError> {
-    info!("Getting receipts for pointer: {}", pointer_id);
+    create_audit_log(
+        &mut *tx,
+        Some(scheduled.org_id),
+        Some(scheduled.pointer_id),
+        None,
+        "pointer_orphan_scheduled",
+        json!({
+            "subject_id": scheduled.subject_id,
+            "reason": req.reason,
+            "effective_at": effective_at,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
 
-    let receipts = get_receipts_by_pointer(&state.db_pool, pointer_id).await?;
+    enqueue_webhook_event(
+        &mut *tx,
+        scheduled.org_id,
+        scheduled.pointer_id,
+        "orphan_scheduled",
+        &signed_receipt,
+        receipt_data.timestamp,
+    )
+    .await?;
 
-    let receipt_summaries: Vec<ReceiptSummary> = receipts
-        .into_iter()
-        .map(|r| ReceiptSummary {
-            operation: match r.operation {
-                ReceiptOperation::Create => "create".to_string(),
-                ReceiptOperation::Resolve => "resolve".to_string(),
-                ReceiptOperation::Orphan => "orphan".to_string(),
-            },
-            receipt_hash: r.receipt_hash,
-            signature: data_encoding::BASE64.encode(&r.signature),
-            prev_hash: r.prev_hash,
-            timestamp: r.timestamp.to_rfc3339(),
-        })
-        .collect();
+    tx.commit().await?;
+    state.pointer_written(&req.pointer_id);
+    state.webhook_wakeup.notify_one();
 
-    Ok(Json(GetReceiptsResponse {
-        pointer_id,
-        receipts: receipt_summaries,
+    info!(effective_at = %effective_at, "Pointer scheduled to be orphaned");
+
+    Ok(Json(OrphanPointerResponse {
+        pointer_id: scheduled.pointer_id,
+        status: "pending_orphan".to_string(),
+        orphaned_at: effective_at.to_rfc3339(),
+        already_orphaned: false,
+        effective_at: Some(effective_at.to_rfc3339()),
+        receipt: receipt_info(signed_receipt, receipt_data.timestamp),
     }))
 }
 
 // ============================================================================
-// GET AUDIT TRAIL
+// CANCEL SCHEDULED ORPHAN
 // ============================================================================
 
-#[derive(Debug, Serialize)]
-pub struct GetAuditTrailResponse {
-    pub subject_id: String,
-    pub total_pointers: usize,
-    pub active_pointers: usize,
-    pub orphaned_pointers: usize,
-    pub audit_events: Vec<AuditEventSummary>,
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CancelOrphanRequest {
+    pub pointer_id: Uuid,
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct AuditEventSummary {
-    pub event_type: String,
-    pub timestamp: String,
-    pub pointer_id: Option<Uuid>,
-    pub event_data: serde_json::Value,
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CancelOrphanResponse {
+    pub pointer_id: Uuid,
+    pub status: String,
+    /// Effective time of the orphan that will no longer happen
+    #[schema(format = DateTime)]
+    pub cancelled_effective_at: String,
+    pub receipt: ReceiptInfo,
 }
 
-pub async fn get_audit_trail(
+/// Abort a pending orphan before it takes effect, returning the pointer to
+/// active with an OrphanCancelled receipt
+#[utoipa::path(
+    post,
+    path = "/api/pointer/orphan/cancel",
+    tag = "orphans",
+    request_body = CancelOrphanRequest,
+    responses(
+        (status = 200, description = "Scheduled orphan cancelled", body = CancelOrphanResponse),
+        (status = 400, description = "Reason too large for a receipt", body = ErrorBody),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 409, description = "No orphan pending, or it already took effect", body = ErrorBody),
+        (status = 422, description = "Validation failed", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn cancel_orphan(
     State(state): State<AppState>,
-    Path(subject_id): Path<String>,
-) -> Result<Json<GetAuditTrailResponse>, ApiError> {
-    info!("Getting audit trail for subject: {}", subject_id);
+    auth: AuthContext,
+    ctx: RequestContext,
+    Json(req): Json<CancelOrphanRequest>,
+) -> Result<Json<CancelOrphanResponse>, ApiError> {
+    record_pointer(&req.pointer_id);
+    info!("Cancelling scheduled orphan");
 
-    // Get all pointers for subject
-    let pointers = get_pointers_by_subject(&state.db_pool, &subject_id).await?;
+    let mut errors = FieldErrors::default();
+    check_reason(&mut errors, req.reason.as_deref());
+    errors.into_result()?;
+    if let Some(reason) = &req.reason {
+        check_receipt_field(&state, "reason", reason)?;
+    }
 
-    let active_count = pointers
-        .iter()
-        .filter(|p| matches!(p.status, PointerStatus::Active))
-        .count();
+    let mut tx = begin_write(&state).await?;
 
-    let orphaned_count = pointers
-        .iter()
-        .filter(|p| matches!(p.status, PointerStatus::Orphaned))
-        .count();
+    let pointer = lock_pointers_for_update(&mut *tx, &[req.pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
 
-    // Get audit trail
-    let audit_logs = get_audit_trail_by_subject(&state.db_pool, &subject_id).await?;
+    // Once due the orphan stands, even if the sweeper hasn't finalized it
+    let now = state.clock.now();
+    let effective_at = match (&pointer.status, pointer.orphan_effective_at) {
+        (PointerStatus::Orphaned, _) => return Err(already_orphaned(req.pointer_id)),
+        (PointerStatus::PendingOrphan, Some(at)) if at <= now => {
+            return Err(ApiError::Conflict {
+                conflict_type: "orphan_in_effect".to_string(),
+                message: format!("Scheduled orphan took effect at {}", at.to_rfc3339()),
+            })
+        }
+        (PointerStatus::PendingOrphan, Some(at)) => at,
+        _ => {
+            return Err(ApiError::Conflict {
+                conflict_type: "no_pending_orphan".to_string(),
+                message: format!("Pointer {} has no scheduled orphan", req.pointer_id),
+            })
+        }
+    };
 
-    let audit_summaries: Vec<AuditEventSummary> = audit_logs
-        .into_iter()
-        .map(|log| AuditEventSummary {
-            event_type: log.event_type,
-            timestamp: log.timestamp.to_rfc3339(),
-            pointer_id: log.pointer_id,
-            event_data: log.event_data,
+    let cancelled = queries::cancel_pending_orphan(&mut *tx, req.pointer_id)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Pending orphan vanished under lock".to_string()))?;
+
+    let prev_hash = get_latest_receipt_hash(&mut *tx, req.pointer_id).await?;
+    let mut receipt_data = ReceiptData::new(
+        cancelled.pointer_id,
+        ReceiptOperation::OrphanCancelled,
+        cancelled.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "cancelled_effective_at": effective_at,
+            "scheduled_reason": pointer
+                .orphan_reason
+                .as_deref()
+                .unwrap_or(DEFAULT_ORPHAN_REASON),
+            "reason": req.reason,
+        }),
+        now,
+    );
+    let signed_receipt = sign_receipt(&state, cancelled.org_id, &mut receipt_data).await?;
+
+    store_receipt(
+        &state,
+        &mut tx,
+        cancelled.pointer_id,
+        cancelled.org_id,
+        ReceiptOperation::OrphanCancelled,
+        &signed_receipt,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(cancelled.org_id),
+        Some(cancelled.pointer_id),
+        None,
+        "pointer_orphan_cancelled",
+        json!({
+            "subject_id": cancelled.subject_id,
+            "cancelled_effective_at": effective_at,
+            "reason": req.reason,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    enqueue_webhook_event(
+        &mut *tx,
+        cancelled.org_id,
+        cancelled.pointer_id,
+        "orphan_cancelled",
+        &signed_receipt,
+        now,
+    )
+    .await?;
+
+    tx.commit().await?;
+    state.pointer_written(&req.pointer_id);
+    state.webhook_wakeup.notify_one();
+
+    info!("Scheduled orphan cancelled");
+
+    Ok(Json(CancelOrphanResponse {
+        pointer_id: cancelled.pointer_id,
+        status: "active".to_string(),
+        cancelled_effective_at: effective_at.to_rfc3339(),
+        receipt: receipt_info(signed_receipt, receipt_data.timestamp),
+    }))
+}
+
+// ============================================================================
+// ORPHAN REQUESTS (DUAL APPROVAL)
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RequestOrphanRequest {
+    pub pointer_id: Uuid,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ApproveOrphanRequest {
+    pub request_id: Uuid,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrphanRequestResponse {
+    pub request_id: Uuid,
+    pub pointer_id: Uuid,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub requested_by: String,
+    #[schema(format = DateTime)]
+    pub requested_at: String,
+    #[schema(format = DateTime)]
+    pub expires_at: String,
+    pub receipt: ReceiptInfo,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrphanRequestSummary {
+    pub request_id: Uuid,
+    /// pending, approved or expired
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub requested_by: String,
+    #[schema(format = DateTime)]
+    pub requested_at: String,
+    #[schema(format = DateTime)]
+    pub expires_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approved_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub closed_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrphanRequestsResponse {
+    pub pointer_id: Uuid,
+    pub requests: Vec<OrphanRequestSummary>,
+}
+
+/// Refuse the single-call veto for a pointer that needs two people
+fn check_dual_approval(pointer: &Pointer) -> Result<(), ApiError> {
+    if !pointer.requires_dual_approval {
+        return Ok(());
+    }
+    Err(ApiError::Conflict {
+        conflict_type: "dual_approval_required".to_string(),
+        message: format!(
+            "Pointer {} requires dual approval; request its orphan at \
+             /api/pointer/orphan/request for another actor to approve",
+            pointer.pointer_id
+        ),
+    })
+}
+
+/// First half of a two-person veto: record who asked, with an
+/// OrphanRequested receipt. The pointer stays active until a different
+/// actor approves the request before it expires.
+#[utoipa::path(
+    post,
+    path = "/api/pointer/orphan/request",
+    tag = "orphans",
+    request_body = RequestOrphanRequest,
+    responses(
+        (status = 201, description = "Orphan requested", body = OrphanRequestResponse),
+        (status = 400, description = "No X-Actor-Id to record as the requester, or reason too large for a receipt", body = ErrorBody),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 409, description = "Already orphaned, a request is pending, or dual approval is not required", body = ErrorBody),
+        (status = 422, description = "Validation failed", body = ErrorBody),
+        (status = 423, description = "Under a legal hold", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn request_orphan(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Json(req): Json<RequestOrphanRequest>,
+) -> Result<(StatusCode, Json<OrphanRequestResponse>), ApiError> {
+    record_pointer(&req.pointer_id);
+    info!("Requesting orphan");
+
+    let mut errors = FieldErrors::default();
+    check_reason(&mut errors, req.reason.as_deref());
+    errors.into_result()?;
+    if let Some(reason) = &req.reason {
+        check_receipt_field(&state, "reason", reason)?;
+    }
+
+    let requested_by = ctx
+        .actor_id
+        .clone()
+        .ok_or_else(|| ApiError::BadRequest("An orphan request needs an actor id".to_string()))?;
+
+    let mut tx = begin_write(&state).await?;
+
+    let pointer = lock_pointers_for_update(&mut *tx, &[req.pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    if matches!(pointer.status, PointerStatus::Orphaned) {
+        return Err(already_orphaned(req.pointer_id));
+    }
+    if !pointer.requires_dual_approval {
+        return Err(ApiError::Conflict {
+            conflict_type: "dual_approval_not_required".to_string(),
+            message: format!(
+                "Pointer {} does not require dual approval; orphan it directly",
+                req.pointer_id
+            ),
+        });
+    }
+    check_legal_hold(&pointer)?;
+
+    // A lapsed request no longer blocks a new one
+    let now = state.clock.now();
+    expire_orphan_request(&mut *tx, req.pointer_id, now).await?;
+
+    let expires_at = now + chrono::Duration::seconds(state.config.orphan_request_ttl_seconds);
+    let request = create_orphan_request(
+        &mut *tx,
+        &pointer,
+        req.reason.as_deref(),
+        &requested_by,
+        now,
+        expires_at,
+    )
+    .await?;
+
+    let prev_hash = get_latest_receipt_hash(&mut *tx, req.pointer_id).await?;
+    let mut receipt_data = ReceiptData::new(
+        pointer.pointer_id,
+        ReceiptOperation::OrphanRequested,
+        pointer.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "orphan_request_id": request.request_id,
+            "reason": req.reason.as_deref().unwrap_or(DEFAULT_ORPHAN_REASON),
+            "requested_by": requested_by,
+            "expires_at": expires_at,
+        }),
+        now,
+    );
+    let signed_receipt = sign_receipt(&state, pointer.org_id, &mut receipt_data).await?;
+
+    store_receipt(
+        &state,
+        &mut tx,
+        pointer.pointer_id,
+        pointer.org_id,
+        ReceiptOperation::OrphanRequested,
+        &signed_receipt,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(pointer.org_id),
+        Some(pointer.pointer_id),
+        None,
+        "pointer_orphan_requested",
+        json!({
+            "subject_id": pointer.subject_id,
+            "orphan_request_id": request.request_id,
+            "reason": req.reason,
+            "expires_at": expires_at,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    tx.commit().await?;
+    state.pointer_written(&req.pointer_id);
+
+    info!(orphan_request_id = %request.request_id, "Orphan requested");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(OrphanRequestResponse {
+            request_id: request.request_id,
+            pointer_id: request.pointer_id,
+            status: request.status,
+            reason: request.reason,
+            requested_by: request.requested_by,
+            requested_at: request.requested_at.to_rfc3339(),
+            expires_at: request.expires_at.to_rfc3339(),
+            receipt: receipt_info(signed_receipt, receipt_data.timestamp),
+        }),
+    ))
+}
+
+/// Second half of a two-person veto: an actor other than the requester
+/// approves a pending request, orphaning the pointer
+#[utoipa::path(
+    post,
+    path = "/api/pointer/orphan/approve",
+    tag = "orphans",
+    request_body = ApproveOrphanRequest,
+    responses(
+        (status = 200, description = "Request approved and pointer orphaned", body = OrphanPointerResponse),
+        (status = 400, description = "Request expired or already closed", body = ErrorBody),
+        (status = 403, description = "Another org's pointer, or approved by the requester", body = ErrorBody),
+        (status = 404, description = "Request not found", body = ErrorBody),
+        (status = 409, description = "Already orphaned", body = ErrorBody),
+        (status = 423, description = "Under a legal hold, or chain frozen", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn approve_orphan_request(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Json(req): Json<ApproveOrphanRequest>,
+) -> Result<Json<OrphanPointerResponse>, ApiError> {
+    let started = std::time::Instant::now();
+
+    let request = get_orphan_request(&state.db.writer, req.request_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Orphan request not found".to_string()))?;
+    auth.authorize(request.org_id)?;
+    record_pointer(&request.pointer_id);
+    info!(orphan_request_id = %req.request_id, "Approving orphan request");
+
+    let approved_by = ctx.actor_id.clone().ok_or_else(|| {
+        ApiError::BadRequest("Approving an orphan request needs an actor id".to_string())
+    })?;
+
+    let mut tx = begin_write(&state).await?;
+
+    // Lock the pointer, then re-read the request now that no other approval
+    // or request for it can run
+    let pointer_before = lock_pointers_for_update(&mut *tx, &[request.pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    let request = get_orphan_request(&mut *tx, req.request_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Orphan request not found".to_string()))?;
+
+    let now = state.clock.now();
+    if !request.is_pending(now) {
+        if request.status_at(now) == "expired" {
+            // Record the lapse so the pointer can be requested again
+            expire_orphan_request(&mut *tx, request.pointer_id, now).await?;
+            tx.commit().await?;
+            return Err(ApiError::Conflict {
+                conflict_type: "orphan_request_expired".to_string(),
+                message: format!(
+                    "Orphan request {} expired at {}",
+                    request.request_id,
+                    request.expires_at.to_rfc3339()
+                ),
+            });
+        }
+        return Err(ApiError::Conflict {
+            conflict_type: "orphan_request_not_pending".to_string(),
+            message: format!(
+                "Orphan request {} is already {}",
+                request.request_id, request.status
+            ),
+        });
+    }
+
+    if approved_by == request.requested_by {
+        return Err(ApiError::Forbidden(
+            "forbidden: an orphan request must be approved by a different actor".to_string(),
+        ));
+    }
+    if matches!(pointer_before.status, PointerStatus::Orphaned) {
+        return Err(already_orphaned(request.pointer_id));
+    }
+    check_legal_hold(&pointer_before)?;
+
+    let approved = queries::approve_orphan_request(&mut *tx, request.request_id, &approved_by, now)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Orphan request changed under lock".to_string()))?;
+
+    commit_orphan(
+        &state,
+        tx,
+        &ctx,
+        &pointer_before,
+        approved.reason.as_deref(),
+        Some(&approved),
+        started,
+    )
+    .await
+}
+
+/// A pointer's orphan requests, newest first
+#[utoipa::path(
+    get,
+    path = "/api/pointer/{id}/orphan-requests",
+    tag = "orphans",
+    params(("id" = Uuid, Path, description = "Pointer id")),
+    responses(
+        (status = 200, description = "The pointer's orphan requests, newest first", body = OrphanRequestsResponse),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+    )
+)]
+pub async fn list_orphan_requests(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(pointer_id): Path<Uuid>,
+) -> Result<Json<OrphanRequestsResponse>, ApiError> {
+    let pointer = get_pointer(&state.db.reader, pointer_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    let now = state.clock.now();
+    let requests = get_orphan_requests(&state.db.reader, pointer_id)
+        .await?
+        .into_iter()
+        .map(|request| OrphanRequestSummary {
+            request_id: request.request_id,
+            status: request.status_at(now).to_string(),
+            reason: request.reason,
+            requested_by: request.requested_by,
+            requested_at: request.requested_at.to_rfc3339(),
+            expires_at: request.expires_at.to_rfc3339(),
+            approved_by: request.approved_by,
+            closed_at: request.closed_at.map(|at| at.to_rfc3339()),
         })
         .collect();
 
-    Ok(Json(GetAuditTrailResponse {
-        subject_id,
-        total_pointers: pointers.len(),
-        active_pointers: active_count,
-        orphaned_pointers: orphaned_count,
-        audit_events: audit_summaries,
+    Ok(Json(OrphanRequestsResponse {
+        pointer_id,
+        requests,
+    }))
+}
+
+// ============================================================================
+// REINSTATE ORPHANED POINTER
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReinstatePointerRequest {
+    pub pointer_id: Uuid,
+    /// Why the orphan was a mistake; signed into the reinstate receipt
+    pub justification: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReinstatePointerResponse {
+    pub pointer_id: Uuid,
+    pub status: String,
+    /// The orphan this reverses
+    #[schema(format = DateTime)]
+    pub orphaned_at: String,
+    pub orphan_reason: String,
+    #[schema(format = DateTime)]
+    pub reinstated_at: String,
+    pub receipt: ReceiptInfo,
+}
+
+/// Undo an orphan made in error: the pointer is active again, a Reinstate
+/// receipt follows the orphan receipt in its chain, and the orphan is kept in
+/// metadata.orphan_history. Off unless ALLOW_REINSTATEMENT is set.
+#[utoipa::path(
+    post,
+    path = "/api/pointer/reinstate",
+    tag = "orphans",
+    request_body = ReinstatePointerRequest,
+    responses(
+        (status = 200, description = "Pointer active again", body = ReinstatePointerResponse),
+        (status = 400, description = "Pointer is not orphaned", body = ErrorBody),
+        (status = 403, description = "Reinstatement disabled, or another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 409, description = "Pointer changed concurrently", body = ErrorBody),
+        (status = 422, description = "Validation failed", body = ErrorBody),
+        (status = 423, description = "Under a legal hold, or chain frozen", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn reinstate_pointer(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Json(req): Json<ReinstatePointerRequest>,
+) -> Result<Json<ReinstatePointerResponse>, ApiError> {
+    if !state.config.allow_reinstatement {
+        return Err(ApiError::ReinstatementDisabled(
+            "reinstatement_disabled: Reinstating orphaned pointers is not enabled on this server"
+                .to_string(),
+        ));
+    }
+
+    let mut errors = FieldErrors::default();
+    check_justification(&mut errors, &req.justification);
+    errors.into_result()?;
+    let justification = req.justification.trim();
+    check_receipt_field(&state, "justification", justification)?;
+
+    record_pointer(&req.pointer_id);
+    info!("Reinstating pointer");
+
+    let mut tx = begin_write(&state).await?;
+
+    let pointer = lock_pointers_for_update(&mut *tx, &[req.pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    let (PointerStatus::Orphaned, Some(orphaned_at)) = (&pointer.status, pointer.orphaned_at)
+    else {
+        return Err(ApiError::BadRequest(format!(
+            "Pointer {} is not orphaned",
+            req.pointer_id
+        )));
+    };
+
+    // Reinstating can't bring back a payload that is gone, or outlive the
+    // retention deadline that would orphan the pointer again
+    let now = state.clock.now();
+    let data = get_data_store(&mut *tx, pointer.data_id)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Pointer data row missing".to_string()))?;
+    if let Some(purged_at) = data.payload_purged_at {
+        return Err(ApiError::Conflict {
+            conflict_type: "payload_purged".to_string(),
+            message: format!(
+                "Pointer payload was purged at {}; it cannot be reinstated",
+                purged_at.to_rfc3339()
+            ),
+        });
+    }
+    if let Some(retain_until) = data.retain_until.filter(|at| *at <= now) {
+        return Err(ApiError::Conflict {
+            conflict_type: "retention_expired".to_string(),
+            message: format!(
+                "Pointer data passed its retention deadline at {}",
+                retain_until.to_rfc3339()
+            ),
+        });
+    }
+
+    let orphan_reason = pointer
+        .orphan_reason
+        .clone()
+        .unwrap_or_else(|| DEFAULT_ORPHAN_REASON.to_string());
+    let history_entry = json!({
+        "orphaned_at": orphaned_at,
+        "orphan_reason": orphan_reason,
+        "reinstated_at": now,
+        "reinstated_by": ctx.actor_id,
+        "justification": justification,
+    });
+    let reinstated = queries::reinstate_pointer(&mut *tx, req.pointer_id, &history_entry)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Pointer changed under lock".to_string()))?;
+
+    let prev_hash = get_latest_receipt_hash(&mut *tx, req.pointer_id).await?;
+    let mut receipt_data = ReceiptData::new(
+        reinstated.pointer_id,
+        ReceiptOperation::Reinstate,
+        reinstated.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "justification": justification,
+            "orphaned_at": orphaned_at,
+            "orphan_reason": orphan_reason,
+            "reinstated_by": ctx.actor_id,
+        }),
+        now,
+    );
+    let signed_receipt = sign_receipt(&state, reinstated.org_id, &mut receipt_data).await?;
+
+    store_receipt(
+        &state,
+        &mut tx,
+        reinstated.pointer_id,
+        reinstated.org_id,
+        ReceiptOperation::Reinstate,
+        &signed_receipt,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(reinstated.org_id),
+        Some(reinstated.pointer_id),
+        None,
+        "pointer_reinstated",
+        json!({
+            "subject_id": reinstated.subject_id,
+            "justification": justification,
+            "orphaned_at": orphaned_at,
+            "orphan_reason": orphan_reason,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    enqueue_webhook_event(
+        &mut *tx,
+        reinstated.org_id,
+        reinstated.pointer_id,
+        "pointer_reinstated",
+        &signed_receipt,
+        now,
+    )
+    .await?;
+
+    tx.commit().await?;
+    state.pointer_written(&req.pointer_id);
+    state.webhook_wakeup.notify_one();
+
+    info!(orphaned_at = %orphaned_at, "Pointer reinstated");
+
+    Ok(Json(ReinstatePointerResponse {
+        pointer_id: reinstated.pointer_id,
+        status: "active".to_string(),
+        orphaned_at: orphaned_at.to_rfc3339(),
+        orphan_reason,
+        reinstated_at: now.to_rfc3339(),
+        receipt: receipt_info(signed_receipt, receipt_data.timestamp),
+    }))
+}
+
+// ============================================================================
+// LEGAL HOLD
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LegalHoldRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LegalHoldResponse {
+    pub pointer_id: Uuid,
+    pub legal_hold: bool,
+    pub reason: String,
+    #[schema(format = DateTime)]
+    pub set_at: String,
+    /// Set when the hold was released by this request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub released_at: Option<String>,
+    pub receipt: ReceiptInfo,
+}
+
+/// Refuse to orphan a pointer under a legal hold
+fn check_legal_hold(pointer: &Pointer) -> Result<(), ApiError> {
+    if !pointer.legal_hold {
+        return Ok(());
+    }
+    Err(ApiError::LegalHold {
+        pointer_id: pointer.pointer_id,
+        reason: pointer.legal_hold_reason.clone(),
+        set_at: pointer.legal_hold_set_at.map(|at| at.to_rfc3339()),
+    })
+}
+
+/// Place a legal hold: the pointer keeps resolving but cannot be orphaned
+/// until the hold is released. A scheduled orphan falling due meanwhile
+/// waits for the release.
+#[utoipa::path(
+    post,
+    path = "/api/pointer/{id}/hold",
+    tag = "holds",
+    params(("id" = Uuid, Path, description = "Pointer id")),
+    request_body = LegalHoldRequest,
+    responses(
+        (status = 200, description = "Hold placed", body = LegalHoldResponse),
+        (status = 400, description = "Reason too large", body = ErrorBody),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 409, description = "Already held, or already orphaned", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn place_legal_hold(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Path(pointer_id): Path<Uuid>,
+    Json(req): Json<LegalHoldRequest>,
+) -> Result<Json<LegalHoldResponse>, ApiError> {
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::BadRequest("reason must not be empty".to_string()));
+    }
+    check_receipt_field(&state, "reason", reason)?;
+
+    record_pointer(&pointer_id);
+    info!(reason = %reason, "Placing legal hold");
+
+    let mut tx = begin_write(&state).await?;
+    let pointer = lock_pointers_for_update(&mut *tx, &[pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    if matches!(pointer.status, PointerStatus::Orphaned) {
+        return Err(already_orphaned(pointer_id));
+    }
+    if pointer.legal_hold {
+        return Err(ApiError::Conflict {
+            conflict_type: "legal_hold_active".to_string(),
+            message: format!("Pointer {} is already under a legal hold", pointer_id),
+        });
+    }
+
+    let set_at = state.clock.now();
+    let held = queries::set_legal_hold(&mut *tx, pointer_id, reason, set_at)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Pointer changed under lock".to_string()))?;
+
+    let prev_hash = get_latest_receipt_hash(&mut *tx, pointer_id).await?;
+    let mut receipt_data = ReceiptData::new(
+        pointer_id,
+        ReceiptOperation::LegalHold,
+        held.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "reason": reason,
+            "set_at": set_at,
+        }),
+        set_at,
+    );
+    let signed_receipt = sign_receipt(&state, held.org_id, &mut receipt_data).await?;
+
+    store_receipt(
+        &state,
+        &mut tx,
+        pointer_id,
+        held.org_id,
+        ReceiptOperation::LegalHold,
+        &signed_receipt,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(held.org_id),
+        Some(pointer_id),
+        None,
+        "pointer_legal_hold_set",
+        json!({
+            "subject_id": held.subject_id,
+            "reason": reason,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    tx.commit().await?;
+    state.pointer_written(&pointer_id);
+
+    Ok(Json(LegalHoldResponse {
+        pointer_id,
+        legal_hold: true,
+        reason: reason.to_string(),
+        set_at: set_at.to_rfc3339(),
+        released_at: None,
+        receipt: receipt_info(signed_receipt, receipt_data.timestamp),
     }))
 }
+
+#[utoipa::path(
+    delete,
+    path = "/api/pointer/{id}/hold",
+    tag = "holds",
+    params(("id" = Uuid, Path, description = "Pointer id")),
+    responses(
+        (status = 200, description = "Hold released", body = LegalHoldResponse),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 409, description = "No hold to release", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn release_legal_hold(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Path(pointer_id): Path<Uuid>,
+) -> Result<Json<LegalHoldResponse>, ApiError> {
+    record_pointer(&pointer_id);
+    info!("Releasing legal hold");
+
+    let mut tx = begin_write(&state).await?;
+    let pointer = lock_pointers_for_update(&mut *tx, &[pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    let (Some(reason), Some(set_at)) = (
+        pointer
+            .legal_hold_reason
+            .clone()
+            .filter(|_| pointer.legal_hold),
+        pointer.legal_hold_set_at,
+    ) else {
+        return Err(ApiError::Conflict {
+            conflict_type: "no_legal_hold".to_string(),
+            message: format!("Pointer {} is not under a legal hold", pointer_id),
+        });
+    };
+
+    let released = queries::release_legal_hold(&mut *tx, pointer_id)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Pointer changed under lock".to_string()))?;
+    let released_at = state.clock.now();
+
+    let prev_hash = get_latest_receipt_hash(&mut *tx, pointer_id).await?;
+    let mut receipt_data = ReceiptData::new(
+        pointer_id,
+        ReceiptOperation::LegalHoldRelease,
+        released.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "reason": reason,
+            "set_at": set_at,
+            "released_at": released_at,
+        }),
+        released_at,
+    );
+    let signed_receipt = sign_receipt(&state, released.org_id, &mut receipt_data).await?;
+
+    store_receipt(
+        &state,
+        &mut tx,
+        pointer_id,
+        released.org_id,
+        ReceiptOperation::LegalHoldRelease,
+        &signed_receipt,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(released.org_id),
+        Some(pointer_id),
+        None,
+        "pointer_legal_hold_released",
+        json!({
+            "subject_id": released.subject_id,
+            "reason": reason,
+            "set_at": set_at,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    tx.commit().await?;
+    state.pointer_written(&pointer_id);
+
+    Ok(Json(LegalHoldResponse {
+        pointer_id,
+        legal_hold: false,
+        reason,
+        set_at: set_at.to_rfc3339(),
+        released_at: Some(released_at.to_rfc3339()),
+        receipt: receipt_info(signed_receipt, receipt_data.timestamp),
+    }))
+}
+
+// ============================================================================
+// PURGE PAYLOAD (CRYPTO-SHREDDING)
+// ============================================================================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PurgePayloadResponse {
+    pub pointer_id: Uuid,
+    pub data_id: Uuid,
+    /// Hash of the destroyed payload; the data row keeps it
+    pub content_hash: String,
+    pub payload_bytes: i32,
+    #[schema(format = DateTime)]
+    pub purged_at: String,
+    pub receipt: ReceiptInfo,
+}
+
+/// Destroy the payload behind an orphaned pointer once the purge window has
+/// passed since the veto
+#[utoipa::path(
+    post,
+    path = "/api/pointer/{id}/purge",
+    tag = "orphans",
+    params(("id" = Uuid, Path, description = "Pointer id")),
+    responses(
+        (status = 200, description = "Payload purged", body = PurgePayloadResponse),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 409, description = "Not orphaned, still in its grace window, or held", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn purge_pointer_payload(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(pointer_id): Path<Uuid>,
+) -> Result<Json<PurgePayloadResponse>, ApiError> {
+    record_pointer(&pointer_id);
+    info!("Purging payload");
+
+    let mut tx = begin_write(&state).await?;
+    let pointer = lock_pointers_for_update(&mut *tx, &[pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    let conflict = |conflict_type: &str, message: String| ApiError::Conflict {
+        conflict_type: conflict_type.to_string(),
+        message,
+    };
+    let orphaned_at = match (&pointer.status, pointer.orphaned_at) {
+        (PointerStatus::Orphaned, Some(orphaned_at)) => orphaned_at,
+        _ => {
+            return Err(conflict(
+                "pointer_not_orphaned",
+                format!("Pointer {} is not orphaned", pointer_id),
+            ))
+        }
+    };
+
+    let now = state.clock.now();
+    let purgeable_at = orphaned_at + purge_window(&state.config);
+    if now < purgeable_at {
+        return Err(conflict(
+            "purge_window_open",
+            format!("Payload can be purged from {}", purgeable_at.to_rfc3339()),
+        ));
+    }
+
+    let data = get_data_store(&mut *tx, pointer.data_id)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Data not found for pointer".to_string()))?;
+    if data.encrypted_payload.is_none() {
+        return Err(conflict(
+            "no_payload",
+            format!("Data {} has no payload to purge", data.data_id),
+        ));
+    }
+
+    let signer = state.signer(pointer.org_id).await?;
+    let purged = purge_payload_with_receipt(&mut tx, &signer, &pointer, "request", now)
+        .await?
+        .ok_or_else(|| {
+            conflict(
+                "payload_retained",
+                format!(
+                    "Data {} is under legal hold or still referenced by a live pointer",
+                    data.data_id
+                ),
+            )
+        })?;
+
+    tx.commit().await?;
+    state.pointer_written(&pointer_id);
+
+    Ok(Json(PurgePayloadResponse {
+        pointer_id: purged.pointer_id,
+        data_id: purged.data_id,
+        content_hash: purged.content_hash,
+        payload_bytes: purged.payload_bytes,
+        purged_at: now.to_rfc3339(),
+        receipt: receipt_info(purged.receipt, purged.receipt_timestamp),
+    }))
+}
+
+// ============================================================================
+// ORPHAN POINTER GROUP (ATOMIC VETO)
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct OrphanGroupRequest {
+    pub pointer_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrphanGroupResponse {
+    pub group_id: Uuid,
+    pub org_id: Uuid,
+    pub status: String,
+    pub members: Vec<OrphanGroupMember>,
+    pub manifest: ReceiptInfo,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrphanGroupMember {
+    pub pointer_id: Uuid,
+    #[schema(format = DateTime)]
+    pub orphaned_at: String,
+    pub receipt: ReceiptInfo,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/pointer/orphan-group",
+    tag = "orphans",
+    request_body = OrphanGroupRequest,
+    responses(
+        (status = 200, description = "Every member orphaned", body = OrphanGroupResponse),
+        (status = 400, description = "Empty or oversized group", body = ErrorBody),
+        (status = 409, description = "A member blocked the group; none were orphaned", body = ErrorBody),
+        (status = 422, description = "Validation failed", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn orphan_group(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Json(req): Json<OrphanGroupRequest>,
+) -> Result<Json<OrphanGroupResponse>, ApiError> {
+    let started = std::time::Instant::now();
+
+    let mut errors = FieldErrors::default();
+    check_reason(&mut errors, req.reason.as_deref());
+    errors.into_result()?;
+
+    // Deduplicate while keeping the caller's order (it is part of the manifest)
+    let mut pointer_ids: Vec<Uuid> = Vec::with_capacity(req.pointer_ids.len());
+    for id in req.pointer_ids {
+        if !pointer_ids.contains(&id) {
+            pointer_ids.push(id);
+        }
+    }
+
+    if pointer_ids.is_empty() {
+        return Err(ApiError::BadRequest(
+            "pointer_ids must not be empty".to_string(),
+        ));
+    }
+
+    let reason = req
+        .reason
+        .unwrap_or_else(|| DEFAULT_ORPHAN_REASON.to_string());
+    check_receipt_field(&state, "reason", &reason)?;
+    let group_id = Uuid::new_v4();
+
+    record_group(&group_id);
+    info!(pointers = pointer_ids.len(), "Orphaning pointer group");
+
+    let mut tx = begin_write(&state).await?;
+
+    // 1. Lock all members and validate them together; all must be the
+    // caller's
+    let locked = lock_pointers_for_update(&mut *tx, &pointer_ids).await?;
+    let org_id = auth.org_id;
+
+    let failures: Vec<GroupMemberFailure> = pointer_ids
+        .iter()
+        .filter_map(|id| {
+            let reason = match locked.iter().find(|p| p.pointer_id == *id) {
+                None => "not_found",
+                Some(p) if matches!(p.status, PointerStatus::Orphaned) => "already_orphaned",
+                Some(p) if p.org_id != org_id => "org_mismatch",
+                Some(p) if p.legal_hold => "legal_hold",
+                Some(p) if p.requires_dual_approval => "dual_approval_required",
+                Some(_) => return None,
+            };
+            Some(GroupMemberFailure {
+                pointer_id: *id,
+                reason: reason.to_string(),
+            })
+        })
+        .collect();
+
+    if !failures.is_empty() {
+        // Dropping the transaction rolls it back
+        return Err(ApiError::OrphanGroupRejected(failures));
+    }
+
+    // 2. Orphan each member with its own chained receipt
+    let mut members = Vec::with_capacity(pointer_ids.len());
+    let mut manifest_members = Vec::with_capacity(pointer_ids.len());
+    let orphaned_at = state.clock.now();
+
+    for pointer_id in &pointer_ids {
+        let orphaned_pointer =
+            queries::orphan_pointer(&mut *tx, *pointer_id, Some(&reason), orphaned_at)
+                .await?
+                .ok_or_else(|| already_orphaned(*pointer_id))?;
+        let lifted = lift_open_restriction(&mut *tx, *pointer_id, "orphaned").await?;
+
+        let prev_hash = get_latest_receipt_hash(&mut *tx, *pointer_id).await?;
+
+        let mut metadata = json!({
+            "reason": reason,
+            "orphaned_at": orphaned_pointer.orphaned_at,
+            "group_id": group_id,
+        });
+        if let Some(lifted) = lifted {
+            metadata["lifted_restriction_id"] = json!(lifted.restriction_id);
+        }
+
+        let mut receipt_data = ReceiptData::new(
+            orphaned_pointer.pointer_id,
+            ReceiptOperation::Orphan,
+            orphaned_pointer.subject_id.clone(),
+            prev_hash.clone(),
+            metadata,
+            orphaned_at,
+        );
+
+        let signed_receipt = sign_receipt(&state, org_id, &mut receipt_data).await?;
+
+        store_receipt(
+            &state,
+            &mut tx,
+            orphaned_pointer.pointer_id,
+            org_id,
+            ReceiptOperation::Orphan,
+            &signed_receipt,
+            prev_hash.as_deref(),
+        )
+        .await?;
+
+        create_audit_log(
+            &mut *tx,
+            Some(org_id),
+            Some(orphaned_pointer.pointer_id),
+            None,
+            "pointer_orphaned",
+            json!({
+                "subject_id": orphaned_pointer.subject_id,
+                "reason": reason,
+                "group_id": group_id,
+            }),
+            ctx.actor_id.as_deref(),
+            ctx.ip_address,
+            ctx.user_agent.as_deref(),
+        )
+        .await?;
+
+        enqueue_webhook_event(
+            &mut *tx,
+            org_id,
+            orphaned_pointer.pointer_id,
+            "pointer_orphaned",
+            &signed_receipt,
+            orphaned_at,
+        )
+        .await?;
+
+        manifest_members.push(GroupMember {
+            pointer_id: orphaned_pointer.pointer_id,
+            receipt_hash: signed_receipt.receipt_hash.clone(),
+        });
+
+        members.push(OrphanGroupMember {
+            pointer_id: orphaned_pointer.pointer_id,
+            orphaned_at: orphaned_pointer
+                .orphaned_at
+                .unwrap_or_else(chrono::Utc::now)
+                .to_rfc3339(),
+            receipt: ReceiptInfo {
+                receipt_hash: signed_receipt.receipt_hash,
+                signature: data_encoding::BASE64.encode(&signed_receipt.signature),
+                signature_algorithm: signed_receipt.signature_algorithm,
+                key_id: signed_receipt.key_id,
+                timestamp: receipt_data.timestamp.to_rfc3339(),
+            },
+        });
+    }
+
+    // 3. Sign and store the group manifest
+    let manifest = OrphanGroupManifest::new(group_id, org_id, reason.clone(), manifest_members);
+    let signer = state.signer(org_id).await?;
+    let signed_manifest = manifest.sign(&signer, state.config.receipt_max_bytes)?;
+    if let Some(overflow) = &signed_manifest.overflow {
+        create_receipt_overflow(&mut *tx, &overflow.overflow_hash, &overflow.content).await?;
+    }
+
+    create_orphan_group(
+        &mut *tx,
+        group_id,
+        org_id,
+        &pointer_ids,
+        &reason,
+        signed_manifest.receipt_json.clone(),
+        &signed_manifest.receipt_hash,
+        &signed_manifest.signature,
+        &signed_manifest.signature_algorithm,
+    )
+    .await?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(org_id),
+        None,
+        None,
+        "orphan_group_created",
+        json!({
+            "group_id": group_id,
+            "member_pointer_ids": pointer_ids,
+            "manifest_hash": signed_manifest.receipt_hash,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    tx.commit().await?;
+    for pointer_id in &pointer_ids {
+        state.pointer_written(pointer_id);
+    }
+    state.webhook_wakeup.notify_one();
+    record_veto(
+        &state.db.writer,
+        org_id,
+        state.clock.now(),
+        started.elapsed(),
+        state.config.veto_slo_target_ms,
+    )
+    .await;
+
+    info!("Orphan group committed");
+
+    Ok(Json(OrphanGroupResponse {
+        group_id,
+        org_id,
+        status: "orphaned".to_string(),
+        members,
+        manifest: ReceiptInfo {
+            receipt_hash: signed_manifest.receipt_hash,
+            signature: data_encoding::BASE64.encode(&signed_manifest.signature),
+            signature_algorithm: signed_manifest.signature_algorithm,
+            key_id: signed_manifest.key_id,
+            timestamp: manifest.timestamp.to_rfc3339(),
+        },
+    }))
+}
+
+// ============================================================================
+// ORPHAN ALL POINTERS FOR A SUBJECT
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct OrphanSubjectRequest {
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrphanSubjectResponse {
+    pub subject_id: String,
+    /// Shared by every receipt and the audit event of this call
+    pub bulk_id: Uuid,
+    pub orphaned: Vec<OrphanGroupMember>,
+    /// Pointers that were already orphaned and were left untouched
+    pub skipped: Vec<Uuid>,
+    pub orphaned_count: usize,
+    pub skipped_count: usize,
+}
+
+/// Veto every active pointer of a subject in one transaction. A subject with
+/// no pointers at all is a 404; one whose pointers are all orphaned already
+/// gets a 200 with nothing orphaned, so repeats are safe.
+#[utoipa::path(
+    post,
+    path = "/api/subject/{id}/orphan-all",
+    tag = "orphans",
+    params(("id" = String, Path, description = "Subject id")),
+    request_body = OrphanSubjectRequest,
+    responses(
+        (status = 200, description = "The subject's active pointers orphaned", body = OrphanSubjectResponse),
+        (status = 400, description = "Reason too large for a receipt", body = ErrorBody),
+        (status = 403, description = "Another org's subject", body = ErrorBody),
+        (status = 404, description = "No pointers for subject", body = ErrorBody),
+        (status = 409, description = "A pointer requires dual approval", body = ErrorBody),
+        (status = 422, description = "Validation failed", body = ErrorBody),
+        (status = 423, description = "A pointer is under a legal hold", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn orphan_subject(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Path(subject_id): Path<String>,
+    Json(req): Json<OrphanSubjectRequest>,
+) -> Result<Json<OrphanSubjectResponse>, ApiError> {
+    let started = std::time::Instant::now();
+    let mut errors = FieldErrors::default();
+    check_reason(&mut errors, req.reason.as_deref());
+    errors.into_result()?;
+
+    let subject_id = SubjectRules::from_config(&state.config).canonical(&subject_id);
+    let reason = req
+        .reason
+        .unwrap_or_else(|| DEFAULT_ORPHAN_REASON.to_string());
+    check_receipt_field(&state, "reason", &reason)?;
+    let bulk_id = Uuid::new_v4();
+
+    record_subject(&subject_id);
+    info!("Orphaning all pointers of subject");
+
+    let mut tx = begin_write(&state).await?;
+
+    // 1. Lock every pointer of the subject so none can change mid-veto
+    let pointers = auth.own(
+        lock_pointers_by_subject(&mut *tx, &subject_id).await?,
+        |p| p.org_id,
+    )?;
+    if pointers.is_empty() {
+        return Err(ApiError::NotFound("No pointers for subject".to_string()));
+    }
+
+    let (active, already): (Vec<Pointer>, Vec<Pointer>) = pointers
+        .into_iter()
+        .partition(|p| !matches!(p.status, PointerStatus::Orphaned));
+    let skipped: Vec<Uuid> = already.iter().map(|p| p.pointer_id).collect();
+
+    // A hold or a dual-approval flag on any of them blocks the whole veto;
+    // nothing is orphaned
+    for pointer in &active {
+        check_legal_hold(pointer)?;
+        check_dual_approval(pointer)?;
+    }
+
+    // 2. Orphan each active pointer with its own chained receipt
+    let mut orphaned = Vec::with_capacity(active.len());
+    let orphaned_at = state.clock.now();
+
+    for pointer in &active {
+        let orphaned_pointer =
+            queries::orphan_pointer(&mut *tx, pointer.pointer_id, Some(&reason), orphaned_at)
+                .await?
+                .ok_or_else(|| already_orphaned(pointer.pointer_id))?;
+        let lifted = lift_open_restriction(&mut *tx, pointer.pointer_id, "orphaned").await?;
+
+        let prev_hash = get_latest_receipt_hash(&mut *tx, pointer.pointer_id).await?;
+
+        let mut metadata = json!({
+            "reason": reason,
+            "orphaned_at": orphaned_pointer.orphaned_at,
+            "bulk_id": bulk_id,
+        });
+        if let Some(lifted) = lifted {
+            metadata["lifted_restriction_id"] = json!(lifted.restriction_id);
+        }
+
+        let mut receipt_data = ReceiptData::new(
+            pointer.pointer_id,
+            ReceiptOperation::Orphan,
+            pointer.subject_id.clone(),
+            prev_hash.clone(),
+            metadata,
+            orphaned_at,
+        );
+        let signed_receipt = sign_receipt(&state, pointer.org_id, &mut receipt_data).await?;
+
+        store_receipt(
+            &state,
+            &mut tx,
+            pointer.pointer_id,
+            pointer.org_id,
+            ReceiptOperation::Orphan,
+            &signed_receipt,
+            prev_hash.as_deref(),
+        )
+        .await?;
+
+        enqueue_webhook_event(
+            &mut *tx,
+            pointer.org_id,
+            pointer.pointer_id,
+            "pointer_orphaned",
+            &signed_receipt,
+            orphaned_at,
+        )
+        .await?;
+
+        orphaned.push(OrphanGroupMember {
+            pointer_id: pointer.pointer_id,
+            orphaned_at: orphaned_at.to_rfc3339(),
+            receipt: ReceiptInfo {
+                receipt_hash: signed_receipt.receipt_hash,
+                signature: data_encoding::BASE64.encode(&signed_receipt.signature),
+                signature_algorithm: signed_receipt.signature_algorithm,
+                key_id: signed_receipt.key_id,
+                timestamp: receipt_data.timestamp.to_rfc3339(),
+            },
+        });
+    }
+
+    // 3. One audit event for the whole operation
+    let orphaned_ids: Vec<Uuid> = orphaned.iter().map(|m| m.pointer_id).collect();
+    let mut org_ids: Vec<Uuid> = active.iter().chain(&already).map(|p| p.org_id).collect();
+    org_ids.sort();
+    org_ids.dedup();
+    let audit_org_id = match org_ids.as_slice() {
+        [org_id] => Some(*org_id),
+        _ => None,
+    };
+
+    create_audit_log(
+        &mut *tx,
+        audit_org_id,
+        None,
+        None,
+        "subject_orphaned",
+        json!({
+            "subject_id": subject_id,
+            "bulk_id": bulk_id,
+            "reason": reason,
+            "orphaned_pointer_ids": orphaned_ids,
+            "skipped_pointer_ids": skipped,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    tx.commit().await?;
+    for pointer_id in &orphaned_ids {
+        state.pointer_written(pointer_id);
+    }
+    state.webhook_wakeup.notify_one();
+
+    let mut vetoed_orgs: Vec<Uuid> = active.iter().map(|p| p.org_id).collect();
+    vetoed_orgs.sort();
+    vetoed_orgs.dedup();
+    for org_id in vetoed_orgs {
+        record_veto(
+            &state.db.writer,
+            org_id,
+            state.clock.now(),
+            started.elapsed(),
+            state.config.veto_slo_target_ms,
+        )
+        .await;
+    }
+
+    info!(
+        orphaned = orphaned.len(),
+        already_orphaned = skipped.len(),
+        "Subject orphaned"
+    );
+
+    Ok(Json(OrphanSubjectResponse {
+        subject_id,
+        bulk_id,
+        orphaned_count: orphaned.len(),
+        skipped_count: skipped.len(),
+        orphaned,
+        skipped,
+    }))
+}
+
+// ============================================================================
+// GET ORPHAN GROUP
+// ============================================================================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GetOrphanGroupResponse {
+    pub group_id: Uuid,
+    pub org_id: Uuid,
+    pub reason: String,
+    pub member_pointer_ids: Vec<Uuid>,
+    pub manifest: serde_json::Value,
+    pub manifest_hash: String,
+    #[schema(format = Byte)]
+    pub signature: String,
+    pub signature_algorithm: String,
+    #[schema(format = DateTime)]
+    pub created_at: String,
+    /// Member list of a manifest too large to carry it, from its overflow
+    /// record
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overflow_members: Option<serde_json::Value>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orphan-groups/{id}",
+    tag = "orphans",
+    params(("id" = Uuid, Path, description = "Orphan group id")),
+    responses(
+        (status = 200, description = "The group's signed manifest", body = GetOrphanGroupResponse),
+        (status = 403, description = "Another org's group", body = ErrorBody),
+        (status = 404, description = "Group not found", body = ErrorBody),
+    )
+)]
+pub async fn get_orphan_group(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(group_id): Path<Uuid>,
+) -> Result<Json<GetOrphanGroupResponse>, ApiError> {
+    record_group(&group_id);
+    info!("Getting orphan group");
+
+    let group = queries::get_orphan_group(&state.db.reader, group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Orphan group not found".to_string()))?;
+    auth.authorize(group.org_id)?;
+
+    let members = &group.manifest_json["members"];
+    let overflow = match overflow_hash(members) {
+        Some(hash) => get_receipt_overflow(&state.db.reader, hash).await?,
+        None => None,
+    };
+    let overflow_members = resolve_overflow(members, overflow.as_deref()).map_err(|_| {
+        ApiError::Internal(format!(
+            "Member list of orphan group {} does not match its manifest",
+            group_id
+        ))
+    })?;
+
+    Ok(Json(GetOrphanGroupResponse {
+        group_id: group.group_id,
+        org_id: group.org_id,
+        reason: group.reason,
+        member_pointer_ids: group.member_pointer_ids,
+        manifest: group.manifest_json,
+        manifest_hash: group.manifest_hash,
+        signature: data_encoding::BASE64.encode(&group.signature),
+        signature_algorithm: group.signature_algorithm,
+        created_at: group.created_at.to_rfc3339(),
+        overflow_members,
+    }))
+}
+
+// ============================================================================
+// ORG HEARTBEATS
+// ============================================================================
+
+/// Default window when `from` is omitted
+const HEARTBEAT_DEFAULT_WINDOW_DAYS: i64 = 7;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HeartbeatsQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HeartbeatSummary {
+    pub heartbeat_id: Uuid,
+    pub kind: String,
+    pub interval_start: String,
+    pub interval_end: String,
+    pub receipt: serde_json::Value,
+    pub receipt_hash: String,
+    #[schema(format = Byte)]
+    pub signature: String,
+    pub signature_algorithm: String,
+    pub prev_hash: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GetOrgHeartbeatsResponse {
+    pub org_id: Uuid,
+    pub heartbeat_interval_seconds: Option<i32>,
+    pub heartbeats: Vec<HeartbeatSummary>,
+    pub verification: HeartbeatVerification,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orgs/{id}/heartbeats",
+    tag = "orgs",
+    params(("id" = Uuid, Path, description = "Organization id"), HeartbeatsQuery),
+    responses(
+        (status = 200, description = "Heartbeats in the window and their verification", body = GetOrgHeartbeatsResponse),
+        (status = 400, description = "Invalid window", body = ErrorBody),
+        (status = 403, description = "Another org", body = ErrorBody),
+        (status = 404, description = "Organization not found", body = ErrorBody),
+    )
+)]
+pub async fn get_org_heartbeats(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<HeartbeatsQuery>,
+) -> Result<Json<GetOrgHeartbeatsResponse>, ApiError> {
+    auth.authorize(org_id)?;
+    let org = get_organization(&state.db.reader, org_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Organization not found".to_string()))?;
+
+    let now = state.clock.now();
+    let to = query.to.unwrap_or(now);
+    let from = query
+        .from
+        .unwrap_or(to - chrono::Duration::days(HEARTBEAT_DEFAULT_WINDOW_DAYS));
+
+    if from >= to {
+        return Err(ApiError::BadRequest("from must be before to".to_string()));
+    }
+
+    let heartbeats = queries::get_org_heartbeats(&state.db.reader, org_id, from, to).await?;
+
+    // The latest complete interval may not be beaten yet, the one before must be
+    let expected = org.heartbeat_interval_seconds.map(|seconds| {
+        let interval = chrono::Duration::seconds(seconds.into());
+        from..interval_floor(to.min(now), interval) - interval
+    });
+    let verification = verify_heartbeats(&heartbeats, expected);
+
+    Ok(Json(GetOrgHeartbeatsResponse {
+        org_id,
+        heartbeat_interval_seconds: org.heartbeat_interval_seconds,
+        heartbeats: heartbeats
+            .into_iter()
+            .map(|h| HeartbeatSummary {
+                heartbeat_id: h.heartbeat_id,
+                kind: h.kind,
+                interval_start: h.interval_start.to_rfc3339(),
+                interval_end: h.interval_end.to_rfc3339(),
+                receipt: h.receipt_json,
+                receipt_hash: h.receipt_hash,
+                signature: data_encoding::BASE64.encode(&h.signature),
+                signature_algorithm: h.signature_algorithm,
+                prev_hash: h.prev_hash,
+            })
+            .collect(),
+        verification,
+    }))
+}
+
+// ============================================================================
+// ORG STATS
+// ============================================================================
+
+/// Days in the created-per-day series when `from` is omitted
+const ORG_STATS_DEFAULT_DAYS: i64 = 30;
+/// Longest created-per-day series one request may ask for
+const ORG_STATS_MAX_DAYS: i64 = 366;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OrgStatsQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Dashboard aggregates. Without a range the counts cover all time and the
+/// daily series the last 30 days.
+#[utoipa::path(
+    get,
+    path = "/api/orgs/{id}/stats",
+    tag = "orgs",
+    params(("id" = Uuid, Path, description = "Organization id"), OrgStatsQuery),
+    responses(
+        (status = 200, description = "The org's pointer and receipt statistics", body = OrgStats),
+        (status = 400, description = "Invalid window", body = ErrorBody),
+        (status = 403, description = "Another org", body = ErrorBody),
+        (status = 404, description = "Organization not found", body = ErrorBody),
+    )
+)]
+pub async fn get_org_stats(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<OrgStatsQuery>,
+) -> Result<Json<OrgStats>, ApiError> {
+    auth.authorize(org_id)?;
+    if get_organization(&state.db.reader, org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Organization not found".to_string()));
+    }
+
+    let series_to = query.to.unwrap_or_else(|| state.clock.now());
+    let series_from = query
+        .from
+        .unwrap_or(series_to - chrono::Duration::days(ORG_STATS_DEFAULT_DAYS));
+    if series_from >= series_to {
+        return Err(ApiError::BadRequest("from must be before to".to_string()));
+    }
+    if series_to - series_from > chrono::Duration::days(ORG_STATS_MAX_DAYS) {
+        return Err(ApiError::BadRequest(format!(
+            "Range must be at most {} days",
+            ORG_STATS_MAX_DAYS
+        )));
+    }
+
+    let pool = &state.db.reader;
+    let (pointers, created_per_day, receipts_by_operation, median_seconds_to_orphan) = tokio::try_join!(
+        get_org_pointer_counts(pool, org_id, query.from, query.to),
+        get_org_pointers_per_day(pool, org_id, series_from, series_to),
+        get_org_receipts_by_operation(pool, org_id, query.from, query.to),
+        get_org_median_seconds_to_orphan(pool, org_id, query.from, query.to),
+    )?;
+
+    Ok(Json(OrgStats {
+        org_id,
+        from: query.from,
+        to: query.to,
+        pointers,
+        created_per_day,
+        receipts_by_operation: receipts_by_operation.into_iter().collect(),
+        median_seconds_to_orphan,
+    }))
+}
+
+// ============================================================================
+// GET RECEIPTS
+// ============================================================================
+
+const PAGE_DEFAULT_LIMIT: i64 = 100;
+const PAGE_MAX_LIMIT: i64 = 1000;
+
+/// Values accepted by the receipts `operation` filter
+const RECEIPT_OPERATIONS: [&str; 10] = [
+    "create",
+    "resolve",
+    "orphan",
+    "denied",
+    "merge",
+    "restrict",
+    "unrestrict",
+    "repair",
+    "data_swap",
+    "chain_freeze_review",
+];
+
+/// Cursor is `<timestamp in microseconds>.<id>` of the last row returned
+fn parse_page_cursor(cursor: &str) -> Option<(chrono::DateTime<chrono::Utc>, Uuid)> {
+    let (micros, id) = cursor.split_once('.')?;
+    let timestamp = chrono::DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+    Some((timestamp, id.parse().ok()?))
+}
+
+fn page_cursor(timestamp: chrono::DateTime<chrono::Utc>, id: Uuid) -> String {
+    format!("{}.{}", timestamp.timestamp_micros(), id)
+}
+
+fn pagination(limit: Option<i64>, cursor: Option<&str>) -> Result<Pagination, ApiError> {
+    let after = match cursor {
+        Some(cursor) => Some(
+            parse_page_cursor(cursor)
+                .ok_or_else(|| ApiError::BadRequest("Invalid page cursor".to_string()))?,
+        ),
+        None => None,
+    };
+
+    Ok(Pagination {
+        after,
+        limit: limit.unwrap_or(PAGE_DEFAULT_LIMIT).clamp(1, PAGE_MAX_LIMIT),
+    })
+}
+
+/// Fetch one row past the page, then drop it: its presence is what says
+/// another page follows
+fn page_rows<T>(
+    mut rows: Vec<T>,
+    page: &Pagination,
+    key: impl Fn(&T) -> (chrono::DateTime<chrono::Utc>, Uuid),
+) -> (Vec<T>, Option<String>) {
+    if rows.len() as i64 <= page.limit {
+        return (rows, None);
+    }
+    rows.truncate(page.limit as usize);
+    let next_cursor = rows.last().map(|row| {
+        let (timestamp, id) = key(row);
+        page_cursor(timestamp, id)
+    });
+    (rows, next_cursor)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/receipts/{id}",
+    tag = "receipts",
+    params(("id" = Uuid, Path, description = "Pointer id"), ReceiptsQuery),
+    responses(
+        (status = 200, description = "A page of the pointer's receipts, newest first", body = GetReceiptsResponse),
+        (status = 400, description = "Unknown operation or bad cursor", body = ErrorBody),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+    )
+)]
+pub async fn get_receipts(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(pointer_id): Path<Uuid>,
+    Query(query): Query<ReceiptsQuery>,
+) -> Result<Json<GetReceiptsResponse>, ApiError> {
+    record_pointer(&pointer_id);
+    info!("Getting receipts");
+
+    // Strict reads record the failures they find, so they read what they
+    // report from the primary
+    let strict = state.config.strict_receipt_reads;
+    let pool = if strict {
+        &state.db.writer
+    } else {
+        &state.db.reader
+    };
+
+    // An unknown pointer simply has no receipts
+    if let Some(pointer) = get_pointer(pool, pointer_id).await? {
+        auth.authorize(pointer.org_id)?;
+    }
+
+    if let Some(operation) = &query.operation {
+        if !RECEIPT_OPERATIONS.contains(&operation.as_str()) {
+            return Err(ApiError::BadRequest(format!(
+                "operation must be one of: {}",
+                RECEIPT_OPERATIONS.join(", ")
+            )));
+        }
+    }
+    let page = pagination(query.limit, query.cursor.as_deref())?;
+
+    let receipts = get_receipts_by_pointer_page(
+        pool,
+        pointer_id,
+        query.operation.as_deref(),
+        &Pagination {
+            limit: page.limit + 1,
+            ..page
+        },
+    )
+    .await?;
+    let (receipts, next_cursor) = page_rows(receipts, &page, |r| (r.timestamp, r.receipt_id));
+    let keys = if strict {
+        receipt_keys(pool, &state.signing_keys, &receipts).await?
+    } else {
+        KeySet::default()
+    };
+
+    let mut entries = Vec::with_capacity(receipts.len());
+    let mut integrity_verified = true;
+    for r in receipts {
+        if strict {
+            let (verified, fresh) = state.receipt_verifier.verify(&r, &keys);
+            if let Err(failure) = verified {
+                integrity_verified = false;
+                // Cached failures were already reported
+                if fresh {
+                    report_receipt_failure(&state, &r, failure).await?;
+                }
+                entries.push(ReceiptEntry::Tombstone(ReceiptTombstone {
+                    receipt_id: r.receipt_id,
+                    failure,
+                }));
+                continue;
+            }
+        }
+
+        let overflow_metadata =
+            resolve_overflow(&r.receipt_json["metadata"], r.overflow.as_deref()).unwrap_or(None);
+        entries.push(ReceiptEntry::Receipt(ReceiptSummary {
+            operation: r.operation.as_str().to_string(),
+            receipt_hash: r.receipt_hash,
+            signature: data_encoding::BASE64.encode(&r.signature),
+            key_id: r.key_id,
+            prev_hash: r.prev_hash,
+            timestamp: r.timestamp.to_rfc3339(),
+            overflow_metadata,
+        }));
+    }
+
+    Ok(Json(GetReceiptsResponse {
+        pointer_id,
+        receipts: entries,
+        integrity_verified: strict.then_some(integrity_verified),
+        next_cursor,
+    }))
+}
+
+async fn report_receipt_failure(
+    state: &AppState,
+    receipt: &GovernanceReceipt,
+    failure: ReceiptFailure,
+) -> Result<(), ApiError> {
+    warn!(
+        receipt_id = %receipt.receipt_id,
+        receipt_pointer_id = %receipt.pointer_id,
+        failure = ?failure,
+        "ALERT receipt failed verification"
+    );
+
+    create_audit_log(
+        &state.db.writer,
+        Some(receipt.org_id),
+        Some(receipt.pointer_id),
+        Some(receipt.receipt_id),
+        "receipt_verification_failed",
+        json!({
+            "receipt_hash": receipt.receipt_hash,
+            "failure": failure,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+// ============================================================================
+// VERIFY RECEIPT CHAIN
+// ============================================================================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerifyChainResponse {
+    pub pointer_id: Uuid,
+    #[serde(flatten)]
+    pub verification: ChainVerification,
+}
+
+/// Recompute every receipt's hash, check its signature against the key that
+/// signed it, and check each prev_hash links to the receipt before it
+#[utoipa::path(
+    get,
+    path = "/api/receipts/{id}/verify",
+    tag = "receipts",
+    params(("id" = Uuid, Path, description = "Pointer id")),
+    responses(
+        (status = 200, description = "Each receipt's check and the chain's links", body = VerifyChainResponse),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+    )
+)]
+pub async fn verify_receipt_chain(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(pointer_id): Path<Uuid>,
+) -> Result<Json<VerifyChainResponse>, ApiError> {
+    record_pointer(&pointer_id);
+    info!("Verifying receipt chain");
+
+    let pointer = get_pointer(&state.db.reader, pointer_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    let receipts = get_receipts_by_pointer(&state.db.reader, pointer_id).await?;
+    let keys = receipt_keys(&state.db.reader, &state.signing_keys, &receipts).await?;
+    let verification = verify_chain(&receipts, &keys);
+
+    if !verification.chain_valid {
+        warn!("Receipt chain failed verification");
+    }
+
+    Ok(Json(VerifyChainResponse {
+        pointer_id,
+        verification,
+    }))
+}
+
+// ============================================================================
+// VERIFY SUBMITTED RECEIPT
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerifySubmittedReceiptRequest {
+    /// The receipt object, or its JSON text. Canonicalized before hashing,
+    /// so whitespace and member order need not match what was signed.
+    pub receipt_json: serde_json::Value,
+    pub receipt_hash: String,
+    #[schema(format = Byte)]
+    pub signature_base64: String,
+    #[serde(default = "default_signature_algorithm")]
+    pub signature_algorithm: String,
+    /// Check against this key instead of the ones this service publishes
+    #[schema(format = Byte)]
+    pub public_key_base64: Option<String>,
+}
+
+fn default_signature_algorithm() -> String {
+    "ED25519".to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// public_key_base64 from the request
+    Supplied,
+    /// One of this service's signing keys
+    Service,
+    /// An organization's signing key
+    Organization,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerifySubmittedReceiptResponse {
+    pub valid: bool,
+    #[serde(flatten)]
+    pub result: VerificationResult,
+    /// Where the key checked against came from; null when none is known
+    /// for the receipt's key_id
+    pub key_source: Option<KeySource>,
+    pub key_id: Option<String>,
+    /// Owner of the key, when it is an organization's
+    pub org_id: Option<Uuid>,
+}
+
+fn decode_public_key(encoded: &str) -> Result<ed25519_dalek::VerifyingKey, ApiError> {
+    data_encoding::BASE64
+        .decode(encoded.trim().as_bytes())
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or_else(|| {
+            ApiError::BadRequest("public_key_base64 is not a base64 ED25519 public key".to_string())
+        })
+}
+
+/// Verify a receipt handed over out of band, such as an exported or
+/// emailed one, against a supplied public key or, without one, the key this
+/// service recorded under the receipt's key_id. Touches no pointer, so it
+/// needs no API key.
+#[utoipa::path(
+    post,
+    path = "/api/receipts/verify",
+    tag = "receipts",
+    request_body = VerifySubmittedReceiptRequest,
+    security(()),
+    responses(
+        (status = 200, description = "The receipt checked, valid or not", body = VerifySubmittedReceiptResponse),
+        (status = 400, description = "Malformed receipt, signature or key", body = ErrorBody),
+    )
+)]
+pub async fn verify_submitted_receipt(
+    State(state): State<AppState>,
+    Json(req): Json<VerifySubmittedReceiptRequest>,
+) -> Result<Json<VerifySubmittedReceiptResponse>, ApiError> {
+    let receipt_json = match req.receipt_json {
+        serde_json::Value::String(text) => serde_json::from_str(&text)
+            .map_err(|e| ApiError::BadRequest(format!("receipt_json is not valid JSON: {}", e)))?,
+        value => value,
+    };
+    let signature = data_encoding::BASE64
+        .decode(req.signature_base64.trim().as_bytes())
+        .map_err(|_| ApiError::BadRequest("signature_base64 is not valid base64".to_string()))?;
+
+    let receipt_key_id = receipt_json
+        .get("key_id")
+        .and_then(|id| id.as_str())
+        .map(str::to_string);
+    let mut keys = KeySet::default();
+    let (key_source, key_id, org_id) = match &req.public_key_base64 {
+        Some(encoded) => {
+            let key = decode_public_key(encoded)?;
+            keys.insert(key);
+            (Some(KeySource::Supplied), Some(key_id(&key)), None)
+        }
+        None => match receipt_key_id {
+            Some(id) => match known_key(&state.db.reader, &state.signing_keys, &id).await? {
+                Some(known) => {
+                    keys.insert(known.key);
+                    let source = match known.org_id {
+                        Some(_) => KeySource::Organization,
+                        None => KeySource::Service,
+                    };
+                    (Some(source), Some(id), known.org_id)
+                }
+                None => (None, Some(id), None),
+            },
+            // From before key ids were recorded: any service key may have
+            // signed it
+            None => {
+                keys = state.signing_keys.key_set();
+                (Some(KeySource::Service), None, None)
+            }
+        },
+    };
+
+    let result = verify_receipt(
+        &receipt_json,
+        &req.receipt_hash,
+        &req.signature_algorithm,
+        &signature,
+        &keys,
+    );
+
+    Ok(Json(VerifySubmittedReceiptResponse {
+        valid: result.valid(),
+        result,
+        key_source,
+        key_id,
+        org_id,
+    }))
+}
+
+// ============================================================================
+// RECEIPT CHECKPOINTS
+// ============================================================================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CheckpointSummary {
+    pub checkpoint_id: Uuid,
+    pub sequence: i64,
+    /// End of the previous checkpoint's period; null on the first
+    pub period_start: Option<String>,
+    pub period_end: String,
+    pub receipt_count: i32,
+    pub merkle_root: String,
+    pub receipt: serde_json::Value,
+    pub receipt_hash: String,
+    #[schema(format = Byte)]
+    pub signature: String,
+    pub signature_algorithm: String,
+    pub key_id: String,
+    pub prev_hash: Option<String>,
+    #[schema(format = DateTime)]
+    pub created_at: String,
+}
+
+impl From<ReceiptCheckpoint> for CheckpointSummary {
+    fn from(c: ReceiptCheckpoint) -> Self {
+        Self {
+            checkpoint_id: c.checkpoint_id,
+            sequence: c.sequence,
+            period_start: c.period_start.map(|t| t.to_rfc3339()),
+            period_end: c.period_end.to_rfc3339(),
+            receipt_count: c.receipt_count,
+            merkle_root: c.merkle_root,
+            receipt: c.receipt_json,
+            receipt_hash: c.receipt_hash,
+            signature: data_encoding::BASE64.encode(&c.signature),
+            signature_algorithm: c.signature_algorithm,
+            key_id: c.key_id,
+            prev_hash: c.prev_hash,
+            created_at: c.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListCheckpointsQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// `next_cursor` from the previous page; omit for the first
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListCheckpointsResponse {
+    pub checkpoints: Vec<CheckpointSummary>,
+    /// Set when older checkpoints follow this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Signed receipt checkpoints, newest first
+#[utoipa::path(
+    get,
+    path = "/api/checkpoints",
+    tag = "receipts",
+    params(ListCheckpointsQuery),
+    responses(
+        (status = 200, description = "Signed checkpoints, newest first", body = ListCheckpointsResponse),
+    )
+)]
+pub async fn list_checkpoints(
+    State(state): State<AppState>,
+    _auth: AuthContext,
+    Query(query): Query<ListCheckpointsQuery>,
+) -> Result<Json<ListCheckpointsResponse>, ApiError> {
+    let page = pagination(query.limit, query.cursor.as_deref())?;
+    let checkpoints = queries::list_receipt_checkpoints(
+        &state.db.reader,
+        &Pagination {
+            limit: page.limit + 1,
+            ..page
+        },
+    )
+    .await?;
+    let (checkpoints, next_cursor) =
+        page_rows(checkpoints, &page, |c| (c.created_at, c.checkpoint_id));
+
+    Ok(Json(ListCheckpointsResponse {
+        checkpoints: checkpoints.into_iter().map(Into::into).collect(),
+        next_cursor,
+    }))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReceiptProofResponse {
+    #[serde(flatten)]
+    pub proof: InclusionProof,
+    /// The checkpoint whose signed merkle_root the path leads to
+    pub checkpoint: CheckpointSummary,
+}
+
+/// Merkle inclusion path from a receipt to the signed root of the
+/// checkpoint covering it
+#[utoipa::path(
+    get,
+    path = "/api/receipts/{id}/proof",
+    tag = "receipts",
+    params(
+        ("id" = String, Path, description = "Receipt hash, 128 hex characters"),
+    ),
+    responses(
+        (status = 200, description = "Inclusion proof and the checkpoint it leads to", body = ReceiptProofResponse),
+        (status = 400, description = "Not a SHA3-512 hex hash", body = ErrorBody),
+        (status = 403, description = "Another org's receipt", body = ErrorBody),
+        (status = 404, description = "Receipt not found, or no checkpoint covers it yet", body = ErrorBody),
+    )
+)]
+pub async fn get_receipt_proof(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(receipt_hash): Path<String>,
+) -> Result<Json<ReceiptProofResponse>, ApiError> {
+    if !is_sha3_512_hex(&receipt_hash) {
+        return Err(ApiError::BadRequest(
+            "receipt_hash must be 128 lowercase hex characters".to_string(),
+        ));
+    }
+
+    let receipt = get_receipt_by_hash(&state.db.reader, &receipt_hash)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Receipt not found".to_string()))?;
+    auth.authorize(receipt.org_id)?;
+
+    let (proof, checkpoint) = inclusion_proof(&state.db.reader, receipt.receipt_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound("Receipt is not covered by a checkpoint yet".to_string())
+        })?;
+
+    Ok(Json(ReceiptProofResponse {
+        proof,
+        checkpoint: checkpoint.into(),
+    }))
+}
+
+/// Shortest prefix accepted by a receipt lookup with `prefix=true`
+const MIN_RECEIPT_HASH_PREFIX: usize = 16;
+/// Most candidates listed for an ambiguous prefix
+const MAX_RECEIPT_HASH_MATCHES: i64 = 20;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReceiptByHashQuery {
+    /// Match receipts whose hash starts with the given one
+    #[serde(default)]
+    pub prefix: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReceiptByHashResponse {
+    pub receipt_id: Uuid,
+    pub pointer_id: Uuid,
+    pub operation: String,
+    /// The signed receipt as stored
+    pub receipt_json: serde_json::Value,
+    pub receipt_hash: String,
+    #[schema(format = Byte)]
+    pub signature: String,
+    pub signature_algorithm: String,
+    /// Key that signed it; null on receipts from before key ids were recorded
+    pub key_id: Option<String>,
+    pub prev_hash: Option<String>,
+    #[schema(format = DateTime)]
+    pub timestamp: String,
+}
+
+impl From<GovernanceReceipt> for ReceiptByHashResponse {
+    fn from(receipt: GovernanceReceipt) -> Self {
+        Self {
+            receipt_id: receipt.receipt_id,
+            pointer_id: receipt.pointer_id,
+            operation: receipt.operation.as_str().to_string(),
+            receipt_json: receipt.receipt_json,
+            receipt_hash: receipt.receipt_hash,
+            signature: data_encoding::BASE64.encode(&receipt.signature),
+            signature_algorithm: receipt.signature_algorithm,
+            key_id: receipt.key_id,
+            prev_hash: receipt.prev_hash,
+            timestamp: receipt.timestamp.to_rfc3339(),
+        }
+    }
+}
+
+/// Body of a 300 answer to a prefix matching more than one receipt
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReceiptHashMatchesResponse {
+    pub error: String,
+    pub message: String,
+    pub matches: Vec<ReceiptHashMatch>,
+    /// Set when more receipts match than are listed
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReceiptHashMatch {
+    pub receipt_hash: String,
+    pub pointer_id: Uuid,
+    pub operation: String,
+    #[schema(format = DateTime)]
+    pub timestamp: String,
+}
+
+/// The full receipt with a given hash. With `prefix=true` the hash may be
+/// cut down to 16 characters; a prefix shared by several of the caller's
+/// receipts is answered with 300 and the candidates.
+#[utoipa::path(
+    get,
+    path = "/api/receipts/by-hash/{receipt_hash}",
+    tag = "receipts",
+    params(
+        ("receipt_hash" = String, Path, description = "Full receipt hash, or a prefix with `prefix=true`"),
+        ReceiptByHashQuery,
+    ),
+    responses(
+        (status = 200, description = "The receipt", body = ReceiptByHashResponse),
+        (status = 300, description = "The prefix matches more than one receipt", body = ReceiptHashMatchesResponse),
+        (status = 400, description = "Not a full hash, or a prefix too short", body = ErrorBody),
+        (status = 403, description = "Another org's receipt", body = ErrorBody),
+        (status = 404, description = "No receipt matches", body = ErrorBody),
+    )
+)]
+pub async fn get_receipt_by_hash_handler(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(receipt_hash): Path<String>,
+    Query(query): Query<ReceiptByHashQuery>,
+) -> Result<axum::response::Response, ApiError> {
+    use axum::response::IntoResponse;
+
+    if !query.prefix {
+        if !is_sha3_512_hex(&receipt_hash) {
+            return Err(ApiError::BadRequest(
+                "receipt_hash must be 128 lowercase hex characters".to_string(),
+            ));
+        }
+        let receipt = get_receipt_by_hash(&state.db.reader, &receipt_hash)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("Receipt not found".to_string()))?;
+        auth.authorize(receipt.org_id)?;
+        record_pointer(&receipt.pointer_id);
+        return Ok(Json(ReceiptByHashResponse::from(receipt)).into_response());
+    }
+
+    if !(MIN_RECEIPT_HASH_PREFIX..=128).contains(&receipt_hash.len())
+        || !receipt_hash
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "receipt_hash prefix must be {} to 128 lowercase hex characters",
+            MIN_RECEIPT_HASH_PREFIX
+        )));
+    }
+
+    let mut receipts = get_receipts_by_hash_prefix(
+        &state.db.reader,
+        auth.org_id,
+        &receipt_hash,
+        MAX_RECEIPT_HASH_MATCHES + 1,
+    )
+    .await?;
+    match receipts.len() {
+        0 => Err(ApiError::NotFound("Receipt not found".to_string())),
+        1 => {
+            let receipt = receipts.remove(0);
+            record_pointer(&receipt.pointer_id);
+            Ok(Json(ReceiptByHashResponse::from(receipt)).into_response())
+        }
+        found => {
+            let truncated = found as i64 > MAX_RECEIPT_HASH_MATCHES;
+            receipts.truncate(MAX_RECEIPT_HASH_MATCHES as usize);
+            let matches = receipts
+                .into_iter()
+                .map(|r| ReceiptHashMatch {
+                    receipt_hash: r.receipt_hash,
+                    pointer_id: r.pointer_id,
+                    operation: r.operation.as_str().to_string(),
+                    timestamp: r.timestamp.to_rfc3339(),
+                })
+                .collect();
+            Ok((
+                StatusCode::MULTIPLE_CHOICES,
+                Json(ReceiptHashMatchesResponse {
+                    error: "ambiguous_receipt_hash".to_string(),
+                    message: "receipt_hash prefix matches more than one receipt".to_string(),
+                    matches,
+                    truncated,
+                }),
+            )
+                .into_response())
+        }
+    }
+}
+
+// ============================================================================
+// VETO SLO
+// ============================================================================
+
+/// Default report length when `from` is omitted
+const DEFAULT_SLO_DAYS: i64 = 30;
+/// Longest report served in one request
+const MAX_SLO_DAYS: i64 = 366;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VetoSloQuery {
+    pub org_id: Option<Uuid>,
+    pub from: Option<chrono::NaiveDate>,
+    pub to: Option<chrono::NaiveDate>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VetoSloResponse {
+    /// Target new vetoes are measured against
+    pub target_ms: u32,
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub days: Vec<VetoSloSummary>,
+}
+
+/// Per-org daily veto latency compliance, from the persisted tallies. The
+/// operator org sees any org, or every org when none is named; other orgs
+/// see only their own.
+#[utoipa::path(
+    get,
+    path = "/api/admin/slo",
+    tag = "admin",
+    params(VetoSloQuery),
+    responses(
+        (status = 200, description = "Daily veto latency compliance", body = VetoSloResponse),
+        (status = 400, description = "Invalid window", body = ErrorBody),
+        (status = 403, description = "Another org's report", body = ErrorBody),
+    )
+)]
+pub async fn get_veto_slo(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(query): Query<VetoSloQuery>,
+) -> Result<Json<VetoSloResponse>, ApiError> {
+    let org_id = if authorize_operator(&state, &auth).is_ok() {
+        query.org_id
+    } else {
+        auth.authorize(query.org_id.unwrap_or(auth.org_id))?;
+        Some(auth.org_id)
+    };
+    let to = query.to.unwrap_or_else(|| state.clock.now().date_naive());
+    let from = query
+        .from
+        .unwrap_or(to - chrono::Duration::days(DEFAULT_SLO_DAYS - 1));
+
+    if from > to {
+        return Err(ApiError::BadRequest(
+            "from must not be after to".to_string(),
+        ));
+    }
+    if (to - from).num_days() >= MAX_SLO_DAYS {
+        return Err(ApiError::BadRequest(format!(
+            "Reports cover at most {} days",
+            MAX_SLO_DAYS
+        )));
+    }
+
+    let days = get_veto_slo_days(&state.db.reader, org_id, from, to).await?;
+
+    Ok(Json(VetoSloResponse {
+        target_ms: state.config.veto_slo_target_ms,
+        from,
+        to,
+        days: days.into_iter().map(VetoSloSummary::from).collect(),
+    }))
+}
+
+/// Today's veto SLO tallies as OpenMetrics gauges, plus degraded-read
+/// counters. Those matter most during an outage, so they are still served
+/// (without the tallies) when the database is unreachable. They cover every
+/// org, so only the operator org scrapes them.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "admin",
+    responses(
+        (status = 200, description = "OpenMetrics exposition", body = String, content_type = "application/openmetrics-text"),
+        (status = 403, description = "Not the operator org", body = ErrorBody),
+    )
+)]
+pub async fn get_metrics(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<impl axum::response::IntoResponse, ApiError> {
+    authorize_operator(&state, &auth)?;
+    let today = state.clock.now().date_naive();
+    let days = if state.db_circuit.is_open() {
+        None
+    } else {
+        let fetched = get_veto_slo_days(&state.db.reader, None, today, today).await;
+        state.db_circuit.observe(fetched)?
+    };
+
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, OPENMETRICS_CONTENT_TYPE)],
+        render_openmetrics(
+            &days.unwrap_or_default(),
+            &state.last_known.stats(),
+            &state.receipt_metrics,
+            &state.rate_limiter,
+            &state.pointer_cache,
+            &state.compression_metrics,
+        ),
+    ))
+}
+
+// ============================================================================
+// RETENTION REPORT
+// ============================================================================
+
+/// Default look-ahead when `days` is omitted
+const DEFAULT_RETENTION_REPORT_DAYS: i64 = 30;
+/// Longest look-ahead served in one request
+const MAX_RETENTION_REPORT_DAYS: i64 = 366;
+/// Most pointers listed in one report
+const MAX_RETENTION_REPORT_ROWS: i64 = 1000;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RetentionReportQuery {
+    #[serde(default)]
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RetentionReportResponse {
+    /// Forced expirations listed are due before this time
+    pub until: String,
+    pub classes: Vec<RetentionClassReport>,
+    /// True when more expirations are due than the report lists
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RetentionClassReport {
+    /// None for data expiring only because its creator asked
+    pub retention_class: Option<String>,
+    pub max_age_seconds: Option<i64>,
+    pub count: usize,
+    pub expirations: Vec<RetentionExpiration>,
+}
+
+/// Active pointers of every org the retention sweeper will orphan in the
+/// next `days`, soonest first, grouped by class. Every configured class is
+/// listed. Only the operator org may see it.
+#[utoipa::path(
+    get,
+    path = "/api/admin/retention",
+    tag = "admin",
+    params(RetentionReportQuery),
+    responses(
+        (status = 200, description = "Pointers due to expire, by retention class", body = RetentionReportResponse),
+        (status = 400, description = "Invalid window", body = ErrorBody),
+        (status = 403, description = "Not the operator org", body = ErrorBody),
+    )
+)]
+pub async fn get_retention_report(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(query): Query<RetentionReportQuery>,
+) -> Result<Json<RetentionReportResponse>, ApiError> {
+    authorize_operator(&state, &auth)?;
+    let days = query.days.unwrap_or(DEFAULT_RETENTION_REPORT_DAYS);
+    if !(1..=MAX_RETENTION_REPORT_DAYS).contains(&days) {
+        return Err(ApiError::BadRequest(format!(
+            "days must be between 1 and {}",
+            MAX_RETENTION_REPORT_DAYS
+        )));
+    }
+    let until = state.clock.now() + chrono::Duration::days(days);
+
+    let mut upcoming =
+        get_upcoming_retention_expirations(&state.db.reader, until, MAX_RETENTION_REPORT_ROWS + 1)
+            .await?;
+    let truncated = upcoming.len() as i64 > MAX_RETENTION_REPORT_ROWS;
+    upcoming.truncate(MAX_RETENTION_REPORT_ROWS as usize);
+
+    let mut classes: Vec<RetentionClassReport> = state
+        .config
+        .retention_classes
+        .iter()
+        .map(|(class, max_age_seconds)| RetentionClassReport {
+            retention_class: Some(class.to_string()),
+            max_age_seconds: Some(max_age_seconds),
+            count: 0,
+            expirations: Vec::new(),
+        })
+        .collect();
+    for expiration in upcoming {
+        let report = match classes
+            .iter_mut()
+            .position(|c| c.retention_class == expiration.retention_class)
+        {
+            Some(i) => &mut classes[i],
+            // Unclassified, or a class since dropped from the config
+            None => {
+                classes.push(RetentionClassReport {
+                    retention_class: expiration.retention_class.clone(),
+                    max_age_seconds: None,
+                    count: 0,
+                    expirations: Vec::new(),
+                });
+                classes.last_mut().unwrap()
+            }
+        };
+        report.count += 1;
+        report.expirations.push(expiration);
+    }
+
+    Ok(Json(RetentionReportResponse {
+        until: until.to_rfc3339(),
+        classes,
+        truncated,
+    }))
+}
+
+// ============================================================================
+// GET ACCESS STATS
+// ============================================================================
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AccessStatsQuery {
+    #[serde(default)]
+    pub hours: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccessStatsResponse {
+    pub pointer_id: Uuid,
+    pub window_hours: i64,
+    pub total_resolves: i64,
+    pub buckets: Vec<AccessStatsBucket>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccessStatsBucket {
+    pub bucket_start: String,
+    pub resolve_count: i64,
+    pub spike_alerted: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/pointer/{id}/access-stats",
+    tag = "pointers",
+    params(("id" = Uuid, Path, description = "Pointer id"), AccessStatsQuery),
+    responses(
+        (status = 200, description = "Resolve counts per bucket", body = AccessStatsResponse),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+    )
+)]
+pub async fn get_access_stats(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(pointer_id): Path<Uuid>,
+    Query(query): Query<AccessStatsQuery>,
+) -> Result<Json<AccessStatsResponse>, ApiError> {
+    record_pointer(&pointer_id);
+    info!("Getting access stats");
+
+    let window_hours = query.hours.unwrap_or(24).clamp(1, 24 * 30);
+
+    let pointer = get_pointer(&state.db.reader, pointer_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Pointer not found".to_string()))?;
+    auth.authorize(pointer.org_id)?;
+
+    let since = state.clock.now() - chrono::Duration::hours(window_hours - 1);
+    let stats = get_resolve_stats(&state.db.reader, pointer_id, since).await?;
+
+    Ok(Json(AccessStatsResponse {
+        pointer_id,
+        window_hours,
+        total_resolves: stats.iter().map(|s| s.resolve_count).sum(),
+        buckets: stats
+            .into_iter()
+            .map(|s| AccessStatsBucket {
+                bucket_start: s.bucket_start.to_rfc3339(),
+                resolve_count: s.resolve_count,
+                spike_alerted: s.alerted_at.is_some(),
+            })
+            .collect(),
+    }))
+}
+
+// ============================================================================
+// RESOLVE COALESCING STATS
+// ============================================================================
+
+/// Counters since startup for this process, across every org: resolve
+/// fetches issued versus resolves served from a concurrent request's fetch
+#[utoipa::path(
+    get,
+    path = "/api/stats/resolve-coalescing",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Resolve fetch coalescing counters", body = SingleFlightStats),
+        (status = 403, description = "Not the operator org", body = ErrorBody),
+    )
+)]
+pub async fn get_resolve_coalescing_stats(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<Json<SingleFlightStats>, ApiError> {
+    authorize_operator(&state, &auth)?;
+    Ok(Json(state.resolve_flights.stats()))
+}
+
+// ============================================================================
+// POINTER CHANGES FEED
+// ============================================================================
+
+const CHANGES_DEFAULT_LIMIT: i64 = 100;
+const CHANGES_MAX_LIMIT: i64 = 1000;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PointerChangesQuery {
+    /// Opaque cursor from a previous page's `next_cursor`; omit to start over
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PointerChangesResponse {
+    pub changes: Vec<PointerChangeRecord>,
+    pub next_cursor: String,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PointerChangeRecord {
+    pub pointer_id: Uuid,
+    pub org_id: Uuid,
+    pub subject_id: String,
+    /// "orphaned" for vetoed pointers, "upsert" for anything else
+    pub change: String,
+    pub status: String,
+    #[schema(format = DateTime)]
+    pub orphaned_at: Option<String>,
+    pub metadata: serde_json::Value,
+    #[schema(format = DateTime)]
+    pub updated_at: String,
+}
+
+/// Cursor is `<change_xid>.<pointer_id>` of the last change returned
+fn parse_changes_cursor(cursor: &str) -> Option<(u64, Uuid)> {
+    let (xid, pointer_id) = cursor.split_once('.')?;
+    Some((xid.parse().ok()?, pointer_id.parse().ok()?))
+}
+
+fn changes_cursor(xid: u64, pointer_id: Uuid) -> String {
+    format!("{}.{}", xid, pointer_id)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/pointers/changes",
+    tag = "pointers",
+    params(PointerChangesQuery),
+    responses(
+        (status = 200, description = "Pointer changes after the cursor, oldest first", body = PointerChangesResponse),
+        (status = 400, description = "Bad cursor or limit", body = ErrorBody),
+    )
+)]
+pub async fn get_pointer_changes(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(query): Query<PointerChangesQuery>,
+) -> Result<Json<PointerChangesResponse>, ApiError> {
+    let (after_xid, after_pointer_id) = match query.since.as_deref() {
+        Some(cursor) => parse_changes_cursor(cursor)
+            .ok_or_else(|| ApiError::BadRequest("Invalid changes cursor".to_string()))?,
+        None => (0, Uuid::nil()),
+    };
+    let limit = query
+        .limit
+        .unwrap_or(CHANGES_DEFAULT_LIMIT)
+        .clamp(1, CHANGES_MAX_LIMIT);
+
+    // Fetch one extra row to learn whether another page follows. Cursors
+    // are transaction ids of the primary's snapshots, so this reads there.
+    let mut changes = queries::get_pointer_changes(
+        &state.db.writer,
+        auth.org_id,
+        after_xid,
+        after_pointer_id,
+        limit + 1,
+    )
+    .await?;
+    let has_more = changes.len() as i64 > limit;
+    changes.truncate(limit as usize);
+
+    let next_cursor = match changes.last() {
+        Some(last) => changes_cursor(
+            last.change_xid
+                .parse()
+                .map_err(|_| ApiError::Internal("Invalid change_xid".to_string()))?,
+            last.pointer_id,
+        ),
+        None => changes_cursor(after_xid, after_pointer_id),
+    };
+
+    Ok(Json(PointerChangesResponse {
+        changes: changes
+            .into_iter()
+            .map(|c| {
+                let change = match c.status {
+                    PointerStatus::Orphaned => "orphaned",
+                    PointerStatus::Active | PointerStatus::PendingOrphan => "upsert",
+                };
+                let status = c.status.as_str();
+                PointerChangeRecord {
+                    pointer_id: c.pointer_id,
+                    org_id: c.org_id,
+                    subject_id: c.subject_id,
+                    change: change.to_string(),
+                    status: status.to_string(),
+                    orphaned_at: c.orphaned_at.map(|t| t.to_rfc3339()),
+                    metadata: c.metadata,
+                    updated_at: c.updated_at.to_rfc3339(),
+                }
+            })
+            .collect(),
+        next_cursor,
+        has_more,
+    }))
+}
+
+// ============================================================================
+// RESTRICT / UNRESTRICT SUBJECT (GDPR Art. 18)
+// ============================================================================
+
+/// Longest restriction window accepted in one request (90 days)
+const MAX_RESTRICTION_SECONDS: i64 = 90 * 24 * 60 * 60;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RestrictSubjectRequest {
+    pub duration_seconds: i64,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestrictSubjectResponse {
+    pub subject_id: String,
+    #[schema(format = DateTime)]
+    pub restricted_at: String,
+    #[schema(format = DateTime)]
+    pub expires_at: String,
+    pub pointers: Vec<RestrictedPointer>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestrictedPointer {
+    pub pointer_id: Uuid,
+    pub restriction_id: Uuid,
+    pub receipt: ReceiptInfo,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/subject/{id}/restrict",
+    tag = "restrictions",
+    params(("id" = String, Path, description = "Subject id")),
+    request_body = RestrictSubjectRequest,
+    responses(
+        (status = 200, description = "The subject's pointers restricted", body = RestrictSubjectResponse),
+        (status = 400, description = "Invalid expiry or reason", body = ErrorBody),
+        (status = 403, description = "Another org's subject", body = ErrorBody),
+        (status = 404, description = "No pointers for subject", body = ErrorBody),
+    )
+)]
+pub async fn restrict_subject(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(subject_id): Path<String>,
+    Json(req): Json<RestrictSubjectRequest>,
+) -> Result<Json<RestrictSubjectResponse>, ApiError> {
+    let subject_id = SubjectRules::from_config(&state.config).canonical(&subject_id);
+
+    if !(1..=MAX_RESTRICTION_SECONDS).contains(&req.duration_seconds) {
+        return Err(ApiError::BadRequest(format!(
+            "duration_seconds must be between 1 and {}",
+            MAX_RESTRICTION_SECONDS
+        )));
+    }
+    if req.reason.trim().is_empty() {
+        return Err(ApiError::BadRequest("reason must not be empty".to_string()));
+    }
+    check_receipt_field(&state, "reason", &req.reason)?;
+
+    record_subject(&subject_id);
+    info!("Restricting processing");
+
+    // Stored timestamps have microsecond precision
+    let restricted_at = state.clock.now().trunc_subsecs(6);
+    let expires_at = restricted_at + chrono::Duration::seconds(req.duration_seconds);
+
+    let mut tx = state.db.writer.begin().await?;
+
+    // Close out windows that ended but haven't been swept yet; any restriction
+    // still in force makes the insert below fail as a conflict
+    for open in lock_open_restrictions_by_subject(&mut *tx, &subject_id).await? {
+        if open.org_id == auth.org_id && open.expires_at <= restricted_at {
+            let signer = state.signer(open.org_id).await?;
+            lift_with_receipt(&mut tx, &signer, &open, "expired", restricted_at).await?;
+        }
+    }
+
+    // Orphaned pointers are already beyond restriction
+    let active: Vec<_> = auth
+        .own(
+            lock_pointers_by_subject(&mut *tx, &subject_id).await?,
+            |p| p.org_id,
+        )?
+        .into_iter()
+        .filter(|p| !matches!(p.status, PointerStatus::Orphaned))
+        .collect();
+
+    if active.is_empty() {
+        return Err(ApiError::NotFound(
+            "No active pointers for subject".to_string(),
+        ));
+    }
+
+    let mut pointers = Vec::with_capacity(active.len());
+
+    for pointer in &active {
+        let restriction = create_pointer_restriction(
+            &mut *tx,
+            pointer.pointer_id,
+            &req.reason,
+            restricted_at,
+            expires_at,
+        )
+        .await?;
+
+        let prev_hash = get_latest_receipt_hash(&mut *tx, pointer.pointer_id).await?;
+
+        let mut receipt_data = ReceiptData::new(
+            pointer.pointer_id,
+            ReceiptOperation::Restrict,
+            pointer.subject_id.clone(),
+            prev_hash.clone(),
+            json!({
+                "restriction_id": restriction.restriction_id,
+                "reason": req.reason,
+                "expires_at": expires_at,
+            }),
+            restricted_at,
+        );
+        let signed_receipt = sign_receipt(&state, pointer.org_id, &mut receipt_data).await?;
+
+        store_receipt(
+            &state,
+            &mut tx,
+            pointer.pointer_id,
+            pointer.org_id,
+            ReceiptOperation::Restrict,
+            &signed_receipt,
+            prev_hash.as_deref(),
+        )
+        .await?;
+
+        create_audit_log(
+            &mut *tx,
+            Some(pointer.org_id),
+            Some(pointer.pointer_id),
+            None,
+            "pointer_restricted",
+            json!({
+                "subject_id": pointer.subject_id,
+                "restriction_id": restriction.restriction_id,
+                "reason": req.reason,
+                "expires_at": expires_at,
+            }),
+            None,
+            None,
+            None,
+        )
+        .await?;
+
+        pointers.push(RestrictedPointer {
+            pointer_id: pointer.pointer_id,
+            restriction_id: restriction.restriction_id,
+            receipt: ReceiptInfo {
+                receipt_hash: signed_receipt.receipt_hash,
+                signature: data_encoding::BASE64.encode(&signed_receipt.signature),
+                signature_algorithm: signed_receipt.signature_algorithm,
+                key_id: signed_receipt.key_id,
+                timestamp: receipt_data.timestamp.to_rfc3339(),
+            },
+        });
+    }
+
+    tx.commit().await?;
+    for pointer in &pointers {
+        state.pointer_written(&pointer.pointer_id);
+    }
+
+    Ok(Json(RestrictSubjectResponse {
+        subject_id,
+        restricted_at: restricted_at.to_rfc3339(),
+        expires_at: expires_at.to_rfc3339(),
+        pointers,
+    }))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnrestrictSubjectResponse {
+    pub subject_id: String,
+    pub pointers: Vec<RestrictedPointer>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/subject/{id}/unrestrict",
+    tag = "restrictions",
+    params(("id" = String, Path, description = "Subject id")),
+    responses(
+        (status = 200, description = "Open restrictions lifted", body = UnrestrictSubjectResponse),
+        (status = 403, description = "Another org's subject", body = ErrorBody),
+        (status = 404, description = "No pointers for subject", body = ErrorBody),
+    )
+)]
+pub async fn unrestrict_subject(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(subject_id): Path<String>,
+) -> Result<Json<UnrestrictSubjectResponse>, ApiError> {
+    let subject_id = SubjectRules::from_config(&state.config).canonical(&subject_id);
+
+    record_subject(&subject_id);
+    info!("Lifting processing restriction");
+
+    let mut tx = state.db.writer.begin().await?;
+    let open = auth.own(
+        lock_open_restrictions_by_subject(&mut *tx, &subject_id).await?,
+        |r| r.org_id,
+    )?;
+
+    if open.is_empty() {
+        return Err(ApiError::NotFound(
+            "No open restrictions for subject".to_string(),
+        ));
+    }
+
+    let mut pointers = Vec::with_capacity(open.len());
+
+    for restriction in &open {
+        let signer = state.signer(restriction.org_id).await?;
+        if let Some(lifted) =
+            lift_with_receipt(&mut tx, &signer, restriction, "manual", state.clock.now()).await?
+        {
+            pointers.push(RestrictedPointer {
+                pointer_id: lifted.restriction.pointer_id,
+                restriction_id: lifted.restriction.restriction_id,
+                receipt: ReceiptInfo {
+                    receipt_hash: lifted.receipt.receipt_hash,
+                    signature: data_encoding::BASE64.encode(&lifted.receipt.signature),
+                    signature_algorithm: lifted.receipt.signature_algorithm,
+                    key_id: lifted.receipt.key_id,
+                    timestamp: lifted.receipt_timestamp.to_rfc3339(),
+                },
+            });
+        }
+    }
+
+    tx.commit().await?;
+    for pointer in &pointers {
+        state.pointer_written(&pointer.pointer_id);
+    }
+
+    Ok(Json(UnrestrictSubjectResponse {
+        subject_id,
+        pointers,
+    }))
+}
+
+// ============================================================================
+// SUBJECT DATA EXPORT (GDPR Art. 15)
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportSubjectQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Include the encrypted payloads of active pointers
+    #[serde(default)]
+    pub include_payloads: bool,
+}
+
+/// Everything this org holds about a subject, streamed as a download. The
+/// body is written as it is read, so an error partway through cuts it short
+/// rather than turning into an error status.
+#[utoipa::path(
+    get,
+    path = "/api/subject/{id}/export",
+    tag = "subjects",
+    params(("id" = String, Path, description = "Subject id"), ExportSubjectQuery),
+    responses(
+        (status = 200, description = "Everything held about the subject, streamed as a download", content(("application/json"), ("application/x-ndjson"))),
+        (status = 403, description = "Another org's subject", body = ErrorBody),
+        (status = 404, description = "No pointers for subject", body = ErrorBody),
+    )
+)]
+pub async fn export_subject(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Path(subject_id): Path<String>,
+    Query(query): Query<ExportSubjectQuery>,
+) -> Result<impl axum::response::IntoResponse, ApiError> {
+    let subject_id = SubjectRules::from_config(&state.config).canonical(&subject_id);
+    if auth
+        .own(
+            get_subject_org_ids(&state.db.writer, &subject_id).await?,
+            |id| *id,
+        )?
+        .is_empty()
+    {
+        return Err(ApiError::NotFound("No pointers for subject".to_string()));
+    }
+
+    record_subject(&subject_id);
+    info!("Exporting subject data");
+
+    let signer = state.signer(auth.org_id).await?;
+    let public_keys = verification_jwks(&state, &signer.key_id(), &signer.public_key_bytes())
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<_, _>>()
+        .map_err(anyhow::Error::from)?;
+    let export = SubjectExport {
+        subject_id,
+        org_id: auth.org_id,
+        exported_at: state.clock.now(),
+        format: query.format,
+        include_payloads: query.include_payloads,
+        public_keys,
+        payload_cipher: state.payload_cipher.clone(),
+    };
+
+    create_audit_log(
+        &state.db.writer,
+        Some(auth.org_id),
+        None,
+        None,
+        "subject_exported",
+        json!({
+            "subject_id": export.subject_id,
+            "format": export.format.extension(),
+            "include_payloads": export.include_payloads,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    let filename = format!(
+        "subject-export-{}.{}",
+        export.exported_at.format("%Y%m%dT%H%M%SZ"),
+        export.format.extension()
+    );
+    let content_type = export.format.content_type();
+
+    let (sender, receiver) = tokio::sync::mpsc::channel::<ExportChunk>(4);
+    let pool = state.db.reader.clone();
+    tokio::spawn(
+        async move {
+            if let Err(e) = write_subject_export(&pool, &export, sender.clone()).await {
+                warn!(error = %format!("{:#}", e), "Subject export failed");
+                let _ = sender.send(Err(std::io::Error::other(e.to_string()))).await;
+            }
+        }
+        // Failures streaming the body are still logged against the request
+        .in_current_span(),
+    );
+
+    Ok((
+        [
+            (axum::http::header::CONTENT_TYPE, content_type.to_string()),
+            (
+                axum::http::header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        axum::body::Body::from_stream(tokio_stream::wrappers::ReceiverStream::new(receiver)),
+    ))
+}
+
+// ============================================================================
+// SUBJECT NORMALIZATION REPORT AND MERGE
+// ============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/api/subjects/duplicates",
+    tag = "subjects",
+    responses(
+        (status = 200, description = "The org's subjects that normalize to the same id", body = Vec<crate::subjects::DuplicateSubjectGroup>),
+    )
+)]
+pub async fn get_duplicate_subjects(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<Json<Vec<crate::subjects::DuplicateSubjectGroup>>, ApiError> {
+    let rules = SubjectRules::from_config(&state.config);
+    Ok(Json(
+        find_duplicate_subjects(&state.db.reader, auth.org_id, rules).await?,
+    ))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeSubjectsRequest {
+    /// Merge only the group with this canonical subject_id; all groups if omitted
+    #[serde(default)]
+    pub canonical: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MergeSubjectsResponse {
+    pub groups_merged: usize,
+    pub pointers: Vec<MergedSubjectPointer>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MergedSubjectPointer {
+    pub pointer_id: Uuid,
+    pub previous_subject_id: String,
+    pub subject_id: String,
+    pub receipt: ReceiptInfo,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/subjects/merge",
+    tag = "subjects",
+    request_body = MergeSubjectsRequest,
+    responses(
+        (status = 200, description = "The org's pointers moved to their normalized subject", body = MergeSubjectsResponse),
+    )
+)]
+pub async fn merge_subjects(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(req): Json<MergeSubjectsRequest>,
+) -> Result<Json<MergeSubjectsResponse>, ApiError> {
+    let rules = SubjectRules::from_config(&state.config);
+
+    let groups: Vec<_> = find_duplicate_subjects(&state.db.writer, auth.org_id, rules)
+        .await?
+        .into_iter()
+        .filter(|g| {
+            req.canonical
+                .as_deref()
+                .is_none_or(|c| rules.canonical(c) == g.canonical)
+        })
+        .collect();
+
+    info!(groups = groups.len(), "Merging duplicate subject groups");
+
+    let signer = state.signer(auth.org_id).await?;
+    let mut pointers = Vec::new();
+    for group in &groups {
+        for merged in merge_subject_group(
+            &state.db.writer,
+            &signer,
+            auth.org_id,
+            group,
+            state.clock.now(),
+        )
+        .await?
+        {
+            state.pointer_written(&merged.pointer_id);
+            pointers.push(MergedSubjectPointer {
+                pointer_id: merged.pointer_id,
+                previous_subject_id: merged.previous_subject_id,
+                subject_id: merged.subject_id,
+                receipt: ReceiptInfo {
+                    receipt_hash: merged.receipt.receipt_hash,
+                    signature: data_encoding::BASE64.encode(&merged.receipt.signature),
+                    signature_algorithm: merged.receipt.signature_algorithm,
+                    key_id: merged.receipt.key_id,
+                    timestamp: merged.receipt_timestamp.to_rfc3339(),
+                },
+            });
+        }
+    }
+
+    Ok(Json(MergeSubjectsResponse {
+        groups_merged: groups.len(),
+        pointers,
+    }))
+}
+
+// ============================================================================
+// CONSISTENCY CHECK
+// ============================================================================
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConsistencyQuery {
+    #[serde(default)]
+    pub repair: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConsistencyReport {
+    pub checked: usize,
+    pub mismatches: Vec<ConsistencyMismatch>,
+    pub repaired: Vec<Uuid>,
+}
+
+/// Check and optionally repair `states`, all pointers of `org_id`, signing
+/// any repair receipts with the org's key
+async fn consistency_report(
+    state: &AppState,
+    org_id: Uuid,
+    states: &[PointerChainState],
+    repair: bool,
+) -> Result<ConsistencyReport, ApiError> {
+    // Rewriting status is only allowed where the operator opted in
+    if repair && !state.config.consistency_auto_repair {
+        return Err(ApiError::BadRequest(
+            "Repair is disabled; set CONSISTENCY_AUTO_REPAIR=true".to_string(),
+        ));
+    }
+
+    let (mismatches, repaired) = check_and_report(
+        &state.db.writer,
+        &state.signer(org_id).await?,
+        &state.pointer_cache,
+        states,
+        repair,
+        state.clock.now(),
+    )
+    .await?;
+    for pointer_id in &repaired {
+        state.pointer_written(pointer_id);
+        state.receipt_verifier.invalidate_pointer(*pointer_id);
+    }
+
+    Ok(ConsistencyReport {
+        checked: states.len(),
+        mismatches,
+        repaired,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/consistency/pointer/{id}",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Pointer id"), ConsistencyQuery),
+    responses(
+        (status = 200, description = "Status and chain disagreements found", body = ConsistencyReport),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+    )
+)]
+pub async fn check_pointer_consistency(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(pointer_id): Path<Uuid>,
+    Query(query): Query<ConsistencyQuery>,
+) -> Result<Json<ConsistencyReport>, ApiError> {
+    let chain_state = get_pointer_chain_state(&state.db.writer, pointer_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Pointer {} not found", pointer_id)))?;
+    auth.authorize(chain_state.org_id)?;
+
+    Ok(Json(
+        consistency_report(&state, auth.org_id, &[chain_state], query.repair).await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/consistency/subject/{id}",
+    tag = "admin",
+    params(("id" = String, Path, description = "Subject id"), ConsistencyQuery),
+    responses(
+        (status = 200, description = "Status and chain disagreements found", body = ConsistencyReport),
+        (status = 403, description = "Only another org has pointers for the subject", body = ErrorBody),
+    )
+)]
+pub async fn check_subject_consistency(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(subject_id): Path<String>,
+    Query(query): Query<ConsistencyQuery>,
+) -> Result<Json<ConsistencyReport>, ApiError> {
+    let subject_id = SubjectRules::from_config(&state.config).canonical(&subject_id);
+    let states = auth.own(
+        get_subject_chain_states(&state.db.writer, &subject_id).await?,
+        |s| s.org_id,
+    )?;
+
+    Ok(Json(
+        consistency_report(&state, auth.org_id, &states, query.repair).await?,
+    ))
+}
+
+// ============================================================================
+// CHAIN FREEZE
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FreezeChainRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FreezeChainResponse {
+    pub pointer_id: Uuid,
+    pub freeze_id: Uuid,
+    pub reason: String,
+    #[schema(format = DateTime)]
+    pub frozen_at: String,
+    /// Receipt of the freeze on the org chain
+    pub org_receipt: ReceiptInfo,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UnfreezeChainRequest {
+    pub outcome: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnfreezeChainResponse {
+    pub pointer_id: Uuid,
+    pub freeze_id: Uuid,
+    pub outcome: String,
+    #[schema(format = DateTime)]
+    pub cleared_at: String,
+    /// Receipt of the clearing on the org chain
+    pub org_receipt: ReceiptInfo,
+    /// ChainFreezeReview receipt resuming the pointer chain
+    pub review_receipt: ReceiptInfo,
+}
+
+fn receipt_info(receipt: SignedReceipt, timestamp: chrono::DateTime<chrono::Utc>) -> ReceiptInfo {
+    ReceiptInfo {
+        receipt_hash: receipt.receipt_hash,
+        signature: data_encoding::BASE64.encode(&receipt.signature),
+        signature_algorithm: receipt.signature_algorithm,
+        key_id: receipt.key_id,
+        timestamp: timestamp.to_rfc3339(),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/pointer/{id}/freeze",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Pointer id")),
+    request_body = FreezeChainRequest,
+    responses(
+        (status = 200, description = "Chain frozen", body = FreezeChainResponse),
+        (status = 400, description = "Reason too large, or already frozen", body = ErrorBody),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn freeze_pointer_chain(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(pointer_id): Path<Uuid>,
+    Json(req): Json<FreezeChainRequest>,
+) -> Result<Json<FreezeChainResponse>, ApiError> {
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::BadRequest("reason must not be empty".to_string()));
+    }
+    check_receipt_field(&state, "reason", reason)?;
+
+    record_pointer(&pointer_id);
+    info!(reason = %reason, "Freezing receipt chain");
+
+    let mut tx = begin_write(&state).await?;
+    let pointer = lock_pointers_for_update(&mut *tx, &[pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound(format!("Pointer {} not found", pointer_id)))?;
+    auth.authorize(pointer.org_id)?;
+
+    let now = state.clock.now();
+    let signer = state.signer(pointer.org_id).await?;
+    let frozen = freeze_chain_with_receipt(&mut tx, &signer, &pointer, reason, now).await?;
+
+    tx.commit().await?;
+    state.pointer_written(&pointer_id);
+
+    Ok(Json(FreezeChainResponse {
+        pointer_id,
+        freeze_id: frozen.freeze.freeze_id,
+        reason: frozen.freeze.reason,
+        frozen_at: frozen.freeze.frozen_at.to_rfc3339(),
+        org_receipt: receipt_info(frozen.org_receipt, now),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/pointer/{id}/unfreeze",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Pointer id")),
+    request_body = UnfreezeChainRequest,
+    responses(
+        (status = 200, description = "Chain unfrozen", body = UnfreezeChainResponse),
+        (status = 400, description = "Resolution too large, or not frozen", body = ErrorBody),
+        (status = 403, description = "Another org's pointer", body = ErrorBody),
+        (status = 404, description = "Pointer not found", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn unfreeze_pointer_chain(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(pointer_id): Path<Uuid>,
+    Json(req): Json<UnfreezeChainRequest>,
+) -> Result<Json<UnfreezeChainResponse>, ApiError> {
+    let outcome = req.outcome.trim();
+    if outcome.is_empty() {
+        return Err(ApiError::BadRequest(
+            "outcome must not be empty".to_string(),
+        ));
+    }
+    check_receipt_field(&state, "outcome", outcome)?;
+
+    record_pointer(&pointer_id);
+    info!(outcome = %outcome, "Clearing chain freeze");
+
+    let mut tx = begin_write(&state).await?;
+    let pointer = lock_pointers_for_update(&mut *tx, &[pointer_id])
+        .await?
+        .pop()
+        .ok_or_else(|| ApiError::NotFound(format!("Pointer {} not found", pointer_id)))?;
+    auth.authorize(pointer.org_id)?;
+
+    let now = state.clock.now();
+    let signer = state.signer(pointer.org_id).await?;
+    let cleared = clear_chain_freeze_with_receipt(&mut tx, &signer, &pointer, outcome, now)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Pointer chain is not frozen".to_string()))?;
+
+    tx.commit().await?;
+    state.pointer_written(&pointer_id);
+
+    Ok(Json(UnfreezeChainResponse {
+        pointer_id,
+        freeze_id: cleared.freeze.freeze_id,
+        outcome: outcome.to_string(),
+        cleared_at: now.to_rfc3339(),
+        org_receipt: receipt_info(cleared.org_receipt, now),
+        review_receipt: receipt_info(cleared.review_receipt, now),
+    }))
+}
+
+// ============================================================================
+// API KEYS
+// ============================================================================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiKeySummary {
+    pub key_id: Uuid,
+    pub label: String,
+    /// active, rotating (replaced, still inside its overlap) or revoked
+    pub status: String,
+    pub valid_from: String,
+    /// When the key stops or stopped authenticating; null while open-ended
+    #[schema(format = DateTime)]
+    pub valid_until: Option<String>,
+    #[schema(format = DateTime)]
+    pub revoked_at: Option<String>,
+    pub replaced_by: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListApiKeysResponse {
+    pub org_id: Uuid,
+    pub keys: Vec<ApiKeySummary>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RotateApiKeyResponse {
+    pub key_id: Uuid,
+    /// The new key; only its hash is stored, so it is shown only here
+    pub api_key: String,
+    pub label: String,
+    pub previous_key_id: Uuid,
+    /// The previous key keeps working until then and is revoked after
+    #[schema(format = DateTime)]
+    pub previous_key_valid_until: String,
+    pub overlap_seconds: i64,
+    /// Receipt of the new key's issuance on the org chain
+    pub org_receipt: ReceiptInfo,
+    /// Receipt of the previous key's overlap on the org chain
+    pub rotation_receipt: ReceiptInfo,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RevokeApiKeyResponse {
+    pub key_id: Uuid,
+    #[schema(format = DateTime)]
+    pub revoked_at: String,
+    /// Receipt of the revocation on the org chain
+    pub org_receipt: ReceiptInfo,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/keys",
+    tag = "api-keys",
+    responses(
+        (status = 200, description = "The org's API keys", body = ListApiKeysResponse),
+    )
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<Json<ListApiKeysResponse>, ApiError> {
+    let now = state.clock.now();
+    let keys = queries::list_api_keys(&state.db.reader, auth.org_id)
+        .await?
+        .into_iter()
+        .map(|api_key| {
+            let status = if key_is_valid(&api_key, now) {
+                if api_key.replaced_by.is_some() {
+                    "rotating"
+                } else {
+                    "active"
+                }
+            } else {
+                "revoked"
+            };
+
+            ApiKeySummary {
+                key_id: api_key.key_id,
+                status: status.to_string(),
+                valid_from: api_key.created_at.to_rfc3339(),
+                valid_until: key_valid_until(&api_key).map(|t| t.to_rfc3339()),
+                revoked_at: api_key.revoked_at.map(|t| t.to_rfc3339()),
+                replaced_by: api_key.replaced_by,
+                label: api_key.label,
+            }
+        })
+        .collect();
+
+    Ok(Json(ListApiKeysResponse {
+        org_id: auth.org_id,
+        keys,
+    }))
+}
+
+/// Replace the key the request authenticated with. Both keys work until the
+/// configured overlap ends; the key sweeper then revokes the old one.
+#[utoipa::path(
+    post,
+    path = "/api/keys/rotate",
+    tag = "api-keys",
+    responses(
+        (status = 200, description = "The new key, shown once", body = RotateApiKeyResponse),
+        (status = 409, description = "The key was already rotated", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn rotate_api_key(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+) -> Result<Json<RotateApiKeyResponse>, ApiError> {
+    info!(key_id = %auth.key_id, "Rotating API key");
+
+    let mut tx = begin_write(&state).await?;
+    let current = lock_api_key(&mut *tx, auth.key_id)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("unauthorized: Unknown API key".to_string()))?;
+    if current.replaced_by.is_some() {
+        return Err(ApiError::Conflict {
+            conflict_type: "api_key_already_rotated".to_string(),
+            message: "This key has already been rotated; rotate its successor instead".to_string(),
+        });
+    }
+
+    let now = state.clock.now();
+    let overlap_seconds = state.config.api_key_rotation_overlap_seconds.max(0);
+    let rotated = rotate_api_key_with_receipt(
+        &mut tx,
+        &state.signer(current.org_id).await?,
+        &current,
+        chrono::Duration::seconds(overlap_seconds),
+        &ctx,
+        now,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let issued = rotated.issued;
+    Ok(Json(RotateApiKeyResponse {
+        key_id: issued.api_key.key_id,
+        api_key: issued.key,
+        label: issued.api_key.label,
+        previous_key_id: rotated.previous.key_id,
+        previous_key_valid_until: key_valid_until(&rotated.previous)
+            .unwrap_or(now)
+            .to_rfc3339(),
+        overlap_seconds,
+        org_receipt: receipt_info(issued.org_receipt, now),
+        rotation_receipt: receipt_info(rotated.rotation_receipt, now),
+    }))
+}
+
+/// Revoke one of the org's keys now, such as a rotated key once every client
+/// has switched to its successor
+#[utoipa::path(
+    post,
+    path = "/api/keys/{id}/revoke",
+    tag = "api-keys",
+    params(("id" = Uuid, Path, description = "API key id")),
+    responses(
+        (status = 200, description = "Key revoked", body = RevokeApiKeyResponse),
+        (status = 400, description = "Revoking the key making the request", body = ErrorBody),
+        (status = 403, description = "Another org's key", body = ErrorBody),
+        (status = 404, description = "Key not found", body = ErrorBody),
+        (status = 409, description = "Already revoked", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Path(key_id): Path<Uuid>,
+) -> Result<Json<RevokeApiKeyResponse>, ApiError> {
+    info!(key_id = %key_id, "Revoking API key");
+
+    let mut tx = begin_write(&state).await?;
+    let api_key = lock_api_key(&mut *tx, key_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("API key {} not found", key_id)))?;
+    auth.authorize(api_key.org_id)?;
+
+    let now = state.clock.now();
+    let revoked = revoke_api_key_with_receipt(
+        &mut tx,
+        &state.signer(api_key.org_id).await?,
+        &api_key,
+        REVOKED_ON_REQUEST,
+        now,
+        &ctx,
+        now,
+    )
+    .await?
+    .ok_or_else(|| ApiError::Conflict {
+        conflict_type: "api_key_already_revoked".to_string(),
+        message: format!("API key {} is already revoked", key_id),
+    })?;
+
+    tx.commit().await?;
+
+    Ok(Json(RevokeApiKeyResponse {
+        key_id,
+        revoked_at: now.to_rfc3339(),
+        org_receipt: receipt_info(revoked.org_receipt, now),
+    }))
+}
+
+// ============================================================================
+// WEBHOOKS
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    /// Events to deliver; defaults to pointer_orphaned
+    #[serde(default)]
+    pub events: Option<Vec<String>>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateWebhookRequest {
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub events: Option<Vec<String>>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookSummary {
+    pub webhook_id: Uuid,
+    pub org_id: Uuid,
+    pub url: String,
+    pub enabled: bool,
+    pub events: Vec<String>,
+    #[schema(format = DateTime)]
+    pub created_at: String,
+    #[schema(format = DateTime)]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateWebhookResponse {
+    #[serde(flatten)]
+    pub webhook: WebhookSummary,
+    /// HMAC-SHA256 key for X-Veto-Signature; only shown here
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListWebhooksResponse {
+    pub org_id: Uuid,
+    pub webhooks: Vec<WebhookSummary>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryAttemptInfo {
+    #[schema(format = DateTime)]
+    pub attempted_at: String,
+    /// Receiver's HTTP status; null when no response arrived
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryInfo {
+    pub delivery_id: Uuid,
+    pub event_type: String,
+    pub pointer_id: Option<Uuid>,
+    /// pending, delivered or failed (out of attempts)
+    pub status: String,
+    pub attempts: i32,
+    /// Next try while pending
+    #[schema(format = DateTime)]
+    pub next_attempt_at: Option<String>,
+    pub last_error: Option<String>,
+    #[schema(format = DateTime)]
+    pub created_at: String,
+    #[schema(format = DateTime)]
+    pub delivered_at: Option<String>,
+    pub attempt_log: Vec<WebhookDeliveryAttemptInfo>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveriesResponse {
+    pub webhook_id: Uuid,
+    pub deliveries: Vec<WebhookDeliveryInfo>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WebhookDeliveriesQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+impl From<Webhook> for WebhookSummary {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            webhook_id: webhook.webhook_id,
+            org_id: webhook.org_id,
+            url: webhook.url,
+            enabled: webhook.enabled,
+            events: webhook.events,
+            created_at: webhook.created_at.to_rfc3339(),
+            updated_at: webhook.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+fn check_webhook_url(url: &str) -> Result<(), ApiError> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.has_host() => Ok(()),
+        _ => Err(ApiError::BadRequest(format!(
+            "url must be an absolute http(s) URL, got \"{}\"",
+            url
+        ))),
+    }
+}
+
+/// Deduplicated event list, refusing events no webhook can receive
+fn check_webhook_events(events: Vec<String>) -> Result<Vec<String>, ApiError> {
+    let mut checked: Vec<String> = Vec::with_capacity(events.len());
+    for event in events {
+        if !WEBHOOK_EVENTS.contains(&event.as_str()) {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown webhook event \"{}\"; expected one of {}",
+                event,
+                WEBHOOK_EVENTS.join(", ")
+            )));
+        }
+        if !checked.contains(&event) {
+            checked.push(event);
+        }
+    }
+    if checked.is_empty() {
+        return Err(ApiError::BadRequest("events must not be empty".to_string()));
+    }
+    Ok(checked)
+}
+
+/// Fetch a webhook the caller's org owns
+async fn owned_webhook(
+    state: &AppState,
+    auth: &AuthContext,
+    webhook_id: Uuid,
+) -> Result<Webhook, ApiError> {
+    let webhook = queries::get_webhook(&state.db.writer, webhook_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Webhook {} not found", webhook_id)))?;
+    auth.authorize(webhook.org_id)?;
+    Ok(webhook)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook created; its secret is shown once", body = CreateWebhookResponse),
+        (status = 422, description = "Validation failed", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Json(req): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<CreateWebhookResponse>), ApiError> {
+    check_webhook_url(&req.url)?;
+    let events = check_webhook_events(
+        req.events
+            .unwrap_or_else(|| vec!["pointer_orphaned".to_string()]),
+    )?;
+    let secret = generate_webhook_secret();
+
+    let mut tx = begin_write(&state).await?;
+    let webhook = queries::create_webhook(
+        &mut *tx,
+        auth.org_id,
+        &req.url,
+        &secret,
+        &events,
+        req.enabled.unwrap_or(true),
+    )
+    .await?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(auth.org_id),
+        None,
+        None,
+        "webhook_created",
+        json!({
+            "webhook_id": webhook.webhook_id,
+            "url": webhook.url,
+            "events": webhook.events,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+    tx.commit().await?;
+
+    info!(webhook_id = %webhook.webhook_id, "Webhook registered");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateWebhookResponse {
+            webhook: webhook.into(),
+            secret,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "The org's webhooks", body = ListWebhooksResponse),
+    )
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<Json<ListWebhooksResponse>, ApiError> {
+    let webhooks = queries::list_webhooks(&state.db.reader, auth.org_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListWebhooksResponse {
+        org_id: auth.org_id,
+        webhooks,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Webhook id")),
+    responses(
+        (status = 200, description = "The webhook", body = WebhookSummary),
+        (status = 404, description = "Webhook not found", body = ErrorBody),
+    )
+)]
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<WebhookSummary>, ApiError> {
+    Ok(Json(owned_webhook(&state, &auth, webhook_id).await?.into()))
+}
+
+/// Change a webhook's URL, events or enabled state. Disabling it holds its
+/// pending deliveries until it is enabled again.
+#[utoipa::path(
+    patch,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Webhook id")),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "The updated webhook", body = WebhookSummary),
+        (status = 404, description = "Webhook not found", body = ErrorBody),
+        (status = 422, description = "Validation failed", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Path(webhook_id): Path<Uuid>,
+    Json(req): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookSummary>, ApiError> {
+    owned_webhook(&state, &auth, webhook_id).await?;
+    if let Some(url) = &req.url {
+        check_webhook_url(url)?;
+    }
+    let events = req.events.map(check_webhook_events).transpose()?;
+
+    let mut tx = begin_write(&state).await?;
+    let webhook = queries::update_webhook(
+        &mut *tx,
+        webhook_id,
+        req.url.as_deref(),
+        events.as_deref(),
+        req.enabled,
+    )
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("Webhook {} not found", webhook_id)))?;
+
+    create_audit_log(
+        &mut *tx,
+        Some(webhook.org_id),
+        None,
+        None,
+        "webhook_updated",
+        json!({
+            "webhook_id": webhook_id,
+            "url": webhook.url,
+            "events": webhook.events,
+            "enabled": webhook.enabled,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+    tx.commit().await?;
+
+    if webhook.enabled {
+        state.webhook_wakeup.notify_one();
+    }
+
+    Ok(Json(webhook.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Webhook id")),
+    responses(
+        (status = 204, description = "Webhook deleted"),
+        (status = 404, description = "Webhook not found", body = ErrorBody),
+        (status = 503, description = "Database unavailable", body = ErrorBody),
+    )
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    ctx: RequestContext,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let webhook = owned_webhook(&state, &auth, webhook_id).await?;
+
+    let mut tx = begin_write(&state).await?;
+    if !queries::delete_webhook(&mut *tx, webhook_id).await? {
+        return Err(ApiError::NotFound(format!(
+            "Webhook {} not found",
+            webhook_id
+        )));
+    }
+
+    create_audit_log(
+        &mut *tx,
+        Some(webhook.org_id),
+        None,
+        None,
+        "webhook_deleted",
+        json!({
+            "webhook_id": webhook_id,
+            "url": webhook.url,
+        }),
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+    tx.commit().await?;
+
+    info!(webhook_id = %webhook_id, "Webhook deleted");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Recent deliveries of a webhook, newest first, each with its attempt log
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Webhook id"), WebhookDeliveriesQuery),
+    responses(
+        (status = 200, description = "Recent deliveries and their attempts", body = WebhookDeliveriesResponse),
+        (status = 404, description = "Webhook not found", body = ErrorBody),
+    )
+)]
+pub async fn get_webhook_deliveries(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(webhook_id): Path<Uuid>,
+    Query(query): Query<WebhookDeliveriesQuery>,
+) -> Result<Json<WebhookDeliveriesResponse>, ApiError> {
+    owned_webhook(&state, &auth, webhook_id).await?;
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+
+    let deliveries = queries::list_webhook_deliveries(&state.db.reader, webhook_id, limit).await?;
+    let delivery_ids: Vec<Uuid> = deliveries.iter().map(|d| d.delivery_id).collect();
+    let attempts = queries::get_webhook_delivery_attempts(&state.db.reader, &delivery_ids).await?;
+
+    let deliveries = deliveries
+        .into_iter()
+        .map(|delivery| {
+            let attempt_log = attempts
+                .iter()
+                .filter(|a| a.delivery_id == delivery.delivery_id)
+                .map(|a| WebhookDeliveryAttemptInfo {
+                    attempted_at: a.attempted_at.to_rfc3339(),
+                    status_code: a.status_code,
+                    error: a.error.clone(),
+                    duration_ms: a.duration_ms,
+                })
+                .collect();
+
+            WebhookDeliveryInfo {
+                delivery_id: delivery.delivery_id,
+                event_type: delivery.event_type,
+                pointer_id: delivery.pointer_id,
+                next_attempt_at: (delivery.status == "pending")
+                    .then(|| delivery.next_attempt_at.to_rfc3339()),
+                status: delivery.status,
+                attempts: delivery.attempts,
+                last_error: delivery.last_error,
+                created_at: delivery.created_at.to_rfc3339(),
+                delivered_at: delivery.delivered_at.map(|t| t.to_rfc3339()),
+                attempt_log,
+            }
+        })
+        .collect();
+
+    Ok(Json(WebhookDeliveriesResponse {
+        webhook_id,
+        deliveries,
+    }))
+}
+
+// ============================================================================
+// GET AUDIT TRAIL
+// ============================================================================
+
+impl From<AuditLog> for AuditEventSummary {
+    fn from(log: AuditLog) -> Self {
+        Self {
+            event_type: log.event_type,
+            timestamp: log.timestamp.to_rfc3339(),
+            pointer_id: log.pointer_id,
+            event_data: log.event_data,
+            actor_id: log.actor_id,
+            ip_address: log.ip_address.map(|ip| ip.to_string()),
+        }
+    }
+}
+
+/// A page of the audit trail, or with `format` all of it as a download
+#[utoipa::path(
+    get,
+    path = "/api/audit/{subject_id}",
+    tag = "audit",
+    params(
+        ("subject_id" = String, Path, description = "Subject id"),
+        AuditTrailQuery,
+    ),
+    responses(
+        (status = 200, description = "A page of the audit trail, or with `format` every matching event as a download", content((GetAuditTrailResponse = "application/json"), ("text/csv"), ("application/x-ndjson"))),
+        (status = 400, description = "Bad cursor or limit", body = ErrorBody),
+        (status = 403, description = "Another org's subject", body = ErrorBody),
+    )
+)]
+pub async fn get_audit_trail_or_export(
+    state: State<AppState>,
+    auth: AuthContext,
+    subject_id: Path<String>,
+    query: Query<AuditTrailQuery>,
+) -> Result<axum::response::Response, ApiError> {
+    use axum::response::IntoResponse;
+
+    match query.format {
+        Some(format) => export_audit_trail(state, auth, subject_id, query, format).await,
+        None => Ok(get_audit_trail(state, auth, subject_id, query)
+            .await?
+            .into_response()),
+    }
+}
+
+/// Every event matching the filters, newest first, streamed as CSV or JSON
+/// Lines. Cursor and limit do not apply.
+async fn export_audit_trail(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(subject_id): Path<String>,
+    Query(query): Query<AuditTrailQuery>,
+    format: AuditExportFormat,
+) -> Result<axum::response::Response, ApiError> {
+    use axum::response::IntoResponse;
+
+    let subject_id = SubjectRules::from_config(&state.config).canonical(&subject_id);
+    auth.own(
+        get_subject_org_ids(&state.db.reader, &subject_id).await?,
+        |id| *id,
+    )?;
+
+    record_subject(&subject_id);
+    info!("Exporting audit trail");
+
+    let filter = AuditFilter {
+        org_id: Some(auth.org_id),
+        event_type: query.event_type,
+        from: query.from,
+        to: query.to,
+    };
+    let filename = format!(
+        "audit-{}.{}",
+        state.clock.now().format("%Y%m%dT%H%M%SZ"),
+        format.extension()
+    );
+
+    let (sender, receiver) = tokio::sync::mpsc::channel::<AuditExportChunk>(4);
+    let pool = state.db.reader.clone();
+    tokio::spawn(
+        async move {
+            if let Err(e) =
+                write_audit_export(&pool, &subject_id, &filter, format, sender.clone()).await
+            {
+                warn!(error = %format!("{:#}", e), "Audit export failed");
+                let _ = sender.send(Err(std::io::Error::other(e.to_string()))).await;
+            }
+        }
+        // Failures streaming the body are still logged against the request
+        .in_current_span(),
+    );
+
+    Ok((
+        [
+            (
+                axum::http::header::CONTENT_TYPE,
+                format.content_type().to_string(),
+            ),
+            (
+                axum::http::header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        axum::body::Body::from_stream(tokio_stream::wrappers::ReceiverStream::new(receiver)),
+    )
+        .into_response())
+}
+
+pub async fn get_audit_trail(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(subject_id): Path<String>,
+    Query(query): Query<AuditTrailQuery>,
+) -> Result<Json<GetAuditTrailResponse>, ApiError> {
+    let subject_id = SubjectRules::from_config(&state.config).canonical(&subject_id);
+
+    record_subject(&subject_id);
+    info!("Getting audit trail");
+
+    // Get all of the caller's pointers for subject
+    let pointers = auth.own(
+        get_pointers_by_subject(&state.db.reader, &subject_id).await?,
+        |p| p.org_id,
+    )?;
+
+    let active_count = pointers
+        .iter()
+        .filter(|p| matches!(p.status, PointerStatus::Active))
+        .count();
+
+    let orphaned_count = pointers
+        .iter()
+        .filter(|p| matches!(p.status, PointerStatus::Orphaned))
+        .count();
+
+    let restrictions = auth.own(
+        get_restrictions_by_subject(&state.db.reader, &subject_id).await?,
+        |r| r.org_id,
+    )?;
+
+    // Get a page of the audit trail, newest first
+    let page = pagination(query.limit, query.cursor.as_deref())?;
+    let filter = AuditFilter {
+        org_id: Some(auth.org_id),
+        event_type: query.event_type,
+        from: query.from,
+        to: query.to,
+    };
+    let audit_logs = get_audit_trail_by_subject(
+        &state.db.reader,
+        &subject_id,
+        &filter,
+        &Pagination {
+            limit: page.limit + 1,
+            ..page
+        },
+    )
+    .await?;
+    let (audit_logs, next_cursor) = page_rows(audit_logs, &page, |log| (log.timestamp, log.log_id));
+
+    let audit_summaries: Vec<AuditEventSummary> = audit_logs
+        .into_iter()
+        .map(AuditEventSummary::from)
+        .collect();
+
+    Ok(Json(GetAuditTrailResponse {
+        subject_id,
+        total_pointers: pointers.len(),
+        active_pointers: active_count,
+        orphaned_pointers: orphaned_count,
+        restrictions: restrictions
+            .into_iter()
+            .map(|r| RestrictionSummary {
+                restriction_id: r.restriction_id,
+                pointer_id: r.pointer_id,
+                reason: r.reason,
+                restricted_at: r.restricted_at.to_rfc3339(),
+                expires_at: r.expires_at.to_rfc3339(),
+                lifted_at: r.lifted_at.map(|t| t.to_rfc3339()),
+                lift_reason: r.lift_reason,
+            })
+            .collect(),
+        audit_events: audit_summaries,
+        next_cursor,
+    }))
+}
+
+// ============================================================================
+// LIST POINTERS
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListPointersQuery {
+    /// active or orphaned
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub subject_id: Option<String>,
+    /// Pointers created at or after this time
+    #[serde(default)]
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Pointers created before this time
+    #[serde(default)]
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// `next_cursor` from the previous page; omit for the first
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PointerListEntry {
+    pub pointer_id: Uuid,
+    pub subject_id: String,
+    pub status: String,
+    #[schema(format = DateTime)]
+    pub created_at: String,
+    #[schema(format = DateTime)]
+    pub orphaned_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListPointersResponse {
+    pub pointers: Vec<PointerListEntry>,
+    /// Set when older pointers follow this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// The caller's pointers, newest first
+#[utoipa::path(
+    get,
+    path = "/api/pointers",
+    tag = "pointers",
+    params(ListPointersQuery),
+    responses(
+        (status = 200, description = "A page of the org's pointers, newest first", body = ListPointersResponse),
+        (status = 400, description = "Unknown status, bad cursor or limit", body = ErrorBody),
+    )
+)]
+pub async fn list_pointers(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(query): Query<ListPointersQuery>,
+) -> Result<Json<ListPointersResponse>, ApiError> {
+    let status = match query.status.as_deref() {
+        None => None,
+        Some("active") => Some(PointerStatus::Active),
+        Some("orphaned") => Some(PointerStatus::Orphaned),
+        Some("pending_orphan") => Some(PointerStatus::PendingOrphan),
+        Some(_) => {
+            return Err(ApiError::BadRequest(
+                "status must be one of: active, orphaned, pending_orphan".to_string(),
+            ))
+        }
+    };
+    let subject_id = query
+        .subject_id
+        .map(|subject_id| SubjectRules::from_config(&state.config).canonical(&subject_id));
+
+    let page = pagination(query.limit, query.cursor.as_deref())?;
+    let filter = PointerFilter {
+        org_id: auth.org_id,
+        status,
+        subject_id,
+        created_after: query.created_after,
+        created_before: query.created_before,
+    };
+    let pointers = queries::list_pointers(
+        &state.db.reader,
+        &filter,
+        &Pagination {
+            limit: page.limit + 1,
+            ..page
+        },
+    )
+    .await?;
+    let (pointers, next_cursor) = page_rows(pointers, &page, |p| (p.created_at, p.pointer_id));
+
+    Ok(Json(ListPointersResponse {
+        pointers: pointers
+            .into_iter()
+            .map(|p| PointerListEntry {
+                pointer_id: p.pointer_id,
+                subject_id: p.subject_id,
+                status: p.status.as_str().to_string(),
+                created_at: p.created_at.to_rfc3339(),
+                orphaned_at: p.orphaned_at.map(|t| t.to_rfc3339()),
+            })
+            .collect(),
+        next_cursor,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{keypair_from_encoded, SigningKeyring};
+    use crate::db::Db;
+    use crate::keys::register_signing_keys;
+    use crate::test_support::{seed_pointer, test_auth, test_state, unique_subject};
+
+    /// The newest page of a subject's audit trail, unfiltered
+    async fn audit_trail(state: &AppState, subject: &str) -> Vec<crate::db::models::AuditLog> {
+        let page = Pagination {
+            after: None,
+            limit: PAGE_MAX_LIMIT,
+        };
+        get_audit_trail_by_subject(&state.db.writer, subject, &AuditFilter::default(), &page)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_orphan_group_all_or_nothing() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("group");
+
+        let first = seed_pointer(&state, &subject).await;
+        let second = seed_pointer(&state, &subject).await;
+        let missing = Uuid::new_v4();
+
+        // A missing member aborts the whole group
+        let result = orphan_group(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanGroupRequest {
+                pointer_ids: vec![first, second, missing],
+                reason: None,
+            }),
+        )
+        .await;
+
+        match result {
+            Err(ApiError::OrphanGroupRejected(failures)) => {
+                assert_eq!(failures.len(), 1);
+                assert_eq!(failures[0].pointer_id, missing);
+                assert_eq!(failures[0].reason, "not_found");
+            }
+            _ => panic!("expected OrphanGroupRejected"),
+        }
+
+        let untouched = get_pointer(&state.db.writer, first).await.unwrap().unwrap();
+        assert!(matches!(untouched.status, PointerStatus::Active));
+
+        // The valid group commits with chained receipts and a manifest
+        let Json(group) = orphan_group(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanGroupRequest {
+                pointer_ids: vec![first, second, first],
+                reason: Some("document_withdrawn".to_string()),
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("group orphan failed"));
+
+        assert_eq!(group.members.len(), 2);
+
+        for member in &group.members {
+            let receipts = get_receipts_by_pointer(&state.db.writer, member.pointer_id)
+                .await
+                .unwrap();
+            let orphan = receipts.last().unwrap();
+            assert!(matches!(orphan.operation, ReceiptOperation::Orphan));
+            assert_eq!(
+                orphan.prev_hash.as_deref(),
+                Some(receipts[0].receipt_hash.as_str())
+            );
+            assert_eq!(
+                orphan.receipt_json["metadata"]["group_id"],
+                json!(group.group_id)
+            );
+        }
+
+        let Json(stored) = get_orphan_group(
+            State(state.clone()),
+            test_auth(&state),
+            Path(group.group_id),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("group not found"));
+        assert_eq!(stored.member_pointer_ids, vec![first, second]);
+        assert_eq!(stored.manifest_hash, group.manifest.receipt_hash);
+
+        let member_hashes: Vec<GroupMember> = group
+            .members
+            .iter()
+            .map(|m| GroupMember {
+                pointer_id: m.pointer_id,
+                receipt_hash: m.receipt.receipt_hash.clone(),
+            })
+            .collect();
+        assert_eq!(
+            stored.manifest["members_hash"],
+            json!(OrphanGroupManifest::members_hash(&member_hashes))
+        );
+
+        // Already-orphaned members reject a second group
+        let result = orphan_group(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanGroupRequest {
+                pointer_ids: vec![first],
+                reason: None,
+            }),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::OrphanGroupRejected(_))));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_within_orphan_grace() {
+        use crate::clock::FixedClock;
+        use std::sync::Arc;
+
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (name, orphan_grace_seconds) VALUES ('Grace Org', 60) RETURNING org_id",
+        )
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        state.config.default_org_id = org_id;
+
+        let pointer_id = seed_pointer(&state, &unique_subject("grace")).await;
+        let Json(orphaned) = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("orphan failed"));
+        let orphaned_at = get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .orphaned_at
+            .unwrap();
+
+        state.clock = Arc::new(FixedClock(orphaned_at + chrono::Duration::seconds(20)));
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("grace resolve failed"));
+        assert_eq!(resolved.status, "orphaned_grace");
+        assert_eq!(resolved.retry_after_seconds, Some(40));
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let denied = receipts.last().unwrap();
+        assert!(matches!(denied.operation, ReceiptOperation::Denied));
+        assert_eq!(
+            denied.prev_hash.as_deref(),
+            Some(orphaned.receipt.receipt_hash.as_str())
+        );
+        assert_eq!(
+            denied.receipt_json["metadata"]["grace_disclosure"],
+            json!(true)
+        );
+
+        state.clock = Arc::new(FixedClock(orphaned_at + chrono::Duration::seconds(60)));
+        let result = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::PointerOrphaned(_))));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_parallel_lookups_on_slow_pool() {
+        use crate::db::PointerCache;
+        use sqlx::postgres::PgPoolOptions;
+        use std::{
+            sync::Arc,
+            time::{Duration, Instant},
+        };
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let url = state.config.database_url.expose().clone();
+        let pointer_id = seed_pointer(&state, &unique_subject("slow")).await;
+
+        // Every checkout of an idle connection costs DELAY, dwarfing query time
+        const DELAY: Duration = Duration::from_millis(100);
+        let slow_pool = |max_connections: u32| {
+            PgPoolOptions::new()
+                .max_connections(max_connections)
+                .min_connections(max_connections)
+                .before_acquire(|_, _| {
+                    Box::pin(async {
+                        tokio::time::sleep(DELAY).await;
+                        Ok(true)
+                    })
+                })
+                .connect(&url)
+        };
+
+        let mut timings = Vec::new();
+        for max_connections in [4, 1] {
+            let pool = slow_pool(max_connections).await.unwrap();
+            while pool.num_idle() < max_connections as usize {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            // Every resolve reads the database, not the previous one's entry
+            let slow_state = AppState {
+                db: Db::single(pool),
+                pointer_cache: Arc::new(PointerCache::new(Duration::ZERO, 0)),
+                ..state.clone()
+            };
+
+            let started = Instant::now();
+            let Json(resolved) = resolve_pointer(
+                State(slow_state.clone()),
+                test_auth(&slow_state),
+                Path(pointer_id),
+                Query::default(),
+            )
+            .await
+            .unwrap_or_else(|_| panic!("slow resolve failed"));
+            timings.push(started.elapsed());
+            assert_eq!(resolved.status, "active");
+        }
+
+        // pointer, restriction, data and the locking transaction: pointer
+        // overlaps restriction when the pool has room; all serialize when it
+        // doesn't. Lock, chain head and receipt share the transaction's checkout
+        let (parallel, sequential) = (timings[0], timings[1]);
+        assert!(parallel < DELAY * 4, "parallel resolve took {:?}", parallel);
+        assert!(
+            sequential >= DELAY * 4,
+            "sequential resolve took {:?}",
+            sequential
+        );
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        assert_eq!(receipts.len(), 3);
+        assert_eq!(
+            receipts[2].prev_hash.as_deref(),
+            Some(receipts[1].receipt_hash.as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_resolves_share_fetches() {
+        use crate::db::{PointerCache, SingleFlight};
+        use sqlx::postgres::PgPoolOptions;
+        use std::{sync::Arc, time::Duration};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("flash")).await;
+
+        // Slow checkouts so a burst of resolves overlaps in flight
+        let pool = PgPoolOptions::new()
+            .max_connections(10)
+            .before_acquire(|_, _| {
+                Box::pin(async {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    Ok(true)
+                })
+            })
+            .connect(state.config.database_url.expose())
+            .await
+            .unwrap();
+
+        const BURST: u64 = 20;
+        let mut fetches = Vec::new();
+        for max_staleness in [Duration::ZERO, Duration::from_secs(5)] {
+            let burst_state = AppState {
+                db: Db::single(pool.clone()),
+                resolve_flights: Arc::new(SingleFlight::new(max_staleness)),
+                pointer_cache: Arc::new(PointerCache::new(Duration::ZERO, 0)),
+                ..state.clone()
+            };
+
+            let mut resolves = tokio::task::JoinSet::new();
+            for _ in 0..BURST {
+                let burst_state = burst_state.clone();
+                resolves.spawn(async move {
+                    resolve_pointer(
+                        State(burst_state.clone()),
+                        test_auth(&burst_state),
+                        Path(pointer_id),
+                        Query::default(),
+                    )
+                    .await
+                    .map(|Json(resolved)| resolved.status)
+                });
+            }
+            while let Some(resolved) = resolves.join_next().await {
+                assert_eq!(resolved.unwrap().ok().as_deref(), Some("active"));
+            }
+
+            let stats = burst_state.resolve_flights.stats();
+            assert_eq!(stats.fetches + stats.coalesced, BURST);
+            fetches.push(stats.fetches);
+        }
+
+        // Same request count, fewer pointer/restriction/data reads; every
+        // resolve is still receipted
+        assert_eq!(fetches[0], BURST);
+        assert!(fetches[1] < BURST / 2, "coalesced fetches: {}", fetches[1]);
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        assert_eq!(receipts.len() as u64, 1 + 2 * BURST);
+    }
+
+    #[tokio::test]
+    async fn test_orphan_invalidates_in_flight_resolve() {
+        use std::sync::Arc;
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("flight")).await;
+
+        // A stale active read is in flight when the veto commits
+        let release = Arc::new(tokio::sync::Notify::new());
+        let leader = {
+            let (state, release) = (state.clone(), release.clone());
+            tokio::spawn(async move {
+                state
+                    .resolve_flights
+                    .run(pointer_id, || async {
+                        let snapshot = fetch_pointer_snapshot(&state.db.writer, pointer_id).await;
+                        release.notified().await;
+                        snapshot
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        let follower = tokio::spawn(resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        ));
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+        release.notify_one();
+
+        assert!(leader.await.unwrap().is_ok());
+        assert!(matches!(
+            follower.await.unwrap(),
+            Err(ApiError::PointerOrphaned(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_orphan_evicts_cached_pointer_state() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("cache")).await;
+        let readonly = || {
+            resolve_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query(ResolveQuery {
+                    mode: ResolveMode::Readonly,
+                    ..Default::default()
+                }),
+            )
+        };
+
+        // The second resolve is served from the first one's read
+        for _ in 0..2 {
+            let Json(resolved) = readonly()
+                .await
+                .unwrap_or_else(|_| panic!("resolve failed"));
+            assert_eq!(resolved.status, "active");
+        }
+        let stats = state.pointer_cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+
+        // A read that started before the veto commits, and lands after it
+        let ticket = state.pointer_cache.ticket();
+        let stale = fetch_pointer_snapshot(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+        state.pointer_cache.insert(ticket, &stale);
+
+        // Neither the cached entry nor the overtaken read is served
+        assert!(matches!(
+            readonly().await,
+            Err(ApiError::PointerOrphaned(_))
+        ));
+        assert!(matches!(
+            resolve_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query::default(),
+            )
+            .await,
+            Err(ApiError::PointerOrphaned(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cached_pointer_state_expires_after_ttl() {
+        use crate::db::PointerCache;
+        use std::{sync::Arc, time::Duration};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        const TTL: Duration = Duration::from_millis(100);
+        let state = AppState {
+            pointer_cache: Arc::new(PointerCache::new(TTL, 100)),
+            ..state
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("cache_ttl")).await;
+        let readonly = || {
+            resolve_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query(ResolveQuery {
+                    mode: ResolveMode::Readonly,
+                    ..Default::default()
+                }),
+            )
+        };
+        assert!(readonly().await.is_ok());
+
+        // An orphan this instance didn't write, as by another instance
+        sqlx::query(
+            "UPDATE pointers SET status = 'orphaned', orphaned_at = NOW() WHERE pointer_id = $1",
+        )
+        .bind(pointer_id)
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+
+        // Served from the entry until it expires, then read again
+        let Json(cached) = readonly()
+            .await
+            .unwrap_or_else(|_| panic!("cached resolve failed"));
+        assert_eq!(cached.status, "active");
+        tokio::time::sleep(TTL).await;
+        assert!(matches!(
+            readonly().await,
+            Err(ApiError::PointerOrphaned(_))
+        ));
+
+        // Attested resolves enforce on the locked row regardless
+        assert!(matches!(
+            resolve_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query::default(),
+            )
+            .await,
+            Err(ApiError::PointerOrphaned(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_attested_resolve_reads_restriction_under_lock() {
+        use crate::db::PointerCache;
+        use std::{sync::Arc, time::Duration};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        // Long enough that only eviction can end the cached entry
+        let state = AppState {
+            pointer_cache: Arc::new(PointerCache::new(Duration::from_secs(600), 100)),
+            ..state
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("cache_restrict")).await;
+        let readonly = || {
+            resolve_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query(ResolveQuery {
+                    mode: ResolveMode::Readonly,
+                    ..Default::default()
+                }),
+            )
+        };
+        assert!(readonly().await.is_ok());
+
+        // A restriction this instance didn't write, as by another instance
+        let now = state.clock.now();
+        create_pointer_restriction(
+            &state.db.writer,
+            pointer_id,
+            "dispute",
+            now,
+            now + chrono::Duration::hours(1),
+        )
+        .await
+        .unwrap();
+
+        // The cached snapshot still has no restriction...
+        assert!(readonly().await.is_ok());
+        // ...but an attested resolve reads it under the pointer's lock
+        assert!(matches!(
+            resolve_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query::default(),
+            )
+            .await,
+            Err(ApiError::ProcessingRestricted { .. })
+        ));
+    }
+
+    async fn drain_changes(
+        state: &AppState,
+        auth: AuthContext,
+        mut cursor: Option<String>,
+    ) -> (Vec<PointerChangeRecord>, String) {
+        let mut changes = Vec::new();
+        loop {
+            let Json(page) = get_pointer_changes(
+                State(state.clone()),
+                auth,
+                Query(PointerChangesQuery {
+                    since: cursor.clone(),
+                    limit: Some(50),
+                }),
+            )
+            .await
+            .unwrap_or_else(|_| panic!("changes feed failed"));
+            changes.extend(page.changes);
+            cursor = Some(page.next_cursor);
+            if !page.has_more {
+                return (changes, cursor.unwrap());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pointer_changes_across_cursor() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("changes");
+        let orphaned = seed_pointer(&state, &subject).await;
+        let updated = seed_pointer(&state, &subject).await;
+        let held = seed_pointer(&state, &subject).await;
+
+        let (_, cursor) = drain_changes(&state, test_auth(&state), None).await;
+
+        let created = seed_pointer(&state, &subject).await;
+        let Json(_) = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id: orphaned,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("orphan failed"));
+        sqlx::query("UPDATE pointers SET metadata = '{\"tier\": \"gold\"}' WHERE pointer_id = $1")
+            .bind(updated)
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+
+        // An open transaction holds back its own write and everything after it
+        let mut tx = state.db.writer.begin().await.unwrap();
+        sqlx::query("UPDATE pointers SET metadata = '{}' WHERE pointer_id = $1")
+            .bind(held)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+
+        let ours = |changes: Vec<PointerChangeRecord>| -> Vec<PointerChangeRecord> {
+            changes
+                .into_iter()
+                .filter(|c| c.subject_id == subject)
+                .collect()
+        };
+
+        let mut seen = Vec::new();
+        let mut cursor = Some(cursor);
+        for _ in 0..50 {
+            let (page, next) = drain_changes(&state, test_auth(&state), cursor.take()).await;
+            seen.extend(ours(page));
+            cursor = Some(next);
+            if seen.len() >= 3 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        let mut ids: Vec<Uuid> = seen.iter().map(|c| c.pointer_id).collect();
+        ids.sort();
+        let mut expected = vec![created, orphaned, updated];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        let change_of = |id: Uuid| seen.iter().find(|c| c.pointer_id == id).unwrap();
+        assert_eq!(change_of(orphaned).change, "orphaned");
+        assert_eq!(change_of(created).change, "upsert");
+        assert_eq!(change_of(updated).metadata, json!({"tier": "gold"}));
+
+        tx.commit().await.unwrap();
+
+        let mut late = Vec::new();
+        for _ in 0..50 {
+            let (page, next) = drain_changes(&state, test_auth(&state), cursor.take()).await;
+            late.extend(ours(page));
+            cursor = Some(next);
+            if !late.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(late.len(), 1);
+        assert_eq!(late[0].pointer_id, held);
+
+        // Another org's feed carries none of the default org's pointers
+        let other = AuthContext {
+            org_id: Uuid::new_v4(),
+            key_id: Uuid::new_v4(),
+        };
+        let (theirs, _) = drain_changes(&state, other, None).await;
+        assert!(theirs.is_empty(), "{} foreign changes", theirs.len());
+    }
+
+    #[test]
+    fn test_changes_cursor_round_trip() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            parse_changes_cursor(&changes_cursor(42, id)),
+            Some((42, id))
+        );
+        assert_eq!(parse_changes_cursor("42"), None);
+        assert_eq!(parse_changes_cursor("x.y"), None);
+    }
+
+    fn orphan_request(pointer_id: Uuid, reason: Option<&str>) -> Json<OrphanPointerRequest> {
+        Json(OrphanPointerRequest {
+            pointer_id,
+            reason: reason.map(str::to_string),
+            effective_at: None,
+        })
+    }
+
+    fn scheduled_orphan_request(
+        pointer_id: Uuid,
+        effective_at: chrono::DateTime<chrono::Utc>,
+    ) -> Json<OrphanPointerRequest> {
+        Json(OrphanPointerRequest {
+            pointer_id,
+            reason: Some("pending_cleanup".to_string()),
+            effective_at: Some(effective_at),
+        })
+    }
+
+    async fn resolve_attested(
+        state: &AppState,
+        pointer_id: Uuid,
+    ) -> Result<Json<ResolvePointerResponse>, ApiError> {
+        resolve_pointer(
+            State(state.clone()),
+            test_auth(state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_orphan_resolves_until_cancelled() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("scheduled")).await;
+        let effective_at = chrono::Utc::now().trunc_subsecs(6) + chrono::Duration::days(7);
+
+        let Json(scheduled) = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            scheduled_orphan_request(pointer_id, effective_at),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("schedule failed"));
+        assert_eq!(scheduled.status, "pending_orphan");
+        assert_eq!(scheduled.effective_at, Some(effective_at.to_rfc3339()));
+
+        // Still resolvable, with the scheduled time
+        let Json(resolved) = resolve_attested(&state, pointer_id)
+            .await
+            .unwrap_or_else(|_| panic!("resolve of pending orphan failed"));
+        assert_eq!(resolved.status, "pending_orphan");
+        assert_eq!(
+            resolved.orphan_effective_at,
+            Some(effective_at.to_rfc3339())
+        );
+
+        // Rescheduling is refused; cancel first
+        let rescheduled = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            scheduled_orphan_request(pointer_id, effective_at + chrono::Duration::days(1)),
+        )
+        .await;
+        assert!(matches!(
+            rescheduled,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "orphan_already_scheduled"
+        ));
+
+        let Json(cancelled) = cancel_orphan(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(CancelOrphanRequest {
+                pointer_id,
+                reason: Some("cleanup_not_needed".to_string()),
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("cancel failed"));
+        assert_eq!(cancelled.status, "active");
+        assert_eq!(cancelled.cancelled_effective_at, effective_at.to_rfc3339());
+
+        let pointer = get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(pointer.status, PointerStatus::Active));
+        assert!(pointer.orphan_effective_at.is_none());
+
+        let operations: Vec<String> = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|r| r.receipt_json["operation"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            operations,
+            ["create", "orphan_scheduled", "resolve", "orphan_cancelled"]
+        );
+
+        // Nothing left to cancel
+        let again = cancel_orphan(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(CancelOrphanRequest {
+                pointer_id,
+                reason: None,
+            }),
+        )
+        .await;
+        assert!(matches!(
+            again,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "no_pending_orphan"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_immediate_veto_supersedes_scheduled_orphan() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("superseded")).await;
+        let effective_at = chrono::Utc::now() + chrono::Duration::days(7);
+
+        let _ = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            scheduled_orphan_request(pointer_id, effective_at),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("schedule failed"));
+
+        let Json(orphaned) = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("immediate veto failed"));
+        assert_eq!(orphaned.status, "orphaned");
+        assert!(orphaned.effective_at.is_none());
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let orphan = receipts.last().unwrap();
+        assert_eq!(orphan.receipt_json["operation"], "orphan");
+        assert!(orphan.receipt_json["metadata"]["superseded_effective_at"].is_string());
+
+        // Too late to cancel
+        let cancelled = cancel_orphan(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(CancelOrphanRequest {
+                pointer_id,
+                reason: None,
+            }),
+        )
+        .await;
+        assert!(matches!(
+            cancelled,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "already_orphaned"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_legal_hold_blocks_orphaning_until_released() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("held");
+        let pointer_id = seed_pointer(&state, &subject).await;
+
+        let Json(held) = place_legal_hold(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Path(pointer_id),
+            Json(LegalHoldRequest {
+                reason: "litigation_2026_114".to_string(),
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("hold failed"));
+        assert!(held.legal_hold);
+
+        // Vetoes are refused, single or by subject; resolve still works
+        let vetoed = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        assert!(matches!(vetoed, Err(ApiError::LegalHold { .. })));
+        let by_subject = orphan_subject(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Path(subject.clone()),
+            Json(OrphanSubjectRequest { reason: None }),
+        )
+        .await;
+        assert!(matches!(by_subject, Err(ApiError::LegalHold { .. })));
+        assert!(resolve_attested(&state, pointer_id).await.is_ok());
+
+        let Json(released) = release_legal_hold(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Path(pointer_id),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("release failed"));
+        assert!(!released.legal_hold);
+        assert_eq!(released.reason, "litigation_2026_114");
+
+        let vetoed = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        assert!(vetoed.is_ok(), "veto after release failed");
+
+        let operations: Vec<String> = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|r| r.receipt_json["operation"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            operations,
+            [
+                "create",
+                "legal_hold",
+                "resolve",
+                "legal_hold_release",
+                "orphan"
+            ]
+        );
+
+        let events: Vec<String> = sqlx::query_scalar(
+            "SELECT event_type FROM audit_log WHERE pointer_id = $1 AND event_type LIKE 'pointer_legal_hold%' ORDER BY timestamp",
+        )
+        .bind(pointer_id)
+        .fetch_all(&state.db.writer)
+        .await
+        .unwrap();
+        assert_eq!(
+            events,
+            ["pointer_legal_hold_set", "pointer_legal_hold_released"]
+        );
+    }
+
+    async fn seed_dual_approval_pointer(state: &AppState, subject: &str) -> Uuid {
+        let (_, Json(created)) = create_pointer(
+            State(state.clone()),
+            test_auth(state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(CreatePointerRequest {
+                requires_dual_approval: true,
+                ..create_request(subject, &"cd".repeat(64))
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("create failed"));
+        assert!(created.requires_dual_approval);
+        created.pointer_id
+    }
+
+    fn actor(actor_id: &str) -> RequestContext {
+        RequestContext {
+            actor_id: Some(actor_id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    async fn request_orphan_as(
+        state: &AppState,
+        actor_id: &str,
+        pointer_id: Uuid,
+    ) -> Result<OrphanRequestResponse, ApiError> {
+        request_orphan(
+            State(state.clone()),
+            test_auth(state),
+            actor(actor_id),
+            Json(RequestOrphanRequest {
+                pointer_id,
+                reason: Some("high_value_withdrawal".to_string()),
+            }),
+        )
+        .await
+        .map(|(_, Json(requested))| requested)
+    }
+
+    async fn approve_as(
+        state: &AppState,
+        actor_id: &str,
+        request_id: Uuid,
+    ) -> Result<Json<OrphanPointerResponse>, ApiError> {
+        approve_orphan_request(
+            State(state.clone()),
+            test_auth(state),
+            actor(actor_id),
+            Json(ApproveOrphanRequest { request_id }),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_dual_approval_pointer_needs_a_second_actor() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("dual");
+        let pointer_id = seed_dual_approval_pointer(&state, &subject).await;
+
+        // The single-call veto is refused, alone or by subject
+        let vetoed = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            actor("alice"),
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        assert!(matches!(
+            vetoed,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "dual_approval_required"
+        ));
+        let by_subject = orphan_subject(
+            State(state.clone()),
+            test_auth(&state),
+            actor("alice"),
+            Path(subject.clone()),
+            Json(OrphanSubjectRequest { reason: None }),
+        )
+        .await;
+        assert!(matches!(
+            by_subject,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "dual_approval_required"
+        ));
+
+        let requested = request_orphan_as(&state, "alice", pointer_id)
+            .await
+            .unwrap_or_else(|_| panic!("request failed"));
+        assert_eq!(requested.status, "pending");
+        assert_eq!(requested.requested_by, "alice");
+        assert!(resolve_attested(&state, pointer_id).await.is_ok());
+
+        // One open request at a time
+        let again = request_orphan_as(&state, "carol", pointer_id).await;
+        assert!(matches!(
+            again,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "orphan_request_pending"
+        ));
+
+        let self_approved = approve_as(&state, "alice", requested.request_id).await;
+        assert!(matches!(self_approved, Err(ApiError::Forbidden(_))));
+
+        let Json(orphaned) = approve_as(&state, "bob", requested.request_id)
+            .await
+            .unwrap_or_else(|_| panic!("approval failed"));
+        assert_eq!(orphaned.status, "orphaned");
+
+        let approved_again = approve_as(&state, "carol", requested.request_id).await;
+        assert!(matches!(
+            approved_again,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "orphan_request_not_pending"
+        ));
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let operations: Vec<&str> = receipts
+            .iter()
+            .map(|r| r.receipt_json["operation"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            operations,
+            ["create", "orphan_requested", "resolve", "orphan"]
+        );
+        let orphan = &receipts.last().unwrap().receipt_json["metadata"];
+        assert_eq!(orphan["orphan_request_id"], json!(requested.request_id));
+        assert_eq!(orphan["requested_by"], "alice");
+        assert_eq!(orphan["approved_by"], "bob");
+        assert_eq!(orphan["reason"], "high_value_withdrawal");
+        let keys = receipt_keys(&state.db.writer, &state.signing_keys, &receipts)
+            .await
+            .unwrap();
+        assert!(verify_chain(&receipts, &keys).chain_valid);
+
+        let Json(listed) =
+            list_orphan_requests(State(state.clone()), test_auth(&state), Path(pointer_id))
+                .await
+                .unwrap_or_else(|_| panic!("list failed"));
+        assert_eq!(listed.requests.len(), 1);
+        assert_eq!(listed.requests[0].status, "approved");
+        assert_eq!(listed.requests[0].approved_by.as_deref(), Some("bob"));
+    }
+
+    #[tokio::test]
+    async fn test_orphan_request_expires_after_ttl() {
+        use crate::clock::ManualClock;
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let requested_at = state.clock.now();
+        let clock = std::sync::Arc::new(ManualClock::new(requested_at));
+        let state = AppState {
+            clock: clock.clone(),
+            ..state
+        };
+        let pointer_id = seed_dual_approval_pointer(&state, &unique_subject("dual-ttl")).await;
+
+        let requested = request_orphan_as(&state, "alice", pointer_id)
+            .await
+            .unwrap_or_else(|_| panic!("request failed"));
+
+        clock
+            .set(requested_at + chrono::Duration::seconds(state.config.orphan_request_ttl_seconds));
+
+        let Json(listed) =
+            list_orphan_requests(State(state.clone()), test_auth(&state), Path(pointer_id))
+                .await
+                .unwrap_or_else(|_| panic!("list failed"));
+        assert_eq!(listed.requests[0].status, "expired");
+
+        let late = approve_as(&state, "bob", requested.request_id).await;
+        assert!(matches!(
+            late,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "orphan_request_expired"
+        ));
+        assert!(resolve_attested(&state, pointer_id).await.is_ok());
+
+        // The lapse frees the pointer for a fresh request
+        let renewed = request_orphan_as(&state, "alice", pointer_id)
+            .await
+            .unwrap_or_else(|_| panic!("renewed request failed"));
+        assert_ne!(renewed.request_id, requested.request_id);
+    }
+
+    #[tokio::test]
+    async fn test_unflagged_pointer_rejects_orphan_requests() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("single")).await;
+
+        let requested = request_orphan_as(&state, "alice", pointer_id).await;
+        assert!(matches!(
+            requested,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "dual_approval_not_required"
+        ));
+        let vetoed = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            actor("alice"),
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        assert!(vetoed.is_ok(), "single-call veto failed");
+    }
+
+    /// State that allows reinstating orphaned pointers
+    fn with_reinstatement(state: &AppState) -> AppState {
+        let mut config = state.config.clone();
+        config.allow_reinstatement = true;
+        AppState {
+            config,
+            ..state.clone()
+        }
+    }
+
+    async fn reinstate(
+        state: &AppState,
+        pointer_id: Uuid,
+    ) -> Result<Json<ReinstatePointerResponse>, ApiError> {
+        reinstate_pointer(
+            State(state.clone()),
+            test_auth(state),
+            actor("support:dana"),
+            Json(ReinstatePointerRequest {
+                pointer_id,
+                justification: "Wrong pointer id pasted, ticket 4411".to_string(),
+            }),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_reinstated_pointer_resolves_and_keeps_its_orphan_history() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let state = with_reinstatement(&state);
+        let pointer_id = seed_pointer(&state, &unique_subject("reinstate")).await;
+
+        let _ = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, Some("operator_error")),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("orphan failed"));
+        assert!(matches!(
+            resolve_attested(&state, pointer_id).await,
+            Err(ApiError::PointerOrphaned(_))
+        ));
+
+        let Json(reinstated) = reinstate(&state, pointer_id)
+            .await
+            .unwrap_or_else(|_| panic!("reinstate failed"));
+        assert_eq!(reinstated.status, "active");
+        assert_eq!(reinstated.orphan_reason, "operator_error");
+        assert!(resolve_attested(&state, pointer_id).await.is_ok());
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let operations: Vec<&str> = receipts
+            .iter()
+            .map(|r| r.receipt_json["operation"].as_str().unwrap())
+            .collect();
+        assert_eq!(operations, ["create", "orphan", "reinstate", "resolve"]);
+        let metadata = &receipts[2].receipt_json["metadata"];
+        assert_eq!(
+            metadata["justification"],
+            "Wrong pointer id pasted, ticket 4411"
+        );
+        assert_eq!(metadata["reinstated_by"], "support:dana");
+        let keys = receipt_keys(&state.db.writer, &state.signing_keys, &receipts)
+            .await
+            .unwrap();
+        assert!(verify_chain(&receipts, &keys).chain_valid);
+
+        // The orphan moves from the columns to the metadata history
+        let pointer = get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(pointer.orphaned_at.is_none());
+        let history = pointer.metadata["orphan_history"].as_array().unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0]["orphan_reason"], "operator_error");
+        assert_eq!(history[0]["reinstated_by"], "support:dana");
+
+        let actor_id: Option<String> = sqlx::query_scalar(
+            "SELECT actor_id FROM audit_log WHERE pointer_id = $1 AND event_type = 'pointer_reinstated'",
+        )
+        .bind(pointer_id)
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        assert_eq!(actor_id.as_deref(), Some("support:dana"));
+
+        // The chain's orphan is reversed, so the auditor sees no mismatch
+        let Json(report) = check_pointer_consistency(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query(ConsistencyQuery { repair: false }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("consistency check failed"));
+        assert!(report.mismatches.is_empty(), "{:?}", report.mismatches);
+    }
+
+    #[tokio::test]
+    async fn test_reinstating_an_active_pointer_is_a_bad_request() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let state = with_reinstatement(&state);
+        let pointer_id = seed_pointer(&state, &unique_subject("reinstate-active")).await;
+
+        let result = reinstate(&state, pointer_id).await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reinstatement_is_refused_unless_enabled() {
+        use axum::response::IntoResponse;
+
+        let state = crate::test_support::offline_state();
+        assert!(!state.config.allow_reinstatement);
+
+        let result = reinstate(&state, Uuid::new_v4()).await;
+        let Err(err) = result else {
+            panic!("reinstate allowed while disabled");
+        };
+        assert_eq!(err.code(), "reinstatement_disabled");
+        assert_eq!(err.into_response().status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_repeat_orphan_is_idempotent() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("repeat")).await;
+
+        let Json(first) = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, Some("moved")),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("first orphan failed"));
+        assert!(!first.already_orphaned);
+
+        // Same reason and no reason both return the original receipt
+        for reason in [Some("moved"), None] {
+            let Json(repeat) = orphan_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                orphan_request(pointer_id, reason),
+            )
+            .await
+            .unwrap_or_else(|_| panic!("repeat orphan failed"));
+            assert!(repeat.already_orphaned);
+            assert_eq!(repeat.orphaned_at, first.orphaned_at);
+            assert_eq!(repeat.receipt.receipt_hash, first.receipt.receipt_hash);
+        }
+
+        // A different reason is a conflict rather than silently dropped
+        let result = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, Some("other")),
+        )
+        .await;
+        match result {
+            Err(ApiError::Conflict { conflict_type, .. }) => {
+                assert_eq!(conflict_type, "orphan_reason_mismatch")
+            }
+            _ => panic!("expected Conflict"),
+        }
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        assert_eq!(receipts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_orphans_write_one_receipt() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("race")).await;
+
+        let (a, b) = tokio::join!(
+            orphan_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                orphan_request(pointer_id, None)
+            ),
+            orphan_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                orphan_request(pointer_id, None)
+            ),
+        );
+        let (Ok(Json(a)), Ok(Json(b))) = (a, b) else {
+            panic!("concurrent orphan failed")
+        };
+
+        assert!(a.already_orphaned != b.already_orphaned);
+        assert_eq!(a.receipt.receipt_hash, b.receipt.receipt_hash);
+        assert_eq!(a.orphaned_at, b.orphaned_at);
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let orphans = receipts
+            .iter()
+            .filter(|r| matches!(r.operation, ReceiptOperation::Orphan))
+            .count();
+        assert_eq!(orphans, 1);
+    }
+
+    fn restrict_request(duration_seconds: i64) -> Json<RestrictSubjectRequest> {
+        Json(RestrictSubjectRequest {
+            duration_seconds,
+            reason: "subject_dispute".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_restriction_denies_until_manual_lift() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("restrict");
+        let pointer_id = seed_pointer(&state, &subject).await;
+
+        let Json(restricted) = restrict_subject(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            restrict_request(3600),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("restrict failed"));
+        assert_eq!(restricted.pointers.len(), 1);
+
+        let result = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        match result {
+            Err(ApiError::ProcessingRestricted { restricted_until }) => {
+                assert_eq!(restricted_until, restricted.expires_at)
+            }
+            _ => panic!("expected ProcessingRestricted"),
+        }
+
+        // A second restriction while one is in force is a conflict
+        let again = restrict_subject(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            restrict_request(60),
+        )
+        .await;
+        assert!(matches!(again, Err(ApiError::Conflict { .. })));
+
+        let Json(lifted) = unrestrict_subject(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("unrestrict failed"));
+        assert_eq!(lifted.pointers.len(), 1);
+
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "resolve after lift failed");
+
+        let operations: Vec<String> = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|r| r.receipt_json["operation"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(operations, ["create", "restrict", "unrestrict", "resolve"]);
+
+        let Json(trail) = get_audit_trail(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("audit trail failed"));
+        assert_eq!(trail.restrictions.len(), 1);
+        assert_eq!(trail.restrictions[0].lift_reason.as_deref(), Some("manual"));
+    }
+
+    #[tokio::test]
+    async fn test_orphan_during_restriction_supersedes_it() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("restrict");
+        let pointer_id = seed_pointer(&state, &subject).await;
+
+        let Json(restricted) = restrict_subject(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            restrict_request(3600),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("restrict failed"));
+
+        let Json(orphaned) = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("orphan during restriction failed"));
+        assert!(!orphaned.already_orphaned);
+
+        let result = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::PointerOrphaned(_))));
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let orphan = receipts.last().unwrap();
+        assert_eq!(
+            orphan.receipt_json["metadata"]["lifted_restriction_id"],
+            json!(restricted.pointers[0].restriction_id)
+        );
+        assert!(get_open_restriction(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_saturated_background_pool_leaves_resolve_unaffected() {
+        use crate::{db::create_pool, restrictions::sweep_expired_restrictions};
+        use std::time::{Duration, Instant};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("partition")).await;
+
+        let background = create_pool(
+            state.config.database_url.expose(),
+            &state.config.database_pool.with_max_connections(1),
+            "background",
+        )
+        .await
+        .unwrap();
+        let _held = background.acquire().await.unwrap();
+
+        // Background work queues behind the held connection...
+        let sweep = tokio::time::timeout(
+            Duration::from_millis(200),
+            sweep_expired_restrictions(&background, &state.keypair(), chrono::Utc::now()),
+        )
+        .await;
+        assert!(sweep.is_err(), "background sweep should be starved");
+
+        // ...while interactive resolves stay within budget
+        let started = Instant::now();
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "resolve failed");
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_long_subjects_are_stored_under_digest_key() {
+        use crate::subjects::{subject_digest, SUBJECT_DIGEST_PREFIX};
+
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        state.config.subject_digest_threshold = 64;
+
+        // A JWT-sized identifier
+        let subject = format!("{}.{}", unique_subject("jwt"), "eyJhbGciOi".repeat(200));
+        let key = subject_digest(&subject);
+        let pointer_id = seed_pointer(&state, &subject).await;
+
+        // Lookups by the full value and by the key land on the same pointer
+        for lookup in [subject.clone(), key.clone()] {
+            let Json(trail) = get_audit_trail(
+                State(state.clone()),
+                test_auth(&state),
+                Path(lookup),
+                Query::default(),
+            )
+            .await
+            .unwrap_or_else(|_| panic!("audit trail failed"));
+            assert_eq!(trail.subject_id, key);
+            assert_eq!(trail.total_pointers, 1);
+        }
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let create = &receipts[0].receipt_json;
+        assert_eq!(create["subject_id"], json!(key));
+        assert!(create["metadata"]["subject_id_preview"].is_string());
+        assert!(!create.to_string().contains(&subject));
+
+        let stored: String =
+            sqlx::query_scalar("SELECT subject_value FROM subjects WHERE subject_key = $1")
+                .bind(&key)
+                .fetch_one(&state.db.writer)
+                .await
+                .unwrap();
+        assert_eq!(stored, subject);
+
+        // Short ids are stored as-is
+        let short = unique_subject("short");
+        let short_id = seed_pointer(&state, &short).await;
+        let pointer = get_pointer(&state.db.writer, short_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pointer.subject_id, short);
+        assert!(!pointer.subject_id.starts_with(SUBJECT_DIGEST_PREFIX));
+
+        // Beyond the limit the request is rejected outright
+        let req = CreatePointerRequest {
+            subject_id: "x".repeat(MAX_SUBJECT_BYTES + 1),
+            content_hash: "ab".repeat(64),
+            encrypted_payload: None,
+            payload_encoding: None,
+            retention_class: None,
+            expires_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
+        };
+        assert!(matches!(
+            create_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                IdempotencyKey::default(),
+                Json(req)
+            )
+            .await,
+            Err(ApiError::ValidationFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_strict_reads_tombstone_tampered_receipts() {
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("strict")).await;
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "resolve failed");
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let tampered = receipts[0].receipt_id;
+        sqlx::query(
+            "UPDATE governance_receipts \
+             SET receipt_json = jsonb_set(receipt_json, '{subject_id}', '\"someone_else\"') \
+             WHERE receipt_id = $1",
+        )
+        .bind(tampered)
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+
+        // Non-strict reads serve the stored row as-is
+        let Json(raw) = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("receipts failed"));
+        assert!(raw.integrity_verified.is_none());
+        assert!(raw
+            .receipts
+            .iter()
+            .all(|r| matches!(r, ReceiptEntry::Receipt(_))));
+
+        state.config.strict_receipt_reads = true;
+        let alerts = || async {
+            sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM audit_log \
+                 WHERE receipt_id = $1 AND event_type = 'receipt_verification_failed'",
+            )
+            .bind(tampered)
+            .fetch_one(&state.db.writer)
+            .await
+            .unwrap()
+        };
+
+        for _ in 0..2 {
+            let Json(strict) = get_receipts(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query::default(),
+            )
+            .await
+            .unwrap_or_else(|_| panic!("strict receipts failed"));
+            assert_eq!(strict.integrity_verified, Some(false));
+            assert!(matches!(
+                &strict.receipts[0],
+                ReceiptEntry::Tombstone(t)
+                    if t.receipt_id == tampered && t.failure == ReceiptFailure::HashMismatch
+            ));
+            assert!(matches!(strict.receipts[1], ReceiptEntry::Receipt(_)));
+        }
+
+        // The cached failure is not re-alerted on the second read
+        assert_eq!(alerts().await, 1);
+
+        // Untouched chains verify under strict reads
+        let clean = seed_pointer(&state, &unique_subject("strict")).await;
+        let Json(verified) = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(clean),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("strict receipts failed"));
+        assert_eq!(verified.integrity_verified, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_failed_create_persists_nothing() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("atomic");
+        let function = format!("fail_{}", Uuid::new_v4().simple());
+
+        // Fail the receipt insert, after data and pointer rows are written,
+        // for this subject only
+        sqlx::query(&format!(
+            "CREATE FUNCTION {function}() RETURNS TRIGGER AS $$ BEGIN \
+                 IF NEW.receipt_json->>'subject_id' = '{subject}' THEN \
+                     RAISE EXCEPTION 'injected receipt failure'; \
+                 END IF; \
+                 RETURN NEW; \
+             END; $$ LANGUAGE plpgsql"
+        ))
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+        sqlx::query(&format!(
+            "CREATE TRIGGER {function} BEFORE INSERT ON governance_receipts \
+             FOR EACH ROW EXECUTE FUNCTION {function}()"
+        ))
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+
+        let req = CreatePointerRequest {
+            subject_id: subject.clone(),
+            content_hash: "ab".repeat(64),
+            encrypted_payload: None,
+            payload_encoding: None,
+            retention_class: None,
+            expires_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
+        };
+        let created = create_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(req),
+        )
+        .await;
+
+        sqlx::query(&format!("DROP TRIGGER {function} ON governance_receipts"))
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+        sqlx::query(&format!("DROP FUNCTION {function}()"))
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+
+        assert!(created.is_err(), "create should fail");
+        assert!(get_pointers_by_subject(&state.db.writer, &subject)
+            .await
+            .unwrap()
+            .is_empty());
+        let data_rows: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM data_store WHERE subject_id = $1")
+                .bind(&subject)
+                .fetch_one(&state.db.writer)
+                .await
+                .unwrap();
+        assert_eq!(data_rows, 0);
+        assert!(audit_trail(&state, &subject).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolves_racing_orphan_keep_chain_linear() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("race")).await;
+
+        let mut calls = tokio::task::JoinSet::new();
+        for i in 0..8 {
+            let state = state.clone();
+            calls.spawn(async move {
+                if i == 4 {
+                    let req = OrphanPointerRequest {
+                        pointer_id,
+                        reason: None,
+                        effective_at: None,
+                    };
+                    orphan_pointer(
+                        State(state.clone()),
+                        test_auth(&state),
+                        RequestContext::default(),
+                        Json(req),
+                    )
+                    .await
+                    .is_ok()
+                } else {
+                    // Denied once the veto lands; that is expected
+                    let _ = resolve_pointer(
+                        State(state.clone()),
+                        test_auth(&state),
+                        Path(pointer_id),
+                        Query::default(),
+                    )
+                    .await;
+                    true
+                }
+            });
+        }
+        while let Some(ok) = calls.join_next().await {
+            assert!(ok.unwrap(), "orphan failed");
+        }
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        for pair in receipts.windows(2) {
+            assert_eq!(
+                pair[1].prev_hash.as_deref(),
+                Some(pair[0].receipt_hash.as_str()),
+                "chain forked"
+            );
+        }
+
+        // Nothing is granted after the veto
+        let orphan_at = receipts
+            .iter()
+            .position(|r| matches!(r.operation, ReceiptOperation::Orphan))
+            .expect("orphan receipt");
+        assert!(receipts[orphan_at + 1..]
+            .iter()
+            .all(|r| !matches!(r.operation, ReceiptOperation::Resolve)));
+    }
+
+    #[tokio::test]
+    async fn test_orphan_update_applies_once() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("conditional")).await;
+
+        let now = chrono::Utc::now();
+        let orphan = || queries::orphan_pointer(&state.db.writer, pointer_id, None, now);
+        assert!(orphan().await.unwrap().is_some());
+        assert!(orphan().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_verify_receipt_chain() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("verify")).await;
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "resolve failed");
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+
+        let Json(intact) =
+            verify_receipt_chain(State(state.clone()), test_auth(&state), Path(pointer_id))
+                .await
+                .unwrap_or_else(|_| panic!("verify failed"));
+        assert!(intact.verification.chain_valid);
+        assert_eq!(intact.verification.receipts.len(), 3);
+
+        // Deleting the resolve receipt orphans the link from the orphan receipt
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM governance_receipts WHERE receipt_id = $1")
+            .bind(receipts[1].receipt_id)
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+
+        let Json(broken) =
+            verify_receipt_chain(State(state.clone()), test_auth(&state), Path(pointer_id))
+                .await
+                .unwrap_or_else(|_| panic!("verify failed"));
+        assert!(!broken.verification.chain_valid);
+        let link = broken.verification.first_broken_link.unwrap();
+        assert_eq!(link.receipt_id, receipts[2].receipt_id);
+        assert_eq!(
+            link.prev_hash.as_deref(),
+            Some(receipts[1].receipt_hash.as_str())
+        );
+        assert!(broken
+            .verification
+            .receipts
+            .iter()
+            .all(|r| r.failure.is_none()));
+
+        let missing = verify_receipt_chain(
+            State(state.clone()),
+            test_auth(&state),
+            Path(Uuid::new_v4()),
+        )
+        .await;
+        assert!(matches!(missing, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_veto_slo_compliance_from_injected_latency() {
+        use axum::response::IntoResponse;
+
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (name) VALUES ('SLO Org') RETURNING org_id",
+        )
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        state.config.default_org_id = org_id;
+        state.config.veto_slo_target_ms = 250;
+
+        let slow_subject = unique_subject("slo_slow");
+        let function = format!("slow_{}", Uuid::new_v4().simple());
+
+        // Stall orphan receipts for one subject past the target
+        sqlx::query(&format!(
+            "CREATE FUNCTION {function}() RETURNS TRIGGER AS $$ BEGIN \
+                 IF NEW.receipt_json->>'subject_id' = '{slow_subject}' \
+                    AND NEW.operation = 'orphan' THEN \
+                     PERFORM pg_sleep(0.5); \
+                 END IF; \
+                 RETURN NEW; \
+             END; $$ LANGUAGE plpgsql"
+        ))
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+        sqlx::query(&format!(
+            "CREATE TRIGGER {function} BEFORE INSERT ON governance_receipts \
+             FOR EACH ROW EXECUTE FUNCTION {function}()"
+        ))
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+
+        let slow = seed_pointer(&state, &slow_subject).await;
+        let mut fast = Vec::new();
+        for _ in 0..5 {
+            fast.push(seed_pointer(&state, &unique_subject("slo_fast")).await);
+        }
+
+        let slow_orphan = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(slow, None),
+        )
+        .await;
+        for pointer_id in &fast[..3] {
+            let orphaned = orphan_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                orphan_request(*pointer_id, None),
+            );
+            assert!(orphaned.await.is_ok(), "orphan failed");
+        }
+        // A group veto counts once
+        let group = orphan_group(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanGroupRequest {
+                pointer_ids: fast[3..].to_vec(),
+                reason: None,
+            }),
+        )
+        .await;
+
+        sqlx::query(&format!("DROP TRIGGER {function} ON governance_receipts"))
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+        sqlx::query(&format!("DROP FUNCTION {function}()"))
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+        assert!(slow_orphan.is_ok(), "slow orphan failed");
+        assert!(group.is_ok(), "group orphan failed");
+
+        let query = VetoSloQuery {
+            org_id: Some(org_id),
+            from: None,
+            to: None,
+        };
+        let Json(report) = get_veto_slo(State(state.clone()), test_auth(&state), Query(query))
+            .await
+            .unwrap_or_else(|_| panic!("slo report failed"));
+        assert_eq!(report.days.len(), 1);
+        let today = &report.days[0];
+        assert_eq!(today.day.target_ms, 250);
+        assert_eq!(today.day.veto_count, 5);
+        assert_eq!(today.day.within_target_count, 4);
+        assert!(today.day.max_latency_ms >= 500);
+        assert_eq!(today.compliance_percent, 80.0);
+
+        let metrics = get_metrics(State(state.clone()), test_auth(&state))
+            .await
+            .unwrap_or_else(|_| panic!("metrics failed"))
+            .into_response();
+        let body = axum::body::to_bytes(metrics.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        let sample = format!(
+            "veto_slo_compliance_percent{{org_id=\"{}\",target_ms=\"250\"}} 80\n",
+            org_id
+        );
+        assert!(text.contains(&sample), "{}", text);
+
+        // Other orgs see only their own days, and may not name this one
+        let tenant = AuthContext {
+            org_id: Uuid::new_v4(),
+            key_id: Uuid::new_v4(),
+        };
+        let unnamed = VetoSloQuery {
+            org_id: None,
+            from: None,
+            to: None,
+        };
+        let Json(theirs) = get_veto_slo(State(state.clone()), tenant, Query(unnamed))
+            .await
+            .unwrap_or_else(|_| panic!("tenant slo report failed"));
+        assert!(theirs.days.is_empty());
+        let named = VetoSloQuery {
+            org_id: Some(org_id),
+            from: None,
+            to: None,
+        };
+        let result = get_veto_slo(State(state.clone()), tenant, Query(named)).await;
+        assert!(matches!(result, Err(ApiError::Forbidden(_))));
+
+        let reversed = VetoSloQuery {
+            org_id: None,
+            from: Some(today.day.day),
+            to: today.day.day.pred_opt(),
+        };
+        let result = get_veto_slo(State(state.clone()), test_auth(&state), Query(reversed)).await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_cross_org_admin_views_are_operator_only() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let tenant = AuthContext {
+            org_id: Uuid::new_v4(),
+            key_id: Uuid::new_v4(),
+        };
+        let forbidden = |result: Result<(), ApiError>| {
+            assert!(
+                matches!(result, Err(ApiError::Forbidden(_))),
+                "expected Forbidden, got {:?}",
+                result
+            )
+        };
+
+        let slo = get_veto_slo(
+            State(state.clone()),
+            tenant,
+            Query(VetoSloQuery {
+                org_id: Some(state.config.default_org_id),
+                from: None,
+                to: None,
+            }),
+        )
+        .await;
+        forbidden(slo.map(|_| ()));
+        let metrics = get_metrics(State(state.clone()), tenant).await;
+        forbidden(metrics.map(|_| ()));
+        let retention = get_retention_report(
+            State(state.clone()),
+            tenant,
+            Query(RetentionReportQuery::default()),
+        )
+        .await;
+        forbidden(retention.map(|_| ()));
+        let coalescing = get_resolve_coalescing_stats(State(state.clone()), tenant).await;
+        forbidden(coalescing.map(|_| ()));
+
+        assert!(
+            get_resolve_coalescing_stats(State(state.clone()), test_auth(&state))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_receipt_verifies_with_published_key() {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let req = CreatePointerRequest {
+            subject_id: unique_subject("jwks"),
+            content_hash: "ab".repeat(64),
+            encrypted_payload: None,
+            payload_encoding: None,
+            retention_class: None,
+            expires_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
+        };
+        let (_, Json(created)) = create_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(req),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("create failed"));
+
+        // Round-trip through JSON: only what a client would see
+        let Json(keys) = get_public_keys(
+            State(state.clone()),
+            Query(PublicKeysQuery {
+                org_id: Some(state.config.default_org_id),
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("public keys failed"));
+        let keys = serde_json::to_value(&keys).unwrap();
+        let jwk: Jwk = serde_json::from_value(keys["keys"][0].clone()).unwrap();
+        assert_eq!((jwk.kty.as_str(), jwk.crv.as_str()), ("OKP", "Ed25519"));
+        assert_eq!(jwk.kid, created.receipt.key_id);
+
+        let x = data_encoding::BASE64URL_NOPAD
+            .decode(jwk.x.as_bytes())
+            .unwrap();
+        let key = VerifyingKey::from_bytes(&x.try_into().unwrap()).unwrap();
+        let signature = data_encoding::BASE64
+            .decode(created.receipt.signature.as_bytes())
+            .unwrap();
+        let signature = Signature::from_slice(&signature).unwrap();
+        assert!(key
+            .verify(created.receipt.receipt_hash.as_bytes(), &signature)
+            .is_ok());
+
+        let hex_key = data_encoding::HEXLOWER
+            .decode(keys["public_key_hex"].as_str().unwrap().as_bytes())
+            .unwrap();
+        assert_eq!(hex_key, key.to_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_org_receipts_verify_only_with_own_key() {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let published_key = |org_id: Option<Uuid>| {
+            let state = state.clone();
+            async move {
+                get_public_keys(State(state), Query(PublicKeysQuery { org_id }))
+                    .await
+                    .map(|Json(published)| published)
+            }
+        };
+
+        let mut orgs = Vec::new();
+        for name in ["Org Key A", "Org Key B"] {
+            let mut org_state = state.clone();
+            org_state.config.default_org_id =
+                sqlx::query_scalar("INSERT INTO organizations (name) VALUES ($1) RETURNING org_id")
+                    .bind(name)
+                    .fetch_one(&state.db.writer)
+                    .await
+                    .unwrap();
+            let pointer_id = seed_pointer(&org_state, &unique_subject("org_key")).await;
+            let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+                .await
+                .unwrap();
+
+            let published = published_key(Some(org_state.config.default_org_id))
+                .await
+                .unwrap_or_else(|_| panic!("public keys failed"));
+            assert_eq!(
+                receipts[0].key_id.as_deref(),
+                Some(published.key_id.as_str())
+            );
+            let key = data_encoding::BASE64
+                .decode(published.public_key_base64.as_bytes())
+                .unwrap();
+            let key = VerifyingKey::from_bytes(&key.try_into().unwrap()).unwrap();
+            orgs.push((org_state, pointer_id, receipts, key));
+        }
+        let [(a, a_pointer, a_receipts, a_key), (b, _, _, b_key)] = &orgs[..] else {
+            unreachable!()
+        };
+        assert_ne!(a_key, b_key);
+        assert_ne!(a_receipts[0].key_id, Some(state.keypair().key_id()));
+
+        assert!(verify_chain(a_receipts, a_key).chain_valid);
+        let against_b = verify_chain(a_receipts, b_key);
+        assert!(!against_b.chain_valid);
+        assert_eq!(
+            against_b.receipts[0].failure,
+            Some(ReceiptFailure::UnknownKey)
+        );
+        let signature = Signature::from_slice(&a_receipts[0].signature).unwrap();
+        assert!(b_key
+            .verify(a_receipts[0].receipt_hash.as_bytes(), &signature)
+            .is_err());
+
+        let Json(verified) = verify_receipt_chain(State(a.clone()), test_auth(a), Path(*a_pointer))
+            .await
+            .unwrap_or_else(|_| panic!("verify failed"));
+        assert!(verified.verification.chain_valid);
+
+        // Filed under org B, A's receipt finds no key to verify it
+        let mut moved = a_receipts.clone();
+        moved[0].org_id = b.config.default_org_id;
+        let keys = receipt_keys(&state.db.writer, &state.signing_keys, &moved)
+            .await
+            .unwrap();
+        assert_eq!(
+            verify_chain(&moved, &keys).receipts[0].failure,
+            Some(ReceiptFailure::UnknownKey)
+        );
+
+        let unknown = published_key(Some(Uuid::new_v4())).await;
+        assert!(matches!(unknown, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_verify_submitted_receipt() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("submitted")).await;
+        let receipt = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .remove(0);
+        let submit = |receipt_json: serde_json::Value, public_key_base64: Option<String>| {
+            let state = state.clone();
+            let req = VerifySubmittedReceiptRequest {
+                receipt_json,
+                receipt_hash: receipt.receipt_hash.clone(),
+                signature_base64: data_encoding::BASE64.encode(&receipt.signature),
+                signature_algorithm: default_signature_algorithm(),
+                public_key_base64,
+            };
+            async move {
+                verify_submitted_receipt(State(state), Json(req))
+                    .await
+                    .map(|Json(verified)| verified)
+            }
+        };
+
+        // As emailed: pretty-printed JSON text, checked against the org's
+        // key this service recorded under the receipt's key_id
+        let text = serde_json::to_string_pretty(&receipt.receipt_json).unwrap();
+        let verified = submit(json!(text), None)
+            .await
+            .unwrap_or_else(|_| panic!("verify failed"));
+        assert!(verified.valid);
+        assert_eq!(verified.key_source, Some(KeySource::Organization));
+        assert_eq!(verified.key_id, receipt.key_id);
+        assert_eq!(verified.org_id, Some(state.config.default_org_id));
+
+        let Json(published) = get_public_keys(
+            State(state.clone()),
+            Query(PublicKeysQuery {
+                org_id: Some(state.config.default_org_id),
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("public keys failed"));
+        let verified = submit(
+            receipt.receipt_json.clone(),
+            Some(published.public_key_base64),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("verify failed"));
+        assert!(verified.valid);
+        assert_eq!(verified.key_source, Some(KeySource::Supplied));
+
+        let other = data_encoding::BASE64
+            .encode(&crate::crypto::Ed25519Keypair::generate().public_key_bytes());
+        let verified = submit(receipt.receipt_json.clone(), Some(other))
+            .await
+            .unwrap_or_else(|_| panic!("verify failed"));
+        assert!(!verified.valid);
+        assert!(verified.result.hash_valid);
+        assert_eq!(verified.result.failure, Some(ReceiptFailure::UnknownKey));
+
+        let mut tampered = receipt.receipt_json.clone();
+        tampered["operation"] = json!("ORPHAN");
+        let verified = submit(tampered, None)
+            .await
+            .unwrap_or_else(|_| panic!("verify failed"));
+        assert_eq!(verified.result.failure, Some(ReceiptFailure::HashMismatch));
+
+        let mut unknown = receipt.receipt_json.clone();
+        unknown["key_id"] = json!("0".repeat(64));
+        let verified = submit(unknown, None)
+            .await
+            .unwrap_or_else(|_| panic!("verify failed"));
+        assert_eq!(verified.key_source, None);
+
+        assert!(matches!(
+            submit(json!("{not json"), None).await,
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            submit(receipt.receipt_json.clone(), Some("AAAA".to_string())).await,
+            Err(ApiError::BadRequest(_))
+        ));
+        let bad_signature = verify_submitted_receipt(
+            State(state.clone()),
+            Json(VerifySubmittedReceiptRequest {
+                receipt_json: receipt.receipt_json.clone(),
+                receipt_hash: receipt.receipt_hash.clone(),
+                signature_base64: "not base64!".to_string(),
+                signature_algorithm: default_signature_algorithm(),
+                public_key_base64: None,
+            }),
+        )
+        .await;
+        assert!(matches!(bad_signature, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_receipt_proof_leads_to_listed_checkpoint() {
+        use crate::checkpoints::write_checkpoints;
+        use crate::crypto::merkle::verify_inclusion;
+        use crate::test_support::checkpoint_keypair;
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("proof")).await;
+        let receipt = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .remove(0);
+        let proof = |auth: AuthContext, receipt_hash: String| {
+            let state = state.clone();
+            async move {
+                get_receipt_proof(State(state), auth, Path(receipt_hash))
+                    .await
+                    .map(|Json(proof)| proof)
+            }
+        };
+
+        assert!(matches!(
+            proof(test_auth(&state), "not-a-hash".to_string()).await,
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            proof(test_auth(&state), "cd".repeat(64)).await,
+            Err(ApiError::NotFound(_))
+        ));
+        let other = AuthContext {
+            org_id: Uuid::new_v4(),
+            key_id: Uuid::new_v4(),
+        };
+        assert!(matches!(
+            proof(other, receipt.receipt_hash.clone()).await,
+            Err(ApiError::Forbidden(_))
+        ));
+
+        write_checkpoints(
+            &state.db.writer,
+            &checkpoint_keypair(),
+            chrono::Utc::now() + chrono::Duration::seconds(10),
+        )
+        .await
+        .unwrap();
+        let proved = proof(test_auth(&state), receipt.receipt_hash.clone())
+            .await
+            .unwrap_or_else(|_| panic!("proof failed"));
+        assert_eq!(proved.proof.receipt_hash, receipt.receipt_hash);
+        assert!(verify_inclusion(
+            &receipt.receipt_hash,
+            &proved.proof.path,
+            &proved.checkpoint.merkle_root
+        ));
+
+        // The checkpoint is listed with the same signed root
+        let Json(listed) = list_checkpoints(
+            State(state.clone()),
+            test_auth(&state),
+            Query(ListCheckpointsQuery {
+                limit: Some(PAGE_MAX_LIMIT),
+                cursor: None,
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("list failed"));
+        let listed = listed
+            .checkpoints
+            .iter()
+            .find(|c| c.checkpoint_id == proved.checkpoint.checkpoint_id)
+            .expect("checkpoint not listed");
+        assert_eq!(listed.merkle_root, proved.checkpoint.merkle_root);
+        assert_eq!(listed.signature, proved.checkpoint.signature);
+    }
+
+    #[tokio::test]
+    async fn test_receipt_lookup_by_hash_and_prefix() {
+        use axum::response::IntoResponse;
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let lookup = |receipt_hash: String, prefix: bool| {
+            let state = state.clone();
+            async move {
+                let response = match get_receipt_by_hash_handler(
+                    State(state.clone()),
+                    test_auth(&state),
+                    Path(receipt_hash),
+                    Query(ReceiptByHashQuery { prefix }),
+                )
+                .await
+                {
+                    Ok(response) => response,
+                    Err(e) => e.into_response(),
+                };
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+                )
+            }
+        };
+
+        let first = seed_pointer(&state, &unique_subject("by_hash")).await;
+        let second = seed_pointer(&state, &unique_subject("by_hash")).await;
+        let receipt = get_receipts_by_pointer(&state.db.writer, first)
+            .await
+            .unwrap()
+            .remove(0);
+
+        // Exact match returns the stored row
+        let (status, body) = lookup(receipt.receipt_hash.clone(), false).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["receipt_id"], receipt.receipt_id.to_string());
+        assert_eq!(body["pointer_id"], first.to_string());
+        assert_eq!(body["operation"], "create");
+        assert_eq!(body["receipt_json"], receipt.receipt_json);
+        assert_eq!(
+            body["signature"],
+            data_encoding::BASE64.encode(&receipt.signature)
+        );
+        assert_eq!(body["prev_hash"], json!(receipt.prev_hash));
+
+        // A unique prefix finds the same receipt
+        let (status, body) = lookup(receipt.receipt_hash[..24].to_string(), true).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["receipt_hash"], receipt.receipt_hash);
+
+        // Two receipts sharing a prefix are listed, not picked from
+        let shared = Uuid::new_v4().simple().to_string()[..16].to_string();
+        sqlx::query(
+            "UPDATE governance_receipts SET receipt_hash = $1 || substr(receipt_hash, 17)
+             WHERE pointer_id = ANY($2)",
+        )
+        .bind(&shared)
+        .bind(vec![first, second])
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+        let (status, body) = lookup(shared.clone(), true).await;
+        assert_eq!(status, StatusCode::MULTIPLE_CHOICES);
+        assert_eq!(body["error"], "ambiguous_receipt_hash");
+        assert_eq!(body["truncated"], false);
+        let mut pointers: Vec<&str> = body["matches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["pointer_id"].as_str().unwrap())
+            .collect();
+        pointers.sort();
+        let mut expected = [first.to_string(), second.to_string()];
+        expected.sort();
+        assert_eq!(pointers, expected);
+
+        // Not found, and malformed hashes or prefixes
+        let (status, _) = lookup("cd".repeat(64), false).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = lookup("cd".repeat(10), true).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        for (hash, prefix) in [
+            (shared.clone(), false),
+            (shared[..15].to_string(), true),
+            (shared.to_uppercase(), true),
+        ] {
+            let (status, _) = lookup(hash, prefix).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_verifies_across_signing_key_rotation() {
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        // Only the service key signs, as without SIGNING_MASTER_KEY
+        state.org_signing_keys = None;
+        register_signing_keys(&state.db.writer, &state.signing_keys, state.clock.now())
+            .await
+            .unwrap();
+        let old_keypair = state.keypair();
+        let old_key_id = old_keypair.key_id();
+        let pointer_id = seed_pointer(&state, &unique_subject("rotation")).await;
+
+        let other = AuthContext {
+            org_id: Uuid::new_v4(),
+            key_id: Uuid::new_v4(),
+        };
+        let denied =
+            rotate_receipt_signing_key(State(state.clone()), other, RequestContext::default())
+                .await;
+        assert!(matches!(denied, Err(ApiError::Forbidden(_))));
+
+        let Json(rotated) = rotate_receipt_signing_key(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("rotate failed"));
+        let new_key_id = rotated.key.key_id.clone();
+        assert_ne!(new_key_id, old_key_id);
+        assert_eq!(state.keypair().key_id(), new_key_id);
+        let retired = rotated.retired.unwrap();
+        assert_eq!(retired.key_id, old_key_id);
+        assert!(retired.retired_at.is_some() && !retired.active);
+        // No key file configured, so the seed comes back to be configured
+        assert!(!rotated.persisted);
+        let seed = rotated.signing_private_key.unwrap();
+        assert_eq!(
+            keypair_from_encoded(&seed, None).unwrap().key_id(),
+            new_key_id
+        );
+
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let key_ids: Vec<_> = receipts.iter().map(|r| r.key_id.clone()).collect();
+        assert_eq!(
+            key_ids,
+            [Some(old_key_id.clone()), Some(new_key_id.clone())]
+        );
+
+        let Json(verified) =
+            verify_receipt_chain(State(state.clone()), test_auth(&state), Path(pointer_id))
+                .await
+                .unwrap_or_else(|_| panic!("verify failed"));
+        assert!(verified.verification.chain_valid);
+
+        // An instance that never held either key learns both from the table
+        let Some(mut fresh) = test_state().await else {
+            return;
+        };
+        fresh.config.default_org_id = state.config.default_org_id;
+        let Json(elsewhere) =
+            verify_receipt_chain(State(fresh.clone()), test_auth(&fresh), Path(pointer_id))
+                .await
+                .unwrap_or_else(|_| panic!("verify failed"));
+        assert!(elsewhere.verification.chain_valid);
+
+        let Json(published) = get_public_keys(State(state.clone()), Query::default())
+            .await
+            .unwrap_or_else(|_| panic!("public keys failed"));
+        let kids: Vec<_> = published.keys.iter().map(|k| k.kid.as_str()).collect();
+        assert_eq!(kids[0], new_key_id);
+        assert!(kids.contains(&old_key_id.as_str()));
+
+        // The retired key is refused at startup
+        let stale = SigningKeyring::new(old_keypair);
+        assert!(
+            register_signing_keys(&fresh.db.writer, &stale, fresh.clock.now())
+                .await
+                .is_err()
+        );
+
+        // With a key file, the committed key replaces the file's seed
+        let path = std::env::temp_dir().join(format!("veto-key-{}", Uuid::new_v4()));
+        let mut with_file = state.clone();
+        with_file.config.signing_key_path = Some(path.to_string_lossy().into_owned());
+        let Json(rotated) = rotate_receipt_signing_key(
+            State(with_file.clone()),
+            test_auth(&with_file),
+            RequestContext::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("rotate failed"));
+        assert!(rotated.persisted && rotated.signing_private_key.is_none());
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            keypair_from_encoded(written.trim(), None).unwrap().key_id(),
+            rotated.key.key_id
+        );
+
+        // A rotation that fails to commit leaves the file and the keyring alone
+        let mut failing = with_file.clone();
+        failing.config.default_org_id = Uuid::new_v4();
+        let failed = rotate_receipt_signing_key(
+            State(failing.clone()),
+            test_auth(&failing),
+            RequestContext::default(),
+        )
+        .await;
+        assert!(failed.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), written);
+        assert_eq!(state.keypair().key_id(), rotated.key.key_id);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_checks_content_hash() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let create = |content_hash: String, encrypted_payload: Option<&str>| {
+            create_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                IdempotencyKey::default(),
+                Json(CreatePointerRequest {
+                    subject_id: unique_subject("content_hash"),
+                    content_hash,
+                    encrypted_payload: encrypted_payload.map(str::to_string),
+                    payload_encoding: None,
+                    retention_class: None,
+                    expires_at: None,
+                    external_ref: None,
+                    requires_dual_approval: false,
+                }),
+            )
+        };
+
+        // Matching payload: verified by the server
+        let (_, Json(created)) = create(sha3_512_hash(b"secret"), Some("c2VjcmV0"))
+            .await
+            .unwrap_or_else(|_| panic!("create failed"));
+        let receipts = get_receipts_by_pointer(&state.db.writer, created.pointer_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            receipts[0].receipt_json["metadata"]["content_hash_verified"],
+            true
+        );
+
+        // No payload: accepted as asserted by the client
+        let (_, Json(created)) = create("ab".repeat(64), None)
+            .await
+            .unwrap_or_else(|_| panic!("create failed"));
+        let receipts = get_receipts_by_pointer(&state.db.writer, created.pointer_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            receipts[0].receipt_json["metadata"]["content_hash_verified"],
+            false
+        );
+
+        let mismatched = create(sha3_512_hash(b"other"), Some("c2VjcmV0")).await;
+        assert!(matches!(mismatched, Err(ApiError::BadRequest(m)) if m.contains("does not match")));
+
+        for malformed in ["AB".repeat(64), "ab".repeat(32), "zz".repeat(64)] {
+            let result = create(malformed, None).await;
+            assert!(
+                matches!(result, Err(ApiError::ValidationFailed(e)) if e[0].field == "content_hash")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_orphan_subject_skips_already_orphaned() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("orphan_all");
+        let first = seed_pointer(&state, &subject).await;
+        let second = seed_pointer(&state, &subject).await;
+        let earlier = seed_pointer(&state, &subject).await;
+        let orphan = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(earlier, None),
+        )
+        .await;
+        assert!(orphan.is_ok(), "orphan failed");
+
+        let request = || {
+            Json(OrphanSubjectRequest {
+                reason: Some("user_consent_revoked".to_string()),
+            })
+        };
+        let Json(response) = orphan_subject(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Path(subject.clone()),
+            request(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("orphan-all failed"));
+
+        let mut orphaned: Vec<Uuid> = response.orphaned.iter().map(|m| m.pointer_id).collect();
+        orphaned.sort();
+        let mut expected = vec![first, second];
+        expected.sort();
+        assert_eq!(orphaned, expected);
+        assert_eq!(response.skipped, [earlier]);
+        assert_eq!((response.orphaned_count, response.skipped_count), (2, 1));
+
+        // Each veto extends that pointer's own chain
+        for pointer_id in [first, second] {
+            let pointer = get_pointer(&state.db.writer, pointer_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(matches!(pointer.status, PointerStatus::Orphaned));
+
+            let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+                .await
+                .unwrap();
+            assert_eq!(receipts.len(), 2);
+            assert_eq!(
+                receipts[1].prev_hash.as_deref(),
+                Some(receipts[0].receipt_hash.as_str())
+            );
+            assert_eq!(
+                receipts[1].receipt_json["metadata"]["bulk_id"],
+                json!(response.bulk_id)
+            );
+            let keys = receipt_keys(&state.db.writer, &state.signing_keys, &receipts)
+                .await
+                .unwrap();
+            assert!(verify_chain(&receipts, &keys).chain_valid);
+        }
+        let receipts = get_receipts_by_pointer(&state.db.writer, earlier)
+            .await
+            .unwrap();
+        assert_eq!(
+            receipts.len(),
+            2,
+            "skipped pointer gets no second orphan receipt"
+        );
+
+        let trail = audit_trail(&state, &subject).await;
+        let summaries: Vec<_> = trail
+            .iter()
+            .filter(|l| l.event_type == "subject_orphaned")
+            .collect();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].event_data["bulk_id"], json!(response.bulk_id));
+
+        // Nothing left to veto is still a success
+        let Json(repeat) = orphan_subject(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Path(subject),
+            request(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("repeat orphan-all failed"));
+        assert!(repeat.orphaned.is_empty());
+        assert_eq!(repeat.skipped_count, 3);
+
+        let unknown = unique_subject("orphan_all");
+        let result = orphan_subject(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Path(unknown),
+            request(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::NotFound(_))));
+    }
+
+    fn swap_request(content_hash: &str, purge_old: bool) -> Json<SwapDataRequest> {
+        Json(SwapDataRequest {
+            content_hash: content_hash.to_string(),
+            encrypted_payload: Some("Y29ycmVjdGVk".to_string()),
+            payload_encoding: None,
+            purge_old,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_swap_data_keeps_provenance() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("swap")).await;
+        let original = get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let Json(swapped) = swap_pointer_data(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            swap_request(&"cd".repeat(64), false),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("swap failed"));
+        assert_eq!(swapped.old_data_id, original.data_id);
+        assert_eq!(swapped.old_content_hash, "ab".repeat(64));
+        assert!(!swapped.old_data_purged);
+
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("resolve failed"));
+        assert_eq!(resolved.data_id, swapped.new_data_id);
+        assert_eq!(resolved.content_hash, "cd".repeat(64));
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let swap = &receipts[1];
+        assert!(matches!(swap.operation, ReceiptOperation::DataSwap));
+        assert_eq!(swap.receipt_json["operation"], "data_swap");
+        let metadata = &swap.receipt_json["metadata"];
+        assert_eq!(metadata["old_data_id"], json!(original.data_id));
+        assert_eq!(metadata["old_content_hash"], json!("ab".repeat(64)));
+        assert_eq!(metadata["new_data_id"], json!(swapped.new_data_id));
+        assert_eq!(metadata["new_content_hash"], json!("cd".repeat(64)));
+        let keys = receipt_keys(&state.db.writer, &state.signing_keys, &receipts)
+            .await
+            .unwrap();
+        assert!(verify_chain(&receipts, &keys).chain_valid);
+
+        // The replaced row is kept and reachable from the receipt trail, with
+        // its GC grace period starting at the swap
+        let old = get_data_store(&state.db.writer, original.data_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(old.content_hash, "ab".repeat(64));
+        assert!(old.released_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_swap_purge_spares_shared_data() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let first = seed_pointer(&state, &unique_subject("swap_shared")).await;
+        let second = seed_pointer(&state, &unique_subject("swap_shared")).await;
+        let shared = get_pointer(&state.db.writer, first)
+            .await
+            .unwrap()
+            .unwrap()
+            .data_id;
+        sqlx::query("UPDATE pointers SET data_id = $1 WHERE pointer_id = $2")
+            .bind(shared)
+            .bind(second)
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+
+        let swap = |pointer_id| {
+            swap_pointer_data(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                swap_request(&"ef".repeat(64), true),
+            )
+        };
+
+        let Json(kept) = swap(first).await.unwrap_or_else(|_| panic!("swap failed"));
+        assert!(!kept.old_data_purged, "second pointer still references it");
+        assert!(get_data_store(&state.db.writer, shared)
+            .await
+            .unwrap()
+            .is_some());
+
+        let Json(purged) = swap(second).await.unwrap_or_else(|_| panic!("swap failed"));
+        assert!(purged.old_data_purged);
+        assert!(get_data_store(&state.db.writer, shared)
+            .await
+            .unwrap()
+            .is_none());
+        let receipts = get_receipts_by_pointer(&state.db.writer, second)
+            .await
+            .unwrap();
+        assert_eq!(
+            receipts.last().unwrap().receipt_json["metadata"]["old_data_purged"],
+            true
+        );
+    }
+
+    #[tokio::test]
+    async fn test_swap_rejected_for_orphaned_or_restricted_pointer() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("swap_denied");
+        let restricted = seed_pointer(&state, &subject).await;
+        let restrict = restrict_subject(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject),
+            Json(RestrictSubjectRequest {
+                duration_seconds: 3600,
+                reason: "subject_dispute".to_string(),
+            }),
+        )
+        .await;
+        assert!(restrict.is_ok(), "restrict failed");
+
+        let result = swap_pointer_data(
+            State(state.clone()),
+            test_auth(&state),
+            Path(restricted),
+            swap_request(&"cd".repeat(64), false),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ProcessingRestricted { .. })));
+
+        let orphaned = seed_pointer(&state, &unique_subject("swap_denied")).await;
+        let orphan = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(orphaned, None),
+        )
+        .await;
+        assert!(orphan.is_ok(), "orphan failed");
+        let result = swap_pointer_data(
+            State(state.clone()),
+            test_auth(&state),
+            Path(orphaned),
+            swap_request(&"cd".repeat(64), false),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::PointerOrphaned(_))));
+
+        for pointer_id in [restricted, orphaned] {
+            let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+                .await
+                .unwrap();
+            assert!(receipts
+                .iter()
+                .all(|r| !matches!(r.operation, ReceiptOperation::DataSwap)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_releases_payload_only_when_granted() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let include = || {
+            Query(ResolveQuery {
+                include_payload: true,
+                ..Default::default()
+            })
+        };
+        let req = CreatePointerRequest {
+            subject_id: unique_subject("payload"),
+            content_hash: sha3_512_hash(b"secret"),
+            encrypted_payload: Some("c2VjcmV0".to_string()),
+            payload_encoding: None,
+            retention_class: None,
+            expires_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
+        };
+        let (_, Json(created)) = create_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(req),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("create failed"));
+        let with_payload = created.pointer_id;
+
+        // Only on request
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(with_payload),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("resolve failed"));
+        assert!(resolved.encrypted_payload.is_none());
+
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(with_payload),
+            include(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("resolve failed"));
+        assert_eq!(resolved.encrypted_payload.as_deref(), Some("c2VjcmV0"));
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, with_payload)
+            .await
+            .unwrap();
+        let released: Vec<&serde_json::Value> = receipts[1..]
+            .iter()
+            .map(|r| &r.receipt_json["metadata"]["payload_released"])
+            .collect();
+        assert_eq!(released, [&json!(false), &json!(true)]);
+
+        // Nothing stored, nothing released
+        let without_payload = seed_pointer(&state, &unique_subject("payload")).await;
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(without_payload),
+            include(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("resolve failed"));
+        assert!(resolved.encrypted_payload.is_none());
+        let receipts = get_receipts_by_pointer(&state.db.writer, without_payload)
+            .await
+            .unwrap();
+        assert_eq!(
+            receipts[1].receipt_json["metadata"]["payload_released"],
+            false
+        );
+
+        // Enforcement runs first: an orphaned pointer never leaks it
+        let orphan = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(with_payload, None),
+        )
+        .await;
+        assert!(orphan.is_ok(), "orphan failed");
+        let result = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(with_payload),
+            include(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::PointerOrphaned(_))));
+        let receipts = get_receipts_by_pointer(&state.db.writer, with_payload)
+            .await
+            .unwrap();
+        assert!(matches!(
+            receipts.last().unwrap().operation,
+            ReceiptOperation::Orphan
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_server_encrypted_payload_resolves_as_sent() {
+        use crate::crypto::{MasterKey, PayloadCipher};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let state = AppState {
+            payload_cipher: Some(std::sync::Arc::new(PayloadCipher::new(
+                MasterKey::from_bytes(&[9u8; 32]),
+            ))),
+            ..state
+        };
+        let include = || {
+            Query(ResolveQuery {
+                include_payload: true,
+                ..Default::default()
+            })
+        };
+        let (_, Json(created)) = create_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(CreatePointerRequest {
+                encrypted_payload: Some("c2VjcmV0".to_string()),
+                ..create_request(&unique_subject("sealed"), &sha3_512_hash(b"secret"))
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("create failed"));
+
+        // Ciphertext at rest, marked as the server's
+        let data = get_data_store(&state.db.writer, created.data_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(data.encrypted_payload.as_deref(), Some(&b"secret"[..]));
+        assert_eq!(data.metadata["encryption"], "aes-256-gcm");
+
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(created.pointer_id),
+            include(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("resolve failed"));
+        assert_eq!(resolved.encrypted_payload.as_deref(), Some("c2VjcmV0"));
+
+        // An altered ciphertext is a decryption failure, not an empty payload
+        sqlx::query(
+            "UPDATE data_store SET encrypted_payload = set_byte(encrypted_payload, 20, \
+             get_byte(encrypted_payload, 20) # 1) WHERE data_id = $1",
+        )
+        .bind(created.data_id)
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+        state.pointer_written(&created.pointer_id);
+        let result = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(created.pointer_id),
+            include(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::PayloadDecryptionFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_restriction_expiry_and_grace_in_simulated_time() {
+        use crate::test_support::SimulatedTime;
+        use chrono::Duration;
+
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (name, orphan_grace_seconds) \
+             VALUES ('Simulated Org', 60) RETURNING org_id",
+        )
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        state.config.default_org_id = org_id;
+
+        let mut sim = SimulatedTime::new(state);
+        let subject = unique_subject("simulated");
+        let pointer_id = sim.seed_pointer(&subject).await;
+
+        let restricted = restrict_subject(
+            State(sim.state.clone()),
+            test_auth(&sim.state),
+            Path(subject),
+            Json(RestrictSubjectRequest {
+                duration_seconds: 30 * 24 * 3600,
+                reason: "subject_dispute".to_string(),
+            }),
+        )
+        .await;
+        assert!(restricted.is_ok(), "restrict failed");
+
+        // Still denied a day before the 30-day window ends
+        let due = sim.advance(Duration::days(29)).await;
+        assert_eq!(due.restrictions_lifted, 0);
+        let result = resolve_pointer(
+            State(sim.state.clone()),
+            test_auth(&sim.state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ProcessingRestricted { .. })));
+
+        // The sweep due at expiry lifts it with an "expired" receipt
+        let due = sim.advance(Duration::days(1)).await;
+        assert_eq!(due.restrictions_lifted, 1);
+        let receipts = get_receipts_by_pointer(&sim.state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let unrestrict = receipts.last().unwrap();
+        assert!(matches!(unrestrict.operation, ReceiptOperation::Unrestrict));
+        assert_eq!(
+            unrestrict.receipt_json["metadata"]["lift_reason"],
+            "expired"
+        );
+        let resolved = resolve_pointer(
+            State(sim.state.clone()),
+            test_auth(&sim.state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "resolve after expiry failed");
+
+        // Orphaned at simulated time, then disclosed only inside the grace window
+        let orphaned = orphan_pointer(
+            State(sim.state.clone()),
+            test_auth(&sim.state),
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("orphan failed"));
+        assert_eq!(orphaned.orphaned_at, sim.now().to_rfc3339());
+        assert_eq!(orphaned.receipt.timestamp, orphaned.orphaned_at);
+
+        sim.advance(Duration::seconds(20)).await;
+        let Json(resolved) = resolve_pointer(
+            State(sim.state.clone()),
+            test_auth(&sim.state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("grace resolve failed"));
+        assert_eq!(resolved.status, "orphaned_grace");
+        assert_eq!(resolved.retry_after_seconds, Some(40));
+
+        sim.advance(Duration::seconds(41)).await;
+        let result = resolve_pointer(
+            State(sim.state.clone()),
+            test_auth(&sim.state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::PointerOrphaned(_))));
+    }
+
+    #[tokio::test]
+    async fn test_outage_serves_last_known_status_within_bound() {
+        use crate::{clock::ManualClock, enforcement::backfill_degraded_resolves};
+        use sqlx::postgres::PgPoolOptions;
+        use std::sync::Arc;
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let seen = seed_pointer(&state, &unique_subject("outage")).await;
+        let unseen = seed_pointer(&state, &unique_subject("outage")).await;
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(seen),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "resolve failed");
+
+        // Nothing listens on port 1, so every checkout fails
+        let seen_at = state.clock.now();
+        let clock = Arc::new(ManualClock::new(seen_at));
+        let outage = AppState {
+            db: Db::single(
+                PgPoolOptions::new()
+                    .acquire_timeout(std::time::Duration::from_millis(100))
+                    .connect_lazy("postgres://veto@127.0.0.1:1/veto")
+                    .unwrap(),
+            ),
+            clock: clock.clone(),
+            ..state.clone()
+        };
+
+        // Status only, even when the payload is asked for, and no receipt yet
+        let include = Query(ResolveQuery {
+            include_payload: true,
+            ..Default::default()
+        });
+        let Json(resolved) = resolve_pointer(
+            State(outage.clone()),
+            test_auth(&outage),
+            Path(seen),
+            include,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("degraded resolve failed"));
+        assert!(resolved.degraded);
+        assert_eq!(resolved.status, "active");
+        assert!(resolved.receipt.is_none());
+        assert!(resolved.encrypted_payload.is_none());
+
+        // Never seen: nothing to fall back on
+        let result = resolve_pointer(
+            State(outage.clone()),
+            test_auth(&outage),
+            Path(unseen),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ServiceUnavailable(_))));
+
+        // Writes never degrade, and once the circuit opens they don't wait
+        let result = orphan_pointer(
+            State(outage.clone()),
+            test_auth(&outage),
+            RequestContext::default(),
+            orphan_request(seen, None),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ServiceUnavailable(_))));
+        assert!(outage.db_circuit.is_open());
+        let req = CreatePointerRequest {
+            subject_id: unique_subject("outage"),
+            content_hash: "ab".repeat(64),
+            encrypted_payload: None,
+            payload_encoding: None,
+            retention_class: None,
+            expires_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
+        };
+        let started = std::time::Instant::now();
+        let result = create_pointer(
+            State(outage.clone()),
+            test_auth(&outage),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(req),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ServiceUnavailable(_))));
+        assert!(started.elapsed() < std::time::Duration::from_millis(100));
+
+        // Past the staleness bound the cached status is no longer served
+        clock.set(seen_at + chrono::Duration::seconds(61));
+        let result = resolve_pointer(
+            State(outage.clone()),
+            test_auth(&outage),
+            Path(seen),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ServiceUnavailable(_))));
+        assert_eq!(state.last_known.stats().served, 1);
+
+        // After recovery the owed receipt lands at the head of the chain
+        let written = backfill_degraded_resolves(
+            &state.db.writer,
+            &state.keypair(),
+            &state.last_known,
+            state.clock.now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(written, 1);
+        assert_eq!(state.last_known.stats().pending, 0);
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, seen)
+            .await
+            .unwrap();
+        let backfilled = receipts.last().unwrap();
+        assert!(matches!(backfilled.operation, ReceiptOperation::Resolve));
+        assert_eq!(backfilled.receipt_json["metadata"]["degraded"], true);
+        assert_eq!(
+            backfilled.receipt_json["metadata"]["payload_released"],
+            false
+        );
+        assert_eq!(
+            backfilled.prev_hash.as_deref(),
+            Some(receipts[receipts.len() - 2].receipt_hash.as_str())
+        );
+    }
+
+    fn create_request(subject: &str, content_hash: &str) -> CreatePointerRequest {
+        CreatePointerRequest {
+            subject_id: subject.to_string(),
+            content_hash: content_hash.to_string(),
+            encrypted_payload: None,
+            payload_encoding: None,
+            retention_class: None,
+            expires_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
+        }
+    }
+
+    async fn create_with_key(
+        state: &AppState,
+        key: &str,
+        req: CreatePointerRequest,
+    ) -> Result<(StatusCode, CreatePointerResponse), ApiError> {
+        create_pointer(
+            State(state.clone()),
+            test_auth(state),
+            RequestContext::default(),
+            IdempotencyKey(Some(key.to_string())),
+            Json(req),
+        )
+        .await
+        .map(|(status, Json(created))| (status, created))
+    }
+
+    async fn pointer_count(state: &AppState, subject: &str) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM pointers WHERE subject_id = $1")
+            .bind(subject)
+            .fetch_one(&state.db.writer)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_create_replays_the_original_response() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("idem");
+        let key = Uuid::new_v4().to_string();
+
+        let (status, created) =
+            create_with_key(&state, &key, create_request(&subject, &"ab".repeat(64)))
+                .await
+                .unwrap_or_else(|_| panic!("create failed"));
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, replayed) =
+            create_with_key(&state, &key, create_request(&subject, &"ab".repeat(64)))
+                .await
+                .unwrap_or_else(|_| panic!("replay failed"));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            serde_json::to_value(&replayed).unwrap(),
+            serde_json::to_value(&created).unwrap()
+        );
+        assert_eq!(pointer_count(&state, &subject).await, 1);
+
+        // Same key, different content
+        let conflicting =
+            create_with_key(&state, &key, create_request(&subject, &"cd".repeat(64))).await;
+        assert!(matches!(
+            conflicting,
+            Err(ApiError::IdempotencyKeyReused(_))
+        ));
+        assert_eq!(pointer_count(&state, &subject).await, 1);
+
+        // Keys are per org, so another org's use of it creates its own pointer
+        let other_org: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (name) VALUES ('Idempotency Org') RETURNING org_id",
+        )
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        let mut other = state.clone();
+        other.config.default_org_id = other_org;
+        let (status, theirs) =
+            create_with_key(&other, &key, create_request(&subject, &"ab".repeat(64)))
+                .await
+                .unwrap_or_else(|_| panic!("other org create failed"));
+        assert_eq!(status, StatusCode::CREATED);
+        assert_ne!(theirs.pointer_id, created.pointer_id);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_creates_with_one_key_make_one_pointer() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("idem_race");
+        let key = Uuid::new_v4().to_string();
+
+        let (first, second) = tokio::join!(
+            create_with_key(&state, &key, create_request(&subject, &"ab".repeat(64))),
+            create_with_key(&state, &key, create_request(&subject, &"ab".repeat(64))),
+        );
+        let (first, second) = (
+            first.unwrap_or_else(|_| panic!("first create failed")),
+            second.unwrap_or_else(|_| panic!("second create failed")),
+        );
+
+        let mut statuses = [first.0, second.0];
+        statuses.sort();
+        assert_eq!(statuses, [StatusCode::OK, StatusCode::CREATED]);
+        assert_eq!(first.1.pointer_id, second.1.pointer_id);
+        assert_eq!(pointer_count(&state, &subject).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_idempotency_key_creates_again() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("idem_expired");
+        let key = Uuid::new_v4().to_string();
+        let ttl = chrono::Duration::seconds(state.config.idempotency_key_ttl_seconds);
+        let past = AppState {
+            clock: std::sync::Arc::new(crate::clock::FixedClock(chrono::Utc::now() - ttl * 2)),
+            ..state.clone()
+        };
+
+        let (_, old) = create_with_key(&past, &key, create_request(&subject, &"ab".repeat(64)))
+            .await
+            .unwrap_or_else(|_| panic!("old create failed"));
+        let (status, fresh) =
+            create_with_key(&state, &key, create_request(&subject, &"cd".repeat(64)))
+                .await
+                .unwrap_or_else(|_| panic!("create after expiry failed"));
+        assert_eq!(status, StatusCode::CREATED);
+        assert_ne!(fresh.pointer_id, old.pointer_id);
+        assert_eq!(pointer_count(&state, &subject).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_while_database_is_down() {
+        let state = crate::test_support::offline_state();
+
+        let (status, Json(ready)) = health_ready(State(state.clone())).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(ready.status, "unhealthy");
+        assert_eq!(ready.checks.database.status, "error");
+        assert!(ready.checks.database.error.is_some());
+        assert_eq!(ready.schema_version, None);
+        assert_eq!(ready.checks.signing.status, "ok");
+
+        // ?deep=true is the same check; liveness never looks at the database
+        let deep = health_check(State(state.clone()), Query(HealthQuery { deep: true })).await;
+        assert_eq!(deep.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let shallow = health_check(State(state), Query(HealthQuery::default())).await;
+        assert_eq!(shallow.status(), StatusCode::OK);
+        assert_eq!(health_live().await.0.status, "healthy");
+    }
+
+    #[tokio::test]
+    async fn test_readiness_times_out_on_a_stalled_database() {
+        let mut state = crate::test_support::offline_state();
+        // Accepts connections but never answers the startup handshake
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "postgres://postgres@{}/stalled",
+            listener.local_addr().unwrap()
+        );
+        state.db.writer = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy(&url)
+            .unwrap();
+        state.config.health_db_timeout_ms = 100;
+
+        let (status, Json(ready)) = health_ready(State(state)).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            ready.checks.database.error.as_deref(),
+            Some("timed out after 100ms")
+        );
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_ok_with_database() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let (status, Json(ready)) = health_ready(State(state)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ready.status, "healthy");
+        assert_eq!(ready.checks.database.status, "ok");
+        assert_eq!(ready.checks.signing.status, "ok");
+        assert_eq!(
+            ready.schema_version,
+            Some(crate::db::migrations::latest_version())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_pointers_filters() {
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        // Own org, so the listing only sees this test's pointers
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (name) VALUES ('Listing Org') RETURNING org_id",
+        )
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        state.config.default_org_id = org_id;
+
+        let alice = unique_subject("list_alice");
+        let bob = unique_subject("list_bob");
+        let now = chrono::Utc::now();
+        let days_ago = |days: i64| now - chrono::Duration::days(days);
+
+        let mut seeded = Vec::new();
+        for (subject, age_days, orphan) in [
+            (&alice, 10, false),
+            (&alice, 3, true),
+            (&bob, 2, false),
+            (&bob, 1, true),
+        ] {
+            let pointer_id = seed_pointer(&state, subject).await;
+            if orphan {
+                let orphaned = orphan_pointer(
+                    State(state.clone()),
+                    test_auth(&state),
+                    RequestContext::default(),
+                    Json(OrphanPointerRequest {
+                        pointer_id,
+                        reason: None,
+                        effective_at: None,
+                    }),
+                )
+                .await;
+                assert!(orphaned.is_ok(), "orphan failed");
+            }
+            sqlx::query("UPDATE pointers SET created_at = $2 WHERE pointer_id = $1")
+                .bind(pointer_id)
+                .bind(days_ago(age_days))
+                .execute(&state.db.writer)
+                .await
+                .unwrap();
+            seeded.push(pointer_id);
+        }
+        let [old_alice, orphaned_alice, active_bob, orphaned_bob] = seeded[..] else {
+            unreachable!()
+        };
+
+        let list = |query: ListPointersQuery| {
+            let state = state.clone();
+            async move {
+                list_pointers(State(state.clone()), test_auth(&state), Query(query))
+                    .await
+                    .map(|Json(listed)| listed)
+            }
+        };
+        let ids = |listed: &ListPointersResponse| -> Vec<Uuid> {
+            listed.pointers.iter().map(|p| p.pointer_id).collect()
+        };
+
+        // Unfiltered: everything, newest first
+        let all = list(ListPointersQuery::default()).await.unwrap();
+        assert_eq!(
+            ids(&all),
+            vec![orphaned_bob, active_bob, orphaned_alice, old_alice]
+        );
+        assert!(all.next_cursor.is_none());
+
+        // Each filter on its own
+        let orphaned = list(ListPointersQuery {
+            status: Some("orphaned".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(ids(&orphaned), vec![orphaned_bob, orphaned_alice]);
+        assert!(orphaned.pointers.iter().all(|p| p.orphaned_at.is_some()));
+
+        let active = list(ListPointersQuery {
+            status: Some("active".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(ids(&active), vec![active_bob, old_alice]);
+
+        let alices = list(ListPointersQuery {
+            subject_id: Some(alice.clone()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(ids(&alices), vec![orphaned_alice, old_alice]);
+
+        let recent = list(ListPointersQuery {
+            created_after: Some(days_ago(7)),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(ids(&recent), vec![orphaned_bob, active_bob, orphaned_alice]);
+
+        let older = list(ListPointersQuery {
+            created_before: Some(days_ago(2)),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(ids(&older), vec![orphaned_alice, old_alice]);
+
+        // Combined: orphaned in the last seven days, for one subject
+        let combined = list(ListPointersQuery {
+            status: Some("orphaned".to_string()),
+            subject_id: Some(alice.clone()),
+            created_after: Some(days_ago(7)),
+            created_before: Some(now),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(ids(&combined), vec![orphaned_alice]);
+
+        // Pages of two walk the same order
+        let first = list(ListPointersQuery {
+            limit: Some(2),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let second = list(ListPointersQuery {
+            limit: Some(2),
+            cursor: first.next_cursor.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(ids(&first), vec![orphaned_bob, active_bob]);
+        assert_eq!(ids(&second), vec![orphaned_alice, old_alice]);
+        assert!(second.next_cursor.is_none());
+
+        // Nothing matches
+        let none = list(ListPointersQuery {
+            status: Some("active".to_string()),
+            subject_id: Some(bob.clone()),
+            created_before: Some(days_ago(5)),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert!(none.pointers.is_empty());
+        assert!(none.next_cursor.is_none());
+
+        let bad = list(ListPointersQuery {
+            status: Some("deleted".to_string()),
+            ..Default::default()
+        })
+        .await;
+        assert!(matches!(bad, Err(ApiError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_page_cursor_round_trip() {
+        let id = Uuid::new_v4();
+        let at = chrono::DateTime::from_timestamp_micros(1_764_151_200_123_456).unwrap();
+        assert_eq!(parse_page_cursor(&page_cursor(at, id)), Some((at, id)));
+        assert_eq!(parse_page_cursor("1764151200123456"), None);
+        assert_eq!(parse_page_cursor("x.y"), None);
+    }
+
+    #[tokio::test]
+    async fn test_receipt_pages_are_stable_and_filtered() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("pages")).await;
+        for _ in 0..4 {
+            let resolved = resolve_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query::default(),
+            )
+            .await;
+            assert!(resolved.is_ok(), "resolve failed");
+        }
+        let hashes = |receipts: &[ReceiptEntry]| -> Vec<String> {
+            receipts
+                .iter()
+                .map(|entry| match entry {
+                    ReceiptEntry::Receipt(r) => r.receipt_hash.clone(),
+                    ReceiptEntry::Tombstone(t) => panic!("unexpected tombstone {:?}", t),
+                })
+                .collect()
+        };
+        let page_of = |limit: i64, cursor: Option<String>, operation: Option<&str>| {
+            Query(ReceiptsQuery {
+                limit: Some(limit),
+                cursor,
+                operation: operation.map(str::to_string),
+            })
+        };
+
+        let Json(whole) = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("receipts failed"));
+        assert_eq!(whole.receipts.len(), 5);
+        assert!(whole.next_cursor.is_none());
+
+        // Pages of two walk the same order without gaps or repeats
+        let mut paged = Vec::new();
+        let mut cursor = None;
+        loop {
+            let query = page_of(2, cursor.take(), None);
+            let Json(page) = get_receipts(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                query,
+            )
+            .await
+            .unwrap_or_else(|_| panic!("receipt page failed"));
+            assert!(page.receipts.len() <= 2);
+            paged.extend(hashes(&page.receipts));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(paged, hashes(&whole.receipts));
+
+        // Filters narrow before paging
+        let query = page_of(10, None, Some("resolve"));
+        let Json(resolves) = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            query,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("filtered receipts failed"));
+        assert_eq!(resolves.receipts.len(), 4);
+        assert_eq!(hashes(&resolves.receipts), hashes(&whole.receipts)[1..]);
+
+        let query = page_of(10, None, Some("orphan"));
+        let Json(orphans) = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            query,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("filtered receipts failed"));
+        assert!(orphans.receipts.is_empty());
+
+        let query = page_of(10, None, Some("delete"));
+        let result = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            query,
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+        let query = page_of(10, Some("not-a-cursor".to_string()), None);
+        let result = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            query,
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_audit_trail_pages_and_filters() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("audit_pages");
+        let mut pointer_ids = Vec::new();
+        for _ in 0..3 {
+            pointer_ids.push(seed_pointer(&state, &subject).await);
+        }
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_ids[0], None),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+        let trail_query = |limit: i64, cursor: Option<String>| AuditTrailQuery {
+            limit: Some(limit),
+            cursor,
+            ..Default::default()
+        };
+        let events = |trail: &GetAuditTrailResponse| -> Vec<(String, String)> {
+            trail
+                .audit_events
+                .iter()
+                .map(|e| (e.event_type.clone(), e.timestamp.clone()))
+                .collect()
+        };
+
+        let Json(whole) = get_audit_trail(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            Query(trail_query(100, None)),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("audit trail failed"));
+        // Three creates, then the orphan and the status-change trigger's row,
+        // which share a timestamp: the id breaks the tie
+        assert_eq!(whole.audit_events.len(), 5);
+
+        // One event per page, newest first, same order as the single page
+        let mut paged = Vec::new();
+        let mut cursor = None;
+        loop {
+            let query = Query(trail_query(1, cursor.take()));
+            let Json(page) = get_audit_trail(
+                State(state.clone()),
+                test_auth(&state),
+                Path(subject.clone()),
+                query,
+            )
+            .await
+            .unwrap_or_else(|_| panic!("audit page failed"));
+            paged.extend(events(&page));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(paged, events(&whole));
+
+        // Event type and time window filters
+        let query = Query(AuditTrailQuery {
+            event_type: Some("pointer_created".to_string()),
+            ..trail_query(100, None)
+        });
+        let Json(created) = get_audit_trail(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            query,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("filtered audit trail failed"));
+        assert_eq!(created.audit_events.len(), 3);
+
+        let orphaned_at: chrono::DateTime<chrono::Utc> =
+            whole.audit_events[0].timestamp.parse().unwrap();
+        let query = Query(AuditTrailQuery {
+            from: Some(orphaned_at),
+            ..trail_query(100, None)
+        });
+        let Json(since) = get_audit_trail(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            query,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("windowed audit trail failed"));
+        assert_eq!(events(&since), events(&whole)[..2]);
+
+        let query = Query(AuditTrailQuery {
+            to: Some(orphaned_at),
+            ..trail_query(100, None)
+        });
+        let Json(before) = get_audit_trail(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            query,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("windowed audit trail failed"));
+        assert_eq!(events(&before), events(&whole)[2..]);
+    }
+
+    #[tokio::test]
+    async fn test_audit_trail_records_actor_and_ip() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("audit_actor");
+        let pointer_id = seed_pointer(&state, &subject).await;
+
+        let ctx = RequestContext {
+            actor_id: Some("user:alice".to_string()),
+            ip_address: Some("203.0.113.7".parse().unwrap()),
+            user_agent: Some("veto-client/1.0".to_string()),
+        };
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            ctx,
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+
+        let Json(trail) = get_audit_trail(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("audit trail failed"));
+        let orphan = trail
+            .audit_events
+            .iter()
+            .find(|e| e.event_type == "pointer_orphaned")
+            .unwrap();
+        assert_eq!(orphan.actor_id.as_deref(), Some("user:alice"));
+        assert_eq!(orphan.ip_address.as_deref(), Some("203.0.113.7"));
+
+        let user_agent: Option<String> = sqlx::query_scalar(
+            "SELECT user_agent FROM audit_log \
+             WHERE pointer_id = $1 AND event_type = 'pointer_orphaned'",
+        )
+        .bind(pointer_id)
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        assert_eq!(user_agent.as_deref(), Some("veto-client/1.0"));
+
+        // A context-free create records nothing about the caller
+        let created = trail
+            .audit_events
+            .iter()
+            .find(|e| e.event_type == "pointer_created")
+            .unwrap();
+        assert_eq!(created.actor_id, None);
+        assert_eq!(created.ip_address, None);
+    }
+
+    #[tokio::test]
+    async fn test_create_enforces_retention_class_maximum() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let create = |retention_class: Option<&str>,
+                      expires_at: Option<chrono::DateTime<chrono::Utc>>| {
+            create_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                IdempotencyKey::default(),
+                Json(CreatePointerRequest {
+                    subject_id: unique_subject("retention_class"),
+                    content_hash: sha3_512_hash(b"secret"),
+                    encrypted_payload: Some("c2VjcmV0".to_string()),
+                    payload_encoding: None,
+                    retention_class: retention_class.map(str::to_string),
+                    expires_at,
+                    external_ref: None,
+                    requires_dual_approval: false,
+                }),
+            )
+        };
+        let now = state.clock.now();
+        let day = chrono::Duration::days(1);
+
+        let unknown = create(Some("marketing"), None).await;
+        assert!(
+            matches!(unknown, Err(ApiError::BadRequest(m)) if m.contains("unknown retention_class"))
+        );
+        let too_long = create(Some("biometric"), Some(now + day * 31)).await;
+        assert!(
+            matches!(too_long, Err(ApiError::BadRequest(m)) if m.contains("retention maximum"))
+        );
+
+        // The class maximum applies when no expiry is asked for
+        let (_, Json(created)) = create(Some("biometric"), None)
+            .await
+            .unwrap_or_else(|_| panic!("create failed"));
+        let retain_until: chrono::DateTime<chrono::Utc> =
+            created.retain_until.unwrap().parse().unwrap();
+        assert!(retain_until <= state.clock.now() + day * 30);
+        assert!(retain_until > now + day * 29);
+        let receipts = get_receipts_by_pointer(&state.db.writer, created.pointer_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            receipts[0].receipt_json["metadata"]["retention_class"],
+            "biometric"
+        );
+
+        // An earlier expiry wins and shows up in the report
+        let (_, Json(soon)) = create(Some("biometric"), Some(now + chrono::Duration::hours(1)))
+            .await
+            .unwrap_or_else(|_| panic!("create failed"));
+        let report = |days: i64| {
+            get_retention_report(
+                State(state.clone()),
+                test_auth(&state),
+                Query(RetentionReportQuery { days: Some(days) }),
+            )
+        };
+        let Json(report_1d) = report(1)
+            .await
+            .unwrap_or_else(|_| panic!("retention report failed"));
+        let classes: Vec<_> = report_1d
+            .classes
+            .iter()
+            .map(|c| c.retention_class.as_deref())
+            .collect();
+        assert!(classes.starts_with(&[Some("biometric"), Some("transaction_record")]));
+        let listed = |report: &RetentionReportResponse, pointer_id: Uuid| {
+            report.classes[0]
+                .expirations
+                .iter()
+                .any(|e| e.pointer_id == pointer_id)
+        };
+        assert!(listed(&report_1d, soon.pointer_id));
+        assert!(!listed(&report_1d, created.pointer_id));
+
+        let out_of_range = report(0).await;
+        assert!(matches!(out_of_range, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_receipt_phase_timings_reach_metrics() {
+        use crate::analytics::ReceiptPhaseMetrics;
+        use axum::response::IntoResponse;
+        use std::{sync::Arc, time::Duration};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        // Every receipt counts as slow against a zero threshold
+        let state = AppState {
+            receipt_metrics: Arc::new(ReceiptPhaseMetrics::new(true, Duration::ZERO)),
+            ..state
+        };
+
+        let pointer_id = seed_pointer(&state, &unique_subject("receipt_timing")).await;
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "resolve failed");
+
+        let metrics = get_metrics(State(state.clone()), test_auth(&state))
+            .await
+            .unwrap_or_else(|_| panic!("metrics failed"))
+            .into_response();
+        let body = axum::body::to_bytes(metrics.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        for phase in ["canonicalize", "hash", "sign", "persist"] {
+            let count = format!("receipt_phase_seconds_count{{phase=\"{}\"}} 2\n", phase);
+            assert!(text.contains(&count), "{}", text);
+        }
+        assert!(text.contains("slow_receipts_total 2\n"), "{}", text);
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[tokio::test]
+    async fn test_readonly_resolve_writes_no_receipt() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("readonly")).await;
+        let readonly = || {
+            Query(ResolveQuery {
+                mode: ResolveMode::Readonly,
+                ..Default::default()
+            })
+        };
+        let receipt_count = || async {
+            get_receipts_by_pointer(&state.db.writer, pointer_id)
+                .await
+                .unwrap()
+                .len()
+        };
+
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            readonly(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("readonly resolve failed"));
+        assert_eq!(resolved.status, "active");
+        assert!(!resolved.receipt_generated);
+        assert!(resolved.receipt.is_none());
+        assert_eq!(receipt_count().await, 1, "only the create receipt");
+
+        // The default stays attested
+        let Json(attested) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("attested resolve failed"));
+        assert!(attested.receipt_generated);
+        assert_eq!(receipt_count().await, 2);
+
+        // Payloads are only released with a receipt
+        let with_payload = Query(ResolveQuery {
+            include_payload: true,
+            mode: ResolveMode::Readonly,
+        });
+        let result = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            with_payload,
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+
+        // Orphan status is still enforced, with no denial receipt
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+        let result = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            readonly(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::PointerOrphaned(_))));
+        assert_eq!(receipt_count().await, 3, "create, resolve and orphan");
+    }
+
+    #[tokio::test]
+    async fn test_frozen_chain_blocks_receipts_until_review() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("freeze");
+        let pointer_id = seed_pointer(&state, &subject).await;
+        let receipts = || async {
+            get_receipts_by_pointer(&state.db.writer, pointer_id)
+                .await
+                .unwrap()
+        };
+        let head = receipts().await.last().unwrap().receipt_hash.clone();
+
+        let freeze = Json(FreezeChainRequest {
+            reason: "chain gap reported by auditor".to_string(),
+        });
+        let Json(frozen) = freeze_pointer_chain(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            freeze,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("freeze failed"));
+
+        // Every operation that would append a receipt is refused
+        let result = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ChainFrozen(_))));
+        let result = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ChainFrozen(_))));
+        let result = restrict_subject(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            restrict_request(60),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ChainFrozen(_))));
+        let again = freeze_pointer_chain(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Json(FreezeChainRequest {
+                reason: "again".to_string(),
+            }),
+        )
+        .await;
+        assert!(matches!(again, Err(ApiError::Conflict { .. })));
+
+        // Reads of the status and the existing chain still work
+        let readonly = Query(ResolveQuery {
+            mode: ResolveMode::Readonly,
+            ..Default::default()
+        });
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            readonly,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("readonly resolve failed"));
+        assert_eq!(resolved.status, "active");
+        let Json(listed) = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("get receipts failed"));
+        assert_eq!(listed.receipts.len(), 1, "nothing was appended");
+
+        let outcome = Json(UnfreezeChainRequest {
+            outcome: "gap explained by clock skew, chain intact".to_string(),
+        });
+        let Json(cleared) = unfreeze_pointer_chain(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            outcome,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("unfreeze failed"));
+        assert_eq!(cleared.freeze_id, frozen.freeze_id);
+
+        // One review receipt resumes the chain from the head it froze at
+        let review = receipts().await.pop().unwrap();
+        assert!(matches!(
+            review.operation,
+            ReceiptOperation::ChainFreezeReview
+        ));
+        assert_eq!(review.prev_hash.as_deref(), Some(head.as_str()));
+        assert_eq!(
+            review.receipt_json["metadata"]["org_receipt_hash"],
+            cleared.org_receipt.receipt_hash
+        );
+
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "resolve after unfreeze failed");
+        let chain = receipts().await;
+        assert_eq!(chain.len(), 3);
+        assert_eq!(
+            chain[2].prev_hash.as_deref(),
+            Some(review.receipt_hash.as_str())
+        );
+
+        // Freeze and clearing are chained on the org chain instead
+        let org_chain: Vec<(String, String, Option<String>)> = sqlx::query_as(
+            "SELECT kind, receipt_hash, prev_hash FROM org_receipts \
+             WHERE pointer_id = $1 ORDER BY created_at",
+        )
+        .bind(pointer_id)
+        .fetch_all(&state.db.writer)
+        .await
+        .unwrap();
+        assert_eq!(org_chain.len(), 2);
+        assert_eq!(org_chain[0].0, "chain_freeze");
+        assert_eq!(org_chain[0].1, frozen.org_receipt.receipt_hash);
+        assert_eq!(org_chain[1].0, "chain_unfreeze");
+        assert_eq!(org_chain[1].2.as_deref(), Some(org_chain[0].1.as_str()));
+
+        let unfrozen = unfreeze_pointer_chain(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Json(UnfreezeChainRequest {
+                outcome: "again".to_string(),
+            }),
+        )
+        .await;
+        assert!(matches!(unfrozen, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_other_orgs_pointers_are_forbidden() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("cross_org");
+        let pointer_id = seed_pointer(&state, &subject).await;
+
+        let other_org = Uuid::new_v4();
+        sqlx::query("INSERT INTO organizations (org_id, name) VALUES ($1, 'Other Org')")
+            .bind(other_org)
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+        let other = AuthContext {
+            org_id: other_org,
+            key_id: Uuid::new_v4(),
+        };
+        let forbidden = |result: Result<(), ApiError>| {
+            assert!(
+                matches!(result, Err(ApiError::Forbidden(_))),
+                "expected Forbidden, got {:?}",
+                result
+            )
+        };
+
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            other,
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        forbidden(resolved.map(|_| ()));
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            other,
+            RequestContext::default(),
+            orphan_request(pointer_id, None),
+        )
+        .await;
+        forbidden(orphaned.map(|_| ()));
+        let receipts = get_receipts(
+            State(state.clone()),
+            other,
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        forbidden(receipts.map(|_| ()));
+        let trail = get_audit_trail(
+            State(state.clone()),
+            other,
+            Path(subject.clone()),
+            Query::default(),
+        )
+        .await;
+        forbidden(trail.map(|_| ()));
+        // A frozen chain refuses every receipt, so freezing another org's
+        // pointer would block it
+        let frozen = freeze_pointer_chain(
+            State(state.clone()),
+            other,
+            Path(pointer_id),
+            Json(FreezeChainRequest {
+                reason: "not mine".to_string(),
+            }),
+        )
+        .await;
+        forbidden(frozen.map(|_| ()));
+        let unfrozen = unfreeze_pointer_chain(
+            State(state.clone()),
+            other,
+            Path(pointer_id),
+            Json(UnfreezeChainRequest {
+                outcome: "not mine".to_string(),
+            }),
+        )
+        .await;
+        forbidden(unfrozen.map(|_| ()));
+        let checked = check_pointer_consistency(
+            State(state.clone()),
+            other,
+            Path(pointer_id),
+            Query(ConsistencyQuery { repair: false }),
+        )
+        .await;
+        forbidden(checked.map(|_| ()));
+        let checked = check_subject_consistency(
+            State(state.clone()),
+            other,
+            Path(subject.clone()),
+            Query(ConsistencyQuery { repair: false }),
+        )
+        .await;
+        forbidden(checked.map(|_| ()));
+
+        // The other org creates in its own org, and sees only its own pointer
+        let req = CreatePointerRequest {
+            subject_id: subject.clone(),
+            content_hash: "cd".repeat(64),
+            encrypted_payload: None,
+            payload_encoding: None,
+            retention_class: None,
+            expires_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
+        };
+        let (_, Json(created)) = create_pointer(
+            State(state.clone()),
+            other,
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(req),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("create failed"));
+        let pointer = get_pointer(&state.db.writer, created.pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pointer.org_id, other_org);
+
+        let Json(trail) = get_audit_trail(
+            State(state.clone()),
+            other,
+            Path(subject.clone()),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("audit trail failed"));
+        assert_eq!(trail.total_pointers, 1);
+        assert!(trail
+            .audit_events
+            .iter()
+            .all(|e| e.pointer_id == Some(created.pointer_id)));
+
+        // Untouched by the refused veto
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "owner resolve failed");
+    }
+
+    /// State whose receipts are capped at `receipt_max_bytes`
+    fn with_receipt_limit(state: &AppState, receipt_max_bytes: usize) -> AppState {
+        let mut config = state.config.clone();
+        config.receipt_max_bytes = receipt_max_bytes;
+        AppState {
+            config,
+            ..state.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_client_reason_is_rejected() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("large_reason");
+        let pointer_id = seed_pointer(&state, &subject).await;
+        let reason = "x".repeat(3_000_000);
+
+        let result = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            orphan_request(pointer_id, Some(&reason)),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::ValidationFailed(e)) if e[0].field == "reason"));
+        let result = restrict_subject(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            Json(RestrictSubjectRequest {
+                duration_seconds: 60,
+                reason,
+            }),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+
+        let pointer = get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(pointer.status, PointerStatus::Active));
+        assert_eq!(
+            get_receipts_by_pointer(&state.db.writer, pointer_id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_overflowed_receipt_verifies_against_its_record() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        // Too small for a create receipt's metadata, so it overflows
+        let state = with_receipt_limit(&state, 480);
+        // Records are shared by identical metadata, so make this one unique
+        let content_hash = sha3_512_hash(Uuid::new_v4().as_bytes());
+        let req = CreatePointerRequest {
+            subject_id: unique_subject("overflow"),
+            content_hash: content_hash.clone(),
+            encrypted_payload: None,
+            payload_encoding: None,
+            retention_class: None,
+            expires_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
+        };
+        let (_, Json(created)) = create_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(req),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("create failed"));
+        let pointer_id = created.pointer_id;
+
+        let receipts = get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let create = &receipts[0];
+        assert!(serde_json::to_string(&create.receipt_json).unwrap().len() <= 480);
+        let hash = overflow_hash(&create.receipt_json["metadata"])
+            .unwrap()
+            .to_string();
+        assert!(create.overflow.is_some());
+
+        let verify = |state: AppState| async move {
+            let Json(verified) =
+                verify_receipt_chain(State(state.clone()), test_auth(&state), Path(pointer_id))
+                    .await
+                    .unwrap_or_else(|_| panic!("verify failed"));
+            verified.verification
+        };
+        let verification = verify(state.clone()).await;
+        assert!(verification.chain_valid);
+
+        // Reads serve the full metadata back
+        let Json(page) = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("get receipts failed"));
+        let ReceiptEntry::Receipt(summary) = &page.receipts[0] else {
+            panic!("expected a receipt");
+        };
+        let metadata = summary.overflow_metadata.as_ref().unwrap();
+        assert_eq!(metadata["content_hash"], json!(content_hash));
+
+        // An edited record no longer matches what the receipt signed
+        sqlx::query(
+            "UPDATE receipt_overflow SET content = replace(content, $2, $3) WHERE overflow_hash = $1",
+        )
+        .bind(&hash)
+        .bind(&content_hash)
+        .bind("cd".repeat(64))
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+        let verification = verify(state.clone()).await;
+        assert!(!verification.chain_valid);
+        assert_eq!(
+            verification.receipts[0].failure,
+            Some(ReceiptFailure::OverflowMismatch)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_large_orphan_group_manifest_overflows_its_members() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        // Member receipts fit, a manifest listing 25 members doesn't
+        let state = with_receipt_limit(&state, 4096);
+        let subject = unique_subject("large_group");
+        let mut pointer_ids = Vec::new();
+        for _ in 0..25 {
+            pointer_ids.push(seed_pointer(&state, &subject).await);
+        }
+
+        let Json(group) = orphan_group(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanGroupRequest {
+                pointer_ids: pointer_ids.clone(),
+                reason: None,
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("orphan group failed"));
+
+        let Json(stored) = get_orphan_group(
+            State(state.clone()),
+            test_auth(&state),
+            Path(group.group_id),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("get orphan group failed"));
+        assert!(serde_json::to_string(&stored.manifest).unwrap().len() <= 4096);
+        assert!(overflow_hash(&stored.manifest["members"]).is_some());
+
+        let members = stored.overflow_members.unwrap();
+        let listed: Vec<Uuid> = members
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| serde_json::from_value(m["pointer_id"].clone()).unwrap())
+            .collect();
+        assert_eq!(listed, pointer_ids);
+        let member_hashes: Vec<GroupMember> = serde_json::from_value(members).unwrap();
+        assert_eq!(
+            stored.manifest["members_hash"],
+            json!(OrphanGroupManifest::members_hash(&member_hashes))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_events_follow_subscription() {
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        state.config.default_org_id = sqlx::query_scalar(
+            "INSERT INTO organizations (name) VALUES ('Webhook Filter Org') RETURNING org_id",
+        )
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+
+        let register = |events: Option<Vec<&str>>, url: &str| {
+            create_webhook(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                Json(CreateWebhookRequest {
+                    url: url.to_string(),
+                    events: events.map(|e| e.into_iter().map(String::from).collect()),
+                    enabled: None,
+                }),
+            )
+        };
+
+        // Bad URLs and unknown events are refused
+        assert!(matches!(
+            register(None, "ftp://example.com/hook").await,
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            register(Some(vec!["pointer_created"]), "https://example.com/hook").await,
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            register(Some(vec![]), "https://example.com/hook").await,
+            Err(ApiError::BadRequest(_))
+        ));
+
+        let (_, Json(orphans_only)) = register(None, "https://example.com/orphans")
+            .await
+            .unwrap_or_else(|_| panic!("register failed"));
+        let (_, Json(schedules)) = register(
+            Some(vec!["orphan_scheduled", "orphan_cancelled"]),
+            "https://example.com/schedules",
+        )
+        .await
+        .unwrap_or_else(|_| panic!("register failed"));
+        let (_, Json(disabled)) = register(None, "https://example.com/disabled")
+            .await
+            .unwrap_or_else(|_| panic!("register failed"));
+        let Json(updated) = update_webhook(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Path(disabled.webhook.webhook_id),
+            Json(UpdateWebhookRequest {
+                url: None,
+                events: None,
+                enabled: Some(false),
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("update failed"));
+        assert!(!updated.enabled);
+
+        // Schedule, cancel, then orphan
+        let pointer_id = seed_pointer(&state, &unique_subject("webhook_filter")).await;
+        let orphan = |effective_at| {
+            orphan_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                Json(OrphanPointerRequest {
+                    pointer_id,
+                    reason: None,
+                    effective_at,
+                }),
+            )
+        };
+        assert!(orphan(Some(state.clock.now() + chrono::Duration::hours(1)))
+            .await
+            .is_ok());
+        assert!(cancel_orphan(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(CancelOrphanRequest {
+                pointer_id,
+                reason: None,
+            }),
+        )
+        .await
+        .is_ok());
+        assert!(orphan(None).await.is_ok());
+
+        let events_of = |webhook_id: Uuid| {
+            let state = state.clone();
+            async move {
+                let Json(log) = get_webhook_deliveries(
+                    State(state.clone()),
+                    test_auth(&state),
+                    Path(webhook_id),
+                    Query(WebhookDeliveriesQuery { limit: None }),
+                )
+                .await
+                .unwrap_or_else(|_| panic!("delivery log failed"));
+                let mut events: Vec<String> =
+                    log.deliveries.into_iter().map(|d| d.event_type).collect();
+                events.sort();
+                events
+            }
+        };
+        assert_eq!(
+            events_of(orphans_only.webhook.webhook_id).await,
+            vec!["pointer_orphaned"]
+        );
+        assert_eq!(
+            events_of(schedules.webhook.webhook_id).await,
+            vec!["orphan_cancelled", "orphan_scheduled"]
+        );
+        assert!(events_of(disabled.webhook.webhook_id).await.is_empty());
+
+        // Other orgs can neither see nor delete them
+        let mut other = test_auth(&state);
+        other.org_id = Uuid::new_v4();
+        assert!(matches!(
+            delete_webhook(
+                State(state.clone()),
+                other,
+                RequestContext::default(),
+                Path(orphans_only.webhook.webhook_id),
+            )
+            .await,
+            Err(ApiError::Forbidden(_))
+        ));
+
+        let deleted = delete_webhook(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Path(orphans_only.webhook.webhook_id),
+        )
+        .await;
+        assert!(matches!(deleted, Ok(StatusCode::NO_CONTENT)));
+        let Json(listed) = list_webhooks(State(state.clone()), test_auth(&state))
+            .await
+            .unwrap_or_else(|_| panic!("list failed"));
+        let listed: Vec<Uuid> = listed.webhooks.iter().map(|w| w.webhook_id).collect();
+        assert_eq!(
+            listed,
+            vec![schedules.webhook.webhook_id, disabled.webhook.webhook_id]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_subject_downloads_only_own_subjects() {
+        use axum::response::IntoResponse;
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("export");
+        let pointer_id = seed_pointer(&state, &subject).await;
+        let export = |auth: AuthContext, subject: String, format: ExportFormat| {
+            let state = state.clone();
+            async move {
+                export_subject(
+                    State(state),
+                    auth,
+                    RequestContext::default(),
+                    Path(subject),
+                    Query(ExportSubjectQuery {
+                        format,
+                        include_payloads: false,
+                    }),
+                )
+                .await
+                .map(IntoResponse::into_response)
+            }
+        };
+
+        let response = export(test_auth(&state), subject.clone(), ExportFormat::Jsonl)
+            .await
+            .unwrap_or_else(|_| panic!("export failed"));
+        let headers = response.headers();
+        assert_eq!(
+            headers[axum::http::header::CONTENT_TYPE],
+            "application/x-ndjson"
+        );
+        let disposition = headers[axum::http::header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap();
+        assert!(disposition.starts_with("attachment; filename=\"subject-export-"));
+        assert!(disposition.ends_with(".jsonl\""));
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let lines: Vec<serde_json::Value> = String::from_utf8(body.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let keys = get_public_keys(State(state.clone()), Query(PublicKeysQuery::default()))
+            .await
+            .unwrap_or_else(|_| panic!("public keys failed"))
+            .0;
+        assert!(lines
+            .iter()
+            .any(|l| l["record"] == "public_key" && l["kid"] == keys.key_id));
+        assert!(lines
+            .iter()
+            .any(|l| l["record"] == "pointer" && l["pointer_id"] == json!(pointer_id)));
+
+        let logged = get_audit_trail_by_subject(
+            &state.db.writer,
+            &subject,
+            &AuditFilter {
+                event_type: Some("subject_exported".to_string()),
+                ..AuditFilter::default()
+            },
+            &Pagination {
+                after: None,
+                limit: 10,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(logged.len(), 1);
+
+        // Another org's subject, and one nobody holds
+        let other_org = AuthContext {
+            org_id: Uuid::new_v4(),
+            key_id: Uuid::nil(),
+        };
+        assert!(matches!(
+            export(other_org, subject, ExportFormat::Json).await,
+            Err(ApiError::Forbidden(_))
+        ));
+        assert!(matches!(
+            export(
+                test_auth(&state),
+                unique_subject("nobody"),
+                ExportFormat::Json
+            )
+            .await,
+            Err(ApiError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_audit_trail_downloads_as_csv() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("audit_csv");
+        let pointer_id = seed_pointer(&state, &subject).await;
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id,
+                reason: Some("said \"stop\", twice".to_string()),
+                effective_at: None,
+            }),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+
+        let download = |auth: AuthContext, format: AuditExportFormat| {
+            let state = state.clone();
+            let subject = subject.clone();
+            async move {
+                get_audit_trail_or_export(
+                    State(state),
+                    auth,
+                    Path(subject),
+                    Query(AuditTrailQuery {
+                        format: Some(format),
+                        ..AuditTrailQuery::default()
+                    }),
+                )
+                .await
+            }
+        };
+
+        let response = download(test_auth(&state), AuditExportFormat::Csv)
+            .await
+            .unwrap_or_else(|_| panic!("audit export failed"));
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        assert!(response.headers()[axum::http::header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .ends_with(".csv\""));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let csv = String::from_utf8(body.to_vec()).unwrap();
+        let rows: Vec<&str> = csv.split_terminator("\r\n").collect();
+
+        // Header, then newest first
+        assert_eq!(
+            rows[0],
+            "timestamp,event_type,pointer_id,actor_id,ip_address,event_data"
+        );
+        assert_eq!(rows.len(), 4);
+        let orphaned = rows
+            .iter()
+            .find(|row| row.contains(",pointer_orphaned,"))
+            .unwrap();
+        assert!(orphaned.contains(r#"""said \""stop\"", twice"""#));
+        assert!(rows[3].contains(",pointer_created,"));
+
+        let other_org = AuthContext {
+            org_id: Uuid::new_v4(),
+            key_id: Uuid::nil(),
+        };
+        assert!(matches!(
+            download(other_org, AuditExportFormat::Jsonl).await,
+            Err(ApiError::Forbidden(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_org_stats_counts_seeded_org() {
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (name) VALUES ('Stats Org') RETURNING org_id",
+        )
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        state.config.default_org_id = org_id;
+        let stats = |query: OrgStatsQuery| {
+            let state = state.clone();
+            async move {
+                get_org_stats(
+                    State(state.clone()),
+                    test_auth(&state),
+                    Path(org_id),
+                    Query(query),
+                )
+                .await
+                .map(|Json(stats)| stats)
+            }
+        };
+
+        // Nothing yet: zeros across the last 30 days and no median
+        let empty = stats(OrgStatsQuery::default())
+            .await
+            .unwrap_or_else(|_| panic!("stats failed"));
+        assert_eq!(empty.pointers.total_pointers, 0);
+        assert_eq!(empty.created_per_day.len(), 31);
+        assert!(empty.created_per_day.iter().all(|d| d.count == 0));
+        assert!(empty.receipts_by_operation.is_empty());
+        assert_eq!(empty.median_seconds_to_orphan, None);
+
+        // Four pointers: one three days old orphaned after 100s, one a day
+        // old orphaned after 300s, two from today still active
+        let now = chrono::Utc::now();
+        let subject = unique_subject("stats");
+        let mut pointer_ids = Vec::new();
+        for _ in 0..4 {
+            pointer_ids.push(seed_pointer(&state, &subject).await);
+        }
+        for (pointer_id, age_days, seconds_to_orphan) in
+            [(pointer_ids[0], 3, 100.0), (pointer_ids[1], 1, 300.0)]
+        {
+            let orphaned = orphan_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                Json(OrphanPointerRequest {
+                    pointer_id,
+                    reason: None,
+                    effective_at: None,
+                }),
+            )
+            .await;
+            assert!(orphaned.is_ok(), "orphan failed");
+
+            let created_at = now - chrono::Duration::days(age_days);
+            sqlx::query(
+                "UPDATE pointers SET created_at = $2, orphaned_at = $2 + make_interval(secs => $3)
+                 WHERE pointer_id = $1",
+            )
+            .bind(pointer_id)
+            .bind(created_at)
+            .bind(seconds_to_orphan)
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+        }
+
+        let all = stats(OrgStatsQuery::default())
+            .await
+            .unwrap_or_else(|_| panic!("stats failed"));
+        assert_eq!(all.pointers.total_pointers, 4);
+        assert_eq!(all.pointers.active_pointers, 2);
+        assert_eq!(all.pointers.orphaned_pointers, 2);
+        assert_eq!(all.pointers.pending_orphan_pointers, 0);
+        assert_eq!(all.receipts_by_operation["create"], 4);
+        assert_eq!(all.receipts_by_operation["orphan"], 2);
+        assert_eq!(all.median_seconds_to_orphan, Some(200.0));
+
+        let count_on = |days_ago: i64| {
+            let day = (now - chrono::Duration::days(days_ago)).date_naive();
+            all.created_per_day
+                .iter()
+                .find(|d| d.day == day)
+                .map(|d| d.count)
+        };
+        assert_eq!(count_on(0), Some(2));
+        assert_eq!(count_on(1), Some(1));
+        assert_eq!(count_on(3), Some(1));
+        assert_eq!(count_on(2), Some(0));
+        assert_eq!(all.created_per_day.iter().map(|d| d.count).sum::<i64>(), 4);
+
+        // A range leaves out the three-day-old pointer and its orphaning
+        let recent = stats(OrgStatsQuery {
+            from: Some(now - chrono::Duration::days(2)),
+            to: None,
+        })
+        .await
+        .unwrap_or_else(|_| panic!("stats failed"));
+        assert_eq!(recent.pointers.total_pointers, 3);
+        assert_eq!(recent.pointers.orphaned_pointers, 1);
+        assert_eq!(recent.median_seconds_to_orphan, Some(300.0));
+        assert_eq!(recent.created_per_day.len(), 3);
+
+        assert!(matches!(
+            stats(OrgStatsQuery {
+                from: Some(now),
+                to: Some(now - chrono::Duration::days(1)),
+            })
+            .await,
+            Err(ApiError::BadRequest(_))
+        ));
+        let other_org = AuthContext {
+            org_id: Uuid::new_v4(),
+            key_id: Uuid::nil(),
+        };
+        assert!(matches!(
+            get_org_stats(
+                State(state.clone()),
+                other_org,
+                Path(org_id),
+                Query(OrgStatsQuery::default())
+            )
+            .await,
+            Err(ApiError::Forbidden(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_external_ref_is_unique_per_org_and_resolves() {
+        use axum::response::IntoResponse;
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let external_ref = format!("crm-{}", Uuid::new_v4());
+        let create = |state: AppState, external_ref: Option<String>| async move {
+            create_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                RequestContext::default(),
+                IdempotencyKey::default(),
+                Json(CreatePointerRequest {
+                    subject_id: unique_subject("ref"),
+                    content_hash: "ab".repeat(64),
+                    encrypted_payload: None,
+                    payload_encoding: None,
+                    retention_class: None,
+                    expires_at: None,
+                    external_ref,
+                    requires_dual_approval: false,
+                }),
+            )
+            .await
+            .map(|(_, Json(created))| created)
+        };
+        let by_ref = |state: AppState, external_ref: String| async move {
+            resolve_pointer_by_ref(
+                State(state.clone()),
+                test_auth(&state),
+                Path(external_ref),
+                Query(ResolveQuery::default()),
+            )
+            .await
+            .map(|Json(resolved)| resolved)
+        };
+
+        let created = create(state.clone(), Some(external_ref.clone()))
+            .await
+            .unwrap_or_else(|_| panic!("create failed"));
+        assert_eq!(created.external_ref.as_deref(), Some(external_ref.as_str()));
+
+        // A second create with the ref names the pointer that holds it
+        let duplicate = create(state.clone(), Some(external_ref.clone())).await;
+        match duplicate {
+            Err(ApiError::ExternalRefExists { pointer_id }) => {
+                assert_eq!(pointer_id, created.pointer_id);
+                let response = ApiError::ExternalRefExists { pointer_id }.into_response();
+                assert_eq!(response.status(), StatusCode::CONFLICT);
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                assert_eq!(body["conflict_type"], "external_ref_exists");
+                assert_eq!(body["pointer_id"], json!(created.pointer_id));
+            }
+            _ => panic!("expected ExternalRefExists"),
+        }
+        assert!(matches!(
+            create(state.clone(), Some(" ".to_string())).await,
+            Err(ApiError::ValidationFailed(_))
+        ));
+
+        // Refs are scoped to the org
+        let mut other = state.clone();
+        other.config.default_org_id = sqlx::query_scalar(
+            "INSERT INTO organizations (name) VALUES ('Ref Org') RETURNING org_id",
+        )
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        let elsewhere = create(other.clone(), Some(external_ref.clone()))
+            .await
+            .unwrap_or_else(|_| panic!("create in other org failed"));
+        assert_ne!(elsewhere.pointer_id, created.pointer_id);
+
+        let resolved = by_ref(state.clone(), external_ref.clone())
+            .await
+            .unwrap_or_else(|_| panic!("resolve by ref failed"));
+        assert_eq!(resolved.pointer_id, created.pointer_id);
+        assert!(resolved.receipt_generated);
+        assert!(matches!(
+            by_ref(state.clone(), "no-such-ref".to_string()).await,
+            Err(ApiError::NotFound(_))
+        ));
+
+        // An orphaned pointer keeps its ref and is refused like any resolve
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id: created.pointer_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+        assert!(matches!(
+            by_ref(state.clone(), external_ref.clone()).await,
+            Err(ApiError::PointerOrphaned(_))
+        ));
+        assert!(matches!(
+            create(state.clone(), Some(external_ref)).await,
+            Err(ApiError::ExternalRefExists { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_requests_list_every_field_before_touching_the_database() {
+        use crate::api::validation::MAX_REASON_CHARS;
+
+        let mut state = crate::test_support::offline_state();
+        state.config.max_payload_bytes = 4;
+
+        let created = create_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(CreatePointerRequest {
+                subject_id: format!("user\u{0}{}", "x".repeat(MAX_SUBJECT_BYTES)),
+                content_hash: "nope".to_string(),
+                encrypted_payload: Some("c2VjcmV0".to_string()),
+                payload_encoding: None,
+                retention_class: None,
+                expires_at: None,
+                external_ref: Some(" ".to_string()),
+                requires_dual_approval: false,
+            }),
+        )
+        .await;
+        let Err(ApiError::ValidationFailed(errors)) = created else {
+            panic!("expected ValidationFailed");
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "subject_id",
+                "subject_id",
+                "content_hash",
+                "external_ref",
+                "encrypted_payload"
+            ]
+        );
+
+        let long_reason = Some("x".repeat(MAX_REASON_CHARS + 1));
+        let orphaned = orphan_group(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanGroupRequest {
+                pointer_ids: vec![Uuid::new_v4()],
+                reason: long_reason.clone(),
+            }),
+        )
+        .await;
+        assert!(matches!(orphaned, Err(ApiError::ValidationFailed(_))));
+        let orphaned = orphan_subject(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Path("someone".to_string()),
+            Json(OrphanSubjectRequest {
+                reason: long_reason.clone(),
+            }),
+        )
+        .await;
+        assert!(matches!(orphaned, Err(ApiError::ValidationFailed(_))));
+        let cancelled = cancel_orphan(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(CancelOrphanRequest {
+                pointer_id: Uuid::new_v4(),
+                reason: long_reason,
+            }),
+        )
+        .await;
+        assert!(matches!(cancelled, Err(ApiError::ValidationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_queries_go_to_the_reader() {
+        use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        // A reader that finds no tables, so anything sent to it fails
+        let options = state
+            .config
+            .database_url
+            .expose()
+            .parse::<PgConnectOptions>()
+            .unwrap()
+            .options([("search_path", "veto_no_such_schema")]);
+        let reader = PgPoolOptions::new()
+            .max_connections(2)
+            .connect_with(options)
+            .await
+            .unwrap();
+        let search_path: String = sqlx::query_scalar("SHOW search_path")
+            .fetch_one(&reader)
+            .await
+            .unwrap();
+        assert_eq!(search_path, "veto_no_such_schema");
+        let mut state = AppState {
+            db: Db {
+                writer: state.db.writer.clone(),
+                reader,
+            },
+            ..state
+        };
+
+        let subject = unique_subject("replica");
+        let pointer_id = seed_pointer(&state, &subject).await;
+
+        // Resolve and writes read the primary
+        let Json(resolved) = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("resolve failed"));
+        assert_eq!(resolved.status, "active");
+        let Json(orphaned) = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("orphan failed"));
+        assert_eq!(orphaned.status, "orphaned");
+
+        // Receipts, audit trail and listings read the reader
+        assert!(get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .is_err());
+        assert!(get_audit_trail(
+            State(state.clone()),
+            test_auth(&state),
+            Path(subject.clone()),
+            Query::default(),
+        )
+        .await
+        .is_err());
+        assert!(list_pointers(
+            State(state.clone()),
+            test_auth(&state),
+            Query(ListPointersQuery {
+                subject_id: Some(subject),
+                ..Default::default()
+            }),
+        )
+        .await
+        .is_err());
+
+        // Strict receipt reads record what they find, so they stay on the
+        // primary
+        state.config.strict_receipt_reads = true;
+        let Json(strict) = get_receipts(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("strict receipts failed"));
+        assert_eq!(strict.integrity_verified, Some(true));
+    }
+}
@@ -0,0 +1,73 @@
+// Idempotent pointer creation
+// A client retrying a create after a timeout sends the same Idempotency-Key.
+// Keys are scoped to the caller's organization and remembered for
+// IDEMPOTENCY_KEY_TTL_SECONDS: a replay inside that window gets the original
+// response back instead of a second pointer, and a replay with different
+// content is refused.
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+
+use super::ApiError;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Longest key accepted, the width of idempotency_keys.idempotency_key
+const MAX_IDEMPOTENCY_KEY_BYTES: usize = 255;
+
+/// The request's Idempotency-Key, if it sent one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdempotencyKey(pub Option<String>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for IdempotencyKey {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, ApiError> {
+        let Some(value) = parts.headers.get(IDEMPOTENCY_KEY_HEADER) else {
+            return Ok(IdempotencyKey(None));
+        };
+
+        let key = value.to_str().map(str::trim).map_err(|_| {
+            ApiError::BadRequest("Idempotency-Key must be visible ASCII".to_string())
+        })?;
+        if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_BYTES {
+            return Err(ApiError::BadRequest(format!(
+                "Idempotency-Key must be 1 to {} bytes",
+                MAX_IDEMPOTENCY_KEY_BYTES
+            )));
+        }
+
+        Ok(IdempotencyKey(Some(key.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    async fn extract(value: Option<&str>) -> Result<IdempotencyKey, ApiError> {
+        let mut request = Request::builder();
+        if let Some(value) = value {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, value);
+        }
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        IdempotencyKey::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_header() {
+        assert_eq!(extract(None).await.unwrap(), IdempotencyKey(None));
+        assert_eq!(
+            extract(Some(" retry-7 ")).await.unwrap(),
+            IdempotencyKey(Some("retry-7".to_string()))
+        );
+        assert!(matches!(
+            extract(Some("  ")).await,
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            extract(Some(&"k".repeat(256))).await,
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+}
@@ -1,16 +1,84 @@
 // API module
+pub mod audit_export;
+pub mod auth;
+#[cfg(test)]
+mod client_tests;
+pub mod compression;
+pub mod context;
+pub mod cors;
 pub mod errors;
 pub mod handlers;
+pub mod idempotency;
+pub mod openapi;
+pub mod payload;
+pub mod rate_limit;
+pub mod request_id;
+pub mod routes;
+pub mod validation;
 
 pub use errors::*;
-pub use handlers::*;
 
 // Application state shared across handlers
-use sqlx::PgPool;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub db_pool: PgPool,
-    pub keypair: crate::crypto::Ed25519Keypair,
+    /// Primary, and the replica obviously read-only queries go to
+    pub db: crate::db::Db,
+    /// Receipt signer, swapped on rotation, and the keys it replaced
+    pub signing_keys: Arc<crate::crypto::SigningKeyring>,
+    /// Per-org receipt signers; None without SIGNING_MASTER_KEY, when the
+    /// service key signs for every org
+    pub org_signing_keys: Option<Arc<crate::keys::OrgSigningKeys>>,
     pub config: crate::config::Config,
+    pub clock: Arc<dyn crate::clock::Clock>,
+    pub resolve_flights: Arc<ResolveFlights>,
+    /// Pointer state resolve reads before locking, see `db::pointer_cache`
+    pub pointer_cache: Arc<crate::db::PointerCache>,
+    pub receipt_verifier: Arc<crate::crypto::ReceiptVerifier>,
+    pub db_circuit: Arc<crate::db::CircuitBreaker>,
+    pub last_known: Arc<crate::enforcement::LastKnownStatuses>,
+    pub receipt_metrics: Arc<crate::analytics::ReceiptPhaseMetrics>,
+    pub api_keys: Arc<auth::KnownApiKeys>,
+    /// Wakes the webhook dispatcher when a request queues a delivery
+    pub webhook_wakeup: Arc<tokio::sync::Notify>,
+    /// Per-client token buckets, see `rate_limit`
+    pub rate_limiter: Arc<rate_limit::RateLimiter>,
+    /// Bytes before and after compression per route, see `compression`
+    pub compression_metrics: Arc<compression::CompressionMetrics>,
+    /// Encrypts payloads at rest; None without PAYLOAD_ENCRYPTION_KEY, when
+    /// payloads are stored as sent
+    pub payload_cipher: Option<Arc<crate::crypto::PayloadCipher>>,
 }
+
+impl AppState {
+    /// The key receipts are signed with now
+    pub fn keypair(&self) -> crate::crypto::Ed25519Keypair {
+        self.signing_keys.active()
+    }
+
+    /// The key `org_id`'s receipts are signed with on the request path
+    pub async fn signer(
+        &self,
+        org_id: uuid::Uuid,
+    ) -> anyhow::Result<crate::crypto::Ed25519Keypair> {
+        match &self.org_signing_keys {
+            Some(org_keys) => {
+                org_keys
+                    .keypair(&self.db.writer, org_id, self.clock.now())
+                    .await
+            }
+            None => Ok(self.keypair()),
+        }
+    }
+
+    /// Drop everything held in memory about a pointer after a write to it
+    pub fn pointer_written(&self, pointer_id: &uuid::Uuid) {
+        self.pointer_cache.evict(pointer_id);
+        self.resolve_flights.invalidate(pointer_id);
+        self.last_known.forget(pointer_id);
+    }
+}
+
+/// In-flight resolve fetches, shared by concurrent resolves of one pointer
+pub type ResolveFlights = crate::db::SingleFlight<uuid::Uuid, crate::db::PointerSnapshot>;
@@ -0,0 +1,360 @@
+// OpenAPI description of the HTTP API
+// Derived from the handlers' `utoipa::path` annotations and the request and
+// response types they name, so clients can be generated from the same
+// structs the handlers serialize instead of being kept in step by hand.
+// Served at /api/openapi.json; with SERVE_API_DOCS, Swagger UI renders it at
+// /api/docs.
+use axum::Json;
+use utoipa::{
+    openapi::{
+        security::{Http, HttpAuthScheme, SecurityScheme},
+        ContentBuilder, Ref, RefOr, Response, ResponseBuilder,
+    },
+    Modify, OpenApi,
+};
+use utoipa_swagger_ui::{Config, SwaggerUi};
+
+use super::{handlers, ErrorBody};
+
+/// Where the document is served, and where Swagger UI loads it from
+pub const OPENAPI_PATH: &str = "/api/openapi.json";
+
+/// Where Swagger UI is served with SERVE_API_DOCS
+pub const API_DOCS_PATH: &str = "/api/docs";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Veto Frontier API",
+        description = "Pointers to stored data that the data subject's \
+                       organization can veto, with a signed receipt for every \
+                       change. Errors share one body; `code` is stable."
+    ),
+    paths(
+        get_openapi,
+        handlers::health_check,
+        handlers::health_live,
+        handlers::health_ready,
+        handlers::get_test_vectors,
+        handlers::get_public_keys,
+        handlers::list_signing_keys,
+        handlers::rotate_receipt_signing_key,
+        handlers::create_pointer,
+        handlers::resolve_pointer,
+        handlers::resolve_pointer_by_ref,
+        handlers::swap_pointer_data,
+        handlers::orphan_pointer,
+        handlers::cancel_orphan,
+        handlers::request_orphan,
+        handlers::approve_orphan_request,
+        handlers::list_orphan_requests,
+        handlers::reinstate_pointer,
+        handlers::place_legal_hold,
+        handlers::release_legal_hold,
+        handlers::purge_pointer_payload,
+        handlers::orphan_group,
+        handlers::orphan_subject,
+        handlers::get_orphan_group,
+        handlers::get_org_heartbeats,
+        handlers::get_org_stats,
+        handlers::get_receipts,
+        handlers::verify_receipt_chain,
+        handlers::verify_submitted_receipt,
+        handlers::list_checkpoints,
+        handlers::get_receipt_proof,
+        handlers::get_receipt_by_hash_handler,
+        handlers::get_veto_slo,
+        handlers::get_metrics,
+        handlers::get_retention_report,
+        handlers::get_access_stats,
+        handlers::get_resolve_coalescing_stats,
+        handlers::get_pointer_changes,
+        handlers::restrict_subject,
+        handlers::unrestrict_subject,
+        handlers::export_subject,
+        handlers::get_duplicate_subjects,
+        handlers::merge_subjects,
+        handlers::check_pointer_consistency,
+        handlers::check_subject_consistency,
+        handlers::freeze_pointer_chain,
+        handlers::unfreeze_pointer_chain,
+        handlers::list_api_keys,
+        handlers::rotate_api_key,
+        handlers::revoke_api_key,
+        handlers::create_webhook,
+        handlers::list_webhooks,
+        handlers::get_webhook,
+        handlers::update_webhook,
+        handlers::delete_webhook,
+        handlers::get_webhook_deliveries,
+        handlers::get_audit_trail_or_export,
+        handlers::list_pointers,
+    ),
+    components(schemas(ErrorBody)),
+    modifiers(&ApiKeyAuth),
+    security(("api_key" = [])),
+    tags(
+        (name = "pointers", description = "Create, resolve and list pointers"),
+        (name = "orphans", description = "Vetoes: orphaning pointers, alone or in groups"),
+        (name = "holds", description = "Legal holds that block orphaning"),
+        (name = "restrictions", description = "Processing restrictions on a subject"),
+        (name = "receipts", description = "Signed receipts, their chains and checkpoints"),
+        (name = "audit", description = "Audit trail of a subject"),
+        (name = "subjects", description = "Subject exports and normalization"),
+        (name = "orgs", description = "Organization statistics and heartbeats"),
+        (name = "keys", description = "Receipt signing keys and verification material"),
+        (name = "api-keys", description = "The organization's API keys"),
+        (name = "webhooks", description = "Webhook subscriptions and deliveries"),
+        (name = "admin", description = "Operator reports and chain freezes"),
+        (name = "health", description = "Liveness and readiness probes"),
+        (name = "meta", description = "This description of the API"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Declares the bearer API key every operation needs unless it opts out
+/// with an empty security requirement, and the 401 and 429 answers the
+/// middleware gives before any handler runs
+struct ApiKeyAuth;
+
+impl Modify for ApiKeyAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+            );
+        }
+
+        let error = |description: &str| -> RefOr<Response> {
+            ResponseBuilder::new()
+                .description(description)
+                .content(
+                    "application/json",
+                    ContentBuilder::new()
+                        .schema(Some(Ref::from_schema_name("ErrorBody")))
+                        .build(),
+                )
+                .into()
+        };
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            // The probes are neither authenticated nor rate limited
+            let probe = path.starts_with("/health");
+            for operation in [
+                &mut item.get,
+                &mut item.post,
+                &mut item.patch,
+                &mut item.delete,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let responses = &mut operation.responses.responses;
+                if operation.security.is_none() {
+                    responses.insert(
+                        "401".to_string(),
+                        error("Missing, unknown, revoked or rotated API key"),
+                    );
+                }
+                if !probe {
+                    responses.insert(
+                        "429".to_string(),
+                        error("Over the rate limit; see Retry-After"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// This document
+#[utoipa::path(
+    get,
+    path = "/api/openapi.json",
+    tag = "meta",
+    security(()),
+    responses((status = 200, description = "The OpenAPI document", body = Object))
+)]
+pub async fn get_openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// Swagger UI over the document served at OPENAPI_PATH
+pub fn swagger_ui() -> SwaggerUi {
+    SwaggerUi::new(API_DOCS_PATH).config(Config::from(OPENAPI_PATH))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::routes::build_app, test_support::offline_state};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use serde_json::Value;
+    use std::collections::BTreeSet;
+    use tower::ServiceExt;
+
+    async fn get(app: Router, uri: &str) -> (StatusCode, Vec<u8>) {
+        let response = app
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, body.to_vec())
+    }
+
+    /// Method and path of every `.route(..)` in routes.rs, in OpenAPI's
+    /// `{param}` syntax
+    fn registered_routes() -> BTreeSet<(String, String)> {
+        let source = include_str!("routes.rs");
+        let source = &source[..source.find("#[cfg(test)]").unwrap()];
+
+        let mut routes = BTreeSet::new();
+        for (start, _) in source.match_indices(".route(") {
+            let args = &source[start + ".route(".len()..];
+            let mut depth = 1;
+            let end = args
+                .char_indices()
+                .find(|&(_, c)| {
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                    depth == 0
+                })
+                .unwrap()
+                .0;
+            let args = &args[..end];
+
+            let path = args.split('"').nth(1).unwrap();
+            let path = path
+                .split('/')
+                .map(|segment| match segment.strip_prefix(':') {
+                    Some(param) => format!("{{{}}}", param),
+                    None => segment.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            for method in ["get", "post", "patch", "delete"] {
+                let called = args.match_indices(&format!("{}(", method)).any(|(i, _)| {
+                    !args[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == ':')
+                });
+                if called {
+                    routes.insert((method.to_string(), path.clone()));
+                }
+            }
+        }
+        routes
+    }
+
+    async fn served_spec() -> Value {
+        let (status, body) = get(build_app(offline_state()), "/api/openapi.json").await;
+        assert_eq!(status, StatusCode::OK);
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_spec_documents_every_registered_route() {
+        let spec = served_spec().await;
+
+        let documented: BTreeSet<(String, String)> = spec["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| {
+                item.as_object()
+                    .unwrap()
+                    .keys()
+                    .filter(|key| *key != "parameters")
+                    .map(move |method| (method.clone(), path.clone()))
+            })
+            .collect();
+
+        let registered = registered_routes();
+        assert!(registered.len() > 50);
+        let undocumented: Vec<_> = registered.difference(&documented).collect();
+        assert!(undocumented.is_empty(), "undocumented: {:?}", undocumented);
+        let unrouted: Vec<_> = documented.difference(&registered).collect();
+        assert!(
+            unrouted.is_empty(),
+            "documented but not routed: {:?}",
+            unrouted
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spec_describes_statuses_and_formats() {
+        let spec = served_spec().await;
+        let paths = &spec["paths"];
+        let schemas = &spec["components"]["schemas"];
+        let error_ref = "#/components/schemas/ErrorBody";
+
+        let create = &paths["/api/pointer/create"]["post"]["responses"];
+        assert!(create["201"].is_object());
+        assert_eq!(
+            create["422"]["content"]["application/json"]["schema"]["$ref"],
+            error_ref
+        );
+
+        let resolve = &paths["/api/pointer/resolve/{id}"]["get"];
+        for status in ["403", "404", "401", "429"] {
+            assert_eq!(
+                resolve["responses"][status]["content"]["application/json"]["schema"]["$ref"],
+                error_ref,
+                "resolve {}",
+                status
+            );
+        }
+        let id = resolve["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"] == "id")
+            .unwrap();
+        assert_eq!(id["in"], "path");
+        assert_eq!(id["schema"]["format"], "uuid");
+
+        let resolved = &schemas["ResolvePointerResponse"]["properties"];
+        assert_eq!(resolved["pointer_id"]["format"], "uuid");
+        assert_eq!(resolved["created_at"]["format"], "date-time");
+        assert_eq!(resolved["encrypted_payload"]["format"], "byte");
+        assert_eq!(
+            schemas["ReceiptInfo"]["properties"]["signature"]["format"],
+            "byte"
+        );
+        assert!(schemas["ErrorBody"]["properties"]["code"].is_object());
+
+        // Public endpoints opt out of the API key, the rest need it
+        assert_eq!(
+            paths["/api/keys/public"]["get"]["security"],
+            serde_json::json!([{}])
+        );
+        assert!(paths["/api/keys/public"]["get"]["responses"]["401"].is_null());
+        assert!(spec["components"]["securitySchemes"]["api_key"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_swagger_ui_is_served_only_with_serve_api_docs() {
+        let (status, _) = get(build_app(offline_state()), "/api/docs/").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let mut state = offline_state();
+        state.config.serve_api_docs = true;
+        let app = build_app(state);
+        let (status, body) = get(app.clone(), "/api/docs/").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(String::from_utf8_lossy(&body).contains("swagger"));
+
+        // It loads the document from the route above rather than its own copy
+        let (status, body) = get(app, "/api/docs/swagger-initializer.js").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(String::from_utf8_lossy(&body).contains(OPENAPI_PATH));
+    }
+}
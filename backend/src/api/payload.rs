@@ -0,0 +1,190 @@
+// Payload decoding for create requests
+// Accepts the base64 variants clients actually send, plus explicit hex
+use data_encoding::{
+    Encoding, BASE64, BASE64URL, BASE64URL_NOPAD, BASE64_NOPAD, HEXLOWER_PERMISSIVE,
+};
+use thiserror::Error;
+pub use veto_types::PayloadEncoding;
+
+/// Concrete variant the payload was decoded with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectedEncoding {
+    Base64,
+    Base64NoPad,
+    Base64Url,
+    Base64UrlNoPad,
+    Hex,
+}
+
+impl DetectedEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DetectedEncoding::Base64 => "base64",
+            DetectedEncoding::Base64NoPad => "base64_nopad",
+            DetectedEncoding::Base64Url => "base64url",
+            DetectedEncoding::Base64UrlNoPad => "base64url_nopad",
+            DetectedEncoding::Hex => "hex",
+        }
+    }
+
+    fn encoding(&self) -> Encoding {
+        match self {
+            DetectedEncoding::Base64 => BASE64,
+            DetectedEncoding::Base64NoPad => BASE64_NOPAD,
+            DetectedEncoding::Base64Url => BASE64URL,
+            DetectedEncoding::Base64UrlNoPad => BASE64URL_NOPAD,
+            DetectedEncoding::Hex => HEXLOWER_PERMISSIVE,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DecodedPayload {
+    pub bytes: Vec<u8>,
+    pub encoding: DetectedEncoding,
+}
+
+#[derive(Debug, Error)]
+#[error(
+    "Invalid payload: expected one of [{}]; closest match {} failed with {} at byte offset {}",
+    expected_list(.expected),
+    .closest.as_str(),
+    .kind,
+    .offset
+)]
+pub struct PayloadDecodeError {
+    pub expected: Vec<DetectedEncoding>,
+    pub closest: DetectedEncoding,
+    pub kind: String,
+    pub offset: usize,
+}
+
+fn expected_list(expected: &[DetectedEncoding]) -> String {
+    expected
+        .iter()
+        .map(|e| e.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Candidate variants, tried in order, for a declared (or absent) encoding.
+/// Hex is only accepted when declared since every hex string is also valid base64.
+fn candidates(declared: Option<PayloadEncoding>) -> Vec<DetectedEncoding> {
+    match declared {
+        Some(PayloadEncoding::Base64) => {
+            vec![DetectedEncoding::Base64, DetectedEncoding::Base64NoPad]
+        }
+        Some(PayloadEncoding::Base64url) => {
+            vec![
+                DetectedEncoding::Base64Url,
+                DetectedEncoding::Base64UrlNoPad,
+            ]
+        }
+        Some(PayloadEncoding::Hex) => vec![DetectedEncoding::Hex],
+        None => vec![
+            DetectedEncoding::Base64,
+            DetectedEncoding::Base64NoPad,
+            DetectedEncoding::Base64Url,
+            DetectedEncoding::Base64UrlNoPad,
+        ],
+    }
+}
+
+/// Decode a client payload, returning the bytes and the variant that matched.
+/// When every candidate fails, the error reports the variant that got furthest.
+pub fn decode_payload(
+    input: &str,
+    declared: Option<PayloadEncoding>,
+) -> Result<DecodedPayload, PayloadDecodeError> {
+    let expected = candidates(declared);
+    let mut closest: Option<(DetectedEncoding, data_encoding::DecodeError)> = None;
+
+    for variant in &expected {
+        match variant.encoding().decode(input.as_bytes()) {
+            Ok(bytes) => {
+                return Ok(DecodedPayload {
+                    bytes,
+                    encoding: *variant,
+                })
+            }
+            Err(err) => {
+                let further = closest
+                    .as_ref()
+                    .is_none_or(|(_, best)| err.position > best.position);
+                if further {
+                    closest = Some((*variant, err));
+                }
+            }
+        }
+    }
+
+    let (closest, err) = closest.expect("at least one candidate encoding");
+    Err(PayloadDecodeError {
+        expected,
+        closest,
+        kind: err.kind.to_string(),
+        offset: err.position,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES: &[u8] = &[0xfb, 0xff, 0x00, 0x10, 0x3e];
+
+    #[test]
+    fn test_decodes_each_base64_variant() {
+        let cases = [
+            (BASE64.encode(BYTES), DetectedEncoding::Base64),
+            (BASE64_NOPAD.encode(BYTES), DetectedEncoding::Base64NoPad),
+            (BASE64URL.encode(BYTES), DetectedEncoding::Base64Url),
+            (
+                BASE64URL_NOPAD.encode(BYTES),
+                DetectedEncoding::Base64UrlNoPad,
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let decoded = decode_payload(&input, None).unwrap();
+            assert_eq!(decoded.bytes, BYTES, "input {}", input);
+            assert_eq!(decoded.encoding, expected, "input {}", input);
+        }
+    }
+
+    #[test]
+    fn test_declared_encodings() {
+        let decoded = decode_payload("fbff00103e", Some(PayloadEncoding::Hex)).unwrap();
+        assert_eq!(decoded.bytes, BYTES);
+        assert_eq!(decoded.encoding, DetectedEncoding::Hex);
+
+        let url = BASE64URL_NOPAD.encode(BYTES);
+        let decoded = decode_payload(&url, Some(PayloadEncoding::Base64url)).unwrap();
+        assert_eq!(decoded.encoding, DetectedEncoding::Base64UrlNoPad);
+
+        // URL-safe input is rejected when standard base64 is declared
+        assert!(decode_payload(&url, Some(PayloadEncoding::Base64)).is_err());
+    }
+
+    #[test]
+    fn test_mixed_alphabet_rejected() {
+        // '+' is standard-only and '_' is URL-safe-only
+        let err = decode_payload("ab+c_def", None).unwrap_err();
+        assert_eq!(err.expected.len(), 4);
+        assert_eq!(err.offset, 4);
+        assert_eq!(err.closest, DetectedEncoding::Base64);
+    }
+
+    #[test]
+    fn test_error_message_content() {
+        let err = decode_payload("abc$", None).unwrap_err();
+        let message = err.to_string();
+
+        assert!(message.contains("base64, base64_nopad, base64url, base64url_nopad"));
+        assert!(message.contains("byte offset 3"));
+
+        let err = decode_payload("zz", Some(PayloadEncoding::Hex)).unwrap_err();
+        assert!(err.to_string().contains("expected one of [hex]"));
+        assert_eq!(err.offset, 0);
+    }
+}
@@ -0,0 +1,608 @@
+// Rate limiting
+// A token bucket per client and route class: reads (GET and HEAD) and
+// writes (every other method) are limited separately, so a client flooding
+// orphans still resolves at its own pace. Authenticated requests are keyed
+// by API key, everything else by client IP. Requests that fail to
+// authenticate are charged to their IP too, so guessing keys is limited.
+// Buckets live in memory on this instance and are dropped once idle long
+// enough to have refilled.
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    net::{IpAddr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::{auth::AuthContext, context::client_ip, ApiError, AppState};
+use crate::{clock::Clock, db::queries::create_audit_log};
+
+/// How often idle buckets are dropped
+const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    Read,
+    Write,
+}
+
+impl RouteClass {
+    pub fn of(method: &Method) -> Self {
+        if method == Method::GET || method == Method::HEAD {
+            RouteClass::Read
+        } else {
+            RouteClass::Write
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            RouteClass::Read => "read",
+            RouteClass::Write => "write",
+        }
+    }
+}
+
+/// Who a bucket belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientKey {
+    ApiKey(Uuid),
+    /// None when the address is unknown, which then shares one bucket
+    Ip(Option<IpAddr>),
+}
+
+/// Why a request was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limited {
+    pub retry_after_seconds: u64,
+    /// First refusal since the client was last let through
+    pub first: bool,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: DateTime<Utc>,
+    limited: bool,
+}
+
+pub struct RateLimiter {
+    reads_per_minute: u32,
+    writes_per_minute: u32,
+    buckets: Mutex<HashMap<(ClientKey, RouteClass), Bucket>>,
+    rejected_reads: AtomicU64,
+    rejected_writes: AtomicU64,
+}
+
+impl RateLimiter {
+    /// A limit of zero leaves that class unlimited
+    pub fn new(reads_per_minute: u32, writes_per_minute: u32) -> Self {
+        Self {
+            reads_per_minute,
+            writes_per_minute,
+            buckets: Mutex::new(HashMap::new()),
+            rejected_reads: AtomicU64::new(0),
+            rejected_writes: AtomicU64::new(0),
+        }
+    }
+
+    fn per_minute(&self, class: RouteClass) -> u32 {
+        match class {
+            RouteClass::Read => self.reads_per_minute,
+            RouteClass::Write => self.writes_per_minute,
+        }
+    }
+
+    /// Take a token from the client's bucket. A bucket holds a minute's
+    /// worth, so a client may burst up to its limit, and refills evenly.
+    pub fn check(
+        &self,
+        client: ClientKey,
+        class: RouteClass,
+        now: DateTime<Utc>,
+    ) -> Result<(), Limited> {
+        self.take(client, class, now, true)
+    }
+
+    /// Refuse like `check` once the client's bucket is empty, but leave the
+    /// token for `charge` to take if the request turns out to count
+    pub fn peek(
+        &self,
+        client: ClientKey,
+        class: RouteClass,
+        now: DateTime<Utc>,
+    ) -> Result<(), Limited> {
+        self.take(client, class, now, false)
+    }
+
+    /// Take a token for a request `peek` let through
+    pub fn charge(&self, client: ClientKey, class: RouteClass, now: DateTime<Utc>) {
+        let per_minute = self.per_minute(class);
+        if per_minute == 0 {
+            return;
+        }
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = refilled(&mut buckets, client, class, now, per_minute);
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+
+    fn take(
+        &self,
+        client: ClientKey,
+        class: RouteClass,
+        now: DateTime<Utc>,
+        consume: bool,
+    ) -> Result<(), Limited> {
+        let per_minute = self.per_minute(class);
+        if per_minute == 0 {
+            return Ok(());
+        }
+        let per_second = f64::from(per_minute) / 60.0;
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = refilled(&mut buckets, client, class, now, per_minute);
+
+        if bucket.tokens >= 1.0 {
+            if consume {
+                bucket.tokens -= 1.0;
+            }
+            bucket.limited = false;
+            return Ok(());
+        }
+
+        let limited = Limited {
+            // Until the next whole token
+            retry_after_seconds: ((1.0 - bucket.tokens) / per_second).ceil().max(1.0) as u64,
+            first: !bucket.limited,
+        };
+        bucket.limited = true;
+        drop(buckets);
+
+        match class {
+            RouteClass::Read => &self.rejected_reads,
+            RouteClass::Write => &self.rejected_writes,
+        }
+        .fetch_add(1, Ordering::Relaxed);
+        Err(limited)
+    }
+
+    /// Drop buckets idle long enough to have refilled; they would start
+    /// over full anyway
+    pub fn evict_idle(&self, now: DateTime<Utc>) -> usize {
+        let mut buckets = self.buckets.lock().unwrap();
+        let before = buckets.len();
+        buckets.retain(|_, bucket| now - bucket.updated_at < chrono::Duration::minutes(1));
+        before - buckets.len()
+    }
+
+    pub fn render_openmetrics(&self, out: &mut String) {
+        let _ = writeln!(out, "# TYPE rate_limited_requests counter");
+        let _ = writeln!(
+            out,
+            "# HELP rate_limited_requests Requests refused with 429 by the rate limiter"
+        );
+        for (class, rejected) in [
+            (RouteClass::Read, &self.rejected_reads),
+            (RouteClass::Write, &self.rejected_writes),
+        ] {
+            let _ = writeln!(
+                out,
+                "rate_limited_requests_total{{class=\"{}\"}} {}",
+                class.as_str(),
+                rejected.load(Ordering::Relaxed)
+            );
+        }
+    }
+}
+
+/// A client's bucket, topped up for the time since it was last used
+fn refilled(
+    buckets: &mut HashMap<(ClientKey, RouteClass), Bucket>,
+    client: ClientKey,
+    class: RouteClass,
+    now: DateTime<Utc>,
+    per_minute: u32,
+) -> &mut Bucket {
+    let capacity = f64::from(per_minute);
+    let bucket = buckets.entry((client, class)).or_insert(Bucket {
+        tokens: capacity,
+        updated_at: now,
+        limited: false,
+    });
+    let elapsed = (now - bucket.updated_at).to_std().unwrap_or_default();
+    bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * capacity / 60.0).min(capacity);
+    bucket.updated_at = now;
+    bucket
+}
+
+fn request_ip(state: &AppState, request: &Request) -> Option<IpAddr> {
+    client_ip(
+        request.headers(),
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip()),
+        state.config.trusted_proxy,
+    )
+}
+
+/// Middleware: refuse a request over its client's limit with 429. Behind
+/// `require_api_key` it keys by the authenticated key, elsewhere by IP.
+pub async fn rate_limit(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let auth = request.extensions().get::<AuthContext>().copied();
+    let ip = request_ip(&state, &request);
+    let client = match auth {
+        Some(auth) => ClientKey::ApiKey(auth.key_id),
+        None => ClientKey::Ip(ip),
+    };
+    let class = RouteClass::of(request.method());
+
+    match state.rate_limiter.check(client, class, state.clock.now()) {
+        Ok(()) => Ok(next.run(request).await),
+        Err(limited) => Err(refuse(&state, request, auth, ip, class, limited).await),
+    }
+}
+
+/// Middleware in front of `require_api_key`: a request refused with 401 is
+/// charged to its client IP, and once the address has used up its bucket
+/// its requests are refused with 429 before their key is looked up
+pub async fn rate_limit_auth_failures(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let ip = request_ip(&state, &request);
+    let client = ClientKey::Ip(ip);
+    let class = RouteClass::of(request.method());
+
+    if let Err(limited) = state.rate_limiter.peek(client, class, state.clock.now()) {
+        return Err(refuse(&state, request, None, ip, class, limited).await);
+    }
+    let response = next.run(request).await;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        state.rate_limiter.charge(client, class, state.clock.now());
+    }
+    Ok(response)
+}
+
+/// The 429 for a limited request, logged and audited once per burst
+async fn refuse(
+    state: &AppState,
+    request: Request,
+    auth: Option<AuthContext>,
+    ip: Option<IpAddr>,
+    class: RouteClass,
+    limited: Limited,
+) -> ApiError {
+    // One audit row per burst, not per refused request
+    if limited.first {
+        info!(
+            route_class = class.as_str(),
+            retry_after_seconds = limited.retry_after_seconds,
+            "Rate limit exceeded"
+        );
+        if state.config.rate_limit_audit && !state.db_circuit.is_open() {
+            let user_agent = request
+                .headers()
+                .get(axum::http::header::USER_AGENT)
+                .and_then(|value| value.to_str().ok());
+            if let Err(e) = create_audit_log(
+                &state.db.writer,
+                auth.map(|auth| auth.org_id),
+                None,
+                None,
+                "rate_limited",
+                json!({
+                    "route_class": class.as_str(),
+                    "method": request.method().as_str(),
+                    "path": request.uri().path(),
+                    "limit_per_minute": state.rate_limiter.per_minute(class),
+                    "retry_after_seconds": limited.retry_after_seconds,
+                }),
+                auth.map(|auth| format!("api_key:{}", auth.key_id))
+                    .as_deref(),
+                ip,
+                user_agent,
+            )
+            .await
+            {
+                warn!("Failed to audit rate limiting: {:#}", e);
+            }
+        }
+    }
+
+    ApiError::RateLimited {
+        retry_after_seconds: limited.retry_after_seconds,
+    }
+}
+
+/// Drop idle buckets every minute so one-off clients don't accumulate
+pub async fn run_rate_limit_eviction(limiter: Arc<RateLimiter>, clock: Arc<dyn Clock>) {
+    let mut interval = tokio::time::interval(EVICTION_INTERVAL);
+    loop {
+        interval.tick().await;
+        limiter.evict_idle(clock.now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::routes::build_app,
+        clock::ManualClock,
+        keys::mint_api_key,
+        test_support::{offline_state, test_state},
+    };
+    use axum::{
+        body::Body,
+        http::{
+            header::{AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER},
+            Request as HttpRequest, StatusCode,
+        },
+        Router,
+    };
+    use tower::ServiceExt;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_764_000_000 + seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn test_bucket_bursts_to_the_limit_then_refills() {
+        let limiter = RateLimiter::new(0, 60);
+        let client = ClientKey::Ip(None);
+
+        for _ in 0..60 {
+            limiter.check(client, RouteClass::Write, at(0)).unwrap();
+        }
+        assert_eq!(
+            limiter.check(client, RouteClass::Write, at(0)),
+            Err(Limited {
+                retry_after_seconds: 1,
+                first: true
+            })
+        );
+        assert!(
+            !limiter
+                .check(client, RouteClass::Write, at(0))
+                .unwrap_err()
+                .first
+        );
+
+        // One a second comes back, and never more than the limit
+        limiter.check(client, RouteClass::Write, at(1)).unwrap();
+        assert!(limiter.check(client, RouteClass::Write, at(1)).is_err());
+        for _ in 0..60 {
+            limiter.check(client, RouteClass::Write, at(600)).unwrap();
+        }
+        assert!(limiter.check(client, RouteClass::Write, at(600)).is_err());
+
+        // Reads are unlimited at zero, and other clients have their own bucket
+        for _ in 0..1000 {
+            limiter.check(client, RouteClass::Read, at(600)).unwrap();
+        }
+        let other = ClientKey::ApiKey(Uuid::new_v4());
+        limiter.check(other, RouteClass::Write, at(600)).unwrap();
+    }
+
+    #[test]
+    fn test_peek_refuses_only_once_charged_empty() {
+        let limiter = RateLimiter::new(0, 2);
+        let client = ClientKey::Ip(None);
+
+        // Peeking alone never empties the bucket
+        for _ in 0..10 {
+            limiter.peek(client, RouteClass::Write, at(0)).unwrap();
+        }
+        limiter.charge(client, RouteClass::Write, at(0));
+        limiter.charge(client, RouteClass::Write, at(0));
+        let limited = limiter.peek(client, RouteClass::Write, at(0)).unwrap_err();
+        assert!(limited.first);
+        assert_eq!(limited.retry_after_seconds, 30);
+
+        limiter.peek(client, RouteClass::Write, at(30)).unwrap();
+    }
+
+    #[test]
+    fn test_idle_buckets_are_evicted() {
+        let limiter = RateLimiter::new(10, 10);
+        limiter
+            .check(ClientKey::Ip(None), RouteClass::Read, at(0))
+            .unwrap();
+        limiter
+            .check(ClientKey::Ip(None), RouteClass::Write, at(30))
+            .unwrap();
+
+        assert_eq!(limiter.evict_idle(at(59)), 0);
+        assert_eq!(limiter.evict_idle(at(60)), 1);
+        assert_eq!(limiter.evict_idle(at(90)), 1);
+        assert_eq!(limiter.evict_idle(at(90)), 0);
+    }
+
+    fn limited_app(writes_per_minute: u32, clock: Arc<ManualClock>) -> Router {
+        let mut state = offline_state();
+        state.clock = clock;
+        state.rate_limiter = Arc::new(RateLimiter::new(0, writes_per_minute));
+        build_app(state)
+    }
+
+    async fn verify_receipt(app: &Router) -> axum::response::Response {
+        let request = HttpRequest::builder()
+            .method(Method::POST)
+            .uri("/api/receipts/verify")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from("{}"))
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_burst_over_the_limit_is_refused_until_the_window_passes() {
+        let clock = Arc::new(ManualClock::new(at(0)));
+        let app = limited_app(3, clock.clone());
+
+        for _ in 0..3 {
+            assert_ne!(
+                verify_receipt(&app).await.status(),
+                StatusCode::TOO_MANY_REQUESTS
+            );
+        }
+        for _ in 0..2 {
+            let response = verify_receipt(&app).await;
+            assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+            assert_eq!(response.headers()[RETRY_AFTER], "20");
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["code"], "rate_limited");
+            assert_eq!(body["retry_after_seconds"], 20);
+        }
+
+        // Probes are never limited
+        let probe = HttpRequest::builder()
+            .uri("/health/live")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            app.clone().oneshot(probe).await.unwrap().status(),
+            StatusCode::OK
+        );
+
+        // A full window later the whole burst is available again
+        clock.set(at(60));
+        for _ in 0..3 {
+            assert_ne!(
+                verify_receipt(&app).await.status(),
+                StatusCode::TOO_MANY_REQUESTS
+            );
+        }
+        assert_eq!(
+            verify_receipt(&app).await.status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_authentication_is_limited_per_ip() {
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        state.clock = Arc::new(ManualClock::new(Utc::now()));
+        state.rate_limiter = Arc::new(RateLimiter::new(0, 2));
+        let app = build_app(state.clone());
+
+        let mut statuses = Vec::new();
+        for attempt in 0..4 {
+            let request = HttpRequest::builder()
+                .method(Method::POST)
+                .uri("/api/pointer/orphan")
+                .header(AUTHORIZATION, format!("Bearer vf_guess_{}", attempt))
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from("{}"))
+                .unwrap();
+            statuses.push(app.clone().oneshot(request).await.unwrap().status());
+        }
+        assert_eq!(
+            statuses,
+            [
+                StatusCode::UNAUTHORIZED,
+                StatusCode::UNAUTHORIZED,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::TOO_MANY_REQUESTS,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_are_limited_separately_and_audited_once_per_burst() {
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        let clock = Arc::new(ManualClock::new(Utc::now()));
+        state.clock = clock.clone();
+        state.rate_limiter = Arc::new(RateLimiter::new(0, 2));
+        state.config.rate_limit_audit = true;
+        let app = build_app(state.clone());
+
+        let mut keys = Vec::new();
+        for label in ["rate-limit-a", "rate-limit-b"] {
+            let issued = mint_api_key(
+                &state.db.writer,
+                &state.keypair(),
+                state.config.default_org_id,
+                label,
+                Utc::now(),
+            )
+            .await
+            .unwrap();
+            keys.push((issued.api_key.key_id, issued.key));
+        }
+        let orphan = |key: &str| {
+            HttpRequest::builder()
+                .method(Method::POST)
+                .uri("/api/pointer/orphan")
+                .header(AUTHORIZATION, format!("Bearer {}", key))
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from("{}"))
+                .unwrap()
+        };
+
+        let (limited_key_id, limited_key) = &keys[0];
+        let statuses = |key: String, count: usize| {
+            let app = app.clone();
+            async move {
+                let mut statuses = Vec::new();
+                for _ in 0..count {
+                    statuses.push(app.clone().oneshot(orphan(&key)).await.unwrap().status());
+                }
+                statuses
+            }
+        };
+        let burst = statuses(limited_key.clone(), 5).await;
+        assert!(burst[..2]
+            .iter()
+            .all(|status| *status != StatusCode::TOO_MANY_REQUESTS));
+        assert!(burst[2..]
+            .iter()
+            .all(|status| *status == StatusCode::TOO_MANY_REQUESTS));
+
+        // The other key is unaffected
+        let other = statuses(keys[1].1.clone(), 2).await;
+        assert!(other
+            .iter()
+            .all(|status| *status != StatusCode::TOO_MANY_REQUESTS));
+
+        let audited: Vec<serde_json::Value> = sqlx::query_scalar(
+            "SELECT event_data FROM audit_log WHERE event_type = 'rate_limited' AND actor_id = $1",
+        )
+        .bind(format!("api_key:{}", limited_key_id))
+        .fetch_all(&state.db.writer)
+        .await
+        .unwrap();
+        assert_eq!(audited.len(), 1);
+        assert_eq!(audited[0]["route_class"], "write");
+        assert_eq!(audited[0]["path"], "/api/pointer/orphan");
+
+        let mut metrics = String::new();
+        state.rate_limiter.render_openmetrics(&mut metrics);
+        assert!(metrics.contains("rate_limited_requests_total{class=\"write\"} 3\n"));
+    }
+}
@@ -0,0 +1,140 @@
+// Request ids and request spans
+// Every request runs inside a `request` span carrying its id, method, path
+// and, once known, status and the org, pointer, subject or group it acted
+// on, so each log line it writes can be traced back to it. The id is taken
+// from the caller's X-Request-Id when it sends a usable one, and echoed on
+// the response either way.
+use axum::{
+    extract::{MatchedPath, Request},
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::{fmt::Display, time::Instant};
+use tracing::{field::Empty, info, info_span, Instrument, Span};
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest caller-supplied request id kept; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Id of the request being handled, outside of a request None
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// The caller's id when it is printable ASCII of a sane length, so it can't
+/// forge log lines; otherwise a fresh one
+fn request_id_for(request: &Request) -> String {
+    request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| {
+            (1..=MAX_REQUEST_ID_LEN).contains(&id.len()) && id.bytes().all(|b| b.is_ascii_graphic())
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+pub async fn trace_request(request: Request, next: Next) -> Response {
+    let request_id = request_id_for(&request);
+    // The route template, so ids in the path don't make every line unique
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let span = info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %path,
+        status = Empty,
+        org_id = Empty,
+        pointer_id = Empty,
+        subject_id = Empty,
+        group_id = Empty,
+    );
+
+    let started = Instant::now();
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .instrument(span.clone())
+        .await;
+
+    let status = response.status().as_u16();
+    span.record("status", status);
+    span.in_scope(|| {
+        info!(
+            latency_ms = started.elapsed().as_millis() as u64,
+            "Request finished"
+        )
+    });
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Record the org the request authenticated as
+pub fn record_org(org_id: &Uuid) {
+    Span::current().record("org_id", tracing::field::display(org_id));
+}
+
+/// Record the pointer the request acts on
+pub fn record_pointer(pointer_id: &impl Display) {
+    Span::current().record("pointer_id", tracing::field::display(pointer_id));
+}
+
+/// Record the subject the request acts on
+pub fn record_subject(subject_id: &impl Display) {
+    Span::current().record("subject_id", tracing::field::display(subject_id));
+}
+
+/// Record the orphan group the request acts on
+pub fn record_group(group_id: &Uuid) {
+    Span::current().record("group_id", tracing::field::display(group_id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    fn with_header(value: &str) -> Request {
+        Request::builder()
+            .header(&REQUEST_ID_HEADER, value)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_usable_caller_ids_are_kept() {
+        for id in [
+            "abc-123",
+            "7f1c2a9e-0d4b-4c55-9a61-2b3f1e0c8d77",
+            &"x".repeat(128),
+        ] {
+            assert_eq!(request_id_for(&with_header(id)), id);
+        }
+    }
+
+    #[test]
+    fn test_unusable_caller_ids_are_replaced() {
+        for id in ["", "has space", "tab\there", &"x".repeat(129)] {
+            let generated = request_id_for(&with_header(id));
+            assert_ne!(generated, id);
+            assert!(generated.parse::<Uuid>().is_ok(), "{}", generated);
+        }
+
+        let missing = Request::builder().body(Body::empty()).unwrap();
+        assert!(request_id_for(&missing).parse::<Uuid>().is_ok());
+    }
+}
@@ -0,0 +1,371 @@
+// Router
+// Every route, its authentication and its response layers, assembled in one
+// place so tests can drive the whole app with `oneshot`
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post},
+    Router,
+};
+use tower_http::limit::RequestBodyLimitLayer;
+
+use super::{
+    auth::require_api_key,
+    compression::compressed,
+    cors::cors_layer,
+    handlers, openapi,
+    rate_limit::{rate_limit, rate_limit_auth_failures},
+    request_id::trace_request,
+    validation::request_body_limit,
+    AppState,
+};
+
+pub fn build_app(state: AppState) -> Router {
+    // Large read responses are compressed; resolve is deliberately left out
+    // so the hot path never pays for the encoder
+    let compressed_routes = Router::new()
+        .route("/api/receipts/:id", get(handlers::get_receipts))
+        .route(
+            "/api/receipts/:id/verify",
+            get(handlers::verify_receipt_chain),
+        )
+        .route(
+            "/api/audit/:subject_id",
+            get(handlers::get_audit_trail_or_export),
+        )
+        .route("/api/subject/:id/export", get(handlers::export_subject))
+        .route("/api/pointers", get(handlers::list_pointers))
+        .route("/api/pointers/changes", get(handlers::get_pointer_changes));
+    let compressed_routes = compressed(compressed_routes, &state);
+
+    // Everything but the health check, the published verification material,
+    // the API description and verifying a submitted receipt needs an API
+    // key. Those are limited per client IP, except the probes.
+    let mut public_routes = Router::new()
+        .route("/api/keys/public", get(handlers::get_public_keys))
+        .route("/api/openapi.json", get(openapi::get_openapi))
+        .route(
+            "/api/receipts/verify",
+            post(handlers::verify_submitted_receipt),
+        )
+        .merge(compressed(
+            Router::new().route("/api/meta/test-vectors", get(handlers::get_test_vectors)),
+            &state,
+        ))
+        .route_layer(middleware::from_fn_with_state(state.clone(), rate_limit))
+        .route("/health", get(handlers::health_check))
+        .route("/health/live", get(handlers::health_live))
+        .route("/health/ready", get(handlers::health_ready));
+    if state.config.serve_api_docs {
+        public_routes = public_routes.merge(openapi::swagger_ui());
+    }
+
+    Router::new()
+        .route("/api/pointer/create", post(handlers::create_pointer))
+        .route("/api/pointer/resolve/:id", get(handlers::resolve_pointer))
+        .route(
+            "/api/pointer/by-ref/:external_ref",
+            get(handlers::resolve_pointer_by_ref),
+        )
+        .route("/api/pointer/orphan", post(handlers::orphan_pointer))
+        .route("/api/pointer/orphan/cancel", post(handlers::cancel_orphan))
+        .route(
+            "/api/pointer/orphan/request",
+            post(handlers::request_orphan),
+        )
+        .route(
+            "/api/pointer/orphan/approve",
+            post(handlers::approve_orphan_request),
+        )
+        .route(
+            "/api/pointer/:id/orphan-requests",
+            get(handlers::list_orphan_requests),
+        )
+        .route("/api/pointer/reinstate", post(handlers::reinstate_pointer))
+        .route(
+            "/api/pointer/:id/hold",
+            post(handlers::place_legal_hold).delete(handlers::release_legal_hold),
+        )
+        .route(
+            "/api/pointer/:id/purge",
+            post(handlers::purge_pointer_payload),
+        )
+        .route(
+            "/api/pointer/:id/swap-data",
+            post(handlers::swap_pointer_data),
+        )
+        .route("/api/pointer/orphan-group", post(handlers::orphan_group))
+        .route("/api/orphan-groups/:id", get(handlers::get_orphan_group))
+        .route(
+            "/api/orgs/:id/heartbeats",
+            get(handlers::get_org_heartbeats),
+        )
+        .route("/api/orgs/:id/stats", get(handlers::get_org_stats))
+        .route(
+            "/api/pointer/:id/access-stats",
+            get(handlers::get_access_stats),
+        )
+        .route("/api/admin/slo", get(handlers::get_veto_slo))
+        .route("/api/admin/retention", get(handlers::get_retention_report))
+        .route("/metrics", get(handlers::get_metrics))
+        .route(
+            "/api/stats/resolve-coalescing",
+            get(handlers::get_resolve_coalescing_stats),
+        )
+        .route(
+            "/api/subject/:id/orphan-all",
+            post(handlers::orphan_subject),
+        )
+        .route(
+            "/api/subject/:id/restrict",
+            post(handlers::restrict_subject),
+        )
+        .route(
+            "/api/subject/:id/unrestrict",
+            post(handlers::unrestrict_subject),
+        )
+        .route(
+            "/api/subjects/duplicates",
+            get(handlers::get_duplicate_subjects),
+        )
+        .route("/api/subjects/merge", post(handlers::merge_subjects))
+        .route(
+            "/api/consistency/pointer/:id",
+            get(handlers::check_pointer_consistency),
+        )
+        .route(
+            "/api/consistency/subject/:id",
+            get(handlers::check_subject_consistency),
+        )
+        .route(
+            "/api/admin/pointer/:id/freeze",
+            post(handlers::freeze_pointer_chain),
+        )
+        .route(
+            "/api/admin/pointer/:id/unfreeze",
+            post(handlers::unfreeze_pointer_chain),
+        )
+        .route("/api/admin/signing-keys", get(handlers::list_signing_keys))
+        .route(
+            "/api/admin/signing-keys/rotate",
+            post(handlers::rotate_receipt_signing_key),
+        )
+        .route("/api/checkpoints", get(handlers::list_checkpoints))
+        .route("/api/receipts/:id/proof", get(handlers::get_receipt_proof))
+        .route(
+            "/api/receipts/by-hash/:receipt_hash",
+            get(handlers::get_receipt_by_hash_handler),
+        )
+        .route("/api/keys", get(handlers::list_api_keys))
+        .route("/api/keys/rotate", post(handlers::rotate_api_key))
+        .route("/api/keys/:id/revoke", post(handlers::revoke_api_key))
+        .route(
+            "/api/webhooks",
+            post(handlers::create_webhook).get(handlers::list_webhooks),
+        )
+        .route(
+            "/api/webhooks/:id",
+            get(handlers::get_webhook)
+                .patch(handlers::update_webhook)
+                .delete(handlers::delete_webhook),
+        )
+        .route(
+            "/api/webhooks/:id/deliveries",
+            get(handlers::get_webhook_deliveries),
+        )
+        .merge(compressed_routes)
+        // Runs after authentication, so limits are per API key
+        .route_layer(middleware::from_fn_with_state(state.clone(), rate_limit))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_key,
+        ))
+        // Runs before it, so failed attempts are limited per client IP
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_auth_failures,
+        ))
+        .merge(public_routes)
+        // Oversized bodies are refused with 413 before authentication or
+        // parsing, by declared length or once streaming passes the limit
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(request_body_limit(
+            state.config.max_payload_bytes,
+        )))
+        .layer(cors_layer(&state.config.cors_allowed_origins))
+        // Outermost, so even refused requests get an id and a log line
+        .layer(middleware::from_fn(trace_request))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::cors::CorsOrigins, test_support::offline_state};
+    use axum::{
+        body::Body,
+        http::{
+            header::{
+                ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_REQUEST_METHOD, AUTHORIZATION,
+                CONTENT_LENGTH, CONTENT_TYPE, ORIGIN,
+            },
+            HeaderValue, Method, Request, StatusCode,
+        },
+    };
+    use tower::ServiceExt;
+
+    fn app_allowing(origins: &str) -> Router {
+        let mut state = offline_state();
+        state.config.cors_allowed_origins = origins.parse().unwrap();
+        build_app(state)
+    }
+
+    /// Preflight a create from `origin`: status and the allowed origin echoed
+    async fn preflight(app: &Router, origin: &str) -> (StatusCode, Option<HeaderValue>) {
+        let request = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/api/pointer/create")
+            .header(ORIGIN, origin)
+            .header(ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        (
+            response.status(),
+            response.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).cloned(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_preflight_allows_only_configured_origins() {
+        let app = app_allowing("https://app.example.com,http://localhost:3000");
+
+        for origin in ["https://app.example.com", "http://localhost:3000"] {
+            let (status, allowed) = preflight(&app, origin).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(allowed, Some(HeaderValue::from_str(origin).unwrap()));
+        }
+
+        // Near misses get no allow header, so browsers block the call
+        for origin in [
+            "https://evil.example.com",
+            "http://app.example.com",
+            "https://app.example.com:8443",
+            "http://localhost:3001",
+        ] {
+            let (_, allowed) = preflight(&app, origin).await;
+            assert_eq!(allowed, None, "{} should not be allowed", origin);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_allows_any_origin() {
+        let app = app_allowing("*");
+        let (status, allowed) = preflight(&app, "https://anything.example.com").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(allowed, Some(HeaderValue::from_static("*")));
+    }
+
+    #[tokio::test]
+    async fn test_no_origins_allows_none() {
+        let mut state = offline_state();
+        state.config.cors_allowed_origins = CorsOrigins::Exact(vec![]);
+        let app = build_app(state);
+        let (_, allowed) = preflight(&app, "http://localhost:3000").await;
+        assert_eq!(allowed, None);
+
+        // Non-preflight requests still need a key, whatever their origin
+        let request = Request::builder()
+            .uri("/api/pointers")
+            .header(ORIGIN, "http://localhost:3000")
+            .header(AUTHORIZATION, "Basic nope")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_is_refused_before_parsing() {
+        let mut state = offline_state();
+        state.config.max_payload_bytes = 1024;
+        let limit = request_body_limit(1024);
+        let app = build_app(state);
+
+        let create = |body: Vec<u8>| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/api/pointer/create")
+                .header(CONTENT_TYPE, "application/json")
+                .header(CONTENT_LENGTH, body.len())
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        // Not even JSON, and no API key: the size alone decides
+        let response = app
+            .clone()
+            .oneshot(create(vec![b'x'; limit + 1]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Within the limit the same request reaches authentication
+        let response = app.oneshot(create(vec![b'x'; limit])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_json_logs_and_errors_carry_the_request_id() {
+        use crate::api::request_id::REQUEST_ID_HEADER;
+        use crate::logging::{capture::CapturedLogs, subscriber, LogFormat};
+
+        let logs = CapturedLogs::default();
+        let _guard = tracing::subscriber::set_default(subscriber(
+            LogFormat::Json,
+            "info".parse().unwrap(),
+            logs.clone(),
+        ));
+        let app = build_app(offline_state());
+
+        let request = Request::builder()
+            .uri("/api/receipts/not-a-uuid")
+            .header(&REQUEST_ID_HEADER, "support-ticket-42")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.headers().get(&REQUEST_ID_HEADER).unwrap(),
+            "support-ticket-42"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "unauthorized");
+        assert_eq!(body["request_id"], "support-ticket-42");
+
+        let lines = logs.lines();
+        assert!(!lines.is_empty());
+        for line in &lines {
+            let line: serde_json::Value = serde_json::from_str(line)
+                .unwrap_or_else(|e| panic!("not a JSON line ({}): {}", e, line));
+            assert_eq!(line["span"]["request_id"], "support-ticket-42");
+        }
+        let finished: serde_json::Value = serde_json::from_str(lines.last().unwrap()).unwrap();
+        assert_eq!(finished["message"], "Request finished");
+        assert_eq!(finished["span"]["method"], "GET");
+        assert_eq!(finished["span"]["path"], "/api/receipts/:id");
+        assert_eq!(finished["span"]["status"], 401);
+
+        // Without an id from the caller one is made up and still echoed
+        let request = Request::builder()
+            .uri("/health/live")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let generated = response.headers().get(&REQUEST_ID_HEADER).unwrap();
+        assert!(generated.to_str().unwrap().parse::<uuid::Uuid>().is_ok());
+    }
+}
@@ -0,0 +1,267 @@
+// Request validation
+// Field rules checked before a request touches the database or a receipt.
+// Violations are collected rather than returned one at a time, so a client
+// sees every problem with its request in one 422.
+
+use super::ApiError;
+use crate::crypto::hashing::is_sha3_512_hex;
+pub use veto_types::FieldError;
+
+/// Longest orphan reason accepted, in characters
+pub const MAX_REASON_CHARS: usize = 1024;
+
+/// Room in a request body for everything but the payload
+const REQUEST_OVERHEAD_BYTES: usize = 64 * 1024;
+
+/// Largest request body accepted: a MAX_PAYLOAD_BYTES payload in the widest
+/// encoding taken (hex, two characters a byte) plus the other fields
+pub fn request_body_limit(max_payload_bytes: usize) -> usize {
+    max_payload_bytes
+        .saturating_mul(2)
+        .saturating_add(REQUEST_OVERHEAD_BYTES)
+}
+
+#[derive(Debug, Default)]
+pub struct FieldErrors(Vec<FieldError>);
+
+impl FieldErrors {
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.0.push(FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Ok when nothing was added, otherwise a 422 listing every violation
+    pub fn into_result(self) -> Result<(), ApiError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::ValidationFailed(self.0))
+        }
+    }
+}
+
+/// Printable text only: control characters, and the invisible formatting
+/// ones (zero-width, bidi overrides, line separators) that make two ids look
+/// the same, are refused
+fn is_subject_char(c: char) -> bool {
+    !c.is_control()
+        && !matches!(
+            c,
+            '\u{200B}'..='\u{200F}' | '\u{2028}'..='\u{202E}' | '\u{2060}'..='\u{2069}' | '\u{FEFF}'
+        )
+}
+
+/// A subject_id as sent, before normalization
+pub fn check_subject_id(errors: &mut FieldErrors, field: &str, subject_id: &str, max_bytes: usize) {
+    if subject_id.trim().is_empty() {
+        errors.add(field, "must not be empty");
+    } else if subject_id.len() > max_bytes {
+        errors.add(field, format!("must be at most {} bytes", max_bytes));
+    }
+    if !subject_id.chars().all(is_subject_char) {
+        errors.add(
+            field,
+            "must not contain control or invisible formatting characters",
+        );
+    }
+}
+
+/// A subject_id as stored, once normalized and digested if over-long
+pub fn check_subject_key(
+    errors: &mut FieldErrors,
+    field: &str,
+    subject_key: &str,
+    max_bytes: usize,
+) {
+    if subject_key.len() > max_bytes {
+        errors.add(
+            field,
+            format!("must be at most {} bytes once normalized", max_bytes),
+        );
+    }
+}
+
+pub fn check_content_hash(errors: &mut FieldErrors, field: &str, content_hash: &str) {
+    if !is_sha3_512_hex(content_hash) {
+        errors.add(
+            field,
+            "must be a SHA3-512 digest: 128 lowercase hex characters",
+        );
+    }
+}
+
+/// A payload's size once decoded, which is what is stored
+pub fn check_payload_size(
+    errors: &mut FieldErrors,
+    field: &str,
+    decoded_bytes: usize,
+    max_bytes: usize,
+) {
+    if decoded_bytes > max_bytes {
+        errors.add(
+            field,
+            format!(
+                "decodes to {} bytes; at most {} are accepted",
+                decoded_bytes, max_bytes
+            ),
+        );
+    }
+}
+
+pub fn check_reason(errors: &mut FieldErrors, reason: Option<&str>) {
+    if let Some(reason) = reason {
+        if reason.chars().count() > MAX_REASON_CHARS {
+            errors.add(
+                "reason",
+                format!("must be at most {} characters", MAX_REASON_CHARS),
+            );
+        }
+    }
+}
+
+/// A required free-text justification, held to the reason limit
+pub fn check_justification(errors: &mut FieldErrors, justification: &str) {
+    if justification.trim().is_empty() {
+        errors.add("justification", "must not be empty");
+    } else if justification.chars().count() > MAX_REASON_CHARS {
+        errors.add(
+            "justification",
+            format!("must be at most {} characters", MAX_REASON_CHARS),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors_of(check: impl FnOnce(&mut FieldErrors)) -> Vec<FieldError> {
+        let mut errors = FieldErrors::default();
+        check(&mut errors);
+        match errors.into_result() {
+            Ok(()) => Vec::new(),
+            Err(ApiError::ValidationFailed(errors)) => errors,
+            Err(other) => panic!("expected ValidationFailed, got {:?}", other),
+        }
+    }
+
+    fn messages(errors: Vec<FieldError>) -> Vec<String> {
+        errors.into_iter().map(|e| e.message).collect()
+    }
+
+    #[test]
+    fn test_subject_id_rules() {
+        let check = |subject: &str| {
+            messages(errors_of(|e| {
+                check_subject_id(e, "subject_id", subject, 32)
+            }))
+        };
+
+        for ok in ["user_123", "alice@example.com", "José Müller", "a:b/c-d.e"] {
+            assert!(check(ok).is_empty(), "{} refused", ok);
+        }
+        assert_eq!(check(""), ["must not be empty"]);
+        assert_eq!(check("   "), ["must not be empty"]);
+        // The limit is in bytes, not characters
+        assert!(check(&"a".repeat(32)).is_empty());
+        assert_eq!(check(&"é".repeat(17)), ["must be at most 32 bytes"]);
+
+        for hidden in [
+            "user\n1",
+            "user\u{0}1",
+            "user\u{7F}",
+            "us\u{200B}er",
+            "\u{202E}resu",
+            "\u{FEFF}user",
+        ] {
+            assert_eq!(
+                check(hidden),
+                ["must not contain control or invisible formatting characters"],
+                "{:?} accepted",
+                hidden
+            );
+        }
+
+        // Every violation is reported, each against its field
+        let errors = errors_of(|e| check_subject_id(e, "subject_id", &"\t".repeat(40), 32));
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.field == "subject_id"));
+    }
+
+    #[test]
+    fn test_subject_key_limit() {
+        let check =
+            |key: &str| messages(errors_of(|e| check_subject_key(e, "subject_id", key, 73)));
+
+        assert!(check(&format!("sha3-256:{}", "ab".repeat(32))).is_empty());
+        assert_eq!(
+            check(&"a".repeat(74)),
+            ["must be at most 73 bytes once normalized"]
+        );
+    }
+
+    #[test]
+    fn test_content_hash_must_be_sha3_512_hex() {
+        let check = |hash: &str| errors_of(|e| check_content_hash(e, "content_hash", hash));
+
+        assert!(check(&"ab".repeat(64)).is_empty());
+        for bad in [
+            "".to_string(),
+            "ab".repeat(32),
+            "ab".repeat(65),
+            "AB".repeat(64),
+            "zz".repeat(64),
+        ] {
+            assert_eq!(check(&bad).len(), 1, "{} accepted", bad);
+        }
+    }
+
+    #[test]
+    fn test_payload_size_is_capped_when_decoded() {
+        let check =
+            |size: usize| errors_of(|e| check_payload_size(e, "encrypted_payload", size, 1024));
+
+        assert!(check(1024).is_empty());
+        assert_eq!(
+            messages(check(1025)),
+            ["decodes to 1025 bytes; at most 1024 are accepted"]
+        );
+
+        // The body limit leaves room for the payload in hex
+        assert!(request_body_limit(1024) > 2 * 1024);
+        assert_eq!(request_body_limit(usize::MAX), usize::MAX);
+    }
+
+    #[test]
+    fn test_reason_length_is_capped() {
+        let check = |reason: Option<&str>| errors_of(|e| check_reason(e, reason));
+
+        assert!(check(None).is_empty());
+        assert!(check(Some("")).is_empty());
+        assert!(check(Some(&"é".repeat(MAX_REASON_CHARS))).is_empty());
+        let errors = check(Some(&"x".repeat(MAX_REASON_CHARS + 1)));
+        assert_eq!(
+            errors,
+            [FieldError {
+                field: "reason".to_string(),
+                message: format!("must be at most {} characters", MAX_REASON_CHARS),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_justification_is_required_and_capped() {
+        let check =
+            |justification: &str| messages(errors_of(|e| check_justification(e, justification)));
+
+        assert!(check("wrong pointer id pasted, ticket 4411").is_empty());
+        assert_eq!(check(""), ["must not be empty"]);
+        assert_eq!(check(" \t"), ["must not be empty"]);
+        assert_eq!(
+            check(&"x".repeat(MAX_REASON_CHARS + 1)),
+            [format!("must be at most {} characters", MAX_REASON_CHARS)]
+        );
+    }
+}
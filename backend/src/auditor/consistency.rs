@@ -0,0 +1,534 @@
+// Status column vs receipt chain consistency
+// The signed chain is the evidence; a status column that disagrees with it
+// points at a partial failure or a manual edit
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    clock::Clock,
+    config::Config,
+    crypto::{Ed25519Keypair, ReceiptData, SignedReceipt, SigningKeyring},
+    db::{
+        models::{PointerChainState, PointerStatus, ReceiptOperation},
+        queries, PointerCache,
+    },
+};
+
+/// Which side claims the pointer is orphaned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchDirection {
+    /// The chain holds an Orphan receipt but the status column says active
+    ChainOrphanedStatusActive,
+    /// The status column says orphaned but the chain has no Orphan receipt
+    StatusOrphanedChainActive,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ConsistencyMismatch {
+    pub pointer_id: Uuid,
+    pub org_id: Uuid,
+    pub subject_id: String,
+    pub direction: MismatchDirection,
+    pub status_orphaned_at: Option<DateTime<Utc>>,
+    pub chain_orphaned_at: Option<DateTime<Utc>>,
+    pub last_receipt_at: Option<DateTime<Utc>>,
+}
+
+/// Compare a pointer's status with what its chain implies
+pub fn check(state: &PointerChainState) -> Option<ConsistencyMismatch> {
+    let direction = match (&state.status, state.chain_orphaned_at) {
+        (PointerStatus::Active, Some(_)) => MismatchDirection::ChainOrphanedStatusActive,
+        (PointerStatus::Orphaned, None) => MismatchDirection::StatusOrphanedChainActive,
+        _ => return None,
+    };
+
+    Some(ConsistencyMismatch {
+        pointer_id: state.pointer_id,
+        org_id: state.org_id,
+        subject_id: state.subject_id.clone(),
+        direction,
+        status_orphaned_at: state.orphaned_at,
+        chain_orphaned_at: state.chain_orphaned_at,
+        last_receipt_at: state.last_receipt_at,
+    })
+}
+
+/// Alert on a mismatch: an ALERT log line plus a `consistency_mismatch` audit event
+pub async fn report_mismatch(pool: &PgPool, mismatch: &ConsistencyMismatch) -> Result<()> {
+    warn!(
+        "ALERT pointer {} status disagrees with its receipt chain: {:?}",
+        mismatch.pointer_id, mismatch.direction
+    );
+
+    queries::create_audit_log(
+        pool,
+        Some(mismatch.org_id),
+        Some(mismatch.pointer_id),
+        None,
+        "consistency_mismatch",
+        json!(mismatch),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Rewrite the status column to match the signed chain and record the
+/// correction with a Repair receipt. Returns None if the pointer changed
+/// since it was checked.
+pub async fn repair(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    mismatch: &ConsistencyMismatch,
+    now: DateTime<Utc>,
+) -> Result<Option<SignedReceipt>> {
+    let mut tx = pool.begin().await?;
+
+    let Some(pointer) = queries::lock_pointers_for_update(&mut *tx, &[mismatch.pointer_id])
+        .await?
+        .pop()
+    else {
+        return Ok(None);
+    };
+
+    let (expected, status, orphaned_at) = match mismatch.direction {
+        MismatchDirection::ChainOrphanedStatusActive => (
+            PointerStatus::Active,
+            PointerStatus::Orphaned,
+            mismatch.chain_orphaned_at,
+        ),
+        MismatchDirection::StatusOrphanedChainActive => {
+            (PointerStatus::Orphaned, PointerStatus::Active, None)
+        }
+    };
+
+    if std::mem::discriminant(&pointer.status) != std::mem::discriminant(&expected) {
+        return Ok(None);
+    }
+
+    let repaired =
+        queries::set_pointer_status(&mut *tx, pointer.pointer_id, status.clone(), orphaned_at)
+            .await?;
+
+    let prev_hash = queries::get_latest_receipt_hash(&mut *tx, pointer.pointer_id).await?;
+
+    let receipt_data = ReceiptData::new(
+        pointer.pointer_id,
+        ReceiptOperation::Repair,
+        pointer.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "direction": mismatch.direction,
+            "previous_status": pointer.status,
+            "status": status,
+            "previous_orphaned_at": pointer.orphaned_at,
+            "orphaned_at": repaired.orphaned_at,
+        }),
+        now,
+    );
+    let signed_receipt = receipt_data.sign(keypair)?;
+
+    queries::create_governance_receipt(
+        &mut *tx,
+        pointer.pointer_id,
+        pointer.org_id,
+        ReceiptOperation::Repair,
+        signed_receipt.receipt_json.clone(),
+        &signed_receipt.receipt_hash,
+        &signed_receipt.signature,
+        &signed_receipt.signature_algorithm,
+        &signed_receipt.key_id,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    queries::create_audit_log(
+        &mut *tx,
+        Some(pointer.org_id),
+        Some(pointer.pointer_id),
+        None,
+        "consistency_repaired",
+        json!({
+            "direction": mismatch.direction,
+            "receipt_hash": signed_receipt.receipt_hash,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    info!(
+        "Repaired pointer {} status from receipt chain",
+        pointer.pointer_id
+    );
+
+    Ok(Some(signed_receipt))
+}
+
+/// Check the given pointers, alerting on each mismatch and repairing it when
+/// `auto_repair` is set. Repaired pointers are evicted from `pointer_cache`.
+/// Returns the mismatches and the ids repaired.
+pub async fn check_and_report(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    pointer_cache: &PointerCache,
+    states: &[PointerChainState],
+    auto_repair: bool,
+    now: DateTime<Utc>,
+) -> Result<(Vec<ConsistencyMismatch>, Vec<Uuid>)> {
+    let mut mismatches = Vec::new();
+    let mut repaired = Vec::new();
+
+    for mismatch in states.iter().filter_map(check) {
+        report_mismatch(pool, &mismatch).await?;
+
+        if auto_repair && repair(pool, keypair, &mismatch, now).await?.is_some() {
+            pointer_cache.evict(&mismatch.pointer_id);
+            repaired.push(mismatch.pointer_id);
+        }
+
+        mismatches.push(mismatch);
+    }
+
+    Ok((mismatches, repaired))
+}
+
+/// Background loop: check a random sample of pointers every interval
+pub async fn run_consistency_auditor(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    config: Config,
+    pointer_cache: Arc<PointerCache>,
+    clock: Arc<dyn Clock>,
+) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.consistency_audit_interval_seconds.max(1),
+    ));
+
+    info!(
+        "Consistency auditor sampling {} pointers every {}s (auto-repair {})",
+        config.consistency_sample_size,
+        config.consistency_audit_interval_seconds,
+        config.consistency_auto_repair
+    );
+
+    loop {
+        interval.tick().await;
+
+        let states =
+            match queries::sample_pointer_chain_states(&pool, config.consistency_sample_size).await
+            {
+                Ok(states) => states,
+                Err(e) => {
+                    error!("Consistency sampling failed: {:#}", e);
+                    continue;
+                }
+            };
+
+        if let Err(e) = check_and_report(
+            &pool,
+            &signing_keys.active(),
+            &pointer_cache,
+            &states,
+            config.consistency_auto_repair,
+            clock.now(),
+        )
+        .await
+        {
+            error!("Consistency check failed: {:#}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            context::RequestContext,
+            handlers::{
+                check_pointer_consistency, get_public_keys, orphan_pointer, resolve_pointer,
+                ConsistencyQuery, OrphanPointerRequest, PublicKeysQuery, ResolveMode, ResolveQuery,
+            },
+            ApiError, AppState,
+        },
+        test_support::{seed_pointer, test_auth, test_state, unique_subject},
+    };
+    use axum::{
+        extract::{Path, Query, State},
+        Json,
+    };
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    fn chain_state(
+        status: PointerStatus,
+        chain_orphaned_at: Option<DateTime<Utc>>,
+    ) -> PointerChainState {
+        PointerChainState {
+            pointer_id: Uuid::new_v4(),
+            org_id: Uuid::new_v4(),
+            subject_id: "user_123".to_string(),
+            orphaned_at: matches!(status, PointerStatus::Orphaned).then(Utc::now),
+            status,
+            chain_orphaned_at,
+            last_receipt_at: Some(Utc::now()),
+        }
+    }
+
+    #[test]
+    fn test_check_detects_both_directions() {
+        let now = Some(Utc::now());
+
+        assert!(check(&chain_state(PointerStatus::Active, None)).is_none());
+        assert!(check(&chain_state(PointerStatus::Orphaned, now)).is_none());
+        assert_eq!(
+            check(&chain_state(PointerStatus::Active, now))
+                .unwrap()
+                .direction,
+            MismatchDirection::ChainOrphanedStatusActive
+        );
+        assert_eq!(
+            check(&chain_state(PointerStatus::Orphaned, None))
+                .unwrap()
+                .direction,
+            MismatchDirection::StatusOrphanedChainActive
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repair_requires_opt_in() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("consistency")).await;
+
+        // Status says orphaned, chain has no orphan receipt
+        queries::set_pointer_status(
+            &state.db.writer,
+            pointer_id,
+            PointerStatus::Orphaned,
+            Some(Utc::now()),
+        )
+        .await
+        .unwrap();
+
+        let Json(report) = check_pointer_consistency(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query(ConsistencyQuery { repair: false }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(
+            report.mismatches[0].direction,
+            MismatchDirection::StatusOrphanedChainActive
+        );
+        assert!(report.repaired.is_empty());
+
+        let refused = check_pointer_consistency(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query(ConsistencyQuery { repair: true }),
+        )
+        .await;
+        assert!(matches!(refused, Err(ApiError::BadRequest(_))));
+
+        let mut config = state.config.clone();
+        config.consistency_auto_repair = true;
+        let repairing = AppState {
+            config,
+            ..state.clone()
+        };
+
+        let Json(report) = check_pointer_consistency(
+            State(repairing),
+            test_auth(&state),
+            Path(pointer_id),
+            Query(ConsistencyQuery { repair: true }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.repaired, vec![pointer_id]);
+
+        let pointer = queries::get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(pointer.status, PointerStatus::Active));
+        assert!(pointer.orphaned_at.is_none());
+
+        let receipts = queries::get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let repair = receipts.last().unwrap();
+        assert_eq!(repair.receipt_json["operation"], "repair");
+        assert_eq!(
+            repair.receipt_json["metadata"]["direction"],
+            "status_orphaned_chain_active"
+        );
+
+        // Signed like every other receipt of the org: verifies against the
+        // key published for it
+        let Json(keys) = get_public_keys(
+            State(state.clone()),
+            Query(PublicKeysQuery {
+                org_id: Some(state.config.default_org_id),
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("public keys failed"));
+        let org_key = &keys.keys[0];
+        assert_eq!(repair.key_id.as_deref(), Some(org_key.kid.as_str()));
+        let x = data_encoding::BASE64URL_NOPAD
+            .decode(org_key.x.as_bytes())
+            .unwrap();
+        let key = VerifyingKey::from_bytes(&x.try_into().unwrap()).unwrap();
+        let signature = Signature::from_slice(&repair.signature).unwrap();
+        assert!(key
+            .verify(repair.receipt_hash.as_bytes(), &signature)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_repair_restores_orphan_from_chain() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("consistency")).await;
+
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+
+        // Chain says orphaned, status column was reset behind its back
+        queries::set_pointer_status(&state.db.writer, pointer_id, PointerStatus::Active, None)
+            .await
+            .unwrap();
+
+        let chain_state = queries::get_pointer_chain_state(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let (mismatches, repaired) = check_and_report(
+            &state.db.writer,
+            &state.keypair(),
+            &state.pointer_cache,
+            &[chain_state],
+            true,
+            state.clock.now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            mismatches[0].direction,
+            MismatchDirection::ChainOrphanedStatusActive
+        );
+        assert_eq!(repaired, vec![pointer_id]);
+
+        let pointer = queries::get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(pointer.status, PointerStatus::Orphaned));
+        assert_eq!(pointer.orphaned_at, mismatches[0].chain_orphaned_at);
+
+        // Once repaired the pointer checks clean
+        let chain_state = queries::get_pointer_chain_state(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(check(&chain_state).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_repair_evicts_cached_pointer_state() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        // Long enough that only eviction can end the cached entry
+        let state = AppState {
+            pointer_cache: Arc::new(PointerCache::new(std::time::Duration::from_secs(600), 100)),
+            ..state
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("consistency")).await;
+
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+        queries::set_pointer_status(&state.db.writer, pointer_id, PointerStatus::Active, None)
+            .await
+            .unwrap();
+
+        let readonly = || {
+            resolve_pointer(
+                State(state.clone()),
+                test_auth(&state),
+                Path(pointer_id),
+                Query(ResolveQuery {
+                    mode: ResolveMode::Readonly,
+                    ..Default::default()
+                }),
+            )
+        };
+        let Json(cached) = readonly()
+            .await
+            .unwrap_or_else(|_| panic!("resolve failed"));
+        assert_eq!(cached.status, "active");
+
+        let chain_state = queries::get_pointer_chain_state(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let (_, repaired) = check_and_report(
+            &state.db.writer,
+            &state.keypair(),
+            &state.pointer_cache,
+            &[chain_state],
+            true,
+            state.clock.now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(repaired, vec![pointer_id]);
+
+        assert!(matches!(
+            readonly().await,
+            Err(ApiError::PointerOrphaned(_))
+        ));
+    }
+}
@@ -0,0 +1,4 @@
+// Background auditor module
+pub mod consistency;
+
+pub use consistency::*;
@@ -0,0 +1,6 @@
+// Receipt checkpoint module
+pub mod proof;
+pub mod writer;
+
+pub use proof::*;
+pub use writer::*;
@@ -0,0 +1,64 @@
+// Merkle inclusion proofs
+// A proof leads from one receipt's hash to the signed root of the
+// checkpoint covering it, rebuilt from the stored leaves.
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    crypto::merkle::{verify_inclusion, MerkleTree, ProofStep},
+    db::{models::ReceiptCheckpoint, queries},
+};
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InclusionProof {
+    /// The hash the checkpoint committed to, which differs from the
+    /// receipt's current hash only if the receipt was rewritten since
+    pub receipt_hash: String,
+    /// Position among the checkpoint's leaves
+    pub leaf_index: i32,
+    /// Siblings from the leaf up to the root
+    pub path: Vec<ProofStep>,
+}
+
+/// The proof for a receipt and the checkpoint it leads to; None until a
+/// checkpoint covers the receipt
+pub async fn inclusion_proof(
+    pool: &PgPool,
+    receipt_id: Uuid,
+) -> Result<Option<(InclusionProof, ReceiptCheckpoint)>> {
+    let Some(leaf) = queries::get_checkpoint_leaf(pool, receipt_id).await? else {
+        return Ok(None);
+    };
+    let checkpoint = queries::get_receipt_checkpoint(pool, leaf.checkpoint_id)
+        .await?
+        .ok_or_else(|| anyhow!("Checkpoint {} not found", leaf.checkpoint_id))?;
+    let leaves = queries::get_checkpoint_leaf_hashes(pool, leaf.checkpoint_id).await?;
+
+    let path = MerkleTree::new(&leaves)
+        .and_then(|tree| tree.proof(leaf.position as usize))
+        .ok_or_else(|| {
+            anyhow!(
+                "Checkpoint {} has no leaf {}",
+                checkpoint.sequence,
+                leaf.position
+            )
+        })?;
+    if !verify_inclusion(&leaf.receipt_hash, &path, &checkpoint.merkle_root) {
+        bail!(
+            "Checkpoint {} leaves no longer match its signed root",
+            checkpoint.sequence
+        );
+    }
+
+    Ok(Some((
+        InclusionProof {
+            receipt_hash: leaf.receipt_hash,
+            leaf_index: leaf.position,
+            path,
+        },
+        checkpoint,
+    )))
+}
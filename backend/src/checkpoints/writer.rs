@@ -0,0 +1,264 @@
+// Periodic receipt checkpoints
+// Each pointer's chain shows it was not edited in place, but not that the
+// receipt set as a whole was not rewritten. Every interval, the governance
+// receipts written since the previous checkpoint are committed to one Merkle
+// root, signed with the service key and chained to that checkpoint, so
+// rewriting any covered receipt contradicts a root published earlier.
+//
+// A receipt's timestamp is taken at insert but it is only visible once its
+// transaction commits. Checkpoints stop short of now by a settle delay, and
+// each run also looks back before the previous checkpoint for receipts that
+// committed late; those land in the next checkpoint. Every receipt is a leaf
+// of exactly one checkpoint.
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    clock::Clock,
+    config::Config,
+    crypto::{merkle::MerkleTree, CheckpointReceipt, Ed25519Keypair, SigningKeyring},
+    db::{models::ReceiptCheckpoint, queries},
+};
+
+/// How long a receipt may be in flight between insert and commit before a
+/// checkpoint counts on seeing it
+const CHECKPOINT_SETTLE_SECONDS: i64 = 5;
+/// How far before the previous checkpoint's end to look for receipts that
+/// committed after it was written
+const LATE_RECEIPT_LOOKBACK_SECONDS: i64 = 3600;
+/// Most receipts one checkpoint covers; a backlog is split across several
+const MAX_CHECKPOINT_RECEIPTS: i64 = 50_000;
+
+/// Checkpoint every receipt written before `now`, less the settle delay,
+/// that no checkpoint covers yet. Writes nothing when there are none.
+pub async fn write_checkpoints(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    now: DateTime<Utc>,
+) -> Result<Vec<ReceiptCheckpoint>> {
+    let until = now - Duration::seconds(CHECKPOINT_SETTLE_SECONDS);
+    let mut written = Vec::new();
+
+    loop {
+        let Some(checkpoint) = write_checkpoint(pool, keypair, until, now).await? else {
+            return Ok(written);
+        };
+        let full = i64::from(checkpoint.receipt_count) == MAX_CHECKPOINT_RECEIPTS;
+        written.push(checkpoint);
+        if !full {
+            return Ok(written);
+        }
+    }
+}
+
+async fn write_checkpoint(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    until: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<Option<ReceiptCheckpoint>> {
+    let mut tx = pool.begin().await?;
+    queries::lock_receipt_checkpoints(&mut tx).await?;
+
+    let latest = queries::get_latest_checkpoint(&mut *tx).await?;
+    let since = latest
+        .as_ref()
+        .map(|c| c.period_end - Duration::seconds(LATE_RECEIPT_LOOKBACK_SECONDS));
+    let receipts =
+        queries::get_uncheckpointed_receipts(&mut *tx, since, until, MAX_CHECKPOINT_RECEIPTS)
+            .await?;
+    let Some(tree) = MerkleTree::new(
+        &receipts
+            .iter()
+            .map(|(_, receipt_hash, _)| receipt_hash)
+            .collect::<Vec<_>>(),
+    ) else {
+        return Ok(None);
+    };
+
+    // A full batch ends at its last receipt; the rest follow in the next
+    let mut period_end = if receipts.len() as i64 == MAX_CHECKPOINT_RECEIPTS {
+        receipts[receipts.len() - 1].2
+    } else {
+        until
+    };
+    let period_start = latest.as_ref().map(|c| c.period_end);
+    if let Some(start) = period_start {
+        period_end = period_end.max(start);
+    }
+
+    let receipt = CheckpointReceipt {
+        checkpoint_id: Uuid::new_v4(),
+        sequence: latest.as_ref().map_or(1, |c| c.sequence + 1),
+        period_start,
+        period_end,
+        receipt_count: receipts.len() as i32,
+        merkle_root: tree.root(),
+        prev_hash: latest.map(|c| c.receipt_hash),
+        timestamp: now,
+    };
+    let signed = receipt.sign(keypair)?;
+
+    let checkpoint = queries::create_receipt_checkpoint(
+        &mut *tx,
+        receipt.checkpoint_id,
+        receipt.sequence,
+        (receipt.period_start, receipt.period_end),
+        receipt.receipt_count,
+        &receipt.merkle_root,
+        signed.receipt_json,
+        &signed.receipt_hash,
+        &signed.signature,
+        &signed.signature_algorithm,
+        &signed.key_id,
+        receipt.prev_hash.as_deref(),
+    )
+    .await?;
+    let (receipt_ids, receipt_hashes): (Vec<Uuid>, Vec<String>) = receipts
+        .into_iter()
+        .map(|(receipt_id, receipt_hash, _)| (receipt_id, receipt_hash))
+        .unzip();
+    queries::create_checkpoint_leaves(
+        &mut *tx,
+        checkpoint.checkpoint_id,
+        &receipt_ids,
+        &receipt_hashes,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(checkpoint))
+}
+
+/// Background loop: checkpoint new receipts every configured interval
+pub async fn run_checkpoint_writer(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.receipt_checkpoint_interval_minutes.max(1) * 60,
+    ));
+
+    info!(
+        "Receipt checkpoints every {}m",
+        config.receipt_checkpoint_interval_minutes
+    );
+
+    loop {
+        interval.tick().await;
+
+        match write_checkpoints(&pool, &signing_keys.active(), clock.now()).await {
+            Ok(written) => {
+                for checkpoint in written {
+                    info!(
+                        "Checkpoint {} covers {} receipts (root {})",
+                        checkpoint.sequence, checkpoint.receipt_count, checkpoint.merkle_root
+                    );
+                }
+            }
+            Err(e) => error!("Receipt checkpoint failed: {:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        checkpoints::inclusion_proof,
+        crypto::{hashing::sha3_512_hash_str, merkle::verify_inclusion, verify_receipt},
+        test_support::{checkpoint_keypair, seed_pointer, test_state, unique_subject},
+    };
+
+    #[tokio::test]
+    async fn test_checkpoint_commits_receipts_to_signed_root() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pool = &state.db.writer;
+        let keypair = checkpoint_keypair();
+        let pointer_id = seed_pointer(&state, &unique_subject("checkpoint")).await;
+        let created = queries::get_receipts_by_pointer(pool, pointer_id)
+            .await
+            .unwrap()
+            .remove(0);
+
+        let now = Utc::now() + Duration::seconds(CHECKPOINT_SETTLE_SECONDS);
+        write_checkpoints(pool, &keypair, now).await.unwrap();
+        let (proof, checkpoint) = inclusion_proof(pool, created.receipt_id)
+            .await
+            .unwrap()
+            .expect("receipt not checkpointed");
+        assert_eq!(proof.receipt_hash, created.receipt_hash);
+        assert!(verify_inclusion(
+            &proof.receipt_hash,
+            &proof.path,
+            &checkpoint.merkle_root
+        ));
+
+        // The signed checkpoint commits to the root
+        assert_eq!(
+            checkpoint.receipt_json["merkle_root"],
+            checkpoint.merkle_root
+        );
+        assert_eq!(checkpoint.receipt_json["key_id"], keypair.key_id());
+        assert!(verify_receipt(
+            &checkpoint.receipt_json,
+            &checkpoint.receipt_hash,
+            &checkpoint.signature_algorithm,
+            &checkpoint.signature,
+            &keypair.verifying_key,
+        )
+        .valid());
+
+        // Chained to the checkpoint before, its period starting where that
+        // one's ended
+        let prev = sqlx::query_as::<_, ReceiptCheckpoint>(
+            "SELECT * FROM receipt_checkpoints WHERE sequence = $1",
+        )
+        .bind(checkpoint.sequence - 1)
+        .fetch_optional(pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            checkpoint.prev_hash,
+            prev.as_ref().map(|p| p.receipt_hash.clone())
+        );
+        assert_eq!(checkpoint.period_start, prev.map(|p| p.period_end));
+
+        // A receipt committed after the checkpoint its timestamp falls in
+        // lands in the next one; covered receipts stay where they are
+        let latest = queries::get_latest_checkpoint(pool).await.unwrap().unwrap();
+        let late: Uuid = sqlx::query_scalar(
+            "INSERT INTO governance_receipts \
+                 (pointer_id, org_id, operation, receipt_json, receipt_hash, signature, timestamp) \
+             VALUES ($1, $2, 'resolve', '{}', $3, '\\x00', $4) \
+             RETURNING receipt_id",
+        )
+        .bind(pointer_id)
+        .bind(created.org_id)
+        .bind(sha3_512_hash_str(&format!("late:{}", pointer_id)))
+        .bind(latest.period_end - Duration::seconds(30))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        write_checkpoints(pool, &keypair, now + Duration::seconds(1))
+            .await
+            .unwrap();
+        let (_, late_checkpoint) = inclusion_proof(pool, late).await.unwrap().unwrap();
+        assert!(late_checkpoint.sequence > latest.sequence);
+        let (_, again) = inclusion_proof(pool, created.receipt_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(again.checkpoint_id, checkpoint.checkpoint_id);
+    }
+}
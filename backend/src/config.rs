@@ -1,16 +1,80 @@
 // Configuration management
-use anyhow::{Context, Result};
-use serde::Deserialize;
+use anyhow::{ensure, Context, Result};
+use std::{net::SocketAddr, time::Duration};
+
+use crate::{
+    api::cors::CorsOrigins, gc::DataGcMode, retention::RetentionClasses, secret::Secret,
+    subjects::MAX_SUBJECT_BYTES,
+};
 
 #[derive(Debug, Clone)]
 pub struct Config {
-    pub database_url: String,
+    pub database_url: Secret<String>,
+    /// Replica for read-only queries; None sends them to DATABASE_URL
+    pub database_read_url: Option<Secret<String>>,
+    pub database_pool: DbPoolConfig,
+    pub background_pool_size: u32,
+    pub run_migrations: bool,
     pub host: String,
     pub port: u16,
     pub default_org_id: uuid::Uuid,
-    pub cors_allowed_origins: Vec<String>,
-    pub signing_private_key: Option<String>,
+    pub cors_allowed_origins: CorsOrigins,
+    pub signing_private_key: Option<Secret<String>>,
     pub signing_public_key: Option<String>,
+    pub signing_key_path: Option<String>,
+    pub signing_master_key: Option<Secret<String>>,
+    pub payload_encryption_key: Option<Secret<String>>,
+    pub resolve_stats_interval_seconds: u64,
+    pub resolve_spike_ratio: f64,
+    pub resolve_spike_min_count: i64,
+    pub resolve_spike_baseline_hours: i32,
+    pub compression_min_bytes: u16,
+    pub subject_lowercase: bool,
+    pub subject_email_rules: bool,
+    pub subject_digest_threshold: usize,
+    pub subject_max_bytes: usize,
+    pub max_payload_bytes: usize,
+    pub restriction_sweep_interval_seconds: u64,
+    pub consistency_audit_interval_seconds: u64,
+    pub consistency_sample_size: i64,
+    pub consistency_auto_repair: bool,
+    pub resolve_coalesce_max_staleness_ms: u64,
+    pub pointer_cache_ttl_ms: u64,
+    pub pointer_cache_max_entries: usize,
+    pub heartbeat_check_interval_seconds: u64,
+    pub strict_receipt_reads: bool,
+    pub veto_slo_target_ms: u32,
+    pub data_gc_interval_seconds: u64,
+    pub data_gc_grace_seconds: u64,
+    pub data_gc_mode: DataGcMode,
+    pub data_gc_dry_run: bool,
+    pub degraded_read_max_staleness_seconds: i64,
+    pub db_circuit_failure_threshold: u32,
+    pub db_circuit_open_seconds: u64,
+    pub retention_classes: RetentionClasses,
+    pub retention_sweep_interval_seconds: u64,
+    pub pending_orphan_sweep_interval_seconds: u64,
+    pub orphan_request_ttl_seconds: i64,
+    pub allow_reinstatement: bool,
+    pub payload_purge_after_days: u64,
+    pub payload_purge_interval_seconds: u64,
+    pub webhook_max_attempts: i32,
+    pub webhook_retry_base_seconds: u64,
+    pub webhook_timeout_ms: u64,
+    pub webhook_poll_interval_seconds: u64,
+    pub receipt_metrics_enabled: bool,
+    pub slow_receipt_threshold_ms: u64,
+    pub receipt_max_bytes: usize,
+    pub trusted_proxy: bool,
+    pub api_key_rotation_overlap_seconds: i64,
+    pub api_key_sweep_interval_seconds: u64,
+    pub health_db_timeout_ms: u64,
+    pub idempotency_key_ttl_seconds: i64,
+    pub receipt_checkpoint_interval_minutes: u64,
+    pub rate_limit_reads_per_minute: u32,
+    pub rate_limit_writes_per_minute: u32,
+    pub rate_limit_audit: bool,
+    pub serve_api_docs: bool,
 }
 
 impl Config {
@@ -18,10 +82,23 @@ impl Config {
         dotenvy::dotenv().ok(); // Load .env file if present
 
         let database_url = std::env::var("DATABASE_URL")
+            .map(Secret::new)
             .context("DATABASE_URL must be set")?;
+        let database_read_url = std::env::var("DATABASE_READ_URL").ok().map(Secret::new);
+
+        let database_pool = DbPoolConfig::from_vars(|name| std::env::var(name).ok())?;
+
+        let background_pool_size = std::env::var("BACKGROUND_POOL_SIZE")
+            .unwrap_or_else(|_| "2".to_string())
+            .parse()
+            .context("BACKGROUND_POOL_SIZE must be a valid u32")?;
+
+        let run_migrations = std::env::var("RUN_MIGRATIONS")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .context("RUN_MIGRATIONS must be true or false")?;
 
-        let host = std::env::var("HOST")
-            .unwrap_or_else(|_| "127.0.0.1".to_string());
+        let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
 
         let port = std::env::var("PORT")
             .unwrap_or_else(|_| "8888".to_string())
@@ -35,21 +112,587 @@ impl Config {
 
         let cors_allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS")
             .unwrap_or_else(|_| "http://localhost:3000".to_string())
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .collect();
+            .parse()
+            .context("CORS_ALLOWED_ORIGINS must be comma-separated origins, or *")?;
 
-        let signing_private_key = std::env::var("SIGNING_PRIVATE_KEY").ok();
+        let signing_private_key = std::env::var("SIGNING_PRIVATE_KEY").ok().map(Secret::new);
         let signing_public_key = std::env::var("SIGNING_PUBLIC_KEY").ok();
+        let signing_key_path = std::env::var("SIGNING_KEY_PATH").ok();
+        let signing_master_key = std::env::var("SIGNING_MASTER_KEY").ok().map(Secret::new);
+        let payload_encryption_key = std::env::var("PAYLOAD_ENCRYPTION_KEY")
+            .ok()
+            .map(Secret::new);
+
+        let resolve_stats_interval_seconds = std::env::var("RESOLVE_STATS_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .context("RESOLVE_STATS_INTERVAL_SECONDS must be a valid u64")?;
+
+        let resolve_spike_ratio = std::env::var("RESOLVE_SPIKE_RATIO")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .context("RESOLVE_SPIKE_RATIO must be a number")?;
+
+        let resolve_spike_min_count = std::env::var("RESOLVE_SPIKE_MIN_COUNT")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .context("RESOLVE_SPIKE_MIN_COUNT must be a valid i64")?;
+
+        let resolve_spike_baseline_hours = std::env::var("RESOLVE_SPIKE_BASELINE_HOURS")
+            .unwrap_or_else(|_| "24".to_string())
+            .parse()
+            .context("RESOLVE_SPIKE_BASELINE_HOURS must be a valid i32")?;
+
+        let compression_min_bytes = std::env::var("COMPRESSION_MIN_BYTES")
+            .unwrap_or_else(|_| "1024".to_string())
+            .parse()
+            .context("COMPRESSION_MIN_BYTES must be a valid u16")?;
+
+        let subject_lowercase = std::env::var("SUBJECT_NORMALIZE_LOWERCASE")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("SUBJECT_NORMALIZE_LOWERCASE must be true or false")?;
+
+        let subject_email_rules = std::env::var("SUBJECT_NORMALIZE_EMAIL")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .context("SUBJECT_NORMALIZE_EMAIL must be true or false")?;
+
+        let subject_digest_threshold = std::env::var("SUBJECT_DIGEST_THRESHOLD_BYTES")
+            .unwrap_or_else(|_| "255".to_string())
+            .parse()
+            .context("SUBJECT_DIGEST_THRESHOLD_BYTES must be a valid usize")?;
+
+        let subject_max_bytes: usize = std::env::var("SUBJECT_ID_MAX_BYTES")
+            .unwrap_or_else(|_| "256".to_string())
+            .parse()
+            .context("SUBJECT_ID_MAX_BYTES must be a valid usize")?;
+        ensure!(
+            (1..=MAX_SUBJECT_BYTES).contains(&subject_max_bytes),
+            "SUBJECT_ID_MAX_BYTES must be from 1 to {}",
+            MAX_SUBJECT_BYTES
+        );
+        check_subject_digest_threshold(subject_digest_threshold, subject_max_bytes)?;
+
+        let max_payload_bytes = std::env::var("MAX_PAYLOAD_BYTES")
+            .unwrap_or_else(|_| "1048576".to_string())
+            .parse()
+            .context("MAX_PAYLOAD_BYTES must be a valid usize")?;
+
+        let restriction_sweep_interval_seconds =
+            std::env::var("RESTRICTION_SWEEP_INTERVAL_SECONDS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("RESTRICTION_SWEEP_INTERVAL_SECONDS must be a valid u64")?;
+
+        let consistency_audit_interval_seconds =
+            std::env::var("CONSISTENCY_AUDIT_INTERVAL_SECONDS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .context("CONSISTENCY_AUDIT_INTERVAL_SECONDS must be a valid u64")?;
+
+        let consistency_sample_size = std::env::var("CONSISTENCY_SAMPLE_SIZE")
+            .unwrap_or_else(|_| "100".to_string())
+            .parse()
+            .context("CONSISTENCY_SAMPLE_SIZE must be a valid i64")?;
+
+        let consistency_auto_repair = std::env::var("CONSISTENCY_AUTO_REPAIR")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("CONSISTENCY_AUTO_REPAIR must be true or false")?;
+
+        let resolve_coalesce_max_staleness_ms = std::env::var("RESOLVE_COALESCE_MAX_STALENESS_MS")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .context("RESOLVE_COALESCE_MAX_STALENESS_MS must be a valid u64")?;
+
+        let pointer_cache_ttl_ms = std::env::var("POINTER_CACHE_TTL_MS")
+            .unwrap_or_else(|_| "500".to_string())
+            .parse()
+            .context("POINTER_CACHE_TTL_MS must be a valid u64")?;
+
+        let pointer_cache_max_entries = std::env::var("POINTER_CACHE_MAX_ENTRIES")
+            .unwrap_or_else(|_| "10000".to_string())
+            .parse()
+            .context("POINTER_CACHE_MAX_ENTRIES must be a valid usize")?;
+
+        let heartbeat_check_interval_seconds = std::env::var("HEARTBEAT_CHECK_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .context("HEARTBEAT_CHECK_INTERVAL_SECONDS must be a valid u64")?;
+
+        let strict_receipt_reads = std::env::var("STRICT_RECEIPT_READS")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("STRICT_RECEIPT_READS must be true or false")?;
+
+        let veto_slo_target_ms = std::env::var("VETO_SLO_TARGET_MS")
+            .unwrap_or_else(|_| "1000".to_string())
+            .parse()
+            .context("VETO_SLO_TARGET_MS must be a valid u32")?;
+
+        let data_gc_interval_seconds = std::env::var("DATA_GC_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()
+            .context("DATA_GC_INTERVAL_SECONDS must be a valid u64")?;
+
+        let data_gc_grace_seconds = std::env::var("DATA_GC_GRACE_SECONDS")
+            .unwrap_or_else(|_| "604800".to_string())
+            .parse()
+            .context("DATA_GC_GRACE_SECONDS must be a valid u64")?;
+
+        let data_gc_mode = std::env::var("DATA_GC_MODE")
+            .unwrap_or_else(|_| "null_payload".to_string())
+            .parse()
+            .context("DATA_GC_MODE must be delete or null_payload")?;
+
+        let data_gc_dry_run = std::env::var("DATA_GC_DRY_RUN")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("DATA_GC_DRY_RUN must be true or false")?;
+
+        let degraded_read_max_staleness_seconds =
+            std::env::var("DEGRADED_READ_MAX_STALENESS_SECONDS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("DEGRADED_READ_MAX_STALENESS_SECONDS must be a valid i64")?;
+
+        let db_circuit_failure_threshold = std::env::var("DB_CIRCUIT_FAILURE_THRESHOLD")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()
+            .context("DB_CIRCUIT_FAILURE_THRESHOLD must be a valid u32")?;
+
+        let db_circuit_open_seconds = std::env::var("DB_CIRCUIT_OPEN_SECONDS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .context("DB_CIRCUIT_OPEN_SECONDS must be a valid u64")?;
+
+        let retention_classes = std::env::var("RETENTION_CLASSES")
+            .unwrap_or_default()
+            .parse()
+            .context("RETENTION_CLASSES must be a list of class=max_age_seconds")?;
+
+        let retention_sweep_interval_seconds = std::env::var("RETENTION_SWEEP_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .context("RETENTION_SWEEP_INTERVAL_SECONDS must be a valid u64")?;
+
+        let pending_orphan_sweep_interval_seconds =
+            std::env::var("PENDING_ORPHAN_SWEEP_INTERVAL_SECONDS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("PENDING_ORPHAN_SWEEP_INTERVAL_SECONDS must be a valid u64")?;
+
+        let orphan_request_ttl_seconds = std::env::var("ORPHAN_REQUEST_TTL_SECONDS")
+            .unwrap_or_else(|_| "86400".to_string())
+            .parse()
+            .context("ORPHAN_REQUEST_TTL_SECONDS must be a valid i64")?;
+        ensure!(
+            orphan_request_ttl_seconds > 0,
+            "ORPHAN_REQUEST_TTL_SECONDS must be at least 1"
+        );
+
+        let allow_reinstatement = std::env::var("ALLOW_REINSTATEMENT")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("ALLOW_REINSTATEMENT must be true or false")?;
+
+        let payload_purge_after_days = std::env::var("PAYLOAD_PURGE_AFTER_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .context("PAYLOAD_PURGE_AFTER_DAYS must be a valid u64")?;
+
+        let payload_purge_interval_seconds = std::env::var("PAYLOAD_PURGE_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()
+            .context("PAYLOAD_PURGE_INTERVAL_SECONDS must be a valid u64")?;
+
+        let webhook_max_attempts = std::env::var("WEBHOOK_MAX_ATTEMPTS")
+            .unwrap_or_else(|_| "8".to_string())
+            .parse()
+            .context("WEBHOOK_MAX_ATTEMPTS must be a valid i32")?;
+
+        let webhook_retry_base_seconds = std::env::var("WEBHOOK_RETRY_BASE_SECONDS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .context("WEBHOOK_RETRY_BASE_SECONDS must be a valid u64")?;
+
+        let webhook_timeout_ms = std::env::var("WEBHOOK_TIMEOUT_MS")
+            .unwrap_or_else(|_| "5000".to_string())
+            .parse()
+            .context("WEBHOOK_TIMEOUT_MS must be a valid u64")?;
+
+        let webhook_poll_interval_seconds = std::env::var("WEBHOOK_POLL_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .context("WEBHOOK_POLL_INTERVAL_SECONDS must be a valid u64")?;
+
+        let receipt_metrics_enabled = std::env::var("RECEIPT_METRICS_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("RECEIPT_METRICS_ENABLED must be true or false")?;
+
+        let slow_receipt_threshold_ms = std::env::var("SLOW_RECEIPT_THRESHOLD_MS")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .context("SLOW_RECEIPT_THRESHOLD_MS must be a valid u64")?;
+
+        let receipt_max_bytes = std::env::var("RECEIPT_MAX_BYTES")
+            .unwrap_or_else(|_| "65536".to_string())
+            .parse()
+            .context("RECEIPT_MAX_BYTES must be a valid usize")?;
+
+        let trusted_proxy = std::env::var("TRUSTED_PROXY")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("TRUSTED_PROXY must be true or false")?;
+
+        let api_key_rotation_overlap_seconds = std::env::var("API_KEY_ROTATION_OVERLAP_SECONDS")
+            .unwrap_or_else(|_| "86400".to_string())
+            .parse()
+            .context("API_KEY_ROTATION_OVERLAP_SECONDS must be a valid i64")?;
+
+        let api_key_sweep_interval_seconds = std::env::var("API_KEY_SWEEP_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()
+            .context("API_KEY_SWEEP_INTERVAL_SECONDS must be a valid u64")?;
+
+        let health_db_timeout_ms = std::env::var("HEALTH_DB_TIMEOUT_MS")
+            .unwrap_or_else(|_| "1000".to_string())
+            .parse()
+            .context("HEALTH_DB_TIMEOUT_MS must be a valid u64")?;
+
+        let idempotency_key_ttl_seconds = std::env::var("IDEMPOTENCY_KEY_TTL_SECONDS")
+            .unwrap_or_else(|_| "86400".to_string())
+            .parse()
+            .context("IDEMPOTENCY_KEY_TTL_SECONDS must be a valid i64")?;
+
+        let receipt_checkpoint_interval_minutes =
+            std::env::var("RECEIPT_CHECKPOINT_INTERVAL_MINUTES")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .context("RECEIPT_CHECKPOINT_INTERVAL_MINUTES must be a valid u64")?;
+
+        let rate_limit_reads_per_minute = std::env::var("RATE_LIMIT_READS_PER_MINUTE")
+            .unwrap_or_else(|_| "6000".to_string())
+            .parse()
+            .context("RATE_LIMIT_READS_PER_MINUTE must be a valid u32")?;
+
+        let rate_limit_writes_per_minute = std::env::var("RATE_LIMIT_WRITES_PER_MINUTE")
+            .unwrap_or_else(|_| "600".to_string())
+            .parse()
+            .context("RATE_LIMIT_WRITES_PER_MINUTE must be a valid u32")?;
+
+        let rate_limit_audit = std::env::var("RATE_LIMIT_AUDIT")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("RATE_LIMIT_AUDIT must be true or false")?;
+
+        let serve_api_docs = std::env::var("SERVE_API_DOCS")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("SERVE_API_DOCS must be true or false")?;
 
         Ok(Config {
             database_url,
+            database_read_url,
+            database_pool,
+            background_pool_size,
+            run_migrations,
             host,
             port,
             default_org_id,
             cors_allowed_origins,
             signing_private_key,
             signing_public_key,
+            signing_key_path,
+            signing_master_key,
+            payload_encryption_key,
+            resolve_stats_interval_seconds,
+            resolve_spike_ratio,
+            resolve_spike_min_count,
+            resolve_spike_baseline_hours,
+            compression_min_bytes,
+            subject_lowercase,
+            subject_email_rules,
+            subject_digest_threshold,
+            subject_max_bytes,
+            max_payload_bytes,
+            restriction_sweep_interval_seconds,
+            consistency_audit_interval_seconds,
+            consistency_sample_size,
+            consistency_auto_repair,
+            resolve_coalesce_max_staleness_ms,
+            pointer_cache_ttl_ms,
+            pointer_cache_max_entries,
+            heartbeat_check_interval_seconds,
+            strict_receipt_reads,
+            veto_slo_target_ms,
+            data_gc_interval_seconds,
+            data_gc_grace_seconds,
+            data_gc_mode,
+            data_gc_dry_run,
+            degraded_read_max_staleness_seconds,
+            db_circuit_failure_threshold,
+            db_circuit_open_seconds,
+            retention_classes,
+            retention_sweep_interval_seconds,
+            pending_orphan_sweep_interval_seconds,
+            orphan_request_ttl_seconds,
+            allow_reinstatement,
+            payload_purge_after_days,
+            payload_purge_interval_seconds,
+            webhook_max_attempts,
+            webhook_retry_base_seconds,
+            webhook_timeout_ms,
+            webhook_poll_interval_seconds,
+            receipt_metrics_enabled,
+            slow_receipt_threshold_ms,
+            receipt_max_bytes,
+            trusted_proxy,
+            api_key_rotation_overlap_seconds,
+            api_key_sweep_interval_seconds,
+            health_db_timeout_ms,
+            idempotency_key_ttl_seconds,
+            receipt_checkpoint_interval_minutes,
+            rate_limit_reads_per_minute,
+            rate_limit_writes_per_minute,
+            rate_limit_audit,
+            serve_api_docs,
         })
     }
+
+    /// Address to listen on. HOST may be an IPv4 or IPv6 literal, with or
+    /// without brackets, or a hostname, which must resolve.
+    pub async fn listen_addr(&self) -> Result<SocketAddr> {
+        let host = self.host.trim_start_matches('[').trim_end_matches(']');
+        let invalid = || {
+            format!(
+                "HOST '{}' is not an IP address or resolvable hostname",
+                self.host
+            )
+        };
+
+        tokio::net::lookup_host((host, self.port))
+            .await
+            .with_context(invalid)?
+            .next()
+            .with_context(invalid)
+    }
+}
+
+/// Ids at or under the digest threshold are stored as sent, so the threshold
+/// can't leave room for ids too long to be accepted
+fn check_subject_digest_threshold(threshold: usize, max_bytes: usize) -> Result<()> {
+    ensure!(
+        threshold <= MAX_SUBJECT_BYTES,
+        "SUBJECT_DIGEST_THRESHOLD_BYTES must be at most {}",
+        MAX_SUBJECT_BYTES
+    );
+    ensure!(
+        threshold <= max_bytes,
+        "SUBJECT_DIGEST_THRESHOLD_BYTES must not exceed SUBJECT_ID_MAX_BYTES ({})",
+        max_bytes
+    );
+    Ok(())
+}
+
+/// Connection pool sizing and startup behavior. DB_MAX_CONNECTIONS sizes the
+/// interactive pool; the other pools take the rest of the settings as-is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbPoolConfig {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: Duration,
+    /// Further connect attempts at startup after the first one fails
+    pub connect_retries: u32,
+    /// Wait before the first retry, doubling for each one after
+    pub connect_retry_delay: Duration,
+}
+
+impl DbPoolConfig {
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        // DATABASE_POOL_SIZE is the older name for DB_MAX_CONNECTIONS
+        let max_connections = var("DB_MAX_CONNECTIONS")
+            .or_else(|| var("DATABASE_POOL_SIZE"))
+            .unwrap_or_else(|| "10".to_string())
+            .parse()
+            .context("DB_MAX_CONNECTIONS must be a valid u32")?;
+
+        let min_connections = var("DB_MIN_CONNECTIONS")
+            .unwrap_or_else(|| "0".to_string())
+            .parse()
+            .context("DB_MIN_CONNECTIONS must be a valid u32")?;
+
+        let acquire_timeout_seconds = var("DB_ACQUIRE_TIMEOUT_SECONDS")
+            .unwrap_or_else(|| "30".to_string())
+            .parse()
+            .context("DB_ACQUIRE_TIMEOUT_SECONDS must be a valid u64")?;
+
+        let connect_retries = var("DB_CONNECT_RETRIES")
+            .unwrap_or_else(|| "5".to_string())
+            .parse()
+            .context("DB_CONNECT_RETRIES must be a valid u32")?;
+
+        let connect_retry_delay_ms = var("DB_CONNECT_RETRY_DELAY_MS")
+            .unwrap_or_else(|| "500".to_string())
+            .parse()
+            .context("DB_CONNECT_RETRY_DELAY_MS must be a valid u64")?;
+
+        ensure!(max_connections > 0, "DB_MAX_CONNECTIONS must be at least 1");
+        ensure!(
+            min_connections <= max_connections,
+            "DB_MIN_CONNECTIONS ({}) must not exceed DB_MAX_CONNECTIONS ({})",
+            min_connections,
+            max_connections
+        );
+        ensure!(
+            acquire_timeout_seconds > 0,
+            "DB_ACQUIRE_TIMEOUT_SECONDS must be at least 1"
+        );
+
+        Ok(DbPoolConfig {
+            max_connections,
+            min_connections,
+            acquire_timeout: Duration::from_secs(acquire_timeout_seconds),
+            connect_retries,
+            connect_retry_delay: Duration::from_millis(connect_retry_delay_ms),
+        })
+    }
+
+    /// These settings for a pool of `max_connections`, keeping fewer idle
+    /// connections than that if need be
+    pub fn with_max_connections(&self, max_connections: u32) -> Self {
+        DbPoolConfig {
+            max_connections,
+            min_connections: self.min_connections.min(max_connections),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_subject_digest_threshold, DbPoolConfig};
+    use crate::test_support::test_config;
+    use std::{collections::HashMap, net::SocketAddr, time::Duration};
+
+    async fn listen_addr(host: &str) -> anyhow::Result<SocketAddr> {
+        let mut config = test_config("");
+        config.host = host.to_string();
+        config.port = 8888;
+        config.listen_addr().await
+    }
+
+    #[tokio::test]
+    async fn test_listen_addr_from_host() {
+        let v4: SocketAddr = "0.0.0.0:8888".parse().unwrap();
+        let v6: SocketAddr = "[::1]:8888".parse().unwrap();
+        assert_eq!(listen_addr("0.0.0.0").await.unwrap(), v4);
+        assert_eq!(listen_addr("::1").await.unwrap(), v6);
+        assert_eq!(listen_addr("[::1]").await.unwrap(), v6);
+        assert!(listen_addr("localhost").await.unwrap().ip().is_loopback());
+
+        let error = listen_addr("not a host").await.unwrap_err();
+        assert!(error.to_string().contains("HOST 'not a host'"));
+    }
+
+    fn pool_config(vars: &[(&str, &str)]) -> anyhow::Result<DbPoolConfig> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        DbPoolConfig::from_vars(|name| vars.get(name).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn test_pool_config_defaults_and_overrides() {
+        assert_eq!(
+            pool_config(&[]).unwrap(),
+            DbPoolConfig {
+                max_connections: 10,
+                min_connections: 0,
+                acquire_timeout: Duration::from_secs(30),
+                connect_retries: 5,
+                connect_retry_delay: Duration::from_millis(500),
+            }
+        );
+
+        let config = pool_config(&[
+            ("DB_MAX_CONNECTIONS", "20"),
+            ("DB_MIN_CONNECTIONS", "4"),
+            ("DB_ACQUIRE_TIMEOUT_SECONDS", "3"),
+            ("DB_CONNECT_RETRIES", "0"),
+            ("DB_CONNECT_RETRY_DELAY_MS", "250"),
+        ])
+        .unwrap();
+        assert_eq!(config.max_connections, 20);
+        assert_eq!(config.min_connections, 4);
+        assert_eq!(config.acquire_timeout, Duration::from_secs(3));
+        assert_eq!(config.connect_retries, 0);
+        assert_eq!(config.connect_retry_delay, Duration::from_millis(250));
+
+        // The new name wins over the old one
+        let legacy = pool_config(&[("DATABASE_POOL_SIZE", "7")]).unwrap();
+        assert_eq!(legacy.max_connections, 7);
+        let both = pool_config(&[("DATABASE_POOL_SIZE", "7"), ("DB_MAX_CONNECTIONS", "9")]);
+        assert_eq!(both.unwrap().max_connections, 9);
+
+        let background = config.with_max_connections(2);
+        assert_eq!(background.max_connections, 2);
+        assert_eq!(background.min_connections, 2);
+        assert_eq!(background.acquire_timeout, config.acquire_timeout);
+    }
+
+    #[test]
+    fn test_pool_config_rejects_invalid_values() {
+        for (vars, message) in [
+            (
+                vec![("DB_MAX_CONNECTIONS", "ten")],
+                "DB_MAX_CONNECTIONS must be a valid u32",
+            ),
+            (
+                vec![("DB_MAX_CONNECTIONS", "0")],
+                "DB_MAX_CONNECTIONS must be at least 1",
+            ),
+            (
+                vec![("DB_MIN_CONNECTIONS", "-1")],
+                "DB_MIN_CONNECTIONS must be a valid u32",
+            ),
+            (
+                vec![("DB_MAX_CONNECTIONS", "4"), ("DB_MIN_CONNECTIONS", "5")],
+                "DB_MIN_CONNECTIONS (5) must not exceed DB_MAX_CONNECTIONS (4)",
+            ),
+            (
+                vec![("DB_ACQUIRE_TIMEOUT_SECONDS", "0")],
+                "DB_ACQUIRE_TIMEOUT_SECONDS must be at least 1",
+            ),
+            (
+                vec![("DB_ACQUIRE_TIMEOUT_SECONDS", "1.5")],
+                "DB_ACQUIRE_TIMEOUT_SECONDS must be a valid u64",
+            ),
+            (
+                vec![("DB_CONNECT_RETRIES", "many")],
+                "DB_CONNECT_RETRIES must be a valid u32",
+            ),
+            (
+                vec![("DB_CONNECT_RETRY_DELAY_MS", "")],
+                "DB_CONNECT_RETRY_DELAY_MS must be a valid u64",
+            ),
+        ] {
+            let error = pool_config(&vars).unwrap_err();
+            assert_eq!(error.to_string(), message, "{:?}", vars);
+        }
+    }
+
+    #[test]
+    fn test_subject_digest_threshold_bounds() {
+        assert!(check_subject_digest_threshold(255, 256).is_ok());
+        assert!(check_subject_digest_threshold(0, 1).is_ok());
+        assert!(check_subject_digest_threshold(8192, 8192).is_ok());
+
+        let error = check_subject_digest_threshold(8193, 8192).unwrap_err();
+        assert!(error.to_string().contains("at most 8192"));
+        let error = check_subject_digest_threshold(1024, 256).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("must not exceed SUBJECT_ID_MAX_BYTES (256)"));
+    }
 }
@@ -1,11 +1,15 @@
 // ED25519 signature generation and verification
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use data_encoding::{BASE64, HEXLOWER, HEXLOWER_PERMISSIVE};
 use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
 use rand::rngs::OsRng;
+use sha3::{Digest, Sha3_256};
+use std::{io::Write, path::Path};
 use tracing::{info, warn};
 
 use crate::config::Config;
 
+#[derive(Clone)]
 pub struct Ed25519Keypair {
     pub signing_key: SigningKey,
     pub verifying_key: VerifyingKey,
@@ -22,6 +26,17 @@ impl Ed25519Keypair {
         }
     }
 
+    /// Deterministic keypair from a 32-byte secret seed
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let signing_key = SigningKey::from_bytes(seed);
+        let verifying_key = signing_key.verifying_key();
+
+        Ed25519Keypair {
+            signing_key,
+            verifying_key,
+        }
+    }
+
     pub fn sign(&self, message: &[u8]) -> Signature {
         self.signing_key.sign(message)
     }
@@ -34,45 +49,217 @@ impl Ed25519Keypair {
     pub fn public_key_bytes(&self) -> [u8; 32] {
         self.verifying_key.to_bytes()
     }
+
+    /// Stable id for the public key: SHA3-256 of its bytes, hex encoded.
+    /// Lets receipts be matched to keys once more than one is in play.
+    pub fn key_id(&self) -> String {
+        key_id(&self.verifying_key)
+    }
+}
+
+pub fn key_id(verifying_key: &VerifyingKey) -> String {
+    HEXLOWER.encode(&Sha3_256::digest(verifying_key.as_bytes()))
+}
+
+/// Decode a 32-byte key given as base64 or hex (64 hex digits)
+pub fn decode_key_bytes(name: &str, encoded: &str) -> Result<[u8; 32]> {
+    let encoded = encoded.trim();
+    let bytes = if encoded.len() == 64 && encoded.chars().all(|c| c.is_ascii_hexdigit()) {
+        HEXLOWER_PERMISSIVE.decode(encoded.as_bytes())
+    } else {
+        BASE64.decode(encoded.as_bytes())
+    }
+    .with_context(|| format!("{} must be base64 or hex encoded", name))?;
+
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| anyhow!("{} must decode to 32 bytes, got {}", name, b.len()))
+}
+
+/// Build a keypair from an encoded 32-byte seed, rejecting a public key
+/// that doesn't belong to it
+pub fn keypair_from_encoded(private_key: &str, public_key: Option<&str>) -> Result<Ed25519Keypair> {
+    let seed = decode_key_bytes("SIGNING_PRIVATE_KEY", private_key)?;
+    let keypair = Ed25519Keypair::from_seed(&seed);
+
+    if let Some(public_key) = public_key {
+        let expected = decode_key_bytes("SIGNING_PUBLIC_KEY", public_key)?;
+        if expected != keypair.public_key_bytes() {
+            bail!(
+                "SIGNING_PUBLIC_KEY does not match the key derived from SIGNING_PRIVATE_KEY \
+                 (derived {})",
+                BASE64.encode(&keypair.public_key_bytes())
+            );
+        }
+    }
+
+    Ok(keypair)
+}
+
+/// Base64 seed as accepted by SIGNING_PRIVATE_KEY and written to SIGNING_KEY_PATH
+pub fn encode_seed(keypair: &Ed25519Keypair) -> String {
+    BASE64.encode(keypair.signing_key.as_bytes())
 }
 
 pub fn load_or_generate_keypair(config: &Config) -> Result<Ed25519Keypair> {
-    // Try to load from config
-    if let (Some(private_key), Some(public_key)) = (
-        &config.signing_private_key,
-        &config.signing_public_key,
-    ) {
-        // TODO: Parse keys from config (base64/hex encoded)
-        warn!("Key loading from config not yet implemented, generating new keypair");
+    let public_key = config.signing_public_key.as_deref();
+
+    let keypair = if let Some(private_key) = &config.signing_private_key {
+        info!("Loading ED25519 keypair from SIGNING_PRIVATE_KEY");
+        keypair_from_encoded(private_key.expose(), public_key)?
+    } else if let Some(path) = &config.signing_key_path {
+        load_or_create_key_file(Path::new(path), public_key)?
+    } else {
+        warn!(
+            "No SIGNING_PRIVATE_KEY or SIGNING_KEY_PATH set; generating an ephemeral keypair. \
+             Receipts signed with it cannot be verified against this key after a restart"
+        );
+        Ed25519Keypair::generate()
+    };
+
+    info!(
+        "Public key (base64): {}",
+        BASE64.encode(&keypair.public_key_bytes())
+    );
+
+    Ok(keypair)
+}
+
+/// Read the seed at `path`, or generate a keypair and write its seed there
+/// (owner-only permissions) so it survives restarts
+fn load_or_create_key_file(path: &Path, public_key: Option<&str>) -> Result<Ed25519Keypair> {
+    if path.exists() {
+        info!("Loading ED25519 keypair from {}", path.display());
+        let seed = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read signing key from {}", path.display()))?;
+        return keypair_from_encoded(&seed, public_key);
+    }
+
+    if public_key.is_some() {
+        bail!(
+            "SIGNING_PUBLIC_KEY is set but {} does not exist; refusing to generate a key \
+             that cannot match it",
+            path.display()
+        );
     }
 
-    // Generate new keypair
-    info!("Generating new ED25519 keypair");
+    info!("Generating new ED25519 keypair at {}", path.display());
     let keypair = Ed25519Keypair::generate();
 
-    info!("Public key (base64): {}",
-        base64::engine::general_purpose::STANDARD.encode(keypair.public_key_bytes()));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to create signing key file {}", path.display()))?;
+    writeln!(file, "{}", encode_seed(&keypair))
+        .with_context(|| format!("Failed to write signing key to {}", path.display()))?;
 
     Ok(keypair)
 }
 
-// Helper to use base64 crate
-mod base64 {
-    pub mod engine {
-        pub mod general_purpose {
-            use data_encoding::BASE64;
-
-            pub struct StandardBase64;
-            impl StandardBase64 {
-                pub fn encode(&self, input: impl AsRef<[u8]>) -> String {
-                    BASE64.encode(input.as_ref())
-                }
-                pub fn decode(&self, input: impl AsRef<[u8]>) -> Result<Vec<u8>, data_encoding::DecodeError> {
-                    BASE64.decode(input.as_ref())
-                }
-            }
-
-            pub const STANDARD: StandardBase64 = StandardBase64;
+/// Overwrite the key file with a new keypair's seed. The seed goes to a
+/// temporary file first and is renamed over the old one, so a crash leaves
+/// either the old key or the new one, never a partial file.
+pub fn replace_key_file(path: &Path, keypair: &Ed25519Keypair) -> Result<()> {
+    let temp = path.with_extension("rotating");
+    let _ = std::fs::remove_file(&temp);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options
+        .open(&temp)
+        .with_context(|| format!("Failed to create signing key file {}", temp.display()))?;
+    writeln!(file, "{}", encode_seed(keypair))
+        .and_then(|()| file.sync_all())
+        .with_context(|| format!("Failed to write signing key to {}", temp.display()))?;
+
+    std::fs::rename(&temp, path)
+        .with_context(|| format!("Failed to replace signing key file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoded_keypair_round_trips() {
+        let keypair = Ed25519Keypair::generate();
+        let public_b64 = BASE64.encode(&keypair.public_key_bytes());
+
+        let from_b64 = keypair_from_encoded(&encode_seed(&keypair), Some(&public_b64)).unwrap();
+        assert_eq!(from_b64.public_key_bytes(), keypair.public_key_bytes());
+
+        let hex_seed = HEXLOWER
+            .encode(keypair.signing_key.as_bytes())
+            .to_uppercase();
+        let hex_public = HEXLOWER.encode(&keypair.public_key_bytes());
+        let from_hex = keypair_from_encoded(&hex_seed, Some(&hex_public)).unwrap();
+        assert_eq!(from_hex.public_key_bytes(), keypair.public_key_bytes());
+
+        // Signatures from the reloaded key verify under the original
+        let signature = from_b64.sign(b"receipt");
+        assert!(keypair.verify(b"receipt", &signature));
+    }
+
+    #[test]
+    fn test_mismatched_public_key_is_rejected() {
+        let keypair = Ed25519Keypair::generate();
+        let other = BASE64.encode(&Ed25519Keypair::generate().public_key_bytes());
+
+        let Err(err) = keypair_from_encoded(&encode_seed(&keypair), Some(&other)) else {
+            panic!("mismatched public key accepted");
+        };
+        assert!(err.to_string().contains("does not match"), "{}", err);
+    }
+
+    #[test]
+    fn test_malformed_keys_are_rejected() {
+        assert!(keypair_from_encoded("not base64!", None).is_err());
+        assert!(keypair_from_encoded(&BASE64.encode(&[7u8; 16]), None).is_err());
+    }
+
+    #[test]
+    fn test_generated_key_file_is_reused() {
+        let path = std::env::temp_dir().join(format!("veto-key-{}", uuid::Uuid::new_v4()));
+
+        let created = load_or_create_key_file(&path, None).unwrap();
+        let reloaded = load_or_create_key_file(&path, None).unwrap();
+        assert_eq!(created.public_key_bytes(), reloaded.public_key_bytes());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
         }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replaced_key_file_loads_new_key() {
+        let path = std::env::temp_dir().join(format!("veto-key-{}", uuid::Uuid::new_v4()));
+        let created = load_or_create_key_file(&path, None).unwrap();
+
+        let rotated = Ed25519Keypair::generate();
+        replace_key_file(&path, &rotated).unwrap();
+        let reloaded = load_or_create_key_file(&path, None).unwrap();
+        assert_eq!(reloaded.public_key_bytes(), rotated.public_key_bytes());
+        assert_ne!(reloaded.public_key_bytes(), created.public_key_bytes());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        std::fs::remove_file(&path).unwrap();
     }
 }
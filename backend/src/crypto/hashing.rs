@@ -1,5 +1,5 @@
 // SHA3-512 hashing for receipts
-use sha3::{Digest, Sha3_512};
+use sha3::{Digest, Sha3_256, Sha3_512};
 
 pub fn sha3_512_hash(data: &[u8]) -> String {
     let mut hasher = Sha3_512::new();
@@ -12,6 +12,16 @@ pub fn sha3_512_hash_str(data: &str) -> String {
     sha3_512_hash(data.as_bytes())
 }
 
+/// Whether `hash` has the shape of a SHA3-512 digest from `sha3_512_hash`:
+/// 128 lowercase hex characters
+pub fn is_sha3_512_hex(hash: &str) -> bool {
+    hash.len() == 128 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+pub fn sha3_256_hash_str(data: &str) -> String {
+    hex::encode(Sha3_256::digest(data.as_bytes()))
+}
+
 // Helper hex encoding
 mod hex {
     pub fn encode(bytes: impl AsRef<[u8]>) -> String {
@@ -0,0 +1,208 @@
+// JSON Canonicalization Scheme (RFC 8785)
+// The exact bytes every receipt hash is computed over: no whitespace,
+// object members sorted by the UTF-16 code units of their names at every
+// level, strings with only the escapes JSON requires, and numbers as
+// ECMAScript prints IEEE-754 doubles. Any JCS library in another language
+// reproduces these bytes from the stored receipt JSON.
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Canonical form of `value` per RFC 8785
+pub fn canonicalize(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        // Always Some without serde_json's arbitrary_precision feature
+        Value::Number(n) => write_number(out, n.as_f64().unwrap_or_default()),
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push('{');
+            for (i, (name, member)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, name);
+                out.push(':');
+                write_value(out, member);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// ECMAScript Number::toString (ECMA-262 7.1.12.1) of a finite double
+fn write_number(out: &mut String, x: f64) {
+    if x == 0.0 {
+        // Covers -0 as well
+        out.push('0');
+        return;
+    }
+    if x < 0.0 {
+        out.push('-');
+    }
+
+    // Shortest round-trip digits: x = 0.d1d2..dk × 10^n
+    let scientific = format!("{:e}", x.abs());
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("LowerExp output has an exponent");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let n = exponent
+        .parse::<i32>()
+        .expect("LowerExp exponent is an integer")
+        + 1;
+
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.push_str(&"0".repeat((n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.push_str(&"0".repeat(-n as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        let _ = write!(out, "e{}{}", if n > 0 { '+' } else { '-' }, (n - 1).abs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn number(x: f64) -> String {
+        canonicalize(&json!(x))
+    }
+
+    #[test]
+    fn test_rfc8785_example() {
+        // RFC 8785 section 3.2.2
+        let input: Value = serde_json::from_str(
+            r#"{
+                "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+                "string": "€$\u000F\u000aA'B\"\\\\\"\/",
+                "literals": [null, true, false]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            canonicalize(&input),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+    }
+
+    #[test]
+    fn test_members_sort_by_utf16_code_units() {
+        // RFC 8785 section 3.2.3: the emoji's surrogate pair sorts before
+        // U+FB33, though its UTF-8 bytes sort after
+        let input: Value = serde_json::from_str(
+            r#"{
+                "\u20ac": "Euro Sign",
+                "\r": "Carriage Return",
+                "\ufb33": "Hebrew Letter Dalet With Dagesh",
+                "1": "One",
+                "\ud83d\ude00": "Emoji: Grinning Face",
+                "\u0080": "Control",
+                "\u00f6": "Latin Small Letter O With Diaeresis"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            canonicalize(&input),
+            concat!(
+                "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",",
+                "\"\u{f6}\":\"Latin Small Letter O With Diaeresis\",\"\u{20ac}\":\"Euro Sign\",",
+                "\"\u{1f600}\":\"Emoji: Grinning Face\",",
+                "\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_number_serialization() {
+        // RFC 8785 appendix B, and the ECMAScript notation boundaries
+        let cases: [(f64, &str); 17] = [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (1.0, "1"),
+            (-1.5, "-1.5"),
+            (f64::from_bits(0x0000000000000001), "5e-324"),
+            (f64::from_bits(0x8000000000000001), "-5e-324"),
+            (f64::MAX, "1.7976931348623157e+308"),
+            (9007199254740992.0, "9007199254740992"),
+            (9007199254740993u64 as f64, "9007199254740992"),
+            (295147905179352830000.0, "295147905179352830000"),
+            (1e21, "1e+21"),
+            (1e20, "100000000000000000000"),
+            (123e18, "123000000000000000000"),
+            (0.000001, "0.000001"),
+            (0.0000001, "1e-7"),
+            (1.2345e-7, "1.2345e-7"),
+            (4.5, "4.5"),
+        ];
+        for (x, expected) in cases {
+            assert_eq!(number(x), expected, "{:e}", x);
+        }
+
+        assert_eq!(canonicalize(&json!(-42)), "-42");
+        assert_eq!(canonicalize(&json!(u64::MAX)), "18446744073709552000");
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        assert_eq!(
+            canonicalize(&json!("\u{1}\u{1f}\u{7f}\u{2028}</é😀")),
+            "\"\\u0001\\u001f\u{7f}\u{2028}</é😀\""
+        );
+    }
+}
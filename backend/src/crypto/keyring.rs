@@ -0,0 +1,137 @@
+// Receipt signing keys
+// One key signs at a time. Rotating it swaps in a new signer while every
+// key it replaced stays available, by key_id, to verify the receipts it
+// signed. Background workers and handlers read the signer through the same
+// ring, so a rotation reaches all of them at once.
+use ed25519_dalek::VerifyingKey;
+use std::{collections::HashMap, sync::RwLock};
+
+use super::{key_id, Ed25519Keypair};
+
+/// Public keys receipt signatures are checked against
+pub trait ReceiptKeys {
+    /// Keys that may have signed a receipt recorded with `key_id`. Receipts
+    /// from before key ids were recorded (None) may be from any of them.
+    fn keys_for(&self, key_id: Option<&str>) -> Vec<VerifyingKey>;
+}
+
+/// A single key only verifies receipts it signed
+impl ReceiptKeys for VerifyingKey {
+    fn keys_for(&self, id: Option<&str>) -> Vec<VerifyingKey> {
+        match id {
+            Some(id) if id != key_id(self) => Vec::new(),
+            _ => vec![*self],
+        }
+    }
+}
+
+/// Verification keys by key_id
+#[derive(Debug, Clone, Default)]
+pub struct KeySet {
+    keys: HashMap<String, VerifyingKey>,
+}
+
+impl KeySet {
+    pub fn insert(&mut self, key: VerifyingKey) {
+        self.keys.insert(key_id(&key), key);
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.keys.contains_key(id)
+    }
+
+    /// Every key, ordered by key_id
+    pub fn keys(&self) -> Vec<(&str, &VerifyingKey)> {
+        let mut keys: Vec<_> = self
+            .keys
+            .iter()
+            .map(|(id, key)| (id.as_str(), key))
+            .collect();
+        keys.sort_by_key(|(id, _)| *id);
+        keys
+    }
+}
+
+impl ReceiptKeys for KeySet {
+    fn keys_for(&self, key_id: Option<&str>) -> Vec<VerifyingKey> {
+        match key_id {
+            Some(id) => self.keys.get(id).copied().into_iter().collect(),
+            None => self.keys.values().copied().collect(),
+        }
+    }
+}
+
+pub struct SigningKeyring {
+    active: RwLock<Ed25519Keypair>,
+    known: RwLock<KeySet>,
+}
+
+impl SigningKeyring {
+    pub fn new(active: Ed25519Keypair) -> Self {
+        let mut known = KeySet::default();
+        known.insert(active.verifying_key);
+
+        Self {
+            active: RwLock::new(active),
+            known: RwLock::new(known),
+        }
+    }
+
+    /// The key new receipts are signed with
+    pub fn active(&self) -> Ed25519Keypair {
+        self.active.read().unwrap().clone()
+    }
+
+    /// Make `keypair` the signer. The key it replaces stays known for
+    /// verification.
+    pub fn activate(&self, keypair: Ed25519Keypair) {
+        self.remember(keypair.verifying_key);
+        *self.active.write().unwrap() = keypair;
+    }
+
+    /// Know a key for verification only, such as a retired one
+    pub fn remember(&self, key: VerifyingKey) {
+        self.known.write().unwrap().insert(key);
+    }
+
+    pub fn knows(&self, key_id: &str) -> bool {
+        self.known.read().unwrap().contains(key_id)
+    }
+
+    /// Every known key, to verify receipts against
+    pub fn key_set(&self) -> KeySet {
+        self.known.read().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_keeps_replaced_key_for_verification() {
+        let first = Ed25519Keypair::generate();
+        let second = Ed25519Keypair::generate();
+        let ring = SigningKeyring::new(first.clone());
+        assert_eq!(ring.active().key_id(), first.key_id());
+
+        ring.activate(second.clone());
+        assert_eq!(ring.active().key_id(), second.key_id());
+        assert!(ring.knows(&first.key_id()));
+
+        let keys = ring.key_set();
+        assert_eq!(
+            keys.keys_for(Some(&first.key_id())),
+            vec![first.verifying_key]
+        );
+        assert_eq!(keys.keys_for(None).len(), 2);
+        assert!(keys.keys_for(Some("unknown")).is_empty());
+
+        // A lone key refuses receipts recorded as signed by another
+        assert!(first
+            .verifying_key
+            .keys_for(Some(&second.key_id()))
+            .is_empty());
+        assert_eq!(first.verifying_key.keys_for(None).len(), 1);
+    }
+}
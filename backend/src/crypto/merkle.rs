@@ -0,0 +1,233 @@
+// Merkle trees over receipt hashes
+// Receipt checkpoints commit to every receipt they cover through one root.
+// Leaves and interior nodes are hashed with distinct prefixes (as in
+// RFC 6962), so a leaf can never pass for a node:
+//   leaf = SHA3-512(0x00 || receipt_hash)   over the hex string's bytes
+//   node = SHA3-512(0x01 || left || right)  over the raw 64-byte digests
+// A level with an odd count carries its last node up unchanged instead of
+// pairing it with itself, so no two leaf lists share a root through
+// duplication.
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_512};
+use utoipa::ToSchema;
+
+type Hash = [u8; 64];
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+fn leaf_hash(receipt_hash: &str) -> Hash {
+    Sha3_512::new()
+        .chain_update([LEAF_PREFIX])
+        .chain_update(receipt_hash.as_bytes())
+        .finalize()
+        .into()
+}
+
+fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    Sha3_512::new()
+        .chain_update([NODE_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// Which side of the path a sibling sits on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// One level of an inclusion proof: the sibling to hash with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ProofStep {
+    pub side: Side,
+    /// Hex SHA3-512
+    pub hash: String,
+}
+
+pub struct MerkleTree {
+    /// Leaf hashes first, the root alone last
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    /// Tree over receipt hashes in the order given; None when there are none
+    pub fn new<S: AsRef<str>>(receipt_hashes: &[S]) -> Option<Self> {
+        if receipt_hashes.is_empty() {
+            return None;
+        }
+
+        let mut levels = vec![receipt_hashes
+            .iter()
+            .map(|hash| leaf_hash(hash.as_ref()))
+            .collect::<Vec<_>>()];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [carried] => *carried,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Some(Self { levels })
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Hex SHA3-512 root
+    pub fn root(&self) -> String {
+        data_encoding::HEXLOWER.encode(&self.levels[self.levels.len() - 1][0])
+    }
+
+    /// Siblings from the leaf at `index` up to the root. A node carried up
+    /// a level has no sibling there, so contributes no step.
+    pub fn proof(&self, index: usize) -> Option<Vec<ProofStep>> {
+        if index >= self.leaf_count() {
+            return None;
+        }
+
+        let mut steps = Vec::new();
+        let mut index = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = index ^ 1;
+            if let Some(hash) = level.get(sibling) {
+                steps.push(ProofStep {
+                    side: if sibling < index {
+                        Side::Left
+                    } else {
+                        Side::Right
+                    },
+                    hash: data_encoding::HEXLOWER.encode(hash),
+                });
+            }
+            index /= 2;
+        }
+
+        Some(steps)
+    }
+}
+
+/// Whether `proof` leads from `receipt_hash` to `root`. Needs only the
+/// receipt hash, the proof and a root whose signature was checked.
+pub fn verify_inclusion(receipt_hash: &str, proof: &[ProofStep], root: &str) -> bool {
+    let mut hash = leaf_hash(receipt_hash);
+    for step in proof {
+        let Some(sibling) = data_encoding::HEXLOWER
+            .decode(step.hash.as_bytes())
+            .ok()
+            .and_then(|bytes| Hash::try_from(bytes).ok())
+        else {
+            return false;
+        };
+        hash = match step.side {
+            Side::Left => node_hash(&sibling, &hash),
+            Side::Right => node_hash(&hash, &sibling),
+        };
+    }
+
+    data_encoding::HEXLOWER.encode(&hash) == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::hashing::sha3_512_hash_str;
+
+    fn receipt_hashes(count: usize) -> Vec<String> {
+        (0..count)
+            .map(|i| sha3_512_hash_str(&format!("receipt {}", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_tree_has_no_root() {
+        assert!(MerkleTree::new::<String>(&[]).is_none());
+    }
+
+    #[test]
+    fn test_single_leaf_tree() {
+        let hashes = receipt_hashes(1);
+        let tree = MerkleTree::new(&hashes).unwrap();
+
+        assert_eq!(
+            tree.root(),
+            data_encoding::HEXLOWER.encode(&leaf_hash(&hashes[0]))
+        );
+        assert_ne!(tree.root(), hashes[0], "leaves are prefixed");
+        let proof = tree.proof(0).unwrap();
+        assert!(proof.is_empty());
+        assert!(verify_inclusion(&hashes[0], &proof, &tree.root()));
+        assert!(tree.proof(1).is_none());
+    }
+
+    #[test]
+    fn test_odd_leaf_count_carries_last_node_up() {
+        let hashes = receipt_hashes(3);
+        let tree = MerkleTree::new(&hashes).unwrap();
+
+        let leaves: Vec<Hash> = hashes.iter().map(|h| leaf_hash(h)).collect();
+        let expected = node_hash(&node_hash(&leaves[0], &leaves[1]), &leaves[2]);
+        assert_eq!(tree.root(), data_encoding::HEXLOWER.encode(&expected));
+
+        // The carried leaf pairs only at the level above
+        let proof = tree.proof(2).unwrap();
+        assert_eq!(proof.len(), 1);
+        assert_eq!(proof[0].side, Side::Left);
+
+        // Not the tree over [a, b, c, c]
+        let mut duplicated = hashes.clone();
+        duplicated.push(hashes[2].clone());
+        assert_ne!(MerkleTree::new(&duplicated).unwrap().root(), tree.root());
+    }
+
+    #[test]
+    fn test_every_leaf_proves_inclusion() {
+        for count in [2, 3, 5, 7, 8, 13] {
+            let hashes = receipt_hashes(count);
+            let tree = MerkleTree::new(&hashes).unwrap();
+            let root = tree.root();
+
+            for (index, hash) in hashes.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert!(
+                    verify_inclusion(hash, &proof, &root),
+                    "leaf {} of {}",
+                    index,
+                    count
+                );
+
+                // Not for another receipt, another root, or a tampered path
+                let other = &hashes[(index + 1) % count];
+                assert!(!verify_inclusion(other, &proof, &root));
+                assert!(!verify_inclusion(hash, &proof, &sha3_512_hash_str("root")));
+                let mut flipped = proof.clone();
+                flipped[0].side = match flipped[0].side {
+                    Side::Left => Side::Right,
+                    Side::Right => Side::Left,
+                };
+                assert!(!verify_inclusion(hash, &flipped, &root));
+            }
+        }
+    }
+
+    #[test]
+    fn test_malformed_proof_hash_fails() {
+        let hashes = receipt_hashes(2);
+        let tree = MerkleTree::new(&hashes).unwrap();
+        let mut proof = tree.proof(0).unwrap();
+        proof[0].hash.truncate(10);
+        assert!(!verify_inclusion(&hashes[0], &proof, &tree.root()));
+    }
+}
@@ -1,8 +1,20 @@
 // Cryptography module
 pub mod ed25519;
 pub mod hashing;
+pub mod jcs;
+pub mod keyring;
+pub mod merkle;
+pub mod overflow;
+pub mod payload;
 pub mod receipts;
+pub mod sealing;
+pub mod test_vectors;
+pub mod verify;
 
 pub use ed25519::*;
-pub use hashing::*;
+pub use keyring::*;
+pub use overflow::*;
+pub use payload::*;
 pub use receipts::*;
+pub use sealing::*;
+pub use verify::*;
@@ -0,0 +1,167 @@
+// Receipt size limits
+// A receipt's canonical JSON is capped at a configured size. Client-supplied
+// fields that could push a receipt past it are refused up front. Server
+// metadata that grows past it is moved to an overflow record keyed by its
+// SHA3-512 hash, and the receipt signs only that hash and the byte size, so
+// the chain stays compact and the full evidence can still be checked.
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+
+use super::{hashing::sha3_512_hash_str, jcs::canonicalize, ReceiptFailure};
+
+/// Canonical bytes kept free for the server fields around client-supplied
+/// ones: ids, timestamps, hashes and flags
+pub const RECEIPT_ENVELOPE_BYTES: usize = 2048;
+
+/// A receipt field moved out of the signed JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptOverflow {
+    /// SHA3-512 of `content`, as named in the receipt
+    pub overflow_hash: String,
+    /// Canonical JSON of the field
+    pub content: String,
+}
+
+/// Refuse a client-supplied receipt field too large to fit in a receipt of
+/// `max_bytes` alongside the server's own fields
+pub fn check_client_field(name: &str, value: &str, max_bytes: usize) -> Result<()> {
+    let encoded = serde_json::to_string(value)?.len();
+    let budget = max_bytes.saturating_sub(RECEIPT_ENVELOPE_BYTES);
+
+    if encoded > budget {
+        bail!(
+            "{} is {} bytes encoded; receipts allow at most {} bytes of client fields",
+            name,
+            encoded,
+            budget
+        );
+    }
+    Ok(())
+}
+
+/// If `receipt`'s canonical JSON is over `max_bytes`, replace its `field`
+/// with an overflow marker and return the record to store. Fails if the
+/// receipt is still too large without the field.
+pub fn overflow_field(
+    receipt: &mut Value,
+    field: &str,
+    max_bytes: usize,
+) -> Result<Option<ReceiptOverflow>> {
+    if canonicalize(receipt).len() <= max_bytes {
+        return Ok(None);
+    }
+
+    let content = canonicalize(&receipt[field]);
+    let overflow = ReceiptOverflow {
+        overflow_hash: sha3_512_hash_str(&content),
+        content,
+    };
+    receipt[field] = json!({
+        "overflow": {
+            "sha3_512": overflow.overflow_hash,
+            "bytes": overflow.content.len(),
+        }
+    });
+
+    let bounded = canonicalize(receipt).len();
+    if bounded > max_bytes {
+        bail!(
+            "receipt is {} bytes even with {} overflowed, over the {} byte limit",
+            bounded,
+            field,
+            max_bytes
+        );
+    }
+    Ok(Some(overflow))
+}
+
+/// Hash named by an overflow marker, if `field` is one
+pub fn overflow_hash(field: &Value) -> Option<&str> {
+    field.get("overflow")?.get("sha3_512")?.as_str()
+}
+
+/// Check an overflowed field's record against the hash and size its marker
+/// signed. A field that never overflowed needs no record.
+pub fn verify_overflow(field: &Value, content: Option<&str>) -> Result<(), ReceiptFailure> {
+    let Some(hash) = overflow_hash(field) else {
+        return Ok(());
+    };
+    let Some(content) = content else {
+        return Err(ReceiptFailure::OverflowMismatch);
+    };
+
+    let bytes = field["overflow"]["bytes"].as_u64();
+    if sha3_512_hash_str(content) != hash || bytes != Some(content.len() as u64) {
+        return Err(ReceiptFailure::OverflowMismatch);
+    }
+    Ok(())
+}
+
+/// The original value of an overflowed field, once its record checks out.
+/// A field that never overflowed resolves to None.
+pub fn resolve_overflow(
+    field: &Value,
+    content: Option<&str>,
+) -> Result<Option<Value>, ReceiptFailure> {
+    verify_overflow(field, content)?;
+
+    match content {
+        Some(content) if overflow_hash(field).is_some() => serde_json::from_str(content)
+            .map(Some)
+            .map_err(|_| ReceiptFailure::OverflowMismatch),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_field_budget() {
+        assert!(check_client_field("reason", "withdrawn", 65536).is_ok());
+        assert!(check_client_field("reason", &"x".repeat(63_000), 65536).is_ok());
+
+        let error = check_client_field("reason", &"x".repeat(3_000_000), 65536).unwrap_err();
+        assert!(error.to_string().starts_with("reason is 3000002 bytes"));
+    }
+
+    #[test]
+    fn test_overflowed_field_resolves_only_with_its_record() {
+        let members: Vec<Value> = (0..1000).map(|i| json!({ "n": i })).collect();
+        let mut receipt = json!({ "id": 1, "members": members });
+        let original = receipt["members"].clone();
+
+        let overflow = overflow_field(&mut receipt, "members", 4096)
+            .unwrap()
+            .unwrap();
+        assert!(serde_json::to_string(&receipt).unwrap().len() <= 4096);
+        assert_eq!(
+            overflow_hash(&receipt["members"]),
+            Some(overflow.overflow_hash.as_str())
+        );
+        assert_eq!(
+            resolve_overflow(&receipt["members"], Some(&overflow.content)),
+            Ok(Some(original))
+        );
+
+        assert_eq!(
+            resolve_overflow(&receipt["members"], None),
+            Err(ReceiptFailure::OverflowMismatch)
+        );
+        let edited = overflow.content.replace("999", "998");
+        assert_eq!(
+            resolve_overflow(&receipt["members"], Some(&edited)),
+            Err(ReceiptFailure::OverflowMismatch)
+        );
+
+        // Small receipts are left alone
+        let mut small = json!({ "id": 1, "members": [] });
+        assert_eq!(overflow_field(&mut small, "members", 4096).unwrap(), None);
+        assert_eq!(resolve_overflow(&small["members"], None), Ok(None));
+
+        // Overflowing one field can't save a receipt that's large elsewhere
+        let mut large = json!({ "id": "x".repeat(5000), "members": [] });
+        assert!(overflow_field(&mut large, "members", 4096).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+// Server-side encryption of payloads at rest
+// With PAYLOAD_ENCRYPTION_KEY set, payloads are sealed with AES-256-GCM
+// before they reach data_store and opened again on the way out, so the
+// stored bytes are ciphertext whatever the client sent. The associated data
+// binds each payload to its org and content hash, so stored bytes moved to
+// another row fail to open. data_store.metadata.encryption records which
+// rows are server-encrypted; rows without the marker are stored as sent.
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::MasterKey;
+use crate::{config::Config, db::models::DataStore};
+
+/// data_store.metadata key recording how the stored payload is protected
+pub const ENCRYPTION_METADATA_KEY: &str = "encryption";
+/// Marker of a payload sealed by the server
+pub const SERVER_ENCRYPTION: &str = "aes-256-gcm";
+/// Marker of a payload stored exactly as the client sent it
+pub const NO_ENCRYPTION: &str = "none";
+
+#[derive(Clone)]
+pub struct PayloadCipher {
+    key: MasterKey,
+}
+
+impl std::fmt::Debug for PayloadCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadCipher").finish_non_exhaustive()
+    }
+}
+
+impl PayloadCipher {
+    pub fn new(key: MasterKey) -> Self {
+        Self { key }
+    }
+
+    /// None unless PAYLOAD_ENCRYPTION_KEY is set
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        config
+            .payload_encryption_key
+            .as_ref()
+            .map(|key| {
+                Ok(Self::new(MasterKey::from_encoded(
+                    "PAYLOAD_ENCRYPTION_KEY",
+                    key.expose(),
+                )?))
+            })
+            .transpose()
+    }
+
+    pub fn encrypt(&self, org_id: Uuid, content_hash: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+        self.key
+            .seal(plaintext, &associated_data(org_id, content_hash))
+    }
+
+    /// Fails when the ciphertext was altered, sealed under another key, or
+    /// belongs to another org or content hash
+    pub fn decrypt(&self, org_id: Uuid, content_hash: &str, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        self.key
+            .open(ciphertext, &associated_data(org_id, content_hash))
+    }
+}
+
+fn associated_data(org_id: Uuid, content_hash: &str) -> Vec<u8> {
+    format!("veto-payload:v1:{}:{}", org_id, content_hash).into_bytes()
+}
+
+/// The bytes to store for a payload and the data_store metadata marking how
+/// they are protected. Without a cipher the payload passes through.
+pub fn seal_payload(
+    cipher: Option<&PayloadCipher>,
+    org_id: Uuid,
+    content_hash: &str,
+    payload: Option<&[u8]>,
+) -> Result<(Option<Vec<u8>>, Value)> {
+    let Some(payload) = payload else {
+        return Ok((None, json!({})));
+    };
+    match cipher {
+        Some(cipher) => Ok((
+            Some(cipher.encrypt(org_id, content_hash, payload)?),
+            json!({ ENCRYPTION_METADATA_KEY: SERVER_ENCRYPTION }),
+        )),
+        None => Ok((
+            Some(payload.to_vec()),
+            json!({ ENCRYPTION_METADATA_KEY: NO_ENCRYPTION }),
+        )),
+    }
+}
+
+/// The row's payload as the client sent it, decrypting a server-encrypted
+/// one. A server-encrypted payload with no cipher configured is an error,
+/// never handed out as ciphertext.
+pub fn open_payload(cipher: Option<&PayloadCipher>, data: &DataStore) -> Result<Option<Vec<u8>>> {
+    let Some(stored) = &data.encrypted_payload else {
+        return Ok(None);
+    };
+    if data.metadata[ENCRYPTION_METADATA_KEY] != SERVER_ENCRYPTION {
+        return Ok(Some(stored.clone()));
+    }
+    let Some(cipher) = cipher else {
+        bail!(
+            "Payload of data {} is server-encrypted but PAYLOAD_ENCRYPTION_KEY is not set",
+            data.data_id
+        );
+    };
+    cipher
+        .decrypt(data.org_id, &data.content_hash, stored)
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_row(payload: Option<Vec<u8>>, metadata: Value) -> DataStore {
+        DataStore {
+            data_id: Uuid::new_v4(),
+            org_id: Uuid::new_v4(),
+            subject_id: "user_1".to_string(),
+            content_hash: "ab".repeat(64),
+            encrypted_payload: payload,
+            created_at: chrono::Utc::now(),
+            metadata,
+            released_at: None,
+            legal_hold: false,
+            retention_class: None,
+            retain_until: None,
+            payload_purged_at: None,
+        }
+    }
+
+    fn sealed_row(cipher: &PayloadCipher, plaintext: &[u8]) -> DataStore {
+        let mut row = data_row(None, json!({}));
+        let (stored, metadata) =
+            seal_payload(Some(cipher), row.org_id, &row.content_hash, Some(plaintext)).unwrap();
+        row.encrypted_payload = stored;
+        row.metadata = metadata;
+        row
+    }
+
+    #[test]
+    fn test_payload_round_trips_through_server_encryption() {
+        let cipher = PayloadCipher::new(MasterKey::from_bytes(&[5u8; 32]));
+        let row = sealed_row(&cipher, b"plaintext from a lax client");
+
+        assert_eq!(row.metadata[ENCRYPTION_METADATA_KEY], SERVER_ENCRYPTION);
+        let stored = row.encrypted_payload.as_deref().unwrap();
+        assert!(!stored
+            .windows(b"plaintext".len())
+            .any(|w| w == b"plaintext"));
+        assert_eq!(
+            open_payload(Some(&cipher), &row).unwrap().as_deref(),
+            Some(&b"plaintext from a lax client"[..])
+        );
+    }
+
+    #[test]
+    fn test_tampered_or_moved_ciphertext_fails_to_open() {
+        let cipher = PayloadCipher::new(MasterKey::from_bytes(&[5u8; 32]));
+        let row = sealed_row(&cipher, b"payload");
+
+        let mut tampered = row.clone();
+        tampered.encrypted_payload.as_mut().unwrap()[20] ^= 1;
+        assert!(open_payload(Some(&cipher), &tampered).is_err());
+
+        let mut moved = row.clone();
+        moved.content_hash = "cd".repeat(64);
+        assert!(open_payload(Some(&cipher), &moved).is_err());
+
+        let other_key = PayloadCipher::new(MasterKey::from_bytes(&[6u8; 32]));
+        assert!(open_payload(Some(&other_key), &row).is_err());
+
+        // Ciphertext is never passed off as the payload
+        assert!(open_payload(None, &row).is_err());
+    }
+
+    #[test]
+    fn test_unencrypted_rows_pass_through() {
+        let (stored, metadata) =
+            seal_payload(None, Uuid::new_v4(), &"ab".repeat(64), Some(b"as sent")).unwrap();
+        assert_eq!(stored.as_deref(), Some(&b"as sent"[..]));
+        assert_eq!(metadata[ENCRYPTION_METADATA_KEY], NO_ENCRYPTION);
+
+        // Rows from before the marker, with or without a key configured
+        let cipher = PayloadCipher::new(MasterKey::from_bytes(&[5u8; 32]));
+        let legacy = data_row(Some(b"as sent".to_vec()), json!({}));
+        for cipher in [None, Some(&cipher)] {
+            assert_eq!(
+                open_payload(cipher, &legacy).unwrap().as_deref(),
+                Some(&b"as sent"[..])
+            );
+        }
+        assert!(open_payload(None, &data_row(None, json!({})))
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,14 +1,20 @@
 // Canonical receipt generation
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Verifier};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-use super::{hashing::sha3_512_hash_str, Ed25519Keypair};
-use crate::db::models::ReceiptOperation;
+use super::{
+    hashing::sha3_512_hash_str, jcs::canonicalize, overflow_field, stored_receipt_hash,
+    verify_stored_receipt, Ed25519Keypair, ReceiptFailure, ReceiptKeys, ReceiptOverflow,
+};
+use crate::db::models::{GovernanceReceipt, ReceiptOperation};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ReceiptData {
     pub pointer_id: Uuid,
     pub operation: String,
@@ -16,6 +22,9 @@ pub struct ReceiptData {
     pub subject_id: String,
     pub prev_hash: Option<String>,
     pub metadata: serde_json::Value,
+    /// Key the receipt is signed with; filled in when it is signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +33,31 @@ pub struct SignedReceipt {
     pub receipt_hash: String,
     pub signature: Vec<u8>,
     pub signature_algorithm: String,
+    /// Fingerprint of the signing key, see Ed25519Keypair::key_id
+    pub key_id: String,
+    /// Phase timings, only when signed with ReceiptData::sign_timed. Never
+    /// part of the signed payload.
+    #[serde(skip)]
+    pub timings: Option<ReceiptTimings>,
+    /// Field moved out of the signed JSON, to be stored alongside it
+    #[serde(skip)]
+    pub overflow: Option<ReceiptOverflow>,
+}
+
+/// Where the time went generating one receipt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReceiptTimings {
+    pub canonicalize: Duration,
+    pub hash: Duration,
+    pub sign: Duration,
+    /// Length of the canonical JSON that was hashed
+    pub canonical_bytes: usize,
+}
+
+impl ReceiptTimings {
+    pub fn total(&self) -> Duration {
+        self.canonicalize + self.hash + self.sign
+    }
 }
 
 impl ReceiptData {
@@ -33,6 +67,7 @@ impl ReceiptData {
         subject_id: String,
         prev_hash: Option<String>,
         metadata: serde_json::Value,
+        timestamp: DateTime<Utc>,
     ) -> Self {
         Self {
             pointer_id,
@@ -40,18 +75,31 @@ impl ReceiptData {
                 ReceiptOperation::Create => "create".to_string(),
                 ReceiptOperation::Resolve => "resolve".to_string(),
                 ReceiptOperation::Orphan => "orphan".to_string(),
+                ReceiptOperation::Denied => "denied".to_string(),
+                ReceiptOperation::Merge => "merge".to_string(),
+                ReceiptOperation::Restrict => "restrict".to_string(),
+                ReceiptOperation::Unrestrict => "unrestrict".to_string(),
+                ReceiptOperation::Repair => "repair".to_string(),
+                ReceiptOperation::DataSwap => "data_swap".to_string(),
+                ReceiptOperation::ChainFreezeReview => "chain_freeze_review".to_string(),
+                ReceiptOperation::OrphanScheduled => "orphan_scheduled".to_string(),
+                ReceiptOperation::OrphanCancelled => "orphan_cancelled".to_string(),
+                ReceiptOperation::LegalHold => "legal_hold".to_string(),
+                ReceiptOperation::LegalHoldRelease => "legal_hold_release".to_string(),
+                ReceiptOperation::Purge => "purge".to_string(),
+                ReceiptOperation::OrphanRequested => "orphan_requested".to_string(),
+                ReceiptOperation::Reinstate => "reinstate".to_string(),
             },
-            timestamp: Utc::now(),
+            timestamp,
             subject_id,
             prev_hash,
             metadata,
+            key_id: None,
         }
     }
 
-    /// Convert to canonical JSON (sorted keys, no whitespace)
-    pub fn to_canonical_json(&self) -> Result<String> {
-        // Serialize with sorted keys for deterministic hashing
-        let value = json!({
+    fn to_canonical_value(&self) -> serde_json::Value {
+        let mut value = json!({
             "metadata": self.metadata,
             "operation": self.operation,
             "pointer_id": self.pointer_id,
@@ -59,36 +107,726 @@ impl ReceiptData {
             "subject_id": self.subject_id,
             "timestamp": self.timestamp.to_rfc3339(),
         });
+        if let Some(key_id) = &self.key_id {
+            value["key_id"] = json!(key_id);
+        }
+        value
+    }
+
+    /// Canonical JSON as signed by `keypair`, naming it as the signer
+    fn signed_canonical_json(&self, keypair: &Ed25519Keypair) -> Result<String> {
+        let mut value = self.to_canonical_value();
+        value["key_id"] = json!(keypair.key_id());
+        Ok(canonicalize(&value))
+    }
+
+    /// Convert to canonical JSON (RFC 8785), the bytes receipt_hash is over
+    pub fn to_canonical_json(&self) -> Result<String> {
+        Ok(canonicalize(&self.to_canonical_value()))
+    }
 
-        // Compact JSON (no whitespace)
-        Ok(serde_json::to_string(&value)?)
+    /// Keep the canonical receipt within `max_bytes` by moving its metadata
+    /// to an overflow record, returned for storing with the receipt
+    pub fn bound(&mut self, max_bytes: usize) -> Result<Option<ReceiptOverflow>> {
+        let mut value = self.to_canonical_value();
+        if self.key_id.is_none() {
+            // Every key_id is 64 hex digits; leave room for the signer's
+            value["key_id"] = json!("0".repeat(64));
+        }
+        let overflow = overflow_field(&mut value, "metadata", max_bytes)?;
+
+        if overflow.is_some() {
+            self.metadata = value["metadata"].take();
+        }
+        Ok(overflow)
     }
 
     /// Generate signed receipt
     pub fn sign(&self, keypair: &Ed25519Keypair) -> Result<SignedReceipt> {
-        // 1. Generate canonical JSON
-        let canonical_json = self.to_canonical_json()?;
+        sign_canonical_json(&self.signed_canonical_json(keypair)?, keypair)
+    }
 
-        // 2. Hash with SHA3-512
+    /// Generate signed receipt, timing canonicalization, hashing and signing
+    pub fn sign_timed(&self, keypair: &Ed25519Keypair) -> Result<SignedReceipt> {
+        let started = Instant::now();
+        let canonical_json = self.signed_canonical_json(keypair)?;
+        let canonicalize = started.elapsed();
+
+        let started = Instant::now();
         let receipt_hash = sha3_512_hash_str(&canonical_json);
+        let hash = started.elapsed();
 
-        // 3. Sign the hash with ED25519
+        let started = Instant::now();
         let signature = keypair.sign(receipt_hash.as_bytes());
-        let signature_bytes = signature.to_bytes().to_vec();
+        let sign = started.elapsed();
+
+        Ok(SignedReceipt {
+            timings: Some(ReceiptTimings {
+                canonicalize,
+                hash,
+                sign,
+                canonical_bytes: canonical_json.len(),
+            }),
+            ..signed_receipt(&canonical_json, receipt_hash, signature, keypair)?
+        })
+    }
+}
+
+/// Member entry of an orphan group manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupMember {
+    pub pointer_id: Uuid,
+    pub receipt_hash: String,
+}
+
+/// Manifest evidencing an all-or-nothing veto over a set of pointers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanGroupManifest {
+    pub group_id: Uuid,
+    pub org_id: Uuid,
+    pub reason: String,
+    pub members: Vec<GroupMember>,
+    pub members_hash: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl OrphanGroupManifest {
+    pub fn new(group_id: Uuid, org_id: Uuid, reason: String, members: Vec<GroupMember>) -> Self {
+        let members_hash = Self::members_hash(&members);
+
+        Self {
+            group_id,
+            org_id,
+            reason,
+            members,
+            members_hash,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// SHA3-512 over the member receipt hashes, in manifest order
+    pub fn members_hash(members: &[GroupMember]) -> String {
+        let joined: Vec<&str> = members.iter().map(|m| m.receipt_hash.as_str()).collect();
+        sha3_512_hash_str(&joined.join(""))
+    }
+
+    fn to_canonical_value(&self) -> serde_json::Value {
+        let members: Vec<serde_json::Value> = self
+            .members
+            .iter()
+            .map(|m| {
+                json!({
+                    "pointer_id": m.pointer_id,
+                    "receipt_hash": m.receipt_hash,
+                })
+            })
+            .collect();
+
+        json!({
+            "group_id": self.group_id,
+            "members": members,
+            "members_hash": self.members_hash,
+            "org_id": self.org_id,
+            "reason": self.reason,
+            "timestamp": self.timestamp.to_rfc3339(),
+        })
+    }
+
+    /// Generate signed manifest of at most `max_bytes` canonical bytes,
+    /// moving the member list to an overflow record for groups too large to
+    /// list inline
+    pub fn sign(&self, keypair: &Ed25519Keypair, max_bytes: usize) -> Result<SignedReceipt> {
+        let mut value = self.to_canonical_value();
+        let overflow = overflow_field(&mut value, "members", max_bytes)?;
 
-        // 4. Return signed receipt
         Ok(SignedReceipt {
-            receipt_json: serde_json::from_str(&canonical_json)?,
-            receipt_hash,
-            signature: signature_bytes,
-            signature_algorithm: "ED25519".to_string(),
+            overflow,
+            ..sign_canonical_json(&canonicalize(&value), keypair)?
         })
     }
 }
 
+/// Org-level liveness attestation: a beat covers one interval with its
+/// operation counts, a gap marker covers intervals nobody attested
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartbeatReceipt {
+    pub heartbeat_id: Uuid,
+    pub org_id: Uuid,
+    pub kind: String,
+    pub interval_start: DateTime<Utc>,
+    pub interval_end: DateTime<Utc>,
+    pub operation_counts: serde_json::Value,
+    pub chain_heads: serde_json::Value,
+    pub prev_hash: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl HeartbeatReceipt {
+    /// Convert to canonical JSON (RFC 8785)
+    pub fn to_canonical_json(&self) -> Result<String> {
+        let value = json!({
+            "chain_heads": self.chain_heads,
+            "heartbeat_id": self.heartbeat_id,
+            "interval_end": self.interval_end.to_rfc3339(),
+            "interval_start": self.interval_start.to_rfc3339(),
+            "kind": self.kind,
+            "operation_counts": self.operation_counts,
+            "org_id": self.org_id,
+            "prev_hash": self.prev_hash,
+            "timestamp": self.timestamp.to_rfc3339(),
+        });
+
+        Ok(canonicalize(&value))
+    }
+
+    /// Generate signed heartbeat
+    pub fn sign(&self, keypair: &Ed25519Keypair) -> Result<SignedReceipt> {
+        sign_canonical_json(&self.to_canonical_json()?, keypair)
+    }
+}
+
+/// Service-wide checkpoint: the Merkle root over every pointer receipt
+/// written since the previous checkpoint, chained to that checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointReceipt {
+    pub checkpoint_id: Uuid,
+    pub sequence: i64,
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: DateTime<Utc>,
+    pub receipt_count: i32,
+    pub merkle_root: String,
+    pub prev_hash: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl CheckpointReceipt {
+    /// Convert to canonical JSON (RFC 8785), naming the signing key
+    pub fn to_canonical_json(&self, keypair: &Ed25519Keypair) -> Result<String> {
+        let value = json!({
+            "checkpoint_id": self.checkpoint_id,
+            "key_id": keypair.key_id(),
+            "merkle_root": self.merkle_root,
+            "period_end": self.period_end.to_rfc3339(),
+            "period_start": self.period_start.map(|t| t.to_rfc3339()),
+            "prev_hash": self.prev_hash,
+            "receipt_count": self.receipt_count,
+            "sequence": self.sequence,
+            "timestamp": self.timestamp.to_rfc3339(),
+        });
+
+        Ok(canonicalize(&value))
+    }
+
+    /// Generate signed checkpoint
+    pub fn sign(&self, keypair: &Ed25519Keypair) -> Result<SignedReceipt> {
+        sign_canonical_json(&self.to_canonical_json(keypair)?, keypair)
+    }
+}
+
+/// Org-level receipt for an administrative action no pointer chain can
+/// carry, such as a chain freeze or an API key change, chained to the org's
+/// previous one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgReceiptData {
+    pub receipt_id: Uuid,
+    pub org_id: Uuid,
+    pub kind: String,
+    pub pointer_id: Option<Uuid>,
+    pub details: serde_json::Value,
+    pub prev_hash: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl OrgReceiptData {
+    /// Convert to canonical JSON (RFC 8785)
+    pub fn to_canonical_json(&self) -> Result<String> {
+        let value = json!({
+            "details": self.details,
+            "kind": self.kind,
+            "org_id": self.org_id,
+            "pointer_id": self.pointer_id,
+            "prev_hash": self.prev_hash,
+            "receipt_id": self.receipt_id,
+            "timestamp": self.timestamp.to_rfc3339(),
+        });
+
+        Ok(canonicalize(&value))
+    }
+
+    /// Generate signed org receipt
+    pub fn sign(&self, keypair: &Ed25519Keypair) -> Result<SignedReceipt> {
+        sign_canonical_json(&self.to_canonical_json()?, keypair)
+    }
+}
+
+/// Record of one garbage collection run over an org's unreferenced data rows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataGcManifest {
+    pub manifest_id: Uuid,
+    pub org_id: Uuid,
+    pub mode: String,
+    pub data_ids: Vec<Uuid>,
+    pub bytes_reclaimed: i64,
+    /// Rows released after this instant were not eligible
+    pub grace_cutoff: DateTime<Utc>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl DataGcManifest {
+    /// Convert to canonical JSON (RFC 8785)
+    pub fn to_canonical_json(&self) -> Result<String> {
+        let value = json!({
+            "bytes_reclaimed": self.bytes_reclaimed,
+            "count": self.data_ids.len(),
+            "data_ids": self.data_ids,
+            "grace_cutoff": self.grace_cutoff.to_rfc3339(),
+            "manifest_id": self.manifest_id,
+            "mode": self.mode,
+            "org_id": self.org_id,
+            "timestamp": self.timestamp.to_rfc3339(),
+        });
+
+        Ok(canonicalize(&value))
+    }
+
+    /// Generate signed manifest
+    pub fn sign(&self, keypair: &Ed25519Keypair) -> Result<SignedReceipt> {
+        sign_canonical_json(&self.to_canonical_json()?, keypair)
+    }
+}
+
+/// The signing input, for every receipt type: `canonical_json` is the
+/// RFC 8785 form of the receipt, receipt_hash is SHA3-512 over its UTF-8
+/// bytes as 128 lowercase hex digits, and the Ed25519 signature is over the
+/// 128 ASCII bytes of that hex string, not the raw 64-byte digest
+fn sign_canonical_json(canonical_json: &str, keypair: &Ed25519Keypair) -> Result<SignedReceipt> {
+    // 1. Hash with SHA3-512
+    let receipt_hash = sha3_512_hash_str(canonical_json);
+
+    // 2. Sign the hex hash with ED25519
+    let signature = keypair.sign(receipt_hash.as_bytes());
+
+    // 3. Return signed receipt
+    signed_receipt(canonical_json, receipt_hash, signature, keypair)
+}
+
+fn signed_receipt(
+    canonical_json: &str,
+    receipt_hash: String,
+    signature: ed25519_dalek::Signature,
+    keypair: &Ed25519Keypair,
+) -> Result<SignedReceipt> {
+    Ok(SignedReceipt {
+        receipt_json: serde_json::from_str(canonical_json)?,
+        receipt_hash,
+        signature: signature.to_bytes().to_vec(),
+        signature_algorithm: "ED25519".to_string(),
+        key_id: keypair.key_id(),
+        timings: None,
+        overflow: None,
+    })
+}
+
+/// One receipt's result within a chain check
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReceiptCheck {
+    pub receipt_id: Uuid,
+    pub receipt_hash: String,
+    pub prev_hash: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    /// Why the receipt itself failed to verify, if it did
+    pub failure: Option<ReceiptFailure>,
+    /// prev_hash names the preceding receipt (and is null on the first)
+    pub linked: bool,
+}
+
+/// Where the chain first stops linking up
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BrokenLink {
+    pub position: usize,
+    pub receipt_id: Uuid,
+    pub expected_prev_hash: Option<String>,
+    pub prev_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChainVerification {
+    /// Every receipt verified and every link holds
+    pub chain_valid: bool,
+    pub receipts: Vec<ReceiptCheck>,
+    pub first_broken_link: Option<BrokenLink>,
+}
+
+/// Verify a pointer's receipts, given in chain (timestamp) order: each one's
+/// hash and signature under the key that signed it, and that each prev_hash
+/// is the receipt_hash before it. Needs no database, so exported chains can
+/// be checked offline.
+pub fn verify_chain(receipts: &[GovernanceReceipt], keys: &impl ReceiptKeys) -> ChainVerification {
+    let mut checks = Vec::with_capacity(receipts.len());
+    let mut first_broken_link = None;
+    let mut expected_prev_hash: Option<&str> = None;
+
+    for (position, receipt) in receipts.iter().enumerate() {
+        let linked = receipt.prev_hash.as_deref() == expected_prev_hash;
+        if !linked && first_broken_link.is_none() {
+            first_broken_link = Some(BrokenLink {
+                position,
+                receipt_id: receipt.receipt_id,
+                expected_prev_hash: expected_prev_hash.map(str::to_string),
+                prev_hash: receipt.prev_hash.clone(),
+            });
+        }
+
+        checks.push(ReceiptCheck {
+            receipt_id: receipt.receipt_id,
+            receipt_hash: receipt.receipt_hash.clone(),
+            prev_hash: receipt.prev_hash.clone(),
+            timestamp: receipt.timestamp,
+            failure: verify_stored_receipt(receipt, keys).err(),
+            linked,
+        });
+        expected_prev_hash = Some(&receipt.receipt_hash);
+    }
+
+    ChainVerification {
+        chain_valid: first_broken_link.is_none() && checks.iter().all(|c| c.failure.is_none()),
+        receipts: checks,
+        first_broken_link,
+    }
+}
+
+/// Outcome of checking one receipt on its own, as handed over by whoever
+/// holds it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct VerificationResult {
+    /// SHA3-512 of the canonical form of the receipt JSON given
+    pub computed_hash: String,
+    /// computed_hash is the receipt_hash given
+    pub hash_valid: bool,
+    /// The signature is a valid signature of the receipt_hash given
+    pub signature_valid: bool,
+    /// First reason the receipt failed, if it did
+    pub failure: Option<ReceiptFailure>,
+}
+
+impl VerificationResult {
+    pub fn valid(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Verify a receipt from its JSON, hash and signature alone, without a
+/// database or this service: what a holder of an exported receipt runs. The
+/// JSON is canonicalized before hashing, so it need not be byte-for-byte
+/// what was signed, only the same value. `keys` is one trusted public key or
+/// a set of them; the receipt's own key_id picks among them.
+pub fn verify_receipt(
+    receipt_json: &serde_json::Value,
+    receipt_hash: &str,
+    signature_algorithm: &str,
+    signature: &[u8],
+    keys: &impl ReceiptKeys,
+) -> VerificationResult {
+    let computed_hash = stored_receipt_hash(receipt_json, receipt_hash);
+    let hash_valid = computed_hash == receipt_hash;
+
+    let key_id = receipt_json.get("key_id").and_then(|v| v.as_str());
+    let signature_check =
+        check_signature(signature_algorithm, signature, receipt_hash, key_id, keys);
+
+    let failure = if hash_valid {
+        signature_check.err()
+    } else {
+        Some(ReceiptFailure::HashMismatch)
+    };
+
+    VerificationResult {
+        computed_hash,
+        hash_valid,
+        signature_valid: signature_check.is_ok(),
+        failure,
+    }
+}
+
+/// Check that `signature` signs `receipt_hash` under one of the keys known
+/// for `key_id`
+pub fn check_signature(
+    signature_algorithm: &str,
+    signature: &[u8],
+    receipt_hash: &str,
+    key_id: Option<&str>,
+    keys: &impl ReceiptKeys,
+) -> Result<(), ReceiptFailure> {
+    if signature_algorithm != "ED25519" {
+        return Err(ReceiptFailure::UnsupportedAlgorithm);
+    }
+
+    let signature: [u8; 64] = signature
+        .try_into()
+        .map_err(|_| ReceiptFailure::InvalidSignature)?;
+    let signature = Signature::from_bytes(&signature);
+
+    let candidates = keys.keys_for(key_id);
+    if candidates.is_empty() {
+        return Err(ReceiptFailure::UnknownKey);
+    }
+    if candidates
+        .iter()
+        .any(|key| key.verify(receipt_hash.as_bytes(), &signature).is_ok())
+    {
+        Ok(())
+    } else {
+        Err(ReceiptFailure::InvalidSignature)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::{resolve_overflow, KeySet};
+
+    /// Three signed receipts, each linked to the one before
+    fn signed_chain(keypair: &Ed25519Keypair) -> Vec<GovernanceReceipt> {
+        signed_chain_by([keypair, keypair, keypair])
+    }
+
+    /// As `signed_chain`, each receipt signed by its own key
+    fn signed_chain_by(keypairs: [&Ed25519Keypair; 3]) -> Vec<GovernanceReceipt> {
+        let pointer_id = Uuid::new_v4();
+        let operations = [
+            ReceiptOperation::Create,
+            ReceiptOperation::Resolve,
+            ReceiptOperation::Orphan,
+        ];
+        let mut chain: Vec<GovernanceReceipt> = Vec::new();
+
+        for (operation, keypair) in operations.into_iter().zip(keypairs) {
+            let prev_hash = chain.last().map(|r| r.receipt_hash.clone());
+            let data = ReceiptData::new(
+                pointer_id,
+                operation.clone(),
+                "user_123".to_string(),
+                prev_hash.clone(),
+                json!({}),
+                Utc::now(),
+            );
+            let signed = data.sign(keypair).unwrap();
+            chain.push(GovernanceReceipt {
+                receipt_id: Uuid::new_v4(),
+                pointer_id,
+                org_id: Uuid::new_v4(),
+                operation,
+                receipt_json: signed.receipt_json,
+                receipt_hash: signed.receipt_hash,
+                signature: signed.signature,
+                signature_algorithm: signed.signature_algorithm,
+                key_id: Some(signed.key_id),
+                prev_hash,
+                timestamp: data.timestamp,
+                metadata: json!({}),
+                overflow: None,
+            });
+        }
+
+        chain
+    }
+
+    #[test]
+    fn test_verify_chain_accepts_intact_chain() {
+        let keypair = Ed25519Keypair::generate();
+        let chain = signed_chain(&keypair);
+
+        let verification = verify_chain(&chain, &keypair.verifying_key);
+        assert!(verification.chain_valid);
+        assert!(verification.first_broken_link.is_none());
+        assert!(verification
+            .receipts
+            .iter()
+            .all(|c| c.linked && c.failure.is_none()));
+    }
+
+    #[test]
+    fn test_verify_chain_across_key_rotation() {
+        let retired = Ed25519Keypair::generate();
+        let active = Ed25519Keypair::generate();
+        let chain = signed_chain_by([&retired, &retired, &active]);
+        assert_eq!(chain[0].key_id, Some(retired.key_id()));
+        assert_eq!(chain[2].key_id, Some(active.key_id()));
+
+        let mut keys = KeySet::default();
+        keys.insert(active.verifying_key);
+        let verification = verify_chain(&chain, &keys);
+        assert!(!verification.chain_valid);
+        assert_eq!(
+            verification.receipts[0].failure,
+            Some(ReceiptFailure::UnknownKey)
+        );
+        assert!(verification.receipts[2].failure.is_none());
+
+        keys.insert(retired.verifying_key);
+        assert!(verify_chain(&chain, &keys).chain_valid);
+    }
+
+    #[test]
+    fn test_verify_chain_detects_tampered_json() {
+        let keypair = Ed25519Keypair::generate();
+        let mut chain = signed_chain(&keypair);
+        chain[1].receipt_json["subject_id"] = json!("someone_else");
+
+        let verification = verify_chain(&chain, &keypair.verifying_key);
+        assert!(!verification.chain_valid);
+        assert_eq!(
+            verification.receipts[1].failure,
+            Some(ReceiptFailure::HashMismatch)
+        );
+        // The stored hash still links, so only the receipt itself fails
+        assert!(verification.first_broken_link.is_none());
+    }
+
+    #[test]
+    fn test_verify_chain_detects_forged_signature() {
+        let keypair = Ed25519Keypair::generate();
+        let mut chain = signed_chain(&keypair);
+        let forger = Ed25519Keypair::generate();
+        chain[2].signature = forger
+            .sign(chain[2].receipt_hash.as_bytes())
+            .to_bytes()
+            .to_vec();
+
+        let verification = verify_chain(&chain, &keypair.verifying_key);
+        assert!(!verification.chain_valid);
+        assert_eq!(
+            verification.receipts[2].failure,
+            Some(ReceiptFailure::InvalidSignature)
+        );
+        assert!(verification.receipts[..2]
+            .iter()
+            .all(|c| c.failure.is_none()));
+    }
+
+    #[test]
+    fn test_verify_chain_reports_first_broken_link() {
+        let keypair = Ed25519Keypair::generate();
+        let mut chain = signed_chain(&keypair);
+        // Dropping the middle receipt leaves the last one pointing at it
+        let removed = chain.remove(1);
+
+        let verification = verify_chain(&chain, &keypair.verifying_key);
+        assert!(!verification.chain_valid);
+        let broken = verification.first_broken_link.unwrap();
+        assert_eq!(broken.position, 1);
+        assert_eq!(broken.receipt_id, chain[1].receipt_id);
+        assert_eq!(
+            broken.expected_prev_hash,
+            Some(chain[0].receipt_hash.clone())
+        );
+        assert_eq!(broken.prev_hash, Some(removed.receipt_hash));
+        assert!(!verification.receipts[1].linked);
+    }
+
+    /// Golden vector: a verifier in any language canonicalizing these fields
+    /// per RFC 8785 must arrive at exactly this hash
+    #[test]
+    fn test_verify_receipt_from_its_parts_alone() {
+        let keypair = Ed25519Keypair::generate();
+        let receipt = signed_chain(&keypair).remove(1);
+        let verify = |json: &serde_json::Value, algorithm: &str, signature: &[u8]| {
+            verify_receipt(
+                json,
+                &receipt.receipt_hash,
+                algorithm,
+                signature,
+                &keypair.verifying_key,
+            )
+        };
+
+        let result = verify(&receipt.receipt_json, "ED25519", &receipt.signature);
+        assert!(result.valid());
+        assert_eq!(result.computed_hash, receipt.receipt_hash);
+
+        // Pretty-printed and reordered JSON is the same value, so it is
+        // canonicalized back to the bytes that were signed
+        let mut members: Vec<_> = receipt
+            .receipt_json
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(name, value)| format!("{}: {}", json!(name), value))
+            .collect();
+        members.reverse();
+        let resubmitted: serde_json::Value =
+            serde_json::from_str(&format!("{{\n  {}\n}}", members.join(",\n  "))).unwrap();
+        assert!(verify(&resubmitted, "ED25519", &receipt.signature).valid());
+
+        let mut tampered = receipt.receipt_json.clone();
+        tampered["subject_id"] = json!("someone_else");
+        let result = verify(&tampered, "ED25519", &receipt.signature);
+        assert!(!result.hash_valid);
+        assert!(result.signature_valid);
+        assert_eq!(result.failure, Some(ReceiptFailure::HashMismatch));
+
+        let result = verify(&receipt.receipt_json, "ED25519", &receipt.signature[..63]);
+        assert!(result.hash_valid);
+        assert!(!result.signature_valid);
+        assert_eq!(result.failure, Some(ReceiptFailure::InvalidSignature));
+
+        for algorithm in ["RSA", "ed25519", ""] {
+            assert_eq!(
+                verify(&receipt.receipt_json, algorithm, &receipt.signature).failure,
+                Some(ReceiptFailure::UnsupportedAlgorithm)
+            );
+        }
+
+        let other = Ed25519Keypair::generate();
+        let result = verify_receipt(
+            &receipt.receipt_json,
+            &receipt.receipt_hash,
+            "ED25519",
+            &receipt.signature,
+            &other.verifying_key,
+        );
+        assert_eq!(result.failure, Some(ReceiptFailure::UnknownKey));
+    }
+
+    #[test]
+    fn test_canonical_json_golden_vector() {
+        let receipt = |metadata: &str| ReceiptData {
+            pointer_id: Uuid::parse_str("00000000-0000-0000-0000-0000000000aa").unwrap(),
+            operation: "orphan".to_string(),
+            timestamp: DateTime::parse_from_rfc3339("2025-11-26T10:00:00.5Z")
+                .unwrap()
+                .into(),
+            subject_id: "user_123".to_string(),
+            prev_hash: Some("ab".repeat(64)),
+            metadata: serde_json::from_str(metadata).unwrap(),
+            key_id: Some("cd".repeat(32)),
+        };
+        let unordered = receipt(
+            r#"{"reason": "user_request", "detail": {"z": [3, 2.0, 1e0], "a": {"\u00e9": "caf\u00e9", "\ud83d\ude00": null, "b": true}}, "count": 1.0}"#,
+        );
+        let ordered = receipt(
+            r#"{"count":1,"detail":{"a":{"b":true,"é":"café","😀":null},"z":[3,2,1]},"reason":"user_request"}"#,
+        );
+
+        let canonical = unordered.to_canonical_json().unwrap();
+        assert_eq!(
+            canonical,
+            concat!(
+                r#"{"key_id":"cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd","#,
+                r#""metadata":{"count":1,"detail":{"a":{"b":true,"é":"café","😀":null},"#,
+                r#""z":[3,2,1]},"reason":"user_request"},"operation":"orphan","#,
+                r#""pointer_id":"00000000-0000-0000-0000-0000000000aa","#,
+                r#""prev_hash":"abababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababab","#,
+                r#""subject_id":"user_123","timestamp":"2025-11-26T10:00:00.500+00:00"}"#
+            )
+        );
+        assert_eq!(ordered.to_canonical_json().unwrap(), canonical);
+        assert_eq!(
+            sha3_512_hash_str(&canonical),
+            "363395157e7f4c86a3036d9fd020d7bcd3a0726ea91c79ecaa6726b257db20e9\
+             6487a837dd0b2de34b27381e51e12f9e444b8f214c084c8608d219586d58da2c"
+        );
+    }
 
     #[test]
     fn test_canonical_json_deterministic() {
@@ -98,6 +836,7 @@ mod tests {
             "user_123".to_string(),
             None,
             json!({"test": "data"}),
+            Utc::now(),
         );
 
         let json1 = receipt1.to_canonical_json().unwrap();
@@ -115,6 +854,7 @@ mod tests {
             "user_123".to_string(),
             None,
             json!({}),
+            Utc::now(),
         );
 
         let signed = receipt.sign(&keypair).unwrap();
@@ -123,4 +863,107 @@ mod tests {
         assert_eq!(signed.signature.len(), 64); // ED25519 signature is 64 bytes
         assert!(!signed.receipt_hash.is_empty());
     }
+
+    #[test]
+    fn test_sign_timed_records_phases_outside_signed_payload() {
+        let keypair = Ed25519Keypair::generate();
+        let receipt = ReceiptData::new(
+            Uuid::new_v4(),
+            ReceiptOperation::Create,
+            "user_123".to_string(),
+            None,
+            json!({"blob": "x".repeat(100_000)}),
+            Utc::now(),
+        );
+
+        let untimed = receipt.sign(&keypair).unwrap();
+        let timed = receipt.sign_timed(&keypair).unwrap();
+        assert!(untimed.timings.is_none());
+        assert_eq!(timed.receipt_hash, untimed.receipt_hash);
+        assert_eq!(timed.signature, untimed.signature);
+
+        let timings = timed.timings.unwrap();
+        assert_eq!(
+            timings.canonical_bytes,
+            receipt.signed_canonical_json(&keypair).unwrap().len()
+        );
+        assert!(timings.canonicalize > Duration::ZERO);
+        assert!(timings.hash > Duration::ZERO);
+        assert!(timings.sign > Duration::ZERO);
+        assert_eq!(
+            timings.total(),
+            timings.canonicalize + timings.hash + timings.sign
+        );
+
+        let serialized = serde_json::to_value(&timed).unwrap();
+        assert!(serialized.get("timings").is_none());
+    }
+
+    #[test]
+    fn test_group_manifest_members_hash() {
+        let members = vec![
+            GroupMember {
+                pointer_id: Uuid::new_v4(),
+                receipt_hash: "aa".to_string(),
+            },
+            GroupMember {
+                pointer_id: Uuid::new_v4(),
+                receipt_hash: "bb".to_string(),
+            },
+        ];
+
+        let manifest = OrphanGroupManifest::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "user_consent_revoked".to_string(),
+            members.clone(),
+        );
+
+        assert_eq!(manifest.members_hash, sha3_512_hash_str("aabb"));
+
+        // Member order is part of the evidence
+        let reversed: Vec<GroupMember> = members.into_iter().rev().collect();
+        assert_ne!(
+            manifest.members_hash,
+            OrphanGroupManifest::members_hash(&reversed)
+        );
+
+        let keypair = Ed25519Keypair::generate();
+        let signed = manifest.sign(&keypair, 65536).unwrap();
+        assert_eq!(signed.receipt_json["members_hash"], manifest.members_hash);
+        assert_eq!(signed.signature.len(), 64);
+        assert!(signed.overflow.is_none());
+    }
+
+    #[test]
+    fn test_large_group_manifest_overflows_its_members() {
+        let members: Vec<GroupMember> = (0..1000)
+            .map(|_| GroupMember {
+                pointer_id: Uuid::new_v4(),
+                receipt_hash: "ab".repeat(64),
+            })
+            .collect();
+        let manifest = OrphanGroupManifest::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "user_consent_revoked".to_string(),
+            members,
+        );
+        let keypair = Ed25519Keypair::generate();
+
+        let signed = manifest.sign(&keypair, 65536).unwrap();
+        let overflow = signed.overflow.clone().unwrap();
+        assert!(serde_json::to_string(&signed.receipt_json).unwrap().len() <= 65536);
+        assert_eq!(
+            signed.receipt_json["members"]["overflow"]["sha3_512"],
+            json!(overflow.overflow_hash)
+        );
+        // Still committed to by members_hash, and recoverable in full
+        assert_eq!(signed.receipt_json["members_hash"], manifest.members_hash);
+        let listed = resolve_overflow(&signed.receipt_json["members"], Some(&overflow.content))
+            .unwrap()
+            .unwrap();
+        assert_eq!(listed.as_array().unwrap().len(), 1000);
+        assert_eq!(listed, manifest.to_canonical_value()["members"]);
+    }
 }
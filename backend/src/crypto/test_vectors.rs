@@ -0,0 +1,332 @@
+// Interop test vectors for receipt canonicalization and signing
+// Partner verifiers (Python, Go, ...) check their implementation against
+// these; the checked-in file is regenerated with `gen-vectors`
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use data_encoding::BASE64;
+use serde::Serialize;
+use serde_json::json;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Ed25519Keypair, ReceiptData};
+
+/// Bump when a vector's expected output changes
+pub const TEST_VECTORS_VERSION: u32 = 3;
+
+/// Checked-in copy served to partners and diffed by the self-check test
+pub const TEST_VECTORS_PATH: &str = "test_vectors/receipts_v3.json";
+
+/// Secret seed of the published test key. Never use it for real receipts.
+const TEST_KEY_SEED: &[u8; 32] = b"veto-frontier-interop-test-key!!";
+
+/// Longest subject_id the schema accepts (VARCHAR(255), in characters)
+const MAX_SUBJECT_CHARS: usize = 255;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TestVectorFile {
+    pub version: u32,
+    pub canonicalization: &'static str,
+    pub hash_algorithm: &'static str,
+    pub signature_algorithm: &'static str,
+    pub signed_message: &'static str,
+    #[schema(format = Byte)]
+    pub public_key: String,
+    pub vectors: Vec<TestVector>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TestVector {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub input: ReceiptData,
+    pub canonical_json: String,
+    #[schema(format = Byte)]
+    pub canonical_bytes: String,
+    pub receipt_hash: String,
+    #[schema(format = Byte)]
+    pub signature: String,
+}
+
+pub fn test_keypair() -> Ed25519Keypair {
+    Ed25519Keypair::from_seed(TEST_KEY_SEED)
+}
+
+struct Case {
+    name: &'static str,
+    description: &'static str,
+    timestamp: &'static str,
+    subject_id: String,
+    prev_hash: Option<String>,
+    metadata: serde_json::Value,
+}
+
+fn cases() -> Vec<Case> {
+    let chained = Some("ab".repeat(64));
+    let base = |name, description, metadata| Case {
+        name,
+        description,
+        timestamp: "2025-11-26T10:00:00Z",
+        subject_id: "user_123".to_string(),
+        prev_hash: chained.clone(),
+        metadata,
+    };
+
+    vec![
+        Case {
+            prev_hash: None,
+            ..base(
+                "first_receipt_null_prev_hash",
+                "First receipt in a chain: prev_hash is serialized as null, not omitted",
+                json!({"data_id": "00000000-0000-0000-0000-00000000d001"}),
+            )
+        },
+        base(
+            "chained_prev_hash",
+            "prev_hash is the previous receipt_hash as lowercase hex",
+            json!({"data_id": "00000000-0000-0000-0000-00000000d001"}),
+        ),
+        base(
+            "empty_metadata",
+            "Empty metadata object serializes as {}",
+            json!({}),
+        ),
+        base(
+            "metadata_key_order",
+            "Object members are sorted by name at every level, arrays keep their order",
+            json!({
+                "zeta": 1,
+                "Alpha": 2,
+                "alpha": 3,
+                "_underscore": 4,
+                "10": 5,
+                "9": 6,
+                "list": ["b", "a", {"y": 1, "x": 2}],
+            }),
+        ),
+        base(
+            "utf16_key_order",
+            "Member names sort by UTF-16 code units, not UTF-8 bytes: U+1F600 (surrogates \
+             D83D DE00) sorts before U+FB33, though its UTF-8 bytes sort after",
+            json!({
+                "\u{fb33}": "hebrew letter dalet with dagesh",
+                "\u{1f600}": "grinning face",
+                "\u{20ac}": "euro sign",
+                "\u{f6}": "o with diaeresis",
+                "\u{80}": "control",
+                "1": "one",
+                "\r": "carriage return",
+            }),
+        ),
+        base(
+            "deep_nesting",
+            "Nested objects and arrays are canonicalized recursively",
+            json!({"a": {"b": {"c": {"d": {"e": {"f": {"g": {"h": [[[{"z": null, "y": true}]]]}}}}}}}}),
+        ),
+        base(
+            "scalar_types",
+            "Numbers print as ECMAScript prints IEEE-754 doubles: 1.0 is 1, integers past 2^53 \
+             round to the nearest double, exponents from 1e21 up and below 1e-6",
+            json!({
+                "bool_false": false,
+                "bool_true": true,
+                "float": 0.5,
+                "float_whole": 1.0,
+                "negative": -42,
+                "null": null,
+                "zero": 0,
+                "large": 9007199254740993u64,
+                "exponent": 1e21,
+                "below_exponent": 1e20,
+                "tiny": 0.0000001,
+                "small": 0.000001,
+                "negative_zero": -0.0,
+            }),
+        ),
+        Case {
+            subject_id: "usér_名前_😀".to_string(),
+            ..base(
+                "unicode_subject",
+                "Non-ASCII is emitted as raw UTF-8, never \\u escaped (no ensure_ascii)",
+                json!({"note": "naïve café — 日本語 🎉"}),
+            )
+        },
+        Case {
+            subject_id: "user@example.com".to_string(),
+            ..base(
+                "html_and_slash",
+                "<, >, & and / are not escaped (disable Go's HTML escaping)",
+                json!({"url": "https://example.com/a?b=1&c=<d>"}),
+            )
+        },
+        base(
+            "control_characters",
+            "\\\" \\\\ \\n \\t \\r \\b \\f use short escapes, other C0 controls lowercase \\u00XX; DEL is not escaped",
+            json!({"text": "line1\nline2\ttab \"quoted\" back\\slash \u{1} \u{1f} \r \u{8} \u{c} \u{7f}"}),
+        ),
+        Case {
+            timestamp: "2025-11-26T10:00:00.123456789Z",
+            ..base(
+                "fractional_timestamp",
+                "Canonical timestamps use +00:00 (not Z) and 0, 3, 6 or 9 fraction digits",
+                json!({}),
+            )
+        },
+        Case {
+            subject_id: "s".repeat(MAX_SUBJECT_CHARS),
+            ..base(
+                "max_length_subject_ascii",
+                "subject_id at the 255-character limit",
+                json!({}),
+            )
+        },
+        Case {
+            subject_id: "é".repeat(MAX_SUBJECT_CHARS),
+            ..base(
+                "max_length_subject_multibyte",
+                "255 two-byte characters: the limit counts characters, not bytes",
+                json!({}),
+            )
+        },
+    ]
+}
+
+/// Build every vector, signed with the fixed test key
+pub fn generate_test_vectors() -> Result<TestVectorFile> {
+    let keypair = test_keypair();
+    let pointer_id = Uuid::parse_str("00000000-0000-0000-0000-0000000000aa")?;
+
+    let vectors = cases()
+        .into_iter()
+        .map(|case| {
+            let input = ReceiptData {
+                pointer_id,
+                operation: "create".to_string(),
+                timestamp: DateTime::parse_from_rfc3339(case.timestamp)
+                    .with_context(|| format!("Bad timestamp in vector {}", case.name))?
+                    .into(),
+                subject_id: case.subject_id,
+                prev_hash: case.prev_hash,
+                metadata: case.metadata,
+                key_id: Some(keypair.key_id()),
+            };
+
+            let canonical_json = input.to_canonical_json()?;
+            let signed = input.sign(&keypair)?;
+
+            Ok(TestVector {
+                name: case.name,
+                description: case.description,
+                input,
+                canonical_bytes: BASE64.encode(canonical_json.as_bytes()),
+                canonical_json,
+                receipt_hash: signed.receipt_hash,
+                signature: BASE64.encode(&signed.signature),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(TestVectorFile {
+        version: TEST_VECTORS_VERSION,
+        canonicalization: "RFC 8785 (JCS) of {key_id, metadata, operation, pointer_id, \
+            prev_hash, subject_id, timestamp}, as UTF-8",
+        hash_algorithm: "SHA3-512, lowercase hex",
+        signature_algorithm: "ED25519",
+        signed_message: "ASCII bytes of the lowercase hex receipt_hash",
+        public_key: BASE64.encode(&keypair.public_key_bytes()),
+        vectors,
+    })
+}
+
+/// The vector file as written to disk: pretty-printed with a trailing newline
+pub fn render_test_vectors() -> Result<String> {
+    Ok(serde_json::to_string_pretty(&generate_test_vectors()?)? + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::hashing::sha3_512_hash;
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    #[test]
+    fn test_checked_in_vectors_are_current() {
+        let checked_in = std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(TEST_VECTORS_PATH),
+        )
+        .unwrap();
+
+        assert!(
+            checked_in == render_test_vectors().unwrap(),
+            "{} is stale; regenerate with `cargo run -- gen-vectors` \
+             and bump TEST_VECTORS_VERSION if outputs changed",
+            TEST_VECTORS_PATH
+        );
+    }
+
+    #[test]
+    fn test_vectors_verify_independently() {
+        let file = generate_test_vectors().unwrap();
+        let public_key: [u8; 32] = BASE64
+            .decode(file.public_key.as_bytes())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let verifying_key = VerifyingKey::from_bytes(&public_key).unwrap();
+
+        for vector in &file.vectors {
+            let bytes = BASE64.decode(vector.canonical_bytes.as_bytes()).unwrap();
+            assert_eq!(bytes, vector.canonical_json.as_bytes(), "{}", vector.name);
+            assert_eq!(
+                sha3_512_hash(&bytes),
+                vector.receipt_hash,
+                "{}",
+                vector.name
+            );
+
+            let signature: [u8; 64] = BASE64
+                .decode(vector.signature.as_bytes())
+                .unwrap()
+                .try_into()
+                .unwrap();
+            assert!(
+                verifying_key
+                    .verify(
+                        vector.receipt_hash.as_bytes(),
+                        &Signature::from_bytes(&signature)
+                    )
+                    .is_ok(),
+                "{}",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_vectors_cover_edge_cases() {
+        let file = generate_test_vectors().unwrap();
+        let inputs: Vec<&ReceiptData> = file.vectors.iter().map(|v| &v.input).collect();
+
+        assert!(inputs.iter().any(|i| i.prev_hash.is_none()));
+        assert!(inputs.iter().any(|i| i.metadata == json!({})));
+        assert!(inputs.iter().any(|i| !i.subject_id.is_ascii()));
+        assert!(inputs
+            .iter()
+            .any(|i| i.subject_id.chars().count() == MAX_SUBJECT_CHARS && i.subject_id.is_ascii()));
+        assert!(
+            inputs
+                .iter()
+                .any(|i| i.subject_id.chars().count() == MAX_SUBJECT_CHARS
+                    && !i.subject_id.is_ascii())
+        );
+
+        let mut names: Vec<&str> = file.vectors.iter().map(|v| v.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(
+            names.len(),
+            file.vectors.len(),
+            "vector names must be unique"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+// Read-time verification of stored receipts
+// Used by strict receipt reads: every receipt is re-hashed and its signature
+// checked before it is served, with signature checks cached per receipt
+use std::{collections::HashMap, sync::Mutex};
+use uuid::Uuid;
+
+use super::{
+    check_signature, hashing::sha3_512_hash_str, jcs::canonicalize, verify_overflow, ReceiptKeys,
+};
+use crate::db::models::GovernanceReceipt;
+pub use veto_types::ReceiptFailure;
+
+/// Entries kept before the cache is cleared and starts over
+const MAX_CACHED_VERIFICATIONS: usize = 100_000;
+
+struct CachedVerification {
+    pointer_id: Uuid,
+    receipt_hash: String,
+    computed_hash: String,
+    signature: Vec<u8>,
+    overflow_hash: Option<String>,
+    result: Result<(), ReceiptFailure>,
+}
+
+/// Caches results by receipt_id. The hash is always recomputed from the
+/// stored JSON (cheap next to a signature check), and a cached result is
+/// only reused while the row's JSON, hash, signature and overflow record are
+/// unchanged.
+#[derive(Default)]
+pub struct ReceiptVerifier {
+    cache: Mutex<HashMap<Uuid, CachedVerification>>,
+}
+
+impl ReceiptVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify a stored receipt. Returns whether the result came from a fresh
+    /// check, so callers can alert once per failure rather than per read.
+    pub fn verify(
+        &self,
+        receipt: &GovernanceReceipt,
+        keys: &impl ReceiptKeys,
+    ) -> (Result<(), ReceiptFailure>, bool) {
+        let computed_hash = stored_receipt_hash(&receipt.receipt_json, &receipt.receipt_hash);
+        let overflow_hash = receipt.overflow.as_deref().map(sha3_512_hash_str);
+
+        if let Some(cached) = self.cache.lock().unwrap().get(&receipt.receipt_id) {
+            if cached.computed_hash == computed_hash
+                && cached.receipt_hash == receipt.receipt_hash
+                && cached.signature == receipt.signature
+                && cached.overflow_hash == overflow_hash
+            {
+                return (cached.result, false);
+            }
+        }
+
+        let result = if computed_hash != receipt.receipt_hash {
+            Err(ReceiptFailure::HashMismatch)
+        } else {
+            verify_signature(receipt, keys).and_then(|()| verify_receipt_overflow(receipt))
+        };
+
+        // The key may turn up later, such as after a rotation elsewhere
+        if result == Err(ReceiptFailure::UnknownKey) {
+            return (result, true);
+        }
+
+        let mut cache = self.cache.lock().unwrap();
+        if cache.len() >= MAX_CACHED_VERIFICATIONS {
+            cache.clear();
+        }
+        cache.insert(
+            receipt.receipt_id,
+            CachedVerification {
+                pointer_id: receipt.pointer_id,
+                receipt_hash: receipt.receipt_hash.clone(),
+                computed_hash,
+                signature: receipt.signature.clone(),
+                overflow_hash,
+                result,
+            },
+        );
+
+        (result, true)
+    }
+
+    /// Forget cached results for a pointer's receipts after a repair
+    pub fn invalidate_pointer(&self, pointer_id: Uuid) {
+        self.cache
+            .lock()
+            .unwrap()
+            .retain(|_, cached| cached.pointer_id != pointer_id);
+    }
+}
+
+/// SHA3-512 of a stored receipt's canonical JSON. Receipts hashed before
+/// RFC 8785 canonicalization used serde_json's output, which differs only
+/// in number formatting and in the order of member names outside the BMP;
+/// that hash is returned instead when it is the one `recorded`.
+pub fn stored_receipt_hash(receipt_json: &serde_json::Value, recorded: &str) -> String {
+    let hash = sha3_512_hash_str(&canonicalize(receipt_json));
+    if hash == recorded {
+        return hash;
+    }
+
+    match serde_json::to_string(receipt_json) {
+        Ok(legacy) if sha3_512_hash_str(&legacy) == recorded => recorded.to_string(),
+        _ => hash,
+    }
+}
+
+/// Check a stored receipt's hash, signature and any overflow record,
+/// without caching
+pub fn verify_stored_receipt(
+    receipt: &GovernanceReceipt,
+    keys: &impl ReceiptKeys,
+) -> Result<(), ReceiptFailure> {
+    if stored_receipt_hash(&receipt.receipt_json, &receipt.receipt_hash) != receipt.receipt_hash {
+        return Err(ReceiptFailure::HashMismatch);
+    }
+
+    verify_signature(receipt, keys)?;
+    verify_receipt_overflow(receipt)
+}
+
+/// Overflowed metadata must come with its record, read into `overflow`
+fn verify_receipt_overflow(receipt: &GovernanceReceipt) -> Result<(), ReceiptFailure> {
+    verify_overflow(
+        &receipt.receipt_json["metadata"],
+        receipt.overflow.as_deref(),
+    )
+}
+
+fn verify_signature(
+    receipt: &GovernanceReceipt,
+    keys: &impl ReceiptKeys,
+) -> Result<(), ReceiptFailure> {
+    check_signature(
+        &receipt.signature_algorithm,
+        &receipt.signature,
+        &receipt.receipt_hash,
+        receipt.key_id.as_deref(),
+        keys,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::{Ed25519Keypair, ReceiptData},
+        db::models::ReceiptOperation,
+    };
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn stored_receipt(keypair: &Ed25519Keypair) -> GovernanceReceipt {
+        let pointer_id = Uuid::new_v4();
+        let signed = ReceiptData::new(
+            pointer_id,
+            ReceiptOperation::Create,
+            "user_123".to_string(),
+            None,
+            json!({}),
+            Utc::now(),
+        )
+        .sign(keypair)
+        .unwrap();
+
+        GovernanceReceipt {
+            receipt_id: Uuid::new_v4(),
+            pointer_id,
+            org_id: Uuid::new_v4(),
+            operation: ReceiptOperation::Create,
+            receipt_json: signed.receipt_json,
+            receipt_hash: signed.receipt_hash,
+            signature: signed.signature,
+            signature_algorithm: signed.signature_algorithm,
+            key_id: Some(signed.key_id),
+            prev_hash: None,
+            timestamp: chrono::Utc::now(),
+            metadata: json!({}),
+            overflow: None,
+        }
+    }
+
+    #[test]
+    fn test_verification_failures() {
+        let keypair = Ed25519Keypair::generate();
+        let verifier = ReceiptVerifier::new();
+        let key = &keypair.verifying_key;
+
+        let receipt = stored_receipt(&keypair);
+        assert_eq!(verifier.verify(&receipt, key).0, Ok(()));
+
+        let mut edited = stored_receipt(&keypair);
+        edited.receipt_json["subject_id"] = json!("someone_else");
+        assert_eq!(
+            verifier.verify(&edited, key).0,
+            Err(ReceiptFailure::HashMismatch)
+        );
+
+        let forger = Ed25519Keypair::generate();
+        let mut forged = stored_receipt(&keypair);
+        forged.signature = forger
+            .sign(forged.receipt_hash.as_bytes())
+            .to_bytes()
+            .to_vec();
+        assert_eq!(
+            verifier.verify(&forged, key).0,
+            Err(ReceiptFailure::InvalidSignature)
+        );
+
+        // Signed by a key the verifier was not given
+        let foreign = stored_receipt(&forger);
+        assert_eq!(
+            verifier.verify(&foreign, key).0,
+            Err(ReceiptFailure::UnknownKey)
+        );
+
+        let mut unknown = stored_receipt(&keypair);
+        unknown.signature_algorithm = "ML-DSA-65".to_string();
+        assert_eq!(
+            verifier.verify(&unknown, key).0,
+            Err(ReceiptFailure::UnsupportedAlgorithm)
+        );
+    }
+
+    #[test]
+    fn test_receipts_hashed_before_jcs_still_verify() {
+        let keypair = Ed25519Keypair::generate();
+        let mut legacy = stored_receipt(&keypair);
+        // serde_json prints a whole float as 1.0 where RFC 8785 prints 1
+        legacy.receipt_json["metadata"] = json!({"ratio": 1.0});
+        let legacy_json = serde_json::to_string(&legacy.receipt_json).unwrap();
+        assert_ne!(legacy_json, canonicalize(&legacy.receipt_json));
+        legacy.receipt_hash = sha3_512_hash_str(&legacy_json);
+        legacy.signature = keypair
+            .sign(legacy.receipt_hash.as_bytes())
+            .to_bytes()
+            .to_vec();
+
+        assert_eq!(
+            verify_stored_receipt(&legacy, &keypair.verifying_key),
+            Ok(())
+        );
+        assert_eq!(
+            ReceiptVerifier::new()
+                .verify(&legacy, &keypair.verifying_key)
+                .0,
+            Ok(())
+        );
+
+        legacy.receipt_json["metadata"]["ratio"] = json!(2.0);
+        assert_eq!(
+            verify_stored_receipt(&legacy, &keypair.verifying_key),
+            Err(ReceiptFailure::HashMismatch)
+        );
+    }
+
+    #[test]
+    fn test_cache_reuses_only_matching_entries() {
+        let keypair = Ed25519Keypair::generate();
+        let verifier = ReceiptVerifier::new();
+        let key = &keypair.verifying_key;
+        let mut receipt = stored_receipt(&keypair);
+
+        assert_eq!(verifier.verify(&receipt, key), (Ok(()), true));
+        assert_eq!(verifier.verify(&receipt, key), (Ok(()), false));
+
+        // Editing the stored JSON is caught despite the cached result
+        let original = receipt.receipt_json.clone();
+        receipt.receipt_json["metadata"] = json!({"edited": true});
+        assert_eq!(
+            verifier.verify(&receipt, key),
+            (Err(ReceiptFailure::HashMismatch), true)
+        );
+        assert_eq!(
+            verifier.verify(&receipt, key),
+            (Err(ReceiptFailure::HashMismatch), false)
+        );
+        receipt.receipt_json = original;
+
+        // A swapped signature is checked afresh
+        receipt.signature = stored_receipt(&keypair).signature;
+        assert_eq!(
+            verifier.verify(&receipt, key),
+            (Err(ReceiptFailure::InvalidSignature), true)
+        );
+
+        assert_eq!(
+            verifier.verify(&receipt, key),
+            (Err(ReceiptFailure::InvalidSignature), false)
+        );
+        verifier.invalidate_pointer(receipt.pointer_id);
+        assert!(verifier.verify(&receipt, key).1);
+    }
+}
@@ -0,0 +1,128 @@
+// Database circuit breaker
+// Consecutive connection-level failures open the circuit; while it is open,
+// callers fail fast (or degrade) instead of each waiting out the pool's
+// acquire timeout against a database that is not there
+use anyhow::Result;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+#[derive(Default)]
+struct CircuitState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_for: Duration,
+    state: Mutex<CircuitState>,
+}
+
+impl CircuitBreaker {
+    /// Opens after `failure_threshold` consecutive unavailability errors and
+    /// stays open for `open_for`; after that the next call is let through as
+    /// a probe, closing the circuit on success and reopening it on failure
+    pub fn new(failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_for,
+            state: Mutex::new(CircuitState::default()),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state
+            .opened_at
+            .is_some_and(|opened_at| opened_at.elapsed() < self.open_for)
+    }
+
+    pub fn record_success(&self) {
+        *self.state.lock().unwrap() = CircuitState::default();
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.failure_threshold {
+            state.opened_at = Some(Instant::now());
+        }
+    }
+
+    /// Feed a database result into the breaker. Unavailability becomes
+    /// `Ok(None)` so the caller can degrade; any other error means the
+    /// database answered and is passed through.
+    pub fn observe<T>(&self, result: Result<T>) -> Result<Option<T>> {
+        match result {
+            Ok(value) => {
+                self.record_success();
+                Ok(Some(value))
+            }
+            Err(e) if is_unavailable(&e) => {
+                self.record_failure();
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Whether an error means the database could not be reached at all, as
+/// opposed to a query it answered with an error
+pub fn is_unavailable(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<sqlx::Error>(),
+            Some(
+                sqlx::Error::PoolTimedOut
+                    | sqlx::Error::PoolClosed
+                    | sqlx::Error::Io(_)
+                    | sqlx::Error::Tls(_)
+                    | sqlx::Error::WorkerCrashed
+            )
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unavailable() -> Result<()> {
+        Err(sqlx::Error::PoolTimedOut.into())
+    }
+
+    #[test]
+    fn test_opens_after_threshold_and_closes_on_success() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+
+        assert_eq!(breaker.observe(unavailable()).unwrap(), None);
+        assert!(!breaker.is_open());
+        assert_eq!(breaker.observe(unavailable()).unwrap(), None);
+        assert!(breaker.is_open());
+
+        assert_eq!(breaker.observe(Ok(7)).unwrap(), Some(7));
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn test_query_errors_pass_through_without_tripping() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+
+        let answered: Result<()> = Err(sqlx::Error::RowNotFound.into());
+        assert!(breaker.observe(answered).is_err());
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn test_lets_a_probe_through_once_the_open_window_ends() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+
+        breaker.record_failure();
+        assert!(!breaker.is_open(), "zero-length window: next call probes");
+        breaker.record_failure();
+        assert!(!breaker.is_open());
+    }
+}
@@ -0,0 +1,165 @@
+// Database constraint violation mapping
+// Turns Postgres unique (23505) and foreign key (23503) violations into
+// machine-readable conflicts instead of generic 500s
+use sqlx::postgres::PgDatabaseError;
+
+const UNIQUE_VIOLATION: &str = "23505";
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+const OBJECT_NOT_IN_PREREQUISITE_STATE: &str = "55000";
+
+/// Raised by the governance_receipts guard for a frozen pointer chain
+const CHAIN_FROZEN: &str = "pointer_chain_frozen";
+
+/// A constraint violation reported by Postgres
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintViolation {
+    Unique { constraint: String },
+    ForeignKey { constraint: String },
+}
+
+impl ConstraintViolation {
+    pub fn constraint(&self) -> &str {
+        match self {
+            ConstraintViolation::Unique { constraint } => constraint,
+            ConstraintViolation::ForeignKey { constraint } => constraint,
+        }
+    }
+}
+
+/// Conflict a known constraint maps to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownConflict {
+    pub conflict_type: &'static str,
+    pub message: &'static str,
+}
+
+/// Constraints with a client-meaningful conflict, keyed by constraint name.
+/// Register new unique indexes here so they surface as 409s.
+const KNOWN_CONFLICTS: &[(&str, KnownConflict)] = &[
+    (
+        "orphan_groups_pkey",
+        KnownConflict {
+            conflict_type: "orphan_group_exists",
+            message: "An orphan group with this id already exists",
+        },
+    ),
+    (
+        "idx_pointer_chain_freezes_open",
+        KnownConflict {
+            conflict_type: "pointer_chain_already_frozen",
+            message: "Pointer chain is already frozen",
+        },
+    ),
+    (
+        "idx_pointer_restrictions_open",
+        KnownConflict {
+            conflict_type: "pointer_already_restricted",
+            message: "Pointer already has an open processing restriction",
+        },
+    ),
+    (
+        "idx_orphan_requests_pending",
+        KnownConflict {
+            conflict_type: "orphan_request_pending",
+            message: "Pointer already has a pending orphan request",
+        },
+    ),
+    (
+        "idx_pointers_org_external_ref",
+        KnownConflict {
+            conflict_type: "external_ref_exists",
+            message: "Another pointer of the organization has this external_ref",
+        },
+    ),
+    (
+        "data_store_org_id_fkey",
+        KnownConflict {
+            conflict_type: "unknown_organization",
+            message: "Organization does not exist",
+        },
+    ),
+    (
+        "api_keys_org_id_fkey",
+        KnownConflict {
+            conflict_type: "unknown_organization",
+            message: "Organization does not exist",
+        },
+    ),
+    (
+        "pointers_org_id_fkey",
+        KnownConflict {
+            conflict_type: "unknown_organization",
+            message: "Organization does not exist",
+        },
+    ),
+    (
+        "orphan_groups_org_id_fkey",
+        KnownConflict {
+            conflict_type: "unknown_organization",
+            message: "Organization does not exist",
+        },
+    ),
+    (
+        "pointers_data_id_fkey",
+        KnownConflict {
+            conflict_type: "unknown_data",
+            message: "Referenced data object does not exist",
+        },
+    ),
+    (
+        "governance_receipts_pointer_id_fkey",
+        KnownConflict {
+            conflict_type: "unknown_pointer",
+            message: "Referenced pointer does not exist",
+        },
+    ),
+];
+
+/// Extract a unique or foreign key violation from a sqlx error
+pub fn constraint_violation(err: &sqlx::Error) -> Option<ConstraintViolation> {
+    let db_err = err.as_database_error()?;
+    let pg_err = db_err.try_downcast_ref::<PgDatabaseError>()?;
+    let constraint = pg_err.constraint().unwrap_or_default().to_string();
+
+    match pg_err.code() {
+        UNIQUE_VIOLATION => Some(ConstraintViolation::Unique { constraint }),
+        FOREIGN_KEY_VIOLATION => Some(ConstraintViolation::ForeignKey { constraint }),
+        _ => None,
+    }
+}
+
+/// The guard's message if the error is a receipt refused on a frozen chain
+pub fn chain_frozen(err: &sqlx::Error) -> Option<String> {
+    let db_err = err.as_database_error()?;
+    let pg_err = db_err.try_downcast_ref::<PgDatabaseError>()?;
+
+    (pg_err.code() == OBJECT_NOT_IN_PREREQUISITE_STATE && pg_err.constraint() == Some(CHAIN_FROZEN))
+        .then(|| pg_err.message().to_string())
+}
+
+/// Look up the conflict registered for a constraint name
+pub fn known_conflict(constraint: &str) -> Option<KnownConflict> {
+    KNOWN_CONFLICTS
+        .iter()
+        .find(|(name, _)| *name == constraint)
+        .map(|(_, conflict)| *conflict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_conflict_lookup() {
+        assert_eq!(
+            known_conflict("pointers_org_id_fkey").map(|c| c.conflict_type),
+            Some("unknown_organization")
+        );
+        assert!(known_conflict("resolve_stats_pkey").is_none());
+    }
+
+    #[test]
+    fn test_non_database_errors_are_not_violations() {
+        assert!(constraint_violation(&sqlx::Error::RowNotFound).is_none());
+    }
+}
@@ -0,0 +1,200 @@
+// Schema migrations
+// backend/migrations is embedded in the binary and applied at startup when
+// RUN_MIGRATIONS is set. Either way, startup refuses a database whose schema
+// is newer than the binary, rather than failing requests against it later.
+use anyhow::{anyhow, bail, Context, Result};
+use sqlx::{
+    migrate::{Migrate, Migrator},
+    PgPool,
+};
+use tracing::{info, warn};
+
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Version of the initial migration, which is the schema previously applied
+/// by hand from database/schema.sql
+const BASELINE_VERSION: i64 = 1;
+
+/// Latest migration this binary carries
+pub fn latest_version() -> i64 {
+    MIGRATOR.iter().map(|m| m.version).max().unwrap_or(0)
+}
+
+/// Latest migration applied to the database; None when it was never
+/// migrated
+pub async fn applied_version(pool: &PgPool) -> Result<Option<i64>> {
+    let tracked: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await
+        .context("Failed to look up the migrations table")?;
+    if !tracked {
+        return Ok(None);
+    }
+
+    sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+        .fetch_one(pool)
+        .await
+        .context("Failed to read the applied migration version")
+}
+
+/// Fail when the database has migrations this binary doesn't know about
+pub async fn ensure_not_ahead(pool: &PgPool) -> Result<Option<i64>> {
+    let applied = applied_version(pool).await?;
+    let latest = latest_version();
+    if let Some(applied) = applied.filter(|&applied| applied > latest) {
+        bail!(
+            "Database schema is at migration {}, but this build only knows migrations up \
+             to {}; deploy a newer build or restore a matching database",
+            applied,
+            latest
+        );
+    }
+    Ok(applied)
+}
+
+/// Apply pending migrations, returning the version the database is now at
+pub async fn run_migrations(pool: &PgPool) -> Result<i64> {
+    ensure_not_ahead(pool).await?;
+    baseline_manual_schema(pool).await?;
+
+    MIGRATOR
+        .run(pool)
+        .await
+        .map_err(|e| anyhow!("Failed to apply database migrations: {}", e))?;
+
+    let version = applied_version(pool).await?.unwrap_or(0);
+    info!("Database schema at migration {}", version);
+    Ok(version)
+}
+
+/// A database set up from database/schema.sql has the initial schema but no
+/// record of it; record it as applied so the migration isn't run over it
+async fn baseline_manual_schema(pool: &PgPool) -> Result<()> {
+    let untracked: bool = sqlx::query_scalar(
+        "SELECT to_regclass('_sqlx_migrations') IS NULL AND to_regclass('pointers') IS NOT NULL",
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to inspect the database schema")?;
+    if !untracked {
+        return Ok(());
+    }
+
+    let baseline = MIGRATOR
+        .iter()
+        .find(|m| m.version == BASELINE_VERSION)
+        .context("Initial migration is missing from the build")?;
+    warn!(
+        "Database has a schema but no migration history; recording migration {} as applied",
+        BASELINE_VERSION
+    );
+
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table()
+        .await
+        .map_err(|e| anyhow!("Failed to create the migrations table: {}", e))?;
+    sqlx::query(
+        "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+         VALUES ($1, $2, TRUE, $3, 0)
+         ON CONFLICT (version) DO NOTHING",
+    )
+    .bind(baseline.version)
+    .bind(baseline.description.as_ref())
+    .bind(baseline.checksum.as_ref())
+    .execute(&mut *conn)
+    .await
+    .context("Failed to record the baseline migration")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::{postgres::PgConnectOptions, ConnectOptions, Connection, Executor};
+    use uuid::Uuid;
+
+    /// A new, empty database next to the test database, dropped by `drop_db`
+    async fn scratch_database() -> Option<(PgConnectOptions, String, PgPool)> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        let admin: PgConnectOptions = url.parse().unwrap();
+        let name = format!("veto_migrate_{}", Uuid::new_v4().simple());
+
+        let mut conn = admin.connect().await.unwrap();
+        sqlx::query(&format!("CREATE DATABASE {}", name))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        conn.close().await.unwrap();
+
+        let pool = PgPool::connect_with(admin.clone().database(&name))
+            .await
+            .unwrap();
+        Some((admin, name, pool))
+    }
+
+    async fn drop_db(admin: &PgConnectOptions, name: &str, pool: PgPool) {
+        pool.close().await;
+        let mut conn = admin.connect().await.unwrap();
+        sqlx::query(&format!("DROP DATABASE IF EXISTS {} WITH (FORCE)", name))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrations_build_schema_and_refuse_newer_database() {
+        let Some((admin, name, pool)) = scratch_database().await else {
+            return;
+        };
+        assert_eq!(applied_version(&pool).await.unwrap(), None);
+
+        assert_eq!(run_migrations(&pool).await.unwrap(), latest_version());
+        let demo_orgs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM organizations")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(demo_orgs, 1);
+
+        // Running again is a no-op
+        assert_eq!(run_migrations(&pool).await.unwrap(), latest_version());
+
+        // A migration from a newer build
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+             VALUES ($1, 'from the future', TRUE, '\\x00', 0)",
+        )
+        .bind(latest_version() + 1)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let error = run_migrations(&pool).await.unwrap_err().to_string();
+        assert!(
+            error.contains(&format!(
+                "at migration {}, but this build only knows migrations up to {}",
+                latest_version() + 1,
+                latest_version()
+            )),
+            "{}",
+            error
+        );
+        assert!(ensure_not_ahead(&pool).await.is_err());
+
+        drop_db(&admin, &name, pool).await;
+    }
+
+    #[tokio::test]
+    async fn test_manually_applied_schema_is_baselined() {
+        let Some((admin, name, pool)) = scratch_database().await else {
+            return;
+        };
+        let initial = MIGRATOR
+            .iter()
+            .find(|m| m.version == BASELINE_VERSION)
+            .unwrap();
+        pool.execute(&*initial.sql).await.unwrap();
+
+        assert_eq!(run_migrations(&pool).await.unwrap(), latest_version());
+
+        drop_db(&admin, &name, pool).await;
+    }
+}
@@ -1,8 +1,14 @@
 // Database module
+pub mod circuit;
 pub mod connection;
+pub mod constraints;
+pub mod migrations;
 pub mod models;
+pub mod pointer_cache;
 pub mod queries;
+pub mod single_flight;
 
-pub use connection::create_pool;
-pub use models::*;
-pub use queries::*;
+pub use circuit::CircuitBreaker;
+pub use connection::{create_pool, join_if_headroom, Db};
+pub use pointer_cache::{fetch_pointer_snapshot, PointerCache, PointerSnapshot};
+pub use single_flight::{SingleFlight, SingleFlightStats};
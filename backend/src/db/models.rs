@@ -1,7 +1,8 @@
 // Database models
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
@@ -9,6 +10,19 @@ use uuid::Uuid;
 pub enum PointerStatus {
     Active,
     Orphaned,
+    /// Vetoed with a future effective time; still resolvable until then
+    #[sqlx(rename = "pending_orphan")]
+    PendingOrphan,
+}
+
+impl PointerStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PointerStatus::Active => "active",
+            PointerStatus::Orphaned => "orphaned",
+            PointerStatus::PendingOrphan => "pending_orphan",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
@@ -17,8 +31,54 @@ pub enum ReceiptOperation {
     Create,
     Resolve,
     Orphan,
+    Denied,
+    Merge,
+    Restrict,
+    Unrestrict,
+    Repair,
+    #[sqlx(rename = "data_swap")]
+    DataSwap,
+    #[sqlx(rename = "chain_freeze_review")]
+    ChainFreezeReview,
+    #[sqlx(rename = "orphan_scheduled")]
+    OrphanScheduled,
+    #[sqlx(rename = "orphan_cancelled")]
+    OrphanCancelled,
+    #[sqlx(rename = "legal_hold")]
+    LegalHold,
+    #[sqlx(rename = "legal_hold_release")]
+    LegalHoldRelease,
+    Purge,
+    #[sqlx(rename = "orphan_requested")]
+    OrphanRequested,
+    Reinstate,
+}
+
+impl ReceiptOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReceiptOperation::Create => "create",
+            ReceiptOperation::Resolve => "resolve",
+            ReceiptOperation::Orphan => "orphan",
+            ReceiptOperation::Denied => "denied",
+            ReceiptOperation::Merge => "merge",
+            ReceiptOperation::Restrict => "restrict",
+            ReceiptOperation::Unrestrict => "unrestrict",
+            ReceiptOperation::Repair => "repair",
+            ReceiptOperation::DataSwap => "data_swap",
+            ReceiptOperation::ChainFreezeReview => "chain_freeze_review",
+            ReceiptOperation::OrphanScheduled => "orphan_scheduled",
+            ReceiptOperation::OrphanCancelled => "orphan_cancelled",
+            ReceiptOperation::LegalHold => "legal_hold",
+            ReceiptOperation::LegalHoldRelease => "legal_hold_release",
+            ReceiptOperation::Purge => "purge",
+            ReceiptOperation::OrphanRequested => "orphan_requested",
+            ReceiptOperation::Reinstate => "reinstate",
+        }
+    }
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Organization {
     pub org_id: Uuid,
@@ -26,6 +86,23 @@ pub struct Organization {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub metadata: serde_json::Value,
+    pub orphan_grace_seconds: i32,
+    pub heartbeat_interval_seconds: Option<i32>,
+}
+
+/// Bearer key authenticating as one organization; only its hash is stored
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApiKey {
+    pub key_id: Uuid,
+    pub key_hash: String,
+    pub org_id: Uuid,
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Successor issued by rotation
+    pub replaced_by: Option<Uuid>,
+    /// End of the rotation overlap; the key stops authenticating then
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -37,6 +114,12 @@ pub struct DataStore {
     pub encrypted_payload: Option<Vec<u8>>,
     pub created_at: DateTime<Utc>,
     pub metadata: serde_json::Value,
+    pub released_at: Option<DateTime<Utc>>,
+    pub legal_hold: bool,
+    pub retention_class: Option<String>,
+    pub retain_until: Option<DateTime<Utc>>,
+    /// Payload crypto-shredded after its pointer was orphaned
+    pub payload_purged_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -49,7 +132,102 @@ pub struct Pointer {
     pub created_at: DateTime<Utc>,
     pub orphaned_at: Option<DateTime<Utc>>,
     pub orphan_reason: Option<String>,
+    /// When a scheduled orphan takes effect; kept once it has
+    pub orphan_effective_at: Option<DateTime<Utc>>,
     pub metadata: serde_json::Value,
+    /// No receipt can be appended while set, see PointerChainFreeze
+    pub chain_frozen: bool,
+    /// Litigation hold; blocks orphaning until released
+    pub legal_hold: bool,
+    pub legal_hold_reason: Option<String>,
+    pub legal_hold_set_at: Option<DateTime<Utc>>,
+    /// The integrator's own id for the record, unique within the org
+    pub external_ref: Option<String>,
+    /// Orphaning needs a request and a second actor's approval
+    pub requires_dual_approval: bool,
+}
+
+/// An Idempotency-Key of a pointer create, with the response it produced
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IdempotencyRecord {
+    pub org_id: Uuid,
+    pub idempotency_key: String,
+    pub content_hash: String,
+    pub pointer_id: Option<Uuid>,
+    pub response_json: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A receipt signing key. Retired keys no longer sign but still verify.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SigningKey {
+    pub key_id: String,
+    pub public_key: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+    pub retired_at: Option<DateTime<Utc>>,
+}
+
+/// An organization's own receipt signing key. The seed is sealed under the
+/// master key; see `keys::OrgSigningKeys`.
+#[derive(Debug, Clone, FromRow)]
+pub struct OrgSigningKey {
+    pub org_id: Uuid,
+    pub key_id: String,
+    pub public_key: Vec<u8>,
+    pub sealed_private_key: Vec<u8>,
+}
+
+/// An org's endpoint for orphan event notifications
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Webhook {
+    pub webhook_id: Uuid,
+    pub org_id: Uuid,
+    pub url: String,
+    /// The HMAC secret is left out; it is only read when sending
+    pub enabled: bool,
+    pub events: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One event queued for one webhook
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub delivery_id: Uuid,
+    pub webhook_id: Uuid,
+    pub org_id: Uuid,
+    pub pointer_id: Option<Uuid>,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// A claimed delivery with what sending it needs from its webhook
+#[derive(Debug, Clone, FromRow)]
+pub struct DueWebhookDelivery {
+    pub delivery_id: Uuid,
+    pub webhook_id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDeliveryAttempt {
+    pub attempt_id: Uuid,
+    pub delivery_id: Uuid,
+    pub attempted_at: DateTime<Utc>,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -62,9 +240,15 @@ pub struct GovernanceReceipt {
     pub receipt_hash: String,
     pub signature: Vec<u8>,
     pub signature_algorithm: String,
+    /// Key that signed it; null on receipts from before key ids were recorded
+    pub key_id: Option<String>,
     pub prev_hash: Option<String>,
     pub timestamp: DateTime<Utc>,
     pub metadata: serde_json::Value,
+    /// Overflow record content of overflowed metadata, when read with it
+    #[sqlx(default)]
+    #[serde(skip)]
+    pub overflow: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -80,3 +264,293 @@ pub struct AuditLog {
     pub user_agent: Option<String>,
     pub timestamp: DateTime<Utc>,
 }
+
+/// One page of a keyset-paginated listing: at most `limit` rows after the
+/// `(timestamp, id)` of the previous page's last row, in the listing's order
+#[derive(Debug, Clone, Copy)]
+pub struct Pagination {
+    pub after: Option<(DateTime<Utc>, Uuid)>,
+    pub limit: i64,
+}
+
+/// Optional narrowing of a subject's audit trail; `from` is inclusive and
+/// `to` exclusive
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub org_id: Option<Uuid>,
+    pub event_type: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Optional narrowing of an org's pointer listing; `created_after` is
+/// inclusive and `created_before` exclusive
+#[derive(Debug, Clone)]
+pub struct PointerFilter {
+    pub org_id: Uuid,
+    pub status: Option<PointerStatus>,
+    pub subject_id: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ResolveStat {
+    pub pointer_id: Uuid,
+    pub bucket_start: DateTime<Utc>,
+    pub resolve_count: i64,
+    pub alerted_at: Option<DateTime<Utc>>,
+}
+
+/// Latest resolve bucket of a pointer alongside its trailing hourly average
+#[derive(Debug, Clone, FromRow)]
+pub struct ResolveSpikeCandidate {
+    pub pointer_id: Uuid,
+    pub org_id: Uuid,
+    pub subject_id: String,
+    pub bucket_start: DateTime<Utc>,
+    pub resolve_count: i64,
+    pub baseline: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrphanGroup {
+    pub group_id: Uuid,
+    pub org_id: Uuid,
+    pub member_pointer_ids: Vec<Uuid>,
+    pub reason: String,
+    pub manifest_json: serde_json::Value,
+    pub manifest_hash: String,
+    pub signature: Vec<u8>,
+    pub signature_algorithm: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An active pointer whose data has a retention deadline
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct RetentionExpiration {
+    pub pointer_id: Uuid,
+    pub org_id: Uuid,
+    pub data_id: Uuid,
+    pub subject_id: String,
+    pub retention_class: Option<String>,
+    pub retain_until: DateTime<Utc>,
+}
+
+/// A data_store row no pointer referenced when garbage collection scanned it
+#[derive(Debug, Clone, FromRow)]
+pub struct DataGcCandidate {
+    pub data_id: Uuid,
+    pub org_id: Uuid,
+    pub payload_bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DataGcRun {
+    pub manifest_id: Uuid,
+    pub org_id: Uuid,
+    pub mode: String,
+    pub data_ids: Vec<Uuid>,
+    pub bytes_reclaimed: i64,
+    pub manifest_json: serde_json::Value,
+    pub manifest_hash: String,
+    pub signature: Vec<u8>,
+    pub signature_algorithm: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An administrative freeze of a pointer's receipt chain
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PointerChainFreeze {
+    pub freeze_id: Uuid,
+    pub pointer_id: Uuid,
+    pub org_id: Uuid,
+    pub reason: String,
+    pub frozen_at: DateTime<Utc>,
+    pub cleared_at: Option<DateTime<Utc>>,
+    pub outcome: Option<String>,
+}
+
+/// One link of an org's administrative receipt chain
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrgReceipt {
+    pub receipt_id: Uuid,
+    pub org_id: Uuid,
+    pub kind: String,
+    pub pointer_id: Option<Uuid>,
+    pub receipt_json: serde_json::Value,
+    pub receipt_hash: String,
+    pub signature: Vec<u8>,
+    pub signature_algorithm: String,
+    pub prev_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One link of an org's liveness chain: a beat attesting an interval, or a
+/// gap marker covering intervals no beat was emitted for
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrgHeartbeat {
+    pub heartbeat_id: Uuid,
+    pub org_id: Uuid,
+    pub kind: String,
+    pub interval_start: DateTime<Utc>,
+    pub interval_end: DateTime<Utc>,
+    pub receipt_json: serde_json::Value,
+    pub receipt_hash: String,
+    pub signature: Vec<u8>,
+    pub signature_algorithm: String,
+    pub prev_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A signed Merkle root over the governance receipts written in one period
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReceiptCheckpoint {
+    pub checkpoint_id: Uuid,
+    pub sequence: i64,
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: DateTime<Utc>,
+    pub receipt_count: i32,
+    pub merkle_root: String,
+    pub receipt_json: serde_json::Value,
+    pub receipt_hash: String,
+    pub signature: Vec<u8>,
+    pub signature_algorithm: String,
+    pub key_id: String,
+    pub prev_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A receipt's place among a checkpoint's leaves
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CheckpointLeaf {
+    pub checkpoint_id: Uuid,
+    pub position: i32,
+    pub receipt_id: Uuid,
+    pub receipt_hash: String,
+}
+
+/// Current state of a pointer as seen by the changes feed
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PointerChange {
+    pub pointer_id: Uuid,
+    pub org_id: Uuid,
+    pub subject_id: String,
+    pub status: PointerStatus,
+    pub orphaned_at: Option<DateTime<Utc>>,
+    pub metadata: serde_json::Value,
+    pub updated_at: DateTime<Utc>,
+    /// xid8 of the last writing transaction, as text
+    pub change_xid: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SubjectPointerCount {
+    pub subject_id: String,
+    pub pointer_count: i64,
+}
+
+/// Processing restriction on a pointer, with the pointer's current subject
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PointerRestriction {
+    pub restriction_id: Uuid,
+    pub pointer_id: Uuid,
+    pub org_id: Uuid,
+    pub subject_id: String,
+    pub reason: String,
+    pub restricted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub lifted_at: Option<DateTime<Utc>>,
+    pub lift_reason: Option<String>,
+}
+
+/// First half of a two-person orphan, waiting for a second actor
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrphanRequest {
+    pub request_id: Uuid,
+    pub pointer_id: Uuid,
+    pub org_id: Uuid,
+    pub reason: Option<String>,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// pending | approved | expired
+    pub status: String,
+    pub approved_by: Option<String>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+impl OrphanRequest {
+    /// Still waiting for approval at `now`. A request past its expiry stays
+    /// stored as pending until it is next touched.
+    pub fn is_pending(&self, now: DateTime<Utc>) -> bool {
+        self.status == "pending" && now < self.expires_at
+    }
+
+    /// Status as of `now`, counting an unswept lapse as expired
+    pub fn status_at(&self, now: DateTime<Utc>) -> &str {
+        if self.status == "pending" && now >= self.expires_at {
+            "expired"
+        } else {
+            &self.status
+        }
+    }
+}
+
+/// A pointer's status column next to what its receipt chain says
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PointerChainState {
+    pub pointer_id: Uuid,
+    pub org_id: Uuid,
+    pub subject_id: String,
+    pub status: PointerStatus,
+    pub orphaned_at: Option<DateTime<Utc>>,
+    /// Timestamp of the first Orphan receipt, if the chain has one
+    pub chain_orphaned_at: Option<DateTime<Utc>>,
+    pub last_receipt_at: Option<DateTime<Utc>>,
+}
+
+/// An org's pointers by current status
+#[derive(Debug, Clone, Default, Serialize, FromRow, ToSchema)]
+pub struct OrgPointerCounts {
+    pub total_pointers: i64,
+    pub active_pointers: i64,
+    pub pending_orphan_pointers: i64,
+    pub orphaned_pointers: i64,
+}
+
+/// Rows created on a UTC day
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DailyCount {
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+/// Dashboard aggregates for one org. Pointer counts cover pointers created in
+/// the range, receipt counts receipts issued in it, and the median pointers
+/// orphaned in it; either bound may be open. The daily series always has a
+/// closed range and lists days with no pointers as zero.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OrgStats {
+    pub org_id: Uuid,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub pointers: OrgPointerCounts,
+    pub created_per_day: Vec<DailyCount>,
+    /// Receipt count per operation, operations with none left out
+    pub receipts_by_operation: std::collections::BTreeMap<String, i64>,
+    /// None when no pointer was orphaned
+    pub median_seconds_to_orphan: Option<f64>,
+}
+
+/// One org's veto latency tally for a UTC day under one target
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct VetoSloDay {
+    pub org_id: Uuid,
+    pub day: NaiveDate,
+    pub target_ms: i32,
+    pub veto_count: i64,
+    pub within_target_count: i64,
+    pub max_latency_ms: i32,
+}
@@ -0,0 +1,365 @@
+// Read-through cache of what resolve reads before locking a pointer
+// Resolve reads the pointer, any open processing restriction and its data's
+// content hash before taking the lock. With POINTER_CACHE_TTL_MS set, those
+// reads are served from memory for that long. Every write to a pointer on
+// this instance evicts its entry before the writing request returns, and a
+// read that a write overtook is never stored, so a veto is not followed by a
+// resolve served from the state it replaced. Writes by other instances are
+// seen once the entry expires; attested resolves enforce on the locked row
+// either way.
+use anyhow::Result;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+use super::{
+    join_if_headroom,
+    models::{Pointer, PointerRestriction},
+    queries,
+};
+
+/// Pointer, restriction and data content hash as read by one resolve fetch
+#[derive(Debug, Clone)]
+pub struct PointerSnapshot {
+    pub pointer: Option<Pointer>,
+    pub restriction: Option<PointerRestriction>,
+    /// Of the pointer's data row; None when the row is missing
+    pub content_hash: Option<String>,
+}
+
+pub async fn fetch_pointer_snapshot(pool: &PgPool, pointer_id: Uuid) -> Result<PointerSnapshot> {
+    let (pointer, restriction) = join_if_headroom(
+        pool,
+        queries::get_pointer(pool, pointer_id),
+        queries::get_open_restriction(pool, pointer_id),
+    )
+    .await;
+    let pointer = pointer?;
+
+    let content_hash = match &pointer {
+        Some(pointer) => queries::get_data_content_hash(pool, pointer.data_id).await?,
+        None => None,
+    };
+
+    Ok(PointerSnapshot {
+        pointer,
+        restriction: restriction?,
+        content_hash,
+    })
+}
+
+/// Taken before a fetch; the fetched snapshot is only stored if its pointer
+/// was not evicted in between
+#[derive(Debug, Clone, Copy)]
+pub struct CacheTicket(u64);
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PointerCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+struct Entry {
+    snapshot: PointerSnapshot,
+    stored_at: Instant,
+}
+
+#[derive(Default)]
+struct Entries {
+    snapshots: HashMap<Uuid, Entry>,
+    /// Advanced by every eviction
+    generation: u64,
+    /// Generation of each pointer's latest eviction
+    evicted: HashMap<Uuid, u64>,
+    /// Tickets older than this may predate evictions no longer tracked
+    floor: u64,
+}
+
+pub struct PointerCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl PointerCache {
+    /// Entries are served for `ttl` after they are read; a zero `ttl` or
+    /// `max_entries` disables the cache
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries,
+            entries: Mutex::new(Entries::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        !self.ttl.is_zero() && self.max_entries > 0
+    }
+
+    pub fn get(&self, pointer_id: &Uuid) -> Option<PointerSnapshot> {
+        self.get_at(pointer_id, Instant::now())
+    }
+
+    fn get_at(&self, pointer_id: &Uuid, now: Instant) -> Option<PointerSnapshot> {
+        if !self.enabled() {
+            return None;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        match entries.snapshots.get(pointer_id) {
+            Some(entry) if now.saturating_duration_since(entry.stored_at) < self.ttl => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.snapshot.clone())
+            }
+            expired => {
+                if expired.is_some() {
+                    entries.snapshots.remove(pointer_id);
+                }
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    pub fn ticket(&self) -> CacheTicket {
+        CacheTicket(self.entries.lock().unwrap().generation)
+    }
+
+    /// Store a snapshot fetched after `ticket` was taken. Pointers that were
+    /// not found are not stored.
+    pub fn insert(&self, ticket: CacheTicket, snapshot: &PointerSnapshot) {
+        self.insert_at(ticket, snapshot, Instant::now())
+    }
+
+    fn insert_at(&self, ticket: CacheTicket, snapshot: &PointerSnapshot, now: Instant) {
+        let Some(pointer) = &snapshot.pointer else {
+            return;
+        };
+        if !self.enabled() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        let overtaken = ticket.0 < entries.floor
+            || entries
+                .evicted
+                .get(&pointer.pointer_id)
+                .is_some_and(|&evicted| evicted > ticket.0);
+        if overtaken {
+            return;
+        }
+
+        if entries.snapshots.len() >= self.max_entries {
+            let ttl = self.ttl;
+            entries
+                .snapshots
+                .retain(|_, entry| now.saturating_duration_since(entry.stored_at) < ttl);
+            if entries.snapshots.len() >= self.max_entries {
+                entries.snapshots.clear();
+            }
+        }
+        entries.snapshots.insert(
+            pointer.pointer_id,
+            Entry {
+                snapshot: snapshot.clone(),
+                stored_at: now,
+            },
+        );
+    }
+
+    /// Drop a pointer's entry after a write to it commits, and refuse any
+    /// snapshot of it read before now
+    pub fn evict(&self, pointer_id: &Uuid) {
+        if !self.enabled() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.snapshots.remove(pointer_id);
+        entries.generation += 1;
+        let generation = entries.generation;
+        if entries.evicted.len() >= self.max_entries {
+            entries.evicted.clear();
+            entries.floor = generation;
+        }
+        entries.evicted.insert(*pointer_id, generation);
+    }
+
+    pub fn stats(&self) -> PointerCacheStats {
+        PointerCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap().snapshots.len(),
+        }
+    }
+
+    /// Append the hit and miss counters and the entry count to an
+    /// OpenMetrics exposition
+    pub fn render_openmetrics(&self, out: &mut String) {
+        let stats = self.stats();
+        for (name, help, value) in [
+            (
+                "pointer_cache_hits",
+                "Resolves that read pointer state from the cache",
+                stats.hits,
+            ),
+            (
+                "pointer_cache_misses",
+                "Resolves that read pointer state from the database",
+                stats.misses,
+            ),
+        ] {
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "{}_total {}", name, value);
+        }
+        let _ = writeln!(out, "# TYPE pointer_cache_entries gauge");
+        let _ = writeln!(
+            out,
+            "# HELP pointer_cache_entries Pointers currently held in the cache"
+        );
+        let _ = writeln!(out, "pointer_cache_entries {}", stats.entries);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::PointerStatus;
+    use chrono::Utc;
+
+    const TTL: Duration = Duration::from_secs(1);
+
+    fn snapshot(pointer_id: Uuid, status: PointerStatus) -> PointerSnapshot {
+        PointerSnapshot {
+            pointer: Some(Pointer {
+                pointer_id,
+                org_id: Uuid::new_v4(),
+                subject_id: "user_1".to_string(),
+                data_id: Uuid::new_v4(),
+                status,
+                created_at: Utc::now(),
+                orphaned_at: None,
+                orphan_reason: None,
+                orphan_effective_at: None,
+                metadata: serde_json::json!({}),
+                chain_frozen: false,
+                legal_hold: false,
+                legal_hold_reason: None,
+                legal_hold_set_at: None,
+                external_ref: None,
+                requires_dual_approval: false,
+            }),
+            restriction: None,
+            content_hash: Some("ab".repeat(64)),
+        }
+    }
+
+    fn status(snapshot: Option<PointerSnapshot>) -> Option<&'static str> {
+        snapshot.map(|s| s.pointer.unwrap().status.as_str())
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = PointerCache::new(TTL, 10);
+        let pointer_id = Uuid::new_v4();
+        let stored = Instant::now();
+
+        cache.insert_at(
+            cache.ticket(),
+            &snapshot(pointer_id, PointerStatus::Active),
+            stored,
+        );
+        assert_eq!(status(cache.get_at(&pointer_id, stored)), Some("active"));
+        assert_eq!(
+            status(cache.get_at(&pointer_id, stored + TTL - Duration::from_millis(1))),
+            Some("active")
+        );
+        assert_eq!(status(cache.get_at(&pointer_id, stored + TTL)), None);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (2, 1, 0));
+    }
+
+    #[test]
+    fn test_eviction_refuses_reads_it_overtook() {
+        let cache = PointerCache::new(TTL, 10);
+        let pointer_id = Uuid::new_v4();
+
+        cache.insert(cache.ticket(), &snapshot(pointer_id, PointerStatus::Active));
+        assert_eq!(status(cache.get(&pointer_id)), Some("active"));
+
+        // A read that started before the orphan committed lands after its
+        // eviction: it must not be stored
+        let stale_ticket = cache.ticket();
+        cache.evict(&pointer_id);
+        assert_eq!(status(cache.get(&pointer_id)), None);
+        cache.insert(stale_ticket, &snapshot(pointer_id, PointerStatus::Active));
+        assert_eq!(status(cache.get(&pointer_id)), None);
+
+        // A read started after the eviction is
+        cache.insert(
+            cache.ticket(),
+            &snapshot(pointer_id, PointerStatus::Orphaned),
+        );
+        assert_eq!(status(cache.get(&pointer_id)), Some("orphaned"));
+
+        // Evicting other pointers doesn't hold this one's reads back
+        let ticket = cache.ticket();
+        cache.evict(&Uuid::new_v4());
+        cache.insert(ticket, &snapshot(pointer_id, PointerStatus::Orphaned));
+        assert_eq!(status(cache.get(&pointer_id)), Some("orphaned"));
+    }
+
+    #[test]
+    fn test_forgotten_evictions_refuse_older_reads() {
+        let cache = PointerCache::new(TTL, 2);
+        let pointer_id = Uuid::new_v4();
+
+        let stale_ticket = cache.ticket();
+        cache.evict(&pointer_id);
+        // Past max_entries the eviction record of pointer_id is dropped
+        cache.evict(&Uuid::new_v4());
+        cache.evict(&Uuid::new_v4());
+        cache.insert(stale_ticket, &snapshot(pointer_id, PointerStatus::Active));
+        assert_eq!(status(cache.get(&pointer_id)), None);
+    }
+
+    #[test]
+    fn test_capacity_and_disabled_cache() {
+        let cache = PointerCache::new(TTL, 2);
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        for id in &ids {
+            cache.insert(cache.ticket(), &snapshot(*id, PointerStatus::Active));
+        }
+        assert!(cache.stats().entries <= 2);
+        assert!(cache.get(&ids[2]).is_some());
+
+        let missing = PointerSnapshot {
+            pointer: None,
+            restriction: None,
+            content_hash: None,
+        };
+        cache.insert(cache.ticket(), &missing);
+        assert!(cache.stats().entries <= 2);
+
+        let disabled = PointerCache::new(Duration::ZERO, 10);
+        disabled.insert(disabled.ticket(), &snapshot(ids[0], PointerStatus::Active));
+        assert!(disabled.get(&ids[0]).is_none());
+        assert_eq!(disabled.stats().misses, 0);
+    }
+}
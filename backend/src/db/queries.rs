@@ -1,25 +1,37 @@
 // Database queries
-use anyhow::{Context, Result};
-use sqlx::PgPool;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgConnection, PgExecutor, PgPool};
 use uuid::Uuid;
 
 use super::models::*;
 
+/// Round trip to the database, for readiness checks
+pub async fn ping<'e>(executor: impl PgExecutor<'e>) -> Result<()> {
+    sqlx::query("SELECT 1")
+        .execute(executor)
+        .await
+        .context("Failed to reach the database")?;
+
+    Ok(())
+}
+
 // ============================================================================
 // DATA STORE QUERIES
 // ============================================================================
 
-pub async fn create_data_store(
-    pool: &PgPool,
+pub async fn create_data_store<'e>(
+    executor: impl PgExecutor<'e>,
     org_id: Uuid,
     subject_id: &str,
     content_hash: &str,
     encrypted_payload: Option<&[u8]>,
+    metadata: &serde_json::Value,
 ) -> Result<DataStore> {
     let data = sqlx::query_as::<_, DataStore>(
         r#"
-        INSERT INTO data_store (org_id, subject_id, content_hash, encrypted_payload)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO data_store (org_id, subject_id, content_hash, encrypted_payload, metadata)
+        VALUES ($1, $2, $3, $4, $5)
         RETURNING *
         "#,
     )
@@ -27,54 +39,336 @@ pub async fn create_data_store(
     .bind(subject_id)
     .bind(content_hash)
     .bind(encrypted_payload)
-    .fetch_one(pool)
+    .bind(metadata)
+    .fetch_one(executor)
     .await
     .context("Failed to insert into data_store")?;
 
     Ok(data)
 }
 
-pub async fn get_data_store(pool: &PgPool, data_id: Uuid) -> Result<Option<DataStore>> {
+pub async fn get_data_store<'e>(
+    executor: impl PgExecutor<'e>,
+    data_id: Uuid,
+) -> Result<Option<DataStore>> {
     let data = sqlx::query_as::<_, DataStore>(
         r#"
         SELECT * FROM data_store WHERE data_id = $1
         "#,
     )
     .bind(data_id)
-    .fetch_optional(pool)
+    .fetch_optional(executor)
     .await
     .context("Failed to query data_store")?;
 
     Ok(data)
 }
 
+/// Content hash of a data row, without reading its payload
+pub async fn get_data_content_hash(pool: &PgPool, data_id: Uuid) -> Result<Option<String>> {
+    let content_hash = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT content_hash FROM data_store WHERE data_id = $1
+        "#,
+    )
+    .bind(data_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to query data_store content hash")?;
+
+    Ok(content_hash)
+}
+
+/// Delete a data row unless a pointer still references it; returns whether
+/// it was deleted
+pub async fn delete_unreferenced_data_store<'e>(
+    executor: impl PgExecutor<'e>,
+    data_id: Uuid,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM data_store d
+        WHERE d.data_id = $1
+          AND NOT EXISTS (SELECT 1 FROM pointers p WHERE p.data_id = d.data_id)
+        "#,
+    )
+    .bind(data_id)
+    .execute(executor)
+    .await
+    .context("Failed to delete data_store entry")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Stamp a row a pointer just moved off, so garbage collection grace starts now
+pub async fn mark_data_store_released<'e>(
+    executor: impl PgExecutor<'e>,
+    data_id: Uuid,
+    released_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE data_store SET released_at = $2 WHERE data_id = $1
+        "#,
+    )
+    .bind(data_id)
+    .bind(released_at)
+    .execute(executor)
+    .await
+    .context("Failed to mark data_store entry released")?;
+
+    Ok(())
+}
+
+/// Record a row's retention class and hard deadline
+pub async fn set_data_store_retention<'e>(
+    executor: impl PgExecutor<'e>,
+    data_id: Uuid,
+    retention_class: Option<&str>,
+    retain_until: Option<DateTime<Utc>>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE data_store SET retention_class = $2, retain_until = $3 WHERE data_id = $1
+        "#,
+    )
+    .bind(data_id)
+    .bind(retention_class)
+    .bind(retain_until)
+    .execute(executor)
+    .await
+    .context("Failed to set data_store retention")?;
+
+    Ok(())
+}
+
+/// Unreferenced, unheld rows last released (or created) at or before `cutoff`.
+/// In payload-nulling mode rows already nulled are skipped.
+pub async fn get_data_gc_candidates(
+    pool: &PgPool,
+    org_id: Option<Uuid>,
+    cutoff: DateTime<Utc>,
+    null_payload: bool,
+    limit: i64,
+) -> Result<Vec<DataGcCandidate>> {
+    let candidates = sqlx::query_as::<_, DataGcCandidate>(
+        r#"
+        SELECT d.data_id, d.org_id,
+               COALESCE(octet_length(d.encrypted_payload), 0)::BIGINT AS payload_bytes
+        FROM data_store d
+        WHERE ($1::uuid IS NULL OR d.org_id = $1)
+          AND COALESCE(d.released_at, d.created_at) <= $2
+          AND NOT d.legal_hold
+          AND (NOT $3 OR d.encrypted_payload IS NOT NULL)
+          AND NOT EXISTS (SELECT 1 FROM pointers p WHERE p.data_id = d.data_id)
+        ORDER BY d.org_id, COALESCE(d.released_at, d.created_at)
+        LIMIT $4
+        "#,
+    )
+    .bind(org_id)
+    .bind(cutoff)
+    .bind(null_payload)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query data_store GC candidates")?;
+
+    Ok(candidates)
+}
+
+/// Row-lock data_store entries, skipping any another transaction holds.
+/// A concurrent pointer insert or relink takes a key-share lock on the row
+/// it references, so once locked here no new reference can appear until the
+/// locking transaction ends.
+pub async fn lock_data_store_rows<'e>(
+    executor: impl PgExecutor<'e>,
+    data_ids: &[Uuid],
+) -> Result<Vec<Uuid>> {
+    let locked = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT data_id FROM data_store
+        WHERE data_id = ANY($1)
+        ORDER BY data_id
+        FOR UPDATE SKIP LOCKED
+        "#,
+    )
+    .bind(data_ids)
+    .fetch_all(executor)
+    .await
+    .context("Failed to lock data_store entries")?;
+
+    Ok(locked)
+}
+
+/// Delete, or null the payload of, the given rows that are still eligible
+/// for collection. Eligibility is re-checked here, in a fresh statement after
+/// the rows were locked, so references made since the scan are honored.
+/// Returns each collected row with the payload bytes it held.
+pub async fn collect_data_store<'e>(
+    executor: impl PgExecutor<'e>,
+    data_ids: &[Uuid],
+    cutoff: DateTime<Utc>,
+    null_payload: bool,
+) -> Result<Vec<(Uuid, i64)>> {
+    let sql = if null_payload {
+        r#"
+        WITH eligible AS (
+            SELECT d.data_id, octet_length(d.encrypted_payload)::BIGINT AS payload_bytes
+            FROM data_store d
+            WHERE d.data_id = ANY($1)
+              AND COALESCE(d.released_at, d.created_at) <= $2
+              AND NOT d.legal_hold
+              AND d.encrypted_payload IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM pointers p WHERE p.data_id = d.data_id)
+        )
+        UPDATE data_store d SET encrypted_payload = NULL
+        FROM eligible e
+        WHERE d.data_id = e.data_id
+        RETURNING d.data_id, e.payload_bytes
+        "#
+    } else {
+        r#"
+        DELETE FROM data_store d
+        WHERE d.data_id = ANY($1)
+          AND COALESCE(d.released_at, d.created_at) <= $2
+          AND NOT d.legal_hold
+          AND NOT EXISTS (SELECT 1 FROM pointers p WHERE p.data_id = d.data_id)
+        RETURNING d.data_id, COALESCE(octet_length(d.encrypted_payload), 0)::BIGINT
+        "#
+    };
+
+    let collected = sqlx::query_as::<_, (Uuid, i64)>(sql)
+        .bind(data_ids)
+        .bind(cutoff)
+        .fetch_all(executor)
+        .await
+        .context("Failed to collect data_store entries")?;
+
+    Ok(collected)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_data_gc_run<'e>(
+    executor: impl PgExecutor<'e>,
+    manifest_id: Uuid,
+    org_id: Uuid,
+    mode: &str,
+    data_ids: &[Uuid],
+    bytes_reclaimed: i64,
+    manifest_json: serde_json::Value,
+    manifest_hash: &str,
+    signature: &[u8],
+    signature_algorithm: &str,
+) -> Result<DataGcRun> {
+    let run = sqlx::query_as::<_, DataGcRun>(
+        r#"
+        INSERT INTO data_gc_runs
+            (manifest_id, org_id, mode, data_ids, bytes_reclaimed, manifest_json,
+             manifest_hash, signature, signature_algorithm)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+    )
+    .bind(manifest_id)
+    .bind(org_id)
+    .bind(mode)
+    .bind(data_ids)
+    .bind(bytes_reclaimed)
+    .bind(manifest_json)
+    .bind(manifest_hash)
+    .bind(signature)
+    .bind(signature_algorithm)
+    .fetch_one(executor)
+    .await
+    .context("Failed to insert data GC run")?;
+
+    Ok(run)
+}
+
 // ============================================================================
 // POINTER QUERIES
 // ============================================================================
 
-pub async fn create_pointer(
-    pool: &PgPool,
+pub async fn create_pointer<'e>(
+    executor: impl PgExecutor<'e>,
     org_id: Uuid,
     data_id: Uuid,
     subject_id: &str,
+    external_ref: Option<&str>,
+    requires_dual_approval: bool,
 ) -> Result<Pointer> {
     let pointer = sqlx::query_as::<_, Pointer>(
         r#"
-        INSERT INTO pointers (org_id, data_id, subject_id, status)
-        VALUES ($1, $2, $3, 'active')
+        INSERT INTO pointers (org_id, data_id, subject_id, status, external_ref,
+                              requires_dual_approval)
+        VALUES ($1, $2, $3, 'active', $4, $5)
         RETURNING *
         "#,
     )
     .bind(org_id)
     .bind(data_id)
     .bind(subject_id)
-    .fetch_one(pool)
+    .bind(external_ref)
+    .bind(requires_dual_approval)
+    .fetch_one(executor)
     .await
     .context("Failed to insert pointer")?;
 
     Ok(pointer)
 }
 
+pub async fn get_pointer_by_external_ref<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+    external_ref: &str,
+) -> Result<Option<Pointer>> {
+    let pointer = sqlx::query_as::<_, Pointer>(
+        r#"
+        SELECT * FROM pointers
+        WHERE org_id = $1 AND external_ref = $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(external_ref)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to query pointer by external reference")?;
+
+    Ok(pointer)
+}
+
+pub async fn get_organization(pool: &PgPool, org_id: Uuid) -> Result<Option<Organization>> {
+    let org = sqlx::query_as::<_, Organization>(
+        r#"
+        SELECT * FROM organizations WHERE org_id = $1
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to query organization")?;
+
+    Ok(org)
+}
+
+/// Orphan grace window configured for an organization, in seconds
+pub async fn get_org_orphan_grace_seconds<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+) -> Result<i64> {
+    let grace: Option<i32> = sqlx::query_scalar(
+        r#"
+        SELECT orphan_grace_seconds FROM organizations WHERE org_id = $1
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to query organization grace period")?;
+
+    Ok(grace.unwrap_or(0).into())
+}
+
 pub async fn get_pointer(pool: &PgPool, pointer_id: Uuid) -> Result<Option<Pointer>> {
     let pointer = sqlx::query_as::<_, Pointer>(
         r#"
@@ -89,177 +383,2812 @@ pub async fn get_pointer(pool: &PgPool, pointer_id: Uuid) -> Result<Option<Point
     Ok(pointer)
 }
 
-pub async fn orphan_pointer(
-    pool: &PgPool,
+/// Repoint a pointer at another data row
+pub async fn set_pointer_data<'e>(
+    executor: impl PgExecutor<'e>,
     pointer_id: Uuid,
-    reason: Option<&str>,
+    data_id: Uuid,
 ) -> Result<Pointer> {
+    let pointer = sqlx::query_as::<_, Pointer>(
+        r#"
+        UPDATE pointers SET data_id = $2
+        WHERE pointer_id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(data_id)
+    .fetch_one(executor)
+    .await
+    .context("Failed to update pointer data")?;
+
+    Ok(pointer)
+}
+
+/// Orphan an active pointer. Returns None if it is missing or already
+/// orphaned, so a racing veto can never be applied twice.
+pub async fn orphan_pointer<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    reason: Option<&str>,
+    orphaned_at: DateTime<Utc>,
+) -> Result<Option<Pointer>> {
     let pointer = sqlx::query_as::<_, Pointer>(
         r#"
         UPDATE pointers
         SET status = 'orphaned',
-            orphaned_at = NOW(),
+            orphaned_at = $3,
             orphan_reason = $2
-        WHERE pointer_id = $1
+        WHERE pointer_id = $1 AND status IN ('active', 'pending_orphan')
         RETURNING *
         "#,
     )
     .bind(pointer_id)
     .bind(reason)
-    .fetch_one(pool)
+    .bind(orphaned_at)
+    .fetch_optional(executor)
     .await
     .context("Failed to orphan pointer")?;
 
     Ok(pointer)
 }
 
-pub async fn get_pointers_by_subject(
-    pool: &PgPool,
-    subject_id: &str,
-) -> Result<Vec<Pointer>> {
-    let pointers = sqlx::query_as::<_, Pointer>(
+/// Return an orphaned pointer to active, appending its orphan to
+/// metadata.orphan_history. None if it is missing or not orphaned.
+pub async fn reinstate_pointer<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    history_entry: &serde_json::Value,
+) -> Result<Option<Pointer>> {
+    let pointer = sqlx::query_as::<_, Pointer>(
         r#"
-        SELECT * FROM pointers
-        WHERE subject_id = $1
-        ORDER BY created_at DESC
+        UPDATE pointers
+        SET status = 'active',
+            orphaned_at = NULL,
+            orphan_reason = NULL,
+            orphan_effective_at = NULL,
+            metadata = jsonb_set(
+                COALESCE(metadata, '{}'::jsonb),
+                '{orphan_history}',
+                COALESCE(metadata->'orphan_history', '[]'::jsonb) || jsonb_build_array($2::jsonb)
+            )
+        WHERE pointer_id = $1 AND status = 'orphaned'
+        RETURNING *
         "#,
     )
-    .bind(subject_id)
-    .fetch_all(pool)
+    .bind(pointer_id)
+    .bind(history_entry)
+    .fetch_optional(executor)
     .await
-    .context("Failed to query pointers by subject")?;
+    .context("Failed to reinstate pointer")?;
 
-    Ok(pointers)
+    Ok(pointer)
 }
 
-// ============================================================================
-// GOVERNANCE RECEIPT QUERIES
-// ============================================================================
-
-pub async fn create_governance_receipt(
-    pool: &PgPool,
+/// Schedule an active pointer's orphaning for `effective_at`; None if it
+/// was not active
+pub async fn schedule_orphan<'e>(
+    executor: impl PgExecutor<'e>,
     pointer_id: Uuid,
-    org_id: Uuid,
-    operation: ReceiptOperation,
-    receipt_json: serde_json::Value,
-    receipt_hash: &str,
-    signature: &[u8],
-    signature_algorithm: &str,
-    prev_hash: Option<&str>,
-) -> Result<GovernanceReceipt> {
-    let receipt = sqlx::query_as::<_, GovernanceReceipt>(
+    reason: Option<&str>,
+    effective_at: DateTime<Utc>,
+) -> Result<Option<Pointer>> {
+    let pointer = sqlx::query_as::<_, Pointer>(
         r#"
-        INSERT INTO governance_receipts
-            (pointer_id, org_id, operation, receipt_json, receipt_hash,
-             signature, signature_algorithm, prev_hash)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        UPDATE pointers
+        SET status = 'pending_orphan',
+            orphan_effective_at = $3,
+            orphan_reason = $2
+        WHERE pointer_id = $1 AND status = 'active'
         RETURNING *
         "#,
     )
     .bind(pointer_id)
-    .bind(org_id)
-    .bind(operation)
-    .bind(receipt_json)
-    .bind(receipt_hash)
-    .bind(signature)
-    .bind(signature_algorithm)
-    .bind(prev_hash)
-    .fetch_one(pool)
+    .bind(reason)
+    .bind(effective_at)
+    .fetch_optional(executor)
     .await
-    .context("Failed to insert governance receipt")?;
+    .context("Failed to schedule orphan")?;
 
-    Ok(receipt)
+    Ok(pointer)
 }
 
-pub async fn get_receipts_by_pointer(
-    pool: &PgPool,
+/// Return a pending orphan to active; None if it was not pending
+pub async fn cancel_pending_orphan<'e>(
+    executor: impl PgExecutor<'e>,
     pointer_id: Uuid,
-) -> Result<Vec<GovernanceReceipt>> {
-    let receipts = sqlx::query_as::<_, GovernanceReceipt>(
+) -> Result<Option<Pointer>> {
+    let pointer = sqlx::query_as::<_, Pointer>(
         r#"
-        SELECT * FROM governance_receipts
-        WHERE pointer_id = $1
-        ORDER BY timestamp ASC
+        UPDATE pointers
+        SET status = 'active',
+            orphan_effective_at = NULL,
+            orphan_reason = NULL
+        WHERE pointer_id = $1 AND status = 'pending_orphan'
+        RETURNING *
         "#,
     )
     .bind(pointer_id)
-    .fetch_all(pool)
+    .fetch_optional(executor)
     .await
-    .context("Failed to query receipts by pointer")?;
+    .context("Failed to cancel pending orphan")?;
 
-    Ok(receipts)
+    Ok(pointer)
 }
 
-pub async fn get_latest_receipt_hash(
-    pool: &PgPool,
+/// Place a legal hold on a pointer not yet orphaned; None if it was already
+/// held or orphaned
+pub async fn set_legal_hold<'e>(
+    executor: impl PgExecutor<'e>,
     pointer_id: Uuid,
-) -> Result<Option<String>> {
-    let result = sqlx::query_scalar::<_, Option<String>>(
+    reason: &str,
+    set_at: DateTime<Utc>,
+) -> Result<Option<Pointer>> {
+    let pointer = sqlx::query_as::<_, Pointer>(
         r#"
-        SELECT receipt_hash FROM governance_receipts
-        WHERE pointer_id = $1
-        ORDER BY timestamp DESC
-        LIMIT 1
+        UPDATE pointers
+        SET legal_hold = TRUE,
+            legal_hold_reason = $2,
+            legal_hold_set_at = $3
+        WHERE pointer_id = $1 AND NOT legal_hold AND status != 'orphaned'
+        RETURNING *
         "#,
     )
     .bind(pointer_id)
-    .fetch_one(pool)
+    .bind(reason)
+    .bind(set_at)
+    .fetch_optional(executor)
     .await
-    .context("Failed to query latest receipt hash")?;
+    .context("Failed to set legal hold")?;
 
-    Ok(result)
+    Ok(pointer)
 }
 
-// ============================================================================
-// AUDIT LOG QUERIES
-// ============================================================================
-
-pub async fn create_audit_log(
-    pool: &PgPool,
-    org_id: Option<Uuid>,
-    pointer_id: Option<Uuid>,
-    receipt_id: Option<Uuid>,
-    event_type: &str,
-    event_data: serde_json::Value,
-    actor_id: Option<&str>,
-) -> Result<AuditLog> {
-    let log = sqlx::query_as::<_, AuditLog>(
+/// Release a pointer's legal hold; None if it had none
+pub async fn release_legal_hold<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+) -> Result<Option<Pointer>> {
+    let pointer = sqlx::query_as::<_, Pointer>(
         r#"
-        INSERT INTO audit_log
-            (org_id, pointer_id, receipt_id, event_type, event_data, actor_id)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        UPDATE pointers
+        SET legal_hold = FALSE,
+            legal_hold_reason = NULL,
+            legal_hold_set_at = NULL
+        WHERE pointer_id = $1 AND legal_hold
         RETURNING *
         "#,
     )
-    .bind(org_id)
     .bind(pointer_id)
-    .bind(receipt_id)
-    .bind(event_type)
-    .bind(event_data)
-    .bind(actor_id)
-    .fetch_one(pool)
+    .fetch_optional(executor)
     .await
-    .context("Failed to insert audit log")?;
+    .context("Failed to release legal hold")?;
 
-    Ok(log)
+    Ok(pointer)
 }
 
-pub async fn get_audit_trail_by_subject(
-    pool: &PgPool,
-    subject_id: &str,
-) -> Result<Vec<AuditLog>> {
-    let logs = sqlx::query_as::<_, AuditLog>(
+/// Null the payload of an orphaned pointer's data row, unless it is held or
+/// still referenced by a pointer that is not orphaned. Returns the content
+/// hash and the size of the destroyed payload, or None if nothing was purged.
+pub async fn purge_orphaned_payload<'e>(
+    executor: impl PgExecutor<'e>,
+    data_id: Uuid,
+    purged_at: DateTime<Utc>,
+) -> Result<Option<(String, i32)>> {
+    let purged = sqlx::query_as::<_, (String, i32)>(
         r#"
-        SELECT al.* FROM audit_log al
-        JOIN pointers p ON al.pointer_id = p.pointer_id
-        WHERE p.subject_id = $1
-        ORDER BY al.timestamp DESC
+        UPDATE data_store d
+        SET encrypted_payload = NULL, payload_purged_at = $2
+        FROM (
+            SELECT data_id, octet_length(encrypted_payload) AS payload_bytes
+            FROM data_store
+            WHERE data_id = $1
+            FOR UPDATE
+        ) old
+        WHERE d.data_id = old.data_id
+          AND d.encrypted_payload IS NOT NULL
+          AND NOT d.legal_hold
+          AND NOT EXISTS (
+              SELECT 1 FROM pointers p
+              WHERE p.data_id = d.data_id AND p.status != 'orphaned'
+          )
+        RETURNING d.content_hash, old.payload_bytes
         "#,
     )
-    .bind(subject_id)
-    .fetch_all(pool)
+    .bind(data_id)
+    .bind(purged_at)
+    .fetch_optional(executor)
     .await
-    .context("Failed to query audit trail by subject")?;
+    .context("Failed to purge payload")?;
 
-    Ok(logs)
+    Ok(purged)
+}
+
+/// Lock orphaned pointers orphaned before `orphaned_before` whose data row
+/// still has a payload nothing else needs
+pub async fn lock_purgeable_orphans<'e>(
+    executor: impl PgExecutor<'e>,
+    orphaned_before: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<Pointer>> {
+    let pointers = sqlx::query_as::<_, Pointer>(
+        r#"
+        SELECT p.* FROM pointers p
+        JOIN data_store d ON d.data_id = p.data_id
+        WHERE p.status = 'orphaned' AND NOT p.chain_frozen
+          AND p.orphaned_at <= $1
+          AND d.encrypted_payload IS NOT NULL AND NOT d.legal_hold
+          AND NOT EXISTS (
+              SELECT 1 FROM pointers other
+              WHERE other.data_id = p.data_id AND other.status != 'orphaned'
+          )
+        ORDER BY p.orphaned_at, p.pointer_id
+        LIMIT $2
+        FOR UPDATE OF p SKIP LOCKED
+        "#,
+    )
+    .bind(orphaned_before)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("Failed to lock purgeable orphans")?;
+
+    Ok(pointers)
+}
+
+/// Lock pending orphans due at `now`, skipping frozen chains, held pointers
+/// and rows another sweeper holds
+pub async fn lock_due_pending_orphans<'e>(
+    executor: impl PgExecutor<'e>,
+    now: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<Pointer>> {
+    let pointers = sqlx::query_as::<_, Pointer>(
+        r#"
+        SELECT * FROM pointers
+        WHERE status = 'pending_orphan' AND NOT chain_frozen AND NOT legal_hold
+          AND orphan_effective_at <= $1
+        ORDER BY orphan_effective_at, pointer_id
+        LIMIT $2
+        FOR UPDATE SKIP LOCKED
+        "#,
+    )
+    .bind(now)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("Failed to lock due pending orphans")?;
+
+    Ok(pointers)
+}
+
+/// Lock a set of pointers for the rest of the transaction
+pub async fn lock_pointers_for_update<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_ids: &[Uuid],
+) -> Result<Vec<Pointer>> {
+    let pointers = sqlx::query_as::<_, Pointer>(
+        r#"
+        SELECT * FROM pointers
+        WHERE pointer_id = ANY($1)
+        ORDER BY pointer_id
+        FOR UPDATE
+        "#,
+    )
+    .bind(pointer_ids)
+    .fetch_all(executor)
+    .await
+    .context("Failed to lock pointers")?;
+
+    Ok(pointers)
+}
+
+pub async fn get_pointers_by_subject(pool: &PgPool, subject_id: &str) -> Result<Vec<Pointer>> {
+    let pointers = sqlx::query_as::<_, Pointer>(
+        r#"
+        SELECT * FROM pointers
+        WHERE subject_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(subject_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query pointers by subject")?;
+
+    Ok(pointers)
+}
+
+/// Every organization holding a pointer for the subject
+pub async fn get_subject_org_ids(pool: &PgPool, subject_id: &str) -> Result<Vec<Uuid>> {
+    let org_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT DISTINCT org_id FROM pointers
+        WHERE subject_id = $1
+        "#,
+    )
+    .bind(subject_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query organizations by subject")?;
+
+    Ok(org_ids)
+}
+
+/// A page of an org's pointers, newest first. Unset filters bind NULL and
+/// drop out of the WHERE clause.
+pub async fn list_pointers(
+    pool: &PgPool,
+    filter: &PointerFilter,
+    page: &Pagination,
+) -> Result<Vec<Pointer>> {
+    let pointers = sqlx::query_as::<_, Pointer>(
+        r#"
+        SELECT * FROM pointers
+        WHERE org_id = $1
+          AND ($2::pointer_status IS NULL OR status = $2)
+          AND ($3::text IS NULL OR subject_id = $3)
+          AND ($4::timestamptz IS NULL OR created_at >= $4)
+          AND ($5::timestamptz IS NULL OR created_at < $5)
+          AND ($6::timestamptz IS NULL OR (created_at, pointer_id) < ($6, $7))
+        ORDER BY created_at DESC, pointer_id DESC
+        LIMIT $8
+        "#,
+    )
+    .bind(filter.org_id)
+    .bind(filter.status.clone())
+    .bind(filter.subject_id.as_deref())
+    .bind(filter.created_after)
+    .bind(filter.created_before)
+    .bind(page.after.map(|(created_at, _)| created_at))
+    .bind(page.after.map(|(_, pointer_id)| pointer_id))
+    .bind(page.limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list pointers")?;
+
+    Ok(pointers)
+}
+
+/// Claim an idempotency key for a create inside the caller's transaction.
+/// None when claimed, which an expired record doesn't prevent; otherwise
+/// the live record of the earlier request. A concurrent claim of the same key
+/// waits on the unique index until the first transaction ends, then sees
+/// its record, so only one of them ever creates.
+pub async fn claim_idempotency_key(
+    conn: &mut PgConnection,
+    org_id: Uuid,
+    idempotency_key: &str,
+    content_hash: &str,
+    now: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+) -> Result<Option<IdempotencyRecord>> {
+    let claimed = sqlx::query_as::<_, IdempotencyRecord>(
+        r#"
+        INSERT INTO idempotency_keys
+            (org_id, idempotency_key, content_hash, created_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (org_id, idempotency_key) DO UPDATE
+            SET content_hash = EXCLUDED.content_hash,
+                pointer_id = NULL,
+                response_json = NULL,
+                created_at = EXCLUDED.created_at,
+                expires_at = EXCLUDED.expires_at
+            WHERE idempotency_keys.expires_at <= EXCLUDED.created_at
+        RETURNING *
+        "#,
+    )
+    .bind(org_id)
+    .bind(idempotency_key)
+    .bind(content_hash)
+    .bind(now)
+    .bind(expires_at)
+    .fetch_optional(&mut *conn)
+    .await
+    .context("Failed to claim idempotency key")?;
+    if claimed.is_some() {
+        return Ok(None);
+    }
+
+    let existing = sqlx::query_as::<_, IdempotencyRecord>(
+        "SELECT * FROM idempotency_keys WHERE org_id = $1 AND idempotency_key = $2",
+    )
+    .bind(org_id)
+    .bind(idempotency_key)
+    .fetch_one(&mut *conn)
+    .await
+    .context("Failed to read idempotency key")?;
+
+    Ok(Some(existing))
+}
+
+/// Record what a claimed key's create produced
+pub async fn complete_idempotency_key<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+    idempotency_key: &str,
+    pointer_id: Uuid,
+    response_json: serde_json::Value,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE idempotency_keys
+        SET pointer_id = $3, response_json = $4
+        WHERE org_id = $1 AND idempotency_key = $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(idempotency_key)
+    .bind(pointer_id)
+    .bind(response_json)
+    .execute(executor)
+    .await
+    .context("Failed to complete idempotency key")?;
+
+    Ok(())
+}
+
+// ============================================================================
+// GOVERNANCE RECEIPT QUERIES
+// ============================================================================
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_governance_receipt<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    org_id: Uuid,
+    operation: ReceiptOperation,
+    receipt_json: serde_json::Value,
+    receipt_hash: &str,
+    signature: &[u8],
+    signature_algorithm: &str,
+    key_id: &str,
+    prev_hash: Option<&str>,
+) -> Result<GovernanceReceipt> {
+    let receipt = sqlx::query_as::<_, GovernanceReceipt>(
+        r#"
+        INSERT INTO governance_receipts
+            (pointer_id, org_id, operation, receipt_json, receipt_hash,
+             signature, signature_algorithm, key_id, prev_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(org_id)
+    .bind(operation)
+    .bind(receipt_json)
+    .bind(receipt_hash)
+    .bind(signature)
+    .bind(signature_algorithm)
+    .bind(key_id)
+    .bind(prev_hash)
+    .fetch_one(executor)
+    .await
+    .context("Failed to insert governance receipt")?;
+
+    Ok(receipt)
+}
+
+pub async fn get_receipts_by_pointer(
+    pool: &PgPool,
+    pointer_id: Uuid,
+) -> Result<Vec<GovernanceReceipt>> {
+    let receipts = sqlx::query_as::<_, GovernanceReceipt>(
+        r#"
+        SELECT r.*, o.content AS overflow
+        FROM governance_receipts r
+        LEFT JOIN receipt_overflow o
+            ON o.overflow_hash = r.receipt_json #>> '{metadata,overflow,sha3_512}'
+        WHERE r.pointer_id = $1
+        ORDER BY r.timestamp ASC
+        "#,
+    )
+    .bind(pointer_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query receipts by pointer")?;
+
+    Ok(receipts)
+}
+
+/// A page of a pointer's receipts, oldest first, optionally of one operation
+pub async fn get_receipts_by_pointer_page(
+    pool: &PgPool,
+    pointer_id: Uuid,
+    operation: Option<&str>,
+    page: &Pagination,
+) -> Result<Vec<GovernanceReceipt>> {
+    let receipts = sqlx::query_as::<_, GovernanceReceipt>(
+        r#"
+        SELECT r.*, o.content AS overflow
+        FROM governance_receipts r
+        LEFT JOIN receipt_overflow o
+            ON o.overflow_hash = r.receipt_json #>> '{metadata,overflow,sha3_512}'
+        WHERE r.pointer_id = $1
+          AND ($2::text IS NULL OR r.operation::text = $2)
+          AND ($3::timestamptz IS NULL OR (r.timestamp, r.receipt_id) > ($3, $4))
+        ORDER BY r.timestamp ASC, r.receipt_id ASC
+        LIMIT $5
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(operation)
+    .bind(page.after.map(|(timestamp, _)| timestamp))
+    .bind(page.after.map(|(_, receipt_id)| receipt_id))
+    .bind(page.limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query receipt page")?;
+
+    Ok(receipts)
+}
+
+/// Store a receipt field moved out of the signed JSON. Records are keyed by
+/// content hash, so storing one twice is a no-op.
+pub async fn create_receipt_overflow<'e>(
+    executor: impl PgExecutor<'e>,
+    overflow_hash: &str,
+    content: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO receipt_overflow (overflow_hash, content, byte_size)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (overflow_hash) DO NOTHING
+        "#,
+    )
+    .bind(overflow_hash)
+    .bind(content)
+    .bind(content.len() as i32)
+    .execute(executor)
+    .await
+    .context("Failed to insert receipt overflow")?;
+
+    Ok(())
+}
+
+pub async fn get_receipt_overflow<'e>(
+    executor: impl PgExecutor<'e>,
+    overflow_hash: &str,
+) -> Result<Option<String>> {
+    let content = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT content FROM receipt_overflow
+        WHERE overflow_hash = $1
+        "#,
+    )
+    .bind(overflow_hash)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to query receipt overflow")?;
+
+    Ok(content)
+}
+
+/// The receipt with this hash. Hashes are unique, so two receipts sharing
+/// one means the table was tampered with and is an error, not a pick.
+pub async fn get_receipt_by_hash(
+    pool: &PgPool,
+    receipt_hash: &str,
+) -> Result<Option<GovernanceReceipt>> {
+    let mut receipts = sqlx::query_as::<_, GovernanceReceipt>(
+        r#"
+        SELECT r.*, o.content AS overflow
+        FROM governance_receipts r
+        LEFT JOIN receipt_overflow o
+            ON o.overflow_hash = r.receipt_json #>> '{metadata,overflow,sha3_512}'
+        WHERE r.receipt_hash = $1
+        ORDER BY r.timestamp ASC
+        LIMIT 2
+        "#,
+    )
+    .bind(receipt_hash)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query receipt by hash")?;
+
+    if receipts.len() > 1 {
+        bail!(
+            "Receipts {} and {} share receipt_hash {}",
+            receipts[0].receipt_id,
+            receipts[1].receipt_id,
+            receipt_hash
+        );
+    }
+    Ok(receipts.pop())
+}
+
+/// An org's receipts whose hash starts with `prefix`, oldest first
+pub async fn get_receipts_by_hash_prefix(
+    pool: &PgPool,
+    org_id: Uuid,
+    prefix: &str,
+    limit: i64,
+) -> Result<Vec<GovernanceReceipt>> {
+    let receipts = sqlx::query_as::<_, GovernanceReceipt>(
+        r#"
+        SELECT r.*, o.content AS overflow
+        FROM governance_receipts r
+        LEFT JOIN receipt_overflow o
+            ON o.overflow_hash = r.receipt_json #>> '{metadata,overflow,sha3_512}'
+        WHERE r.receipt_hash LIKE $1 || '%' AND r.org_id = $2
+        ORDER BY r.timestamp ASC, r.receipt_id ASC
+        LIMIT $3
+        "#,
+    )
+    .bind(prefix)
+    .bind(org_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query receipts by hash prefix")?;
+
+    Ok(receipts)
+}
+
+/// The Orphan receipt that vetoed a pointer, if any
+pub async fn get_orphan_receipt<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+) -> Result<Option<GovernanceReceipt>> {
+    let receipt = sqlx::query_as::<_, GovernanceReceipt>(
+        r#"
+        SELECT * FROM governance_receipts
+        WHERE pointer_id = $1 AND operation = 'orphan'
+        ORDER BY timestamp DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(pointer_id)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to query orphan receipt")?;
+
+    Ok(receipt)
+}
+
+pub async fn get_latest_receipt_hash<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+) -> Result<Option<String>> {
+    let result = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+        SELECT receipt_hash FROM governance_receipts
+        WHERE pointer_id = $1
+        ORDER BY timestamp DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(pointer_id)
+    .fetch_one(executor)
+    .await
+    .context("Failed to query latest receipt hash")?;
+
+    Ok(result)
+}
+
+// ============================================================================
+// AUDIT LOG QUERIES
+// ============================================================================
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_audit_log<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Option<Uuid>,
+    pointer_id: Option<Uuid>,
+    receipt_id: Option<Uuid>,
+    event_type: &str,
+    event_data: serde_json::Value,
+    actor_id: Option<&str>,
+    ip_address: Option<std::net::IpAddr>,
+    user_agent: Option<&str>,
+) -> Result<AuditLog> {
+    let log = sqlx::query_as::<_, AuditLog>(
+        r#"
+        INSERT INTO audit_log
+            (org_id, pointer_id, receipt_id, event_type, event_data, actor_id,
+             ip_address, user_agent)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+    )
+    .bind(org_id)
+    .bind(pointer_id)
+    .bind(receipt_id)
+    .bind(event_type)
+    .bind(event_data)
+    .bind(actor_id)
+    .bind(ip_address)
+    .bind(user_agent)
+    .fetch_one(executor)
+    .await
+    .context("Failed to insert audit log")?;
+
+    Ok(log)
+}
+
+/// A page of a subject's audit events, newest first
+/// A subject's audit trail, newest first; every matching row without `page`
+fn audit_trail_by_subject<'q>(
+    subject_id: &'q str,
+    filter: &'q AuditFilter,
+    page: Option<&Pagination>,
+) -> sqlx::query::QueryAs<'q, sqlx::Postgres, AuditLog, sqlx::postgres::PgArguments> {
+    let after = page.and_then(|p| p.after);
+    sqlx::query_as::<_, AuditLog>(
+        r#"
+        SELECT al.* FROM audit_log al
+        LEFT JOIN pointers p ON al.pointer_id = p.pointer_id
+        WHERE (p.subject_id = $1
+               OR (al.pointer_id IS NULL AND al.event_data->>'subject_id' = $1))
+          AND ($2::text IS NULL OR al.event_type = $2)
+          AND ($3::timestamptz IS NULL OR al.timestamp >= $3)
+          AND ($4::timestamptz IS NULL OR al.timestamp < $4)
+          AND ($5::timestamptz IS NULL OR (al.timestamp, al.log_id) < ($5, $6))
+          AND ($8::uuid IS NULL OR al.org_id = $8)
+        ORDER BY al.timestamp DESC, al.log_id DESC
+        LIMIT $7
+        "#,
+    )
+    .bind(subject_id)
+    .bind(filter.event_type.as_deref())
+    .bind(filter.from)
+    .bind(filter.to)
+    .bind(after.map(|(timestamp, _)| timestamp))
+    .bind(after.map(|(_, log_id)| log_id))
+    .bind(page.map(|p| p.limit))
+    .bind(filter.org_id)
+}
+
+pub async fn get_audit_trail_by_subject(
+    pool: &PgPool,
+    subject_id: &str,
+    filter: &AuditFilter,
+    page: &Pagination,
+) -> Result<Vec<AuditLog>> {
+    let logs = audit_trail_by_subject(subject_id, filter, Some(page))
+        .fetch_all(pool)
+        .await
+        .context("Failed to query audit trail by subject")?;
+
+    Ok(logs)
+}
+
+/// The whole audit trail as rows arrive from the server, for exports too
+/// large to hold
+pub fn stream_audit_trail_by_subject<'a>(
+    pool: &'a PgPool,
+    subject_id: &'a str,
+    filter: &'a AuditFilter,
+) -> impl tokio_stream::Stream<Item = Result<AuditLog>> + 'a {
+    use tokio_stream::StreamExt;
+
+    audit_trail_by_subject(subject_id, filter, None)
+        .fetch(pool)
+        .map(|row| row.context("Failed to stream audit trail by subject"))
+}
+
+// ============================================================================
+// ORPHAN GROUP QUERIES
+// ============================================================================
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_orphan_group<'e>(
+    executor: impl PgExecutor<'e>,
+    group_id: Uuid,
+    org_id: Uuid,
+    member_pointer_ids: &[Uuid],
+    reason: &str,
+    manifest_json: serde_json::Value,
+    manifest_hash: &str,
+    signature: &[u8],
+    signature_algorithm: &str,
+) -> Result<OrphanGroup> {
+    let group = sqlx::query_as::<_, OrphanGroup>(
+        r#"
+        INSERT INTO orphan_groups
+            (group_id, org_id, member_pointer_ids, reason, manifest_json,
+             manifest_hash, signature, signature_algorithm)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+    )
+    .bind(group_id)
+    .bind(org_id)
+    .bind(member_pointer_ids)
+    .bind(reason)
+    .bind(manifest_json)
+    .bind(manifest_hash)
+    .bind(signature)
+    .bind(signature_algorithm)
+    .fetch_one(executor)
+    .await
+    .context("Failed to insert orphan group")?;
+
+    Ok(group)
+}
+
+pub async fn get_orphan_group(pool: &PgPool, group_id: Uuid) -> Result<Option<OrphanGroup>> {
+    let group = sqlx::query_as::<_, OrphanGroup>(
+        r#"
+        SELECT * FROM orphan_groups WHERE group_id = $1
+        "#,
+    )
+    .bind(group_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to query orphan group")?;
+
+    Ok(group)
+}
+
+// ============================================================================
+// RESOLVE STATS QUERIES
+// ============================================================================
+
+/// Recompute hourly resolve counts from resolve receipts, by the time each
+/// was signed at, for every bucket starting at or after `since`. Idempotent,
+/// so overlapping runs are safe.
+pub async fn aggregate_resolve_stats(pool: &PgPool, since: DateTime<Utc>) -> Result<u64> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO resolve_stats (pointer_id, bucket_start, resolve_count)
+        SELECT pointer_id, date_trunc('hour', receipt_signed_at(receipt_json)), COUNT(*)
+        FROM governance_receipts
+        WHERE operation = 'resolve'
+          AND receipt_signed_at(receipt_json) >= date_trunc('hour', $1::timestamptz)
+        GROUP BY pointer_id, date_trunc('hour', receipt_signed_at(receipt_json))
+        ON CONFLICT (pointer_id, bucket_start)
+        DO UPDATE SET resolve_count = EXCLUDED.resolve_count
+        "#,
+    )
+    .bind(since)
+    .execute(pool)
+    .await
+    .context("Failed to aggregate resolve stats")?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn get_resolve_stats(
+    pool: &PgPool,
+    pointer_id: Uuid,
+    since: DateTime<Utc>,
+) -> Result<Vec<ResolveStat>> {
+    let stats = sqlx::query_as::<_, ResolveStat>(
+        r#"
+        SELECT * FROM resolve_stats
+        WHERE pointer_id = $1 AND bucket_start >= date_trunc('hour', $2::timestamptz)
+        ORDER BY bucket_start ASC
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query resolve stats")?;
+
+    Ok(stats)
+}
+
+/// Unalerted buckets at `bucket_start` with the average of the preceding
+/// `baseline_hours` buckets (missing buckets count as zero)
+pub async fn get_resolve_spike_candidates(
+    pool: &PgPool,
+    bucket_start: DateTime<Utc>,
+    baseline_hours: i32,
+) -> Result<Vec<ResolveSpikeCandidate>> {
+    let candidates = sqlx::query_as::<_, ResolveSpikeCandidate>(
+        r#"
+        SELECT
+            s.pointer_id,
+            p.org_id,
+            p.subject_id,
+            s.bucket_start,
+            s.resolve_count,
+            COALESCE((
+                SELECT SUM(b.resolve_count)::float8 / $2
+                FROM resolve_stats b
+                WHERE b.pointer_id = s.pointer_id
+                  AND b.bucket_start >= s.bucket_start - make_interval(hours => $2)
+                  AND b.bucket_start < s.bucket_start
+            ), 0) AS baseline
+        FROM resolve_stats s
+        JOIN pointers p ON p.pointer_id = s.pointer_id
+        WHERE s.bucket_start = $1 AND s.alerted_at IS NULL
+        "#,
+    )
+    .bind(bucket_start)
+    .bind(baseline_hours)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query resolve spike candidates")?;
+
+    Ok(candidates)
+}
+
+/// Claim the bucket's alert, true only for the one caller that flips
+/// alerted_at from null; concurrent detectors on other replicas get false
+pub async fn claim_resolve_spike_alert<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    bucket_start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<bool> {
+    let claimed = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        UPDATE resolve_stats SET alerted_at = $3
+        WHERE pointer_id = $1 AND bucket_start = $2 AND alerted_at IS NULL
+        RETURNING pointer_id
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(bucket_start)
+    .bind(now)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to claim resolve spike alert")?;
+
+    Ok(claimed.is_some())
+}
+
+// ============================================================================
+// POINTER CHANGES FEED QUERIES
+// ============================================================================
+
+/// Pointers last written after the `(change_xid, pointer_id)` cursor, oldest
+/// first. Rows from transactions at or above the snapshot xmin are held back:
+/// an older transaction may still be open, and returning newer rows first
+/// would let the cursor skip past its writes once it commits.
+pub async fn get_pointer_changes(
+    pool: &PgPool,
+    org_id: Uuid,
+    after_xid: u64,
+    after_pointer_id: Uuid,
+    limit: i64,
+) -> Result<Vec<PointerChange>> {
+    let changes = sqlx::query_as::<_, PointerChange>(
+        r#"
+        SELECT
+            pointer_id, org_id, subject_id, status, orphaned_at,
+            COALESCE(metadata, '{}'::jsonb) AS metadata,
+            updated_at, change_xid::text AS change_xid
+        FROM pointers
+        WHERE org_id = $1
+          AND change_xid < pg_snapshot_xmin(pg_current_snapshot())
+          AND (change_xid, pointer_id) > ($2::text::xid8, $3)
+        ORDER BY change_xid, pointer_id
+        LIMIT $4
+        "#,
+    )
+    .bind(org_id)
+    .bind(after_xid.to_string())
+    .bind(after_pointer_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query pointer changes")?;
+
+    Ok(changes)
+}
+
+// ============================================================================
+// SUBJECT QUERIES
+// ============================================================================
+
+/// Keep the full value behind a digest subject key; the first write wins
+pub async fn upsert_subject_value<'e>(
+    executor: impl PgExecutor<'e>,
+    subject_key: &str,
+    subject_value: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO subjects (subject_key, subject_value)
+        VALUES ($1, $2)
+        ON CONFLICT (subject_key) DO NOTHING
+        "#,
+    )
+    .bind(subject_key)
+    .bind(subject_value)
+    .execute(executor)
+    .await
+    .context("Failed to store subject value")?;
+
+    Ok(())
+}
+
+/// Every distinct subject_id on a pointer, with its pointer count
+pub async fn get_subject_pointer_counts(
+    pool: &PgPool,
+    org_id: Uuid,
+) -> Result<Vec<SubjectPointerCount>> {
+    let counts = sqlx::query_as::<_, SubjectPointerCount>(
+        r#"
+        SELECT subject_id, COUNT(*) AS pointer_count
+        FROM pointers
+        WHERE org_id = $1
+        GROUP BY subject_id
+        ORDER BY subject_id
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query subject pointer counts")?;
+
+    Ok(counts)
+}
+
+pub async fn lock_pointers_by_subject<'e>(
+    executor: impl PgExecutor<'e>,
+    subject_id: &str,
+) -> Result<Vec<Pointer>> {
+    let pointers = sqlx::query_as::<_, Pointer>(
+        r#"
+        SELECT * FROM pointers
+        WHERE subject_id = $1
+        ORDER BY pointer_id
+        FOR UPDATE
+        "#,
+    )
+    .bind(subject_id)
+    .fetch_all(executor)
+    .await
+    .context("Failed to lock pointers by subject")?;
+
+    Ok(pointers)
+}
+
+/// Move a pointer and its data row to another subject_id
+pub async fn rehome_pointer_subject<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    subject_id: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        WITH moved AS (
+            UPDATE pointers SET subject_id = $2
+            WHERE pointer_id = $1
+            RETURNING data_id
+        )
+        UPDATE data_store SET subject_id = $2
+        WHERE data_id IN (SELECT data_id FROM moved)
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(subject_id)
+    .execute(executor)
+    .await
+    .context("Failed to re-home pointer subject")?;
+
+    Ok(())
+}
+
+// ============================================================================
+// POINTER RESTRICTION QUERIES
+// ============================================================================
+
+pub async fn create_pointer_restriction<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    reason: &str,
+    restricted_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+) -> Result<PointerRestriction> {
+    let restriction = sqlx::query_as::<_, PointerRestriction>(
+        r#"
+        WITH inserted AS (
+            INSERT INTO pointer_restrictions (pointer_id, org_id, reason, restricted_at, expires_at)
+            SELECT pointer_id, org_id, $2, $3, $4 FROM pointers WHERE pointer_id = $1
+            RETURNING *
+        )
+        SELECT r.*, p.subject_id
+        FROM inserted r
+        JOIN pointers p ON p.pointer_id = r.pointer_id
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(reason)
+    .bind(restricted_at)
+    .bind(expires_at)
+    .fetch_one(executor)
+    .await
+    .context("Failed to create pointer restriction")?;
+
+    Ok(restriction)
+}
+
+/// The pointer's open (not yet lifted) restriction, expired or not
+pub async fn get_open_restriction<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+) -> Result<Option<PointerRestriction>> {
+    let restriction = sqlx::query_as::<_, PointerRestriction>(
+        r#"
+        SELECT r.*, p.subject_id
+        FROM pointer_restrictions r
+        JOIN pointers p ON p.pointer_id = r.pointer_id
+        WHERE r.pointer_id = $1 AND r.lifted_at IS NULL
+        "#,
+    )
+    .bind(pointer_id)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to query open restriction")?;
+
+    Ok(restriction)
+}
+
+/// Every restriction ever placed on the subject's pointers, newest first
+pub async fn get_restrictions_by_subject(
+    pool: &PgPool,
+    subject_id: &str,
+) -> Result<Vec<PointerRestriction>> {
+    let restrictions = sqlx::query_as::<_, PointerRestriction>(
+        r#"
+        SELECT r.*, p.subject_id
+        FROM pointer_restrictions r
+        JOIN pointers p ON p.pointer_id = r.pointer_id
+        WHERE p.subject_id = $1
+        ORDER BY r.restricted_at DESC, r.pointer_id
+        "#,
+    )
+    .bind(subject_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query restrictions by subject")?;
+
+    Ok(restrictions)
+}
+
+pub async fn lock_open_restrictions_by_subject<'e>(
+    executor: impl PgExecutor<'e>,
+    subject_id: &str,
+) -> Result<Vec<PointerRestriction>> {
+    let restrictions = sqlx::query_as::<_, PointerRestriction>(
+        r#"
+        SELECT r.*, p.subject_id
+        FROM pointer_restrictions r
+        JOIN pointers p ON p.pointer_id = r.pointer_id
+        WHERE p.subject_id = $1 AND r.lifted_at IS NULL
+        ORDER BY r.pointer_id
+        FOR UPDATE OF r
+        "#,
+    )
+    .bind(subject_id)
+    .fetch_all(executor)
+    .await
+    .context("Failed to lock open restrictions")?;
+
+    Ok(restrictions)
+}
+
+/// Open restrictions whose window has ended, skipping rows another sweeper holds
+pub async fn lock_expired_restrictions<'e>(
+    executor: impl PgExecutor<'e>,
+    now: DateTime<Utc>,
+    pointer_ids: Option<&[Uuid]>,
+    limit: i64,
+) -> Result<Vec<PointerRestriction>> {
+    let restrictions = sqlx::query_as::<_, PointerRestriction>(
+        r#"
+        SELECT r.*, p.subject_id
+        FROM pointer_restrictions r
+        JOIN pointers p ON p.pointer_id = r.pointer_id
+        WHERE r.lifted_at IS NULL AND r.expires_at <= $1 AND NOT p.chain_frozen
+          AND ($2::uuid[] IS NULL OR r.pointer_id = ANY($2))
+        ORDER BY r.expires_at
+        LIMIT $3
+        FOR UPDATE OF r SKIP LOCKED
+        "#,
+    )
+    .bind(now)
+    .bind(pointer_ids)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("Failed to lock expired restrictions")?;
+
+    Ok(restrictions)
+}
+
+/// Close the pointer's open restriction, if any
+pub async fn lift_open_restriction<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    lift_reason: &str,
+) -> Result<Option<PointerRestriction>> {
+    let restriction = sqlx::query_as::<_, PointerRestriction>(
+        r#"
+        UPDATE pointer_restrictions r
+        SET lifted_at = NOW(), lift_reason = $2
+        FROM pointers p
+        WHERE r.pointer_id = $1 AND r.lifted_at IS NULL AND p.pointer_id = r.pointer_id
+        RETURNING r.*, p.subject_id
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(lift_reason)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to lift restriction")?;
+
+    Ok(restriction)
+}
+
+// ============================================================================
+// ORPHAN REQUEST QUERIES
+// ============================================================================
+
+/// Close the pointer's pending request if its window has ended, so a new
+/// one can be made; returns the request it expired
+pub async fn expire_orphan_request<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<Option<OrphanRequest>> {
+    let request = sqlx::query_as::<_, OrphanRequest>(
+        r#"
+        UPDATE orphan_requests
+        SET status = 'expired', closed_at = $2
+        WHERE pointer_id = $1 AND status = 'pending' AND expires_at <= $2
+        RETURNING *
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(now)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to expire orphan request")?;
+
+    Ok(request)
+}
+
+pub async fn create_orphan_request<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer: &Pointer,
+    reason: Option<&str>,
+    requested_by: &str,
+    requested_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+) -> Result<OrphanRequest> {
+    let request = sqlx::query_as::<_, OrphanRequest>(
+        r#"
+        INSERT INTO orphan_requests
+            (pointer_id, org_id, reason, requested_by, requested_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(pointer.pointer_id)
+    .bind(pointer.org_id)
+    .bind(reason)
+    .bind(requested_by)
+    .bind(requested_at)
+    .bind(expires_at)
+    .fetch_one(executor)
+    .await
+    .context("Failed to insert orphan request")?;
+
+    Ok(request)
+}
+
+pub async fn get_orphan_request<'e>(
+    executor: impl PgExecutor<'e>,
+    request_id: Uuid,
+) -> Result<Option<OrphanRequest>> {
+    let request =
+        sqlx::query_as::<_, OrphanRequest>("SELECT * FROM orphan_requests WHERE request_id = $1")
+            .bind(request_id)
+            .fetch_optional(executor)
+            .await
+            .context("Failed to query orphan request")?;
+
+    Ok(request)
+}
+
+/// Record the approval of a pending request; None if it is not pending
+pub async fn approve_orphan_request<'e>(
+    executor: impl PgExecutor<'e>,
+    request_id: Uuid,
+    approved_by: &str,
+    approved_at: DateTime<Utc>,
+) -> Result<Option<OrphanRequest>> {
+    let request = sqlx::query_as::<_, OrphanRequest>(
+        r#"
+        UPDATE orphan_requests
+        SET status = 'approved', approved_by = $2, closed_at = $3
+        WHERE request_id = $1 AND status = 'pending'
+        RETURNING *
+        "#,
+    )
+    .bind(request_id)
+    .bind(approved_by)
+    .bind(approved_at)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to approve orphan request")?;
+
+    Ok(request)
+}
+
+/// A pointer's orphan requests, newest first
+pub async fn get_orphan_requests(pool: &PgPool, pointer_id: Uuid) -> Result<Vec<OrphanRequest>> {
+    let requests = sqlx::query_as::<_, OrphanRequest>(
+        r#"
+        SELECT * FROM orphan_requests
+        WHERE pointer_id = $1
+        ORDER BY requested_at DESC, request_id
+        "#,
+    )
+    .bind(pointer_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query orphan requests")?;
+
+    Ok(requests)
+}
+
+// ============================================================================
+// CONSISTENCY QUERIES
+// ============================================================================
+
+const POINTER_CHAIN_STATE: &str = r#"
+    SELECT
+        p.pointer_id, p.org_id, p.subject_id, p.status, p.orphaned_at,
+        (
+            SELECT MIN(r.timestamp) FROM governance_receipts r
+            WHERE r.pointer_id = p.pointer_id AND r.operation = 'orphan'
+              AND NOT EXISTS (
+                  SELECT 1 FROM governance_receipts later
+                  WHERE later.pointer_id = p.pointer_id
+                    AND later.operation = 'reinstate'
+                    AND later.timestamp > r.timestamp
+              )
+        ) AS chain_orphaned_at,
+        (
+            SELECT MAX(r.timestamp) FROM governance_receipts r
+            WHERE r.pointer_id = p.pointer_id
+        ) AS last_receipt_at
+    FROM pointers p
+"#;
+
+pub async fn get_pointer_chain_state(
+    pool: &PgPool,
+    pointer_id: Uuid,
+) -> Result<Option<PointerChainState>> {
+    let state = sqlx::query_as::<_, PointerChainState>(&format!(
+        "{} WHERE p.pointer_id = $1",
+        POINTER_CHAIN_STATE
+    ))
+    .bind(pointer_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to query pointer chain state")?;
+
+    Ok(state)
+}
+
+pub async fn get_subject_chain_states(
+    pool: &PgPool,
+    subject_id: &str,
+) -> Result<Vec<PointerChainState>> {
+    let states = sqlx::query_as::<_, PointerChainState>(&format!(
+        "{} WHERE p.subject_id = $1 ORDER BY p.created_at",
+        POINTER_CHAIN_STATE
+    ))
+    .bind(subject_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query subject chain states")?;
+
+    Ok(states)
+}
+
+/// A random sample of pointers for background auditing
+pub async fn sample_pointer_chain_states(
+    pool: &PgPool,
+    sample_size: i64,
+) -> Result<Vec<PointerChainState>> {
+    let states = sqlx::query_as::<_, PointerChainState>(&format!(
+        "{} ORDER BY random() LIMIT $1",
+        POINTER_CHAIN_STATE
+    ))
+    .bind(sample_size)
+    .fetch_all(pool)
+    .await
+    .context("Failed to sample pointer chain states")?;
+
+    Ok(states)
+}
+
+/// Overwrite a pointer's status, bypassing the normal orphan path
+pub async fn set_pointer_status<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    status: PointerStatus,
+    orphaned_at: Option<DateTime<Utc>>,
+) -> Result<Pointer> {
+    let pointer = sqlx::query_as::<_, Pointer>(
+        r#"
+        UPDATE pointers
+        SET status = $2, orphaned_at = $3
+        WHERE pointer_id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(status)
+    .bind(orphaned_at)
+    .fetch_one(executor)
+    .await
+    .context("Failed to set pointer status")?;
+
+    Ok(pointer)
+}
+
+// ============================================================================
+// HEARTBEAT QUERIES
+// ============================================================================
+
+/// Orgs with heartbeats enabled, with their interval in seconds
+pub async fn get_heartbeat_orgs(pool: &PgPool) -> Result<Vec<(Uuid, i32)>> {
+    let orgs = sqlx::query_as::<_, (Uuid, i32)>(
+        r#"
+        SELECT org_id, heartbeat_interval_seconds FROM organizations
+        WHERE heartbeat_interval_seconds IS NOT NULL
+        ORDER BY org_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to query heartbeat orgs")?;
+
+    Ok(orgs)
+}
+
+/// Lock an org's row so only one instance extends its heartbeat chain at a
+/// time; returns the current interval, None if disabled or missing
+pub async fn lock_org_heartbeat_interval<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+) -> Result<Option<i32>> {
+    let interval = sqlx::query_scalar::<_, Option<i32>>(
+        r#"
+        SELECT heartbeat_interval_seconds FROM organizations
+        WHERE org_id = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to lock org for heartbeat")?;
+
+    Ok(interval.flatten())
+}
+
+pub async fn get_latest_heartbeat<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+) -> Result<Option<OrgHeartbeat>> {
+    let heartbeat = sqlx::query_as::<_, OrgHeartbeat>(
+        r#"
+        SELECT * FROM org_heartbeats
+        WHERE org_id = $1
+        ORDER BY interval_start DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to query latest heartbeat")?;
+
+    Ok(heartbeat)
+}
+
+/// Receipts written for an org in [start, end), by operation
+pub async fn count_org_operations<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<(String, i64)>> {
+    let counts = sqlx::query_as::<_, (String, i64)>(
+        r#"
+        SELECT operation::text, COUNT(*) FROM governance_receipts
+        WHERE org_id = $1 AND timestamp >= $2 AND timestamp < $3
+        GROUP BY operation
+        ORDER BY operation::text
+        "#,
+    )
+    .bind(org_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(executor)
+    .await
+    .context("Failed to count org operations")?;
+
+    Ok(counts)
+}
+
+/// Current chain head of the org's `limit` most active pointers in [start, end)
+pub async fn get_active_chain_heads<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<(Uuid, String)>> {
+    let heads = sqlx::query_as::<_, (Uuid, String)>(
+        r#"
+        WITH active AS (
+            SELECT pointer_id, COUNT(*) AS receipts FROM governance_receipts
+            WHERE org_id = $1 AND timestamp >= $2 AND timestamp < $3
+            GROUP BY pointer_id
+            ORDER BY receipts DESC, pointer_id
+            LIMIT $4
+        )
+        SELECT a.pointer_id, (
+            SELECT r.receipt_hash FROM governance_receipts r
+            WHERE r.pointer_id = a.pointer_id
+            ORDER BY r.timestamp DESC
+            LIMIT 1
+        )
+        FROM active a
+        ORDER BY a.receipts DESC, a.pointer_id
+        "#,
+    )
+    .bind(org_id)
+    .bind(start)
+    .bind(end)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("Failed to query active chain heads")?;
+
+    Ok(heads)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_org_heartbeat<'e>(
+    executor: impl PgExecutor<'e>,
+    heartbeat_id: Uuid,
+    org_id: Uuid,
+    kind: &str,
+    interval_start: DateTime<Utc>,
+    interval_end: DateTime<Utc>,
+    receipt_json: serde_json::Value,
+    receipt_hash: &str,
+    signature: &[u8],
+    signature_algorithm: &str,
+    prev_hash: Option<&str>,
+) -> Result<OrgHeartbeat> {
+    let heartbeat = sqlx::query_as::<_, OrgHeartbeat>(
+        r#"
+        INSERT INTO org_heartbeats
+            (heartbeat_id, org_id, kind, interval_start, interval_end, receipt_json,
+             receipt_hash, signature, signature_algorithm, prev_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING *
+        "#,
+    )
+    .bind(heartbeat_id)
+    .bind(org_id)
+    .bind(kind)
+    .bind(interval_start)
+    .bind(interval_end)
+    .bind(receipt_json)
+    .bind(receipt_hash)
+    .bind(signature)
+    .bind(signature_algorithm)
+    .bind(prev_hash)
+    .fetch_one(executor)
+    .await
+    .context("Failed to insert heartbeat")?;
+
+    Ok(heartbeat)
+}
+
+/// Heartbeats whose interval overlaps [from, to), oldest first
+pub async fn get_org_heartbeats(
+    pool: &PgPool,
+    org_id: Uuid,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<OrgHeartbeat>> {
+    let heartbeats = sqlx::query_as::<_, OrgHeartbeat>(
+        r#"
+        SELECT * FROM org_heartbeats
+        WHERE org_id = $1 AND interval_end > $2 AND interval_start < $3
+        ORDER BY interval_start
+        "#,
+    )
+    .bind(org_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query heartbeats")?;
+
+    Ok(heartbeats)
+}
+
+// ============================================================================
+// RECEIPT CHECKPOINT QUERIES
+// ============================================================================
+
+/// Hold off other checkpoint writers until the caller's transaction ends;
+/// readers are unaffected
+pub async fn lock_receipt_checkpoints(conn: &mut PgConnection) -> Result<()> {
+    sqlx::query("LOCK TABLE receipt_checkpoints IN EXCLUSIVE MODE")
+        .execute(conn)
+        .await
+        .context("Failed to lock receipt checkpoints")?;
+
+    Ok(())
+}
+
+pub async fn get_latest_checkpoint<'e>(
+    executor: impl PgExecutor<'e>,
+) -> Result<Option<ReceiptCheckpoint>> {
+    let checkpoint = sqlx::query_as::<_, ReceiptCheckpoint>(
+        r#"
+        SELECT * FROM receipt_checkpoints
+        ORDER BY sequence DESC
+        LIMIT 1
+        "#,
+    )
+    .fetch_optional(executor)
+    .await
+    .context("Failed to query latest checkpoint")?;
+
+    Ok(checkpoint)
+}
+
+/// Governance receipts written at or after `since` and before `until` that
+/// no checkpoint covers yet, oldest first, as (receipt_id, receipt_hash,
+/// timestamp)
+pub async fn get_uncheckpointed_receipts<'e>(
+    executor: impl PgExecutor<'e>,
+    since: Option<DateTime<Utc>>,
+    until: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<(Uuid, String, DateTime<Utc>)>> {
+    let receipts = sqlx::query_as::<_, (Uuid, String, DateTime<Utc>)>(
+        r#"
+        SELECT r.receipt_id, r.receipt_hash, r.timestamp
+        FROM governance_receipts r
+        WHERE ($1::timestamptz IS NULL OR r.timestamp >= $1)
+          AND r.timestamp < $2
+          AND NOT EXISTS (
+              SELECT 1 FROM receipt_checkpoint_leaves l
+              WHERE l.receipt_id = r.receipt_id
+          )
+        ORDER BY r.timestamp ASC, r.receipt_id ASC
+        LIMIT $3
+        "#,
+    )
+    .bind(since)
+    .bind(until)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("Failed to query uncheckpointed receipts")?;
+
+    Ok(receipts)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_receipt_checkpoint<'e>(
+    executor: impl PgExecutor<'e>,
+    checkpoint_id: Uuid,
+    sequence: i64,
+    (period_start, period_end): (Option<DateTime<Utc>>, DateTime<Utc>),
+    receipt_count: i32,
+    merkle_root: &str,
+    receipt_json: serde_json::Value,
+    receipt_hash: &str,
+    signature: &[u8],
+    signature_algorithm: &str,
+    key_id: &str,
+    prev_hash: Option<&str>,
+) -> Result<ReceiptCheckpoint> {
+    let checkpoint = sqlx::query_as::<_, ReceiptCheckpoint>(
+        r#"
+        INSERT INTO receipt_checkpoints
+            (checkpoint_id, sequence, period_start, period_end, receipt_count, merkle_root,
+             receipt_json, receipt_hash, signature, signature_algorithm, key_id, prev_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING *
+        "#,
+    )
+    .bind(checkpoint_id)
+    .bind(sequence)
+    .bind(period_start)
+    .bind(period_end)
+    .bind(receipt_count)
+    .bind(merkle_root)
+    .bind(receipt_json)
+    .bind(receipt_hash)
+    .bind(signature)
+    .bind(signature_algorithm)
+    .bind(key_id)
+    .bind(prev_hash)
+    .fetch_one(executor)
+    .await
+    .context("Failed to insert receipt checkpoint")?;
+
+    Ok(checkpoint)
+}
+
+/// Record a checkpoint's leaves, positioned in the order given
+pub async fn create_checkpoint_leaves<'e>(
+    executor: impl PgExecutor<'e>,
+    checkpoint_id: Uuid,
+    receipt_ids: &[Uuid],
+    receipt_hashes: &[String],
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO receipt_checkpoint_leaves (checkpoint_id, position, receipt_id, receipt_hash)
+        SELECT $1, (leaf.ordinality - 1)::int, leaf.receipt_id, leaf.receipt_hash
+        FROM UNNEST($2::uuid[], $3::text[]) WITH ORDINALITY
+            AS leaf(receipt_id, receipt_hash, ordinality)
+        "#,
+    )
+    .bind(checkpoint_id)
+    .bind(receipt_ids)
+    .bind(receipt_hashes)
+    .execute(executor)
+    .await
+    .context("Failed to insert checkpoint leaves")?;
+
+    Ok(())
+}
+
+/// Checkpoints newest first
+pub async fn list_receipt_checkpoints(
+    pool: &PgPool,
+    page: &Pagination,
+) -> Result<Vec<ReceiptCheckpoint>> {
+    let checkpoints = sqlx::query_as::<_, ReceiptCheckpoint>(
+        r#"
+        SELECT * FROM receipt_checkpoints
+        WHERE ($1::timestamptz IS NULL OR (created_at, checkpoint_id) < ($1, $2))
+        ORDER BY created_at DESC, checkpoint_id DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(page.after.map(|(created_at, _)| created_at))
+    .bind(page.after.map(|(_, checkpoint_id)| checkpoint_id))
+    .bind(page.limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list receipt checkpoints")?;
+
+    Ok(checkpoints)
+}
+
+pub async fn get_receipt_checkpoint(
+    pool: &PgPool,
+    checkpoint_id: Uuid,
+) -> Result<Option<ReceiptCheckpoint>> {
+    let checkpoint = sqlx::query_as::<_, ReceiptCheckpoint>(
+        r#"
+        SELECT * FROM receipt_checkpoints
+        WHERE checkpoint_id = $1
+        "#,
+    )
+    .bind(checkpoint_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to query receipt checkpoint")?;
+
+    Ok(checkpoint)
+}
+
+/// The checkpoint leaf for a receipt, once a checkpoint covers it
+pub async fn get_checkpoint_leaf(
+    pool: &PgPool,
+    receipt_id: Uuid,
+) -> Result<Option<CheckpointLeaf>> {
+    let leaf = sqlx::query_as::<_, CheckpointLeaf>(
+        r#"
+        SELECT * FROM receipt_checkpoint_leaves
+        WHERE receipt_id = $1
+        "#,
+    )
+    .bind(receipt_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to query checkpoint leaf")?;
+
+    Ok(leaf)
+}
+
+/// Every leaf hash of a checkpoint, in tree order
+pub async fn get_checkpoint_leaf_hashes(pool: &PgPool, checkpoint_id: Uuid) -> Result<Vec<String>> {
+    let hashes = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT receipt_hash FROM receipt_checkpoint_leaves
+        WHERE checkpoint_id = $1
+        ORDER BY position ASC
+        "#,
+    )
+    .bind(checkpoint_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query checkpoint leaves")?;
+
+    Ok(hashes)
+}
+
+// ============================================================================
+// VETO SLO QUERIES
+// ============================================================================
+
+/// Count one veto against its org's day, and whether it met the target
+pub async fn record_veto_latency(
+    pool: &PgPool,
+    org_id: Uuid,
+    day: NaiveDate,
+    target_ms: i32,
+    latency_ms: i32,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO veto_slo_daily
+            (org_id, day, target_ms, veto_count, within_target_count, max_latency_ms)
+        VALUES ($1, $2, $3, 1, ($4 <= $3)::int, $4)
+        ON CONFLICT (org_id, day, target_ms) DO UPDATE SET
+            veto_count = veto_slo_daily.veto_count + 1,
+            within_target_count = veto_slo_daily.within_target_count
+                + EXCLUDED.within_target_count,
+            max_latency_ms = GREATEST(veto_slo_daily.max_latency_ms, EXCLUDED.max_latency_ms)
+        "#,
+    )
+    .bind(org_id)
+    .bind(day)
+    .bind(target_ms)
+    .bind(latency_ms)
+    .execute(pool)
+    .await
+    .context("Failed to record veto latency")?;
+
+    Ok(())
+}
+
+/// Daily tallies from `from` to `to` inclusive, for one org or all of them
+pub async fn get_veto_slo_days(
+    pool: &PgPool,
+    org_id: Option<Uuid>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<VetoSloDay>> {
+    let days = sqlx::query_as::<_, VetoSloDay>(
+        r#"
+        SELECT * FROM veto_slo_daily
+        WHERE ($1::uuid IS NULL OR org_id = $1)
+          AND day BETWEEN $2 AND $3
+        ORDER BY org_id, day, target_ms
+        "#,
+    )
+    .bind(org_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query veto SLO days")?;
+
+    Ok(days)
+}
+
+// ============================================================================
+// ORG STATS QUERIES
+// ============================================================================
+// Each is scoped by org_id first so it runs off an org-leading index:
+// idx_pointers_org_created, idx_pointers_org_orphaned and
+// idx_receipts_org_timestamp.
+
+/// The org's pointers created in `[from, to)` by current status
+pub async fn get_org_pointer_counts(
+    pool: &PgPool,
+    org_id: Uuid,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<OrgPointerCounts> {
+    let counts = sqlx::query_as::<_, OrgPointerCounts>(
+        r#"
+        SELECT
+            COUNT(*) AS total_pointers,
+            COUNT(*) FILTER (WHERE status = 'active') AS active_pointers,
+            COUNT(*) FILTER (WHERE status = 'pending_orphan') AS pending_orphan_pointers,
+            COUNT(*) FILTER (WHERE status = 'orphaned') AS orphaned_pointers
+        FROM pointers
+        WHERE org_id = $1
+          AND ($2::timestamptz IS NULL OR created_at >= $2)
+          AND ($3::timestamptz IS NULL OR created_at < $3)
+        "#,
+    )
+    .bind(org_id)
+    .bind(from)
+    .bind(to)
+    .fetch_one(pool)
+    .await
+    .context("Failed to count org pointers")?;
+
+    Ok(counts)
+}
+
+/// Pointers created per UTC day in `[from, to)`, every day listed
+pub async fn get_org_pointers_per_day(
+    pool: &PgPool,
+    org_id: Uuid,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<DailyCount>> {
+    let days = sqlx::query_as::<_, DailyCount>(
+        r#"
+        WITH created AS (
+            SELECT date_trunc('day', created_at AT TIME ZONE 'UTC')::date AS day,
+                   COUNT(*) AS count
+            FROM pointers
+            WHERE org_id = $1 AND created_at >= $2 AND created_at < $3
+            GROUP BY 1
+        )
+        SELECT d::date AS day, COALESCE(created.count, 0) AS count
+        FROM generate_series(
+            date_trunc('day', $2 AT TIME ZONE 'UTC'),
+            date_trunc('day', ($3 - interval '1 microsecond') AT TIME ZONE 'UTC'),
+            interval '1 day'
+        ) AS d
+        LEFT JOIN created ON created.day = d::date
+        ORDER BY d
+        "#,
+    )
+    .bind(org_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+    .context("Failed to count org pointers per day")?;
+
+    Ok(days)
+}
+
+/// The org's receipts issued in `[from, to)` per operation
+pub async fn get_org_receipts_by_operation(
+    pool: &PgPool,
+    org_id: Uuid,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Vec<(String, i64)>> {
+    let counts = sqlx::query_as::<_, (String, i64)>(
+        r#"
+        SELECT operation::text, COUNT(*)
+        FROM governance_receipts
+        WHERE org_id = $1
+          AND ($2::timestamptz IS NULL OR timestamp >= $2)
+          AND ($3::timestamptz IS NULL OR timestamp < $3)
+        GROUP BY operation
+        "#,
+    )
+    .bind(org_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+    .context("Failed to count org receipts by operation")?;
+
+    Ok(counts)
+}
+
+/// Median seconds from create to orphan of the org's pointers orphaned in
+/// `[from, to)`; None when there are none
+pub async fn get_org_median_seconds_to_orphan(
+    pool: &PgPool,
+    org_id: Uuid,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Option<f64>> {
+    let median = sqlx::query_scalar::<_, Option<f64>>(
+        r#"
+        SELECT percentile_cont(0.5) WITHIN GROUP (
+            ORDER BY EXTRACT(EPOCH FROM orphaned_at - created_at)::float8
+        )
+        FROM pointers
+        WHERE org_id = $1
+          AND orphaned_at IS NOT NULL
+          AND status = 'orphaned'
+          AND ($2::timestamptz IS NULL OR orphaned_at >= $2)
+          AND ($3::timestamptz IS NULL OR orphaned_at < $3)
+        "#,
+    )
+    .bind(org_id)
+    .bind(from)
+    .bind(to)
+    .fetch_one(pool)
+    .await
+    .context("Failed to query org median time to orphan")?;
+
+    Ok(median)
+}
+
+// ============================================================================
+// RETENTION QUERIES
+// ============================================================================
+
+/// Lock active pointers whose data passed its retention deadline at `now`,
+/// earliest deadline first, skipping pointers another transaction holds
+pub async fn lock_retention_expired_pointers<'e>(
+    executor: impl PgExecutor<'e>,
+    now: DateTime<Utc>,
+    org_id: Option<Uuid>,
+    limit: i64,
+) -> Result<Vec<RetentionExpiration>> {
+    let expired = sqlx::query_as::<_, RetentionExpiration>(
+        r#"
+        SELECT p.pointer_id, p.org_id, p.data_id, p.subject_id,
+               d.retention_class, d.retain_until
+        FROM pointers p
+        JOIN data_store d ON d.data_id = p.data_id
+        WHERE p.status IN ('active', 'pending_orphan') AND NOT p.chain_frozen
+          AND NOT p.legal_hold
+          AND d.retain_until <= $1
+          AND ($2::uuid IS NULL OR p.org_id = $2)
+        ORDER BY d.retain_until, p.pointer_id
+        LIMIT $3
+        FOR UPDATE OF p SKIP LOCKED
+        "#,
+    )
+    .bind(now)
+    .bind(org_id)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("Failed to lock retention-expired pointers")?;
+
+    Ok(expired)
+}
+
+/// Drop the payload of every unheld row past its retention deadline,
+/// returning `(data_id, org_id)` of each row purged
+pub async fn purge_retention_expired_payloads<'e>(
+    executor: impl PgExecutor<'e>,
+    now: DateTime<Utc>,
+    org_id: Option<Uuid>,
+) -> Result<Vec<(Uuid, Uuid)>> {
+    let purged = sqlx::query_as::<_, (Uuid, Uuid)>(
+        r#"
+        UPDATE data_store
+        SET encrypted_payload = NULL
+        WHERE retain_until <= $1
+          AND encrypted_payload IS NOT NULL
+          AND NOT legal_hold
+          AND ($2::uuid IS NULL OR org_id = $2)
+        RETURNING data_id, org_id
+        "#,
+    )
+    .bind(now)
+    .bind(org_id)
+    .fetch_all(executor)
+    .await
+    .context("Failed to purge retention-expired payloads")?;
+
+    Ok(purged)
+}
+
+/// Active pointers whose retention deadline falls before `until`, soonest first
+pub async fn get_upcoming_retention_expirations(
+    pool: &PgPool,
+    until: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<RetentionExpiration>> {
+    let upcoming = sqlx::query_as::<_, RetentionExpiration>(
+        r#"
+        SELECT p.pointer_id, p.org_id, p.data_id, p.subject_id,
+               d.retention_class, d.retain_until
+        FROM pointers p
+        JOIN data_store d ON d.data_id = p.data_id
+        WHERE p.status = 'active' AND d.retain_until < $1
+        ORDER BY d.retain_until, p.pointer_id
+        LIMIT $2
+        "#,
+    )
+    .bind(until)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query upcoming retention expirations")?;
+
+    Ok(upcoming)
+}
+
+// ============================================================================
+// CHAIN FREEZE QUERIES
+// ============================================================================
+
+/// Freeze a pointer's receipt chain and record why. Fails on the open-freeze
+/// index if it is already frozen.
+pub async fn open_chain_freeze<'e>(
+    executor: impl PgExecutor<'e>,
+    freeze_id: Uuid,
+    pointer_id: Uuid,
+    reason: &str,
+    now: DateTime<Utc>,
+) -> Result<PointerChainFreeze> {
+    let freeze = sqlx::query_as::<_, PointerChainFreeze>(
+        r#"
+        WITH frozen AS (
+            UPDATE pointers SET chain_frozen = TRUE
+            WHERE pointer_id = $2
+            RETURNING pointer_id, org_id
+        )
+        INSERT INTO pointer_chain_freezes (freeze_id, pointer_id, org_id, reason, frozen_at)
+        SELECT $1, pointer_id, org_id, $3, $4 FROM frozen
+        RETURNING *
+        "#,
+    )
+    .bind(freeze_id)
+    .bind(pointer_id)
+    .bind(reason)
+    .bind(now)
+    .fetch_one(executor)
+    .await
+    .context("Failed to freeze pointer chain")?;
+
+    Ok(freeze)
+}
+
+/// Clear the pointer's open freeze, if any, and let its chain grow again
+pub async fn clear_chain_freeze<'e>(
+    executor: impl PgExecutor<'e>,
+    pointer_id: Uuid,
+    outcome: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<PointerChainFreeze>> {
+    let freeze = sqlx::query_as::<_, PointerChainFreeze>(
+        r#"
+        WITH cleared AS (
+            UPDATE pointer_chain_freezes SET cleared_at = $3, outcome = $2
+            WHERE pointer_id = $1 AND cleared_at IS NULL
+            RETURNING *
+        ), thawed AS (
+            UPDATE pointers SET chain_frozen = FALSE
+            WHERE pointer_id IN (SELECT pointer_id FROM cleared)
+        )
+        SELECT * FROM cleared
+        "#,
+    )
+    .bind(pointer_id)
+    .bind(outcome)
+    .bind(now)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to clear pointer chain freeze")?;
+
+    Ok(freeze)
+}
+
+/// Lock an org's row so only one writer extends its org receipt chain at a
+/// time; returns the chain head, None if the chain is empty
+pub async fn lock_org_receipt_head<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+) -> Result<Option<String>> {
+    let head = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+        SELECT (
+            SELECT receipt_hash FROM org_receipts r
+            WHERE r.org_id = o.org_id
+            ORDER BY created_at DESC
+            LIMIT 1
+        )
+        FROM organizations o
+        WHERE o.org_id = $1
+        FOR UPDATE OF o
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to lock org receipt chain")?;
+
+    Ok(head.flatten())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_org_receipt<'e>(
+    executor: impl PgExecutor<'e>,
+    receipt_id: Uuid,
+    org_id: Uuid,
+    kind: &str,
+    pointer_id: Option<Uuid>,
+    receipt_json: serde_json::Value,
+    receipt_hash: &str,
+    signature: &[u8],
+    signature_algorithm: &str,
+    prev_hash: Option<&str>,
+) -> Result<OrgReceipt> {
+    let receipt = sqlx::query_as::<_, OrgReceipt>(
+        r#"
+        INSERT INTO org_receipts
+            (receipt_id, org_id, kind, pointer_id, receipt_json, receipt_hash, signature,
+             signature_algorithm, prev_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+    )
+    .bind(receipt_id)
+    .bind(org_id)
+    .bind(kind)
+    .bind(pointer_id)
+    .bind(receipt_json)
+    .bind(receipt_hash)
+    .bind(signature)
+    .bind(signature_algorithm)
+    .bind(prev_hash)
+    .fetch_one(executor)
+    .await
+    .context("Failed to insert org receipt")?;
+
+    Ok(receipt)
+}
+
+// ============================================================================
+// API KEY QUERIES
+// ============================================================================
+
+pub async fn create_api_key<'e>(
+    executor: impl PgExecutor<'e>,
+    key_id: Uuid,
+    key_hash: &str,
+    org_id: Uuid,
+    label: &str,
+) -> Result<ApiKey> {
+    let key = sqlx::query_as::<_, ApiKey>(
+        r#"
+        INSERT INTO api_keys (key_id, key_hash, org_id, label)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(key_id)
+    .bind(key_hash)
+    .bind(org_id)
+    .bind(label)
+    .fetch_one(executor)
+    .await
+    .context("Failed to create API key")?;
+
+    Ok(key)
+}
+
+/// Look a key up by hash, revoked or not
+pub async fn get_api_key_by_hash<'e>(
+    executor: impl PgExecutor<'e>,
+    key_hash: &str,
+) -> Result<Option<ApiKey>> {
+    let key = sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys WHERE key_hash = $1")
+        .bind(key_hash)
+        .fetch_optional(executor)
+        .await
+        .context("Failed to get API key")?;
+
+    Ok(key)
+}
+
+/// Lock a key so only one request rotates or revokes it at a time
+pub async fn lock_api_key<'e>(
+    executor: impl PgExecutor<'e>,
+    key_id: Uuid,
+) -> Result<Option<ApiKey>> {
+    let key = sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys WHERE key_id = $1 FOR UPDATE")
+        .bind(key_id)
+        .fetch_optional(executor)
+        .await
+        .context("Failed to lock API key")?;
+
+    Ok(key)
+}
+
+/// An org's keys, newest first
+pub async fn list_api_keys<'e>(executor: impl PgExecutor<'e>, org_id: Uuid) -> Result<Vec<ApiKey>> {
+    let keys = sqlx::query_as::<_, ApiKey>(
+        "SELECT * FROM api_keys WHERE org_id = $1 ORDER BY created_at DESC, key_id",
+    )
+    .bind(org_id)
+    .fetch_all(executor)
+    .await
+    .context("Failed to list API keys")?;
+
+    Ok(keys)
+}
+
+/// Record a key's successor and the end of its overlap. An earlier expiry
+/// already set is kept.
+pub async fn mark_api_key_rotated<'e>(
+    executor: impl PgExecutor<'e>,
+    key_id: Uuid,
+    replaced_by: Uuid,
+    expires_at: DateTime<Utc>,
+) -> Result<ApiKey> {
+    let key = sqlx::query_as::<_, ApiKey>(
+        r#"
+        UPDATE api_keys
+        SET replaced_by = $2, expires_at = LEAST(expires_at, $3)
+        WHERE key_id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(key_id)
+    .bind(replaced_by)
+    .bind(expires_at)
+    .fetch_one(executor)
+    .await
+    .context("Failed to mark API key rotated")?;
+
+    Ok(key)
+}
+
+/// Lock a batch of unrevoked keys whose rotation overlap ended by `now`,
+/// for one org or all of them
+pub async fn lock_expired_api_keys<'e>(
+    executor: impl PgExecutor<'e>,
+    now: DateTime<Utc>,
+    org_id: Option<Uuid>,
+    limit: i64,
+) -> Result<Vec<ApiKey>> {
+    let keys = sqlx::query_as::<_, ApiKey>(
+        r#"
+        SELECT * FROM api_keys
+        WHERE revoked_at IS NULL AND expires_at <= $1
+          AND ($2::uuid IS NULL OR org_id = $2)
+        ORDER BY expires_at
+        LIMIT $3
+        FOR UPDATE SKIP LOCKED
+        "#,
+    )
+    .bind(now)
+    .bind(org_id)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("Failed to lock expired API keys")?;
+
+    Ok(keys)
+}
+
+/// Revoke a key; None if it doesn't exist or was already revoked
+pub async fn revoke_api_key<'e>(
+    executor: impl PgExecutor<'e>,
+    key_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<Option<ApiKey>> {
+    let key = sqlx::query_as::<_, ApiKey>(
+        r#"
+        UPDATE api_keys SET revoked_at = $2
+        WHERE key_id = $1 AND revoked_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(key_id)
+    .bind(now)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to revoke API key")?;
+
+    Ok(key)
+}
+
+// ============================================================================
+// SIGNING KEY QUERIES
+// ============================================================================
+
+pub async fn get_signing_key<'e>(
+    executor: impl PgExecutor<'e>,
+    key_id: &str,
+) -> Result<Option<SigningKey>> {
+    let key = sqlx::query_as::<_, SigningKey>("SELECT * FROM signing_keys WHERE key_id = $1")
+        .bind(key_id)
+        .fetch_optional(executor)
+        .await
+        .context("Failed to query signing key")?;
+
+    Ok(key)
+}
+
+/// Every signing key, oldest first
+pub async fn list_signing_keys(pool: &PgPool) -> Result<Vec<SigningKey>> {
+    let keys =
+        sqlx::query_as::<_, SigningKey>("SELECT * FROM signing_keys ORDER BY created_at, key_id")
+            .fetch_all(pool)
+            .await
+            .context("Failed to list signing keys")?;
+
+    Ok(keys)
+}
+
+/// Lock the signing key currently in use, so rotations serialize
+pub async fn lock_active_signing_key<'e>(
+    executor: impl PgExecutor<'e>,
+) -> Result<Option<SigningKey>> {
+    let key = sqlx::query_as::<_, SigningKey>(
+        "SELECT * FROM signing_keys WHERE retired_at IS NULL FOR UPDATE",
+    )
+    .fetch_optional(executor)
+    .await
+    .context("Failed to lock active signing key")?;
+
+    Ok(key)
+}
+
+pub async fn retire_signing_key<'e>(
+    executor: impl PgExecutor<'e>,
+    key_id: &str,
+    retired_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query("UPDATE signing_keys SET retired_at = $2 WHERE key_id = $1 AND retired_at IS NULL")
+        .bind(key_id)
+        .bind(retired_at)
+        .execute(executor)
+        .await
+        .context("Failed to retire signing key")?;
+
+    Ok(())
+}
+
+pub async fn create_signing_key<'e>(
+    executor: impl PgExecutor<'e>,
+    key_id: &str,
+    public_key: &[u8],
+    created_at: DateTime<Utc>,
+) -> Result<SigningKey> {
+    let key = sqlx::query_as::<_, SigningKey>(
+        r#"
+        INSERT INTO signing_keys (key_id, public_key, created_at)
+        VALUES ($1, $2, $3)
+        RETURNING *
+        "#,
+    )
+    .bind(key_id)
+    .bind(public_key)
+    .bind(created_at)
+    .fetch_one(executor)
+    .await
+    .context("Failed to create signing key")?;
+
+    Ok(key)
+}
+
+pub async fn get_org_signing_key<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+) -> Result<Option<OrgSigningKey>> {
+    let key =
+        sqlx::query_as::<_, OrgSigningKey>("SELECT * FROM org_signing_keys WHERE org_id = $1")
+            .bind(org_id)
+            .fetch_optional(executor)
+            .await
+            .context("Failed to query org signing key")?;
+
+    Ok(key)
+}
+
+pub async fn get_org_signing_key_by_key_id<'e>(
+    executor: impl PgExecutor<'e>,
+    key_id: &str,
+) -> Result<Option<OrgSigningKey>> {
+    let key =
+        sqlx::query_as::<_, OrgSigningKey>("SELECT * FROM org_signing_keys WHERE key_id = $1")
+            .bind(key_id)
+            .fetch_optional(executor)
+            .await
+            .context("Failed to query org signing key")?;
+
+    Ok(key)
+}
+
+/// Store an org's first key. None when another instance stored one first.
+pub async fn create_org_signing_key<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+    key_id: &str,
+    public_key: &[u8],
+    sealed_private_key: &[u8],
+    created_at: DateTime<Utc>,
+) -> Result<Option<OrgSigningKey>> {
+    let key = sqlx::query_as::<_, OrgSigningKey>(
+        r#"
+        INSERT INTO org_signing_keys (org_id, key_id, public_key, sealed_private_key, created_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (org_id) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(org_id)
+    .bind(key_id)
+    .bind(public_key)
+    .bind(sealed_private_key)
+    .bind(created_at)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to create org signing key")?;
+
+    Ok(key)
+}
+
+// ============================================================================
+// WEBHOOK QUERIES
+// ============================================================================
+
+pub async fn create_webhook<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+    url: &str,
+    secret: &str,
+    events: &[String],
+    enabled: bool,
+) -> Result<Webhook> {
+    let webhook = sqlx::query_as::<_, Webhook>(
+        r#"
+        INSERT INTO webhooks (org_id, url, secret, events, enabled)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(org_id)
+    .bind(url)
+    .bind(secret)
+    .bind(events)
+    .bind(enabled)
+    .fetch_one(executor)
+    .await
+    .context("Failed to create webhook")?;
+
+    Ok(webhook)
+}
+
+pub async fn get_webhook(pool: &PgPool, webhook_id: Uuid) -> Result<Option<Webhook>> {
+    let webhook = sqlx::query_as::<_, Webhook>("SELECT * FROM webhooks WHERE webhook_id = $1")
+        .bind(webhook_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to query webhook")?;
+
+    Ok(webhook)
+}
+
+pub async fn list_webhooks(pool: &PgPool, org_id: Uuid) -> Result<Vec<Webhook>> {
+    let webhooks = sqlx::query_as::<_, Webhook>(
+        r#"
+        SELECT * FROM webhooks
+        WHERE org_id = $1
+        ORDER BY created_at, webhook_id
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list webhooks")?;
+
+    Ok(webhooks)
+}
+
+/// Change whichever fields are given; None if the webhook doesn't exist
+pub async fn update_webhook<'e>(
+    executor: impl PgExecutor<'e>,
+    webhook_id: Uuid,
+    url: Option<&str>,
+    events: Option<&[String]>,
+    enabled: Option<bool>,
+) -> Result<Option<Webhook>> {
+    let webhook = sqlx::query_as::<_, Webhook>(
+        r#"
+        UPDATE webhooks
+        SET url = COALESCE($2, url),
+            events = COALESCE($3, events),
+            enabled = COALESCE($4, enabled),
+            updated_at = NOW()
+        WHERE webhook_id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(webhook_id)
+    .bind(url)
+    .bind(events)
+    .bind(enabled)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to update webhook")?;
+
+    Ok(webhook)
+}
+
+/// Delete a webhook with its delivery log; false if it didn't exist
+pub async fn delete_webhook<'e>(executor: impl PgExecutor<'e>, webhook_id: Uuid) -> Result<bool> {
+    let deleted = sqlx::query("DELETE FROM webhooks WHERE webhook_id = $1")
+        .bind(webhook_id)
+        .execute(executor)
+        .await
+        .context("Failed to delete webhook")?;
+
+    Ok(deleted.rows_affected() > 0)
+}
+
+/// Queue an event for every enabled webhook of the org subscribed to it,
+/// inside the caller's transaction. Returns how many deliveries were queued.
+pub async fn enqueue_webhook_deliveries<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+    pointer_id: Option<Uuid>,
+    event_type: &str,
+    payload: &serde_json::Value,
+    now: DateTime<Utc>,
+) -> Result<u64> {
+    let queued = sqlx::query(
+        r#"
+        INSERT INTO webhook_deliveries
+            (webhook_id, org_id, pointer_id, event_type, payload, next_attempt_at, created_at)
+        SELECT webhook_id, org_id, $2, $3, $4, $5, $5
+        FROM webhooks
+        WHERE org_id = $1 AND enabled AND $3 = ANY(events)
+        "#,
+    )
+    .bind(org_id)
+    .bind(pointer_id)
+    .bind(event_type)
+    .bind(payload)
+    .bind(now)
+    .execute(executor)
+    .await
+    .context("Failed to enqueue webhook deliveries")?;
+
+    Ok(queued.rows_affected())
+}
+
+/// Claim pending deliveries due at `now` for enabled webhooks, pushing their
+/// next attempt to `lease_until` so no other dispatcher sends them meanwhile
+pub async fn claim_due_webhook_deliveries<'e>(
+    executor: impl PgExecutor<'e>,
+    now: DateTime<Utc>,
+    lease_until: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<DueWebhookDelivery>> {
+    let due = sqlx::query_as::<_, DueWebhookDelivery>(
+        r#"
+        UPDATE webhook_deliveries d
+        SET next_attempt_at = $2
+        FROM webhooks w
+        WHERE w.webhook_id = d.webhook_id
+          AND d.delivery_id IN (
+              SELECT pending.delivery_id
+              FROM webhook_deliveries pending
+              JOIN webhooks hook ON hook.webhook_id = pending.webhook_id
+              WHERE pending.status = 'pending' AND pending.next_attempt_at <= $1
+                AND hook.enabled
+              ORDER BY pending.next_attempt_at
+              LIMIT $3
+              FOR UPDATE OF pending SKIP LOCKED
+          )
+        RETURNING d.delivery_id, d.webhook_id, w.url, w.secret, d.event_type, d.payload,
+                  d.attempts
+        "#,
+    )
+    .bind(now)
+    .bind(lease_until)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("Failed to claim webhook deliveries")?;
+
+    Ok(due)
+}
+
+pub async fn record_webhook_attempt<'e>(
+    executor: impl PgExecutor<'e>,
+    delivery_id: Uuid,
+    attempted_at: DateTime<Utc>,
+    status_code: Option<i32>,
+    error: Option<&str>,
+    duration_ms: i64,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO webhook_delivery_attempts
+            (delivery_id, attempted_at, status_code, error, duration_ms)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(delivery_id)
+    .bind(attempted_at)
+    .bind(status_code)
+    .bind(error)
+    .bind(duration_ms)
+    .execute(executor)
+    .await
+    .context("Failed to record webhook attempt")?;
+
+    Ok(())
+}
+
+/// Settle a delivery after an attempt: delivered, retried at `next_attempt_at`,
+/// or failed for good
+pub async fn settle_webhook_delivery<'e>(
+    executor: impl PgExecutor<'e>,
+    delivery_id: Uuid,
+    status: &str,
+    error: Option<&str>,
+    next_attempt_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = $2,
+            attempts = attempts + 1,
+            last_error = $3,
+            next_attempt_at = $4,
+            delivered_at = CASE WHEN $2 = 'delivered' THEN $5 END
+        WHERE delivery_id = $1
+        "#,
+    )
+    .bind(delivery_id)
+    .bind(status)
+    .bind(error)
+    .bind(next_attempt_at)
+    .bind(now)
+    .execute(executor)
+    .await
+    .context("Failed to settle webhook delivery")?;
+
+    Ok(())
+}
+
+/// Most recent deliveries of a webhook, newest first
+pub async fn list_webhook_deliveries(
+    pool: &PgPool,
+    webhook_id: Uuid,
+    limit: i64,
+) -> Result<Vec<WebhookDelivery>> {
+    let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        SELECT * FROM webhook_deliveries
+        WHERE webhook_id = $1
+        ORDER BY created_at DESC, delivery_id DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(webhook_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list webhook deliveries")?;
+
+    Ok(deliveries)
+}
+
+/// Attempts of the given deliveries, oldest first
+pub async fn get_webhook_delivery_attempts(
+    pool: &PgPool,
+    delivery_ids: &[Uuid],
+) -> Result<Vec<WebhookDeliveryAttempt>> {
+    let attempts = sqlx::query_as::<_, WebhookDeliveryAttempt>(
+        r#"
+        SELECT * FROM webhook_delivery_attempts
+        WHERE delivery_id = ANY($1)
+        ORDER BY attempted_at, attempt_id
+        "#,
+    )
+    .bind(delivery_ids)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query webhook delivery attempts")?;
+
+    Ok(attempts)
 }
@@ -0,0 +1,214 @@
+// Single-flight coalescing of identical reads
+// Concurrent callers asking for the same key share one in-flight fetch
+// instead of each issuing the same queries
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::OnceCell;
+use utoipa::ToSchema;
+
+struct Flight<V> {
+    started: Instant,
+    invalidated: AtomicBool,
+    result: OnceCell<V>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+pub struct SingleFlightStats {
+    /// Fetches actually issued
+    pub fetches: u64,
+    /// Callers served from another caller's fetch
+    pub coalesced: u64,
+    /// Flights dropped because the key was written while they ran
+    pub invalidated: u64,
+}
+
+pub struct SingleFlight<K, V> {
+    max_age: Duration,
+    flights: Mutex<HashMap<K, Arc<Flight<V>>>>,
+    fetches: AtomicU64,
+    coalesced: AtomicU64,
+    invalidated: AtomicU64,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> SingleFlight<K, V> {
+    /// Callers only join a flight started less than `max_age` ago; a zero
+    /// `max_age` disables coalescing
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            flights: Mutex::new(HashMap::new()),
+            fetches: AtomicU64::new(0),
+            coalesced: AtomicU64::new(0),
+            invalidated: AtomicU64::new(0),
+        }
+    }
+
+    /// Run `fetch` for `key`, or wait for a concurrent caller's fetch of the
+    /// same key. A failed fetch is not shared; the next waiter fetches itself.
+    pub async fn run<F, Fut, E>(&self, key: K, fetch: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        if self.max_age.is_zero() {
+            self.fetches.fetch_add(1, Ordering::Relaxed);
+            return fetch().await;
+        }
+
+        let flight = {
+            let mut flights = self.flights.lock().unwrap();
+            match flights.get(&key) {
+                Some(flight) if flight.started.elapsed() < self.max_age => flight.clone(),
+                _ => {
+                    let flight = Arc::new(Flight {
+                        started: Instant::now(),
+                        invalidated: AtomicBool::new(false),
+                        result: OnceCell::new(),
+                    });
+                    flights.insert(key.clone(), flight.clone());
+                    flight
+                }
+            }
+        };
+
+        // Left in place when another caller's fetch is used
+        let mut fetch = Some(fetch);
+        let result = flight
+            .result
+            .get_or_try_init(|| {
+                self.fetches.fetch_add(1, Ordering::Relaxed);
+                fetch.take().expect("fetch runs at most once")()
+            })
+            .await
+            .cloned();
+
+        // Only in-flight reads are shared; later callers start a new flight
+        {
+            let mut flights = self.flights.lock().unwrap();
+            if flights
+                .get(&key)
+                .is_some_and(|current| Arc::ptr_eq(current, &flight))
+            {
+                flights.remove(&key);
+            }
+        }
+
+        // A follower must not be served a read that a write has since
+        // superseded; the leader's own read is no staler than an uncoalesced one
+        if let Some(fetch) = fetch {
+            if flight.invalidated.load(Ordering::Acquire) {
+                self.fetches.fetch_add(1, Ordering::Relaxed);
+                return fetch().await;
+            }
+            self.coalesced.fetch_add(1, Ordering::Relaxed);
+        }
+
+        result
+    }
+
+    /// Drop any in-flight read of `key` after a write to it commits, so
+    /// callers arriving from now on fetch fresh state
+    pub fn invalidate(&self, key: &K) {
+        if let Some(flight) = self.flights.lock().unwrap().remove(key) {
+            flight.invalidated.store(true, Ordering::Release);
+            self.invalidated.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn stats(&self) -> SingleFlightStats {
+        SingleFlightStats {
+            fetches: self.fetches.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            invalidated: self.invalidated.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Notify;
+
+    #[tokio::test]
+    async fn test_concurrent_callers_share_one_fetch() {
+        let flights = Arc::new(SingleFlight::<u32, u32>::new(Duration::from_secs(1)));
+        let release = Arc::new(Notify::new());
+
+        let mut callers = tokio::task::JoinSet::new();
+        for _ in 0..10 {
+            let (flights, release) = (flights.clone(), release.clone());
+            callers.spawn(async move {
+                flights
+                    .run(7, || async move {
+                        release.notified().await;
+                        Ok::<_, ()>(42)
+                    })
+                    .await
+            });
+        }
+
+        // Let every caller join before the fetch completes
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        release.notify_one();
+
+        while let Some(result) = callers.join_next().await {
+            assert_eq!(result.unwrap(), Ok(42));
+        }
+        let stats = flights.stats();
+        assert_eq!(stats.fetches, 1);
+        assert_eq!(stats.coalesced, 9);
+    }
+
+    #[tokio::test]
+    async fn test_invalidated_flight_is_not_shared() {
+        let flights = Arc::new(SingleFlight::<u32, u32>::new(Duration::from_secs(1)));
+        let release = Arc::new(Notify::new());
+
+        let leader = {
+            let (flights, release) = (flights.clone(), release.clone());
+            tokio::spawn(async move {
+                flights
+                    .run(7, || async move {
+                        release.notified().await;
+                        Ok::<_, ()>(1)
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let follower = {
+            let flights = flights.clone();
+            tokio::spawn(async move { flights.run(7, || async { Ok::<_, ()>(2) }).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // A write lands while both wait on the stale read
+        flights.invalidate(&7);
+        release.notify_one();
+
+        assert_eq!(leader.await.unwrap(), Ok(1));
+        assert_eq!(follower.await.unwrap(), Ok(2));
+        assert_eq!(flights.stats().coalesced, 0);
+        assert_eq!(flights.stats().invalidated, 1);
+    }
+
+    #[tokio::test]
+    async fn test_zero_max_age_disables_coalescing() {
+        let flights = SingleFlight::<u32, u32>::new(Duration::ZERO);
+
+        for _ in 0..3 {
+            assert_eq!(flights.run(7, || async { Ok::<_, ()>(1) }).await, Ok(1));
+        }
+        assert_eq!(flights.stats().fetches, 3);
+    }
+}
@@ -0,0 +1,179 @@
+// Administrative chain freezes
+// A pointer whose receipt chain the auditor flagged can be frozen pending
+// review: the database then refuses any receipt on it, whatever the
+// operation, while its status and existing receipts stay as they are. The
+// freeze and its clearing are receipted on the org chain, since the pointer
+// chain itself cannot take them; clearing also appends one ChainFreezeReview
+// receipt to the pointer chain, linked to the head it was frozen at.
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use super::append_org_receipt;
+use crate::{
+    crypto::{Ed25519Keypair, ReceiptData, SignedReceipt},
+    db::{
+        models::{Pointer, PointerChainFreeze, ReceiptOperation},
+        queries,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct FrozenChain {
+    pub freeze: PointerChainFreeze,
+    /// Org chain receipt of the freeze
+    pub org_receipt: SignedReceipt,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClearedChainFreeze {
+    pub freeze: PointerChainFreeze,
+    /// Org chain receipt of the clearing
+    pub org_receipt: SignedReceipt,
+    /// ChainFreezeReview receipt resuming the pointer chain
+    pub review_receipt: SignedReceipt,
+}
+
+/// Freeze a locked pointer's chain inside the caller's transaction, with an
+/// org receipt naming the chain head under investigation
+pub async fn freeze_chain_with_receipt(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    pointer: &Pointer,
+    reason: &str,
+    now: DateTime<Utc>,
+) -> Result<FrozenChain> {
+    let freeze =
+        queries::open_chain_freeze(&mut *conn, Uuid::new_v4(), pointer.pointer_id, reason, now)
+            .await?;
+    let chain_head = queries::get_latest_receipt_hash(&mut *conn, pointer.pointer_id).await?;
+
+    let org_receipt = append_org_receipt(
+        conn,
+        keypair,
+        pointer.org_id,
+        Some(pointer.pointer_id),
+        "chain_freeze",
+        json!({
+            "freeze_id": freeze.freeze_id,
+            "reason": reason,
+            "frozen_at": freeze.frozen_at,
+            "chain_head": chain_head,
+        }),
+        now,
+    )
+    .await?;
+
+    queries::create_audit_log(
+        &mut *conn,
+        Some(pointer.org_id),
+        Some(pointer.pointer_id),
+        None,
+        "pointer_chain_frozen",
+        json!({
+            "freeze_id": freeze.freeze_id,
+            "reason": reason,
+            "org_receipt_hash": org_receipt.receipt_hash,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(FrozenChain {
+        freeze,
+        org_receipt,
+    })
+}
+
+/// Clear a locked pointer's open freeze inside the caller's transaction:
+/// an org receipt records the outcome, then a ChainFreezeReview receipt
+/// summarizing the investigation resumes the pointer chain. Returns None if
+/// the chain was not frozen.
+pub async fn clear_chain_freeze_with_receipt(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    pointer: &Pointer,
+    outcome: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<ClearedChainFreeze>> {
+    let Some(freeze) =
+        queries::clear_chain_freeze(&mut *conn, pointer.pointer_id, outcome, now).await?
+    else {
+        return Ok(None);
+    };
+
+    let org_receipt = append_org_receipt(
+        conn,
+        keypair,
+        pointer.org_id,
+        Some(pointer.pointer_id),
+        "chain_unfreeze",
+        json!({
+            "freeze_id": freeze.freeze_id,
+            "outcome": outcome,
+            "cleared_at": freeze.cleared_at,
+        }),
+        now,
+    )
+    .await?;
+
+    let prev_hash = queries::get_latest_receipt_hash(&mut *conn, pointer.pointer_id).await?;
+    let receipt_data = ReceiptData::new(
+        pointer.pointer_id,
+        ReceiptOperation::ChainFreezeReview,
+        pointer.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "freeze_id": freeze.freeze_id,
+            "reason": freeze.reason,
+            "frozen_at": freeze.frozen_at,
+            "cleared_at": freeze.cleared_at,
+            "outcome": outcome,
+            "org_receipt_hash": org_receipt.receipt_hash,
+        }),
+        now,
+    );
+    let review_receipt = receipt_data.sign(keypair)?;
+
+    queries::create_governance_receipt(
+        &mut *conn,
+        pointer.pointer_id,
+        pointer.org_id,
+        ReceiptOperation::ChainFreezeReview,
+        review_receipt.receipt_json.clone(),
+        &review_receipt.receipt_hash,
+        &review_receipt.signature,
+        &review_receipt.signature_algorithm,
+        &review_receipt.key_id,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    queries::create_audit_log(
+        &mut *conn,
+        Some(pointer.org_id),
+        Some(pointer.pointer_id),
+        None,
+        "pointer_chain_unfrozen",
+        json!({
+            "freeze_id": freeze.freeze_id,
+            "outcome": outcome,
+            "org_receipt_hash": org_receipt.receipt_hash,
+            "review_receipt_hash": review_receipt.receipt_hash,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(Some(ClearedChainFreeze {
+        freeze,
+        org_receipt,
+        review_receipt,
+    }))
+}
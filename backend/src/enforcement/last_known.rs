@@ -0,0 +1,256 @@
+// Last-known pointer status for degraded reads
+// Every resolve that reaches the database records what it saw. While the
+// database is unreachable, resolve answers from these entries if they are
+// fresh enough: status only, never payloads. The resolve receipts those
+// answers are owed are written once the database is back.
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::PgPool;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    clock::Clock,
+    crypto::{Ed25519Keypair, ReceiptData, SigningKeyring},
+    db::{
+        circuit::CircuitBreaker,
+        models::{Pointer, ReceiptOperation},
+        queries,
+    },
+};
+
+/// Entries kept before the cache is cleared and starts over
+const MAX_KNOWN_POINTERS: usize = 100_000;
+
+/// How often the backfill worker looks for owed receipts
+const BACKFILL_CHECK_SECONDS: u64 = 5;
+
+#[derive(Debug, Clone)]
+pub struct KnownPointer {
+    pub pointer: Pointer,
+    /// Content hash as of the last granted resolve; None for entries
+    /// recorded from a denial
+    pub content_hash: Option<String>,
+    pub seen_at: DateTime<Utc>,
+}
+
+/// A resolve answered from the cache whose receipt is still owed
+#[derive(Debug, Clone)]
+pub struct DegradedResolve {
+    pub pointer_id: Uuid,
+    pub data_id: Uuid,
+    pub served_at: DateTime<Utc>,
+    pub last_known_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DegradedReadStats {
+    /// Resolves answered from last-known status
+    pub served: u64,
+    /// Receipts written for them after recovery
+    pub backfilled: u64,
+    /// Receipts still owed
+    pub pending: usize,
+}
+
+pub struct LastKnownStatuses {
+    max_staleness: Duration,
+    entries: Mutex<HashMap<Uuid, KnownPointer>>,
+    pending: Mutex<Vec<DegradedResolve>>,
+    served: AtomicU64,
+    backfilled: AtomicU64,
+}
+
+impl LastKnownStatuses {
+    /// Entries older than `max_staleness` are never served; zero disables
+    /// degraded reads
+    pub fn new(max_staleness: Duration) -> Self {
+        Self {
+            max_staleness,
+            entries: Mutex::new(HashMap::new()),
+            pending: Mutex::new(Vec::new()),
+            served: AtomicU64::new(0),
+            backfilled: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, pointer: &Pointer, content_hash: Option<&str>, seen_at: DateTime<Utc>) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_KNOWN_POINTERS {
+            entries.clear();
+        }
+        entries.insert(
+            pointer.pointer_id,
+            KnownPointer {
+                pointer: pointer.clone(),
+                content_hash: content_hash.map(str::to_string),
+                seen_at,
+            },
+        );
+    }
+
+    /// Drop what is known about a pointer after a write to it
+    pub fn forget(&self, pointer_id: &Uuid) {
+        self.entries.lock().unwrap().remove(pointer_id);
+    }
+
+    /// The entry for a pointer if it was seen within the staleness bound
+    pub fn fresh(&self, pointer_id: &Uuid, now: DateTime<Utc>) -> Option<KnownPointer> {
+        if self.max_staleness <= Duration::zero() {
+            return None;
+        }
+
+        self.entries
+            .lock()
+            .unwrap()
+            .get(pointer_id)
+            .filter(|known| now - known.seen_at <= self.max_staleness)
+            .cloned()
+    }
+
+    /// Count a degraded serve and owe it a receipt
+    pub fn served(&self, resolve: DegradedResolve) {
+        self.served.fetch_add(1, Ordering::Relaxed);
+        self.pending.lock().unwrap().push(resolve);
+    }
+
+    pub fn stats(&self) -> DegradedReadStats {
+        DegradedReadStats {
+            served: self.served.load(Ordering::Relaxed),
+            backfilled: self.backfilled.load(Ordering::Relaxed),
+            pending: self.pending.lock().unwrap().len(),
+        }
+    }
+}
+
+/// Write the receipts owed for degraded serves, oldest first. Each lands at
+/// the head of its pointer's chain as a `resolve` marked `degraded`, with the
+/// time it was actually served. On failure the unwritten ones stay owed, as
+/// do those for pointers whose chain is frozen until the freeze is cleared.
+pub async fn backfill_degraded_resolves(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    last_known: &LastKnownStatuses,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let pending = std::mem::take(&mut *last_known.pending.lock().unwrap());
+    let mut deferred = Vec::new();
+    let mut written = 0;
+
+    for (i, resolve) in pending.iter().enumerate() {
+        match write_backfill_receipt(pool, keypair, resolve, now).await {
+            Ok(true) => {
+                written += 1;
+                last_known.backfilled.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(false) => deferred.push(resolve.clone()),
+            Err(e) => {
+                deferred.extend(pending[i..].iter().cloned());
+                last_known.pending.lock().unwrap().splice(0..0, deferred);
+                return Err(e);
+            }
+        }
+    }
+
+    last_known.pending.lock().unwrap().splice(0..0, deferred);
+    Ok(written)
+}
+
+async fn write_backfill_receipt(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    resolve: &DegradedResolve,
+    now: DateTime<Utc>,
+) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    let Some(pointer) = queries::lock_pointers_for_update(&mut *tx, &[resolve.pointer_id])
+        .await?
+        .pop()
+    else {
+        warn!(
+            "Pointer {} vanished before its degraded resolve was receipted",
+            resolve.pointer_id
+        );
+        return Ok(true);
+    };
+
+    if pointer.chain_frozen {
+        warn!(
+            "Deferring degraded resolve receipt for pointer {}: its chain is frozen",
+            pointer.pointer_id
+        );
+        return Ok(false);
+    }
+
+    let prev_hash = queries::get_latest_receipt_hash(&mut *tx, pointer.pointer_id).await?;
+    let receipt_data = ReceiptData::new(
+        pointer.pointer_id,
+        ReceiptOperation::Resolve,
+        pointer.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "data_id": resolve.data_id,
+            "payload_released": false,
+            "degraded": true,
+            "served_at": resolve.served_at,
+            "last_known_at": resolve.last_known_at,
+        }),
+        now,
+    );
+    let signed_receipt = receipt_data.sign(keypair)?;
+
+    queries::create_governance_receipt(
+        &mut *tx,
+        pointer.pointer_id,
+        pointer.org_id,
+        ReceiptOperation::Resolve,
+        signed_receipt.receipt_json,
+        &signed_receipt.receipt_hash,
+        &signed_receipt.signature,
+        &signed_receipt.signature_algorithm,
+        &signed_receipt.key_id,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Background loop: once the circuit closes, write the receipts owed
+pub async fn run_degraded_backfill(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    last_known: Arc<LastKnownStatuses>,
+    circuit: Arc<CircuitBreaker>,
+    clock: Arc<dyn Clock>,
+) {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(BACKFILL_CHECK_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        if circuit.is_open() || last_known.stats().pending == 0 {
+            continue;
+        }
+
+        match backfill_degraded_resolves(&pool, &signing_keys.active(), &last_known, clock.now())
+            .await
+        {
+            Ok(written) => info!("Backfilled {} degraded resolve receipts", written),
+            Err(e) => error!("Degraded resolve backfill failed: {:#}", e),
+        }
+    }
+}
@@ -1,4 +1,10 @@
 // Enforcement module
+pub mod chain_freeze;
+pub mod last_known;
+pub mod org_chain;
 pub mod pointer_guard;
 
+pub use chain_freeze::*;
+pub use last_known::*;
+pub use org_chain::*;
 pub use pointer_guard::*;
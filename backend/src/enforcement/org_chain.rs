@@ -0,0 +1,53 @@
+// Org receipt chain
+// Administrative actions that no pointer chain can carry are receipted on
+// their organization's own chain: chain freezes, and API key issuance and
+// revocation. Writers lock the org row, so the chain never forks.
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    crypto::{Ed25519Keypair, OrgReceiptData, SignedReceipt},
+    db::queries,
+};
+
+/// Extend an org's chain with a signed receipt inside the caller's
+/// transaction, optionally about one of its pointers
+pub async fn append_org_receipt(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    org_id: Uuid,
+    pointer_id: Option<Uuid>,
+    kind: &str,
+    details: serde_json::Value,
+    now: DateTime<Utc>,
+) -> Result<SignedReceipt> {
+    let prev_hash = queries::lock_org_receipt_head(&mut *conn, org_id).await?;
+    let receipt = OrgReceiptData {
+        receipt_id: Uuid::new_v4(),
+        org_id,
+        kind: kind.to_string(),
+        pointer_id,
+        details,
+        prev_hash: prev_hash.clone(),
+        timestamp: now,
+    };
+    let signed = receipt.sign(keypair)?;
+
+    queries::create_org_receipt(
+        &mut *conn,
+        receipt.receipt_id,
+        receipt.org_id,
+        kind,
+        receipt.pointer_id,
+        signed.receipt_json.clone(),
+        &signed.receipt_hash,
+        &signed.signature,
+        &signed.signature_algorithm,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    Ok(signed)
+}
@@ -1,21 +1,89 @@
 // Pointer orphaning enforcement layer
 // Per US 19/240,581 Claim 9: Orphaned pointers cannot be resolved
 
-use crate::db::models::{Pointer, PointerStatus};
-use anyhow::{anyhow, Result};
+use crate::{
+    clock::Clock,
+    db::models::{Pointer, PointerRestriction, PointerStatus},
+};
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Outcome of an access check that did not hard-deny
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PointerAccess {
+    Granted,
+    /// Orphaned, but still inside the org's grace window: the caller may
+    /// disclose the revocation but must not release data
+    OrphanedGrace {
+        hard_denial_at: DateTime<Utc>,
+    },
+}
+
+/// Why an access check hard-denied
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EnforcementError {
+    #[error("pointer_orphaned: This pointer has been orphaned and cannot be resolved")]
+    PointerOrphaned {
+        pointer_id: Uuid,
+        orphaned_at: Option<DateTime<Utc>>,
+        reason: Option<String>,
+    },
+}
+
+impl EnforcementError {
+    pub fn orphaned(pointer: &Pointer) -> Self {
+        EnforcementError::PointerOrphaned {
+            pointer_id: pointer.pointer_id,
+            orphaned_at: pointer.orphaned_at.or(pointer.orphan_effective_at),
+            reason: pointer.orphan_reason.clone(),
+        }
+    }
+}
 
 /// Enforces pointer access rules
-/// Returns Err if pointer is orphaned
-pub fn enforce_pointer_access(pointer: &Pointer) -> Result<()> {
-    match pointer.status {
-        PointerStatus::Active => Ok(()),
-        PointerStatus::Orphaned => Err(anyhow!(
-            "pointer_orphaned: This pointer has been orphaned and cannot be resolved"
-        )),
+/// Returns Err if pointer is orphaned and outside the grace window. A pending
+/// orphan is granted until its effective time and orphaned from then on,
+/// whether or not the sweeper has finalized it yet, unless a legal hold is
+/// deferring it.
+pub fn enforce_pointer_access(
+    pointer: &Pointer,
+    grace: Duration,
+    clock: &dyn Clock,
+) -> Result<PointerAccess, EnforcementError> {
+    let orphaned_at = match pointer.status {
+        PointerStatus::Active => return Ok(PointerAccess::Granted),
+        PointerStatus::PendingOrphan if pointer.legal_hold => return Ok(PointerAccess::Granted),
+        PointerStatus::PendingOrphan => match pointer.orphan_effective_at {
+            Some(effective_at) if clock.now() < effective_at => return Ok(PointerAccess::Granted),
+            effective_at => effective_at,
+        },
+        PointerStatus::Orphaned => pointer.orphaned_at,
+    };
+
+    match orphaned_at {
+        Some(orphaned_at) if clock.now() < orphaned_at + grace => {
+            Ok(PointerAccess::OrphanedGrace {
+                hard_denial_at: orphaned_at + grace,
+            })
+        }
+        _ => Err(EnforcementError::orphaned(pointer)),
     }
 }
 
+/// End of the restriction window if the restriction is still in force.
+/// An expired restriction no longer denies even before the sweeper lifts it.
+pub fn restricted_until(
+    restriction: Option<&PointerRestriction>,
+    clock: &dyn Clock,
+) -> Option<DateTime<Utc>> {
+    restriction
+        .filter(|r| r.lifted_at.is_none() && clock.now() < r.expires_at)
+        .map(|r| r.expires_at)
+}
+
 /// Check if pointer can be accessed
+#[allow(dead_code)]
 pub fn is_pointer_accessible(pointer: &Pointer) -> bool {
     matches!(pointer.status, PointerStatus::Active)
 }
@@ -23,8 +91,7 @@ pub fn is_pointer_accessible(pointer: &Pointer) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
-    use uuid::Uuid;
+    use crate::clock::{FixedClock, SystemClock};
 
     fn create_test_pointer(status: PointerStatus) -> Pointer {
         Pointer {
@@ -36,21 +103,137 @@ mod tests {
             created_at: Utc::now(),
             orphaned_at: None,
             orphan_reason: None,
+            orphan_effective_at: None,
             metadata: serde_json::json!({}),
+            chain_frozen: false,
+            legal_hold: false,
+            legal_hold_reason: None,
+            legal_hold_set_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
         }
     }
 
     #[test]
     fn test_active_pointer_accessible() {
         let pointer = create_test_pointer(PointerStatus::Active);
-        assert!(enforce_pointer_access(&pointer).is_ok());
+        assert_eq!(
+            enforce_pointer_access(&pointer, Duration::zero(), &SystemClock).unwrap(),
+            PointerAccess::Granted
+        );
         assert!(is_pointer_accessible(&pointer));
     }
 
     #[test]
     fn test_orphaned_pointer_blocked() {
-        let pointer = create_test_pointer(PointerStatus::Orphaned);
-        assert!(enforce_pointer_access(&pointer).is_err());
+        let mut pointer = create_test_pointer(PointerStatus::Orphaned);
+        pointer.orphaned_at = Some(Utc::now());
+        pointer.orphan_reason = Some("user_request".to_string());
+        assert_eq!(
+            enforce_pointer_access(&pointer, Duration::zero(), &SystemClock),
+            Err(EnforcementError::PointerOrphaned {
+                pointer_id: pointer.pointer_id,
+                orphaned_at: pointer.orphaned_at,
+                reason: Some("user_request".to_string()),
+            })
+        );
         assert!(!is_pointer_accessible(&pointer));
     }
+
+    #[test]
+    fn test_orphan_grace_window() {
+        let orphaned_at = Utc::now();
+        let grace = Duration::seconds(60);
+        let mut pointer = create_test_pointer(PointerStatus::Orphaned);
+        pointer.orphaned_at = Some(orphaned_at);
+
+        let inside = FixedClock(orphaned_at + Duration::seconds(59));
+        assert_eq!(
+            enforce_pointer_access(&pointer, grace, &inside).unwrap(),
+            PointerAccess::OrphanedGrace {
+                hard_denial_at: orphaned_at + grace
+            }
+        );
+
+        // Hard denial begins exactly at the end of the window
+        let boundary = FixedClock(orphaned_at + grace);
+        assert!(enforce_pointer_access(&pointer, grace, &boundary).is_err());
+
+        let after = FixedClock(orphaned_at + Duration::seconds(61));
+        assert!(enforce_pointer_access(&pointer, grace, &after).is_err());
+    }
+
+    #[test]
+    fn test_pending_orphan_resolves_until_effective() {
+        let effective_at = Utc::now();
+        let grace = Duration::seconds(60);
+        let mut pointer = create_test_pointer(PointerStatus::PendingOrphan);
+        pointer.orphan_effective_at = Some(effective_at);
+
+        let before = FixedClock(effective_at - Duration::seconds(1));
+        assert_eq!(
+            enforce_pointer_access(&pointer, grace, &before).unwrap(),
+            PointerAccess::Granted
+        );
+
+        // Due but not yet finalized: orphaned as of the effective time
+        let inside = FixedClock(effective_at + Duration::seconds(30));
+        assert_eq!(
+            enforce_pointer_access(&pointer, grace, &inside).unwrap(),
+            PointerAccess::OrphanedGrace {
+                hard_denial_at: effective_at + grace
+            }
+        );
+        let after = FixedClock(effective_at + grace);
+        assert_eq!(
+            enforce_pointer_access(&pointer, grace, &after),
+            Err(EnforcementError::PointerOrphaned {
+                pointer_id: pointer.pointer_id,
+                orphaned_at: Some(effective_at),
+                reason: None,
+            })
+        );
+
+        // A legal hold defers it
+        pointer.legal_hold = true;
+        pointer.legal_hold_set_at = Some(effective_at);
+        assert_eq!(
+            enforce_pointer_access(&pointer, grace, &after).unwrap(),
+            PointerAccess::Granted
+        );
+    }
+
+    #[test]
+    fn test_restriction_window() {
+        let restricted_at = Utc::now();
+        let expires_at = restricted_at + Duration::seconds(60);
+        let restriction = PointerRestriction {
+            restriction_id: Uuid::new_v4(),
+            pointer_id: Uuid::new_v4(),
+            org_id: Uuid::new_v4(),
+            subject_id: "test_user".to_string(),
+            reason: "dispute".to_string(),
+            restricted_at,
+            expires_at,
+            lifted_at: None,
+            lift_reason: None,
+        };
+
+        let inside = FixedClock(restricted_at + Duration::seconds(30));
+        assert_eq!(
+            restricted_until(Some(&restriction), &inside),
+            Some(expires_at)
+        );
+        assert_eq!(restricted_until(None, &inside), None);
+
+        let expired = FixedClock(expires_at);
+        assert_eq!(restricted_until(Some(&restriction), &expired), None);
+
+        let lifted = PointerRestriction {
+            lifted_at: Some(restricted_at),
+            lift_reason: Some("manual".to_string()),
+            ..restriction
+        };
+        assert_eq!(restricted_until(Some(&lifted), &inside), None);
+    }
 }
@@ -0,0 +1,462 @@
+// Garbage collection of unreferenced data_store rows
+// A swap leaves the old row behind so it stays provable; once no pointer has
+// referenced it for the grace period it is deleted or has its payload
+// dropped, and each run is recorded per org in a signed manifest
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::PgPool;
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    clock::Clock,
+    config::Config,
+    crypto::{DataGcManifest, Ed25519Keypair, SigningKeyring},
+    db::{
+        models::{DataGcCandidate, DataGcRun},
+        queries,
+    },
+};
+
+/// Most rows considered per run; the rest wait for the next interval
+const DATA_GC_BATCH_SIZE: i64 = 1000;
+
+/// What collection does to an eligible row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataGcMode {
+    /// Remove the row entirely
+    Delete,
+    /// Keep the row and its content hash, drop the encrypted payload
+    NullPayload,
+}
+
+impl DataGcMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataGcMode::Delete => "delete",
+            DataGcMode::NullPayload => "null_payload",
+        }
+    }
+}
+
+impl FromStr for DataGcMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "delete" => Ok(DataGcMode::Delete),
+            "null_payload" => Ok(DataGcMode::NullPayload),
+            other => bail!("unknown data GC mode '{}'", other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DataGcSettings {
+    pub mode: DataGcMode,
+    /// How long a row must have gone unreferenced before it is eligible
+    pub grace: Duration,
+    /// Report what would be collected without changing anything
+    pub dry_run: bool,
+}
+
+impl DataGcSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            mode: config.data_gc_mode,
+            grace: Duration::seconds(config.data_gc_grace_seconds as i64),
+            dry_run: config.data_gc_dry_run,
+        }
+    }
+
+    fn null_payload(&self) -> bool {
+        self.mode == DataGcMode::NullPayload
+    }
+}
+
+/// Outcome of one run for one org
+#[derive(Debug, Clone, Serialize)]
+pub struct DataGcReport {
+    pub org_id: Uuid,
+    pub mode: DataGcMode,
+    pub dry_run: bool,
+    pub data_ids: Vec<Uuid>,
+    pub bytes_reclaimed: i64,
+    /// Signed manifest; absent on dry runs
+    pub run: Option<DataGcRun>,
+}
+
+/// Scan for rows eligible at `now`, optionally within one org
+pub async fn find_candidates(
+    pool: &PgPool,
+    org_id: Option<Uuid>,
+    settings: DataGcSettings,
+    now: DateTime<Utc>,
+) -> Result<Vec<DataGcCandidate>> {
+    queries::get_data_gc_candidates(
+        pool,
+        org_id,
+        now - settings.grace,
+        settings.null_payload(),
+        DATA_GC_BATCH_SIZE,
+    )
+    .await
+}
+
+/// Collect scanned rows of one org in a single transaction. Rows are locked
+/// and then re-checked, so a row referenced again since the scan is left
+/// alone. Returns None if nothing was still eligible.
+pub async fn collect_org(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    org_id: Uuid,
+    data_ids: &[Uuid],
+    settings: DataGcSettings,
+    now: DateTime<Utc>,
+) -> Result<Option<DataGcReport>> {
+    let cutoff = now - settings.grace;
+    let mut tx = pool.begin().await?;
+
+    let locked = queries::lock_data_store_rows(&mut *tx, data_ids).await?;
+    let collected =
+        queries::collect_data_store(&mut *tx, &locked, cutoff, settings.null_payload()).await?;
+
+    if collected.is_empty() {
+        return Ok(None);
+    }
+
+    let data_ids: Vec<Uuid> = collected.iter().map(|(data_id, _)| *data_id).collect();
+    let bytes_reclaimed = collected.iter().map(|(_, bytes)| bytes).sum();
+
+    let manifest = DataGcManifest {
+        manifest_id: Uuid::new_v4(),
+        org_id,
+        mode: settings.mode.as_str().to_string(),
+        data_ids: data_ids.clone(),
+        bytes_reclaimed,
+        grace_cutoff: cutoff,
+        timestamp: now,
+    };
+    let signed = manifest.sign(keypair)?;
+
+    let run = queries::create_data_gc_run(
+        &mut *tx,
+        manifest.manifest_id,
+        org_id,
+        &manifest.mode,
+        &data_ids,
+        bytes_reclaimed,
+        signed.receipt_json,
+        &signed.receipt_hash,
+        &signed.signature,
+        &signed.signature_algorithm,
+    )
+    .await?;
+
+    queries::create_audit_log(
+        &mut *tx,
+        Some(org_id),
+        None,
+        None,
+        "data_store_collected",
+        json!({
+            "manifest_id": run.manifest_id,
+            "manifest_hash": run.manifest_hash,
+            "mode": run.mode,
+            "count": data_ids.len(),
+            "bytes_reclaimed": bytes_reclaimed,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    info!(
+        "Data GC collected {} rows ({} bytes, {}) for org {}",
+        data_ids.len(),
+        bytes_reclaimed,
+        run.mode,
+        org_id
+    );
+
+    Ok(Some(DataGcReport {
+        org_id,
+        mode: settings.mode,
+        dry_run: false,
+        data_ids,
+        bytes_reclaimed,
+        run: Some(run),
+    }))
+}
+
+/// Scan and collect, one report per org with anything eligible
+pub async fn run_data_gc(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    org_id: Option<Uuid>,
+    settings: DataGcSettings,
+    now: DateTime<Utc>,
+) -> Result<Vec<DataGcReport>> {
+    let mut by_org: BTreeMap<Uuid, Vec<DataGcCandidate>> = BTreeMap::new();
+    for candidate in find_candidates(pool, org_id, settings, now).await? {
+        by_org.entry(candidate.org_id).or_default().push(candidate);
+    }
+
+    let mut reports = Vec::new();
+
+    for (org_id, candidates) in by_org {
+        let data_ids: Vec<Uuid> = candidates.iter().map(|c| c.data_id).collect();
+
+        if settings.dry_run {
+            let bytes_reclaimed = candidates.iter().map(|c| c.payload_bytes).sum();
+            info!(
+                "Data GC dry run: would collect {} rows ({} bytes, {}) for org {}",
+                data_ids.len(),
+                bytes_reclaimed,
+                settings.mode.as_str(),
+                org_id
+            );
+            reports.push(DataGcReport {
+                org_id,
+                mode: settings.mode,
+                dry_run: true,
+                data_ids,
+                bytes_reclaimed,
+                run: None,
+            });
+            continue;
+        }
+
+        if let Some(report) = collect_org(pool, keypair, org_id, &data_ids, settings, now).await? {
+            reports.push(report);
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Background loop: collect eligible rows every configured interval
+pub async fn run_data_gc_worker(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+) {
+    let settings = DataGcSettings::from_config(&config);
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.data_gc_interval_seconds.max(1),
+    ));
+
+    info!(
+        "Data GC running every {}s ({}, grace {}s, dry run {})",
+        config.data_gc_interval_seconds,
+        settings.mode.as_str(),
+        config.data_gc_grace_seconds,
+        settings.dry_run
+    );
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) =
+            run_data_gc(&pool, &signing_keys.active(), None, settings, clock.now()).await
+        {
+            error!("Data GC failed: {:#}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::{hashing::sha3_512_hash_str, jcs::canonicalize},
+        db::models::DataStore,
+        test_support::{test_state, unique_subject},
+    };
+    use ed25519_dalek::Signature;
+
+    const SETTINGS: DataGcSettings = DataGcSettings {
+        mode: DataGcMode::Delete,
+        grace: Duration::days(7),
+        dry_run: false,
+    };
+
+    async fn gc_org(pool: &PgPool) -> Uuid {
+        let org_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO organizations (org_id, name) VALUES ($1, 'Data GC Test Org')")
+            .bind(org_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        org_id
+    }
+
+    /// Insert a data row created `age` ago with a payload of `payload_len` bytes
+    async fn seed_data(pool: &PgPool, org_id: Uuid, age: Duration, payload_len: usize) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO data_store \
+             (org_id, subject_id, content_hash, encrypted_payload, created_at) \
+             VALUES ($1, $2, $3, $4, $5) RETURNING data_id",
+        )
+        .bind(org_id)
+        .bind(unique_subject("gc"))
+        .bind("ab".repeat(64))
+        .bind(vec![7u8; payload_len])
+        .bind(Utc::now() - age)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn data_row(pool: &PgPool, data_id: Uuid) -> Option<DataStore> {
+        queries::get_data_store(pool, data_id).await.unwrap()
+    }
+
+    #[test]
+    fn test_mode_parsing() {
+        assert_eq!("delete".parse::<DataGcMode>().unwrap(), DataGcMode::Delete);
+        assert_eq!(
+            "null_payload".parse::<DataGcMode>().unwrap(),
+            DataGcMode::NullPayload
+        );
+        assert!("purge".parse::<DataGcMode>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_collects_only_eligible_rows_with_signed_manifest() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pool = &state.db.writer;
+        let org_id = gc_org(pool).await;
+        let old = Duration::days(30);
+
+        let eligible = seed_data(pool, org_id, old, 100).await;
+        let recent = seed_data(pool, org_id, Duration::days(1), 100).await;
+        let held = seed_data(pool, org_id, old, 100).await;
+        let referenced = seed_data(pool, org_id, old, 100).await;
+        let released = seed_data(pool, org_id, old, 100).await;
+
+        sqlx::query("UPDATE data_store SET legal_hold = true WHERE data_id = $1")
+            .bind(held)
+            .execute(pool)
+            .await
+            .unwrap();
+        queries::create_pointer(pool, org_id, referenced, &unique_subject("gc"), None, false)
+            .await
+            .unwrap();
+        queries::mark_data_store_released(pool, released, Utc::now() - Duration::hours(1))
+            .await
+            .unwrap();
+
+        let reports = run_data_gc(pool, &state.keypair(), Some(org_id), SETTINGS, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].data_ids, [eligible]);
+        assert_eq!(reports[0].bytes_reclaimed, 100);
+
+        assert!(data_row(pool, eligible).await.is_none());
+        for kept in [recent, held, referenced, released] {
+            assert!(data_row(pool, kept).await.is_some());
+        }
+
+        let run = reports[0].run.as_ref().unwrap();
+        assert_eq!(run.data_ids, [eligible]);
+        assert_eq!(run.manifest_json["count"], 1);
+        assert_eq!(run.manifest_json["bytes_reclaimed"], 100);
+        assert_eq!(run.manifest_json["mode"], "delete");
+        assert_eq!(
+            sha3_512_hash_str(&canonicalize(&run.manifest_json)),
+            run.manifest_hash
+        );
+        let signature = Signature::from_slice(&run.signature).unwrap();
+        assert!(state
+            .keypair()
+            .verify(run.manifest_hash.as_bytes(), &signature));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_then_null_payload() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pool = &state.db.writer;
+        let org_id = gc_org(pool).await;
+        let data_id = seed_data(pool, org_id, Duration::days(30), 64).await;
+
+        let settings = DataGcSettings {
+            mode: DataGcMode::NullPayload,
+            dry_run: true,
+            ..SETTINGS
+        };
+        let dry = run_data_gc(pool, &state.keypair(), Some(org_id), settings, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(dry[0].data_ids, [data_id]);
+        assert_eq!(dry[0].bytes_reclaimed, 64);
+        assert!(dry[0].run.is_none());
+        let row = data_row(pool, data_id).await.unwrap();
+        assert_eq!(row.encrypted_payload.as_deref(), Some(&[7u8; 64][..]));
+
+        let settings = DataGcSettings {
+            dry_run: false,
+            ..settings
+        };
+        let reports = run_data_gc(pool, &state.keypair(), Some(org_id), settings, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(reports[0].data_ids, [data_id]);
+        assert_eq!(reports[0].run.as_ref().unwrap().mode, "null_payload");
+
+        // The row and its content hash stay; only the payload is gone
+        let row = data_row(pool, data_id).await.unwrap();
+        assert!(row.encrypted_payload.is_none());
+        assert_eq!(row.content_hash, "ab".repeat(64));
+
+        // Nothing left to reclaim
+        let again = run_data_gc(pool, &state.keypair(), Some(org_id), settings, Utc::now())
+            .await
+            .unwrap();
+        assert!(again.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_row_referenced_after_scan_is_spared() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pool = &state.db.writer;
+        let org_id = gc_org(pool).await;
+        let reused = seed_data(pool, org_id, Duration::days(30), 10).await;
+        let dropped = seed_data(pool, org_id, Duration::days(30), 10).await;
+
+        let now = Utc::now();
+        let candidates = find_candidates(pool, Some(org_id), SETTINGS, now)
+            .await
+            .unwrap();
+        let data_ids: Vec<Uuid> = candidates.iter().map(|c| c.data_id).collect();
+        assert_eq!(data_ids.len(), 2);
+
+        // A pointer picks the row back up between the scan and the collection
+        queries::create_pointer(pool, org_id, reused, &unique_subject("gc"), None, false)
+            .await
+            .unwrap();
+
+        let report = collect_org(pool, &state.keypair(), org_id, &data_ids, SETTINGS, now)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.data_ids, [dropped]);
+        assert!(data_row(pool, reused).await.is_some());
+        assert!(data_row(pool, dropped).await.is_none());
+    }
+}
@@ -0,0 +1,4 @@
+// Data store garbage collection module
+pub mod collector;
+
+pub use collector::*;
@@ -0,0 +1,355 @@
+// Per-org heartbeat emission
+// Each elapsed interval gets one signed beat chained to the org's previous
+// heartbeat. Intervals missed while the service was down get a single gap
+// marker rather than beats nobody was around to sign at the time.
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    clock::Clock,
+    config::Config,
+    crypto::{Ed25519Keypair, HeartbeatReceipt, SigningKeyring},
+    db::{models::OrgHeartbeat, queries},
+};
+
+/// Most active pointers whose chain heads each beat commits to
+const HEARTBEAT_CHAIN_HEADS: i64 = 10;
+
+/// Start of the interval containing `now`, aligned to the Unix epoch
+pub fn interval_floor(now: DateTime<Utc>, interval: Duration) -> DateTime<Utc> {
+    let seconds = interval.num_seconds().max(1);
+    let start = now.timestamp() - now.timestamp().rem_euclid(seconds);
+    DateTime::from_timestamp(start, 0).unwrap_or(now)
+}
+
+/// Extend the org's heartbeat chain up to the last interval completed at
+/// `now`. Safe to call repeatedly and from several instances: the org row
+/// lock and the unique interval constraint prevent duplicate beats.
+pub async fn emit_org_heartbeats(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    org_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<Vec<OrgHeartbeat>> {
+    let mut tx = pool.begin().await?;
+
+    let Some(interval_seconds) = queries::lock_org_heartbeat_interval(&mut *tx, org_id).await?
+    else {
+        return Ok(vec![]);
+    };
+    let interval = Duration::seconds(interval_seconds.into());
+
+    let current_start = interval_floor(now, interval);
+    let due_start = current_start - interval;
+    let latest = queries::get_latest_heartbeat(&mut *tx, org_id).await?;
+
+    let mut emitted = Vec::new();
+    let mut prev_hash = latest.as_ref().map(|h| h.receipt_hash.clone());
+    let mut beat_start = due_start;
+
+    if let Some(latest) = &latest {
+        if latest.interval_end >= current_start {
+            return Ok(vec![]);
+        }
+
+        if latest.interval_end < due_start {
+            // Down for at least one whole interval: say so explicitly
+            let gap = write_heartbeat(
+                &mut tx,
+                keypair,
+                org_id,
+                "gap",
+                (latest.interval_end, due_start),
+                json!({}),
+                json!([]),
+                prev_hash.take(),
+            )
+            .await?;
+            warn!(
+                "Heartbeat gap for org {} from {} to {}",
+                org_id, gap.interval_start, gap.interval_end
+            );
+            prev_hash = Some(gap.receipt_hash.clone());
+            emitted.push(gap);
+        } else {
+            // Interval length changed since the last beat; stay contiguous
+            beat_start = latest.interval_end;
+        }
+    }
+
+    let counts = queries::count_org_operations(&mut *tx, org_id, beat_start, current_start).await?;
+    let heads = queries::get_active_chain_heads(
+        &mut *tx,
+        org_id,
+        beat_start,
+        current_start,
+        HEARTBEAT_CHAIN_HEADS,
+    )
+    .await?;
+
+    let operation_counts: serde_json::Map<String, serde_json::Value> = counts
+        .into_iter()
+        .map(|(operation, count)| (operation, count.into()))
+        .collect();
+    let chain_heads: Vec<serde_json::Value> = heads
+        .into_iter()
+        .map(|(pointer_id, receipt_hash)| {
+            json!({"pointer_id": pointer_id, "receipt_hash": receipt_hash})
+        })
+        .collect();
+
+    let beat = write_heartbeat(
+        &mut tx,
+        keypair,
+        org_id,
+        "beat",
+        (beat_start, current_start),
+        operation_counts.into(),
+        chain_heads.into(),
+        prev_hash,
+    )
+    .await?;
+    emitted.push(beat);
+
+    tx.commit().await?;
+
+    Ok(emitted)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn write_heartbeat(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    org_id: Uuid,
+    kind: &str,
+    (interval_start, interval_end): (DateTime<Utc>, DateTime<Utc>),
+    operation_counts: serde_json::Value,
+    chain_heads: serde_json::Value,
+    prev_hash: Option<String>,
+) -> Result<OrgHeartbeat> {
+    let receipt = HeartbeatReceipt {
+        heartbeat_id: Uuid::new_v4(),
+        org_id,
+        kind: kind.to_string(),
+        interval_start,
+        interval_end,
+        operation_counts,
+        chain_heads,
+        prev_hash,
+        timestamp: Utc::now(),
+    };
+    let signed = receipt.sign(keypair)?;
+
+    queries::create_org_heartbeat(
+        &mut *conn,
+        receipt.heartbeat_id,
+        org_id,
+        kind,
+        interval_start,
+        interval_end,
+        signed.receipt_json,
+        &signed.receipt_hash,
+        &signed.signature,
+        &signed.signature_algorithm,
+        receipt.prev_hash.as_deref(),
+    )
+    .await
+}
+
+/// Background loop: extend every enabled org's heartbeat chain
+pub async fn run_heartbeat_emitter(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.heartbeat_check_interval_seconds.max(1),
+    ));
+
+    info!(
+        "Heartbeat emitter checking every {}s",
+        config.heartbeat_check_interval_seconds
+    );
+
+    loop {
+        interval.tick().await;
+
+        let orgs = match queries::get_heartbeat_orgs(&pool).await {
+            Ok(orgs) => orgs,
+            Err(e) => {
+                error!("Heartbeat org lookup failed: {:#}", e);
+                continue;
+            }
+        };
+
+        for (org_id, _) in orgs {
+            if let Err(e) =
+                emit_org_heartbeats(&pool, &signing_keys.active(), org_id, clock.now()).await
+            {
+                error!("Heartbeat for org {} failed: {:#}", org_id, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::AppState,
+        heartbeats::verify_heartbeats,
+        test_support::{seed_pointer, test_state, unique_subject},
+    };
+
+    async fn heartbeat_org(state: &AppState) -> Uuid {
+        let org_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO organizations (org_id, name, heartbeat_interval_seconds) \
+             VALUES ($1, 'Heartbeat Test Org', 3600)",
+        )
+        .bind(org_id)
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+        org_id
+    }
+
+    #[test]
+    fn test_interval_floor_aligns_to_epoch() {
+        let at = DateTime::parse_from_rfc3339("2025-11-26T10:42:17Z")
+            .unwrap()
+            .into();
+        assert_eq!(
+            interval_floor(at, Duration::hours(1)).to_rfc3339(),
+            "2025-11-26T10:00:00+00:00"
+        );
+        assert_eq!(
+            interval_floor(at, Duration::minutes(15)).to_rfc3339(),
+            "2025-11-26T10:30:00+00:00"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_downtime_leaves_gap_marker() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let org_id = heartbeat_org(&state).await;
+
+        let mut config = state.config.clone();
+        config.default_org_id = org_id;
+        let org_state = AppState {
+            config,
+            ..state.clone()
+        };
+        let pointer_id = seed_pointer(&org_state, &unique_subject("heartbeat")).await;
+
+        let created = queries::get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let start = interval_floor(created[0].timestamp, Duration::hours(1));
+        let pool = &state.db.writer;
+
+        // First beat covers the hour the pointer was created in
+        let first = emit_org_heartbeats(pool, &state.keypair(), org_id, start + Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].kind, "beat");
+        assert_eq!(first[0].receipt_json["operation_counts"]["create"], 1);
+        assert_eq!(
+            first[0].receipt_json["chain_heads"][0]["pointer_id"],
+            pointer_id.to_string()
+        );
+
+        // A restart within the same interval emits nothing new
+        let repeat = emit_org_heartbeats(
+            pool,
+            &state.keypair(),
+            org_id,
+            start + Duration::hours(1) + Duration::minutes(30),
+        )
+        .await
+        .unwrap();
+        assert!(repeat.is_empty());
+
+        // Down for three hours: one gap marker, then the latest beat
+        let resumed =
+            emit_org_heartbeats(pool, &state.keypair(), org_id, start + Duration::hours(5))
+                .await
+                .unwrap();
+        let kinds: Vec<&str> = resumed.iter().map(|h| h.kind.as_str()).collect();
+        assert_eq!(kinds, ["gap", "beat"]);
+        assert_eq!(resumed[0].interval_start, start + Duration::hours(1));
+        assert_eq!(resumed[0].interval_end, start + Duration::hours(4));
+        assert_eq!(
+            resumed[0].prev_hash.as_deref(),
+            Some(first[0].receipt_hash.as_str())
+        );
+
+        let heartbeats =
+            queries::get_org_heartbeats(pool, org_id, start, start + Duration::hours(6))
+                .await
+                .unwrap();
+        let verification = verify_heartbeats(&heartbeats, None);
+        assert_eq!(verification.beats, 2);
+        assert_eq!(verification.gaps.len(), 1);
+        assert!(verification.gaps[0].marked);
+        assert!(verification.broken_links.is_empty());
+        assert!(verification.hash_mismatches.is_empty());
+        assert!(!verification.continuous);
+
+        // A silently deleted gap marker is still detected
+        let without_marker = [heartbeats[0].clone(), heartbeats[2].clone()];
+        let verification =
+            verify_heartbeats(&without_marker, Some(start..start + Duration::hours(7)));
+        let unmarked: Vec<_> = verification.gaps.iter().filter(|g| !g.marked).collect();
+        assert_eq!(unmarked.len(), 2, "missing marker and trailing gap");
+        assert_eq!(unmarked[0].start, start + Duration::hours(1));
+        assert_eq!(unmarked[1].start, start + Duration::hours(5));
+        assert_eq!(verification.broken_links, [heartbeats[2].heartbeat_id]);
+    }
+
+    #[tokio::test]
+    async fn test_simulated_hours_of_beats_and_outage() {
+        use crate::test_support::SimulatedTime;
+
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let org_id = heartbeat_org(&state).await;
+        let mut sim = SimulatedTime::new(state);
+        sim.track_org(org_id);
+        let start = sim.now();
+
+        let due = sim.advance(Duration::hours(3)).await;
+        let kinds: Vec<&str> = due.heartbeats.iter().map(|h| h.kind.as_str()).collect();
+        assert_eq!(kinds, ["beat", "beat", "beat"]);
+        assert_eq!(due.heartbeats[2].interval_end, start + Duration::hours(3));
+
+        sim.outage(Duration::hours(3));
+        let due = sim.advance(Duration::hours(1)).await;
+        let kinds: Vec<&str> = due.heartbeats.iter().map(|h| h.kind.as_str()).collect();
+        assert_eq!(kinds, ["gap", "beat"]);
+
+        let heartbeats = queries::get_org_heartbeats(
+            &sim.state.db.writer,
+            org_id,
+            start - Duration::hours(1),
+            sim.now(),
+        )
+        .await
+        .unwrap();
+        let verification = verify_heartbeats(&heartbeats, Some(start..sim.now()));
+        assert_eq!(verification.beats, 4);
+        assert_eq!(verification.gaps.len(), 1);
+        assert!(verification.gaps[0].marked);
+        assert!(verification.broken_links.is_empty());
+    }
+}
@@ -0,0 +1,6 @@
+// Org liveness heartbeat module
+pub mod emit;
+pub mod verify;
+
+pub use emit::*;
+pub use verify::*;
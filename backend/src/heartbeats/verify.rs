@@ -0,0 +1,92 @@
+// Heartbeat chain verification
+// Finds intervals without a beat, whether marked by a gap receipt or
+// silently missing, and links or hashes that do not check out
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::ops::Range;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{crypto::stored_receipt_hash, db::models::OrgHeartbeat};
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HeartbeatGap {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// True when the service recorded the gap itself with a gap marker
+    pub marked: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HeartbeatVerification {
+    pub beats: usize,
+    pub gaps: Vec<HeartbeatGap>,
+    /// Heartbeats whose prev_hash does not match their predecessor
+    pub broken_links: Vec<Uuid>,
+    /// Heartbeats whose stored receipt no longer hashes to receipt_hash
+    pub hash_mismatches: Vec<Uuid>,
+    /// Continuous, intact and current attestation over the checked window
+    pub continuous: bool,
+}
+
+/// Check heartbeats ordered by interval_start. `expected` is the span the
+/// chain should cover by now, if heartbeats are enabled; a chain ending
+/// early, or missing entirely, gets an unmarked gap.
+pub fn verify_heartbeats(
+    heartbeats: &[OrgHeartbeat],
+    expected: Option<Range<DateTime<Utc>>>,
+) -> HeartbeatVerification {
+    let mut gaps = Vec::new();
+    let mut broken_links = Vec::new();
+    let mut hash_mismatches = Vec::new();
+
+    for (i, heartbeat) in heartbeats.iter().enumerate() {
+        if stored_receipt_hash(&heartbeat.receipt_json, &heartbeat.receipt_hash)
+            != heartbeat.receipt_hash
+        {
+            hash_mismatches.push(heartbeat.heartbeat_id);
+        }
+
+        if let Some(prev) = i.checked_sub(1).map(|p| &heartbeats[p]) {
+            if heartbeat.prev_hash.as_deref() != Some(prev.receipt_hash.as_str()) {
+                broken_links.push(heartbeat.heartbeat_id);
+            }
+            if heartbeat.interval_start > prev.interval_end {
+                gaps.push(HeartbeatGap {
+                    start: prev.interval_end,
+                    end: heartbeat.interval_start,
+                    marked: false,
+                });
+            }
+        }
+
+        if heartbeat.kind == "gap" {
+            gaps.push(HeartbeatGap {
+                start: heartbeat.interval_start,
+                end: heartbeat.interval_end,
+                marked: true,
+            });
+        }
+    }
+
+    if let Some(expected) = expected {
+        let start = heartbeats
+            .last()
+            .map_or(expected.start, |last| last.interval_end);
+        if start < expected.end {
+            gaps.push(HeartbeatGap {
+                start,
+                end: expected.end,
+                marked: false,
+            });
+        }
+    }
+
+    HeartbeatVerification {
+        beats: heartbeats.iter().filter(|h| h.kind == "beat").count(),
+        continuous: gaps.is_empty() && broken_links.is_empty() && hash_mismatches.is_empty(),
+        gaps,
+        broken_links,
+        hash_mismatches,
+    }
+}
@@ -0,0 +1,494 @@
+// API key issuance, rotation and revocation
+// Keys are issued by mint-key or by rotation, and revoked by revoke-key, the
+// early-revoke endpoint or, once a rotation overlap ends, the key sweeper.
+// Every step is receipted on the org chain and audited, so an org can show
+// which keys could act for it at any moment. A rotated key keeps working
+// alongside its successor until the end of the overlap, so integrators can
+// switch over without downtime.
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use data_encoding::BASE64URL_NOPAD;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        auth::{hash_api_key, API_KEY_PREFIX},
+        context::RequestContext,
+    },
+    crypto::{Ed25519Keypair, SignedReceipt},
+    db::{models::ApiKey, queries},
+    enforcement::append_org_receipt,
+};
+
+/// Revocation reason of a key revoked on request
+pub const REVOKED_ON_REQUEST: &str = "revoked";
+
+/// Revocation reason of a rotated key whose overlap ended
+pub const ROTATION_OVERLAP_ENDED: &str = "rotation_overlap_ended";
+
+#[derive(Debug, Clone)]
+pub struct IssuedApiKey {
+    pub api_key: ApiKey,
+    /// The key itself; only its hash is stored, so this is the one chance
+    /// to hand it out
+    pub key: String,
+    /// Org chain receipt of the issuance
+    pub org_receipt: SignedReceipt,
+}
+
+#[derive(Debug, Clone)]
+pub struct RotatedApiKey {
+    pub issued: IssuedApiKey,
+    /// The replaced key, with the end of its overlap
+    pub previous: ApiKey,
+    /// Org chain receipt of the overlap
+    pub rotation_receipt: SignedReceipt,
+}
+
+#[derive(Debug, Clone)]
+pub struct RevokedApiKey {
+    pub api_key: ApiKey,
+    /// Org chain receipt of the revocation
+    pub org_receipt: SignedReceipt,
+}
+
+/// Whether a key authenticates at `now`: not revoked, and inside any
+/// rotation overlap
+pub fn key_is_valid(api_key: &ApiKey, now: DateTime<Utc>) -> bool {
+    api_key.revoked_at.is_none() && api_key.expires_at.is_none_or(|expires| now < expires)
+}
+
+/// When a key stops, or stopped, authenticating; None while open-ended
+pub fn key_valid_until(api_key: &ApiKey) -> Option<DateTime<Utc>> {
+    match (api_key.revoked_at, api_key.expires_at) {
+        (Some(revoked), Some(expires)) => Some(revoked.min(expires)),
+        (revoked, expires) => revoked.or(expires),
+    }
+}
+
+/// Create a key for an organization
+pub async fn mint_api_key(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    org_id: Uuid,
+    label: &str,
+    now: DateTime<Utc>,
+) -> Result<IssuedApiKey> {
+    let mut tx = pool.begin().await?;
+    let issued = issue_api_key(
+        &mut tx,
+        keypair,
+        org_id,
+        label,
+        None,
+        &RequestContext::default(),
+        now,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(issued)
+}
+
+/// Revoke a key; None if it doesn't exist or was already revoked
+pub async fn revoke_api_key(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    key_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<Option<RevokedApiKey>> {
+    let mut tx = pool.begin().await?;
+    let Some(api_key) = queries::lock_api_key(&mut *tx, key_id).await? else {
+        return Ok(None);
+    };
+    let revoked = revoke_api_key_with_receipt(
+        &mut tx,
+        keypair,
+        &api_key,
+        REVOKED_ON_REQUEST,
+        now,
+        &RequestContext::default(),
+        now,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(revoked)
+}
+
+/// Replace a locked key inside the caller's transaction: issue its
+/// successor, and keep the key valid for `overlap` more
+pub async fn rotate_api_key_with_receipt(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    current: &ApiKey,
+    overlap: Duration,
+    ctx: &RequestContext,
+    now: DateTime<Utc>,
+) -> Result<RotatedApiKey> {
+    let issued = issue_api_key(
+        conn,
+        keypair,
+        current.org_id,
+        &current.label,
+        Some(current.key_id),
+        ctx,
+        now,
+    )
+    .await?;
+    let previous = queries::mark_api_key_rotated(
+        &mut *conn,
+        current.key_id,
+        issued.api_key.key_id,
+        now + overlap,
+    )
+    .await?;
+
+    let details = json!({
+        "key_id": previous.key_id,
+        "replaced_by": issued.api_key.key_id,
+        "valid_until": previous.expires_at,
+    });
+    let rotation_receipt = append_org_receipt(
+        conn,
+        keypair,
+        previous.org_id,
+        None,
+        "api_key_rotated",
+        details.clone(),
+        now,
+    )
+    .await?;
+    audit_key_event(
+        conn,
+        &previous,
+        "api_key_rotated",
+        details,
+        &rotation_receipt,
+        ctx,
+    )
+    .await?;
+
+    Ok(RotatedApiKey {
+        issued,
+        previous,
+        rotation_receipt,
+    })
+}
+
+/// Revoke a locked key inside the caller's transaction, effective at
+/// `revoked_at`; None if it was already revoked
+pub async fn revoke_api_key_with_receipt(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    api_key: &ApiKey,
+    reason: &str,
+    revoked_at: DateTime<Utc>,
+    ctx: &RequestContext,
+    now: DateTime<Utc>,
+) -> Result<Option<RevokedApiKey>> {
+    let Some(api_key) = queries::revoke_api_key(&mut *conn, api_key.key_id, revoked_at).await?
+    else {
+        return Ok(None);
+    };
+
+    let details = json!({
+        "key_id": api_key.key_id,
+        "reason": reason,
+        "revoked_at": api_key.revoked_at,
+        "replaced_by": api_key.replaced_by,
+    });
+    let org_receipt = append_org_receipt(
+        conn,
+        keypair,
+        api_key.org_id,
+        None,
+        "api_key_revoked",
+        details.clone(),
+        now,
+    )
+    .await?;
+    audit_key_event(
+        conn,
+        &api_key,
+        "api_key_revoked",
+        details,
+        &org_receipt,
+        ctx,
+    )
+    .await?;
+
+    Ok(Some(RevokedApiKey {
+        api_key,
+        org_receipt,
+    }))
+}
+
+async fn issue_api_key(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    org_id: Uuid,
+    label: &str,
+    rotated_from: Option<Uuid>,
+    ctx: &RequestContext,
+    now: DateTime<Utc>,
+) -> Result<IssuedApiKey> {
+    let key = format!(
+        "{}{}",
+        API_KEY_PREFIX,
+        BASE64URL_NOPAD.encode(&rand::random::<[u8; 32]>())
+    );
+    let api_key = queries::create_api_key(
+        &mut *conn,
+        Uuid::new_v4(),
+        &hash_api_key(&key),
+        org_id,
+        label,
+    )
+    .await?;
+
+    let details = json!({
+        "key_id": api_key.key_id,
+        "label": api_key.label,
+        "rotated_from": rotated_from,
+    });
+    let org_receipt = append_org_receipt(
+        conn,
+        keypair,
+        org_id,
+        None,
+        "api_key_issued",
+        details.clone(),
+        now,
+    )
+    .await?;
+    audit_key_event(conn, &api_key, "api_key_issued", details, &org_receipt, ctx).await?;
+
+    Ok(IssuedApiKey {
+        api_key,
+        key,
+        org_receipt,
+    })
+}
+
+async fn audit_key_event(
+    conn: &mut PgConnection,
+    api_key: &ApiKey,
+    event_type: &str,
+    mut details: serde_json::Value,
+    org_receipt: &SignedReceipt,
+    ctx: &RequestContext,
+) -> Result<()> {
+    details["org_receipt_hash"] = json!(org_receipt.receipt_hash);
+    queries::create_audit_log(
+        &mut *conn,
+        Some(api_key.org_id),
+        None,
+        None,
+        event_type,
+        details,
+        ctx.actor_id.as_deref(),
+        ctx.ip_address,
+        ctx.user_agent.as_deref(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            auth::{require_api_key, AuthContext},
+            handlers::{revoke_api_key as revoke_handler, rotate_api_key},
+            ApiError, AppState,
+        },
+        clock::ManualClock,
+        test_support::test_state,
+    };
+    use axum::{
+        body::Body,
+        extract::{Path, State},
+        http::{header::AUTHORIZATION, StatusCode},
+        routing::get,
+        Router,
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn whoami_app(state: &AppState) -> Router {
+        Router::new()
+            .route(
+                "/whoami",
+                get(|auth: AuthContext| async move { auth.key_id.to_string() }),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                require_api_key,
+            ))
+            .with_state(state.clone())
+    }
+
+    /// Status and error code of an authenticated request
+    async fn call_with(app: &Router, key: &str) -> (StatusCode, Option<String>) {
+        let request = axum::http::Request::builder()
+            .uri("/whoami")
+            .header(AUTHORIZATION, format!("Bearer {}", key))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let code = serde_json::from_slice::<serde_json::Value>(&bytes)
+            .ok()
+            .and_then(|body| body["code"].as_str().map(str::to_string));
+        (status, code)
+    }
+
+    fn auth_of(api_key: &ApiKey) -> AuthContext {
+        AuthContext {
+            org_id: api_key.org_id,
+            key_id: api_key.key_id,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_both_keys_work_during_overlap_then_rotation_completes() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let start = Utc::now();
+        let clock = Arc::new(ManualClock::new(start));
+        let state = AppState {
+            clock: clock.clone(),
+            ..state
+        };
+        let app = whoami_app(&state);
+
+        let old = mint_api_key(
+            &state.db.writer,
+            &state.keypair(),
+            state.config.default_org_id,
+            "rotation",
+            start,
+        )
+        .await
+        .unwrap();
+
+        let rotated = rotate_api_key(
+            State(state.clone()),
+            auth_of(&old.api_key),
+            RequestContext::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("rotate failed"))
+        .0;
+        assert_eq!(rotated.previous_key_id, old.api_key.key_id);
+        assert_eq!(
+            rotated.overlap_seconds,
+            state.config.api_key_rotation_overlap_seconds
+        );
+
+        // Both keys authenticate while the overlap lasts
+        assert_eq!(call_with(&app, &old.key).await.0, StatusCode::OK);
+        assert_eq!(call_with(&app, &rotated.api_key).await.0, StatusCode::OK);
+
+        // A rotated key can't be rotated a second time
+        let again = rotate_api_key(
+            State(state.clone()),
+            auth_of(&old.api_key),
+            RequestContext::default(),
+        )
+        .await;
+        assert!(matches!(again, Err(ApiError::Conflict { .. })));
+
+        // Once it ends the old key is refused, before any sweep, with a code
+        // telling the caller the rotation completed
+        clock.set(start + Duration::seconds(state.config.api_key_rotation_overlap_seconds));
+        assert_eq!(
+            call_with(&app, &old.key).await,
+            (
+                StatusCode::UNAUTHORIZED,
+                Some("api_key_rotated".to_string())
+            )
+        );
+        assert_eq!(call_with(&app, &rotated.api_key).await.0, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_early_revoke_ends_the_overlap() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let app = whoami_app(&state);
+        let old = mint_api_key(
+            &state.db.writer,
+            &state.keypair(),
+            state.config.default_org_id,
+            "early revoke",
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        let rotated = rotate_api_key(
+            State(state.clone()),
+            auth_of(&old.api_key),
+            RequestContext::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("rotate failed"))
+        .0;
+
+        // The new key revokes the old one once clients have switched
+        let new_auth = AuthContext {
+            org_id: old.api_key.org_id,
+            key_id: rotated.key_id,
+        };
+        let revoked = revoke_handler(
+            State(state.clone()),
+            new_auth,
+            RequestContext::default(),
+            Path(old.api_key.key_id),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("revoke failed"))
+        .0;
+        assert_eq!(revoked.key_id, old.api_key.key_id);
+
+        assert_eq!(
+            call_with(&app, &old.key).await,
+            (
+                StatusCode::UNAUTHORIZED,
+                Some("api_key_rotated".to_string())
+            )
+        );
+        assert_eq!(call_with(&app, &rotated.api_key).await.0, StatusCode::OK);
+
+        // Revoking twice is a conflict, and another org's key is forbidden
+        let twice = revoke_handler(
+            State(state.clone()),
+            new_auth,
+            RequestContext::default(),
+            Path(old.api_key.key_id),
+        )
+        .await;
+        assert!(matches!(twice, Err(ApiError::Conflict { .. })));
+        let other_org = revoke_handler(
+            State(state.clone()),
+            AuthContext {
+                org_id: Uuid::new_v4(),
+                key_id: Uuid::nil(),
+            },
+            RequestContext::default(),
+            Path(rotated.key_id),
+        )
+        .await;
+        assert!(matches!(other_org, Err(ApiError::Forbidden(_))));
+    }
+}
@@ -0,0 +1,8 @@
+// API and signing key lifecycle module
+pub mod lifecycle;
+pub mod signing;
+pub mod sweeper;
+
+pub use lifecycle::*;
+pub use signing::*;
+pub use sweeper::*;
@@ -0,0 +1,381 @@
+// Receipt signing key registration and rotation
+// Every key that signs receipts is recorded in signing_keys under its key_id,
+// which each receipt carries. Rotation retires the current key and swaps in
+// a fresh one; retired keys never sign again but keep verifying the receipts
+// they signed, so chains spanning a rotation still verify end to end.
+//
+// With SIGNING_MASTER_KEY set, each organization also gets its own key,
+// generated on first use and stored sealed in org_signing_keys, and
+// handlers sign the org's receipts with it instead.
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::VerifyingKey;
+use sqlx::{PgConnection, PgPool};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::OnceCell;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    config::Config,
+    crypto::{Ed25519Keypair, KeySet, MasterKey, ReceiptKeys, SigningKeyring},
+    db::{
+        models::{GovernanceReceipt, OrgSigningKey, SigningKey},
+        queries,
+    },
+};
+
+#[derive(Clone)]
+pub struct RotatedSigningKey {
+    pub key: SigningKey,
+    pub keypair: Ed25519Keypair,
+    /// The key it replaced, now retired
+    pub retired: Option<SigningKey>,
+}
+
+pub fn verifying_key(key: &SigningKey) -> Result<VerifyingKey> {
+    public_key(&key.key_id, &key.public_key)
+}
+
+fn public_key(key_id: &str, bytes: &[u8]) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| anyhow!("Signing key {} is not 32 bytes", key_id))?;
+    Ok(VerifyingKey::from_bytes(&bytes)?)
+}
+
+/// Record the configured signer at startup and learn every key it replaced.
+/// A configured key that is new retires the previous one; one that was
+/// retired is refused, as signing with it again would undo a rotation.
+pub async fn register_signing_keys(
+    pool: &PgPool,
+    keyring: &SigningKeyring,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let keypair = keyring.active();
+    let key_id = keypair.key_id();
+
+    let mut tx = pool.begin().await?;
+    let current = queries::lock_active_signing_key(&mut *tx).await?;
+    match queries::get_signing_key(&mut *tx, &key_id).await? {
+        Some(SigningKey {
+            retired_at: Some(retired_at),
+            ..
+        }) => bail!(
+            "Signing key {} was retired at {}; configure the key that replaced it",
+            key_id,
+            retired_at.to_rfc3339()
+        ),
+        Some(_) => {}
+        None => {
+            if let Some(current) = current {
+                info!(
+                    "Configured signing key {} replaces {}",
+                    key_id, current.key_id
+                );
+                queries::retire_signing_key(&mut *tx, &current.key_id, now).await?;
+            }
+            queries::create_signing_key(&mut *tx, &key_id, &keypair.public_key_bytes(), now)
+                .await?;
+        }
+    }
+    tx.commit().await?;
+
+    for key in queries::list_signing_keys(pool).await? {
+        keyring.remember(verifying_key(&key)?);
+    }
+
+    Ok(())
+}
+
+/// Retire the current signer and record a new one, inside the caller's
+/// transaction. Once committed, write the new seed to the key file, if there
+/// is one, and swap it into the ring.
+pub async fn rotate_signing_key(
+    conn: &mut PgConnection,
+    keyring: &SigningKeyring,
+    now: DateTime<Utc>,
+) -> Result<RotatedSigningKey> {
+    let active_id = keyring.active().key_id();
+    let current = queries::lock_active_signing_key(&mut *conn).await?;
+    if let Some(current) = &current {
+        if current.key_id != active_id {
+            bail!(
+                "Signing key {} is no longer current ({} is); restart to pick it up",
+                active_id,
+                current.key_id
+            );
+        }
+        queries::retire_signing_key(&mut *conn, &current.key_id, now).await?;
+    }
+
+    let keypair = Ed25519Keypair::generate();
+    let key = queries::create_signing_key(
+        &mut *conn,
+        &keypair.key_id(),
+        &keypair.public_key_bytes(),
+        now,
+    )
+    .await?;
+
+    let retired = match current {
+        Some(current) => queries::get_signing_key(&mut *conn, &current.key_id).await?,
+        None => None,
+    };
+
+    Ok(RotatedSigningKey {
+        key,
+        keypair,
+        retired,
+    })
+}
+
+/// Keys to verify `receipts` with: the service keys, first learning any key
+/// another instance rotated in since this one started, and the org keys of
+/// the orgs the receipts belong to. An org's key never verifies another
+/// org's receipts.
+pub async fn receipt_keys(
+    pool: &PgPool,
+    keyring: &SigningKeyring,
+    receipts: &[GovernanceReceipt],
+) -> Result<KeySet> {
+    let mut org_keys = Vec::new();
+    for receipt in receipts {
+        let Some(key_id) = receipt.key_id.as_deref() else {
+            continue;
+        };
+        if keyring.knows(key_id) || org_keys.iter().any(|k: &OrgSigningKey| k.key_id == key_id) {
+            continue;
+        }
+        if let Some(key) = queries::get_signing_key(pool, key_id).await? {
+            keyring.remember(verifying_key(&key)?);
+        } else if let Some(key) = queries::get_org_signing_key_by_key_id(pool, key_id).await? {
+            org_keys.push(key);
+        }
+    }
+
+    let mut keys = keyring.key_set();
+    for key in org_keys {
+        if receipts
+            .iter()
+            .all(|r| r.key_id.as_deref() != Some(key.key_id.as_str()) || r.org_id == key.org_id)
+        {
+            keys.insert(public_key(&key.key_id, &key.public_key)?);
+        }
+    }
+
+    Ok(keys)
+}
+
+/// A public key found by key_id, and the org it belongs to if it is an
+/// org key rather than a service key
+pub struct KnownKey {
+    pub key: VerifyingKey,
+    pub org_id: Option<Uuid>,
+}
+
+/// Look up the public key recorded under `key_id`, service keys first
+pub async fn known_key(
+    pool: &PgPool,
+    keyring: &SigningKeyring,
+    key_id: &str,
+) -> Result<Option<KnownKey>> {
+    if let Some(key) = keyring.key_set().keys_for(Some(key_id)).pop() {
+        return Ok(Some(KnownKey { key, org_id: None }));
+    }
+    if let Some(key) = queries::get_signing_key(pool, key_id).await? {
+        let key = verifying_key(&key)?;
+        keyring.remember(key);
+        return Ok(Some(KnownKey { key, org_id: None }));
+    }
+    match queries::get_org_signing_key_by_key_id(pool, key_id).await? {
+        Some(key) => Ok(Some(KnownKey {
+            key: public_key(&key.key_id, &key.public_key)?,
+            org_id: Some(key.org_id),
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Per-organization receipt signers, loaded from org_signing_keys on first
+/// use and kept for the life of the process. An org with no key yet gets
+/// one generated, sealed under the master key and stored; when instances
+/// race, the first stored key wins and every instance signs with it.
+pub struct OrgSigningKeys {
+    master: MasterKey,
+    keys: Mutex<HashMap<Uuid, Arc<OnceCell<Ed25519Keypair>>>>,
+}
+
+impl OrgSigningKeys {
+    pub fn new(master: MasterKey) -> Self {
+        Self {
+            master,
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// None unless SIGNING_MASTER_KEY is set
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        config
+            .signing_master_key
+            .as_ref()
+            .map(|key| {
+                Ok(Self::new(MasterKey::from_encoded(
+                    "SIGNING_MASTER_KEY",
+                    key.expose(),
+                )?))
+            })
+            .transpose()
+    }
+
+    /// The org's signer, generating it on first use
+    pub async fn keypair(
+        &self,
+        pool: &PgPool,
+        org_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Ed25519Keypair> {
+        let cell = self.keys.lock().unwrap().entry(org_id).or_default().clone();
+
+        // Callers for the same org wait on one load; a failed load is retried
+        cell.get_or_try_init(|| self.load_or_create(pool, org_id, now))
+            .await
+            .cloned()
+    }
+
+    async fn load_or_create(
+        &self,
+        pool: &PgPool,
+        org_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Ed25519Keypair> {
+        if let Some(stored) = queries::get_org_signing_key(pool, org_id).await? {
+            return self.open(&stored);
+        }
+
+        let keypair = Ed25519Keypair::generate();
+        let key_id = keypair.key_id();
+        let sealed = self.master.seal(
+            keypair.signing_key.as_bytes(),
+            &sealing_context(org_id, &key_id),
+        )?;
+        let created = queries::create_org_signing_key(
+            pool,
+            org_id,
+            &key_id,
+            &keypair.public_key_bytes(),
+            &sealed,
+            now,
+        )
+        .await?;
+
+        match created {
+            Some(_) => {
+                info!("Generated signing key {} for org {}", key_id, org_id);
+                Ok(keypair)
+            }
+            None => {
+                let stored = queries::get_org_signing_key(pool, org_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("Signing key for org {} vanished", org_id))?;
+                self.open(&stored)
+            }
+        }
+    }
+
+    fn open(&self, stored: &OrgSigningKey) -> Result<Ed25519Keypair> {
+        let seed = self
+            .master
+            .open(
+                &stored.sealed_private_key,
+                &sealing_context(stored.org_id, &stored.key_id),
+            )
+            .map_err(|e| anyhow!("Signing key for org {}: {}", stored.org_id, e))?;
+        let seed: [u8; 32] = seed
+            .try_into()
+            .map_err(|_| anyhow!("Signing key for org {} is not 32 bytes", stored.org_id))?;
+
+        let keypair = Ed25519Keypair::from_seed(&seed);
+        if keypair.key_id() != stored.key_id {
+            bail!(
+                "Signing key for org {} does not match its key_id {}",
+                stored.org_id,
+                stored.key_id
+            );
+        }
+        Ok(keypair)
+    }
+}
+
+/// Associated data binding a sealed seed to its org and key
+fn sealing_context(org_id: Uuid, key_id: &str) -> Vec<u8> {
+    format!("org_signing_key:{}:{}", org_id, key_id).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+
+    #[tokio::test]
+    async fn test_org_keys_round_trip_through_sealed_storage() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pool = &state.db.writer;
+        let org_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (name) VALUES ('Sealed Key Org') RETURNING org_id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let master = [3u8; 32];
+        let now = Utc::now();
+
+        // Two instances meeting the org at once end up with one key
+        let (first, second) = (
+            OrgSigningKeys::new(MasterKey::from_bytes(&master)),
+            OrgSigningKeys::new(MasterKey::from_bytes(&master)),
+        );
+        let (a, b) = tokio::join!(
+            first.keypair(pool, org_id, now),
+            second.keypair(pool, org_id, now)
+        );
+        let (a, b) = (a.unwrap(), b.unwrap());
+        assert_eq!(a.key_id(), b.key_id());
+
+        let stored = queries::get_org_signing_key(pool, org_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.key_id, a.key_id());
+        assert_eq!(stored.public_key, a.public_key_bytes());
+        let seed = a.signing_key.to_bytes();
+        assert!(!stored
+            .sealed_private_key
+            .windows(seed.len())
+            .any(|w| w == seed));
+
+        // A fresh instance opens the stored seed and signs with it
+        let restarted = OrgSigningKeys::new(MasterKey::from_bytes(&master))
+            .keypair(pool, org_id, now)
+            .await
+            .unwrap();
+        assert_eq!(restarted.signing_key.to_bytes(), seed);
+        assert!(a.verify(b"receipt", &restarted.sign(b"receipt")));
+
+        // The wrong master key opens nothing, and generates nothing over it
+        let wrong = OrgSigningKeys::new(MasterKey::from_bytes(&[4u8; 32]));
+        assert!(wrong.keypair(pool, org_id, now).await.is_err());
+
+        // Nor does a seed moved to another org's row
+        let moved = OrgSigningKey {
+            org_id: Uuid::new_v4(),
+            ..stored
+        };
+        assert!(first.open(&moved).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+// Rotated key sweeper
+// Revokes rotated keys once their overlap ends. Authentication already
+// refuses them from that instant, so the sweeper only has to record the
+// revocation, effective at the end of the overlap, with its receipt.
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::{revoke_api_key_with_receipt, ROTATION_OVERLAP_ENDED};
+use crate::{
+    api::context::RequestContext,
+    clock::Clock,
+    config::Config,
+    crypto::{Ed25519Keypair, SigningKeyring},
+    db::queries,
+};
+
+/// Keys revoked per transaction
+const SWEEP_BATCH: i64 = 100;
+
+/// Revoke every key whose overlap ended by `now`, for one org or all of them
+pub async fn sweep_expired_api_keys(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    now: DateTime<Utc>,
+    org_id: Option<Uuid>,
+) -> Result<usize> {
+    let mut revoked = 0;
+
+    loop {
+        let mut tx = pool.begin().await?;
+        let expired = queries::lock_expired_api_keys(&mut *tx, now, org_id, SWEEP_BATCH).await?;
+
+        for api_key in &expired {
+            let revoked_at = api_key.expires_at.unwrap_or(now);
+            if revoke_api_key_with_receipt(
+                &mut tx,
+                keypair,
+                api_key,
+                ROTATION_OVERLAP_ENDED,
+                revoked_at,
+                &RequestContext::default(),
+                now,
+            )
+            .await?
+            .is_some()
+            {
+                revoked += 1;
+            }
+        }
+
+        tx.commit().await?;
+
+        if (expired.len() as i64) < SWEEP_BATCH {
+            return Ok(revoked);
+        }
+    }
+}
+
+/// Background loop: revoke rotated keys every configured interval
+pub async fn run_api_key_sweeper(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.api_key_sweep_interval_seconds.max(1),
+    ));
+
+    info!(
+        "API key sweeper running every {}s",
+        config.api_key_sweep_interval_seconds
+    );
+
+    loop {
+        interval.tick().await;
+
+        match sweep_expired_api_keys(&pool, &signing_keys.active(), clock.now(), None).await {
+            Ok(0) => {}
+            Ok(count) => info!("Revoked {} rotated API keys", count),
+            Err(e) => error!("API key sweep failed: {:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{auth::AuthContext, handlers::rotate_api_key, AppState},
+        clock::FixedClock,
+        keys::{key_is_valid, mint_api_key},
+        test_support::test_state,
+    };
+    use axum::extract::State;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_sweep_revokes_keys_whose_overlap_ended() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+
+        // Own org, so the sweep can't touch keys from other tests
+        let org_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO organizations (org_id, name) VALUES ($1, 'Key Sweep Test Org')")
+            .bind(org_id)
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+
+        // Rotated two days ago with the default one-day overlap
+        let rotated_at = Utc::now() - Duration::days(2);
+        let past = AppState {
+            clock: Arc::new(FixedClock(rotated_at)),
+            ..state.clone()
+        };
+        let old = mint_api_key(
+            &state.db.writer,
+            &state.keypair(),
+            org_id,
+            "sweep",
+            rotated_at,
+        )
+        .await
+        .unwrap();
+        let rotated = rotate_api_key(
+            State(past),
+            AuthContext {
+                org_id,
+                key_id: old.api_key.key_id,
+            },
+            RequestContext::default(),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("rotate failed"))
+        .0;
+
+        let now = Utc::now();
+        let revoked = sweep_expired_api_keys(&state.db.writer, &state.keypair(), now, Some(org_id))
+            .await
+            .unwrap();
+        assert_eq!(revoked, 1);
+
+        let keys = queries::list_api_keys(&state.db.writer, org_id)
+            .await
+            .unwrap();
+        let old_key = keys
+            .iter()
+            .find(|k| k.key_id == old.api_key.key_id)
+            .unwrap();
+        let new_key = keys.iter().find(|k| k.key_id == rotated.key_id).unwrap();
+        assert_eq!(
+            old_key.revoked_at, old_key.expires_at,
+            "revocation takes effect when the overlap ended"
+        );
+        assert!(key_is_valid(new_key, now));
+
+        // Issuance twice, the overlap and the revocation are on the chain
+        let (receipts, audited): (i64, i64) = sqlx::query_as(
+            "SELECT (SELECT COUNT(*) FROM org_receipts WHERE org_id = $1 AND kind LIKE 'api_key_%'), \
+                    (SELECT COUNT(*) FROM audit_log WHERE org_id = $1 AND event_type LIKE 'api_key_%')",
+        )
+        .bind(org_id)
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+        assert_eq!((receipts, audited), (4, 4));
+
+        // Nothing left to sweep
+        let again = sweep_expired_api_keys(&state.db.writer, &state.keypair(), now, Some(org_id))
+            .await
+            .unwrap();
+        assert_eq!(again, 0);
+    }
+}
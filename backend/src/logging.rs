@@ -0,0 +1,142 @@
+// Log output
+// LOG_FORMAT=pretty (the default) writes human-readable lines; json writes
+// one object per line for log aggregators, carrying the fields of the request
+// span (request_id, method, path, pointer_id, ...) on every event.
+use anyhow::{bail, Context, Result};
+use std::str::FromStr;
+use tracing::Subscriber;
+use tracing_subscriber::{fmt::MakeWriter, EnvFilter};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            other => bail!("unknown log format '{}'", other),
+        }
+    }
+}
+
+/// Install the global subscriber, formatted per LOG_FORMAT and filtered by
+/// RUST_LOG (default info). Runs before the rest of the configuration loads,
+/// so it reads .env itself.
+pub fn init() -> Result<()> {
+    dotenvy::dotenv().ok();
+    let format = std::env::var("LOG_FORMAT")
+        .unwrap_or_else(|_| "pretty".to_string())
+        .parse()
+        .context("LOG_FORMAT must be json or pretty")?;
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into());
+
+    tracing::subscriber::set_global_default(subscriber(format, filter, std::io::stdout))
+        .context("Failed to install the log subscriber")
+}
+
+pub fn subscriber<W>(
+    format: LogFormat,
+    filter: EnvFilter,
+    writer: W,
+) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer);
+    match format {
+        LogFormat::Pretty => Box::new(builder.finish()),
+        LogFormat::Json => Box::new(
+            builder
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .with_span_list(false)
+                .finish(),
+        ),
+    }
+}
+
+#[cfg(test)]
+pub mod capture {
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::fmt::MakeWriter;
+
+    /// Log output collected in memory, for asserting on what was written
+    #[derive(Clone, Default)]
+    pub struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl CapturedLogs {
+        pub fn lines(&self) -> Vec<String> {
+            let bytes = self.0.lock().unwrap();
+            String::from_utf8_lossy(&bytes)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        }
+    }
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'w> MakeWriter<'w> for CapturedLogs {
+        type Writer = CapturedLogs;
+
+        fn make_writer(&'w self) -> Self::Writer {
+            self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format_parses() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!(" Pretty ".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_json_lines_carry_span_fields() {
+        let logs = capture::CapturedLogs::default();
+        let subscriber = subscriber(LogFormat::Json, EnvFilter::new("info"), logs.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!(
+                "request",
+                request_id = "req-1",
+                pointer_id = tracing::field::Empty
+            );
+            let _entered = span.enter();
+            span.record("pointer_id", "p-1");
+            tracing::info!(attempt = 2, "Orphaning pointer");
+        });
+
+        let lines = logs.lines();
+        assert_eq!(lines.len(), 1);
+        let line: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(line["message"], "Orphaning pointer");
+        assert_eq!(line["attempt"], 2);
+        assert_eq!(line["span"]["request_id"], "req-1");
+        assert_eq!(line["span"]["pointer_id"], "p-1");
+    }
+}
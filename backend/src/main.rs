@@ -2,29 +2,49 @@
 // Patent-pending pointer orphaning system (US 19/240,581)
 // High-performance Rust implementation targeting <8ms latency
 
-use axum::{
-    routing::{get, post},
-    Router,
-};
-use std::net::SocketAddr;
-use tower_http::cors::{Any, CorsLayer};
-use tracing::{info, warn};
+use anyhow::Context;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tracing::info;
 
+mod analytics;
 mod api;
+mod auditor;
+mod checkpoints;
+mod clock;
 mod config;
 mod crypto;
 mod db;
 mod enforcement;
+mod gc;
+mod heartbeats;
+mod keys;
+mod logging;
+mod orphans;
+mod restrictions;
+mod retention;
+mod secret;
+mod subjects;
+mod webhooks;
+
+#[cfg(test)]
+mod test_support;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "info".into()),
-        )
-        .init();
+    logging::init()?;
+
+    // `gen-vectors [path]` writes the interop test vectors and exits
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("gen-vectors") {
+        let path = args
+            .get(2)
+            .map(String::as_str)
+            .unwrap_or(crypto::test_vectors::TEST_VECTORS_PATH);
+        std::fs::write(path, crypto::test_vectors::render_test_vectors()?)?;
+        info!("Wrote test vectors to {}", path);
+        return Ok(());
+    }
 
     info!("🚀 FinalBoss Veto Frontier Backend starting...");
 
@@ -32,55 +52,330 @@ async fn main() -> anyhow::Result<()> {
     let config = config::Config::from_env()?;
     info!("✓ Configuration loaded");
 
+    // `mint-key <label> [org_id]` prints a new API key for the org (default
+    // DEFAULT_ORG_ID); `revoke-key <key_id>` revokes one. Both then exit.
+    match args.get(1).map(String::as_str) {
+        Some("mint-key") => {
+            let label = args.get(2).context("usage: mint-key <label> [org_id]")?;
+            let org_id = match args.get(3) {
+                Some(org_id) => org_id.parse().context("org_id must be a UUID")?,
+                None => config.default_org_id,
+            };
+            let pool = db::create_pool(
+                config.database_url.expose(),
+                &config.database_pool.with_max_connections(1),
+                "admin",
+            )
+            .await?;
+            let keypair = crypto::ed25519::load_or_generate_keypair(&config)?;
+            let issued =
+                keys::mint_api_key(&pool, &keypair, org_id, label, chrono::Utc::now()).await?;
+            info!(
+                "Minted API key {} for org {}",
+                issued.api_key.key_id, org_id
+            );
+            println!("{}", issued.key);
+            return Ok(());
+        }
+        Some("revoke-key") => {
+            let key_id = args
+                .get(2)
+                .context("usage: revoke-key <key_id>")?
+                .parse()
+                .context("key_id must be a UUID")?;
+            let pool = db::create_pool(
+                config.database_url.expose(),
+                &config.database_pool.with_max_connections(1),
+                "admin",
+            )
+            .await?;
+            let keypair = crypto::ed25519::load_or_generate_keypair(&config)?;
+            match keys::revoke_api_key(&pool, &keypair, key_id, chrono::Utc::now()).await? {
+                Some(revoked) => info!(
+                    "Revoked API key {} of org {}",
+                    key_id, revoked.api_key.org_id
+                ),
+                None => anyhow::bail!("No active API key {}", key_id),
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
     // Initialize database connection pool
-    let db_pool = db::create_pool(&config.database_url).await?;
-    info!("✓ Database connection pool created");
+    let database_url = config.database_url.expose();
+    let db = db::Db::connect(&config).await?;
+    let background_pool = db::create_pool(
+        database_url,
+        &config
+            .database_pool
+            .with_max_connections(config.background_pool_size),
+        "background",
+    )
+    .await?;
+    info!("✓ Database connection pools created");
+    if config.database_read_url.is_some() {
+        info!("Read-only requests go to DATABASE_READ_URL");
+    }
+
+    // Bring the schema up to date, or with RUN_MIGRATIONS=false (schema
+    // managed externally) just refuse one newer than this build
+    if config.run_migrations {
+        let version = db::migrations::run_migrations(&db.writer).await?;
+        info!("✓ Database migrations applied (schema version {})", version);
+    } else {
+        db::migrations::ensure_not_ahead(&db.writer).await?;
+        info!("✓ Database migrations skipped (RUN_MIGRATIONS=false)");
+    }
+
+    // Initialize signing keys: the configured signer, plus every key it
+    // replaced so receipts they signed still verify
+    let signing_keys = Arc::new(crypto::SigningKeyring::new(
+        crypto::ed25519::load_or_generate_keypair(&config)?,
+    ));
+    keys::register_signing_keys(&db.writer, &signing_keys, chrono::Utc::now()).await?;
+    info!(
+        "✓ Cryptographic keypair loaded (key_id {})",
+        signing_keys.active().key_id()
+    );
+    let org_signing_keys = keys::OrgSigningKeys::from_config(&config)?.map(Arc::new);
+    if org_signing_keys.is_none() {
+        info!("SIGNING_MASTER_KEY not set; the service key signs receipts for every org");
+    }
+    let payload_cipher = crypto::PayloadCipher::from_config(&config)?.map(Arc::new);
+    if payload_cipher.is_some() {
+        info!("✓ Payloads are encrypted at rest (PAYLOAD_ENCRYPTION_KEY)");
+    } else {
+        info!("PAYLOAD_ENCRYPTION_KEY not set; payloads are stored as clients send them");
+    }
+
+    // Workers and handlers read time from the same clock
+    let clock: Arc<dyn clock::Clock> = Arc::new(clock::SystemClock);
+
+    // Start resolve analytics aggregation and spike detection
+    tokio::spawn(analytics::run_resolve_stats_worker(
+        background_pool.clone(),
+        config.clone(),
+        clock.clone(),
+    ));
+
+    // Lift processing restrictions once their window ends
+    tokio::spawn(restrictions::run_restriction_sweeper(
+        background_pool.clone(),
+        signing_keys.clone(),
+        config.clone(),
+        clock.clone(),
+    ));
+
+    // Pointer state resolve reads before locking; every write on this
+    // instance evicts what it changed, the background workers' included
+    let pointer_cache = Arc::new(db::PointerCache::new(
+        Duration::from_millis(config.pointer_cache_ttl_ms),
+        config.pointer_cache_max_entries,
+    ));
+
+    // Sample pointers and compare status against the receipt chain
+    tokio::spawn(auditor::run_consistency_auditor(
+        background_pool.clone(),
+        signing_keys.clone(),
+        config.clone(),
+        pointer_cache.clone(),
+        clock.clone(),
+    ));
+
+    // Finalize vetoes scheduled with a future effective time once they fall due
+    tokio::spawn(orphans::run_pending_orphan_sweeper(
+        background_pool.clone(),
+        signing_keys.clone(),
+        config.clone(),
+        clock.clone(),
+        pointer_cache.clone(),
+    ));
+
+    // Crypto-shred payloads of pointers orphaned longer than the purge window
+    tokio::spawn(orphans::run_payload_purger(
+        background_pool.clone(),
+        signing_keys.clone(),
+        config.clone(),
+        clock.clone(),
+    ));
+
+    // Orphan pointers and drop payloads past their retention class maximum
+    tokio::spawn(retention::run_retention_sweeper(
+        background_pool.clone(),
+        signing_keys.clone(),
+        config.clone(),
+        clock.clone(),
+        pointer_cache.clone(),
+    ));
+
+    // Collect data rows no pointer has referenced for the grace period
+    tokio::spawn(gc::run_data_gc_worker(
+        background_pool.clone(),
+        signing_keys.clone(),
+        config.clone(),
+        clock.clone(),
+    ));
+
+    // Resolve serves last-known status while the database circuit is open;
+    // the receipts those serves are owed are written once it closes
+    let db_circuit = Arc::new(db::CircuitBreaker::new(
+        config.db_circuit_failure_threshold,
+        Duration::from_secs(config.db_circuit_open_seconds),
+    ));
+    let last_known = Arc::new(enforcement::LastKnownStatuses::new(
+        chrono::Duration::seconds(config.degraded_read_max_staleness_seconds),
+    ));
+    tokio::spawn(enforcement::run_degraded_backfill(
+        background_pool.clone(),
+        signing_keys.clone(),
+        last_known.clone(),
+        db_circuit.clone(),
+        clock.clone(),
+    ));
+
+    // Revoke rotated API keys once their overlap ends
+    tokio::spawn(keys::run_api_key_sweeper(
+        background_pool.clone(),
+        signing_keys.clone(),
+        config.clone(),
+        clock.clone(),
+    ));
+
+    // Signed per-org liveness heartbeats
+    tokio::spawn(heartbeats::run_heartbeat_emitter(
+        background_pool.clone(),
+        signing_keys.clone(),
+        config.clone(),
+        clock.clone(),
+    ));
+
+    // Commit new receipts to signed Merkle roots
+    tokio::spawn(checkpoints::run_checkpoint_writer(
+        background_pool.clone(),
+        signing_keys.clone(),
+        config.clone(),
+        clock.clone(),
+    ));
 
-    // Run migrations (optional - schema should be pre-initialized)
-    // sqlx::migrate!("../database/migrations").run(&db_pool).await?;
+    // Deliver queued orphan events to registered webhooks
+    let webhook_wakeup = Arc::new(tokio::sync::Notify::new());
+    tokio::spawn(webhooks::run_webhook_dispatcher(
+        background_pool,
+        config.clone(),
+        clock.clone(),
+        webhook_wakeup.clone(),
+    ));
 
-    // Initialize signing keypair
-    let keypair = crypto::ed25519::load_or_generate_keypair(&config)?;
-    info!("✓ Cryptographic keypair loaded");
+    // Per-client request limits, with idle clients forgotten
+    let rate_limiter = Arc::new(api::rate_limit::RateLimiter::new(
+        config.rate_limit_reads_per_minute,
+        config.rate_limit_writes_per_minute,
+    ));
+    tokio::spawn(api::rate_limit::run_rate_limit_eviction(
+        rate_limiter.clone(),
+        clock.clone(),
+    ));
 
     // Build application state
     let app_state = api::AppState {
-        db_pool: db_pool.clone(),
-        keypair,
+        db: db.clone(),
+        signing_keys,
+        org_signing_keys,
         config: config.clone(),
+        clock,
+        resolve_flights: Arc::new(db::SingleFlight::new(Duration::from_millis(
+            config.resolve_coalesce_max_staleness_ms,
+        ))),
+        pointer_cache,
+        receipt_verifier: Arc::new(crypto::ReceiptVerifier::new()),
+        db_circuit,
+        last_known,
+        receipt_metrics: Arc::new(analytics::ReceiptPhaseMetrics::new(
+            config.receipt_metrics_enabled,
+            Duration::from_millis(config.slow_receipt_threshold_ms),
+        )),
+        api_keys: Arc::new(api::auth::KnownApiKeys::new(chrono::Duration::seconds(
+            config.degraded_read_max_staleness_seconds,
+        ))),
+        webhook_wakeup,
+        rate_limiter,
+        compression_metrics: Arc::new(api::compression::CompressionMetrics::new()),
+        payload_cipher,
     };
 
-    // Configure CORS
-    let cors = CorsLayer::new()
-        .allow_origin(Any) // TODO: Restrict to config.cors_allowed_origins in production
-        .allow_methods(Any)
-        .allow_headers(Any);
-
-    // Build router
-    let app = Router::new()
-        .route("/health", get(api::handlers::health_check))
-        .route("/api/pointer/create", post(api::handlers::create_pointer))
-        .route("/api/pointer/resolve/:id", get(api::handlers::resolve_pointer))
-        .route("/api/pointer/orphan", post(api::handlers::orphan_pointer))
-        .route("/api/receipts/:pointer_id", get(api::handlers::get_receipts))
-        .route("/api/audit/:subject_id", get(api::handlers::get_audit_trail))
-        .layer(cors)
-        .with_state(app_state);
+    let app = api::routes::build_app(app_state);
 
     // Start server
-    let addr = SocketAddr::from(([127, 0, 0, 1], config.port));
+    let addr = config.listen_addr().await?;
     info!("🌐 Server listening on http://{}", addr);
     info!("✓ Ready to handle requests");
+    info!("   GET  /health/live          - Liveness");
+    info!("   GET  /health/ready         - Readiness: database and signing key");
     info!("   POST /api/pointer/create   - Create new pointer");
     info!("   GET  /api/pointer/resolve/:id - Resolve pointer");
+    info!("   GET  /api/pointer/by-ref/:ref - Resolve pointer by external reference");
     info!("   POST /api/pointer/orphan    - Orphan pointer (VETO)");
+    info!("   POST /api/pointer/orphan/cancel - Cancel a scheduled orphan");
+    info!("   POST /api/pointer/orphan/request - Request a dual-approval orphan");
+    info!("   POST /api/pointer/orphan/approve - Approve another actor's orphan request");
+    info!("   GET  /api/pointer/:id/orphan-requests - List a pointer's orphan requests");
+    info!("   POST /api/pointer/reinstate - Reinstate a pointer orphaned in error");
+    info!("   POST /api/pointer/:id/hold - Place a legal hold");
+    info!("   DELETE /api/pointer/:id/hold - Release a legal hold");
+    info!("   POST /api/pointer/:id/purge - Purge an orphaned pointer's payload");
+    info!("   POST /api/pointer/orphan-group - Orphan pointer group (atomic VETO)");
+    info!("   POST /api/pointer/:id/swap-data - Repoint pointer at corrected data");
+    info!("   GET  /api/orphan-groups/:id - Get orphan group manifest");
+    info!("   GET  /api/orgs/:id/heartbeats - Org liveness heartbeats");
+    info!("   GET  /api/orgs/:id/stats  - Org pointer, receipt and veto aggregates");
+    info!("   GET  /api/pointer/:id/access-stats - Get hourly resolve counts");
+    info!("   GET  /api/stats/resolve-coalescing - Coalesced resolve counters");
+    info!("   GET  /api/admin/slo         - Per-org daily veto latency compliance");
+    info!("   GET  /api/admin/retention   - Upcoming forced expirations per retention class");
+    info!("   GET  /metrics               - OpenMetrics veto SLO gauges");
+    info!("   POST /api/subject/:id/orphan-all - Orphan all of a subject's pointers (VETO)");
+    info!("   POST /api/subject/:id/restrict - Restrict processing (Art. 18)");
+    info!("   POST /api/subject/:id/unrestrict - Lift processing restriction");
+    info!("   GET  /api/subject/:id/export - Download everything held on a subject (Art. 15)");
+    info!("   GET  /api/subjects/duplicates - Near-duplicate subject report");
+    info!("   POST /api/subjects/merge    - Merge subjects into canonical form");
+    info!("   GET  /api/consistency/pointer/:id - Check status against receipt chain");
+    info!("   GET  /api/consistency/subject/:id - Check a subject's pointers");
+    info!("   POST /api/admin/pointer/:id/freeze - Freeze a pointer's receipt chain");
+    info!("   POST /api/admin/pointer/:id/unfreeze - Clear a chain freeze with its outcome");
     info!("   GET  /api/receipts/:id      - Get governance receipts");
-    info!("   GET  /api/audit/:subject    - Get audit trail");
+    info!("   GET  /api/receipts/:id/verify - Verify a pointer's receipt chain");
+    info!("   POST /api/receipts/verify   - Verify a receipt handed over out of band");
+    info!("   GET  /api/receipts/:hash/proof - Merkle inclusion proof for a receipt");
+    info!("   GET  /api/receipts/by-hash/:hash - Look up a receipt by hash or hash prefix");
+    info!("   GET  /api/checkpoints       - Signed receipt checkpoints");
+    info!("   GET  /api/audit/:subject    - Get audit trail (?format=csv|jsonl to download)");
+    info!("   GET  /api/pointers          - List pointers with filters");
+    info!("   GET  /api/pointers/changes  - Pointer changes since cursor");
+    info!("   GET  /api/meta/test-vectors - Receipt signing interop vectors");
+    info!("   GET  /api/keys/public       - Receipt verification key (JWKS)");
+    info!("   GET  /api/admin/signing-keys - Receipt signing keys, active and retired");
+    info!("   POST /api/admin/signing-keys/rotate - Retire the receipt signing key for a new one");
+    info!("   GET  /api/keys              - The org's API keys and validity windows");
+    info!("   POST /api/keys/rotate       - Replace the calling key, with an overlap");
+    info!("   POST /api/keys/:id/revoke   - Revoke an API key now");
+    info!("   POST /api/webhooks          - Register a webhook for orphan events");
+    info!("   GET  /api/webhooks          - List the org's webhooks");
+    info!("   GET  /api/webhooks/:id      - Get a webhook");
+    info!("   PATCH /api/webhooks/:id     - Update a webhook's URL, events or state");
+    info!("   DELETE /api/webhooks/:id    - Delete a webhook");
+    info!("   GET  /api/webhooks/:id/deliveries - Delivery log with attempts");
     info!("");
     info!("Patent: US 19/240,581 Claim 9 - Pointer orphaning with data preservation");
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // Peer addresses feed the audit log's ip_address
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -0,0 +1,9 @@
+// Scheduled orphaning module
+pub mod purge;
+pub mod sweeper;
+
+pub use purge::*;
+pub use sweeper::*;
+
+/// Reason recorded when a veto doesn't give one
+pub const DEFAULT_ORPHAN_REASON: &str = "user_consent_revoked";
@@ -0,0 +1,382 @@
+// Crypto-shredding of orphaned payloads
+// Orphaning blocks resolution but leaves the ciphertext in data_store. Once
+// a pointer has been orphaned for PAYLOAD_PURGE_AFTER_DAYS its payload is
+// destroyed; the row and content_hash stay so the receipt chain still
+// verifies, and a Purge receipt records what was destroyed.
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use std::sync::Arc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    clock::Clock,
+    config::Config,
+    crypto::{Ed25519Keypair, ReceiptData, SignedReceipt, SigningKeyring},
+    db::{
+        models::{Pointer, ReceiptOperation},
+        queries,
+    },
+};
+
+/// Orphaned pointers purged per transaction
+const PURGE_BATCH: i64 = 100;
+
+#[derive(Debug, Clone)]
+pub struct PurgedPayload {
+    pub pointer_id: Uuid,
+    pub data_id: Uuid,
+    pub content_hash: String,
+    pub payload_bytes: i32,
+    pub receipt: SignedReceipt,
+    pub receipt_timestamp: DateTime<Utc>,
+}
+
+/// How long a pointer must have been orphaned before its payload goes
+pub fn purge_window(config: &Config) -> Duration {
+    Duration::days(config.payload_purge_after_days as i64)
+}
+
+/// Destroy the payload behind one locked, orphaned pointer inside the
+/// caller's transaction, chaining a Purge receipt and a `payload_purged`
+/// audit event. Returns None if there was nothing to purge: no payload, a
+/// held row, or a row another live pointer still references.
+pub async fn purge_payload_with_receipt(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    pointer: &Pointer,
+    trigger: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<PurgedPayload>> {
+    let Some((content_hash, payload_bytes)) =
+        queries::purge_orphaned_payload(&mut *conn, pointer.data_id, now).await?
+    else {
+        return Ok(None);
+    };
+
+    let prev_hash = queries::get_latest_receipt_hash(&mut *conn, pointer.pointer_id).await?;
+    let receipt_data = ReceiptData::new(
+        pointer.pointer_id,
+        ReceiptOperation::Purge,
+        pointer.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "data_id": pointer.data_id,
+            "content_hash": content_hash,
+            "payload_bytes": payload_bytes,
+            "orphaned_at": pointer.orphaned_at,
+            "trigger": trigger,
+        }),
+        now,
+    );
+    let signed_receipt = receipt_data.sign(keypair)?;
+
+    queries::create_governance_receipt(
+        &mut *conn,
+        pointer.pointer_id,
+        pointer.org_id,
+        ReceiptOperation::Purge,
+        signed_receipt.receipt_json.clone(),
+        &signed_receipt.receipt_hash,
+        &signed_receipt.signature,
+        &signed_receipt.signature_algorithm,
+        &signed_receipt.key_id,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    queries::create_audit_log(
+        &mut *conn,
+        Some(pointer.org_id),
+        Some(pointer.pointer_id),
+        None,
+        "payload_purged",
+        json!({
+            "subject_id": pointer.subject_id,
+            "data_id": pointer.data_id,
+            "content_hash": content_hash,
+            "payload_bytes": payload_bytes,
+            "trigger": trigger,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(Some(PurgedPayload {
+        pointer_id: pointer.pointer_id,
+        data_id: pointer.data_id,
+        content_hash,
+        payload_bytes,
+        receipt: signed_receipt,
+        receipt_timestamp: receipt_data.timestamp,
+    }))
+}
+
+/// Purge every payload whose pointer was orphaned before `orphaned_before`,
+/// in batches
+pub async fn purge_orphaned_payloads(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    orphaned_before: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let mut purged = 0;
+
+    loop {
+        let mut tx = pool.begin().await?;
+        let due = queries::lock_purgeable_orphans(&mut *tx, orphaned_before, PURGE_BATCH).await?;
+
+        for pointer in &due {
+            if purge_payload_with_receipt(&mut tx, keypair, pointer, "retention", now)
+                .await?
+                .is_some()
+            {
+                purged += 1;
+            }
+        }
+
+        tx.commit().await?;
+
+        if (due.len() as i64) < PURGE_BATCH {
+            return Ok(purged);
+        }
+    }
+}
+
+/// Background loop: purge payloads past the window every configured interval
+pub async fn run_payload_purger(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.payload_purge_interval_seconds.max(1),
+    ));
+
+    info!(
+        "Payload purger running every {}s (purging {} days after orphaning)",
+        config.payload_purge_interval_seconds, config.payload_purge_after_days
+    );
+
+    loop {
+        interval.tick().await;
+
+        let now = clock.now();
+        match purge_orphaned_payloads(
+            &pool,
+            &signing_keys.active(),
+            now - purge_window(&config),
+            now,
+        )
+        .await
+        {
+            Ok(0) => {}
+            Ok(count) => info!("Purged {} orphaned payloads", count),
+            Err(e) => error!("Payload purge failed: {:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            context::RequestContext,
+            handlers::{
+                create_pointer, orphan_pointer, purge_pointer_payload, resolve_pointer,
+                CreatePointerRequest, OrphanPointerRequest,
+            },
+            idempotency::IdempotencyKey,
+            ApiError, AppState,
+        },
+        clock::FixedClock,
+        crypto::{hashing::sha3_512_hash, verify_chain},
+        keys::receipt_keys,
+        test_support::{test_auth, test_state, unique_subject},
+    };
+    use axum::{
+        extract::{Path, Query, State},
+        Json,
+    };
+
+    /// Create a pointer with a payload and orphan it `days_ago`
+    async fn orphaned_with_payload(state: &AppState, days_ago: i64) -> Uuid {
+        let payload = b"subject biometric template";
+        let (_, Json(created)) = create_pointer(
+            State(state.clone()),
+            test_auth(state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(CreatePointerRequest {
+                subject_id: unique_subject("shred"),
+                content_hash: sha3_512_hash(payload),
+                encrypted_payload: Some(data_encoding::BASE64.encode(payload)),
+                payload_encoding: None,
+                retention_class: None,
+                expires_at: None,
+                external_ref: None,
+                requires_dual_approval: false,
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("create failed"));
+
+        let then = AppState {
+            clock: Arc::new(FixedClock(Utc::now() - Duration::days(days_ago))),
+            ..state.clone()
+        };
+        let orphaned = orphan_pointer(
+            State(then.clone()),
+            test_auth(&then),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id: created.pointer_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+
+        created.pointer_id
+    }
+
+    #[tokio::test]
+    async fn test_purger_shreds_payload_but_keeps_chain() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = orphaned_with_payload(&state, 31).await;
+        let recent = orphaned_with_payload(&state, 1).await;
+
+        let now = Utc::now();
+        let purged = purge_orphaned_payloads(
+            &state.db.writer,
+            &state.keypair(),
+            now - purge_window(&state.config),
+            now,
+        )
+        .await
+        .unwrap();
+        assert!(purged >= 1);
+
+        let pointer = queries::get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let data = queries::get_data_store(&state.db.writer, pointer.data_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(data.encrypted_payload.is_none());
+        assert!(data.payload_purged_at.is_some());
+        assert_eq!(
+            data.content_hash,
+            sha3_512_hash(b"subject biometric template")
+        );
+
+        // Inside the window the payload stays
+        let recent = queries::get_pointer(&state.db.writer, recent)
+            .await
+            .unwrap()
+            .unwrap();
+        let recent_data = queries::get_data_store(&state.db.writer, recent.data_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(recent_data.encrypted_payload.is_some());
+
+        let receipts = queries::get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let purge = receipts.last().unwrap();
+        assert!(matches!(purge.operation, ReceiptOperation::Purge));
+        assert_eq!(
+            purge.receipt_json["metadata"]["content_hash"],
+            data.content_hash
+        );
+        // Created with the org's key, purged with the service key
+        let keys = receipt_keys(&state.db.writer, &state.signing_keys, &receipts)
+            .await
+            .unwrap();
+        assert!(verify_chain(&receipts, &keys).chain_valid);
+
+        // Still the orphan denial, not missing data
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(resolved, Err(ApiError::PointerOrphaned(_))));
+    }
+
+    #[tokio::test]
+    async fn test_purge_endpoint_requires_an_aged_orphan() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = orphaned_with_payload(&state, 1).await;
+
+        let early =
+            purge_pointer_payload(State(state.clone()), test_auth(&state), Path(pointer_id)).await;
+        assert!(matches!(
+            early,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "purge_window_open"
+        ));
+
+        let later = AppState {
+            clock: Arc::new(FixedClock(Utc::now() + Duration::days(30))),
+            ..state.clone()
+        };
+        let Json(purged) =
+            purge_pointer_payload(State(later.clone()), test_auth(&later), Path(pointer_id))
+                .await
+                .unwrap_or_else(|_| panic!("purge failed"));
+        assert_eq!(purged.payload_bytes, 26);
+
+        let again =
+            purge_pointer_payload(State(later.clone()), test_auth(&later), Path(pointer_id)).await;
+        assert!(matches!(
+            again,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "no_payload"
+        ));
+
+        // An active pointer is refused outright
+        let (_, Json(active)) = create_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(CreatePointerRequest {
+                subject_id: unique_subject("shred_active"),
+                content_hash: "ab".repeat(64),
+                encrypted_payload: None,
+                payload_encoding: None,
+                retention_class: None,
+                expires_at: None,
+                external_ref: None,
+                requires_dual_approval: false,
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("create failed"));
+        let refused = purge_pointer_payload(
+            State(later.clone()),
+            test_auth(&later),
+            Path(active.pointer_id),
+        )
+        .await;
+        assert!(matches!(
+            refused,
+            Err(ApiError::Conflict { ref conflict_type, .. }) if conflict_type == "pointer_not_orphaned"
+        ));
+    }
+}
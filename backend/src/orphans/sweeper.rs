@@ -0,0 +1,292 @@
+// Pending orphan sweeper
+// A veto with a future effective time leaves the pointer pending_orphan.
+// Resolve already denies it once that time has passed, so the sweeper only
+// has to move the status and write the orphan receipt the veto is owed.
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use std::sync::Arc;
+use tracing::{error, info};
+
+use super::DEFAULT_ORPHAN_REASON;
+use crate::{
+    clock::Clock,
+    config::Config,
+    crypto::{Ed25519Keypair, ReceiptData, SigningKeyring},
+    db::{
+        models::{Pointer, ReceiptOperation},
+        queries, PointerCache,
+    },
+    webhooks,
+};
+
+/// Pending orphans finalized per transaction
+const SWEEP_BATCH: i64 = 100;
+
+/// Orphan every pending orphan due at `now`, in batches. Finalized pointers
+/// are evicted from `pointer_cache` as each batch commits.
+pub async fn finalize_due_orphans(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    pointer_cache: &PointerCache,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let mut finalized = 0;
+
+    loop {
+        let mut tx = pool.begin().await?;
+        let due = queries::lock_due_pending_orphans(&mut *tx, now, SWEEP_BATCH).await?;
+
+        let mut orphaned = Vec::new();
+        for pending in &due {
+            if finalize_with_receipt(&mut tx, keypair, pending, now).await? {
+                orphaned.push(pending.pointer_id);
+            }
+        }
+
+        tx.commit().await?;
+        for pointer_id in &orphaned {
+            pointer_cache.evict(pointer_id);
+        }
+        finalized += orphaned.len();
+
+        if (due.len() as i64) < SWEEP_BATCH {
+            return Ok(finalized);
+        }
+    }
+}
+
+/// Orphan one locked pending orphan inside the caller's transaction, as of
+/// its effective time, chaining the Orphan receipt its veto was scheduled for
+async fn finalize_with_receipt(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    pending: &Pointer,
+    now: DateTime<Utc>,
+) -> Result<bool> {
+    let effective_at = pending.orphan_effective_at.unwrap_or(now);
+    let Some(orphaned) = queries::orphan_pointer(
+        &mut *conn,
+        pending.pointer_id,
+        pending.orphan_reason.as_deref(),
+        effective_at,
+    )
+    .await?
+    else {
+        return Ok(false);
+    };
+
+    let reason = orphaned
+        .orphan_reason
+        .clone()
+        .unwrap_or_else(|| DEFAULT_ORPHAN_REASON.to_string());
+
+    // A veto supersedes any processing restriction
+    let lifted =
+        queries::lift_open_restriction(&mut *conn, orphaned.pointer_id, "orphaned").await?;
+
+    let prev_hash = queries::get_latest_receipt_hash(&mut *conn, orphaned.pointer_id).await?;
+    let mut metadata = json!({
+        "reason": reason,
+        "orphaned_at": orphaned.orphaned_at,
+        "scheduled": true,
+        "finalized_at": now,
+    });
+    if let Some(lifted) = lifted {
+        metadata["lifted_restriction_id"] = json!(lifted.restriction_id);
+    }
+
+    let receipt_data = ReceiptData::new(
+        orphaned.pointer_id,
+        ReceiptOperation::Orphan,
+        orphaned.subject_id.clone(),
+        prev_hash.clone(),
+        metadata,
+        now,
+    );
+    let signed_receipt = receipt_data.sign(keypair)?;
+
+    queries::create_governance_receipt(
+        &mut *conn,
+        orphaned.pointer_id,
+        orphaned.org_id,
+        ReceiptOperation::Orphan,
+        signed_receipt.receipt_json.clone(),
+        &signed_receipt.receipt_hash,
+        &signed_receipt.signature,
+        &signed_receipt.signature_algorithm,
+        &signed_receipt.key_id,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    queries::create_audit_log(
+        &mut *conn,
+        Some(orphaned.org_id),
+        Some(orphaned.pointer_id),
+        None,
+        "pointer_orphaned",
+        json!({
+            "subject_id": orphaned.subject_id,
+            "reason": orphaned.orphan_reason,
+            "scheduled": true,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    webhooks::enqueue_webhook_event(
+        &mut *conn,
+        orphaned.org_id,
+        orphaned.pointer_id,
+        "pointer_orphaned",
+        &signed_receipt,
+        now,
+    )
+    .await?;
+
+    Ok(true)
+}
+
+/// Background loop: finalize due pending orphans every configured interval
+pub async fn run_pending_orphan_sweeper(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+    pointer_cache: Arc<PointerCache>,
+) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.pending_orphan_sweep_interval_seconds.max(1),
+    ));
+
+    info!(
+        "Pending orphan sweeper running every {}s",
+        config.pending_orphan_sweep_interval_seconds
+    );
+
+    loop {
+        interval.tick().await;
+
+        match finalize_due_orphans(&pool, &signing_keys.active(), &pointer_cache, clock.now()).await
+        {
+            Ok(0) => {}
+            Ok(count) => info!("Finalized {} scheduled orphans", count),
+            Err(e) => error!("Pending orphan sweep failed: {:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            context::RequestContext,
+            handlers::{
+                cancel_orphan, orphan_pointer, resolve_pointer, CancelOrphanRequest,
+                OrphanPointerRequest,
+            },
+            ApiError, AppState,
+        },
+        clock::FixedClock,
+        db::models::PointerStatus,
+        test_support::{seed_pointer, test_auth, test_state, unique_subject},
+    };
+    use axum::{
+        extract::{Path, Query, State},
+        Json,
+    };
+    use chrono::SubsecRound;
+
+    #[tokio::test]
+    async fn test_due_pending_orphan_is_finalized() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let pointer_id = seed_pointer(&state, &unique_subject("pending")).await;
+        let effective_at = Utc::now().trunc_subsecs(6) + chrono::Duration::hours(1);
+
+        let scheduled = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id,
+                reason: Some("pending_cleanup".to_string()),
+                effective_at: Some(effective_at),
+            }),
+        )
+        .await;
+        assert!(scheduled.is_ok(), "schedule failed");
+
+        // Not due yet: nothing to finalize
+        finalize_due_orphans(
+            &state.db.writer,
+            &state.keypair(),
+            &state.pointer_cache,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        let pointer = queries::get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(pointer.status, PointerStatus::PendingOrphan));
+
+        // Once due, resolve denies before the sweeper runs and cancel is refused
+        let later = AppState {
+            clock: Arc::new(FixedClock(effective_at + chrono::Duration::hours(1))),
+            ..state.clone()
+        };
+        let resolved = resolve_pointer(
+            State(later.clone()),
+            test_auth(&later),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(resolved, Err(ApiError::PointerOrphaned(_))));
+        let cancelled = cancel_orphan(
+            State(later.clone()),
+            test_auth(&later),
+            RequestContext::default(),
+            Json(CancelOrphanRequest {
+                pointer_id,
+                reason: None,
+            }),
+        )
+        .await;
+        assert!(matches!(cancelled, Err(ApiError::Conflict { .. })));
+
+        let finalized = finalize_due_orphans(
+            &state.db.writer,
+            &state.keypair(),
+            &state.pointer_cache,
+            later.clock.now(),
+        )
+        .await
+        .unwrap();
+        assert!(finalized >= 1);
+
+        let pointer = queries::get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(pointer.status, PointerStatus::Orphaned));
+        assert_eq!(pointer.orphaned_at, Some(effective_at));
+        assert_eq!(pointer.orphan_reason.as_deref(), Some("pending_cleanup"));
+
+        let receipts = queries::get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let orphan = receipts.last().unwrap();
+        assert!(matches!(orphan.operation, ReceiptOperation::Orphan));
+        assert_eq!(orphan.receipt_json["metadata"]["reason"], "pending_cleanup");
+        assert_eq!(orphan.receipt_json["metadata"]["scheduled"], true);
+    }
+}
@@ -0,0 +1,91 @@
+// Lifting processing restrictions
+// Every lift, manual or by expiry, leaves an Unrestrict receipt on the chain
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::PgConnection;
+
+use crate::{
+    crypto::{Ed25519Keypair, ReceiptData, SignedReceipt},
+    db::{
+        models::{PointerRestriction, ReceiptOperation},
+        queries,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct LiftedRestriction {
+    pub restriction: PointerRestriction,
+    pub receipt: SignedReceipt,
+    pub receipt_timestamp: DateTime<Utc>,
+}
+
+/// Lift a pointer's open restriction inside the caller's transaction,
+/// chaining an Unrestrict receipt and a `pointer_unrestricted` audit event.
+/// Returns None if the pointer had no open restriction.
+pub async fn lift_with_receipt(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    restriction: &PointerRestriction,
+    lift_reason: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<LiftedRestriction>> {
+    let Some(lifted) =
+        queries::lift_open_restriction(&mut *conn, restriction.pointer_id, lift_reason).await?
+    else {
+        return Ok(None);
+    };
+
+    let prev_hash = queries::get_latest_receipt_hash(&mut *conn, lifted.pointer_id).await?;
+
+    let receipt_data = ReceiptData::new(
+        lifted.pointer_id,
+        ReceiptOperation::Unrestrict,
+        lifted.subject_id.clone(),
+        prev_hash.clone(),
+        json!({
+            "restriction_id": lifted.restriction_id,
+            "lift_reason": lift_reason,
+            "expires_at": lifted.expires_at,
+        }),
+        now,
+    );
+    let signed_receipt = receipt_data.sign(keypair)?;
+
+    queries::create_governance_receipt(
+        &mut *conn,
+        lifted.pointer_id,
+        lifted.org_id,
+        ReceiptOperation::Unrestrict,
+        signed_receipt.receipt_json.clone(),
+        &signed_receipt.receipt_hash,
+        &signed_receipt.signature,
+        &signed_receipt.signature_algorithm,
+        &signed_receipt.key_id,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    queries::create_audit_log(
+        &mut *conn,
+        Some(lifted.org_id),
+        Some(lifted.pointer_id),
+        None,
+        "pointer_unrestricted",
+        json!({
+            "subject_id": lifted.subject_id,
+            "restriction_id": lifted.restriction_id,
+            "lift_reason": lift_reason,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(Some(LiftedRestriction {
+        restriction: lifted,
+        receipt: signed_receipt,
+        receipt_timestamp: receipt_data.timestamp,
+    }))
+}
@@ -0,0 +1,6 @@
+// Processing restriction module
+pub mod lift;
+pub mod sweeper;
+
+pub use lift::*;
+pub use sweeper::*;
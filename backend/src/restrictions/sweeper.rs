@@ -0,0 +1,159 @@
+// Expired restriction sweeper
+// Lifts restrictions whose window has passed; resolve already ignores them,
+// so the sweeper only has to keep the table and receipt chain accurate
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::lift_with_receipt;
+use crate::{
+    clock::Clock,
+    config::Config,
+    crypto::{Ed25519Keypair, SigningKeyring},
+    db::queries,
+};
+
+/// Restrictions lifted per transaction
+const SWEEP_BATCH: i64 = 100;
+
+/// Lift every restriction expired at `now`, in batches
+pub async fn sweep_expired_restrictions(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    sweep_expired_restrictions_of(pool, keypair, now, None).await
+}
+
+/// Sweep only the given pointers' restrictions, or all of them for None
+pub async fn sweep_expired_restrictions_of(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    now: DateTime<Utc>,
+    pointer_ids: Option<&[Uuid]>,
+) -> Result<usize> {
+    let mut lifted = 0;
+
+    loop {
+        let mut tx = pool.begin().await?;
+        let expired =
+            queries::lock_expired_restrictions(&mut *tx, now, pointer_ids, SWEEP_BATCH).await?;
+
+        for restriction in &expired {
+            if lift_with_receipt(&mut tx, keypair, restriction, "expired", now)
+                .await?
+                .is_some()
+            {
+                lifted += 1;
+            }
+        }
+
+        tx.commit().await?;
+
+        if (expired.len() as i64) < SWEEP_BATCH {
+            return Ok(lifted);
+        }
+    }
+}
+
+/// Background loop: sweep expired restrictions every configured interval
+pub async fn run_restriction_sweeper(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.restriction_sweep_interval_seconds.max(1),
+    ));
+
+    info!(
+        "Restriction sweeper running every {}s",
+        config.restriction_sweep_interval_seconds
+    );
+
+    loop {
+        interval.tick().await;
+
+        match sweep_expired_restrictions(&pool, &signing_keys.active(), clock.now()).await {
+            Ok(0) => {}
+            Ok(count) => info!("Lifted {} expired processing restrictions", count),
+            Err(e) => error!("Restriction sweep failed: {:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            handlers::{resolve_pointer, restrict_subject, RestrictSubjectRequest},
+            AppState,
+        },
+        clock::FixedClock,
+        test_support::{seed_pointer, test_auth, test_state, unique_subject},
+    };
+    use axum::{
+        extract::{Path, Query, State},
+        Json,
+    };
+
+    #[tokio::test]
+    async fn test_expired_restriction_is_swept() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("sweep");
+        let pointer_id = seed_pointer(&state, &subject).await;
+
+        // Restrict for one minute, two minutes ago
+        let past = AppState {
+            clock: Arc::new(FixedClock(Utc::now() - chrono::Duration::seconds(120))),
+            ..state.clone()
+        };
+        let restricted = restrict_subject(
+            State(past.clone()),
+            test_auth(&past),
+            Path(subject),
+            Json(RestrictSubjectRequest {
+                duration_seconds: 60,
+                reason: "subject_dispute".to_string(),
+            }),
+        )
+        .await;
+        assert!(restricted.is_ok(), "restrict failed");
+
+        // Expired windows stop denying before the sweeper runs
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            Path(pointer_id),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "resolve after expiry failed");
+
+        let lifted = sweep_expired_restrictions(&state.db.writer, &state.keypair(), Utc::now())
+            .await
+            .unwrap();
+        assert!(lifted >= 1);
+        assert!(queries::get_open_restriction(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        let receipts = queries::get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let unrestrict = receipts.last().unwrap();
+        assert_eq!(unrestrict.receipt_json["operation"], "unrestrict");
+        assert_eq!(
+            unrestrict.receipt_json["metadata"]["lift_reason"],
+            "expired"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+// Retention classes
+// Each data category has a legal maximum lifetime, configured as a map of
+// class name to maximum age. A pointer created with a class gets a hard
+// deadline no later than creation plus that age; a requested expiry can only
+// bring it earlier.
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionClasses(BTreeMap<String, i64>);
+
+impl RetentionClasses {
+    /// Maximum age of a class, None if it isn't configured
+    pub fn max_age(&self, class: &str) -> Option<Duration> {
+        self.0.get(class).map(|&seconds| Duration::seconds(seconds))
+    }
+
+    /// Class names with their maximum age in seconds, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.0
+            .iter()
+            .map(|(class, &seconds)| (class.as_str(), seconds))
+    }
+
+    /// The hard deadline for data created at `now`: the class maximum, or the
+    /// requested expiry if that comes first. Without either there is none.
+    pub fn deadline(
+        &self,
+        class: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>> {
+        if let Some(expires_at) = expires_at {
+            if expires_at <= now {
+                bail!("expires_at must be in the future");
+            }
+        }
+
+        let Some(class) = class else {
+            return Ok(expires_at);
+        };
+        let Some(max_age) = self.max_age(class) else {
+            bail!("unknown retention_class '{}'", class);
+        };
+
+        let class_deadline = now + max_age;
+        match expires_at {
+            Some(expires_at) if expires_at > class_deadline => bail!(
+                "expires_at exceeds the {} retention maximum of {}s (latest {})",
+                class,
+                max_age.num_seconds(),
+                class_deadline.to_rfc3339()
+            ),
+            Some(expires_at) => Ok(Some(expires_at)),
+            None => Ok(Some(class_deadline)),
+        }
+    }
+}
+
+/// `class=seconds` pairs separated by commas, e.g.
+/// `biometric=2592000,transaction_record=220752000`
+impl FromStr for RetentionClasses {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut classes = BTreeMap::new();
+
+        for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (class, seconds) = entry
+                .split_once('=')
+                .with_context(|| format!("retention class '{}' has no maximum age", entry))?;
+            let class = class.trim();
+            let seconds: i64 = seconds
+                .trim()
+                .parse()
+                .with_context(|| format!("retention class '{}' maximum age", class))?;
+
+            if class.is_empty() {
+                bail!("retention class name must not be empty");
+            }
+            if seconds <= 0 {
+                bail!("retention class '{}' maximum age must be positive", class);
+            }
+            if classes.insert(class.to_string(), seconds).is_some() {
+                bail!("retention class '{}' is listed twice", class);
+            }
+        }
+
+        Ok(RetentionClasses(classes))
+    }
+}
+
+impl fmt::Display for RetentionClasses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<String> = self
+            .iter()
+            .map(|(class, seconds)| format!("{}={}", class, seconds))
+            .collect();
+        f.write_str(&entries.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classes() -> RetentionClasses {
+        "biometric=2592000, transaction_record=220752000"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_class_map() {
+        let classes = classes();
+        assert_eq!(classes.max_age("biometric"), Some(Duration::days(30)));
+        assert_eq!(classes.max_age("marketing"), None);
+        assert_eq!(
+            classes.to_string(),
+            "biometric=2592000,transaction_record=220752000"
+        );
+
+        assert_eq!(
+            "".parse::<RetentionClasses>().unwrap(),
+            RetentionClasses::default()
+        );
+        assert!("biometric".parse::<RetentionClasses>().is_err());
+        assert!("biometric=0".parse::<RetentionClasses>().is_err());
+        assert!("a=1,a=2".parse::<RetentionClasses>().is_err());
+    }
+
+    #[test]
+    fn test_class_maximum_caps_requested_expiry() {
+        let classes = classes();
+        let now = Utc::now();
+        let day = Duration::days(1);
+
+        // The class maximum when nothing earlier is asked for
+        assert_eq!(
+            classes.deadline(Some("biometric"), None, now).unwrap(),
+            Some(now + day * 30)
+        );
+        assert_eq!(
+            classes
+                .deadline(Some("biometric"), Some(now + day), now)
+                .unwrap(),
+            Some(now + day)
+        );
+        assert!(classes
+            .deadline(Some("biometric"), Some(now + day * 31), now)
+            .is_err());
+
+        // Unclassified data expires only on request
+        assert_eq!(classes.deadline(None, None, now).unwrap(), None);
+        assert_eq!(
+            classes.deadline(None, Some(now + day * 400), now).unwrap(),
+            Some(now + day * 400)
+        );
+        assert!(classes.deadline(None, Some(now - day), now).is_err());
+        assert!(classes.deadline(Some("marketing"), None, now).is_err());
+    }
+}
@@ -0,0 +1,6 @@
+// Retention class module
+pub mod classes;
+pub mod sweeper;
+
+pub use classes::*;
+pub use sweeper::*;
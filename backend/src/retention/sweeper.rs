@@ -0,0 +1,371 @@
+// Retention sweeper
+// Past a data row's retention deadline, every active pointer to it is
+// orphaned with a receipt and the row's payload is dropped. The deadline is
+// the legal maximum, so this runs whatever else the pointer's owner wants;
+// only a legal hold on the pointer defers it.
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use std::{collections::BTreeMap, sync::Arc};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    clock::Clock,
+    config::Config,
+    crypto::{Ed25519Keypair, ReceiptData, SigningKeyring},
+    db::{
+        models::{ReceiptOperation, RetentionExpiration},
+        queries, PointerCache,
+    },
+    webhooks,
+};
+
+/// Orphan reason recorded on pointers the sweeper expires
+pub const RETENTION_ORPHAN_REASON: &str = "retention_expired";
+
+/// Pointers expired per transaction
+const SWEEP_BATCH: i64 = 100;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionSweep {
+    pub pointers_orphaned: usize,
+    pub payloads_purged: usize,
+}
+
+/// Expire everything past its retention deadline at `now`, for one org or
+/// all of them. Orphaned pointers are evicted from `pointer_cache` as each
+/// batch commits.
+pub async fn sweep_retention(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    pointer_cache: &PointerCache,
+    now: DateTime<Utc>,
+    org_id: Option<Uuid>,
+) -> Result<RetentionSweep> {
+    let mut sweep = RetentionSweep::default();
+
+    loop {
+        let mut tx = pool.begin().await?;
+        let expired =
+            queries::lock_retention_expired_pointers(&mut *tx, now, org_id, SWEEP_BATCH).await?;
+
+        let mut orphaned = Vec::new();
+        for pointer in &expired {
+            if orphan_with_receipt(&mut tx, keypair, pointer, now).await? {
+                orphaned.push(pointer.pointer_id);
+            }
+        }
+
+        tx.commit().await?;
+        for pointer_id in &orphaned {
+            pointer_cache.evict(pointer_id);
+        }
+        sweep.pointers_orphaned += orphaned.len();
+
+        if (expired.len() as i64) < SWEEP_BATCH {
+            break;
+        }
+    }
+
+    // Payloads go once no pointer can release them any more
+    let mut tx = pool.begin().await?;
+    let purged = queries::purge_retention_expired_payloads(&mut *tx, now, org_id).await?;
+
+    let mut by_org: BTreeMap<Uuid, Vec<Uuid>> = BTreeMap::new();
+    for (data_id, org_id) in &purged {
+        by_org.entry(*org_id).or_default().push(*data_id);
+    }
+    for (org_id, data_ids) in by_org {
+        queries::create_audit_log(
+            &mut *tx,
+            Some(org_id),
+            None,
+            None,
+            "data_store_retention_purged",
+            json!({ "data_ids": data_ids, "swept_at": now }),
+            None,
+            None,
+            None,
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    sweep.payloads_purged = purged.len();
+
+    Ok(sweep)
+}
+
+/// Orphan one locked pointer inside the caller's transaction, chaining an
+/// Orphan receipt that names the class and deadline it expired under
+async fn orphan_with_receipt(
+    conn: &mut PgConnection,
+    keypair: &Ed25519Keypair,
+    expired: &RetentionExpiration,
+    now: DateTime<Utc>,
+) -> Result<bool> {
+    let Some(orphaned) = queries::orphan_pointer(
+        &mut *conn,
+        expired.pointer_id,
+        Some(RETENTION_ORPHAN_REASON),
+        now,
+    )
+    .await?
+    else {
+        return Ok(false);
+    };
+
+    // A veto supersedes any processing restriction
+    let lifted =
+        queries::lift_open_restriction(&mut *conn, orphaned.pointer_id, "orphaned").await?;
+
+    let prev_hash = queries::get_latest_receipt_hash(&mut *conn, orphaned.pointer_id).await?;
+    let mut metadata = json!({
+        "reason": RETENTION_ORPHAN_REASON,
+        "orphaned_at": orphaned.orphaned_at,
+        "retention_class": expired.retention_class,
+        "retain_until": expired.retain_until,
+    });
+    if let Some(lifted) = lifted {
+        metadata["lifted_restriction_id"] = json!(lifted.restriction_id);
+    }
+
+    let receipt_data = ReceiptData::new(
+        orphaned.pointer_id,
+        ReceiptOperation::Orphan,
+        orphaned.subject_id.clone(),
+        prev_hash.clone(),
+        metadata,
+        now,
+    );
+    let signed_receipt = receipt_data.sign(keypair)?;
+
+    queries::create_governance_receipt(
+        &mut *conn,
+        orphaned.pointer_id,
+        orphaned.org_id,
+        ReceiptOperation::Orphan,
+        signed_receipt.receipt_json.clone(),
+        &signed_receipt.receipt_hash,
+        &signed_receipt.signature,
+        &signed_receipt.signature_algorithm,
+        &signed_receipt.key_id,
+        prev_hash.as_deref(),
+    )
+    .await?;
+
+    queries::create_audit_log(
+        &mut *conn,
+        Some(orphaned.org_id),
+        Some(orphaned.pointer_id),
+        None,
+        "pointer_orphaned",
+        json!({
+            "subject_id": orphaned.subject_id,
+            "reason": RETENTION_ORPHAN_REASON,
+            "retention_class": expired.retention_class,
+        }),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    webhooks::enqueue_webhook_event(
+        &mut *conn,
+        orphaned.org_id,
+        orphaned.pointer_id,
+        "pointer_orphaned",
+        &signed_receipt,
+        now,
+    )
+    .await?;
+
+    Ok(true)
+}
+
+/// Background loop: expire past-deadline data every configured interval
+pub async fn run_retention_sweeper(
+    pool: PgPool,
+    signing_keys: Arc<SigningKeyring>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+    pointer_cache: Arc<PointerCache>,
+) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.retention_sweep_interval_seconds.max(1),
+    ));
+
+    info!(
+        "Retention sweeper running every {}s (classes: {})",
+        config.retention_sweep_interval_seconds, config.retention_classes
+    );
+
+    loop {
+        interval.tick().await;
+
+        match sweep_retention(
+            &pool,
+            &signing_keys.active(),
+            &pointer_cache,
+            clock.now(),
+            None,
+        )
+        .await
+        {
+            Ok(sweep) if sweep == RetentionSweep::default() => {}
+            Ok(sweep) => info!(
+                "Retention sweep orphaned {} pointers and purged {} payloads",
+                sweep.pointers_orphaned, sweep.payloads_purged
+            ),
+            Err(e) => error!("Retention sweep failed: {:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            context::RequestContext,
+            handlers::{create_pointer, resolve_pointer, CreatePointerRequest},
+            idempotency::IdempotencyKey,
+            ApiError, AppState,
+        },
+        clock::FixedClock,
+        crypto::hashing::sha3_512_hash,
+        test_support::{test_auth, test_state, unique_subject},
+    };
+    use axum::{
+        extract::{Path, Query, State},
+        Json,
+    };
+    use chrono::Duration;
+
+    async fn create_classified(state: &AppState, retention_class: &str) -> Uuid {
+        let req = CreatePointerRequest {
+            subject_id: unique_subject("retention"),
+            content_hash: sha3_512_hash(b"secret"),
+            encrypted_payload: Some("c2VjcmV0".to_string()),
+            payload_encoding: None,
+            retention_class: Some(retention_class.to_string()),
+            expires_at: None,
+            external_ref: None,
+            requires_dual_approval: false,
+        };
+        let (_, Json(created)) = create_pointer(
+            State(state.clone()),
+            test_auth(state),
+            RequestContext::default(),
+            IdempotencyKey::default(),
+            Json(req),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("create failed"));
+        created.pointer_id
+    }
+
+    #[tokio::test]
+    async fn test_sweep_expires_data_past_its_class_maximum() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+
+        // Own org, so the sweep can't touch rows from other tests
+        let org_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO organizations (org_id, name) VALUES ($1, 'Retention Test Org')")
+            .bind(org_id)
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+        let mut config = state.config.clone();
+        config.default_org_id = org_id;
+
+        // Both created 31 days ago: biometrics are kept 30 days at most
+        let past = AppState {
+            config,
+            clock: Arc::new(FixedClock(Utc::now() - Duration::days(31))),
+            ..state.clone()
+        };
+        let biometric = create_classified(&past, "biometric").await;
+        let transaction = create_classified(&past, "transaction_record").await;
+
+        let sweep = sweep_retention(
+            &state.db.writer,
+            &state.keypair(),
+            &state.pointer_cache,
+            Utc::now(),
+            Some(org_id),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            sweep,
+            RetentionSweep {
+                pointers_orphaned: 1,
+                payloads_purged: 1,
+            }
+        );
+
+        let result = resolve_pointer(
+            State(state.clone()),
+            test_auth(&past),
+            Path(biometric),
+            Query::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::PointerOrphaned(_))));
+        let resolved = resolve_pointer(
+            State(state.clone()),
+            test_auth(&past),
+            Path(transaction),
+            Query::default(),
+        )
+        .await;
+        assert!(resolved.is_ok(), "unexpired pointer was swept");
+
+        let receipts = queries::get_receipts_by_pointer(&state.db.writer, biometric)
+            .await
+            .unwrap();
+        let orphan = receipts.last().unwrap();
+        assert!(matches!(orphan.operation, ReceiptOperation::Orphan));
+        assert_eq!(
+            orphan.receipt_json["metadata"]["reason"],
+            RETENTION_ORPHAN_REASON
+        );
+        assert_eq!(
+            orphan.receipt_json["metadata"]["retention_class"],
+            "biometric"
+        );
+        assert_eq!(
+            orphan.prev_hash.as_deref(),
+            Some(receipts[0].receipt_hash.as_str())
+        );
+
+        let pointer = queries::get_pointer(&state.db.writer, biometric)
+            .await
+            .unwrap()
+            .unwrap();
+        let data = queries::get_data_store(&state.db.writer, pointer.data_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(data.encrypted_payload.is_none());
+        assert_eq!(data.retention_class.as_deref(), Some("biometric"));
+
+        // Nothing left to do on the next run
+        let sweep = sweep_retention(
+            &state.db.writer,
+            &state.keypair(),
+            &state.pointer_cache,
+            Utc::now(),
+            Some(org_id),
+        )
+        .await
+        .unwrap();
+        assert_eq!(sweep, RetentionSweep::default());
+    }
+}
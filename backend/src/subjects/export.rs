@@ -0,0 +1,507 @@
+// Subject data export (GDPR Art. 15 access requests)
+// Everything one org holds about a subject: its pointers, orphaned ones
+// included, with their data_store content hashes, every receipt on those
+// pointers, its audit trail, and the public keys the receipts verify under.
+// Written as it is read, a page of rows at a time, so a subject with any
+// number of receipts is never held in memory.
+//
+// As one JSON document, sections in this order:
+//   {"export": {...}, "public_keys": [...], "pointers": [...],
+//    "receipts": [...], "audit_events": [...]}
+// or as JSON Lines, one record per line tagged with what it is:
+//   {"record": "pointer", "pointer_id": ..., ...}
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    crypto::{open_payload, PayloadCipher},
+    db::{
+        models::{
+            AuditFilter, AuditLog, DataStore, GovernanceReceipt, Pagination, Pointer,
+            PointerFilter, PointerStatus,
+        },
+        queries,
+    },
+};
+
+/// Rows fetched per query
+const EXPORT_PAGE_SIZE: i64 = 500;
+/// Output buffered before it is sent on as one chunk
+const EXPORT_CHUNK_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Jsonl,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubjectExport {
+    /// Canonical form
+    pub subject_id: String,
+    pub org_id: Uuid,
+    pub exported_at: DateTime<Utc>,
+    pub format: ExportFormat,
+    /// Include encrypted payloads of active pointers
+    pub include_payloads: bool,
+    /// JWKs of the keys the receipts verify under
+    pub public_keys: Vec<Value>,
+    /// Opens server-encrypted payloads, see `crypto::payload`
+    pub payload_cipher: Option<Arc<PayloadCipher>>,
+}
+
+/// A piece of the export body; an error ends the body early
+pub type ExportChunk = std::result::Result<String, std::io::Error>;
+
+/// Write the export to `sender`, ending early if the receiver goes away
+pub async fn write_subject_export(
+    pool: &PgPool,
+    export: &SubjectExport,
+    sender: mpsc::Sender<ExportChunk>,
+) -> Result<()> {
+    write_export_pages(pool, export, sender, EXPORT_PAGE_SIZE).await
+}
+
+async fn write_export_pages(
+    pool: &PgPool,
+    export: &SubjectExport,
+    sender: mpsc::Sender<ExportChunk>,
+    page_size: i64,
+) -> Result<()> {
+    let mut out = ExportWriter::new(export.format, sender);
+
+    out.header(json!({
+        "subject_id": export.subject_id,
+        "org_id": export.org_id,
+        "exported_at": export.exported_at.to_rfc3339(),
+        "include_payloads": export.include_payloads,
+    }))
+    .await?;
+
+    out.section("public_keys", "public_key").await?;
+    for key in &export.public_keys {
+        out.record(key.clone()).await?;
+    }
+
+    // Rows written mid-export are left out, so both pointer passes agree
+    let filter = PointerFilter {
+        org_id: export.org_id,
+        status: None,
+        subject_id: Some(export.subject_id.clone()),
+        created_after: None,
+        created_before: Some(export.exported_at),
+    };
+
+    out.section("pointers", "pointer").await?;
+    let mut after = None;
+    loop {
+        let page = Pagination {
+            after,
+            limit: page_size,
+        };
+        let pointers = queries::list_pointers(pool, &filter, &page).await?;
+        for pointer in &pointers {
+            let data = queries::get_data_store(pool, pointer.data_id).await?;
+            out.record(pointer_record(pointer, data.as_ref(), export)?)
+                .await?;
+        }
+        after = next_page(&pointers, &page, |p| (p.created_at, p.pointer_id));
+        if after.is_none() {
+            break;
+        }
+    }
+
+    out.section("receipts", "receipt").await?;
+    let mut after = None;
+    loop {
+        let page = Pagination {
+            after,
+            limit: page_size,
+        };
+        let pointers = queries::list_pointers(pool, &filter, &page).await?;
+        for pointer in &pointers {
+            let mut receipts_after = None;
+            loop {
+                let receipt_page = Pagination {
+                    after: receipts_after,
+                    limit: page_size,
+                };
+                let receipts = queries::get_receipts_by_pointer_page(
+                    pool,
+                    pointer.pointer_id,
+                    None,
+                    &receipt_page,
+                )
+                .await?;
+                for receipt in &receipts {
+                    out.record(receipt_record(receipt)).await?;
+                }
+                receipts_after =
+                    next_page(&receipts, &receipt_page, |r| (r.timestamp, r.receipt_id));
+                if receipts_after.is_none() {
+                    break;
+                }
+            }
+        }
+        after = next_page(&pointers, &page, |p| (p.created_at, p.pointer_id));
+        if after.is_none() {
+            break;
+        }
+    }
+
+    out.section("audit_events", "audit_event").await?;
+    let audit_filter = AuditFilter {
+        org_id: Some(export.org_id),
+        to: Some(export.exported_at),
+        ..AuditFilter::default()
+    };
+    let mut after = None;
+    loop {
+        let page = Pagination {
+            after,
+            limit: page_size,
+        };
+        let logs =
+            queries::get_audit_trail_by_subject(pool, &export.subject_id, &audit_filter, &page)
+                .await?;
+        for log in &logs {
+            out.record(audit_record(log)).await?;
+        }
+        after = next_page(&logs, &page, |log| (log.timestamp, log.log_id));
+        if after.is_none() {
+            break;
+        }
+    }
+
+    out.finish().await
+}
+
+/// Cursor for the page after `rows`, None when it was the last
+fn next_page<T>(
+    rows: &[T],
+    page: &Pagination,
+    key: impl Fn(&T) -> (DateTime<Utc>, Uuid),
+) -> Option<(DateTime<Utc>, Uuid)> {
+    if (rows.len() as i64) < page.limit {
+        return None;
+    }
+    rows.last().map(key)
+}
+
+fn pointer_record(
+    pointer: &Pointer,
+    data: Option<&DataStore>,
+    export: &SubjectExport,
+) -> Result<Value> {
+    let mut record = json!({
+        "pointer_id": pointer.pointer_id,
+        "status": pointer.status.as_str(),
+        "created_at": pointer.created_at.to_rfc3339(),
+        "orphaned_at": pointer.orphaned_at.map(|t| t.to_rfc3339()),
+        "orphan_reason": pointer.orphan_reason,
+        "orphan_effective_at": pointer.orphan_effective_at.map(|t| t.to_rfc3339()),
+        "legal_hold": pointer.legal_hold,
+        "metadata": pointer.metadata,
+        "data_id": pointer.data_id,
+        "content_hash": data.map(|d| &d.content_hash),
+        "retention_class": data.and_then(|d| d.retention_class.as_ref()),
+        "payload_purged_at": data.and_then(|d| d.payload_purged_at).map(|t| t.to_rfc3339()),
+    });
+
+    // A vetoed pointer's payload is not served again, even to its subject.
+    // One that fails to decrypt ends the export rather than going missing.
+    if export.include_payloads && matches!(pointer.status, PointerStatus::Active) {
+        if let Some(data) = data {
+            if let Some(payload) = open_payload(export.payload_cipher.as_deref(), data)? {
+                record["encrypted_payload"] = json!(data_encoding::BASE64.encode(&payload));
+            }
+        }
+    }
+
+    Ok(record)
+}
+
+fn receipt_record(receipt: &GovernanceReceipt) -> Value {
+    let mut record = json!({
+        "receipt_id": receipt.receipt_id,
+        "pointer_id": receipt.pointer_id,
+        "receipt_json": receipt.receipt_json,
+        "receipt_hash": receipt.receipt_hash,
+        "signature": data_encoding::BASE64.encode(&receipt.signature),
+        "signature_algorithm": receipt.signature_algorithm,
+        "key_id": receipt.key_id,
+        "prev_hash": receipt.prev_hash,
+        "timestamp": receipt.timestamp.to_rfc3339(),
+    });
+
+    // Metadata too large to sign inline, needed to check what it signed
+    if let Some(overflow) = &receipt.overflow {
+        record["overflow"] = json!(overflow);
+    }
+
+    record
+}
+
+fn audit_record(log: &AuditLog) -> Value {
+    json!({
+        "log_id": log.log_id,
+        "event_type": log.event_type,
+        "timestamp": log.timestamp.to_rfc3339(),
+        "pointer_id": log.pointer_id,
+        "receipt_id": log.receipt_id,
+        "event_data": log.event_data,
+        "actor_id": log.actor_id,
+    })
+}
+
+/// Lays records out in either format and sends them on in chunks
+struct ExportWriter {
+    format: ExportFormat,
+    sender: mpsc::Sender<ExportChunk>,
+    buffer: String,
+    /// Tag of the current section's records in JSON Lines
+    record: &'static str,
+    in_section: bool,
+    first_in_section: bool,
+}
+
+impl ExportWriter {
+    fn new(format: ExportFormat, sender: mpsc::Sender<ExportChunk>) -> Self {
+        Self {
+            format,
+            sender,
+            buffer: String::new(),
+            record: "export",
+            in_section: false,
+            first_in_section: true,
+        }
+    }
+
+    async fn header(&mut self, header: Value) -> Result<()> {
+        match self.format {
+            ExportFormat::Json => {
+                self.buffer.push_str("{\"export\":");
+                self.buffer.push_str(&header.to_string());
+            }
+            ExportFormat::Jsonl => self.push_line(header),
+        }
+        self.flush_if_full().await
+    }
+
+    async fn section(&mut self, name: &'static str, record: &'static str) -> Result<()> {
+        if self.format == ExportFormat::Json {
+            if self.in_section {
+                self.buffer.push(']');
+            }
+            self.buffer.push_str(&format!(",\"{}\":[", name));
+        }
+        self.record = record;
+        self.in_section = true;
+        self.first_in_section = true;
+        Ok(())
+    }
+
+    async fn record(&mut self, record: Value) -> Result<()> {
+        match self.format {
+            ExportFormat::Json => {
+                if !self.first_in_section {
+                    self.buffer.push(',');
+                }
+                self.buffer.push_str(&record.to_string());
+            }
+            ExportFormat::Jsonl => self.push_line(record),
+        }
+        self.first_in_section = false;
+        self.flush_if_full().await
+    }
+
+    async fn finish(mut self) -> Result<()> {
+        if self.format == ExportFormat::Json {
+            if self.in_section {
+                self.buffer.push(']');
+            }
+            self.buffer.push('}');
+        }
+        self.flush().await
+    }
+
+    /// The record with its tag first, on a line of its own
+    fn push_line(&mut self, record: Value) {
+        let mut tagged = serde_json::Map::new();
+        tagged.insert("record".to_string(), json!(self.record));
+        if let Value::Object(fields) = record {
+            tagged.extend(fields);
+        }
+        self.buffer.push_str(&Value::Object(tagged).to_string());
+        self.buffer.push('\n');
+    }
+
+    async fn flush_if_full(&mut self) -> Result<()> {
+        if self.buffer.len() >= EXPORT_CHUNK_BYTES {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.sender
+            .send(Ok(std::mem::take(&mut self.buffer)))
+            .await
+            .map_err(|_| anyhow!("Export receiver went away"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            context::RequestContext,
+            handlers::{orphan_pointer, OrphanPointerRequest},
+        },
+        test_support::{seed_pointer, test_auth, test_state, unique_subject},
+    };
+    use axum::{extract::State, Json};
+
+    async fn collect(pool: &PgPool, export: &SubjectExport) -> String {
+        let (sender, mut receiver) = mpsc::channel(4);
+        let writer = write_export_pages(pool, export, sender, 1);
+        let reader = async {
+            let mut body = String::new();
+            while let Some(chunk) = receiver.recv().await {
+                body.push_str(&chunk.unwrap());
+            }
+            body
+        };
+        let (written, body) = tokio::join!(writer, reader);
+        written.unwrap();
+        body
+    }
+
+    #[tokio::test]
+    async fn test_export_covers_orphaned_pointers_across_pages() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = unique_subject("export");
+        let active_id = seed_pointer(&state, &subject).await;
+        let orphaned_id = seed_pointer(&state, &subject).await;
+        sqlx::query(
+            "UPDATE data_store SET encrypted_payload = 'sealed'::bytea
+             WHERE data_id IN (SELECT data_id FROM pointers WHERE pointer_id = ANY($1))",
+        )
+        .bind(vec![active_id, orphaned_id])
+        .execute(&state.db.writer)
+        .await
+        .unwrap();
+        let orphaned = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id: orphaned_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await;
+        assert!(orphaned.is_ok(), "orphan failed");
+
+        let mut export = SubjectExport {
+            subject_id: subject.clone(),
+            org_id: state.config.default_org_id,
+            exported_at: Utc::now(),
+            format: ExportFormat::Json,
+            include_payloads: true,
+            public_keys: vec![json!({"kid": "k1"})],
+            payload_cipher: None,
+        };
+        let document: Value = serde_json::from_str(&collect(&state.db.writer, &export).await)
+            .expect("one JSON document");
+
+        assert_eq!(document["export"]["subject_id"], subject);
+        assert_eq!(document["public_keys"][0]["kid"], "k1");
+
+        let pointers = document["pointers"].as_array().unwrap();
+        assert_eq!(pointers.len(), 2);
+        let pointer = |id: Uuid| {
+            pointers
+                .iter()
+                .find(|p| p["pointer_id"] == json!(id))
+                .unwrap()
+        };
+        assert_eq!(pointer(active_id)["status"], "active");
+        assert_eq!(pointer(active_id)["content_hash"], "ab".repeat(64));
+        assert_eq!(
+            pointer(active_id)["encrypted_payload"],
+            data_encoding::BASE64.encode(b"sealed")
+        );
+        assert_eq!(pointer(orphaned_id)["status"], "orphaned");
+        assert!(pointer(orphaned_id).get("encrypted_payload").is_none());
+
+        // Two creates and an orphan, each chain oldest first
+        let receipts = document["receipts"].as_array().unwrap();
+        assert_eq!(receipts.len(), 3);
+        let orphaned_chain: Vec<_> = receipts
+            .iter()
+            .filter(|r| r["pointer_id"] == json!(orphaned_id))
+            .map(|r| r["receipt_json"]["operation"].as_str().unwrap())
+            .collect();
+        assert_eq!(orphaned_chain, ["create", "orphan"]);
+
+        let events: Vec<_> = document["audit_events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["event_type"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            events.iter().filter(|e| **e == "pointer_created").count(),
+            2
+        );
+
+        // The same records as tagged lines, payloads left out on request
+        export.format = ExportFormat::Jsonl;
+        export.include_payloads = false;
+        let lines: Vec<Value> = collect(&state.db.writer, &export)
+            .await
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let count = |record: &str| lines.iter().filter(|l| l["record"] == record).count();
+        assert_eq!(lines[0]["record"], "export");
+        assert_eq!(count("public_key"), 1);
+        assert_eq!(count("pointer"), 2);
+        assert_eq!(count("receipt"), 3);
+        assert_eq!(count("audit_event"), events.len());
+        assert!(lines.iter().all(|l| l.get("encrypted_payload").is_none()));
+    }
+}
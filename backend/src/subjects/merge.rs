@@ -0,0 +1,375 @@
+// Near-duplicate subject detection and merging
+// Re-homes pointers stored under non-canonical spellings of a subject_id,
+// leaving a chained merge receipt on every pointer that moved
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::info;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{subject_preview, SubjectRules};
+use crate::{
+    crypto::{Ed25519Keypair, ReceiptData, SignedReceipt},
+    db::{models::ReceiptOperation, queries},
+};
+
+/// Stored subject_ids that share one canonical form
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DuplicateSubjectGroup {
+    pub canonical: String,
+    /// Full value behind `canonical` when it is a digest key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_value: Option<String>,
+    pub variants: Vec<SubjectVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SubjectVariant {
+    pub subject_id: String,
+    pub pointer_count: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MergedPointer {
+    pub pointer_id: Uuid,
+    pub previous_subject_id: String,
+    pub subject_id: String,
+    pub receipt: SignedReceipt,
+    pub receipt_timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Group an org's stored subject_ids by canonical form, keeping groups where
+/// at least one stored spelling is not already canonical
+pub async fn find_duplicate_subjects(
+    pool: &PgPool,
+    org_id: Uuid,
+    rules: SubjectRules,
+) -> Result<Vec<DuplicateSubjectGroup>> {
+    let mut groups: BTreeMap<String, (Option<String>, Vec<SubjectVariant>)> = BTreeMap::new();
+
+    for count in queries::get_subject_pointer_counts(pool, org_id).await? {
+        let normalized = rules.normalize(&count.subject_id);
+        let (full_value, variants) = groups.entry(normalized.subject_id).or_default();
+
+        // Stored digest keys don't carry their value; a raw variant does
+        if full_value.is_none() {
+            *full_value = normalized.full_value;
+        }
+        variants.push(SubjectVariant {
+            subject_id: count.subject_id,
+            pointer_count: count.pointer_count,
+        });
+    }
+
+    Ok(groups
+        .into_iter()
+        .filter(|(canonical, (_, variants))| variants.iter().any(|v| &v.subject_id != canonical))
+        .map(
+            |(canonical, (full_value, variants))| DuplicateSubjectGroup {
+                canonical,
+                full_value,
+                variants,
+            },
+        )
+        .collect())
+}
+
+/// Move every pointer of an org's group's non-canonical variants onto the
+/// canonical subject_id in one transaction, chaining a merge receipt signed
+/// with the org's `keypair` onto each pointer
+pub async fn merge_subject_group(
+    pool: &PgPool,
+    keypair: &Ed25519Keypair,
+    org_id: Uuid,
+    group: &DuplicateSubjectGroup,
+    now: DateTime<Utc>,
+) -> Result<Vec<MergedPointer>> {
+    let mut tx = pool.begin().await?;
+    let mut merged = Vec::new();
+
+    if let Some(full_value) = &group.full_value {
+        queries::upsert_subject_value(&mut *tx, &group.canonical, full_value).await?;
+    }
+
+    for variant in &group.variants {
+        if variant.subject_id == group.canonical {
+            continue;
+        }
+
+        // Long variants being digested only appear in receipts as a preview
+        let previous_subject_id = match group.full_value {
+            Some(_) => subject_preview(&variant.subject_id),
+            None => variant.subject_id.clone(),
+        };
+
+        // Another org's pointers under the same spelling are its own to merge
+        let pointers = queries::lock_pointers_by_subject(&mut *tx, &variant.subject_id)
+            .await?
+            .into_iter()
+            .filter(|p| p.org_id == org_id);
+        for pointer in pointers {
+            queries::rehome_pointer_subject(&mut *tx, pointer.pointer_id, &group.canonical).await?;
+
+            let prev_hash = queries::get_latest_receipt_hash(&mut *tx, pointer.pointer_id).await?;
+
+            let receipt_data = ReceiptData::new(
+                pointer.pointer_id,
+                ReceiptOperation::Merge,
+                group.canonical.clone(),
+                prev_hash.clone(),
+                json!({
+                    "previous_subject_id": previous_subject_id,
+                    "subject_id_normalized": true,
+                }),
+                now,
+            );
+            let signed_receipt = receipt_data.sign(keypair)?;
+
+            queries::create_governance_receipt(
+                &mut *tx,
+                pointer.pointer_id,
+                pointer.org_id,
+                ReceiptOperation::Merge,
+                signed_receipt.receipt_json.clone(),
+                &signed_receipt.receipt_hash,
+                &signed_receipt.signature,
+                &signed_receipt.signature_algorithm,
+                &signed_receipt.key_id,
+                prev_hash.as_deref(),
+            )
+            .await?;
+
+            queries::create_audit_log(
+                &mut *tx,
+                Some(pointer.org_id),
+                Some(pointer.pointer_id),
+                None,
+                "subject_merged",
+                json!({
+                    "previous_subject_id": previous_subject_id,
+                    "subject_id": group.canonical,
+                    "receipt_hash": signed_receipt.receipt_hash,
+                }),
+                None,
+                None,
+                None,
+            )
+            .await?;
+
+            merged.push(MergedPointer {
+                pointer_id: pointer.pointer_id,
+                previous_subject_id: variant.subject_id.clone(),
+                subject_id: group.canonical.clone(),
+                receipt: signed_receipt,
+                receipt_timestamp: receipt_data.timestamp,
+            });
+        }
+    }
+
+    tx.commit().await?;
+
+    info!(
+        "Merged {} pointers into subject {}",
+        merged.len(),
+        group.canonical
+    );
+
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        subjects::subject_digest,
+        test_support::{seed_pointer, test_state},
+    };
+
+    const LOWERCASE: SubjectRules = SubjectRules {
+        lowercase: true,
+        email: true,
+        digest_threshold: 255,
+    };
+
+    #[tokio::test]
+    async fn test_merge_rehomes_variants_with_receipts() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let local = format!("Merge_{}", Uuid::new_v4().simple());
+        let canonical = format!("{}@example.com", local.to_lowercase());
+
+        // Creation already applies the default email rules
+        let kept = seed_pointer(&state, &format!(" {}@Example.COM ", local)).await;
+        let kept_pointer = queries::get_pointer(&state.db.writer, kept)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(kept_pointer.subject_id, format!("{}@example.com", local));
+        let receipts = queries::get_receipts_by_pointer(&state.db.writer, kept)
+            .await
+            .unwrap();
+        assert_eq!(
+            receipts[0].receipt_json["metadata"]["subject_id_normalized"],
+            json!(true)
+        );
+
+        // Legacy rows stored before normalization existed
+        let legacy = seed_pointer(&state, &canonical).await;
+        let legacy_spelling = format!("{}@EXAMPLE.com", local.to_uppercase());
+        sqlx::query("UPDATE pointers SET subject_id = $2 WHERE pointer_id = $1")
+            .bind(legacy)
+            .bind(&legacy_spelling)
+            .execute(&state.db.writer)
+            .await
+            .unwrap();
+
+        let group =
+            find_duplicate_subjects(&state.db.writer, state.config.default_org_id, LOWERCASE)
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|g| g.canonical == canonical)
+                .expect("duplicate group reported");
+        assert_eq!(group.variants.len(), 2);
+
+        // Another org neither sees the group nor moves its pointers
+        let other_org = Uuid::new_v4();
+        let theirs = find_duplicate_subjects(&state.db.writer, other_org, LOWERCASE)
+            .await
+            .unwrap();
+        assert!(theirs.iter().all(|g| g.canonical != canonical));
+        let merged = merge_subject_group(
+            &state.db.writer,
+            &state.keypair(),
+            other_org,
+            &group,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert!(merged.is_empty());
+
+        let merged = merge_subject_group(
+            &state.db.writer,
+            &state.keypair(),
+            state.config.default_org_id,
+            &group,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        let mut moved: Vec<(Uuid, String)> = merged
+            .iter()
+            .map(|m| (m.pointer_id, m.previous_subject_id.clone()))
+            .collect();
+        moved.sort();
+        let mut expected = vec![
+            (kept, format!("{}@example.com", local)),
+            (legacy, legacy_spelling),
+        ];
+        expected.sort();
+        assert_eq!(moved, expected);
+
+        for pointer_id in [kept, legacy] {
+            let pointer = queries::get_pointer(&state.db.writer, pointer_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(pointer.subject_id, canonical);
+
+            let data = queries::get_data_store(&state.db.writer, pointer.data_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(data.subject_id, canonical);
+
+            let receipts = queries::get_receipts_by_pointer(&state.db.writer, pointer_id)
+                .await
+                .unwrap();
+            let merge = receipts.last().unwrap();
+            assert!(matches!(merge.operation, ReceiptOperation::Merge));
+            assert_eq!(
+                merge.prev_hash.as_deref(),
+                Some(receipts[0].receipt_hash.as_str())
+            );
+        }
+
+        // Nothing left to merge for this subject
+        let remaining =
+            find_duplicate_subjects(&state.db.writer, state.config.default_org_id, LOWERCASE)
+                .await
+                .unwrap();
+        assert!(remaining.iter().all(|g| g.canonical != canonical));
+    }
+
+    #[tokio::test]
+    async fn test_merge_migrates_long_subjects_to_digest_keys() {
+        let Some(state) = test_state().await else {
+            return;
+        };
+        let subject = format!("long_{}_{}", Uuid::new_v4().simple(), "x".repeat(100));
+
+        // Stored as-is under the default threshold
+        let pointer_id = seed_pointer(&state, &subject).await;
+
+        // Lowering the threshold reports it for migration
+        let rules = SubjectRules {
+            digest_threshold: 64,
+            ..LOWERCASE
+        };
+        let key = subject_digest(&subject);
+        let group = find_duplicate_subjects(&state.db.writer, state.config.default_org_id, rules)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|g| g.canonical == key)
+            .expect("long subject reported");
+        assert_eq!(group.full_value.as_deref(), Some(subject.as_str()));
+
+        merge_subject_group(
+            &state.db.writer,
+            &state.keypair(),
+            state.config.default_org_id,
+            &group,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+
+        let pointer = queries::get_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pointer.subject_id, key);
+
+        let stored: String =
+            sqlx::query_scalar("SELECT subject_value FROM subjects WHERE subject_key = $1")
+                .bind(&key)
+                .fetch_one(&state.db.writer)
+                .await
+                .unwrap();
+        assert_eq!(stored, subject);
+
+        let receipts = queries::get_receipts_by_pointer(&state.db.writer, pointer_id)
+            .await
+            .unwrap();
+        let merge = &receipts.last().unwrap().receipt_json;
+        assert_eq!(merge["subject_id"], json!(key));
+        assert_eq!(
+            merge["metadata"]["previous_subject_id"],
+            json!(subject_preview(&subject))
+        );
+
+        // Lookups by the full value resolve through the key
+        let found = queries::get_pointers_by_subject(&state.db.writer, &rules.canonical(&subject))
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+    }
+}
@@ -0,0 +1,8 @@
+// Subject identifier module
+pub mod export;
+pub mod merge;
+pub mod normalize;
+
+pub use export::*;
+pub use merge::*;
+pub use normalize::*;
@@ -0,0 +1,225 @@
+// Canonical subject identifiers
+// Applied wherever a subject_id enters through the API so one person's veto
+// rights don't fragment across spelling variants of the same identifier.
+// Over-long identifiers are keyed by digest so indexes and receipts stay small.
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    config::Config,
+    crypto::hashing::{sha3_256_hash_str, sha3_512_hash_str},
+};
+
+/// Prefix of the key stored in place of an over-long subject_id
+pub const SUBJECT_DIGEST_PREFIX: &str = "sha3-256:";
+
+/// Longest subject_id accepted at all, before normalization
+pub const MAX_SUBJECT_BYTES: usize = 8192;
+
+/// Characters of an over-long subject_id shown in receipts
+const SUBJECT_PREVIEW_CHARS: usize = 32;
+
+/// Optional rules layered on top of trimming and Unicode NFC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubjectRules {
+    /// Lowercase the whole identifier
+    pub lowercase: bool,
+    /// Lowercase the domain of email-shaped identifiers (the local part is
+    /// case-sensitive per RFC 5321, so it is left alone unless `lowercase`)
+    pub email: bool,
+    /// Longest subject_id, in bytes, stored as-is; longer ones are replaced
+    /// by their digest key
+    pub digest_threshold: usize,
+}
+
+/// A subject_id after normalization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedSubject {
+    pub subject_id: String,
+    /// SHA3-512 of the raw value, set only when normalization changed it
+    pub raw_hash: Option<String>,
+    /// Full canonical value, set only when `subject_id` is its digest key
+    pub full_value: Option<String>,
+}
+
+impl NormalizedSubject {
+    pub fn was_normalized(&self) -> bool {
+        self.raw_hash.is_some()
+    }
+
+    /// Truncated form of the full value for receipts, when digested
+    pub fn preview(&self) -> Option<String> {
+        self.full_value.as_deref().map(subject_preview)
+    }
+}
+
+impl SubjectRules {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            lowercase: config.subject_lowercase,
+            email: config.subject_email_rules,
+            digest_threshold: config.subject_digest_threshold,
+        }
+    }
+
+    /// Key a subject_id is stored and looked up under: its canonical value,
+    /// or the digest of that value when it exceeds the threshold
+    pub fn canonical(&self, raw: &str) -> String {
+        self.subject_key(self.canonical_value(raw))
+    }
+
+    /// Canonical spelling of a subject_id, before any digesting
+    pub fn canonical_value(&self, raw: &str) -> String {
+        let nfc: String = raw.trim().nfc().collect();
+
+        if self.lowercase {
+            return nfc.to_lowercase();
+        }
+
+        if self.email {
+            if let Some((local, domain)) = split_email(&nfc) {
+                return format!("{}@{}", local, domain.to_lowercase());
+            }
+        }
+
+        nfc
+    }
+
+    pub fn normalize(&self, raw: &str) -> NormalizedSubject {
+        let value = self.canonical_value(raw);
+        let raw_hash = (value != raw).then(|| sha3_512_hash_str(raw));
+        let subject_id = self.subject_key(value.clone());
+        let full_value = (subject_id != value).then_some(value);
+
+        NormalizedSubject {
+            subject_id,
+            raw_hash,
+            full_value,
+        }
+    }
+
+    fn subject_key(&self, value: String) -> String {
+        if value.len() <= self.digest_threshold || is_subject_digest(&value) {
+            value
+        } else {
+            subject_digest(&value)
+        }
+    }
+}
+
+/// Digest key for an over-long subject_id
+pub fn subject_digest(value: &str) -> String {
+    format!("{}{}", SUBJECT_DIGEST_PREFIX, sha3_256_hash_str(value))
+}
+
+/// True for keys produced by `subject_digest`; they are never digested again
+pub fn is_subject_digest(value: &str) -> bool {
+    value
+        .strip_prefix(SUBJECT_DIGEST_PREFIX)
+        .is_some_and(|hex| {
+            hex.len() == 64 && hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        })
+}
+
+/// First characters of a subject_id, marked when truncated
+pub fn subject_preview(value: &str) -> String {
+    match value.char_indices().nth(SUBJECT_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &value[..end]),
+        None => value.to_string(),
+    }
+}
+
+fn split_email(value: &str) -> Option<(&str, &str)> {
+    let (local, domain) = value.rsplit_once('@')?;
+    let well_formed =
+        !local.is_empty() && domain.contains('.') && !value.chars().any(char::is_whitespace);
+
+    well_formed.then_some((local, domain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT: SubjectRules = SubjectRules {
+        lowercase: false,
+        email: true,
+        digest_threshold: 255,
+    };
+    const LOWERCASE: SubjectRules = SubjectRules {
+        lowercase: true,
+        email: true,
+        digest_threshold: 255,
+    };
+    const NONE: SubjectRules = SubjectRules {
+        lowercase: false,
+        email: false,
+        digest_threshold: 255,
+    };
+
+    #[test]
+    fn test_normalization_matrix() {
+        let cases = [
+            (NONE, " user_123 ", "user_123"),
+            (NONE, "User@Example.COM", "User@Example.COM"),
+            (DEFAULT, "User@Example.COM", "User@example.com"),
+            (DEFAULT, " user@example.com ", "user@example.com"),
+            (DEFAULT, "User_123", "User_123"),
+            (DEFAULT, "not an@email.com", "not an@email.com"),
+            (DEFAULT, "user@localhost", "user@localhost"),
+            (LOWERCASE, "User@Example.COM", "user@example.com"),
+            (LOWERCASE, " USER_123", "user_123"),
+            // "e" + combining acute composes to U+00E9
+            (NONE, "jose\u{0301}", "jos\u{00e9}"),
+            (
+                LOWERCASE,
+                "JOSE\u{0301}@Example.com",
+                "jos\u{00e9}@example.com",
+            ),
+        ];
+
+        for (rules, raw, expected) in cases {
+            assert_eq!(rules.canonical(raw), expected, "{:?} {:?}", rules, raw);
+        }
+    }
+
+    #[test]
+    fn test_raw_hash_only_when_changed() {
+        let unchanged = DEFAULT.normalize("user@example.com");
+        assert!(!unchanged.was_normalized());
+
+        let changed = DEFAULT.normalize(" user@Example.com");
+        assert_eq!(changed.subject_id, "user@example.com");
+        assert_eq!(
+            changed.raw_hash.as_deref(),
+            Some(sha3_512_hash_str(" user@Example.com").as_str())
+        );
+    }
+
+    #[test]
+    fn test_long_subjects_are_digested_at_threshold() {
+        let rules = SubjectRules {
+            digest_threshold: 16,
+            ..DEFAULT
+        };
+
+        let short = rules.normalize("user_0123456789a");
+        assert_eq!(short.subject_id, "user_0123456789a");
+        assert!(short.full_value.is_none());
+
+        let jwt = format!(" {} ", "eyJhbGciOi".repeat(200));
+        let long = rules.normalize(&jwt);
+        assert_eq!(long.subject_id, subject_digest(jwt.trim()));
+        assert_eq!(long.full_value.as_deref(), Some(jwt.trim()));
+        assert!(long.was_normalized(), "trimmed");
+        assert_eq!(
+            long.preview().unwrap().chars().count(),
+            SUBJECT_PREVIEW_CHARS + 1
+        );
+
+        // Lookups by the full value and by the key agree
+        assert_eq!(rules.canonical(&jwt), long.subject_id);
+        assert_eq!(rules.canonical(&long.subject_id), long.subject_id);
+        assert!(is_subject_digest(&long.subject_id));
+        assert!(long.subject_id.len() <= 255);
+    }
+}
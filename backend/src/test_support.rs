@@ -0,0 +1,363 @@
+// Shared fixtures for database-backed tests
+// These run only when TEST_DATABASE_URL points at a database; the first one
+// brings its schema up with the migrations. Otherwise each test returns early.
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use std::{sync::Arc, time::Duration};
+use uuid::Uuid;
+
+use crate::{
+    analytics::ReceiptPhaseMetrics,
+    api::{
+        auth::{AuthContext, KnownApiKeys},
+        compression::CompressionMetrics,
+        context::RequestContext,
+        cors::CorsOrigins,
+        handlers,
+        idempotency::IdempotencyKey,
+        rate_limit::RateLimiter,
+        AppState,
+    },
+    clock::{ManualClock, SystemClock},
+    config::{Config, DbPoolConfig},
+    crypto::{Ed25519Keypair, ReceiptVerifier, SigningKeyring},
+    db::{
+        migrations::run_migrations, models::OrgHeartbeat, queries, CircuitBreaker, Db,
+        PointerCache, SingleFlight,
+    },
+    enforcement::LastKnownStatuses,
+    gc::DataGcMode,
+    heartbeats::{emit_org_heartbeats, interval_floor},
+    keys::OrgSigningKeys,
+    restrictions::sweep_expired_restrictions_of,
+    secret::Secret,
+};
+
+/// Demo organization seeded by the initial migration
+pub const TEST_ORG_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+/// Fixed, so org keys stored by earlier runs still open
+const TEST_MASTER_KEY: &str = "6d61737465722d6b65792d666f722d746573742d72756e732d6f6e6c79212121";
+
+/// Checkpoints cover every test's receipts, so tests that write them all
+/// sign with this key and can check what another wrote
+pub fn checkpoint_keypair() -> Ed25519Keypair {
+    Ed25519Keypair::from_seed(&[0x43; 32])
+}
+
+pub async fn test_pool() -> Option<PgPool> {
+    let url = match std::env::var("TEST_DATABASE_URL") {
+        Ok(url) => url,
+        Err(_) => {
+            eprintln!("TEST_DATABASE_URL not set, skipping database test");
+            return None;
+        }
+    };
+
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(&url)
+        .await
+        .expect("Failed to connect to TEST_DATABASE_URL");
+
+    static MIGRATED: tokio::sync::OnceCell<()> = tokio::sync::OnceCell::const_new();
+    MIGRATED
+        .get_or_init(|| async {
+            run_migrations(&pool)
+                .await
+                .expect("Failed to migrate TEST_DATABASE_URL");
+        })
+        .await;
+
+    Some(pool)
+}
+
+pub fn test_config(database_url: &str) -> Config {
+    Config {
+        database_url: Secret::new(database_url.to_string()),
+        database_read_url: None,
+        database_pool: DbPoolConfig {
+            max_connections: 5,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(30),
+            connect_retries: 0,
+            connect_retry_delay: Duration::from_millis(500),
+        },
+        background_pool_size: 2,
+        run_migrations: true,
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        default_org_id: TEST_ORG_ID.parse().unwrap(),
+        cors_allowed_origins: CorsOrigins::Exact(vec![]),
+        signing_private_key: None,
+        signing_public_key: None,
+        signing_key_path: None,
+        signing_master_key: Some(Secret::new(TEST_MASTER_KEY.to_string())),
+        payload_encryption_key: None,
+        resolve_stats_interval_seconds: 300,
+        resolve_spike_ratio: 10.0,
+        resolve_spike_min_count: 50,
+        resolve_spike_baseline_hours: 24,
+        compression_min_bytes: 1024,
+        subject_lowercase: false,
+        subject_email_rules: true,
+        subject_digest_threshold: 255,
+        subject_max_bytes: 256,
+        max_payload_bytes: 1024 * 1024,
+        restriction_sweep_interval_seconds: 60,
+        consistency_audit_interval_seconds: 600,
+        consistency_sample_size: 100,
+        consistency_auto_repair: false,
+        resolve_coalesce_max_staleness_ms: 50,
+        pointer_cache_ttl_ms: 500,
+        pointer_cache_max_entries: 10_000,
+        heartbeat_check_interval_seconds: 30,
+        strict_receipt_reads: false,
+        veto_slo_target_ms: 1000,
+        data_gc_interval_seconds: 3600,
+        data_gc_grace_seconds: 604800,
+        data_gc_mode: DataGcMode::NullPayload,
+        data_gc_dry_run: false,
+        degraded_read_max_staleness_seconds: 60,
+        db_circuit_failure_threshold: 3,
+        db_circuit_open_seconds: 5,
+        retention_classes: "biometric=2592000,transaction_record=220752000"
+            .parse()
+            .unwrap(),
+        retention_sweep_interval_seconds: 300,
+        pending_orphan_sweep_interval_seconds: 30,
+        orphan_request_ttl_seconds: 86400,
+        allow_reinstatement: false,
+        payload_purge_after_days: 30,
+        payload_purge_interval_seconds: 3600,
+        webhook_max_attempts: 3,
+        webhook_retry_base_seconds: 10,
+        webhook_timeout_ms: 2000,
+        webhook_poll_interval_seconds: 5,
+        receipt_metrics_enabled: false,
+        slow_receipt_threshold_ms: 50,
+        receipt_max_bytes: 65536,
+        trusted_proxy: false,
+        api_key_rotation_overlap_seconds: 86400,
+        api_key_sweep_interval_seconds: 60,
+        health_db_timeout_ms: 1000,
+        idempotency_key_ttl_seconds: 86400,
+        receipt_checkpoint_interval_minutes: 10,
+        rate_limit_reads_per_minute: 0,
+        rate_limit_writes_per_minute: 0,
+        rate_limit_audit: false,
+        serve_api_docs: false,
+    }
+}
+
+pub async fn test_state() -> Option<AppState> {
+    let db_pool = test_pool().await?;
+    let database_url = std::env::var("TEST_DATABASE_URL").unwrap_or_default();
+
+    Some(state_with_pool(db_pool, &database_url))
+}
+
+/// State whose pool points at a closed port, for tests of what the app does
+/// before touching the database or when it is down
+pub fn offline_state() -> AppState {
+    let database_url = "postgres://postgres@127.0.0.1:1/offline";
+    let db_pool = PgPoolOptions::new()
+        .connect_lazy(database_url)
+        .expect("Failed to build lazy pool");
+
+    state_with_pool(db_pool, database_url)
+}
+
+fn state_with_pool(db_pool: PgPool, database_url: &str) -> AppState {
+    let config = test_config(database_url);
+    let pointer_cache = PointerCache::new(
+        Duration::from_millis(config.pointer_cache_ttl_ms),
+        config.pointer_cache_max_entries,
+    );
+    AppState {
+        db: Db::single(db_pool),
+        signing_keys: Arc::new(SigningKeyring::new(Ed25519Keypair::generate())),
+        org_signing_keys: OrgSigningKeys::from_config(&config).unwrap().map(Arc::new),
+        config,
+        clock: Arc::new(SystemClock),
+        resolve_flights: Arc::new(SingleFlight::new(Duration::from_millis(50))),
+        pointer_cache: Arc::new(pointer_cache),
+        receipt_verifier: Arc::new(ReceiptVerifier::new()),
+        db_circuit: Arc::new(CircuitBreaker::new(3, Duration::from_secs(5))),
+        last_known: Arc::new(LastKnownStatuses::new(chrono::Duration::seconds(60))),
+        receipt_metrics: Arc::new(ReceiptPhaseMetrics::new(false, Duration::from_millis(50))),
+        api_keys: Arc::new(KnownApiKeys::new(chrono::Duration::seconds(60))),
+        webhook_wakeup: Arc::new(tokio::sync::Notify::new()),
+        rate_limiter: Arc::new(RateLimiter::new(0, 0)),
+        compression_metrics: Arc::new(CompressionMetrics::new()),
+        payload_cipher: None,
+    }
+}
+
+/// Authenticated as the state's default org, as its handlers would be
+pub fn test_auth(state: &AppState) -> AuthContext {
+    AuthContext {
+        org_id: state.config.default_org_id,
+        key_id: Uuid::nil(),
+    }
+}
+
+/// Unique subject id so tests sharing a database don't see each other's rows
+pub fn unique_subject(prefix: &str) -> String {
+    format!("{}_{}", prefix, Uuid::new_v4().simple())
+}
+
+/// Create an active pointer through the regular create handler
+pub async fn seed_pointer(state: &AppState, subject_id: &str) -> Uuid {
+    let req = handlers::CreatePointerRequest {
+        subject_id: subject_id.to_string(),
+        content_hash: "ab".repeat(64),
+        encrypted_payload: None,
+        payload_encoding: None,
+        retention_class: None,
+        expires_at: None,
+        external_ref: None,
+        requires_dual_approval: false,
+    };
+
+    let (_, Json(created)) = handlers::create_pointer(
+        State(state.clone()),
+        test_auth(state),
+        RequestContext::default(),
+        IdempotencyKey::default(),
+        Json(req),
+    )
+    .await
+    .unwrap_or_else(|_| panic!("Failed to seed pointer for {}", subject_id));
+
+    created.pointer_id
+}
+
+/// Background work that came due while simulated time advanced
+#[derive(Debug, Default)]
+pub struct DueJobs {
+    pub restrictions_lifted: usize,
+    pub heartbeats: Vec<OrgHeartbeat>,
+}
+
+/// Controllable time for time-driven tests. Handlers read it through the
+/// wrapped state's clock, and the background jobs that would have run in the
+/// meantime are fired at their scheduled ticks, scoped to tracked pointers
+/// and orgs. Starts at least a day ahead of the wall clock so real-time
+/// sweeps from tests sharing the database never find its rows due.
+pub struct SimulatedTime {
+    pub state: AppState,
+    clock: Arc<ManualClock>,
+    last_run: DateTime<Utc>,
+    pointer_ids: Vec<Uuid>,
+    org_ids: Vec<Uuid>,
+}
+
+impl SimulatedTime {
+    pub fn new(state: AppState) -> Self {
+        let day = chrono::Duration::days(1);
+        let start = interval_floor(Utc::now(), day) + day * 2;
+        let clock = Arc::new(ManualClock::new(start));
+
+        SimulatedTime {
+            state: AppState {
+                clock: clock.clone(),
+                ..state
+            },
+            clock,
+            last_run: start,
+            pointer_ids: vec![],
+            org_ids: vec![],
+        }
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.last_run
+    }
+
+    /// Create a pointer whose restrictions the simulated sweeper handles
+    pub async fn seed_pointer(&mut self, subject_id: &str) -> Uuid {
+        let pointer_id = seed_pointer(&self.state, subject_id).await;
+        self.pointer_ids.push(pointer_id);
+        pointer_id
+    }
+
+    /// Emit heartbeats for this org as simulated time passes
+    pub fn track_org(&mut self, org_id: Uuid) {
+        self.org_ids.push(org_id);
+    }
+
+    /// Move time forward, running whatever comes due on the way
+    pub async fn advance(&mut self, by: chrono::Duration) -> DueJobs {
+        self.run_due_jobs(self.last_run + by).await
+    }
+
+    /// Move time forward with the service down: nothing runs, and the ticks
+    /// missed meanwhile are dropped rather than caught up
+    pub fn outage(&mut self, by: chrono::Duration) {
+        self.last_run += by;
+        self.clock.set(self.last_run);
+    }
+
+    /// Run every job tick due in (now, until], then leave the clock at until
+    pub async fn run_due_jobs(&mut self, until: DateTime<Utc>) -> DueJobs {
+        assert!(until >= self.last_run, "simulated time only moves forward");
+        let config = &self.state.config;
+        let pool = &self.state.db.writer;
+        let keypair = &self.state.keypair();
+        let mut due = DueJobs::default();
+
+        // Sweeps are idempotent, so the last one due stands in for the rest
+        let sweep_every = seconds(config.restriction_sweep_interval_seconds);
+        let last_sweep = interval_floor(until, sweep_every);
+        if last_sweep > self.last_run && !self.pointer_ids.is_empty() {
+            due.restrictions_lifted =
+                sweep_expired_restrictions_of(pool, keypair, last_sweep, Some(&self.pointer_ids))
+                    .await
+                    .expect("simulated restriction sweep failed");
+        }
+
+        // Heartbeats depend on which check tick saw each interval end, so
+        // the emitter runs at the first tick after every boundary
+        let check_every = seconds(config.heartbeat_check_interval_seconds);
+        let orgs = queries::get_heartbeat_orgs(pool).await.unwrap();
+        for (org_id, interval_seconds) in orgs {
+            if !self.org_ids.contains(&org_id) {
+                continue;
+            }
+            let interval = chrono::Duration::seconds(interval_seconds.into());
+            let mut boundary = interval_floor(self.last_run, interval) + interval;
+
+            while boundary <= until {
+                let tick = ceil_to(boundary, check_every);
+                if tick > until {
+                    break;
+                }
+                let emitted = emit_org_heartbeats(pool, keypair, org_id, tick)
+                    .await
+                    .expect("simulated heartbeat emission failed");
+                due.heartbeats.extend(emitted);
+                boundary += interval;
+            }
+        }
+
+        self.last_run = until;
+        self.clock.set(until);
+        due
+    }
+}
+
+fn seconds(interval_seconds: u64) -> chrono::Duration {
+    chrono::Duration::seconds(interval_seconds.max(1) as i64)
+}
+
+/// First tick of an epoch-aligned schedule at or after `at`
+fn ceil_to(at: DateTime<Utc>, every: chrono::Duration) -> DateTime<Utc> {
+    let floor = interval_floor(at, every);
+    if floor == at {
+        at
+    } else {
+        floor + every
+    }
+}
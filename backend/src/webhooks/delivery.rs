@@ -0,0 +1,409 @@
+// Webhook delivery
+// Events are queued in webhook_deliveries inside the transaction that
+// produced them, so a rolled-back veto never notifies anyone. A background
+// dispatcher sends them, retrying with exponential backoff and logging
+// every attempt; no request ever waits on a receiver.
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use sqlx::{PgExecutor, PgPool};
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::signing::{sign_webhook_body, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER};
+use crate::{
+    clock::Clock,
+    config::Config,
+    crypto::SignedReceipt,
+    db::{models::DueWebhookDelivery, queries},
+};
+
+/// Deliveries sent per dispatch round
+const DISPATCH_BATCH: i64 = 50;
+
+/// Longest wait between retries
+const MAX_RETRY_DELAY_SECONDS: i64 = 3600;
+
+#[derive(Debug, Clone, Copy)]
+pub struct WebhookSettings {
+    pub max_attempts: i32,
+    pub retry_base: Duration,
+    pub timeout: std::time::Duration,
+}
+
+impl WebhookSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_attempts: config.webhook_max_attempts.max(1),
+            retry_base: Duration::seconds(config.webhook_retry_base_seconds as i64),
+            timeout: std::time::Duration::from_millis(config.webhook_timeout_ms),
+        }
+    }
+
+    /// Wait before the next attempt after `attempts` failed ones
+    pub fn retry_delay(&self, attempts: i32) -> Duration {
+        let factor = 1i64 << (attempts - 1).clamp(0, 20);
+        (self.retry_base * factor as i32).min(Duration::seconds(MAX_RETRY_DELAY_SECONDS))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchRun {
+    pub delivered: usize,
+    pub retrying: usize,
+    pub failed: usize,
+}
+
+/// Queue `event_type` for the org's subscribed webhooks inside the caller's
+/// transaction. The body carries the event's receipt with its hash and
+/// Ed25519 signature, so receivers can check it against the JWKS as well.
+pub async fn enqueue_webhook_event<'e>(
+    executor: impl PgExecutor<'e>,
+    org_id: Uuid,
+    pointer_id: Uuid,
+    event_type: &str,
+    receipt: &SignedReceipt,
+    now: DateTime<Utc>,
+) -> Result<u64> {
+    let payload = json!({
+        "event": event_type,
+        "org_id": org_id,
+        "pointer_id": pointer_id,
+        "occurred_at": now,
+        "receipt": receipt.receipt_json,
+        "receipt_hash": receipt.receipt_hash,
+        "signature": data_encoding::BASE64.encode(&receipt.signature),
+        "signature_algorithm": receipt.signature_algorithm,
+        "key_id": receipt.key_id,
+    });
+    queries::enqueue_webhook_deliveries(
+        executor,
+        org_id,
+        Some(pointer_id),
+        event_type,
+        &payload,
+        now,
+    )
+    .await
+}
+
+pub fn webhook_client(settings: &WebhookSettings) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(settings.timeout)
+        .build()
+        .context("Failed to build webhook HTTP client")
+}
+
+/// Send every delivery due at `now` once, recording each attempt
+pub async fn dispatch_due_webhooks(
+    pool: &PgPool,
+    client: &reqwest::Client,
+    settings: &WebhookSettings,
+    now: DateTime<Utc>,
+) -> Result<DispatchRun> {
+    // Hold claimed deliveries for longer than a send can take
+    let lease_until =
+        now + Duration::from_std(settings.timeout).unwrap_or_else(|_| Duration::seconds(30)) * 2;
+    let due = queries::claim_due_webhook_deliveries(pool, now, lease_until, DISPATCH_BATCH).await?;
+
+    let mut run = DispatchRun::default();
+    for delivery in &due {
+        let started = std::time::Instant::now();
+        let outcome = send(client, delivery).await;
+        let duration_ms = started.elapsed().as_millis() as i64;
+
+        let (status_code, error) = match &outcome {
+            Ok(status) if status.is_success() => (Some(status.as_u16() as i32), None),
+            Ok(status) => (
+                Some(status.as_u16() as i32),
+                Some(format!("receiver answered {}", status)),
+            ),
+            Err(e) => (None, Some(format!("{:#}", e))),
+        };
+        queries::record_webhook_attempt(
+            pool,
+            delivery.delivery_id,
+            now,
+            status_code,
+            error.as_deref(),
+            duration_ms,
+        )
+        .await?;
+
+        let attempts = delivery.attempts + 1;
+        let (status, next_attempt_at) = match &error {
+            None => ("delivered", now),
+            Some(_) if attempts >= settings.max_attempts => ("failed", now),
+            Some(_) => ("pending", now + settings.retry_delay(attempts)),
+        };
+        queries::settle_webhook_delivery(
+            pool,
+            delivery.delivery_id,
+            status,
+            error.as_deref(),
+            next_attempt_at,
+            now,
+        )
+        .await?;
+
+        match status {
+            "delivered" => run.delivered += 1,
+            "failed" => {
+                warn!(
+                    "Webhook {} delivery {} to {} failed after {} attempts: {}",
+                    delivery.webhook_id,
+                    delivery.delivery_id,
+                    delivery.url,
+                    attempts,
+                    error.as_deref().unwrap_or_default()
+                );
+                run.failed += 1;
+            }
+            _ => run.retrying += 1,
+        }
+    }
+
+    Ok(run)
+}
+
+async fn send(
+    client: &reqwest::Client,
+    delivery: &DueWebhookDelivery,
+) -> Result<reqwest::StatusCode> {
+    let body = serde_json::to_vec(&delivery.payload)?;
+    let response = client
+        .post(&delivery.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, sign_webhook_body(&delivery.secret, &body))
+        .header(EVENT_HEADER, &delivery.event_type)
+        .header(DELIVERY_HEADER, delivery.delivery_id.to_string())
+        .body(body)
+        .send()
+        .await
+        .context("request failed")?;
+
+    Ok(response.status())
+}
+
+/// Background loop: dispatch on every poll interval, and as soon as a
+/// handler signals that it queued something
+pub async fn run_webhook_dispatcher(
+    pool: PgPool,
+    config: Config,
+    clock: Arc<dyn Clock>,
+    wakeup: Arc<Notify>,
+) {
+    let settings = WebhookSettings::from_config(&config);
+    let client = match webhook_client(&settings) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Webhook dispatcher not started: {:#}", e);
+            return;
+        }
+    };
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.webhook_poll_interval_seconds.max(1),
+    ));
+
+    info!(
+        "Webhook dispatcher running every {}s (up to {} attempts)",
+        config.webhook_poll_interval_seconds, settings.max_attempts
+    );
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = wakeup.notified() => {}
+        }
+
+        match dispatch_due_webhooks(&pool, &client, &settings, clock.now()).await {
+            Ok(run) if run == DispatchRun::default() => {}
+            Ok(run) => info!(
+                "Webhooks: {} delivered, {} retrying, {} failed",
+                run.delivered, run.retrying, run.failed
+            ),
+            Err(e) => error!("Webhook dispatch failed: {:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            context::RequestContext,
+            handlers::{
+                create_webhook, get_webhook_deliveries, orphan_pointer, CreateWebhookRequest,
+                OrphanPointerRequest, WebhookDeliveriesQuery,
+            },
+        },
+        test_support::{seed_pointer, test_auth, test_config, test_state, unique_subject},
+        webhooks::verify_webhook_signature,
+    };
+    use axum::{
+        body::Bytes,
+        extract::{Path, Query, State},
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Json, Router,
+    };
+    use std::sync::Mutex;
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    /// Receiver that answers 500 to the first request and 200 after
+    async fn receive(
+        State(received): State<Received>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        let mut received = received.lock().unwrap();
+        received.push((headers, body));
+        if received.len() == 1 {
+            StatusCode::INTERNAL_SERVER_ERROR
+        } else {
+            StatusCode::OK
+        }
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_cap() {
+        let settings = WebhookSettings::from_config(&test_config("postgres://unused"));
+        assert_eq!(settings.retry_delay(1), Duration::seconds(10));
+        assert_eq!(settings.retry_delay(2), Duration::seconds(20));
+        assert_eq!(settings.retry_delay(4), Duration::seconds(80));
+        assert_eq!(
+            settings.retry_delay(30),
+            Duration::seconds(MAX_RETRY_DELAY_SECONDS)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_orphan_is_delivered_signed_and_retried_after_failure() {
+        let Some(mut state) = test_state().await else {
+            return;
+        };
+        // An org of its own, so no other test's vetoes reach this webhook
+        state.config.default_org_id = sqlx::query_scalar(
+            "INSERT INTO organizations (name) VALUES ('Webhook Org') RETURNING org_id",
+        )
+        .fetch_one(&state.db.writer)
+        .await
+        .unwrap();
+
+        let received: Received = Arc::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let receiver = Router::new()
+            .route("/hook", post(receive))
+            .with_state(received.clone());
+        tokio::spawn(async move { axum::serve(listener, receiver).await });
+
+        let (status, Json(webhook)) = create_webhook(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(CreateWebhookRequest {
+                url,
+                events: None,
+                enabled: None,
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("webhook create failed"));
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(webhook.webhook.events, vec!["pointer_orphaned"]);
+
+        let pointer_id = seed_pointer(&state, &unique_subject("webhook")).await;
+        let Json(orphaned) = orphan_pointer(
+            State(state.clone()),
+            test_auth(&state),
+            RequestContext::default(),
+            Json(OrphanPointerRequest {
+                pointer_id,
+                reason: None,
+                effective_at: None,
+            }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("orphan failed"));
+
+        // The response came back before anything was sent
+        assert!(received.lock().unwrap().is_empty());
+
+        let settings = WebhookSettings::from_config(&state.config);
+        let client = webhook_client(&settings).unwrap();
+        let now = Utc::now();
+
+        // First attempt fails and is put back for a retry
+        dispatch_due_webhooks(&state.db.writer, &client, &settings, now)
+            .await
+            .unwrap();
+        assert_eq!(received.lock().unwrap().len(), 1);
+
+        // Not due again until the backoff has passed
+        dispatch_due_webhooks(&state.db.writer, &client, &settings, now)
+            .await
+            .unwrap();
+        assert_eq!(received.lock().unwrap().len(), 1);
+
+        dispatch_due_webhooks(
+            &state.db.writer,
+            &client,
+            &settings,
+            now + settings.retry_delay(1),
+        )
+        .await
+        .unwrap();
+
+        let received = received.lock().unwrap().clone();
+        assert_eq!(received.len(), 2);
+        for (headers, body) in &received {
+            let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+            assert!(verify_webhook_signature(&webhook.secret, body, signature));
+            assert!(!verify_webhook_signature("whsec_wrong", body, signature));
+            assert_eq!(headers[EVENT_HEADER], "pointer_orphaned");
+        }
+        assert_eq!(received[0].1, received[1].1);
+        assert_eq!(
+            received[0].0[DELIVERY_HEADER],
+            received[1].0[DELIVERY_HEADER]
+        );
+
+        let payload: serde_json::Value = serde_json::from_slice(&received[1].1).unwrap();
+        assert_eq!(payload["event"], "pointer_orphaned");
+        assert_eq!(payload["pointer_id"], json!(pointer_id));
+        assert_eq!(
+            payload["receipt_hash"],
+            json!(orphaned.receipt.receipt_hash)
+        );
+        assert_eq!(payload["receipt"]["operation"], "orphan");
+
+        // Both attempts are in the delivery log
+        let Json(log) = get_webhook_deliveries(
+            State(state.clone()),
+            test_auth(&state),
+            Path(webhook.webhook.webhook_id),
+            Query(WebhookDeliveriesQuery { limit: None }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("delivery log failed"));
+        assert_eq!(log.deliveries.len(), 1);
+        let delivery = &log.deliveries[0];
+        assert_eq!(delivery.status, "delivered");
+        assert_eq!(delivery.attempts, 2);
+        assert!(delivery.delivered_at.is_some());
+        assert_eq!(
+            delivery
+                .attempt_log
+                .iter()
+                .map(|a| a.status_code)
+                .collect::<Vec<_>>(),
+            vec![Some(500), Some(200)]
+        );
+        assert!(delivery.attempt_log[0].error.is_some());
+    }
+}
@@ -0,0 +1,14 @@
+// Webhook notification module
+pub mod delivery;
+pub mod signing;
+
+pub use delivery::*;
+pub use signing::*;
+
+/// Events a webhook can subscribe to
+pub const WEBHOOK_EVENTS: &[&str] = &[
+    "pointer_orphaned",
+    "orphan_scheduled",
+    "orphan_cancelled",
+    "pointer_reinstated",
+];
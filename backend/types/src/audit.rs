@@ -0,0 +1,92 @@
+// A subject's audit trail
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct AuditTrailQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// `next_cursor` from the previous page; omit for the first
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub event_type: Option<String>,
+    /// Events at or after this time
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Events before this time
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+    /// Download every matching event as csv or jsonl instead of a page
+    #[serde(default)]
+    pub format: Option<AuditExportFormat>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    Csv,
+    Jsonl,
+}
+
+impl AuditExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            AuditExportFormat::Csv => "text/csv; charset=utf-8",
+            AuditExportFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            AuditExportFormat::Csv => "csv",
+            AuditExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetAuditTrailResponse {
+    pub subject_id: String,
+    pub total_pointers: usize,
+    pub active_pointers: usize,
+    pub orphaned_pointers: usize,
+    pub restrictions: Vec<RestrictionSummary>,
+    pub audit_events: Vec<AuditEventSummary>,
+    /// Set when older audit events follow this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RestrictionSummary {
+    pub restriction_id: Uuid,
+    pub pointer_id: Uuid,
+    pub reason: String,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub restricted_at: String,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub expires_at: String,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub lifted_at: Option<String>,
+    pub lift_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AuditEventSummary {
+    pub event_type: String,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub timestamp: String,
+    pub pointer_id: Option<Uuid>,
+    pub event_data: serde_json::Value,
+    /// X-Actor-Id of the request, or the API key it authenticated with
+    pub actor_id: Option<String>,
+    pub ip_address: Option<String>,
+}
@@ -0,0 +1,76 @@
+// The body of every error response
+use serde::{Deserialize, Deserializer, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Why a single member blocked an orphan group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GroupMemberFailure {
+    pub pointer_id: Uuid,
+    pub reason: String,
+}
+
+/// Body of every error response: a message and a stable `code`, plus the
+/// fields particular to some codes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorBody {
+    pub error: String,
+    /// Machine-readable, e.g. `pointer_orphaned`, `not_found`,
+    /// `validation_failed`
+    #[cfg_attr(feature = "openapi", schema(example = "pointer_orphaned"))]
+    pub code: String,
+    /// Quoted in support tickets to find the request's log lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// `processing_restricted`: when the restriction lapses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub restricted_until: Option<String>,
+    /// `rate_limited`: as sent in Retry-After
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_seconds: Option<u64>,
+    /// `conflict`: which conflict, e.g. `already_orphaned`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict_type: Option<String>,
+    /// `conflict` on an external_ref, and `legal_hold`: the pointer concerned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pointer_id: Option<Uuid>,
+    /// `legal_hold`: the hold's reason, null when none was given
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub legal_hold_reason: Option<Option<String>>,
+    /// `legal_hold`: when the hold was placed
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub legal_hold_set_at: Option<Option<String>>,
+    /// `validation_failed`: every field that broke a rule
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<FieldError>>,
+    /// `orphan_group_rejected`: the members that blocked the group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failures: Option<Vec<GroupMemberFailure>>,
+}
+
+/// A field that was sent, even as null; absent fields stay None by `default`
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,37 @@
+// Published receipt verification keys
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// JSON Web Key for an Ed25519 public key (RFC 8037)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    /// Public key bytes, base64url without padding
+    pub x: String,
+    pub kid: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+}
+
+/// A JWKS document, plus the key in the encodings receipts are checked with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PublicKeysResponse {
+    pub keys: Vec<Jwk>,
+    pub key_id: String,
+    pub algorithm: String,
+    #[cfg_attr(feature = "openapi", schema(format = Byte))]
+    pub public_key_base64: String,
+    pub public_key_hex: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct PublicKeysQuery {
+    /// List the keys of this org's receipts
+    pub org_id: Option<Uuid>,
+}
@@ -0,0 +1,28 @@
+// Wire types of the Veto Frontier API
+// The server serializes these and the client deserializes the same
+// definitions, so the two cannot disagree on a field name or shape. Only the
+// endpoints the client covers live here; the rest stay with their handlers.
+// With the `openapi` feature they also describe themselves to utoipa.
+pub mod audit;
+pub mod errors;
+pub mod keys;
+pub mod pointers;
+pub mod receipts;
+
+pub use audit::{
+    AuditEventSummary, AuditExportFormat, AuditTrailQuery, GetAuditTrailResponse,
+    RestrictionSummary,
+};
+pub use errors::{ErrorBody, FieldError, GroupMemberFailure};
+pub use keys::{Jwk, PublicKeysQuery, PublicKeysResponse};
+pub use pointers::{
+    CreatePointerRequest, CreatePointerResponse, OrphanPointerRequest, OrphanPointerResponse,
+    PayloadEncoding, ReceiptInfo, ResolveMode, ResolvePointerResponse, ResolveQuery,
+};
+pub use receipts::{
+    GetReceiptsResponse, ReceiptEntry, ReceiptFailure, ReceiptSummary, ReceiptTombstone,
+    ReceiptsQuery,
+};
+
+/// The only algorithm receipts are signed with
+pub const SIGNATURE_ALGORITHM: &str = "ED25519";
@@ -0,0 +1,158 @@
+// Creating, resolving and orphaning pointers
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Encoding declared by the client via `payload_encoding`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadEncoding {
+    Base64,
+    Base64url,
+    Hex,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreatePointerRequest {
+    pub subject_id: String,
+    pub content_hash: String,
+    #[serde(default)]
+    pub encrypted_payload: Option<String>, // Base64 encoded unless payload_encoding says otherwise
+    #[serde(default)]
+    pub payload_encoding: Option<PayloadEncoding>,
+    /// Data category from RETENTION_CLASSES; caps how long the data is kept
+    #[serde(default)]
+    pub retention_class: Option<String>,
+    /// Requested expiry, no later than the class maximum
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// The integrator's own id for the record, unique within the org and
+    /// resolvable through /api/pointer/by-ref
+    #[serde(default)]
+    pub external_ref: Option<String>,
+    /// Orphaning then takes a request and a second actor's approval, see
+    /// /api/pointer/orphan/request
+    #[serde(default)]
+    pub requires_dual_approval: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreatePointerResponse {
+    pub pointer_id: Uuid,
+    pub data_id: Uuid,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_ref: Option<String>,
+    /// Hard deadline after which the retention sweeper orphans the pointer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub retain_until: Option<String>,
+    #[serde(default)]
+    pub requires_dual_approval: bool,
+    pub receipt: ReceiptInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReceiptInfo {
+    pub receipt_hash: String,
+    #[cfg_attr(feature = "openapi", schema(format = Byte))]
+    pub signature: String, // Base64 encoded
+    pub signature_algorithm: String,
+    /// Id of the key that signed it, as listed by /api/keys/public
+    pub key_id: String,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ResolvePointerResponse {
+    pub pointer_id: Uuid,
+    pub data_id: Uuid,
+    pub subject_id: String,
+    pub content_hash: String,
+    pub status: String,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub created_at: String,
+    /// False on a readonly resolve, and on a degraded one until recovery
+    pub receipt_generated: bool,
+    /// Absent on a degraded resolve; its receipt is written after recovery
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<ReceiptInfo>,
+    /// Answered from last known status while the database was unreachable
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub last_known_at: Option<String>,
+    /// Set only while an orphaned pointer is inside its grace window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub hard_denial_at: Option<String>,
+    /// When a scheduled orphan takes effect; resolution works until then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub orphan_effective_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_seconds: Option<i64>,
+    /// Base64 of the stored payload, only with `include_payload=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(format = Byte))]
+    pub encrypted_payload: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct ResolveQuery {
+    #[serde(default)]
+    pub include_payload: bool,
+    #[serde(default)]
+    pub mode: ResolveMode,
+}
+
+/// Whether a resolve is evidenced in the pointer's receipt chain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ResolveMode {
+    /// Locks the pointer and chains a resolve (or grace denial) receipt
+    #[default]
+    Attested,
+    /// Enforces status without writing anything: no lock, no chain head
+    /// lookup, no receipt
+    Readonly,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrphanPointerRequest {
+    pub pointer_id: Uuid,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Take effect at this time instead of now; the pointer stays resolvable
+    /// until then. A time not in the future orphans immediately.
+    #[serde(default)]
+    pub effective_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrphanPointerResponse {
+    pub pointer_id: Uuid,
+    pub status: String,
+    /// When the pointer was orphaned, or will be if the orphan is scheduled
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub orphaned_at: String,
+    /// True when the pointer was already orphaned and this is the original receipt
+    pub already_orphaned: bool,
+    /// Set when the orphan was scheduled rather than taking effect now
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub effective_at: Option<String>,
+    pub receipt: ReceiptInfo,
+}
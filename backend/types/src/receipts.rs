@@ -0,0 +1,80 @@
+// Reading a pointer's receipt chain
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct ReceiptsQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// `next_cursor` from the previous page; omit for the first
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub operation: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetReceiptsResponse {
+    pub pointer_id: Uuid,
+    pub receipts: Vec<ReceiptEntry>,
+    /// Strict reads only: true when every receipt verified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrity_verified: Option<bool>,
+    /// Set when more receipts follow this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(untagged)]
+pub enum ReceiptEntry {
+    Receipt(ReceiptSummary),
+    Tombstone(ReceiptTombstone),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReceiptSummary {
+    pub operation: String,
+    pub receipt_hash: String,
+    #[cfg_attr(feature = "openapi", schema(format = Byte))]
+    pub signature: String,
+    /// Key that signed it; null on receipts from before key ids were recorded
+    pub key_id: Option<String>,
+    pub prev_hash: Option<String>,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime))]
+    pub timestamp: String,
+    /// Full metadata of a receipt that signed only its overflow hash, when
+    /// the overflow record checks out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overflow_metadata: Option<serde_json::Value>,
+}
+
+/// Stands in for a receipt that failed verification under strict reads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReceiptTombstone {
+    pub receipt_id: Uuid,
+    pub failure: ReceiptFailure,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiptFailure {
+    /// Stored receipt_json no longer hashes to receipt_hash
+    HashMismatch,
+    /// Signature is not a valid ED25519 signature of receipt_hash
+    InvalidSignature,
+    /// signature_algorithm is not one this service can verify
+    UnsupportedAlgorithm,
+    /// Signed by a key_id this service has no public key for
+    UnknownKey,
+    /// Overflowed metadata has no record, or the record no longer matches
+    /// the hash and size the receipt signed
+    OverflowMismatch,
+}
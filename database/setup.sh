@@ -59,13 +59,10 @@ EOSQL
 
 echo "✓ Database and user created"
 
-# Run schema initialization
+# The schema itself is created by the backend's migrations
+# (backend/migrations) the first time it starts
 echo ""
-echo "Initializing database schema..."
-psql -h "$DB_HOST" -p "$DB_PORT" -U "$DB_USER" -d "$DB_NAME" < schema.sql
-
-echo ""
-echo "✓ Schema initialized successfully"
+echo "Schema will be created by the backend on first start (RUN_MIGRATIONS=true)"
 
 # Display connection info
 echo ""